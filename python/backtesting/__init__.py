@@ -1,4 +1,5 @@
 # Backtesting package
 from .backtest_engine import BacktestEngine, BacktestConfig, Trade
+from .experiments import ExperimentTracker, snapshot_dataset
 
-__all__ = ['BacktestEngine', 'BacktestConfig', 'Trade']
+__all__ = ['BacktestEngine', 'BacktestConfig', 'Trade', 'ExperimentTracker', 'snapshot_dataset']
@@ -10,11 +10,14 @@ Implements walk-forward optimization with realistic assumptions:
 
 import pandas as pd
 import numpy as np
-from typing import Dict, List, Tuple, Optional
+from typing import Dict, List, Tuple, Optional, TYPE_CHECKING
 from dataclasses import dataclass
-from datetime import datetime, timedelta
+from datetime import datetime, timedelta, timezone
 import json
 
+if TYPE_CHECKING:
+    from .experiments import ExperimentTracker
+
 
 @dataclass
 class BacktestConfig:
@@ -62,7 +65,9 @@ class BacktestEngine:
         historical_data: pd.DataFrame,
         strategy_signals: pd.DataFrame,
         start_date: str = None,
-        end_date: str = None
+        end_date: str = None,
+        tracker: Optional['ExperimentTracker'] = None,
+        experiment_name: str = 'backtest',
     ) -> Dict:
         """
         Run backtest on historical data
@@ -75,6 +80,8 @@ class BacktestEngine:
                  entry_price, fair_value, strategy]
             start_date: Start date for backtest
             end_date: End date for backtest
+            tracker: Records the run in the experiments table when given
+            experiment_name: Name the run is recorded under
             
         Returns:
             Backtest results dictionary
@@ -96,6 +103,8 @@ class BacktestEngine:
                 strategy_signals['timestamp'] <= end_date
             ]
         
+        started_at = datetime.now(timezone.utc)
+
         # Reset state
         self.trades = []
         self.portfolio_value_history = []
@@ -159,12 +168,17 @@ class BacktestEngine:
         # Calculate performance metrics
         metrics = self._calculate_metrics()
         
-        return {
+        results = {
             'config': self.config.__dict__,
             'metrics': metrics,
             'trades': [t.__dict__ for t in self.trades],
             'portfolio_history': self.portfolio_value_history,
         }
+        if tracker is not None:
+            results['experiment_id'] = tracker.record(
+                experiment_name, results, historical_data, strategy_signals, started_at
+            )
+        return results
     
     def _can_enter_trade(self, signal: pd.Series) -> bool:
         """Check if we can enter a new trade"""
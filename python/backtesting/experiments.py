@@ -0,0 +1,146 @@
+"""
+Experiment tracking for backtests
+
+Each tracked run records what it needs to be reproduced and compared:
+- the backtest config
+- the code version (git commit, suffixed -dirty with uncommitted changes)
+- a hash of the exact price history and signals it ran over
+- its metrics
+"""
+
+import hashlib
+import json
+import os
+import subprocess
+import uuid
+from dataclasses import dataclass
+from datetime import datetime, timezone
+from typing import Dict, List, Optional
+
+import pandas as pd
+from sqlalchemy import create_engine, text
+
+
+@dataclass
+class DatasetSnapshot:
+    """Fingerprint of the data a backtest ran over"""
+    hash: str
+    rows: int
+    start: Optional[datetime]
+    end: Optional[datetime]
+
+
+def snapshot_dataset(historical_data: pd.DataFrame, strategy_signals: pd.DataFrame) -> DatasetSnapshot:
+    """
+    Hash price history and signals independently of row and column order
+
+    Both frames are sorted by timestamp and market, with columns in name
+    order, before hashing, so the same data always hashes the same.
+    """
+    digest = hashlib.sha256()
+    for frame in (historical_data, strategy_signals):
+        canonical = frame[sorted(frame.columns)].sort_values(
+            ['timestamp', 'market_id'], kind='mergesort'
+        )
+        digest.update(canonical.to_csv(index=False).encode())
+
+    timestamps = pd.to_datetime(historical_data['timestamp']) if len(historical_data) else None
+    return DatasetSnapshot(
+        hash=digest.hexdigest(),
+        rows=len(historical_data) + len(strategy_signals),
+        start=timestamps.min().to_pydatetime() if timestamps is not None else None,
+        end=timestamps.max().to_pydatetime() if timestamps is not None else None,
+    )
+
+
+def code_version() -> str:
+    """Current git commit, or GIT_COMMIT when run outside a checkout"""
+    try:
+        commit = subprocess.run(
+            ['git', 'rev-parse', 'HEAD'], capture_output=True, text=True, check=True
+        ).stdout.strip()
+        dirty = subprocess.run(
+            ['git', 'status', '--porcelain', '--untracked-files=no'],
+            capture_output=True, text=True, check=True
+        ).stdout.strip()
+        return f"{commit}-dirty" if dirty else commit
+    except (OSError, subprocess.CalledProcessError):
+        return os.environ.get('GIT_COMMIT', 'unknown')
+
+
+class ExperimentTracker:
+    """
+    Records backtest runs in the `experiments` table
+
+    Works against the bot's Postgres or SQLite database (`database.url`).
+    """
+
+    def __init__(self, database_url: str):
+        self.engine = create_engine(database_url)
+
+    def record(
+        self,
+        name: str,
+        results: Dict,
+        historical_data: pd.DataFrame,
+        strategy_signals: pd.DataFrame,
+        started_at: datetime,
+    ) -> str:
+        """Store a finished run's config and metrics; returns its experiment id"""
+        snapshot = snapshot_dataset(historical_data, strategy_signals)
+        experiment_id = str(uuid.uuid4())
+        with self.engine.begin() as conn:
+            conn.execute(
+                text("""
+                    INSERT INTO experiments (
+                        experiment_id, name, config, code_version, dataset_hash,
+                        dataset_rows, data_start, data_end, metrics, started_at, finished_at
+                    ) VALUES (
+                        :experiment_id, :name, :config, :code_version, :dataset_hash,
+                        :dataset_rows, :data_start, :data_end, :metrics, :started_at, :finished_at
+                    )
+                """),
+                {
+                    'experiment_id': experiment_id,
+                    'name': name,
+                    'config': json.dumps(results['config'], sort_keys=True, default=str),
+                    'code_version': code_version(),
+                    'dataset_hash': snapshot.hash,
+                    'dataset_rows': snapshot.rows,
+                    'data_start': snapshot.start,
+                    'data_end': snapshot.end,
+                    'metrics': json.dumps(results['metrics'], sort_keys=True, default=str),
+                    'started_at': started_at,
+                    'finished_at': datetime.now(timezone.utc),
+                },
+            )
+        return experiment_id
+
+    def compare(self, name: Optional[str] = None, dataset_hash: Optional[str] = None) -> pd.DataFrame:
+        """
+        Runs side by side, newest first, one metric per column
+
+        Filter on `dataset_hash` to compare only runs over identical data.
+        """
+        query = """
+            SELECT experiment_id, name, code_version, dataset_hash, config, metrics, finished_at
+            FROM experiments
+            WHERE (:name IS NULL OR name = :name)
+              AND (:dataset_hash IS NULL OR dataset_hash = :dataset_hash)
+            ORDER BY finished_at DESC
+        """
+        with self.engine.connect() as conn:
+            rows = conn.execute(text(query), {'name': name, 'dataset_hash': dataset_hash}).mappings().all()
+
+        records: List[Dict] = []
+        for row in rows:
+            metrics = row['metrics'] if isinstance(row['metrics'], dict) else json.loads(row['metrics'])
+            records.append({
+                'experiment_id': row['experiment_id'],
+                'name': row['name'],
+                'code_version': row['code_version'],
+                'dataset_hash': row['dataset_hash'],
+                'finished_at': row['finished_at'],
+                **metrics,
+            })
+        return pd.DataFrame(records)
@@ -1 +1,4 @@
 # ML Pipeline package
+from .model_server import MarketFeatures, Prediction, create_app
+
+__all__ = ['MarketFeatures', 'Prediction', 'create_app']
@@ -0,0 +1,79 @@
+"""
+Model server for the bot's `remote_model` strategy
+
+Research models serve fair values to the bot over HTTP without being
+ported to Rust. Wrap any function from market features to predictions:
+
+    from ml_pipeline.model_server import MarketFeatures, Prediction, create_app
+
+    def predict(markets: list[MarketFeatures]) -> list[Prediction]:
+        ...
+
+    app = create_app(predict, model_version="xgb-2024-09")
+    # uvicorn my_model:app --port 8500
+
+The bot posts batches to `POST /predict` and trades predictions whose
+edge at the ask clears `strategies.remote_model.min_edge_pct`. Markets the
+model has no view on are simply left out of the response.
+"""
+
+from datetime import datetime
+from typing import Callable, List
+
+from fastapi import FastAPI
+from pydantic import BaseModel, Field
+
+
+class MarketFeatures(BaseModel):
+    """One market as sent by the bot; prices are share prices in [0, 1]"""
+    market_id: str
+    sport: str
+    event_name: str
+    market_type: str
+    event_time: datetime
+    liquidity: float
+    yes_bid: float
+    yes_ask: float
+    no_bid: float
+    no_ask: float
+
+
+class PredictRequest(BaseModel):
+    markets: List[MarketFeatures]
+
+
+class Prediction(BaseModel):
+    market_id: str
+    yes_probability: float = Field(ge=0.0, le=1.0)
+    confidence: float = Field(ge=0.0, le=1.0)
+
+
+class PredictResponse(BaseModel):
+    model_version: str
+    predictions: List[Prediction]
+
+
+def create_app(predict: Callable[[List[MarketFeatures]], List[Prediction]], model_version: str) -> FastAPI:
+    """FastAPI app serving `predict` under the bot's schema"""
+    app = FastAPI(title="Fair value model server")
+
+    @app.post("/predict", response_model=PredictResponse)
+    def predict_markets(request: PredictRequest) -> PredictResponse:
+        return PredictResponse(model_version=model_version, predictions=predict(request.markets))
+
+    @app.get("/health")
+    def health() -> dict:
+        return {"status": "ok", "model_version": model_version}
+
+    return app
+
+
+def _mid_price(markets: List[MarketFeatures]) -> List[Prediction]:
+    """Reference model: the YES mid with no confidence, for checking the wiring"""
+    return [
+        Prediction(market_id=m.market_id, yes_probability=(m.yes_bid + m.yes_ask) / 2, confidence=0.0)
+        for m in markets
+    ]
+
+
+app = create_app(_mid_price, model_version="mid-price")
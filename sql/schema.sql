@@ -15,16 +15,28 @@ CREATE TABLE markets (
     sport VARCHAR(50) NOT NULL,         -- NFL, NBA, Premier League, MLB
     event_name TEXT NOT NULL,
     event_time TIMESTAMPTZ NOT NULL,
-    market_type VARCHAR(50) NOT NULL,   -- moneyline, spread, total, prop
+    market_type VARCHAR(50) NOT NULL,   -- moneyline, spread, total, prop, combo
     description TEXT,
     resolution_source VARCHAR(100),
     min_liquidity DECIMAL(20, 2),
     current_liquidity DECIMAL(20, 2),
     yes_price DECIMAL(10, 8),
     no_price DECIMAL(10, 8),
-    status VARCHAR(20) DEFAULT 'active', -- active, closed, resolved
+    yes_bid DECIMAL(10, 8),               -- top of book; NULL when the book is empty
+    yes_ask DECIMAL(10, 8),
+    no_bid DECIMAL(10, 8),
+    no_ask DECIMAL(10, 8),
+    yes_bid_size DECIMAL(20, 2),          -- shares at the top of book; NULL when unknown
+    yes_ask_size DECIMAL(20, 2),
+    no_bid_size DECIMAL(20, 2),
+    no_ask_size DECIMAL(20, 2),
+    status VARCHAR(20) DEFAULT 'active', -- active, suspended, in_play, closed, resolved, disputed
     resolution VARCHAR(10),               -- yes, no, invalid
     resolved_at TIMESTAMPTZ,
+    condition_id VARCHAR(66),             -- CTF condition id
+    question_id VARCHAR(66),              -- UMA question id
+    yes_token_id VARCHAR(80),             -- ERC-1155 position ids (uint256)
+    no_token_id VARCHAR(80),
     created_at TIMESTAMPTZ DEFAULT NOW(),
     updated_at TIMESTAMPTZ DEFAULT NOW()
 );
@@ -37,7 +49,7 @@ CREATE INDEX idx_markets_status ON markets(status);
 CREATE TABLE trades (
     trade_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
     market_id VARCHAR(66) NOT NULL REFERENCES markets(market_id),
-    strategy VARCHAR(50) NOT NULL,      -- clv_arb, poisson_ev, news_scalp, etc.
+    strategy VARCHAR(50) NOT NULL,      -- clv_arb, poisson_ev, news_scalp, manual, etc.
     position VARCHAR(10) NOT NULL,      -- yes, no
     quantity DECIMAL(20, 8) NOT NULL,
     entry_price DECIMAL(10, 8) NOT NULL,
@@ -45,12 +57,17 @@ CREATE TABLE trades (
     entry_time TIMESTAMPTZ DEFAULT NOW(),
     exit_time TIMESTAMPTZ,
     gas_cost DECIMAL(10, 4),
+    fees DECIMAL(20, 4),                -- venue fees paid on entry
     slippage DECIMAL(10, 8),
     pnl DECIMAL(20, 4),
     pnl_percent DECIMAL(10, 4),
+    max_adverse_excursion DECIMAL(20, 4),   -- worst mark-to-market PnL while held, set at close
+    max_favorable_excursion DECIMAL(20, 4), -- best mark-to-market PnL while held, set at close
     status VARCHAR(20) DEFAULT 'open',  -- open, closed, stopped_out
     tx_hash_entry VARCHAR(66),
     tx_hash_exit VARCHAR(66),
+    venue VARCHAR(30) NOT NULL DEFAULT 'polymarket',
+    origin VARCHAR(10) NOT NULL DEFAULT 'bot', -- bot, manual
     notes TEXT
 );
 
@@ -58,6 +75,8 @@ CREATE INDEX idx_trades_market_id ON trades(market_id);
 CREATE INDEX idx_trades_strategy ON trades(strategy);
 CREATE INDEX idx_trades_entry_time ON trades(entry_time);
 CREATE INDEX idx_trades_status ON trades(status);
+CREATE INDEX idx_trades_venue ON trades(venue);
+CREATE INDEX idx_trades_tx_hash_entry ON trades(tx_hash_entry);
 
 -- Convert trades to hypertable for time-series optimization
 SELECT create_hypertable('trades', 'entry_time', if_not_exists => TRUE);
@@ -71,12 +90,18 @@ CREATE TABLE signals (
     confidence DECIMAL(5, 4) NOT NULL,  -- 0.0 to 1.0
     edge_size DECIMAL(10, 6) NOT NULL,  -- probability edge in decimal
     recommended_size DECIMAL(20, 8),
-    current_price DECIMAL(10, 8),
+    current_price DECIMAL(10, 8),       -- executable (ask) price at generation
+    executable_size DECIMAL(20, 2),     -- shares offered at current_price
+    quoted_edge DECIMAL(10, 6),         -- edge against the mid
     fair_value DECIMAL(10, 8),
     executed BOOLEAN DEFAULT FALSE,
     executed_trade_id UUID REFERENCES trades(trade_id),
+    claimed_by TEXT,                    -- engine instance processing the signal
+    claimed_until TIMESTAMPTZ,          -- claim lease; expired claims can be retaken
+    execution_quote DECIMAL(10, 8),     -- executable price when last taken up, traded or not
+    execution_quoted_at TIMESTAMPTZ,
     generated_at TIMESTAMPTZ DEFAULT NOW(),
-    metadata JSONB                      -- strategy-specific data
+    metadata JSONB                      -- {"version": n, "data": strategy-specific data}
 );
 
 CREATE INDEX idx_signals_market_id ON signals(market_id);
@@ -86,11 +111,246 @@ CREATE INDEX idx_signals_executed ON signals(executed);
 
 SELECT create_hypertable('signals', 'generated_at', if_not_exists => TRUE);
 
+-- Failed executions: signals whose submission failed, retried with
+-- exponential backoff until they go through or are given up on
+CREATE TABLE failed_executions (
+    signal_id UUID PRIMARY KEY,
+    market_id VARCHAR(66) NOT NULL,
+    strategy VARCHAR(50) NOT NULL,
+    attempts INTEGER NOT NULL,                  -- failed submissions so far
+    last_error TEXT NOT NULL,
+    status VARCHAR(10) NOT NULL,                -- retrying, failed, recovered
+    next_attempt_at TIMESTAMPTZ,                -- set only while retrying
+    first_failed_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_failed_executions_status ON failed_executions(status);
+CREATE INDEX idx_failed_executions_updated_at ON failed_executions(updated_at);
+
+-- Trade marks: latest mark-to-market of each open trade, refreshed every few seconds
+CREATE TABLE trade_marks (
+    trade_id UUID PRIMARY KEY,
+    mark_price DECIMAL(10, 8) NOT NULL,         -- best bid for the shares held
+    unrealized_pnl DECIMAL(20, 4) NOT NULL,
+    max_adverse_excursion DECIMAL(20, 4) NOT NULL, -- worst unrealized PnL since entry
+    max_favorable_excursion DECIMAL(20, 4) NOT NULL, -- best unrealized PnL since entry
+    marked_at TIMESTAMPTZ NOT NULL
+);
+
+-- Convergence: open CLV trades' divergence from the sharp line over time
+CREATE TABLE trade_convergence (
+    sample_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    trade_id UUID NOT NULL REFERENCES trades(trade_id),
+    market_price DECIMAL(10, 8) NOT NULL,       -- mid of the side held
+    fair_value DECIMAL(10, 8) NOT NULL,         -- sharp bookmaker fair value of the side held
+    divergence DECIMAL(10, 8) NOT NULL,         -- fair_value - market_price
+    sampled_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_trade_convergence_trade ON trade_convergence(trade_id, sampled_at);
+
+-- Trade sizing: the bankroll each trade was sized against, for comparing
+-- stakes with the Kelly-optimal stake once the trade closes
+CREATE TABLE trade_sizing (
+    trade_id UUID PRIMARY KEY REFERENCES trades(trade_id),
+    bankroll DECIMAL(20, 6) NOT NULL,
+    stake DECIMAL(20, 6) NOT NULL,
+    sized_at TIMESTAMPTZ NOT NULL
+);
+
+-- Settlement reviews: trades on disputed resolutions, or whose reported final
+-- price disagrees with the CTF payout vector, held until confirmed by hand
+CREATE TABLE settlement_reviews (
+    trade_id UUID PRIMARY KEY REFERENCES trades(trade_id),
+    market_id VARCHAR(66) NOT NULL REFERENCES markets(market_id),
+    reported_payout DECIMAL(10, 8),             -- per share, from the market's final price
+    onchain_payout DECIMAL(10, 8),              -- per share, from the payout vector
+    reason TEXT NOT NULL,
+    flagged_at TIMESTAMPTZ NOT NULL,
+    resolved_at TIMESTAMPTZ
+);
+
+-- Trade settlements: trades booked at resolution, won or lost
+CREATE TABLE trade_settlements (
+    trade_id UUID PRIMARY KEY REFERENCES trades(trade_id),
+    market_id VARCHAR(66) NOT NULL REFERENCES markets(market_id),
+    outcome VARCHAR(4) NOT NULL,                -- won, lost, void
+    payout DECIMAL(10, 8) NOT NULL,             -- per share
+    pnl DECIMAL(20, 6) NOT NULL,
+    tx_hash VARCHAR(66),                        -- redemption, when redeemed on-chain
+    settled_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_trade_settlements_settled_at ON trade_settlements(settled_at);
+
+-- Closing lines: each market's last prices before its event started
+CREATE TABLE closing_lines (
+    market_id VARCHAR(66) PRIMARY KEY REFERENCES markets(market_id),
+    yes_price DECIMAL(10, 8) NOT NULL,
+    no_price DECIMAL(10, 8) NOT NULL,
+    captured_at TIMESTAMPTZ NOT NULL
+);
+
+-- Price history: YES price over each market's life, from live polls and backfills
+CREATE TABLE market_price_history (
+    market_id VARCHAR(66) NOT NULL REFERENCES markets(market_id),
+    yes_price DECIMAL(10, 8) NOT NULL,
+    observed_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (market_id, observed_at)
+);
+
+-- Shadowed strategies: signals are still generated and stored but not traded
+CREATE TABLE shadowed_strategies (
+    strategy VARCHAR(50) PRIMARY KEY,
+    reason TEXT NOT NULL,
+    since TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Strategy throttles: reduced size and daily signal cap while realized edge is negative
+CREATE TABLE strategy_throttles (
+    strategy VARCHAR(50) PRIMARY KEY,
+    size_multiplier DECIMAL(10, 8) NOT NULL,
+    max_daily_signals INTEGER NOT NULL,
+    realized_edge DECIMAL(10, 8) NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Strategy weights: Sharpe-optimal capital shares, scaling each strategy's position size
+CREATE TABLE strategy_weights (
+    strategy VARCHAR(50) PRIMARY KEY,
+    weight DECIMAL(10, 8) NOT NULL,
+    mean_return DECIMAL(12, 8) NOT NULL,         -- daily, on capital
+    volatility DECIMAL(12, 8) NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Edge decay: how fast each strategy's edge fades with signal age, fitted
+-- from signals' prices at generation and when the engine took them up
+CREATE TABLE edge_decay (
+    strategy VARCHAR(50) PRIMARY KEY,
+    half_life_secs BIGINT NOT NULL,             -- age by which half the edge is gone
+    samples BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Resting orders: stop and target exits left on the CLOB after entry
+CREATE TABLE resting_orders (
+    order_id VARCHAR(100) PRIMARY KEY,          -- venue order id
+    trade_id UUID NOT NULL,
+    kind VARCHAR(10) NOT NULL,                  -- stop, target
+    price DECIMAL(10, 8) NOT NULL,
+    quantity DECIMAL(20, 8) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'open', -- open, filled, cancelled
+    placed_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX idx_resting_orders_trade_id ON resting_orders(trade_id) WHERE status = 'open';
+
+-- Entry orders: maker bids resting inside the spread for a signal until they fill or expire
+CREATE TABLE entry_orders (
+    order_id VARCHAR(100) PRIMARY KEY,          -- venue order id
+    signal_id UUID NOT NULL,
+    market_id VARCHAR(100) NOT NULL,
+    position VARCHAR(3) NOT NULL,               -- yes, no
+    price DECIMAL(10, 8) NOT NULL,
+    quantity DECIMAL(20, 8) NOT NULL,
+    gas_cost DECIMAL(10, 4) NOT NULL DEFAULT 0,
+    status VARCHAR(20) NOT NULL DEFAULT 'open', -- open, filled, cancelled
+    placed_at TIMESTAMPTZ NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX idx_entry_orders_open ON entry_orders(placed_at) WHERE status = 'open';
+
+-- Large entries split into child orders over time (twap) or as the best
+-- offer refills (iceberg)
+CREATE TABLE parent_orders (
+    parent_id UUID PRIMARY KEY,
+    signal_id UUID NOT NULL,
+    market_id VARCHAR(100) NOT NULL,
+    position VARCHAR(3) NOT NULL,               -- yes, no
+    mode VARCHAR(10) NOT NULL,                  -- twap, iceberg
+    target_notional DECIMAL(20, 6) NOT NULL,
+    limit_price DECIMAL(10, 8) NOT NULL,
+    slices INTEGER NOT NULL,
+    children_sent INTEGER NOT NULL DEFAULT 0,
+    filled_quantity DECIMAL(20, 8) NOT NULL DEFAULT 0,
+    filled_notional DECIMAL(20, 6) NOT NULL DEFAULT 0,
+    status VARCHAR(20) NOT NULL DEFAULT 'open', -- open, filled, cancelled
+    next_slice_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX idx_parent_orders_open ON parent_orders(created_at) WHERE status = 'open';
+
+CREATE TABLE child_orders (
+    parent_id UUID NOT NULL REFERENCES parent_orders(parent_id),
+    slice INTEGER NOT NULL,
+    trade_id UUID NOT NULL REFERENCES trades(trade_id),
+    quantity DECIMAL(20, 8) NOT NULL,
+    price DECIMAL(10, 8) NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    sent_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (parent_id, slice)
+);
+
+-- On-chain fills: CTF Exchange OrderFilled events for our wallet, the
+-- source of truth for order fill quantity and price
+CREATE TABLE onchain_fills (
+    tx_hash VARCHAR(66) NOT NULL,
+    log_index INTEGER NOT NULL,
+    order_hash VARCHAR(66) NOT NULL,
+    token_id VARCHAR(100) NOT NULL,
+    side VARCHAR(4) NOT NULL,                   -- buy, sell
+    shares DECIMAL(20, 6) NOT NULL,
+    price DECIMAL(10, 8) NOT NULL,              -- USDC per share
+    fee DECIMAL(20, 6) NOT NULL,
+    block_number BIGINT NOT NULL,
+    observed_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (tx_hash, log_index)
+);
+
+CREATE INDEX idx_onchain_fills_order ON onchain_fills(order_hash);
+
+-- Slippage events: fills that moved past tolerance from their signal's price
+CREATE TABLE slippage_events (
+    event_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    trade_id UUID NOT NULL,
+    signal_id UUID NOT NULL,
+    market_id VARCHAR(66) NOT NULL REFERENCES markets(market_id),
+    expected_price DECIMAL(10, 8) NOT NULL, -- signal's executable price
+    fill_price DECIMAL(10, 8) NOT NULL,
+    slippage DECIMAL(10, 8) NOT NULL,       -- fill less expected; positive is adverse
+    flattened BOOLEAN NOT NULL DEFAULT FALSE,
+    occurred_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX idx_slippage_events_occurred_at ON slippage_events(occurred_at);
+
+-- Gas and fees spent outside a trade's entry: exits, entries that failed
+-- after broadcast and redemptions, reverted or not
+CREATE TABLE execution_costs (
+    cost_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    kind VARCHAR(20) NOT NULL,             -- exit, failed_entry, redemption
+    amount DECIMAL(20, 8) NOT NULL,        -- USD
+    trade_id UUID,
+    tx_hash VARCHAR(66),
+    incurred_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_execution_costs_incurred_at ON execution_costs(incurred_at);
+
 -- Performance table: daily performance metrics by strategy
 CREATE TABLE performance (
     performance_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
     date DATE NOT NULL,
     strategy VARCHAR(50) NOT NULL,
+    venue VARCHAR(30) NOT NULL DEFAULT 'polymarket',
     trades_count INTEGER DEFAULT 0,
     wins INTEGER DEFAULT 0,
     losses INTEGER DEFAULT 0,
@@ -110,9 +370,27 @@ CREATE TABLE performance (
     created_at TIMESTAMPTZ DEFAULT NOW()
 );
 
-CREATE UNIQUE INDEX idx_performance_date_strategy ON performance(date, strategy);
+CREATE UNIQUE INDEX idx_performance_date_strategy_venue ON performance(date, strategy, venue);
 CREATE INDEX idx_performance_date ON performance(date);
 
+-- Experiments: backtest runs with what is needed to reproduce and compare them
+CREATE TABLE experiments (
+    experiment_id UUID PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    config JSONB NOT NULL,
+    code_version VARCHAR(80) NOT NULL,     -- git commit, suffixed -dirty with local changes
+    dataset_hash VARCHAR(64) NOT NULL,     -- sha256 of the price history and signals run over
+    dataset_rows INTEGER NOT NULL,
+    data_start TIMESTAMPTZ,
+    data_end TIMESTAMPTZ,
+    metrics JSONB NOT NULL,
+    started_at TIMESTAMPTZ NOT NULL,
+    finished_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_experiments_name ON experiments(name, finished_at);
+CREATE INDEX idx_experiments_dataset ON experiments(dataset_hash);
+
 -- Bookmaker odds: reference odds from sharp bookmakers
 CREATE TABLE bookmaker_odds (
     odds_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
@@ -124,7 +402,8 @@ CREATE TABLE bookmaker_odds (
     no_odds DECIMAL(10, 4),
     yes_implied_prob DECIMAL(10, 8),
     no_implied_prob DECIMAL(10, 8),
-    timestamp TIMESTAMPTZ DEFAULT NOW()
+    timestamp TIMESTAMPTZ DEFAULT NOW(), -- when we received the line
+    source_timestamp TIMESTAMPTZ        -- when the bookmaker last updated it, if the feed says
 );
 
 CREATE INDEX idx_bookmaker_odds_market_id ON bookmaker_odds(market_id);
@@ -132,6 +411,186 @@ CREATE INDEX idx_bookmaker_odds_timestamp ON bookmaker_odds(timestamp);
 
 SELECT create_hypertable('bookmaker_odds', 'timestamp', if_not_exists => TRUE);
 
+-- Market microstructure: order book depth and taker flow per poll, from the YES side
+CREATE TABLE market_microstructure (
+    market_id VARCHAR(66) NOT NULL REFERENCES markets(market_id),
+    bid_depth DECIMAL(20, 6) NOT NULL,          -- shares within the top book levels
+    ask_depth DECIMAL(20, 6) NOT NULL,
+    imbalance DECIMAL(10, 8) NOT NULL,          -- (bid - ask) / total depth
+    bid_depth_change DECIMAL(20, 6),            -- since the previous poll
+    ask_depth_change DECIMAL(20, 6),
+    taker_buy_volume DECIMAL(20, 6) NOT NULL,   -- over the flow window
+    taker_sell_volume DECIMAL(20, 6) NOT NULL,
+    observed_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_market_microstructure_market ON market_microstructure(market_id, observed_at);
+
+-- Order book snapshots: each market's latest YES book, for sizing entries against depth
+CREATE TABLE order_book_snapshots (
+    market_id VARCHAR(66) PRIMARY KEY REFERENCES markets(market_id),
+    bids JSONB NOT NULL,                        -- [{price, size}], any order
+    asks JSONB NOT NULL,
+    captured_at TIMESTAMPTZ NOT NULL
+);
+
+-- Large trades: unusually large taker trades on tracked markets
+CREATE TABLE large_trades (
+    tx_hash VARCHAR(66) NOT NULL,
+    market_id VARCHAR(66) NOT NULL REFERENCES markets(market_id),
+    wallet VARCHAR(42) NOT NULL,                -- taker's proxy wallet
+    position VARCHAR(3) NOT NULL,               -- outcome token traded
+    side VARCHAR(4) NOT NULL,                   -- taker side: buy, sell
+    shares DECIMAL(20, 6) NOT NULL,
+    price DECIMAL(10, 8) NOT NULL,
+    notional DECIMAL(20, 6) NOT NULL,           -- USDC
+    size_multiple DECIMAL(12, 4) NOT NULL,      -- of the market's median recent trade
+    traded_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (tx_hash, wallet, position, side)
+);
+
+CREATE INDEX idx_large_trades_market ON large_trades(market_id, traded_at);
+
+-- Dead letters: rows that failed to map into our types, with why, counted
+-- per item and reason so silent data loss shows up
+CREATE TABLE dead_letters (
+    source VARCHAR(50) NOT NULL,                -- polymarket_listing, bookmaker_odds, market_row
+    item_key VARCHAR(128) NOT NULL,             -- the item's id at its source
+    reason TEXT NOT NULL,
+    raw JSONB NOT NULL,                         -- latest row as received
+    occurrences BIGINT NOT NULL,
+    first_seen TIMESTAMPTZ NOT NULL,
+    last_seen TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (source, item_key, reason)
+);
+
+CREATE INDEX idx_dead_letters_last_seen ON dead_letters(last_seen);
+
+-- Market listings: per sport, markets Polymarket listed on the last poll
+-- and how many parsed into stored markets
+CREATE TABLE market_listings (
+    sport VARCHAR(20) PRIMARY KEY,
+    listed BIGINT NOT NULL,
+    ingested BIGINT NOT NULL,
+    polled_at TIMESTAMPTZ NOT NULL
+);
+
+-- Market evaluations: when each strategy last evaluated each market
+CREATE TABLE market_evaluations (
+    market_id VARCHAR(66) NOT NULL REFERENCES markets(market_id),
+    strategy VARCHAR(50) NOT NULL,              -- strategy name
+    evaluated_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (market_id, strategy)
+);
+
+-- Feed latency: per provider batch, delay from bookmaker update to receipt
+CREATE TABLE odds_feed_latency (
+    provider VARCHAR(50) NOT NULL,      -- the_odds_api, pinnacle, betfair
+    lines INTEGER NOT NULL,             -- lines carrying a bookmaker timestamp
+    median_ms BIGINT NOT NULL,
+    max_ms BIGINT NOT NULL,
+    measured_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_odds_feed_latency_provider ON odds_feed_latency(provider, measured_at);
+
+-- Team ratings: current strength per team, input to the season model
+CREATE TABLE team_ratings (
+    sport VARCHAR(50) NOT NULL,
+    team VARCHAR(100) NOT NULL,
+    conference VARCHAR(50) NOT NULL,    -- AFC, Eastern, American League, ...
+    division VARCHAR(50) NOT NULL,      -- AFC West, Atlantic, AL East, ...
+    rating DOUBLE PRECISION NOT NULL,   -- margin vs league average on a neutral field
+    latitude DOUBLE PRECISION,          -- home venue, for travel
+    longitude DOUBLE PRECISION,
+    elevation_m DOUBLE PRECISION,       -- home venue, for home advantage
+    indoor BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+    PRIMARY KEY (sport, team)
+);
+
+-- Games: regular-season schedule and results
+CREATE TABLE games (
+    game_id VARCHAR(100) PRIMARY KEY,
+    sport VARCHAR(50) NOT NULL,
+    season INTEGER NOT NULL,            -- year the season starts in
+    home_team VARCHAR(100) NOT NULL,
+    away_team VARCHAR(100) NOT NULL,
+    start_time TIMESTAMPTZ NOT NULL,
+    home_score INTEGER,                 -- NULL until played
+    away_score INTEGER
+);
+
+CREATE INDEX idx_games_sport_season ON games(sport, season, start_time);
+
+-- Injury adjustments: rating lost while a player is out
+CREATE TABLE injury_adjustments (
+    injury_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    sport VARCHAR(50) NOT NULL,
+    team VARCHAR(100) NOT NULL,
+    player VARCHAR(100) NOT NULL,
+    rating_delta DOUBLE PRECISION NOT NULL, -- negative for a loss
+    out_until TIMESTAMPTZ,              -- expected return; NULL for the season
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX idx_injury_adjustments_sport ON injury_adjustments(sport, out_until);
+
+-- Home advantage estimates per venue, refreshed with each season simulation
+CREATE TABLE home_advantage_estimates (
+    sport VARCHAR(50) NOT NULL,
+    team VARCHAR(100) NOT NULL,         -- home team, standing in for its venue
+    league DOUBLE PRECISION NOT NULL,
+    games BIGINT NOT NULL,
+    raw DOUBLE PRECISION,               -- NULL without home and away results
+    structural DOUBLE PRECISION NOT NULL,
+    estimate DOUBLE PRECISION NOT NULL,
+    estimated_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (sport, team)
+);
+
+-- Game features: per-team model inputs for each game (rest, travel, ...)
+CREATE TABLE game_features (
+    game_id VARCHAR(100) NOT NULL REFERENCES games(game_id),
+    team VARCHAR(100) NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    value DOUBLE PRECISION NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (game_id, team, name)
+);
+
+-- Official assignments: referees and umpires per game
+CREATE TABLE official_assignments (
+    game_id VARCHAR(100) NOT NULL REFERENCES games(game_id),
+    role VARCHAR(50) NOT NULL,          -- referee, home_plate, ...
+    official VARCHAR(100) NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+    PRIMARY KEY (game_id, role)
+);
+
+CREATE INDEX idx_official_assignments_official ON official_assignments(official, role);
+
+-- Probable pitchers: announced MLB starters with their season line
+CREATE TABLE probable_pitchers (
+    game_id VARCHAR(100) NOT NULL REFERENCES games(game_id),
+    team VARCHAR(100) NOT NULL,
+    pitcher VARCHAR(100) NOT NULL,
+    era DOUBLE PRECISION,                     -- NULL before a first start
+    innings_pitched DOUBLE PRECISION NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+    PRIMARY KEY (game_id, team)
+);
+
+-- Bullpen usage: relief innings per MLB team and game, for fatigue
+CREATE TABLE bullpen_usage (
+    team VARCHAR(100) NOT NULL,
+    game_time TIMESTAMPTZ NOT NULL,
+    innings DOUBLE PRECISION NOT NULL,
+    PRIMARY KEY (team, game_time)
+);
+
+CREATE INDEX idx_bullpen_usage_game_time ON bullpen_usage(game_time);
+
 -- Whale wallets: tracked informed trader addresses
 CREATE TABLE whale_wallets (
     wallet_address VARCHAR(42) PRIMARY KEY,
@@ -175,6 +634,7 @@ CREATE TABLE portfolio_state (
     total_capital DECIMAL(20, 4) NOT NULL,
     available_capital DECIMAL(20, 4) NOT NULL,
     invested_capital DECIMAL(20, 4) NOT NULL,
+    settlement_pending DECIMAL(20, 4) DEFAULT 0, -- open positions on ended, unsettled markets
     unrealized_pnl DECIMAL(20, 4) DEFAULT 0,
     realized_pnl_today DECIMAL(20, 4) DEFAULT 0,
     daily_drawdown DECIMAL(10, 4) DEFAULT 0,
@@ -187,6 +647,21 @@ CREATE TABLE portfolio_state (
 CREATE INDEX idx_portfolio_state_timestamp ON portfolio_state(timestamp);
 SELECT create_hypertable('portfolio_state', 'timestamp', if_not_exists => TRUE);
 
+-- Cash flows: deposits and withdrawals of trading capital
+CREATE TABLE cash_flows (
+    flow_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    kind VARCHAR(20) NOT NULL,          -- deposit, withdrawal
+    amount DECIMAL(20, 4) NOT NULL CHECK (amount > 0),
+    source VARCHAR(20) NOT NULL,        -- manual, on_chain
+    tx_hash VARCHAR(66) UNIQUE,         -- set for on-chain transfers
+    note TEXT,
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    venue VARCHAR(30) NOT NULL DEFAULT 'polymarket',
+    recorded_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX idx_cash_flows_occurred_at ON cash_flows(occurred_at);
+
 -- Risk limits: configurable risk parameters
 CREATE TABLE risk_limits (
     limit_id SERIAL PRIMARY KEY,
@@ -215,7 +690,7 @@ CREATE TABLE circuit_breakers (
     triggered_at TIMESTAMPTZ DEFAULT NOW(),
     cleared_at TIMESTAMPTZ,
     status VARCHAR(20) DEFAULT 'active', -- active, cleared
-    metadata JSONB
+    metadata JSONB                      -- {"version": n, "data": ...}
 );
 
 CREATE INDEX idx_circuit_breakers_status ON circuit_breakers(status);
@@ -238,6 +713,30 @@ CREATE INDEX idx_system_logs_level ON system_logs(level);
 CREATE INDEX idx_system_logs_timestamp ON system_logs(timestamp);
 SELECT create_hypertable('system_logs', 'timestamp', if_not_exists => TRUE);
 
+-- Decision log: automated changes to how the bot trades, and why
+CREATE TABLE decision_log (
+    decision_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    kind VARCHAR(50) NOT NULL,          -- throttle
+    subject VARCHAR(100) NOT NULL,      -- what was changed, e.g. a strategy
+    summary TEXT NOT NULL,
+    detail JSONB NOT NULL,
+    decided_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_decision_log_decided_at ON decision_log(decided_at DESC);
+
+-- Credentials: external API secrets sealed with the master key from the
+-- environment; rotating adds a version and the highest one is used
+CREATE TABLE credentials (
+    provider VARCHAR(50) NOT NULL,      -- pinnacle, betfair, the_odds_api, ...
+    name VARCHAR(50) NOT NULL,          -- api_key, password, ...
+    version INTEGER NOT NULL,
+    nonce BYTEA NOT NULL,
+    ciphertext BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (provider, name, version)
+);
+
 -- API requests: tracking external API calls
 CREATE TABLE api_requests (
     request_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
@@ -258,11 +757,33 @@ SELECT create_hypertable('api_requests', 'timestamp', if_not_exists => TRUE);
 -- ============================================================================
 
 -- Current portfolio summary
+-- Official tendencies: how games an official worked have scored against
+-- the season average, per role
+CREATE VIEW v_official_tendencies AS
+WITH season_average AS (
+    SELECT sport, season, AVG(home_score + away_score) AS average_total
+    FROM games
+    WHERE home_score IS NOT NULL AND away_score IS NOT NULL
+    GROUP BY sport, season
+)
+SELECT
+    g.sport,
+    a.official,
+    a.role,
+    COUNT(*) AS games,
+    AVG(g.home_score + g.away_score - s.average_total)::DOUBLE PRECISION AS total_delta
+FROM official_assignments a
+JOIN games g ON g.game_id = a.game_id
+JOIN season_average s ON s.sport = g.sport AND s.season = g.season
+WHERE g.home_score IS NOT NULL AND g.away_score IS NOT NULL
+GROUP BY g.sport, a.official, a.role;
+
 CREATE VIEW v_portfolio_summary AS
 SELECT 
     total_capital,
     available_capital,
     invested_capital,
+    settlement_pending,
     unrealized_pnl,
     realized_pnl_today,
     daily_drawdown,
@@ -277,6 +798,8 @@ CREATE VIEW v_active_positions AS
 SELECT 
     t.trade_id,
     t.market_id,
+    t.venue,
+    t.origin,
     m.event_name,
     m.sport,
     t.strategy,
@@ -306,6 +829,51 @@ FROM performance
 WHERE date >= CURRENT_DATE - INTERVAL '30 days'
 GROUP BY strategy;
 
+-- MAE/MFE distribution per strategy over 90 days, as percent of cost
+CREATE VIEW v_strategy_excursions_90d AS
+SELECT
+    strategy,
+    COUNT(*) as trades,
+    percentile_cont(0.5) WITHIN GROUP (ORDER BY -max_adverse_excursion / (entry_price * quantity)) * 100 as adverse_p50_pct,
+    percentile_cont(0.9) WITHIN GROUP (ORDER BY -max_adverse_excursion / (entry_price * quantity)) * 100 as adverse_p90_pct,
+    percentile_cont(0.5) WITHIN GROUP (ORDER BY max_favorable_excursion / (entry_price * quantity)) * 100 as favorable_p50_pct,
+    percentile_cont(0.9) WITHIN GROUP (ORDER BY max_favorable_excursion / (entry_price * quantity)) * 100 as favorable_p90_pct
+FROM trades
+WHERE status = 'closed'
+    AND exit_time >= NOW() - INTERVAL '90 days'
+    AND max_adverse_excursion IS NOT NULL
+    AND entry_price * quantity > 0
+GROUP BY strategy;
+
+-- Price history of markets with a known outcome, in the backtester's shape
+CREATE VIEW v_backtest_prices AS
+SELECT
+    h.observed_at as timestamp,
+    m.market_id,
+    m.sport,
+    m.market_type,
+    h.yes_price,
+    1 - h.yes_price as no_price,
+    m.current_liquidity as liquidity,
+    m.resolution
+FROM market_price_history h
+JOIN markets m ON m.market_id = h.market_id
+WHERE m.resolution IS NOT NULL;
+
+-- Venue performance summary (last 30 days)
+CREATE VIEW v_venue_performance_30d AS
+SELECT
+    venue,
+    SUM(trades_count) as total_trades,
+    SUM(wins) as total_wins,
+    SUM(losses) as total_losses,
+    SUM(total_pnl) as total_pnl,
+    AVG(win_rate) as avg_win_rate,
+    AVG(sharpe_ratio) as avg_sharpe
+FROM performance
+WHERE date >= CURRENT_DATE - INTERVAL '30 days'
+GROUP BY venue;
+
 -- ============================================================================
 -- FUNCTIONS
 -- ============================================================================
@@ -329,40 +897,115 @@ END;
 $$ LANGUAGE plpgsql;
 
 -- Function to calculate current portfolio state
-CREATE OR REPLACE FUNCTION calculate_portfolio_state()
+CREATE OR REPLACE FUNCTION calculate_portfolio_state(
+    p_starting_capital DECIMAL(20, 4) DEFAULT 50000.00
+)
 RETURNS TABLE (
     total_capital DECIMAL(20, 4),
     available_capital DECIMAL(20, 4),
     invested_capital DECIMAL(20, 4),
-    unrealized_pnl DECIMAL(20, 4)
+    settlement_pending DECIMAL(20, 4),
+    unrealized_pnl DECIMAL(20, 4),
+    net_cash_flows DECIMAL(20, 4)
 ) AS $$
 BEGIN
     RETURN QUERY
     WITH base_capital AS (
-        SELECT 50000.00::DECIMAL(20, 4) as base  -- Starting capital
+        SELECT p_starting_capital as base  -- Starting capital
     ),
     realized AS (
         SELECT COALESCE(SUM(pnl), 0) as total_realized
         FROM trades
         WHERE status = 'closed'
     ),
-    unrealized AS (
-        SELECT COALESCE(SUM((m.yes_price - t.entry_price) * t.quantity), 0) as total_unrealized
+    flows AS (
+        SELECT COALESCE(SUM(CASE WHEN kind = 'deposit' THEN amount ELSE -amount END), 0) as net_flows
+        FROM cash_flows
+    ),
+    -- Positions on ended markets wait on resolution or settlement: kept out
+    -- of both invested and available capital, but still marked like any
+    -- other so equity does not jump as a market ends
+    open_positions AS (
+        SELECT t.quantity, t.entry_price, m.yes_price,
+               m.status IN ('closed', 'resolved', 'disputed') as awaits_settlement
         FROM trades t
         JOIN markets m ON t.market_id = m.market_id
         WHERE t.status = 'open'
     ),
+    unrealized AS (
+        SELECT COALESCE(SUM((yes_price - entry_price) * quantity), 0) as total_unrealized
+        FROM open_positions
+    ),
     invested AS (
-        SELECT COALESCE(SUM(t.entry_price * t.quantity), 0) as total_invested
-        FROM trades t
-        WHERE t.status = 'open'
+        SELECT COALESCE(SUM(entry_price * quantity) FILTER (WHERE NOT awaits_settlement), 0) as total_invested,
+               COALESCE(SUM(entry_price * quantity) FILTER (WHERE awaits_settlement), 0) as total_pending
+        FROM open_positions
     )
     SELECT 
-        bc.base + r.total_realized as total_capital,
-        bc.base + r.total_realized - i.total_invested as available_capital,
+        bc.base + f.net_flows + r.total_realized as total_capital,
+        bc.base + f.net_flows + r.total_realized - i.total_invested - i.total_pending as available_capital,
         i.total_invested as invested_capital,
-        u.total_unrealized as unrealized_pnl
-    FROM base_capital bc, realized r, unrealized u, invested i;
+        i.total_pending as settlement_pending,
+        u.total_unrealized as unrealized_pnl,
+        f.net_flows as net_cash_flows
+    FROM base_capital bc, realized r, flows f, unrealized u, invested i;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Per-venue flows and PnL; starting capital per venue is applied by the caller
+CREATE OR REPLACE FUNCTION calculate_venue_balances()
+RETURNS TABLE (
+    venue VARCHAR(30),
+    net_cash_flows DECIMAL(20, 4),
+    realized_pnl DECIMAL(20, 4),
+    realized_pnl_today DECIMAL(20, 4),
+    invested_capital DECIMAL(20, 4),
+    unrealized_pnl DECIMAL(20, 4),
+    open_positions BIGINT
+) AS $$
+BEGIN
+    RETURN QUERY
+    WITH venues AS (
+        SELECT t.venue FROM trades t
+        UNION
+        SELECT c.venue FROM cash_flows c
+    ),
+    flows AS (
+        SELECT c.venue,
+            SUM(CASE WHEN c.kind = 'deposit' THEN c.amount ELSE -c.amount END) as net_flows
+        FROM cash_flows c
+        GROUP BY c.venue
+    ),
+    closed AS (
+        SELECT t.venue,
+            SUM(t.pnl) as realized,
+            SUM(t.pnl) FILTER (WHERE DATE(t.exit_time) = CURRENT_DATE) as realized_today
+        FROM trades t
+        WHERE t.status = 'closed'
+        GROUP BY t.venue
+    ),
+    open AS (
+        SELECT t.venue,
+            SUM(t.entry_price * t.quantity) as invested,
+            SUM((m.yes_price - t.entry_price) * t.quantity) as unrealized,
+            COUNT(*) as positions
+        FROM trades t
+        JOIN markets m ON t.market_id = m.market_id
+        WHERE t.status = 'open'
+        GROUP BY t.venue
+    )
+    SELECT
+        v.venue,
+        COALESCE(f.net_flows, 0),
+        COALESCE(c.realized, 0),
+        COALESCE(c.realized_today, 0),
+        COALESCE(o.invested, 0),
+        COALESCE(o.unrealized, 0),
+        COALESCE(o.positions, 0)
+    FROM venues v
+    LEFT JOIN flows f ON f.venue = v.venue
+    LEFT JOIN closed c ON c.venue = v.venue
+    LEFT JOIN open o ON o.venue = v.venue;
 END;
 $$ LANGUAGE plpgsql;
 
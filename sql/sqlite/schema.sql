@@ -0,0 +1,582 @@
+-- Polymarket Trading Bot - SQLite schema for paper trading and backtests
+-- Mirrors the core tables of sql/schema.sql. Decimals are stored as TEXT to
+-- avoid floating point rounding; timestamps as RFC 3339 TEXT.
+
+CREATE TABLE IF NOT EXISTS markets (
+    market_id TEXT PRIMARY KEY,
+    sport TEXT NOT NULL,
+    event_name TEXT NOT NULL,
+    event_time TEXT NOT NULL,
+    market_type TEXT NOT NULL,
+    description TEXT,
+    resolution_source TEXT,
+    min_liquidity TEXT,
+    current_liquidity TEXT,
+    yes_price TEXT,
+    no_price TEXT,
+    yes_bid TEXT,
+    yes_ask TEXT,
+    no_bid TEXT,
+    no_ask TEXT,
+    yes_bid_size TEXT,
+    yes_ask_size TEXT,
+    no_bid_size TEXT,
+    no_ask_size TEXT,
+    status TEXT DEFAULT 'active',
+    resolution TEXT,
+    resolved_at TEXT,
+    condition_id TEXT,
+    question_id TEXT,
+    yes_token_id TEXT,
+    no_token_id TEXT,
+    created_at TEXT DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    updated_at TEXT DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_markets_status_event_time ON markets(status, event_time);
+
+CREATE TABLE IF NOT EXISTS trades (
+    trade_id TEXT PRIMARY KEY,
+    market_id TEXT NOT NULL REFERENCES markets(market_id),
+    strategy TEXT NOT NULL,
+    position TEXT NOT NULL,
+    quantity TEXT NOT NULL,
+    entry_price TEXT NOT NULL,
+    exit_price TEXT,
+    entry_time TEXT NOT NULL,
+    exit_time TEXT,
+    gas_cost TEXT,
+    fees TEXT,
+    slippage TEXT,
+    pnl TEXT,
+    pnl_percent TEXT,
+    max_adverse_excursion TEXT,
+    max_favorable_excursion TEXT,
+    status TEXT DEFAULT 'open',
+    tx_hash_entry TEXT,
+    tx_hash_exit TEXT,
+    venue TEXT NOT NULL DEFAULT 'polymarket',
+    origin TEXT NOT NULL DEFAULT 'bot',
+    notes TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_trades_status ON trades(status);
+CREATE INDEX IF NOT EXISTS idx_trades_entry_time ON trades(entry_time);
+CREATE INDEX IF NOT EXISTS idx_trades_tx_hash_entry ON trades(tx_hash_entry);
+
+CREATE TABLE IF NOT EXISTS signals (
+    signal_id TEXT PRIMARY KEY,
+    market_id TEXT NOT NULL REFERENCES markets(market_id),
+    strategy TEXT NOT NULL,
+    signal_type TEXT NOT NULL,
+    confidence TEXT NOT NULL,
+    edge_size TEXT NOT NULL,
+    recommended_size TEXT,
+    current_price TEXT,
+    executable_size TEXT,
+    quoted_edge TEXT,
+    fair_value TEXT,
+    executed INTEGER DEFAULT 0,
+    executed_trade_id TEXT REFERENCES trades(trade_id),
+    claimed_by TEXT,
+    claimed_until TEXT,
+    execution_quote TEXT,
+    execution_quoted_at TEXT,
+    generated_at TEXT NOT NULL,
+    metadata TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_signals_pending ON signals(executed, generated_at);
+CREATE INDEX IF NOT EXISTS idx_signals_market ON signals(market_id, generated_at);
+
+CREATE TABLE IF NOT EXISTS failed_executions (
+    signal_id TEXT PRIMARY KEY,
+    market_id TEXT NOT NULL,
+    strategy TEXT NOT NULL,
+    attempts INTEGER NOT NULL,
+    last_error TEXT NOT NULL,
+    status TEXT NOT NULL,
+    next_attempt_at TEXT,
+    first_failed_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_failed_executions_updated_at ON failed_executions(updated_at);
+
+CREATE TABLE IF NOT EXISTS trade_marks (
+    trade_id TEXT PRIMARY KEY,
+    mark_price TEXT NOT NULL,
+    unrealized_pnl TEXT NOT NULL,
+    max_adverse_excursion TEXT NOT NULL,
+    max_favorable_excursion TEXT NOT NULL,
+    marked_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS trade_convergence (
+    sample_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    trade_id TEXT NOT NULL REFERENCES trades(trade_id),
+    market_price TEXT NOT NULL,
+    fair_value TEXT NOT NULL,
+    divergence TEXT NOT NULL,
+    sampled_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_trade_convergence_trade ON trade_convergence(trade_id, sampled_at);
+
+CREATE TABLE IF NOT EXISTS trade_sizing (
+    trade_id TEXT PRIMARY KEY REFERENCES trades(trade_id),
+    bankroll TEXT NOT NULL,
+    stake TEXT NOT NULL,
+    sized_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS settlement_reviews (
+    trade_id TEXT PRIMARY KEY REFERENCES trades(trade_id),
+    market_id TEXT NOT NULL REFERENCES markets(market_id),
+    reported_payout TEXT,
+    onchain_payout TEXT,
+    reason TEXT NOT NULL,
+    flagged_at TEXT NOT NULL,
+    resolved_at TEXT
+);
+
+CREATE TABLE IF NOT EXISTS trade_settlements (
+    trade_id TEXT PRIMARY KEY REFERENCES trades(trade_id),
+    market_id TEXT NOT NULL REFERENCES markets(market_id),
+    outcome TEXT NOT NULL,
+    payout TEXT NOT NULL,
+    pnl TEXT NOT NULL,
+    tx_hash TEXT,
+    settled_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_trade_settlements_settled_at ON trade_settlements(settled_at);
+
+CREATE TABLE IF NOT EXISTS closing_lines (
+    market_id TEXT PRIMARY KEY REFERENCES markets(market_id),
+    yes_price TEXT NOT NULL,
+    no_price TEXT NOT NULL,
+    captured_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS market_price_history (
+    market_id TEXT NOT NULL REFERENCES markets(market_id),
+    yes_price TEXT NOT NULL,
+    observed_at TEXT NOT NULL,
+    PRIMARY KEY (market_id, observed_at)
+);
+
+CREATE VIEW IF NOT EXISTS v_backtest_prices AS
+SELECT
+    h.observed_at AS timestamp,
+    m.market_id,
+    m.sport,
+    m.market_type,
+    CAST(h.yes_price AS REAL) AS yes_price,
+    1 - CAST(h.yes_price AS REAL) AS no_price,
+    CAST(m.current_liquidity AS REAL) AS liquidity,
+    m.resolution
+FROM market_price_history h
+JOIN markets m ON m.market_id = h.market_id
+WHERE m.resolution IS NOT NULL;
+
+CREATE TABLE IF NOT EXISTS shadowed_strategies (
+    strategy TEXT PRIMARY KEY,
+    reason TEXT NOT NULL,
+    since TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS strategy_throttles (
+    strategy TEXT PRIMARY KEY,
+    size_multiplier TEXT NOT NULL,
+    max_daily_signals INTEGER NOT NULL,
+    realized_edge TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS strategy_weights (
+    strategy TEXT PRIMARY KEY,
+    weight TEXT NOT NULL,
+    mean_return TEXT NOT NULL,
+    volatility TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS edge_decay (
+    strategy TEXT PRIMARY KEY,
+    half_life_secs INTEGER NOT NULL,
+    samples INTEGER NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS decision_log (
+    decision_id TEXT PRIMARY KEY,
+    kind TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    summary TEXT NOT NULL,
+    detail TEXT NOT NULL,
+    decided_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS credentials (
+    provider TEXT NOT NULL,
+    name TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    nonce BLOB NOT NULL,
+    ciphertext BLOB NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (provider, name, version)
+);
+
+CREATE TABLE IF NOT EXISTS resting_orders (
+    order_id TEXT PRIMARY KEY,
+    trade_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    price TEXT NOT NULL,
+    quantity TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'open',
+    placed_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_resting_orders_trade_id ON resting_orders(trade_id, status);
+
+CREATE TABLE IF NOT EXISTS entry_orders (
+    order_id TEXT PRIMARY KEY,
+    signal_id TEXT NOT NULL,
+    market_id TEXT NOT NULL,
+    position TEXT NOT NULL,
+    price TEXT NOT NULL,
+    quantity TEXT NOT NULL,
+    gas_cost TEXT NOT NULL DEFAULT '0',
+    status TEXT NOT NULL DEFAULT 'open',
+    placed_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_entry_orders_status ON entry_orders(status, placed_at);
+
+CREATE TABLE IF NOT EXISTS parent_orders (
+    parent_id TEXT PRIMARY KEY,
+    signal_id TEXT NOT NULL,
+    market_id TEXT NOT NULL,
+    position TEXT NOT NULL,
+    mode TEXT NOT NULL,
+    target_notional TEXT NOT NULL,
+    limit_price TEXT NOT NULL,
+    slices INTEGER NOT NULL,
+    children_sent INTEGER NOT NULL DEFAULT 0,
+    filled_quantity TEXT NOT NULL DEFAULT '0',
+    filled_notional TEXT NOT NULL DEFAULT '0',
+    status TEXT NOT NULL DEFAULT 'open',
+    next_slice_at TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_parent_orders_status ON parent_orders(status, created_at);
+
+CREATE TABLE IF NOT EXISTS child_orders (
+    parent_id TEXT NOT NULL,
+    slice INTEGER NOT NULL,
+    trade_id TEXT NOT NULL,
+    quantity TEXT NOT NULL,
+    price TEXT NOT NULL,
+    tx_hash TEXT NOT NULL,
+    sent_at TEXT NOT NULL,
+    PRIMARY KEY (parent_id, slice)
+);
+
+CREATE TABLE IF NOT EXISTS onchain_fills (
+    tx_hash TEXT NOT NULL,
+    log_index INTEGER NOT NULL,
+    order_hash TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    side TEXT NOT NULL,
+    shares TEXT NOT NULL,
+    price TEXT NOT NULL,
+    fee TEXT NOT NULL,
+    block_number INTEGER NOT NULL,
+    observed_at TEXT NOT NULL,
+    PRIMARY KEY (tx_hash, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_onchain_fills_order ON onchain_fills(order_hash);
+
+CREATE TABLE IF NOT EXISTS slippage_events (
+    event_id TEXT PRIMARY KEY,
+    trade_id TEXT NOT NULL,
+    signal_id TEXT NOT NULL,
+    market_id TEXT NOT NULL REFERENCES markets(market_id),
+    expected_price TEXT NOT NULL,
+    fill_price TEXT NOT NULL,
+    slippage TEXT NOT NULL,
+    flattened INTEGER NOT NULL DEFAULT 0,
+    occurred_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS execution_costs (
+    cost_id TEXT PRIMARY KEY,
+    kind TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    trade_id TEXT,
+    tx_hash TEXT,
+    incurred_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_execution_costs_incurred_at ON execution_costs(incurred_at);
+
+CREATE TABLE IF NOT EXISTS experiments (
+    experiment_id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    config TEXT NOT NULL,
+    code_version TEXT NOT NULL,
+    dataset_hash TEXT NOT NULL,
+    dataset_rows INTEGER NOT NULL,
+    data_start TEXT,
+    data_end TEXT,
+    metrics TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    finished_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_experiments_name ON experiments(name, finished_at);
+CREATE INDEX IF NOT EXISTS idx_experiments_dataset ON experiments(dataset_hash);
+
+CREATE TABLE IF NOT EXISTS bookmaker_odds (
+    odds_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    market_id TEXT NOT NULL REFERENCES markets(market_id),
+    bookmaker TEXT NOT NULL,
+    odds_type TEXT NOT NULL,
+    line_value TEXT,
+    yes_odds TEXT,
+    no_odds TEXT,
+    yes_implied_prob TEXT,
+    no_implied_prob TEXT,
+    timestamp TEXT NOT NULL,
+    source_timestamp TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_bookmaker_odds_market_time ON bookmaker_odds(market_id, timestamp);
+
+CREATE TABLE IF NOT EXISTS market_microstructure (
+    market_id TEXT NOT NULL REFERENCES markets(market_id),
+    bid_depth TEXT NOT NULL,
+    ask_depth TEXT NOT NULL,
+    imbalance TEXT NOT NULL,
+    bid_depth_change TEXT,
+    ask_depth_change TEXT,
+    taker_buy_volume TEXT NOT NULL,
+    taker_sell_volume TEXT NOT NULL,
+    observed_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_market_microstructure_market ON market_microstructure(market_id, observed_at);
+
+CREATE TABLE IF NOT EXISTS order_book_snapshots (
+    market_id TEXT PRIMARY KEY REFERENCES markets(market_id),
+    bids TEXT NOT NULL,
+    asks TEXT NOT NULL,
+    captured_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS large_trades (
+    tx_hash TEXT NOT NULL,
+    market_id TEXT NOT NULL REFERENCES markets(market_id),
+    wallet TEXT NOT NULL,
+    position TEXT NOT NULL,
+    side TEXT NOT NULL,
+    shares TEXT NOT NULL,
+    price TEXT NOT NULL,
+    notional TEXT NOT NULL,
+    size_multiple TEXT NOT NULL,
+    traded_at TEXT NOT NULL,
+    PRIMARY KEY (tx_hash, wallet, position, side)
+);
+
+CREATE INDEX IF NOT EXISTS idx_large_trades_market ON large_trades(market_id, traded_at);
+
+CREATE TABLE IF NOT EXISTS dead_letters (
+    source TEXT NOT NULL,
+    item_key TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    raw TEXT NOT NULL,
+    occurrences INTEGER NOT NULL,
+    first_seen TEXT NOT NULL,
+    last_seen TEXT NOT NULL,
+    PRIMARY KEY (source, item_key, reason)
+);
+
+CREATE INDEX IF NOT EXISTS idx_dead_letters_last_seen ON dead_letters(last_seen);
+
+CREATE TABLE IF NOT EXISTS market_listings (
+    sport TEXT PRIMARY KEY,
+    listed INTEGER NOT NULL,
+    ingested INTEGER NOT NULL,
+    polled_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS market_evaluations (
+    market_id TEXT NOT NULL REFERENCES markets(market_id),
+    strategy TEXT NOT NULL,
+    evaluated_at TEXT NOT NULL,
+    PRIMARY KEY (market_id, strategy)
+);
+
+CREATE TABLE IF NOT EXISTS odds_feed_latency (
+    provider TEXT NOT NULL,
+    lines INTEGER NOT NULL,
+    median_ms INTEGER NOT NULL,
+    max_ms INTEGER NOT NULL,
+    measured_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_odds_feed_latency_provider ON odds_feed_latency(provider, measured_at);
+
+CREATE TABLE IF NOT EXISTS team_ratings (
+    sport TEXT NOT NULL,
+    team TEXT NOT NULL,
+    conference TEXT NOT NULL,
+    division TEXT NOT NULL,
+    rating REAL NOT NULL,
+    latitude REAL,
+    longitude REAL,
+    elevation_m REAL,
+    indoor INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY (sport, team)
+);
+
+CREATE TABLE IF NOT EXISTS games (
+    game_id TEXT PRIMARY KEY,
+    sport TEXT NOT NULL,
+    season INTEGER NOT NULL,
+    home_team TEXT NOT NULL,
+    away_team TEXT NOT NULL,
+    start_time TEXT NOT NULL,
+    home_score INTEGER,
+    away_score INTEGER
+);
+
+CREATE INDEX IF NOT EXISTS idx_games_sport_season ON games(sport, season, start_time);
+
+CREATE TABLE IF NOT EXISTS injury_adjustments (
+    injury_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    sport TEXT NOT NULL,
+    team TEXT NOT NULL,
+    player TEXT NOT NULL,
+    rating_delta REAL NOT NULL,
+    out_until TEXT,
+    created_at TEXT DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_injury_adjustments_sport ON injury_adjustments(sport, out_until);
+
+CREATE TABLE IF NOT EXISTS home_advantage_estimates (
+    sport TEXT NOT NULL,
+    team TEXT NOT NULL,
+    league REAL NOT NULL,
+    games INTEGER NOT NULL,
+    raw REAL,
+    structural REAL NOT NULL,
+    estimate REAL NOT NULL,
+    estimated_at TEXT NOT NULL,
+    PRIMARY KEY (sport, team)
+);
+
+CREATE TABLE IF NOT EXISTS game_features (
+    game_id TEXT NOT NULL REFERENCES games(game_id),
+    team TEXT NOT NULL,
+    name TEXT NOT NULL,
+    value REAL NOT NULL,
+    computed_at TEXT NOT NULL,
+    PRIMARY KEY (game_id, team, name)
+);
+
+CREATE TABLE IF NOT EXISTS official_assignments (
+    game_id TEXT NOT NULL REFERENCES games(game_id),
+    role TEXT NOT NULL,
+    official TEXT NOT NULL,
+    updated_at TEXT DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY (game_id, role)
+);
+
+CREATE INDEX IF NOT EXISTS idx_official_assignments_official ON official_assignments(official, role);
+
+CREATE TABLE IF NOT EXISTS probable_pitchers (
+    game_id TEXT NOT NULL REFERENCES games(game_id),
+    team TEXT NOT NULL,
+    pitcher TEXT NOT NULL,
+    era REAL,
+    innings_pitched REAL NOT NULL,
+    updated_at TEXT DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY (game_id, team)
+);
+
+CREATE TABLE IF NOT EXISTS bullpen_usage (
+    team TEXT NOT NULL,
+    game_time TEXT NOT NULL,
+    innings REAL NOT NULL,
+    PRIMARY KEY (team, game_time)
+);
+
+CREATE INDEX IF NOT EXISTS idx_bullpen_usage_game_time ON bullpen_usage(game_time);
+
+CREATE VIEW IF NOT EXISTS v_official_tendencies AS
+WITH season_average AS (
+    SELECT sport, season, AVG(home_score + away_score) AS average_total
+    FROM games
+    WHERE home_score IS NOT NULL AND away_score IS NOT NULL
+    GROUP BY sport, season
+)
+SELECT
+    g.sport,
+    a.official,
+    a.role,
+    COUNT(*) AS games,
+    AVG(g.home_score + g.away_score - s.average_total) AS total_delta
+FROM official_assignments a
+JOIN games g ON g.game_id = a.game_id
+JOIN season_average s ON s.sport = g.sport AND s.season = g.season
+WHERE g.home_score IS NOT NULL AND g.away_score IS NOT NULL
+GROUP BY g.sport, a.official, a.role;
+
+CREATE TABLE IF NOT EXISTS portfolio_state (
+    snapshot_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    total_capital TEXT NOT NULL,
+    available_capital TEXT NOT NULL,
+    invested_capital TEXT NOT NULL,
+    settlement_pending TEXT NOT NULL DEFAULT '0',
+    unrealized_pnl TEXT DEFAULT '0',
+    realized_pnl_today TEXT DEFAULT '0',
+    daily_drawdown TEXT DEFAULT '0',
+    max_drawdown TEXT DEFAULT '0',
+    open_positions INTEGER DEFAULT 0,
+    trades_today INTEGER DEFAULT 0,
+    timestamp TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS cash_flows (
+    flow_id TEXT PRIMARY KEY,
+    kind TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    source TEXT NOT NULL,
+    tx_hash TEXT UNIQUE,
+    note TEXT,
+    occurred_at TEXT NOT NULL,
+    venue TEXT NOT NULL DEFAULT 'polymarket',
+    recorded_at TEXT DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_cash_flows_occurred_at ON cash_flows(occurred_at);
+
+CREATE TABLE IF NOT EXISTS circuit_breakers (
+    breaker_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    reason TEXT NOT NULL,
+    triggered_at TEXT NOT NULL,
+    cleared_at TEXT,
+    status TEXT DEFAULT 'active',
+    metadata TEXT
+);
@@ -0,0 +1,204 @@
+use axum::extract::{Query, State};
+use axum::Json;
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+
+use crate::risk::{
+    calibration_report, convergence_summary, current_exposure, excursion_report, kelly_realization, returns_report,
+    scan_consistency, shortfall_report, ConvergenceSummary, ExposureReport, Inconsistency, KellyReport, ReturnsReport,
+    ShortfallReport, StrategyCalibration, StrategyExcursions,
+};
+use crate::data::{coverage_report, SportCoverage};
+use crate::types::{DeadLetter, FeedLatency, HomeAdvantageEstimate, LargeTrade, Sport, Strategy};
+use super::error::ApiError;
+use super::server::ApiState;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ExcursionQuery {
+    since: Option<DateTime<Utc>>,
+}
+
+/// GET /analytics/excursions?since=<rfc3339> (defaults to the last 90 days)
+///
+/// MAE/MFE percentiles of closed trades per strategy.
+pub(crate) async fn strategy_excursions(
+    State(state): State<ApiState>,
+    Query(query): Query<ExcursionQuery>,
+) -> Result<Json<Vec<StrategyExcursions>>, ApiError> {
+    let since = query.since.unwrap_or_else(|| Utc::now() - Duration::days(90));
+    let trades = state.storage.fetch_closed_trades(since).await?;
+    Ok(Json(excursion_report(&trades)))
+}
+
+/// GET /analytics/convergence?since=<rfc3339> (defaults to the last 90 days)
+///
+/// For each closed CLV trade, how much of its divergence from the sharp line
+/// closed while it was held.
+pub(crate) async fn clv_convergence(
+    State(state): State<ApiState>,
+    Query(query): Query<ExcursionQuery>,
+) -> Result<Json<Vec<ConvergenceSummary>>, ApiError> {
+    let since = query.since.unwrap_or_else(|| Utc::now() - Duration::days(90));
+    let mut summaries = Vec::new();
+    for trade in state.storage.fetch_closed_trades(since).await? {
+        if trade.strategy != Strategy::ClvArbitrage {
+            continue;
+        }
+        let samples = state.storage.fetch_convergence_samples(trade.trade_id).await?;
+        summaries.extend(convergence_summary(&trade, &samples));
+    }
+    Ok(Json(summaries))
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CalibrationQuery {
+    since: Option<DateTime<Utc>>,
+    bins: Option<usize>,
+}
+
+/// GET /analytics/calibration?since=<rfc3339>&bins=<n> (defaults to the last
+/// 90 days in 10 bins)
+///
+/// Per strategy, a reliability diagram of predicted probability against
+/// realized frequency, and mean edge at entry against realized return.
+pub(crate) async fn calibration(
+    State(state): State<ApiState>,
+    Query(query): Query<CalibrationQuery>,
+) -> Result<Json<Vec<StrategyCalibration>>, ApiError> {
+    let since = query.since.unwrap_or_else(|| Utc::now() - Duration::days(90));
+    let outcomes = state.storage.fetch_prediction_outcomes(since).await?;
+    Ok(Json(calibration_report(&outcomes, query.bins.unwrap_or(10))))
+}
+
+/// GET /analytics/kelly?since=<rfc3339> (defaults to the last 90 days)
+///
+/// Stakes of closed trades against the full-Kelly stake at realized
+/// probabilities, and whether each strategy over- or under-bets its policy.
+pub(crate) async fn kelly(
+    State(state): State<ApiState>,
+    Query(query): Query<ExcursionQuery>,
+) -> Result<Json<KellyReport>, ApiError> {
+    let since = query.since.unwrap_or_else(|| Utc::now() - Duration::days(90));
+    let outcomes = state.storage.fetch_prediction_outcomes(since).await?;
+    let sizing = state.storage.fetch_trade_sizing(since).await?;
+    Ok(Json(kelly_realization(&outcomes, &sizing, state.risk_manager.kelly_fraction())))
+}
+
+/// GET /analytics/returns?since=<rfc3339> (defaults to the last 90 days)
+///
+/// Time- and money-weighted returns over the portfolio snapshots, alongside
+/// raw PnL, with deposits and withdrawals taken out. `null` until there are
+/// two snapshots in the period.
+pub(crate) async fn returns(
+    State(state): State<ApiState>,
+    Query(query): Query<ExcursionQuery>,
+) -> Result<Json<Option<ReturnsReport>>, ApiError> {
+    let since = query.since.unwrap_or_else(|| Utc::now() - Duration::days(90));
+    let points = state.storage.fetch_capital_history(since).await?;
+    let flows = state.storage.fetch_cash_flows(since).await?;
+    Ok(Json(returns_report(&points, &flows)))
+}
+
+/// GET /analytics/coverage?since=<rfc3339> (defaults to the last hour)
+///
+/// Per sport, the markets Polymarket lists against those that parsed, are
+/// tracked, had bookmaker odds matched and were evaluated by a strategy
+/// since `since`.
+pub(crate) async fn coverage(
+    State(state): State<ApiState>,
+    Query(query): Query<ExcursionQuery>,
+) -> Result<Json<Vec<SportCoverage>>, ApiError> {
+    let since = query.since.unwrap_or_else(|| Utc::now() - Duration::hours(1));
+    let listings = state.storage.fetch_market_listings().await?;
+    let markets = state.storage.fetch_covered_markets(since).await?;
+    Ok(Json(coverage_report(&listings, &markets)))
+}
+
+/// GET /analytics/dead-letters?since=<rfc3339> (defaults to the last 24 hours)
+///
+/// Listings, odds and stored rows that failed to map, with the reason and
+/// the latest raw row, most recently seen first.
+pub(crate) async fn dead_letters(
+    State(state): State<ApiState>,
+    Query(query): Query<ExcursionQuery>,
+) -> Result<Json<Vec<DeadLetter>>, ApiError> {
+    let since = query.since.unwrap_or_else(|| Utc::now() - Duration::hours(24));
+    Ok(Json(state.storage.fetch_dead_letters(since).await?))
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct HomeAdvantageQuery {
+    sport: Option<String>,
+}
+
+/// GET /analytics/home-advantage?sport=<NFL|NBA|MLB|Premier League> (defaults to every sport)
+///
+/// Each venue's latest home edge: the league's, its own results' raw edge,
+/// the structural estimate and the shrunk estimate the models use.
+pub(crate) async fn home_advantage(
+    State(state): State<ApiState>,
+    Query(query): Query<HomeAdvantageQuery>,
+) -> Result<Json<Vec<HomeAdvantageEstimate>>, ApiError> {
+    let sport = match query.sport {
+        Some(sport) => Some(Sport::parse(&sport).ok_or_else(|| ApiError::BadRequest(format!("unknown sport {}", sport)))?),
+        None => None,
+    };
+    Ok(Json(state.storage.fetch_home_advantage(sport).await?))
+}
+
+/// GET /analytics/feed-latency?since=<rfc3339> (defaults to the last 24 hours)
+///
+/// Per odds provider batch, the delay between bookmakers updating lines and
+/// us receiving them.
+pub(crate) async fn feed_latency(
+    State(state): State<ApiState>,
+    Query(query): Query<ExcursionQuery>,
+) -> Result<Json<Vec<FeedLatency>>, ApiError> {
+    let since = query.since.unwrap_or_else(|| Utc::now() - Duration::hours(24));
+    Ok(Json(state.storage.fetch_feed_latency(since).await?))
+}
+
+/// GET /analytics/large-trades?since=<rfc3339> (defaults to the last 24 hours)
+///
+/// Unusually large taker trades on tracked markets, newest first.
+pub(crate) async fn large_trades(
+    State(state): State<ApiState>,
+    Query(query): Query<ExcursionQuery>,
+) -> Result<Json<Vec<LargeTrade>>, ApiError> {
+    let since = query.since.unwrap_or_else(|| Utc::now() - Duration::hours(24));
+    Ok(Json(state.storage.fetch_large_trades(since, None).await?))
+}
+
+/// GET /analytics/consistency
+///
+/// Arbitrage candidates and outlying markets among each upcoming game's
+/// moneyline, spreads and totals.
+pub(crate) async fn consistency(State(state): State<ApiState>) -> Result<Json<Vec<Inconsistency>>, ApiError> {
+    Ok(Json(scan_consistency(state.storage.as_ref(), &state.consistency).await?))
+}
+
+/// GET /analytics/exposure
+///
+/// Open exposure by sport, event, strategy and time to event, with
+/// utilization of the concentration limits.
+pub(crate) async fn exposure(State(state): State<ApiState>) -> Result<Json<ExposureReport>, ApiError> {
+    let total_capital = state.risk_manager.get_portfolio_state().await.total_capital;
+    let report = current_exposure(state.storage.as_ref(), total_capital, &state.exposure_limits).await?;
+    Ok(Json(report))
+}
+
+/// GET /analytics/execution-shortfall?since=<rfc3339> (defaults to the last 30 days)
+///
+/// Per strategy, what live execution cost against a paper run fed the same
+/// signals, split into entry price, exit price and fees and gas.
+pub(crate) async fn execution_shortfall(
+    State(state): State<ApiState>,
+    Query(query): Query<ExcursionQuery>,
+) -> Result<Json<ShortfallReport>, ApiError> {
+    let paper_storage = state.paper_storage.as_ref()
+        .ok_or_else(|| ApiError::BadRequest("api.paper_comparison.database_url is not set".to_string()))?;
+    let since = query.since.unwrap_or_else(|| Utc::now() - Duration::days(30));
+    let live = state.storage.fetch_closed_trades(since).await?;
+    let paper = paper_storage.fetch_closed_trades(since).await?;
+    Ok(Json(shortfall_report(&live, &paper, state.paper_match_window)))
+}
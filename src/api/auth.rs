@@ -0,0 +1,99 @@
+use anyhow::{bail, Result};
+use axum::extract::{MatchedPath, Request, State};
+use axum::http::Method;
+use axum::middleware::Next;
+use axum::response::Response;
+use std::collections::HashMap;
+use tracing::info;
+
+use crate::config::{ApiKeyConfig, ApiRole};
+use super::error::ApiError;
+use super::server::ApiState;
+
+/// API keys and the role each grants; empty leaves the API open
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ApiKeys {
+    keys: HashMap<String, (String, ApiRole)>,
+}
+
+impl ApiKeys {
+    pub fn from_config(keys: &[ApiKeyConfig]) -> Result<Self> {
+        let mut by_key = HashMap::new();
+        for key in keys {
+            if key.key.len() < 16 {
+                bail!("api.keys entry {} needs a key of at least 16 characters", key.name);
+            }
+            if by_key.insert(key.key.clone(), (key.name.clone(), key.role)).is_some() {
+                bail!("api.keys entry {} reuses another entry's key", key.name);
+            }
+        }
+        Ok(Self { keys: by_key })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+/// Least role allowed to call a route
+///
+/// Reads, including what-if scenarios, need a viewer, except credentials. Recording manual
+/// trades, settling reviews and reinstating strategies need an operator.
+/// Moving money, closing positions, credentials and any route not listed
+/// need an admin.
+pub(crate) fn required_role(method: &Method, route: &str) -> ApiRole {
+    match (method, route) {
+        (&Method::GET, "/credentials") => ApiRole::Admin,
+        (&Method::GET, _) | (&Method::POST, "/scenarios") => ApiRole::Viewer,
+        (&Method::POST, "/trades")
+        | (&Method::POST, "/settlements/reviews/:trade_id")
+        | (&Method::DELETE, "/strategies/:strategy/shadow") => ApiRole::Operator,
+        _ => ApiRole::Admin,
+    }
+}
+
+/// Reject requests without a key whose role covers the route
+pub(crate) async fn authorize(State(state): State<ApiState>, request: Request, next: Next) -> Result<Response, ApiError> {
+    if state.keys.is_empty() {
+        return Ok(next.run(request).await);
+    }
+
+    let (name, role) = request.headers()
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .and_then(|key| state.keys.keys.get(key))
+        .ok_or(ApiError::Unauthorized)?;
+
+    let route = request.extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| request.uri().path().to_string(), |path| path.as_str().to_string());
+    let required = required_role(request.method(), &route);
+    if *role < required {
+        return Err(ApiError::Forbidden(format!("{} requires the {} role", route, required.as_str())));
+    }
+
+    if request.method() != Method::GET {
+        info!("🔑 {} ({}) {} {}", name, role.as_str(), request.method(), request.uri().path());
+    }
+    Ok(next.run(request).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn viewers_cannot_move_money_or_close_positions() {
+        assert_eq!(required_role(&Method::GET, "/trades"), ApiRole::Viewer);
+        assert_eq!(required_role(&Method::POST, "/scenarios"), ApiRole::Viewer);
+        assert_eq!(required_role(&Method::POST, "/trades"), ApiRole::Operator);
+        assert_eq!(required_role(&Method::POST, "/trades/:trade_id/close"), ApiRole::Admin);
+        assert_eq!(required_role(&Method::POST, "/cash-flows"), ApiRole::Admin);
+        assert_eq!(required_role(&Method::GET, "/credentials"), ApiRole::Admin);
+        assert!(ApiRole::Operator < ApiRole::Admin && ApiRole::Viewer < ApiRole::Operator);
+
+        let short = ApiKeyConfig { name: "phone".to_string(), key: "1234".to_string(), role: ApiRole::Viewer };
+        assert!(ApiKeys::from_config(&[short]).is_err());
+    }
+}
@@ -0,0 +1,73 @@
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::types::{CashFlow, CashFlowKind, CashFlowSource, Usdc, POLYMARKET_VENUE, USDC_DECIMALS};
+use super::error::ApiError;
+use super::server::ApiState;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CashFlowQuery {
+    since: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct NewCashFlow {
+    kind: CashFlowKind,
+    amount: Decimal,
+    tx_hash: Option<String>,
+    note: Option<String>,
+    /// Defaults to now
+    occurred_at: Option<DateTime<Utc>>,
+    /// Defaults to Polymarket
+    venue: Option<String>,
+}
+
+/// GET /cash-flows?since=<rfc3339> (defaults to the last 30 days)
+pub(crate) async fn list_cash_flows(
+    State(state): State<ApiState>,
+    Query(query): Query<CashFlowQuery>,
+) -> Result<Json<Vec<CashFlow>>, ApiError> {
+    let since = query.since.unwrap_or_else(|| Utc::now() - Duration::days(30));
+    Ok(Json(state.storage.fetch_cash_flows(since).await?))
+}
+
+/// POST /cash-flows
+pub(crate) async fn record_cash_flow(
+    State(state): State<ApiState>,
+    Json(body): Json<NewCashFlow>,
+) -> Result<(StatusCode, Json<CashFlow>), ApiError> {
+    if body.amount <= Decimal::ZERO {
+        return Err(ApiError::BadRequest("amount must be positive".to_string()));
+    }
+    let amount = Usdc::new(body.amount);
+    if !amount.is_exact() {
+        return Err(ApiError::BadRequest(format!("amount has more than USDC's {} decimal places", USDC_DECIMALS)));
+    }
+
+    if let Some(tx_hash) = &body.tx_hash {
+        if state.storage.cash_flow_exists(tx_hash).await? {
+            return Err(ApiError::BadRequest(format!("{} already recorded", tx_hash)));
+        }
+    }
+
+    let flow = CashFlow {
+        flow_id: Uuid::new_v4(),
+        kind: body.kind,
+        amount,
+        source: CashFlowSource::Manual,
+        tx_hash: body.tx_hash,
+        note: body.note,
+        occurred_at: body.occurred_at.unwrap_or_else(Utc::now),
+        venue: body.venue.unwrap_or_else(|| POLYMARKET_VENUE.to_string()),
+    };
+    state.storage.insert_cash_flow(&flow).await?;
+
+    info!("💵 Recorded manual {} of ${} on {}", flow.kind.as_str(), flow.amount, flow.venue);
+    Ok((StatusCode::CREATED, Json(flow)))
+}
@@ -0,0 +1,37 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::credentials::{CredentialInfo, CredentialVault, MASTER_KEY_ENV};
+use super::error::ApiError;
+use super::server::ApiState;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct NewCredential {
+    value: String,
+}
+
+fn vault(state: &ApiState) -> Result<&Arc<CredentialVault>, ApiError> {
+    state.vault.as_ref()
+        .ok_or_else(|| ApiError::BadRequest(format!("{} is not set; credentials cannot be stored", MASTER_KEY_ENV)))
+}
+
+/// GET /credentials (names and versions only, never values)
+pub(crate) async fn list_credentials(State(state): State<ApiState>) -> Result<Json<Vec<CredentialInfo>>, ApiError> {
+    Ok(Json(vault(&state)?.list().await?))
+}
+
+/// PUT /credentials/:provider/:name; takes effect on the next restart
+pub(crate) async fn store_credential(
+    State(state): State<ApiState>,
+    Path((provider, name)): Path<(String, String)>,
+    Json(body): Json<NewCredential>,
+) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
+    if body.value.is_empty() {
+        return Err(ApiError::BadRequest("value must not be empty".to_string()));
+    }
+    let version = vault(&state)?.store(&provider, &name, &body.value).await?;
+    Ok((StatusCode::CREATED, Json(serde_json::json!({ "provider": provider, "name": name, "version": version }))))
+}
@@ -0,0 +1,22 @@
+use axum::extract::{Query, State};
+use axum::Json;
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+
+use crate::types::Decision;
+use super::error::ApiError;
+use super::server::ApiState;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct DecisionQuery {
+    since: Option<DateTime<Utc>>,
+}
+
+/// GET /decisions?since=<rfc3339> (defaults to the last 7 days), most recent first
+pub(crate) async fn list_decisions(
+    State(state): State<ApiState>,
+    Query(query): Query<DecisionQuery>,
+) -> Result<Json<Vec<Decision>>, ApiError> {
+    let since = query.since.unwrap_or_else(|| Utc::now() - Duration::days(7));
+    Ok(Json(state.storage.fetch_decisions(since).await?))
+}
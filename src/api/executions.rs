@@ -0,0 +1,29 @@
+use axum::extract::{Query, State};
+use axum::Json;
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+
+use crate::types::{ExecutionFailureStatus, FailedExecution};
+use super::error::ApiError;
+use super::server::ApiState;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct FailedExecutionQuery {
+    since: Option<DateTime<Utc>>,
+    status: Option<ExecutionFailureStatus>,
+}
+
+/// GET /executions/failed?since=<rfc3339>&status=<retrying|failed|recovered>
+/// (defaults to the last 7 days, any status) — signals whose submission
+/// failed, most recently updated first
+pub(crate) async fn list_failed(
+    State(state): State<ApiState>,
+    Query(query): Query<FailedExecutionQuery>,
+) -> Result<Json<Vec<FailedExecution>>, ApiError> {
+    let since = query.since.unwrap_or_else(|| Utc::now() - Duration::days(7));
+    let failures = state.storage.fetch_failed_executions(since).await?
+        .into_iter()
+        .filter(|f| query.status.map_or(true, |status| f.status == status))
+        .collect();
+    Ok(Json(failures))
+}
@@ -0,0 +1,223 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use futures::stream::{self, select_all, BoxStream, StreamExt};
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use tonic::{Request, Response, Status};
+use tracing::{info, warn};
+
+use crate::cache::Cache;
+use crate::config::{GrpcClientConfig, GrpcConfig};
+use crate::events::{FILLS_CHANNEL, ORDERS_CHANNEL, SIGNALS_CHANNEL};
+use crate::storage::Storage;
+
+pub mod proto {
+    tonic::include_proto!("tradingbot.events.v1");
+}
+
+use proto::event_stream_server::{EventStream, EventStreamServer};
+use proto::{Event, SubscribeRequest, Topic};
+
+const ALL_TOPICS: [Topic; 4] = [Topic::Signals, Topic::Orders, Topic::Fills, Topic::Portfolio];
+
+fn parse_topic(name: &str) -> Option<Topic> {
+    match name {
+        "signals" => Some(Topic::Signals),
+        "orders" => Some(Topic::Orders),
+        "fills" => Some(Topic::Fills),
+        "portfolio" => Some(Topic::Portfolio),
+        _ => None,
+    }
+}
+
+/// A configured client, keyed by its token
+#[derive(Debug, Clone)]
+struct Client {
+    name: String,
+    topics: HashSet<Topic>,
+}
+
+impl Client {
+    fn from_config(config: &GrpcClientConfig) -> Result<Self> {
+        if config.token.len() < 16 {
+            bail!("api.grpc client {} needs a token of at least 16 characters", config.name);
+        }
+        let topics = if config.topics.is_empty() {
+            ALL_TOPICS.into_iter().collect()
+        } else {
+            config.topics.iter()
+                .map(|t| parse_topic(t).with_context(|| format!("api.grpc client {}: unknown topic {}", config.name, t)))
+                .collect::<Result<_>>()?
+        };
+        Ok(Self { name: config.name.clone(), topics })
+    }
+
+    /// Requested topics, or every permitted one when none are requested
+    fn subscription(&self, requested: &[i32]) -> Result<Vec<Topic>, Status> {
+        if requested.is_empty() {
+            let mut topics: Vec<Topic> = self.topics.iter().copied().collect();
+            topics.sort();
+            return Ok(topics);
+        }
+
+        let mut topics = Vec::new();
+        for &value in requested {
+            let topic = Topic::try_from(value)
+                .ok()
+                .filter(|t| *t != Topic::Unspecified)
+                .ok_or_else(|| Status::invalid_argument(format!("unknown topic {}", value)))?;
+            if !self.topics.contains(&topic) {
+                return Err(Status::permission_denied(format!("{} may not subscribe to {:?}", self.name, topic)));
+            }
+            if !topics.contains(&topic) {
+                topics.push(topic);
+            }
+        }
+        Ok(topics)
+    }
+}
+
+type EventResult = Result<Event, Status>;
+
+#[derive(Clone)]
+struct EventService {
+    storage: Arc<dyn Storage>,
+    cache: Arc<dyn Cache>,
+    clients: Arc<HashMap<String, Client>>,
+    portfolio_poll: Duration,
+}
+
+impl EventService {
+    fn authenticate<T>(&self, request: &Request<T>) -> Result<&Client, Status> {
+        let token = request.metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| Status::unauthenticated("missing bearer token"))?;
+        self.clients.get(token).ok_or_else(|| Status::unauthenticated("unknown token"))
+    }
+
+    async fn topic_stream(&self, topic: Topic) -> Result<BoxStream<'static, EventResult>, Status> {
+        let channel = match topic {
+            Topic::Signals => SIGNALS_CHANNEL,
+            Topic::Orders => ORDERS_CHANNEL,
+            Topic::Fills => FILLS_CHANNEL,
+            Topic::Portfolio => return Ok(self.portfolio_stream()),
+            Topic::Unspecified => return Err(Status::invalid_argument("unspecified topic")),
+        };
+
+        let messages = self.cache.subscribe(channel).await
+            .map_err(|e| Status::unavailable(format!("subscribing to {}: {}", channel, e)))?;
+        Ok(messages.map(move |payload| Ok(event(topic, payload, Utc::now()))).boxed())
+    }
+
+    /// Stored portfolio snapshots, each sent once as it appears
+    fn portfolio_stream(&self) -> BoxStream<'static, EventResult> {
+        let storage = self.storage.clone();
+        let tick = interval(self.portfolio_poll);
+        stream::unfold((tick, None::<DateTime<Utc>>), move |(mut tick, last)| {
+            let storage = storage.clone();
+            async move {
+                loop {
+                    tick.tick().await;
+                    match storage.latest_portfolio_snapshot().await {
+                        Ok(Some(state)) if Some(state.timestamp) != last => {
+                            let item = serde_json::to_string(&state)
+                                .map(|payload| event(Topic::Portfolio, payload, state.timestamp))
+                                .map_err(|e| Status::internal(e.to_string()));
+                            return Some((item, (tick, Some(state.timestamp))));
+                        }
+                        Ok(_) => {}
+                        Err(e) => warn!("Failed to load portfolio snapshot for gRPC clients: {}", e),
+                    }
+                }
+            }
+        })
+        .boxed()
+    }
+}
+
+fn event(topic: Topic, payload_json: String, at: DateTime<Utc>) -> Event {
+    Event {
+        topic: topic as i32,
+        payload_json,
+        published_at_ms: at.timestamp_millis(),
+    }
+}
+
+#[tonic::async_trait]
+impl EventStream for EventService {
+    type SubscribeStream = BoxStream<'static, EventResult>;
+
+    async fn subscribe(&self, request: Request<SubscribeRequest>) -> Result<Response<Self::SubscribeStream>, Status> {
+        let client = self.authenticate(&request)?;
+        let topics = client.subscription(&request.get_ref().topics)?;
+        info!("📡 gRPC client {} subscribed to {:?}", client.name, topics);
+
+        let mut streams = Vec::with_capacity(topics.len());
+        for topic in topics {
+            streams.push(self.topic_stream(topic).await?);
+        }
+        Ok(Response::new(select_all(streams).boxed()))
+    }
+}
+
+/// gRPC event stream for external clients; see `proto/events.proto`
+pub struct GrpcApi {
+    service: EventService,
+    bind_address: SocketAddr,
+}
+
+impl GrpcApi {
+    pub fn new(storage: Arc<dyn Storage>, cache: Arc<dyn Cache>, config: &GrpcConfig) -> Result<Self> {
+        let mut clients = HashMap::new();
+        for client in &config.clients {
+            if clients.insert(client.token.clone(), Client::from_config(client)?).is_some() {
+                bail!("api.grpc client {} reuses another client's token", client.name);
+            }
+        }
+
+        Ok(Self {
+            service: EventService {
+                storage,
+                cache,
+                clients: Arc::new(clients),
+                portfolio_poll: Duration::from_secs(config.portfolio_poll_secs.max(1)),
+            },
+            bind_address: config.bind_address.parse().context("api.grpc.bind_address")?,
+        })
+    }
+
+    pub async fn run(self) -> Result<()> {
+        info!("📡 gRPC event stream listening on {}", self.bind_address);
+        tonic::transport::Server::builder()
+            .add_service(EventStreamServer::new(self.service))
+            .serve(self.bind_address)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_subscriptions_to_permitted_topics() {
+        let client = Client::from_config(&GrpcClientConfig {
+            name: "notebook".to_string(),
+            token: "0123456789abcdef".to_string(),
+            topics: vec!["signals".to_string(), "fills".to_string()],
+        }).unwrap();
+
+        assert_eq!(client.subscription(&[]).unwrap(), vec![Topic::Signals, Topic::Fills]);
+        assert_eq!(client.subscription(&[Topic::Fills as i32, Topic::Fills as i32]).unwrap(), vec![Topic::Fills]);
+        assert_eq!(client.subscription(&[Topic::Orders as i32]).unwrap_err().code(), tonic::Code::PermissionDenied);
+        assert_eq!(client.subscription(&[42]).unwrap_err().code(), tonic::Code::InvalidArgument);
+
+        let weak = GrpcClientConfig { name: "weak".to_string(), token: "short".to_string(), topics: vec![] };
+        assert!(Client::from_config(&weak).is_err());
+    }
+}
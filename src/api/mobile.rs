@@ -0,0 +1,338 @@
+use anyhow::{bail, Result};
+use axum::extract::State;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{Html, IntoResponse, Redirect, Response};
+use axum::Form;
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tracing::{info, warn};
+
+use crate::config::MobileConfig;
+use crate::types::{PortfolioState, Position, Trade};
+use super::error::ApiError;
+use super::server::ApiState;
+
+const SESSION_COOKIE: &str = "mobile_session";
+
+/// Seconds between reloads of the page
+const REFRESH_SECS: u32 = 60;
+
+/// Outcome of a PIN entered at the login form
+#[derive(Debug, PartialEq)]
+pub(crate) enum Login {
+    /// Session token for the cookie
+    Granted(String),
+    Denied,
+    LockedOut(DateTime<Utc>),
+}
+
+#[derive(Debug, Default)]
+struct Failures {
+    /// Wrong PINs since the last success or lockout
+    count: u32,
+    locked_until: Option<DateTime<Utc>>,
+}
+
+/// PIN check and the sessions it has opened for the mobile view
+///
+/// Sessions live in memory, so a restart asks for the PIN again. Wrong PINs
+/// lock every login out for a while, as a short PIN is quickly guessed.
+pub(crate) struct MobileAccess {
+    pin: String,
+    session_ttl: Duration,
+    max_failed_attempts: u32,
+    lockout: Duration,
+    /// Session token to its expiry
+    sessions: Mutex<HashMap<String, DateTime<Utc>>>,
+    failures: Mutex<Failures>,
+}
+
+impl MobileAccess {
+    /// `None` when no PIN is configured
+    pub fn from_config(config: &MobileConfig) -> Result<Option<Self>> {
+        let Some(pin) = &config.pin else {
+            return Ok(None);
+        };
+        if pin.len() < 6 {
+            bail!("api.mobile.pin needs at least 6 characters");
+        }
+        if config.max_failed_attempts == 0 {
+            bail!("api.mobile.max_failed_attempts must be at least 1");
+        }
+
+        Ok(Some(Self {
+            pin: pin.clone(),
+            session_ttl: Duration::hours(config.session_hours as i64),
+            max_failed_attempts: config.max_failed_attempts,
+            lockout: Duration::minutes(config.lockout_mins as i64),
+            sessions: Mutex::new(HashMap::new()),
+            failures: Mutex::new(Failures::default()),
+        }))
+    }
+
+    pub fn login(&self, pin: &str, now: DateTime<Utc>) -> Login {
+        let mut failures = self.failures.lock().unwrap();
+        if let Some(until) = failures.locked_until.filter(|until| now < *until) {
+            return Login::LockedOut(until);
+        }
+
+        if !constant_time_eq(pin.as_bytes(), self.pin.as_bytes()) {
+            failures.count += 1;
+            if failures.count >= self.max_failed_attempts {
+                failures.count = 0;
+                failures.locked_until = Some(now + self.lockout);
+                return Login::LockedOut(now + self.lockout);
+            }
+            return Login::Denied;
+        }
+
+        *failures = Failures::default();
+        let token = format!("{:032x}", rand::random::<u128>());
+        let mut sessions = self.sessions.lock().unwrap();
+        sessions.retain(|_, expires| now < *expires);
+        sessions.insert(token.clone(), now + self.session_ttl);
+        Login::Granted(token)
+    }
+
+    pub fn is_valid(&self, token: &str, now: DateTime<Utc>) -> bool {
+        self.sessions.lock().unwrap().get(token).map_or(false, |expires| now < *expires)
+    }
+
+    fn session_secs(&self) -> i64 {
+        self.session_ttl.num_seconds()
+    }
+}
+
+/// Compares without returning early, so timing does not give away how much
+/// of a guess was right
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+fn session_token(headers: &HeaderMap) -> Option<&str> {
+    headers.get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|cookies| cookies.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| *name == SESSION_COOKIE)
+        .map(|(_, token)| token)
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct PinForm {
+    pin: String,
+}
+
+/// An open position as shown on the page, marked at the bid
+pub(crate) struct PositionRow {
+    pub event: String,
+    pub trade: Trade,
+    pub mark: Option<Decimal>,
+}
+
+/// GET /m — portfolio, open positions and today's PnL, or the PIN form
+pub(crate) async fn view(State(state): State<ApiState>, headers: HeaderMap) -> Result<Response, ApiError> {
+    let Some(access) = &state.mobile else {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    };
+    if !session_token(&headers).map_or(false, |token| access.is_valid(token, Utc::now())) {
+        return Ok((StatusCode::UNAUTHORIZED, Html(login_page(None))).into_response());
+    }
+
+    let portfolio = state.risk_manager.get_portfolio_state().await;
+    let mut rows = Vec::new();
+    for trade in state.storage.fetch_open_trades().await? {
+        let market = state.storage.fetch_market(&trade.market_id).await?;
+        rows.push(PositionRow {
+            event: market.as_ref().map_or_else(|| trade.market_id.clone(), |m| m.event_name.clone()),
+            mark: market.map(|m| match trade.position {
+                Position::Yes => m.yes_quote.bid.value(),
+                Position::No => m.no_quote.bid.value(),
+            }),
+            trade,
+        });
+    }
+
+    Ok(Html(dashboard_page(&portfolio, &rows)).into_response())
+}
+
+/// POST /m — check the PIN and start a session
+pub(crate) async fn login(State(state): State<ApiState>, Form(form): Form<PinForm>) -> Response {
+    let Some(access) = &state.mobile else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    match access.login(&form.pin, Utc::now()) {
+        Login::Granted(token) => {
+            info!("📱 Mobile view session started");
+            let cookie = format!(
+                "{}={}; Path=/m; Max-Age={}; HttpOnly; Secure; SameSite=Strict",
+                SESSION_COOKIE, token, access.session_secs()
+            );
+            ([(header::SET_COOKIE, cookie)], Redirect::to("/m")).into_response()
+        }
+        Login::Denied => (StatusCode::UNAUTHORIZED, Html(login_page(Some("Wrong PIN")))).into_response(),
+        Login::LockedOut(until) => {
+            warn!("📱 Mobile view logins locked until {} after repeated wrong PINs", until);
+            let message = format!("Too many wrong PINs; try again after {} UTC", until.format("%H:%M"));
+            (StatusCode::TOO_MANY_REQUESTS, Html(login_page(Some(&message)))).into_response()
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn usd(amount: Decimal) -> String {
+    let amount = amount.round_dp(2);
+    if amount < Decimal::ZERO {
+        format!("-${:.2}", -amount)
+    } else {
+        format!("${:.2}", amount)
+    }
+}
+
+fn signed_class(amount: Decimal) -> &'static str {
+    if amount < Decimal::ZERO { "down" } else { "up" }
+}
+
+fn page(title: &str, refresh: bool, body: &str) -> String {
+    let refresh = if refresh {
+        format!(r#"<meta http-equiv="refresh" content="{}">"#, REFRESH_SECS)
+    } else {
+        String::new()
+    };
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<meta name="robots" content="noindex">
+{refresh}
+<title>{title}</title>
+<style>
+body {{ font-family: -apple-system, system-ui, sans-serif; margin: 0; padding: 12px; background: #111; color: #eee; }}
+h1 {{ font-size: 1.1em; margin: 0 0 8px; }}
+h2 {{ font-size: 1em; margin: 16px 0 6px; color: #aaa; }}
+.grid {{ display: grid; grid-template-columns: 1fr 1fr; gap: 6px; }}
+.card {{ background: #1c1c1c; border-radius: 8px; padding: 8px; }}
+.label {{ font-size: 0.75em; color: #999; }}
+.value {{ font-size: 1.15em; }}
+.up {{ color: #4caf50; }}
+.down {{ color: #f44336; }}
+.muted {{ color: #777; font-size: 0.8em; }}
+input {{ font-size: 1.4em; width: 100%; box-sizing: border-box; padding: 8px; margin: 8px 0; }}
+button {{ font-size: 1.1em; width: 100%; padding: 10px; }}
+</style>
+</head>
+<body>
+{body}
+</body>
+</html>"#,
+    )
+}
+
+pub(crate) fn login_page(message: Option<&str>) -> String {
+    let message = message.map_or_else(String::new, |m| format!(r#"<p class="down">{}</p>"#, escape(m)));
+    page(
+        "Trading bot",
+        false,
+        &format!(
+            r#"<h1>Trading bot</h1>{}
+<form method="post" action="/m">
+<input name="pin" type="password" inputmode="numeric" autocomplete="current-password" placeholder="PIN" autofocus>
+<button type="submit">Unlock</button>
+</form>"#,
+            message
+        ),
+    )
+}
+
+pub(crate) fn dashboard_page(portfolio: &PortfolioState, positions: &[PositionRow]) -> String {
+    let card = |label: &str, value: String, class: &str| {
+        format!(r#"<div class="card"><div class="label">{}</div><div class="value {}">{}</div></div>"#, label, class, value)
+    };
+    let today = portfolio.realized_pnl_today;
+
+    let mut body = String::from("<h1>Trading bot</h1>");
+    body.push_str(r#"<div class="grid">"#);
+    body.push_str(&card("Today's PnL", usd(today), signed_class(today)));
+    body.push_str(&card("Unrealized", usd(portfolio.unrealized_pnl), signed_class(portfolio.unrealized_pnl)));
+    body.push_str(&card("Capital", usd(portfolio.total_capital), ""));
+    body.push_str(&card("Available", usd(portfolio.available_capital), ""));
+    body.push_str(&card("Invested", usd(portfolio.invested_capital), ""));
+    body.push_str(&card("Trades today", portfolio.trades_today.to_string(), ""));
+    body.push_str("</div>");
+
+    body.push_str(&format!("<h2>Open positions ({})</h2>", positions.len()));
+    if positions.is_empty() {
+        body.push_str(r#"<p class="muted">None</p>"#);
+    }
+    for row in positions {
+        let trade = &row.trade;
+        let (mark, pnl) = match row.mark {
+            Some(mark) => {
+                let pnl = trade.unrealized_pnl(mark);
+                (mark.round_dp(3).to_string(), format!(r#"<span class="{}">{}</span>"#, signed_class(pnl), usd(pnl)))
+            }
+            None => ("?".to_string(), "?".to_string()),
+        };
+        body.push_str(&format!(
+            r#"<div class="card" style="margin-bottom:6px"><div>{}</div><div class="muted">{} · {} {} @ {} · bid {}</div><div>{} · {}</div></div>"#,
+            escape(&row.event),
+            escape(trade.strategy.as_str()),
+            trade.quantity.round_dp(2),
+            trade.position.as_str(),
+            trade.entry_price.round_dp(3),
+            mark,
+            usd(trade.position_size_usd()),
+            pnl,
+        ));
+    }
+
+    body.push_str(&format!(
+        r#"<p class="muted">Portfolio as of {} UTC · read-only · refreshes every {}s</p>"#,
+        portfolio.timestamp.format("%H:%M:%S"),
+        REFRESH_SECS
+    ));
+    page("Trading bot", true, &body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrong_pins_lock_logins_out() {
+        let config = MobileConfig { pin: Some("482913".to_string()), max_failed_attempts: 3, ..MobileConfig::default() };
+        let access = MobileAccess::from_config(&config).unwrap().unwrap();
+        let now = Utc::now();
+
+        let Login::Granted(token) = access.login("482913", now) else { panic!("right PIN refused") };
+        assert!(access.is_valid(&token, now));
+        assert!(!access.is_valid(&token, now + Duration::hours(13)));
+        assert!(!access.is_valid("forged", now));
+
+        assert_eq!(access.login("000000", now), Login::Denied);
+        assert_eq!(access.login("000001", now), Login::Denied);
+        let locked = now + Duration::minutes(15);
+        assert_eq!(access.login("000002", now), Login::LockedOut(locked));
+        // Even the right PIN waits out the lockout
+        assert_eq!(access.login("482913", now + Duration::minutes(1)), Login::LockedOut(locked));
+        assert!(matches!(access.login("482913", locked), Login::Granted(_)));
+
+        let short = MobileConfig { pin: Some("1234".to_string()), ..MobileConfig::default() };
+        assert!(MobileAccess::from_config(&short).is_err());
+        assert!(login_page(Some("<script>")).contains("&lt;script&gt;"));
+    }
+}
@@ -0,0 +1,17 @@
+mod analytics;
+mod auth;
+mod cash_flows;
+mod credentials;
+mod decisions;
+mod error;
+mod executions;
+mod grpc;
+mod mobile;
+mod scenarios;
+mod server;
+mod settlements;
+mod strategies;
+mod trades;
+
+pub use grpc::GrpcApi;
+pub use server::AdminApi;
@@ -0,0 +1,16 @@
+use axum::extract::State;
+use axum::Json;
+
+use crate::risk::{ScenarioReport, ScenarioRequest};
+use super::error::ApiError;
+use super::server::ApiState;
+
+/// POST /scenarios
+///
+/// e.g. `{"scenario": {"kind": "favorites_win"}, "until": "2024-11-10T06:00:00Z"}`
+pub(crate) async fn run_scenario(
+    State(state): State<ApiState>,
+    Json(request): Json<ScenarioRequest>,
+) -> Result<Json<ScenarioReport>, ApiError> {
+    Ok(Json(state.scenarios.run(&request).await?))
+}
@@ -0,0 +1,130 @@
+use anyhow::Result;
+use axum::middleware;
+use axum::routing::{delete, get, post, put};
+use axum::Router;
+use chrono::Duration;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::credentials::CredentialVault;
+use crate::storage::Storage;
+use crate::risk::{ConsistencyThresholds, ExposureLimits, RiskManager, ScenarioAnalyzer};
+use super::auth::{self, ApiKeys};
+use super::mobile::{self, MobileAccess};
+use super::{analytics, cash_flows, credentials, decisions, executions, scenarios, settlements, strategies, trades};
+
+/// Shared state for admin API handlers
+#[derive(Clone)]
+pub(crate) struct ApiState {
+    pub storage: Arc<dyn Storage>,
+    pub risk_manager: RiskManager,
+    pub scenarios: Arc<ScenarioAnalyzer>,
+    pub exposure_limits: Arc<ExposureLimits>,
+    pub consistency: Arc<ConsistencyThresholds>,
+    pub keys: Arc<ApiKeys>,
+    /// Unset when the master key is not configured
+    pub vault: Option<Arc<CredentialVault>>,
+    /// The paper run compared against in the execution shortfall report
+    pub paper_storage: Option<Arc<dyn Storage>>,
+    pub paper_match_window: Duration,
+    /// Unset when no mobile PIN is configured
+    pub mobile: Option<Arc<MobileAccess>>,
+}
+
+/// Operator-facing HTTP API, with role-based API keys when `api.keys` is set
+///
+/// Without keys it is unauthenticated, so bind it to localhost. The
+/// read-only mobile view at `/m` sits outside the keys, behind its own PIN.
+pub struct AdminApi {
+    state: ApiState,
+    bind_address: String,
+}
+
+impl ApiState {
+    /// Over `storage` with no keys, vault, paper run or mobile view
+    #[cfg(test)]
+    pub fn detached(storage: Arc<dyn Storage>, risk_manager: RiskManager, config: &Config) -> Result<Self> {
+        Ok(AdminApi::new(storage, risk_manager, None, None, config)?.state)
+    }
+}
+
+impl AdminApi {
+    pub fn new(
+        storage: Arc<dyn Storage>,
+        risk_manager: RiskManager,
+        vault: Option<Arc<CredentialVault>>,
+        paper_storage: Option<Arc<dyn Storage>>,
+        config: &Config,
+    ) -> Result<Self> {
+        let keys = ApiKeys::from_config(&config.api.keys)?;
+        if keys.is_empty() {
+            warn!("Admin API has no api.keys configured; anyone who can reach {} has full access", config.api.bind_address);
+        }
+
+        Ok(Self {
+            state: ApiState {
+                scenarios: Arc::new(ScenarioAnalyzer::new(storage.clone())),
+                exposure_limits: Arc::new(ExposureLimits::from_config(&config.risk.exposure_limits)?),
+                consistency: Arc::new(ConsistencyThresholds::from_config(&config.risk.consistency)?),
+                keys: Arc::new(keys),
+                vault,
+                paper_storage,
+                paper_match_window: Duration::seconds(config.api.paper_comparison.match_window_secs),
+                mobile: MobileAccess::from_config(&config.api.mobile)?.map(Arc::new),
+                storage,
+                risk_manager,
+            },
+            bind_address: config.api.bind_address.clone(),
+        })
+    }
+
+    pub async fn run(self) -> Result<()> {
+        let mobile = Router::new().route("/m", get(mobile::view).post(mobile::login));
+
+        let app = Router::new()
+            .route("/analytics/calibration", get(analytics::calibration))
+            .route("/analytics/consistency", get(analytics::consistency))
+            .route("/analytics/convergence", get(analytics::clv_convergence))
+            .route("/analytics/coverage", get(analytics::coverage))
+            .route("/analytics/dead-letters", get(analytics::dead_letters))
+            .route("/analytics/execution-shortfall", get(analytics::execution_shortfall))
+            .route("/analytics/excursions", get(analytics::strategy_excursions))
+            .route("/analytics/exposure", get(analytics::exposure))
+            .route("/analytics/feed-latency", get(analytics::feed_latency))
+            .route("/analytics/home-advantage", get(analytics::home_advantage))
+            .route("/analytics/kelly", get(analytics::kelly))
+            .route("/analytics/large-trades", get(analytics::large_trades))
+            .route("/analytics/returns", get(analytics::returns))
+            .route(
+                "/cash-flows",
+                get(cash_flows::list_cash_flows).post(cash_flows::record_cash_flow),
+            )
+            .route("/credentials", get(credentials::list_credentials))
+            .route("/credentials/:provider/:name", put(credentials::store_credential))
+            .route("/decisions", get(decisions::list_decisions))
+            .route("/executions/failed", get(executions::list_failed))
+            .route("/scenarios", post(scenarios::run_scenario))
+            .route("/settlements", get(settlements::list_settlements))
+            .route("/settlements/reviews", get(settlements::list_reviews))
+            .route("/settlements/reviews/:trade_id", post(settlements::settle_reviewed))
+            .route("/strategies/shadowed", get(strategies::list_shadowed))
+            .route("/strategies/throttles", get(strategies::list_throttles))
+            .route("/strategies/weights", get(strategies::list_weights))
+            .route("/strategies/edge-decay", get(strategies::list_edge_decay))
+            .route("/strategies/:strategy/shadow", delete(strategies::reinstate))
+            .route("/trades", get(trades::list_open_trades).post(trades::record_manual_trade))
+            .route("/trades/:trade_id/close", post(trades::close_manual_trade))
+            .route("/trades/:trade_id/convergence", get(trades::trade_convergence))
+            .route_layer(middleware::from_fn_with_state(self.state.clone(), auth::authorize))
+            .merge(mobile)
+            .with_state(self.state);
+
+        let listener = TcpListener::bind(&self.bind_address).await?;
+        info!("🛠️ Admin API listening on {}", self.bind_address);
+
+        axum::serve(listener, app).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use axum::extract::{Path, Query, State};
+use axum::Json;
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::types::{Price, SettlementReview, Trade, TradeOutcome, TradeSettlement, TradeStatus, Usdc};
+use super::error::ApiError;
+use super::server::ApiState;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct SettleReviewedTrade {
+    /// Payout per share the reviewer confirmed (1 for a win, 0 for a loss)
+    payout: Decimal,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct SettlementQuery {
+    since: Option<DateTime<Utc>>,
+}
+
+/// GET /settlements?since=<rfc3339> (defaults to the last 7 days) — trades
+/// booked at resolution, won or lost, most recent first
+pub(crate) async fn list_settlements(
+    State(state): State<ApiState>,
+    Query(query): Query<SettlementQuery>,
+) -> Result<Json<Vec<TradeSettlement>>, ApiError> {
+    let since = query.since.unwrap_or_else(|| Utc::now() - Duration::days(7));
+    Ok(Json(state.storage.fetch_trade_settlements(since).await?))
+}
+
+/// GET /settlements/reviews — trades held back from settlement
+pub(crate) async fn list_reviews(
+    State(state): State<ApiState>,
+) -> Result<Json<Vec<SettlementReview>>, ApiError> {
+    Ok(Json(state.storage.fetch_open_settlement_reviews().await?))
+}
+
+/// POST /settlements/reviews/:trade_id — settle a held trade at the confirmed payout
+pub(crate) async fn settle_reviewed(
+    State(state): State<ApiState>,
+    Path(trade_id): Path<Uuid>,
+    Json(body): Json<SettleReviewedTrade>,
+) -> Result<Json<Trade>, ApiError> {
+    let payout = Price::new(body.payout)
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?
+        .value();
+
+    let held = state.storage.fetch_open_settlement_reviews().await?
+        .iter()
+        .any(|r| r.trade_id == trade_id);
+    if !held {
+        return Err(ApiError::BadRequest(format!("trade {} is not held for review", trade_id)));
+    }
+    let trade = state.storage.fetch_trade(trade_id).await?
+        .ok_or_else(|| ApiError::BadRequest(format!("unknown trade {}", trade_id)))?;
+    if trade.status != TradeStatus::Open {
+        return Err(ApiError::BadRequest(format!("trade {} is not open", trade_id)));
+    }
+
+    let now = Utc::now();
+    let pnl = trade.realized_pnl(payout, Usdc::ZERO);
+    state.storage.close_trade(trade_id, payout, now, pnl, "").await?;
+    state.storage.insert_trade_settlement(&TradeSettlement {
+        trade_id,
+        market_id: trade.market_id.clone(),
+        outcome: TradeOutcome::from_payout(payout),
+        payout,
+        pnl,
+        tx_hash: None,
+        settled_at: now,
+    }).await?;
+    state.storage.resolve_settlement_review(trade_id, now).await?;
+    state.risk_manager.update_portfolio(pnl).await?;
+
+    info!("⚖️ Settled reviewed trade {} at {} per share, PnL: {}", trade_id, payout, pnl);
+    Ok(Json(Trade {
+        exit_price: Some(payout),
+        exit_time: Some(now),
+        pnl: Some(pnl),
+        status: TradeStatus::Closed,
+        ..trade
+    }))
+}
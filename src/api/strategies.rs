@@ -0,0 +1,50 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use tracing::info;
+
+use crate::types::{EdgeDecay, ShadowedStrategy, Strategy, StrategyThrottle, StrategyWeight};
+use super::error::ApiError;
+use super::server::ApiState;
+
+/// GET /strategies/shadowed
+pub(crate) async fn list_shadowed(
+    State(state): State<ApiState>,
+) -> Result<Json<Vec<ShadowedStrategy>>, ApiError> {
+    Ok(Json(state.storage.fetch_shadowed_strategies().await?))
+}
+
+/// DELETE /strategies/:strategy/shadow - resume trading a shadowed strategy
+pub(crate) async fn reinstate(
+    State(state): State<ApiState>,
+    Path(strategy): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let strategy = Strategy::parse(&strategy)
+        .ok_or_else(|| ApiError::BadRequest(format!("unknown strategy {}", strategy)))?;
+
+    state.storage.unshadow_strategy(strategy).await?;
+    info!("👤 Strategy {} reinstated from shadow mode", strategy.as_str());
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /strategies/throttles - strategies trading at reduced size
+pub(crate) async fn list_throttles(
+    State(state): State<ApiState>,
+) -> Result<Json<Vec<StrategyThrottle>>, ApiError> {
+    Ok(Json(state.storage.fetch_strategy_throttles().await?))
+}
+
+/// GET /strategies/weights - Sharpe-optimal capital weights from the last solve
+pub(crate) async fn list_weights(
+    State(state): State<ApiState>,
+) -> Result<Json<Vec<StrategyWeight>>, ApiError> {
+    Ok(Json(state.storage.fetch_strategy_weights().await?))
+}
+
+/// GET /strategies/edge-decay - each strategy's fitted edge half-life
+pub(crate) async fn list_edge_decay(
+    State(state): State<ApiState>,
+) -> Result<Json<Vec<EdgeDecay>>, ApiError> {
+    Ok(Json(state.storage.fetch_edge_decays().await?))
+}
@@ -0,0 +1,232 @@
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::types::{
+    ConvergenceSample, ExecutionCost, ExecutionCostKind, Position, Price, Strategy, Trade, TradeOrigin, TradeStatus, Usdc, POLYMARKET_VENUE,
+};
+use super::error::ApiError;
+use super::server::ApiState;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct TradeQuery {
+    origin: Option<TradeOrigin>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct NewManualTrade {
+    market_id: String,
+    position: Position,
+    quantity: Decimal,
+    entry_price: Decimal,
+    /// Defaults to now
+    entry_time: Option<DateTime<Utc>>,
+    fees: Option<Usdc>,
+    tx_hash: Option<String>,
+    /// Defaults to Polymarket
+    venue: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CloseManualTrade {
+    exit_price: Decimal,
+    /// Fees paid on the exit
+    #[serde(default)]
+    exit_fees: Usdc,
+    exit_time: Option<DateTime<Utc>>,
+    tx_hash: Option<String>,
+}
+
+/// GET /trades?origin=bot|manual — open positions
+pub(crate) async fn list_open_trades(
+    State(state): State<ApiState>,
+    Query(query): Query<TradeQuery>,
+) -> Result<Json<Vec<Trade>>, ApiError> {
+    let trades = state.storage.fetch_open_trades().await?
+        .into_iter()
+        .filter(|t| query.origin.map_or(true, |origin| t.origin == origin))
+        .collect();
+
+    Ok(Json(trades))
+}
+
+/// POST /trades — record a position opened outside the bot
+pub(crate) async fn record_manual_trade(
+    State(state): State<ApiState>,
+    Json(body): Json<NewManualTrade>,
+) -> Result<(StatusCode, Json<Trade>), ApiError> {
+    if body.quantity <= Decimal::ZERO {
+        return Err(ApiError::BadRequest("quantity must be positive".to_string()));
+    }
+    let entry_price = Price::new(body.entry_price)
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    if state.storage.fetch_market(&body.market_id).await?.is_none() {
+        return Err(ApiError::BadRequest(format!("unknown market {}", body.market_id)));
+    }
+
+    let trade = Trade {
+        trade_id: Uuid::new_v4(),
+        market_id: body.market_id,
+        strategy: Strategy::Manual,
+        position: body.position,
+        quantity: body.quantity,
+        entry_price: entry_price.value(),
+        exit_price: None,
+        entry_time: body.entry_time.unwrap_or_else(Utc::now),
+        exit_time: None,
+        gas_cost: None,
+        fees: body.fees,
+        slippage: None,
+        pnl: None,
+        pnl_percent: None,
+        max_adverse_excursion: None,
+        max_favorable_excursion: None,
+        status: TradeStatus::Open,
+        tx_hash_entry: body.tx_hash,
+        tx_hash_exit: None,
+        venue: body.venue.unwrap_or_else(|| POLYMARKET_VENUE.to_string()),
+        origin: TradeOrigin::Manual,
+    };
+    state.storage.insert_trade(&trade).await?;
+    state.risk_manager.refresh_portfolio().await?;
+
+    info!("✍️ Recorded manual trade {} on {}", trade.trade_id, trade.market_id);
+    Ok((StatusCode::CREATED, Json(trade)))
+}
+
+/// POST /trades/:trade_id/close — record the exit of a manual position
+pub(crate) async fn close_manual_trade(
+    State(state): State<ApiState>,
+    Path(trade_id): Path<Uuid>,
+    Json(body): Json<CloseManualTrade>,
+) -> Result<Json<Trade>, ApiError> {
+    let exit_price = Price::new(body.exit_price)
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    let trade = state.storage.fetch_trade(trade_id).await?
+        .ok_or_else(|| ApiError::BadRequest(format!("unknown trade {}", trade_id)))?;
+    if trade.origin != TradeOrigin::Manual {
+        return Err(ApiError::BadRequest("only manual trades can be closed here".to_string()));
+    }
+    if trade.status != TradeStatus::Open {
+        return Err(ApiError::BadRequest(format!("trade {} is not open", trade_id)));
+    }
+
+    let exit_time = body.exit_time.unwrap_or_else(Utc::now);
+    let pnl = trade.realized_pnl(exit_price.value(), body.exit_fees);
+    state.storage.close_trade(
+        trade_id,
+        exit_price.value(),
+        exit_time,
+        pnl,
+        body.tx_hash.as_deref().unwrap_or_default(),
+    ).await?;
+    if body.exit_fees > Usdc::ZERO {
+        state.storage.insert_execution_cost(&ExecutionCost {
+            cost_id: Uuid::new_v4(),
+            kind: ExecutionCostKind::Exit,
+            amount: body.exit_fees.value(),
+            trade_id: Some(trade_id),
+            tx_hash: body.tx_hash.clone(),
+            incurred_at: exit_time,
+        }).await?;
+    }
+    state.risk_manager.update_portfolio(pnl).await?;
+
+    info!("✍️ Closed manual trade {} with PnL: {}", trade_id, pnl);
+    Ok(Json(Trade {
+        exit_price: Some(exit_price.value()),
+        exit_time: Some(exit_time),
+        pnl: Some(pnl),
+        status: TradeStatus::Closed,
+        tx_hash_exit: body.tx_hash,
+        ..trade
+    }))
+}
+
+/// GET /trades/:trade_id/convergence — the trade's divergence from the sharp line over time
+pub(crate) async fn trade_convergence(
+    State(state): State<ApiState>,
+    Path(trade_id): Path<Uuid>,
+) -> Result<Json<Vec<ConvergenceSample>>, ApiError> {
+    if state.storage.fetch_trade(trade_id).await?.is_none() {
+        return Err(ApiError::BadRequest(format!("unknown trade {}", trade_id)));
+    }
+    Ok(Json(state.storage.fetch_convergence_samples(trade_id).await?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use std::sync::Arc;
+
+    use crate::cache::InProcessCache;
+    use crate::events::EventBus;
+    use crate::monitoring::Notifier;
+    use crate::risk::RiskManager;
+    use crate::storage::{MarketRepo, MemoryStorage, TradeRepo};
+    use crate::test_support::{market_fixture, test_config, trade_fixture};
+
+    #[tokio::test]
+    async fn manual_trades_count_toward_the_portfolio_and_are_flagged_apart() {
+        let config = test_config("sqlite::memory:");
+        let storage = Arc::new(MemoryStorage::new());
+        storage.upsert_market(&market_fixture("0xmanual", dec!(0.40))).await.unwrap();
+        let events = EventBus::new(Arc::new(InProcessCache::new(100)));
+        let risk = RiskManager::new(storage.clone(), events, &config, Notifier::detached()).await.unwrap();
+        let state = ApiState::detached(storage.clone(), risk.clone(), &config).unwrap();
+        let bot = trade_fixture("0xmanual", dec!(0.40), dec!(100));
+        storage.insert_trade(&bot).await.unwrap();
+
+        let body = serde_json::from_value(serde_json::json!({
+            "market_id": "0xmanual", "position": "Yes", "quantity": "1000", "entry_price": "0.40",
+        })).unwrap();
+        let (status, Json(manual)) = record_manual_trade(State(state.clone()), Json(body)).await.unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!((manual.origin, manual.strategy, manual.venue.as_str()), (TradeOrigin::Manual, Strategy::Manual, POLYMARKET_VENUE));
+        // The $400 is held as invested alongside the bot's $40
+        assert_eq!(risk.get_portfolio_state().await.invested_capital, dec!(440));
+
+        let open = |origin| list_open_trades(State(state.clone()), Query(TradeQuery { origin }));
+        let Json(trades) = open(Some(TradeOrigin::Manual)).await.unwrap();
+        assert_eq!(trades.iter().map(|t| t.trade_id).collect::<Vec<_>>(), vec![manual.trade_id]);
+        let Json(trades) = open(Some(TradeOrigin::Bot)).await.unwrap();
+        assert_eq!(trades.iter().map(|t| t.trade_id).collect::<Vec<_>>(), vec![bot.trade_id]);
+        assert_eq!(open(None).await.unwrap().0.len(), 2);
+
+        // Only manual trades are closed by hand, net of the exit fees
+        let close = |trade_id| {
+            let body = serde_json::from_value(serde_json::json!({ "exit_price": "0.55", "exit_fees": "2" })).unwrap();
+            close_manual_trade(State(state.clone()), Path(trade_id), Json(body))
+        };
+        assert!(matches!(close(bot.trade_id).await, Err(ApiError::BadRequest(_))));
+        let Json(closed) = close(manual.trade_id).await.unwrap();
+        assert_eq!((closed.status, closed.pnl), (TradeStatus::Closed, Some(Usdc::new(dec!(148)))));
+        assert_eq!(storage.fetch_trade(manual.trade_id).await.unwrap().unwrap().status, TradeStatus::Closed);
+        assert!(matches!(close(manual.trade_id).await, Err(ApiError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn manual_trades_on_unknown_markets_or_sizes_are_refused() {
+        let config = test_config("sqlite::memory:");
+        let storage = Arc::new(MemoryStorage::new());
+        storage.upsert_market(&market_fixture("0xmanual", dec!(0.40))).await.unwrap();
+        let events = EventBus::new(Arc::new(InProcessCache::new(100)));
+        let risk = RiskManager::new(storage.clone(), events, &config, Notifier::detached()).await.unwrap();
+        let state = ApiState::detached(storage.clone(), risk, &config).unwrap();
+
+        for (market_id, quantity, price) in [("0xunknown", "1000", "0.40"), ("0xmanual", "0", "0.40"), ("0xmanual", "1000", "1.20")] {
+            let body = serde_json::from_value(serde_json::json!({
+                "market_id": market_id, "position": "No", "quantity": quantity, "entry_price": price,
+            })).unwrap();
+            assert!(matches!(record_manual_trade(State(state.clone()), Json(body)).await, Err(ApiError::BadRequest(_))));
+        }
+        assert!(storage.fetch_open_trades().await.unwrap().is_empty());
+    }
+}
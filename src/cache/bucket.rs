@@ -0,0 +1,51 @@
+use std::time::Instant;
+
+/// Token bucket holding up to `capacity` tokens, refilled continuously
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    pub fn full(capacity: u32, now: Instant) -> Self {
+        Self { tokens: capacity as f64, updated: now }
+    }
+
+    /// Take one token if there is one after refilling up to `now`
+    pub fn take(&mut self, capacity: u32, refill_per_sec: f64, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * refill_per_sec).min(capacity as f64);
+        self.updated = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn allows_a_burst_then_refills_at_the_rate() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::full(2, start);
+
+        assert!(bucket.take(2, 1.0, start));
+        assert!(bucket.take(2, 1.0, start));
+        assert!(!bucket.take(2, 1.0, start + Duration::from_millis(500)));
+        assert!(bucket.take(2, 1.0, start + Duration::from_millis(1000)));
+
+        // Idle time never banks more than the capacity
+        let later = start + Duration::from_secs(60);
+        assert!(bucket.take(2, 1.0, later));
+        assert!(bucket.take(2, 1.0, later));
+        assert!(!bucket.take(2, 1.0, later));
+    }
+}
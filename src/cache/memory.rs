@@ -0,0 +1,122 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::stream::{BoxStream, StreamExt};
+use moka::future::Cache as MokaCache;
+use moka::Expiry;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+
+use super::{Cache, TokenBucket};
+
+/// Buffered messages per channel before slow subscribers start lagging
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// Expires each entry after the TTL it was inserted with
+struct PerEntryTtl;
+
+impl Expiry<String, (String, Duration)> for PerEntryTtl {
+    fn expire_after_create(
+        &self,
+        _key: &String,
+        value: &(String, Duration),
+        _created_at: Instant,
+    ) -> Option<Duration> {
+        Some(value.1)
+    }
+}
+
+/// Single-process replacement for Redis (moka cache + broadcast channels)
+pub struct InProcessCache {
+    entries: MokaCache<String, (String, Duration)>,
+    channels: Mutex<HashMap<String, broadcast::Sender<String>>>,
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+    /// (owner, expiry) of held locks
+    locks: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+impl InProcessCache {
+    pub fn new(max_capacity: u64) -> Self {
+        let entries = MokaCache::builder()
+            .max_capacity(max_capacity)
+            .expire_after(PerEntryTtl)
+            .build();
+
+        Self {
+            entries,
+            channels: Mutex::new(HashMap::new()),
+            buckets: Mutex::new(HashMap::new()),
+            locks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn sender(&self, channel: &str) -> broadcast::Sender<String> {
+        let mut channels = self.channels.lock().expect("cache channel lock poisoned");
+        channels
+            .entry(channel.to_string())
+            .or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0)
+            .clone()
+    }
+}
+
+#[async_trait]
+impl Cache for InProcessCache {
+    async fn get(&self, key: &str) -> Result<Option<String>> {
+        Ok(self.entries.get(key).await.map(|(value, _)| value))
+    }
+
+    async fn set(&self, key: &str, value: &str, ttl: Duration) -> Result<()> {
+        self.entries
+            .insert(key.to_string(), (value.to_string(), ttl))
+            .await;
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        self.entries.invalidate(key).await;
+        Ok(())
+    }
+
+    async fn publish(&self, channel: &str, message: &str) -> Result<()> {
+        // No subscribers is not an error, matching Redis PUBLISH semantics
+        let _ = self.sender(channel).send(message.to_string());
+        Ok(())
+    }
+
+    async fn subscribe(&self, channel: &str) -> Result<BoxStream<'static, String>> {
+        let receiver = self.sender(channel).subscribe();
+        let stream = BroadcastStream::new(receiver)
+            .filter_map(|msg| async move { msg.ok() });
+
+        Ok(stream.boxed())
+    }
+
+    async fn take_token(&self, key: &str, capacity: u32, refill_per_sec: f64) -> Result<bool> {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().expect("cache bucket lock poisoned");
+        Ok(buckets
+            .entry(key.to_string())
+            .or_insert_with(|| TokenBucket::full(capacity, now))
+            .take(capacity, refill_per_sec, now))
+    }
+
+    async fn try_lock(&self, key: &str, owner: &str, ttl: Duration) -> Result<bool> {
+        let now = Instant::now();
+        let mut locks = self.locks.lock().expect("cache lock table poisoned");
+        if matches!(locks.get(key), Some((_, expires)) if *expires > now) {
+            return Ok(false);
+        }
+        locks.insert(key.to_string(), (owner.to_string(), now + ttl));
+        Ok(true)
+    }
+
+    async fn unlock(&self, key: &str, owner: &str) -> Result<()> {
+        let mut locks = self.locks.lock().expect("cache lock table poisoned");
+        if matches!(locks.get(key), Some((held_by, _)) if held_by == owner) {
+            locks.remove(key);
+        }
+        Ok(())
+    }
+}
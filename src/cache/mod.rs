@@ -0,0 +1,44 @@
+mod bucket;
+mod memory;
+mod redis;
+mod reference;
+
+pub use bucket::TokenBucket;
+pub use memory::InProcessCache;
+pub use reference::ReferenceCache;
+pub use self::redis::RedisCache;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use std::time::Duration;
+
+/// Key/value cache and pub/sub channel shared between services
+///
+/// Redis is used for multi-process deployments; single-node setups can run
+/// with the in-process implementation and skip Redis entirely.
+#[async_trait]
+pub trait Cache: Send + Sync {
+    async fn get(&self, key: &str) -> Result<Option<String>>;
+
+    async fn set(&self, key: &str, value: &str, ttl: Duration) -> Result<()>;
+
+    async fn delete(&self, key: &str) -> Result<()>;
+
+    /// Publish a message to every current subscriber of `channel`
+    async fn publish(&self, channel: &str, message: &str) -> Result<()>;
+
+    /// Stream of messages published to `channel` after subscribing
+    async fn subscribe(&self, channel: &str) -> Result<BoxStream<'static, String>>;
+
+    /// Take a token from the bucket at `key`, which holds up to `capacity`
+    /// and refills at `refill_per_sec`; false when it is empty
+    async fn take_token(&self, key: &str, capacity: u32, refill_per_sec: f64) -> Result<bool>;
+
+    /// Hold the lock at `key` as `owner` for `ttl` unless someone already
+    /// holds it; false when they do
+    async fn try_lock(&self, key: &str, owner: &str, ttl: Duration) -> Result<bool>;
+
+    /// Release the lock at `key` if `owner` still holds it
+    async fn unlock(&self, key: &str, owner: &str) -> Result<()>;
+}
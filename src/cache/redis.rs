@@ -0,0 +1,124 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::stream::{BoxStream, StreamExt};
+use redis::aio::ConnectionManager;
+use redis::{AsyncCommands, Client as RedisClient, Script};
+use std::time::Duration;
+
+use super::Cache;
+
+/// Atomic token bucket: refills by Redis server time so every instance
+/// shares one clock, and expires once it would be full again anyway
+const TAKE_TOKEN: &str = r#"
+local capacity = tonumber(ARGV[1])
+local rate = tonumber(ARGV[2])
+local time = redis.call('TIME')
+local now = tonumber(time[1]) + tonumber(time[2]) / 1000000
+local state = redis.call('HMGET', KEYS[1], 'tokens', 'updated')
+local tokens = tonumber(state[1]) or capacity
+local updated = tonumber(state[2]) or now
+tokens = math.min(capacity, tokens + math.max(0, now - updated) * rate)
+local taken = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    taken = 1
+end
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'updated', tostring(now))
+redis.call('EXPIRE', KEYS[1], math.ceil(capacity / rate) + 1)
+return taken
+"#;
+
+/// Delete a lock only while it still holds the caller's value, so a lock
+/// that expired and was taken by someone else is left alone
+const UNLOCK: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+"#;
+
+pub struct RedisCache {
+    client: RedisClient,
+    conn: ConnectionManager,
+    take_token: Script,
+    unlock: Script,
+}
+
+impl RedisCache {
+    pub async fn new(client: RedisClient) -> Result<Self> {
+        let conn = ConnectionManager::new(client.clone()).await?;
+        Ok(Self { client, conn, take_token: Script::new(TAKE_TOKEN), unlock: Script::new(UNLOCK) })
+    }
+}
+
+#[async_trait]
+impl Cache for RedisCache {
+    async fn get(&self, key: &str) -> Result<Option<String>> {
+        let mut conn = self.conn.clone();
+        Ok(conn.get(key).await?)
+    }
+
+    async fn set(&self, key: &str, value: &str, ttl: Duration) -> Result<()> {
+        let mut conn = self.conn.clone();
+        conn.set_ex::<_, _, ()>(key, value, ttl.as_secs().max(1)).await?;
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let mut conn = self.conn.clone();
+        conn.del::<_, ()>(key).await?;
+        Ok(())
+    }
+
+    async fn publish(&self, channel: &str, message: &str) -> Result<()> {
+        let mut conn = self.conn.clone();
+        conn.publish::<_, _, ()>(channel, message).await?;
+        Ok(())
+    }
+
+    async fn subscribe(&self, channel: &str) -> Result<BoxStream<'static, String>> {
+        // Pub/sub needs a dedicated connection; the manager is multiplexed
+        let mut pubsub = self.client.get_async_connection().await?.into_pubsub();
+        pubsub.subscribe(channel).await?;
+
+        let stream = pubsub
+            .into_on_message()
+            .filter_map(|msg| async move { msg.get_payload::<String>().ok() });
+
+        Ok(stream.boxed())
+    }
+
+    async fn take_token(&self, key: &str, capacity: u32, refill_per_sec: f64) -> Result<bool> {
+        let mut conn = self.conn.clone();
+        let taken: i32 = self.take_token
+            .key(key)
+            .arg(capacity)
+            .arg(refill_per_sec)
+            .invoke_async(&mut conn)
+            .await?;
+        Ok(taken == 1)
+    }
+
+    async fn try_lock(&self, key: &str, owner: &str, ttl: Duration) -> Result<bool> {
+        let mut conn = self.conn.clone();
+        let set: Option<String> = redis::cmd("SET")
+            .arg(key)
+            .arg(owner)
+            .arg("NX")
+            .arg("PX")
+            .arg(ttl.as_millis().max(1) as u64)
+            .query_async(&mut conn)
+            .await?;
+        Ok(set.is_some())
+    }
+
+    async fn unlock(&self, key: &str, owner: &str) -> Result<()> {
+        let mut conn = self.conn.clone();
+        self.unlock
+            .key(key)
+            .arg(owner)
+            .invoke_async::<_, i32>(&mut conn)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+use anyhow::{anyhow, Result};
+use moka::future::Cache as MokaCache;
+use std::any::{Any, TypeId};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
+
+use crate::config::ReferenceCacheConfig;
+use crate::models::TeamStrengths;
+use crate::storage::Storage;
+use crate::types::{Game, Market, OfficialTendency, Sport, TeamRating};
+
+type Parsed = Arc<dyn Any + Send + Sync>;
+
+/// Reference data the strategies would otherwise re-load or re-derive on
+/// every pass: team ratings, schedules, fitted team strengths, officials and
+/// parsed market questions
+///
+/// Entries expire after the configured TTL; ingestion invalidates what it
+/// changes (market status transitions, official assignments) so the next
+/// read goes back to storage.
+pub struct ReferenceCache {
+    storage: Arc<dyn Storage>,
+    team_ratings: MokaCache<Sport, Arc<Vec<TeamRating>>>,
+    season_games: MokaCache<Sport, Arc<Vec<Game>>>,
+    team_strengths: MokaCache<Sport, Arc<Option<TeamStrengths>>>,
+    /// Keyed by game id
+    official_tendencies: MokaCache<String, Arc<Vec<OfficialTendency>>>,
+    /// Keyed by market id and the type the question was parsed into
+    parsed: MokaCache<(String, TypeId), Parsed>,
+}
+
+impl ReferenceCache {
+    pub fn new(storage: Arc<dyn Storage>, config: &ReferenceCacheConfig) -> Self {
+        let ttl = Duration::from_secs(config.ttl_secs);
+
+        Self {
+            storage,
+            team_ratings: per_sport(ttl),
+            season_games: per_sport(ttl),
+            team_strengths: per_sport(ttl),
+            official_tendencies: MokaCache::builder()
+                .max_capacity(config.max_capacity)
+                .time_to_live(ttl)
+                .build(),
+            parsed: MokaCache::builder()
+                .max_capacity(config.max_capacity)
+                .time_to_live(ttl)
+                .support_invalidation_closures()
+                .build(),
+        }
+    }
+
+    pub async fn team_ratings(&self, sport: Sport) -> Result<Arc<Vec<TeamRating>>> {
+        let storage = self.storage.clone();
+        load(&self.team_ratings, sport, async move {
+            Ok(Arc::new(storage.fetch_team_ratings(sport).await?))
+        }).await
+    }
+
+    pub async fn season_games(&self, sport: Sport) -> Result<Arc<Vec<Game>>> {
+        let storage = self.storage.clone();
+        load(&self.season_games, sport, async move {
+            Ok(Arc::new(storage.fetch_season_games(sport).await?))
+        }).await
+    }
+
+    /// Strengths fitted by `fit` on a miss; `None` is cached too, so a sport
+    /// without enough history is not refitted every pass
+    pub async fn team_strengths<F>(&self, sport: Sport, fit: F) -> Result<Arc<Option<TeamStrengths>>>
+    where
+        F: Future<Output = Result<Option<TeamStrengths>>> + Send,
+    {
+        load(&self.team_strengths, sport, async move { Ok(Arc::new(fit.await?)) }).await
+    }
+
+    pub async fn official_tendencies(&self, game_id: &str) -> Result<Arc<Vec<OfficialTendency>>> {
+        let storage = self.storage.clone();
+        let key = game_id.to_string();
+        load(&self.official_tendencies, key.clone(), async move {
+            Ok(Arc::new(storage.fetch_official_tendencies(&key).await?))
+        }).await
+    }
+
+    /// A market's question parsed by `parse`, computed once per market and type
+    pub async fn parsed<T, F>(&self, market: &Market, parse: F) -> Option<Arc<T>>
+    where
+        T: Send + Sync + 'static,
+        F: FnOnce(&Market) -> Option<T>,
+    {
+        let key = (market.market_id.clone(), TypeId::of::<Option<Arc<T>>>());
+        let entry = match self.parsed.get(&key).await {
+            Some(entry) => entry,
+            None => {
+                let entry: Parsed = Arc::new(parse(market).map(Arc::new));
+                self.parsed.insert(key, entry.clone()).await;
+                entry
+            }
+        };
+
+        entry.downcast_ref::<Option<Arc<T>>>().cloned().flatten()
+    }
+
+    /// Drop everything parsed from a market's question
+    pub fn invalidate_market(&self, market_id: &str) {
+        let market_id = market_id.to_string();
+        if let Err(e) = self.parsed.invalidate_entries_if(move |(id, _), _| *id == market_id) {
+            debug!("Failed to invalidate parsed market: {}", e);
+        }
+    }
+
+    /// Drop a sport's ratings, schedule and fitted strengths
+    pub async fn invalidate_sport(&self, sport: Sport) {
+        self.team_ratings.invalidate(&sport).await;
+        self.season_games.invalidate(&sport).await;
+        self.team_strengths.invalidate(&sport).await;
+    }
+
+    pub async fn invalidate_officials(&self, game_id: &str) {
+        self.official_tendencies.invalidate(game_id).await;
+    }
+}
+
+fn per_sport<V: Clone + Send + Sync + 'static>(ttl: Duration) -> MokaCache<Sport, V> {
+    MokaCache::builder().time_to_live(ttl).build()
+}
+
+/// Read through `cache`, running `init` once for concurrent misses
+async fn load<K, V, F>(cache: &MokaCache<K, V>, key: K, init: F) -> Result<V>
+where
+    K: std::hash::Hash + Eq + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    F: Future<Output = Result<V>>,
+{
+    cache.try_get_with(key, init)
+        .await
+        .map_err(|e| anyhow!("{:#}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+    use crate::test_support::market_fixture;
+    use rust_decimal_macros::dec;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn parses_once_until_the_market_is_invalidated() {
+        let config = ReferenceCacheConfig { ttl_secs: 300, max_capacity: 100 };
+        let cache = ReferenceCache::new(Arc::new(MemoryStorage::new()), &config);
+        let market = market_fixture("0xref", dec!(0.5));
+        let parses = AtomicUsize::new(0);
+        let parse = |m: &Market| {
+            parses.fetch_add(1, Ordering::SeqCst);
+            Some(m.event_name.len())
+        };
+
+        let first = cache.parsed(&market, parse).await;
+        let second = cache.parsed(&market, parse).await;
+        assert_eq!(first, second);
+        assert_eq!(parses.load(Ordering::SeqCst), 1);
+
+        // A different target type is parsed separately
+        assert_eq!(cache.parsed(&market, |_| None::<String>).await, None);
+
+        cache.invalidate_market(&market.market_id);
+        cache.parsed.run_pending_tasks().await;
+        cache.parsed(&market, parse).await;
+        assert_eq!(parses.load(Ordering::SeqCst), 2);
+    }
+}
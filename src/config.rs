@@ -1,90 +1,2638 @@
-use anyhow::Result;
+use anyhow::{anyhow, bail, Context, Result};
+use ethers::types::Address;
 use serde::Deserialize;
 use sqlx::{postgres::PgPoolOptions, PgPool};
 use redis::Client as RedisClient;
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
+use crate::cache::{Cache, InProcessCache, RedisCache};
+use crate::execution::{ChainRpc, LiveExecutor, PaperExecutor, RecordingExecutor, TradeExecutor};
+use crate::storage::{PostgresStorage, SqliteStorage, Storage};
+use crate::types::{GasUrgency, SliceMode, Sport, TimeInForce, POLYMARKET_VENUE};
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
+    #[serde(default)]
+    pub mode: TradingMode,
     pub database: DatabaseConfig,
     pub redis: RedisConfig,
+    #[serde(default)]
+    pub reference_cache: ReferenceCacheConfig,
     pub blockchain: BlockchainConfig,
     pub polymarket: PolymarketConfig,
+    #[serde(default)]
+    pub fees: FeesConfig,
+    #[serde(default)]
+    pub api: ApiConfig,
+    #[serde(default)]
+    pub odds: OddsConfig,
+    #[serde(default)]
+    pub officials: OfficialsConfig,
+    #[serde(default)]
+    pub pitchers: PitchersConfig,
+    #[serde(default)]
+    pub microstructure: MicrostructureConfig,
+    #[serde(default)]
+    pub whales: WhalesConfig,
+    #[serde(default)]
+    pub backfill: BackfillConfig,
+    #[serde(default)]
+    pub simulation: SimulationConfig,
+    #[serde(default)]
+    pub model_parameters: ModelParametersConfig,
+    #[serde(default)]
+    pub execution: ExecutionConfig,
     pub strategies: StrategiesConfig,
     pub risk: RiskConfig,
     pub monitoring: MonitoringConfig,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct DatabaseConfig {
-    pub url: String,
-    pub max_connections: u32,
-    pub min_connections: u32,
-    pub connection_timeout: u64,
+/// How the bot is being run; only `Live` places real orders
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TradingMode {
+    #[default]
+    Live,
+    Paper,
+    Backtest,
+}
+
+/// Storage backend for markets, signals, trades and portfolio state
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    #[default]
+    Postgres,
+    Sqlite,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DatabaseConfig {
+    #[serde(default)]
+    pub backend: StorageBackend,
+    pub url: String,
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub connection_timeout: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RedisConfig {
+    /// When false, caching and pub/sub run in-process and Redis is never contacted
+    #[serde(default = "default_redis_enabled")]
+    pub enabled: bool,
+    pub url: String,
+    pub pool_size: u32,
+    /// Max entries held by the in-process cache when Redis is disabled
+    #[serde(default = "default_local_cache_capacity")]
+    pub local_cache_capacity: u64,
+}
+
+fn default_redis_enabled() -> bool {
+    true
+}
+
+fn default_local_cache_capacity() -> u64 {
+    10_000
+}
+
+/// In-process cache of team, schedule, officials and parsed market data
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReferenceCacheConfig {
+    /// Seconds an entry is served before it is reloaded; ingestion
+    /// invalidates what it changes sooner
+    #[serde(default = "default_reference_cache_ttl_secs")]
+    pub ttl_secs: u64,
+    /// Max entries per keyed cache (officials, parsed markets)
+    #[serde(default = "default_reference_cache_capacity")]
+    pub max_capacity: u64,
+}
+
+impl Default for ReferenceCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl_secs: default_reference_cache_ttl_secs(),
+            max_capacity: default_reference_cache_capacity(),
+        }
+    }
+}
+
+fn default_reference_cache_ttl_secs() -> u64 {
+    600
+}
+
+fn default_reference_cache_capacity() -> u64 {
+    10_000
+}
+
+/// Chain the bot trades on, by name; must be set explicitly in both
+/// `blockchain` and `polymarket`, and match the addresses configured there
+///
+/// `polygon` and `amoy` are built in. Other chains, or new contract
+/// deployments on a built-in one, are described under `blockchain.chains`.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Hash)]
+#[serde(transparent)]
+pub struct Network(String);
+
+/// Chain id, CLOB and contracts Polymarket runs on a network
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct NetworkProfile {
+    pub chain_id: u64,
+    /// Test funds only
+    #[serde(default)]
+    pub testnet: bool,
+    pub clob_url: String,
+    pub usdc_address: String,
+    pub ctf_exchange_address: String,
+    pub conditional_tokens_address: String,
+}
+
+impl Network {
+    pub fn new(name: &str) -> Self {
+        Network(name.to_lowercase())
+    }
+
+    pub fn polygon() -> Self {
+        Network::new("polygon")
+    }
+
+    pub fn amoy() -> Self {
+        Network::new("amoy")
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Polymarket's own deployments on the built-in networks
+    pub fn builtin_profile(&self) -> Option<NetworkProfile> {
+        let (chain_id, testnet, clob_url, usdc, exchange, ctf) = match self.as_str() {
+            // Polygon PoS mainnet, real funds
+            "polygon" => (
+                137,
+                false,
+                "https://clob.polymarket.com",
+                "0x2791Bca1f2de4661ED88E30C99A7a9449Aa84174",
+                "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E",
+                "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045",
+            ),
+            // Polygon Amoy testnet with Polymarket's staging CLOB, for rehearsals
+            "amoy" => (
+                80002,
+                true,
+                "https://clob-staging.polymarket.com",
+                "0x9c4e1703476e875070ee25b56a58b008cfb8fa78",
+                "0xdFE02Eb6733538f8Ea35D585af8DE5958AD99E40",
+                "0x69308FB512518e39F9b16112fA8d994F4e2Bf8bB",
+            ),
+            _ => return None,
+        };
+
+        Some(NetworkProfile {
+            chain_id,
+            testnet,
+            clob_url: clob_url.to_string(),
+            usdc_address: usdc.to_string(),
+            ctf_exchange_address: exchange.to_string(),
+            conditional_tokens_address: ctf.to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// The chain Polymarket is traded on, with its endpoints and contracts,
+/// resolved from `blockchain` and `polymarket`
+///
+/// Execution and chain monitors take everything chain-specific from here,
+/// so another network or a contract migration is a config change.
+#[derive(Debug, Clone)]
+pub struct Chain {
+    pub network: Network,
+    pub chain_id: u64,
+    pub testnet: bool,
+    /// HTTP endpoints, primary first then the fallbacks
+    pub rpc_urls: Vec<String>,
+    /// WebSocket endpoints, primary first then the fallbacks
+    pub ws_urls: Vec<String>,
+    pub usdc: Address,
+    pub ctf_exchange: Address,
+    pub conditional_tokens: Address,
+    pub gas_token_usd_feed: Address,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BlockchainConfig {
+    pub network: Network,
+    #[serde(alias = "polygon_rpc_url")]
+    pub rpc_url: String,
+    #[serde(alias = "polygon_ws_url")]
+    pub ws_url: String,
+    /// Endpoints failed over to when `rpc_url` is down or behind, in order
+    #[serde(default)]
+    pub fallback_rpc_urls: Vec<String>,
+    /// Endpoints failed over to when `ws_url` is down or behind, in order
+    #[serde(default)]
+    pub fallback_ws_urls: Vec<String>,
+    /// Health checks of the endpoints and failover between them
+    #[serde(default)]
+    pub failover: RpcFailoverConfig,
+    pub private_key: String,
+    pub gas_limit: u64,
+    pub max_gas_price_gwei: u64,
+    /// Gas units an entry's settlement is priced at before it is sent
+    #[serde(default = "default_entry_gas_units")]
+    pub entry_gas_units: u64,
+    /// USDC token watched for deposits and withdrawals
+    #[serde(default = "default_usdc_address")]
+    pub usdc_address: String,
+    /// Chainlink POL/USD feed, for pricing gas in USDC
+    #[serde(default = "default_gas_token_usd_feed")]
+    pub gas_token_usd_feed: String,
+    /// Networks beyond the built-in ones, or replacement profiles for them
+    #[serde(default)]
+    pub chains: HashMap<String, NetworkProfile>,
+    /// EIP-1559 fee bids and replacement of stuck transactions
+    #[serde(default)]
+    pub fees: GasFeeConfig,
+}
+
+/// When an RPC endpoint counts as unhealthy and is failed over from
+#[derive(Debug, Clone, Deserialize)]
+pub struct RpcFailoverConfig {
+    /// Seconds between probes of every endpoint's head
+    #[serde(default = "default_rpc_health_check_interval_secs")]
+    pub health_check_interval_secs: u64,
+    /// Seconds a connection or probe may take before the endpoint is down
+    #[serde(default = "default_rpc_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+    /// Blocks an endpoint may trail the highest head seen and still be used
+    #[serde(default = "default_rpc_max_block_lag")]
+    pub max_block_lag: u64,
+}
+
+impl Default for RpcFailoverConfig {
+    fn default() -> Self {
+        Self {
+            health_check_interval_secs: default_rpc_health_check_interval_secs(),
+            request_timeout_secs: default_rpc_request_timeout_secs(),
+            max_block_lag: default_rpc_max_block_lag(),
+        }
+    }
+}
+
+fn default_rpc_health_check_interval_secs() -> u64 {
+    30
+}
+
+fn default_rpc_request_timeout_secs() -> u64 {
+    5
+}
+
+fn default_rpc_max_block_lag() -> u64 {
+    5
+}
+
+/// How EIP-1559 fees are bid from recent blocks, and raised when a
+/// transaction is not mined
+///
+/// `max_gas_price_gwei` caps the max fee of every bid, replacements included.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GasFeeConfig {
+    /// Recent blocks whose tips the priority fee is taken from
+    #[serde(default = "default_fee_history_blocks")]
+    pub history_blocks: u64,
+    /// Percentile of those tips each urgency bids
+    #[serde(default)]
+    pub priority_fee_percentiles: UrgencyPercentiles,
+    /// Least tip bid; Polygon validators ignore tips under 30 gwei
+    #[serde(default = "default_min_priority_fee_gwei")]
+    pub min_priority_fee_gwei: f64,
+    /// Max fee as a multiple of the next block's base fee, plus the tip, so a
+    /// bid survives base fee rises while it waits
+    #[serde(default = "default_base_fee_multiplier")]
+    pub base_fee_multiplier: f64,
+    /// Seconds a transaction may go unmined before it is replaced
+    #[serde(default = "default_replace_after_secs")]
+    pub replace_after_secs: u64,
+    /// Percent both fees rise by in a replacement; nodes reject under 10
+    #[serde(default = "default_replacement_bump_pct")]
+    pub replacement_bump_pct: f64,
+    /// Replacements sent before a stuck transaction is given up on
+    #[serde(default = "default_max_replacements")]
+    pub max_replacements: u32,
+}
+
+impl GasFeeConfig {
+    /// Longest a transaction can take, from its first send through every
+    /// replacement
+    pub fn longest_send_secs(&self) -> u64 {
+        self.replace_after_secs * (u64::from(self.max_replacements) + 1)
+    }
+}
+
+impl Default for GasFeeConfig {
+    fn default() -> Self {
+        Self {
+            history_blocks: default_fee_history_blocks(),
+            priority_fee_percentiles: UrgencyPercentiles::default(),
+            min_priority_fee_gwei: default_min_priority_fee_gwei(),
+            base_fee_multiplier: default_base_fee_multiplier(),
+            replace_after_secs: default_replace_after_secs(),
+            replacement_bump_pct: default_replacement_bump_pct(),
+            max_replacements: default_max_replacements(),
+        }
+    }
+}
+
+/// Priority fee percentile bid at each urgency
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct UrgencyPercentiles {
+    pub low: f64,
+    pub normal: f64,
+    pub high: f64,
+}
+
+impl Default for UrgencyPercentiles {
+    fn default() -> Self {
+        Self { low: 10.0, normal: 50.0, high: 90.0 }
+    }
+}
+
+impl UrgencyPercentiles {
+    pub fn get(&self, urgency: GasUrgency) -> f64 {
+        match urgency {
+            GasUrgency::Low => self.low,
+            GasUrgency::Normal => self.normal,
+            GasUrgency::High => self.high,
+        }
+    }
+}
+
+fn default_fee_history_blocks() -> u64 {
+    20
+}
+
+fn default_min_priority_fee_gwei() -> f64 {
+    30.0
+}
+
+fn default_base_fee_multiplier() -> f64 {
+    2.0
+}
+
+fn default_replace_after_secs() -> u64 {
+    30
+}
+
+fn default_replacement_bump_pct() -> f64 {
+    12.5
+}
+
+fn default_max_replacements() -> u32 {
+    3
+}
+
+fn default_entry_gas_units() -> u64 {
+    250_000
+}
+
+fn default_usdc_address() -> String {
+    "0x2791Bca1f2de4661ED88E30C99A7a9449Aa84174".to_string()
+}
+
+fn default_gas_token_usd_feed() -> String {
+    "0xAB594600376Ec9fD91F8e885dADF0CE036862dE0".to_string()
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PolymarketConfig {
+    pub network: Network,
+    pub api_url: String,
+    /// CLOB API, for order book depth
+    #[serde(default = "default_clob_url")]
+    pub clob_url: String,
+    pub ws_url: String,
+    pub api_key: Option<String>,
+    pub ctf_exchange_address: String,
+    pub conditional_tokens_address: String,
+    /// Seconds between market/status refreshes in the data pipeline
+    #[serde(default = "default_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    #[serde(default)]
+    pub stream: StreamConfig,
+}
+
+/// Streamed books from `ws_url`, cross-checked against the REST books
+#[derive(Debug, Clone, Deserialize)]
+pub struct StreamConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Seconds between comparisons of streamed and REST books
+    #[serde(default = "default_cross_check_interval_secs")]
+    pub cross_check_interval_secs: u64,
+    /// Markets compared per check
+    #[serde(default = "default_cross_check_sample_size")]
+    pub sample_size: usize,
+    /// Largest gap between streamed and REST bid or ask not counted as divergent
+    #[serde(default = "default_max_divergence")]
+    pub max_divergence: f64,
+    /// Share of the sampled markets that must diverge for a check to fail
+    #[serde(default = "default_max_divergent_share")]
+    pub max_divergent_share: f64,
+    /// Failed checks in a row before failing over to REST
+    #[serde(default = "default_max_divergent_checks")]
+    pub max_divergent_checks: u32,
+    /// Seconds between refreshes of the subscribed markets
+    #[serde(default = "default_resubscribe_secs")]
+    pub resubscribe_secs: u64,
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cross_check_interval_secs: default_cross_check_interval_secs(),
+            sample_size: default_cross_check_sample_size(),
+            max_divergence: default_max_divergence(),
+            max_divergent_share: default_max_divergent_share(),
+            max_divergent_checks: default_max_divergent_checks(),
+            resubscribe_secs: default_resubscribe_secs(),
+        }
+    }
+}
+
+fn default_cross_check_interval_secs() -> u64 {
+    60
+}
+
+fn default_cross_check_sample_size() -> usize {
+    10
+}
+
+fn default_max_divergence() -> f64 {
+    0.02
+}
+
+fn default_max_divergent_share() -> f64 {
+    0.5
+}
+
+fn default_max_divergent_checks() -> u32 {
+    3
+}
+
+fn default_resubscribe_secs() -> u64 {
+    300
+}
+
+fn default_poll_interval_secs() -> u64 {
+    30
+}
+
+fn default_clob_url() -> String {
+    "https://clob.polymarket.com".to_string()
+}
+
+/// Trading fees per venue; venues not listed are treated as fee-free
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FeesConfig {
+    #[serde(default)]
+    pub venues: HashMap<String, FeeScheduleConfig>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FeeScheduleConfig {
+    #[serde(default)]
+    pub maker_bps: f64,
+    #[serde(default)]
+    pub taker_bps: f64,
+    /// Flat fee per order charged by the relayer
+    #[serde(default)]
+    pub relayer_fee_usd: f64,
+    /// Overrides keyed by market type (moneyline, spread, total, prop)
+    #[serde(default)]
+    pub market_types: HashMap<String, FeeOverrideConfig>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FeeOverrideConfig {
+    pub maker_bps: Option<f64>,
+    pub taker_bps: Option<f64>,
+    pub relayer_fee_usd: Option<f64>,
+}
+
+/// Admin HTTP API (cash flows and other operator actions)
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_api_bind_address")]
+    pub bind_address: String,
+    /// Keys for the admin API; without any the API is open
+    #[serde(default)]
+    pub keys: Vec<ApiKeyConfig>,
+    #[serde(default)]
+    pub grpc: GrpcConfig,
+    #[serde(default)]
+    pub paper_comparison: PaperComparisonConfig,
+    #[serde(default)]
+    pub mobile: MobileConfig,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: default_api_bind_address(),
+            keys: Vec::new(),
+            grpc: GrpcConfig::default(),
+            paper_comparison: PaperComparisonConfig::default(),
+            mobile: MobileConfig::default(),
+        }
+    }
+}
+
+/// Read-only phone view of the portfolio at `GET /m`, behind a PIN
+#[derive(Debug, Clone, Deserialize)]
+pub struct MobileConfig {
+    /// Unset disables the view
+    #[serde(default)]
+    pub pin: Option<String>,
+    /// How long a PIN login lasts
+    #[serde(default = "default_mobile_session_hours")]
+    pub session_hours: u64,
+    /// Wrong PINs in a row before logins are locked out
+    #[serde(default = "default_mobile_max_failed_attempts")]
+    pub max_failed_attempts: u32,
+    #[serde(default = "default_mobile_lockout_mins")]
+    pub lockout_mins: u64,
+}
+
+impl Default for MobileConfig {
+    fn default() -> Self {
+        Self {
+            pin: None,
+            session_hours: default_mobile_session_hours(),
+            max_failed_attempts: default_mobile_max_failed_attempts(),
+            lockout_mins: default_mobile_lockout_mins(),
+        }
+    }
+}
+
+fn default_mobile_session_hours() -> u64 {
+    12
+}
+
+fn default_mobile_max_failed_attempts() -> u32 {
+    5
+}
+
+fn default_mobile_lockout_mins() -> u64 {
+    15
+}
+
+/// Database of a paper run fed the same signals, for the execution
+/// shortfall report
+#[derive(Debug, Clone, Deserialize)]
+pub struct PaperComparisonConfig {
+    /// Postgres or `sqlite://` URL; unset disables the report
+    #[serde(default)]
+    pub database_url: Option<String>,
+    /// Furthest apart a live and a paper entry can be and still pair up
+    #[serde(default = "default_match_window_secs")]
+    pub match_window_secs: i64,
+}
+
+impl Default for PaperComparisonConfig {
+    fn default() -> Self {
+        Self {
+            database_url: None,
+            match_window_secs: default_match_window_secs(),
+        }
+    }
+}
+
+fn default_match_window_secs() -> i64 {
+    300
+}
+
+fn default_api_bind_address() -> String {
+    "127.0.0.1:8080".to_string()
+}
+
+/// An admin API key and what it may do
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiKeyConfig {
+    pub name: String,
+    pub key: String,
+    pub role: ApiRole,
+}
+
+/// Admin API roles, each allowed everything the ones before it are
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiRole {
+    /// Read-only monitoring
+    Viewer,
+    /// Day-to-day actions: manual trades, settlement reviews, reinstating strategies
+    Operator,
+    /// Cash flows and closing positions
+    Admin,
+}
+
+impl ApiRole {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ApiRole::Viewer => "viewer",
+            ApiRole::Operator => "operator",
+            ApiRole::Admin => "admin",
+        }
+    }
+}
+
+/// gRPC stream of signals, orders, fills and portfolio snapshots
+#[derive(Debug, Clone, Deserialize)]
+pub struct GrpcConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_grpc_bind_address")]
+    pub bind_address: String,
+    /// Seconds between checks for a new portfolio snapshot
+    #[serde(default = "default_grpc_portfolio_poll_secs")]
+    pub portfolio_poll_secs: u64,
+    #[serde(default)]
+    pub clients: Vec<GrpcClientConfig>,
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: default_grpc_bind_address(),
+            portfolio_poll_secs: default_grpc_portfolio_poll_secs(),
+            clients: Vec::new(),
+        }
+    }
+}
+
+/// A client allowed to subscribe, and the topics it may see
+#[derive(Debug, Clone, Deserialize)]
+pub struct GrpcClientConfig {
+    pub name: String,
+    pub token: String,
+    /// signals, orders, fills, portfolio; all of them when empty
+    #[serde(default)]
+    pub topics: Vec<String>,
+}
+
+fn default_grpc_bind_address() -> String {
+    "127.0.0.1:50051".to_string()
+}
+
+fn default_grpc_portfolio_poll_secs() -> u64 {
+    10
+}
+
+/// Reference bookmaker odds feeds; a provider is enabled by configuring its section
+#[derive(Debug, Clone, Deserialize)]
+pub struct OddsConfig {
+    #[serde(default = "default_odds_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    pub the_odds_api: Option<TheOddsApiConfig>,
+    pub pinnacle: Option<PinnacleConfig>,
+    pub betfair: Option<BetfairConfig>,
+}
+
+impl Default for OddsConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_secs: default_odds_poll_interval_secs(),
+            the_odds_api: None,
+            pinnacle: None,
+            betfair: None,
+        }
+    }
+}
+
+fn default_odds_poll_interval_secs() -> u64 {
+    60
+}
+
+/// How Monte Carlo draws are executed
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SimulationConfig {
+    #[serde(default)]
+    pub backend: SimulationBackend,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SimulationBackend {
+    /// One draw at a time; the reference implementation
+    #[default]
+    Scalar,
+    /// Vectorised counter-based draws on the CPU
+    Simd,
+    /// wgpu compute shaders; requires building with `--features gpu`
+    Gpu,
+}
+
+/// Analyst-maintained sheets of per-league model parameters
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ModelParametersConfig {
+    /// CSV or TOML files, applied in order; later sheets override earlier ones
+    #[serde(default)]
+    pub sheets: Vec<String>,
+}
+
+/// Order submission behaviour of the execution engine
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExecutionConfig {
+    /// Seconds between sweeps for pending signals and open positions;
+    /// published signals wake the engine straight away
+    #[serde(default = "default_execution_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    /// Seconds an engine holds the signals it claims, renewed as it takes up
+    /// each one; a signal whose claim has lapsed, e.g. after the engine
+    /// holding it died, can be claimed again. Must outlast a transaction's
+    /// every replacement
+    #[serde(default = "default_signal_lease_secs")]
+    pub signal_lease_secs: u64,
+    /// Seconds an engine holds a market's lock while sending orders on it,
+    /// so no two engines trade one market at once; a lock left by an engine
+    /// that died lapses. Must outlast a transaction's every replacement
+    #[serde(default = "default_market_lock_secs")]
+    pub market_lock_secs: u64,
+    /// Submissions of a signal, including the first, before giving up on
+    /// transient failures
+    #[serde(default = "default_max_submit_attempts")]
+    pub max_submit_attempts: u32,
+    /// Delay before the first retry; doubles with each further attempt
+    #[serde(default = "default_retry_backoff_secs")]
+    pub retry_backoff_secs: u64,
+    #[serde(default = "default_max_retry_backoff_secs")]
+    pub max_retry_backoff_secs: u64,
+    /// Adverse move of a fill from its signal's price, in probability
+    /// points, that is recorded and alerted on
+    #[serde(default = "default_max_fill_slippage")]
+    pub max_fill_slippage: f64,
+    /// Close positions filled past `max_fill_slippage` straight away
+    #[serde(default)]
+    pub flatten_on_slippage: bool,
+    /// Leave a target exit on the CLOB straight after entry, with a stop the
+    /// position monitor triggers
+    #[serde(default)]
+    pub resting_exits: bool,
+    /// Distance of the stop below the entry price, in probability points
+    #[serde(default = "default_resting_stop_distance")]
+    pub resting_stop_distance: f64,
+    /// Distance of the resting target above the entry price, in probability points
+    #[serde(default = "default_resting_target_distance")]
+    pub resting_target_distance: f64,
+    #[serde(default)]
+    pub order_rules: OrderRulesConfig,
+    /// Exit only when selling at the bid after fees is worth at least the
+    /// model's expected payout from holding to resolution
+    #[serde(default = "default_ev_gated_exits")]
+    pub ev_gated_exits: bool,
+    /// Order rate limits by venue, shared by every instance through Redis
+    #[serde(default = "default_rate_limits")]
+    pub rate_limits: HashMap<String, RateLimitConfig>,
+    /// What orders are sent to; unset follows `mode` (live on chain, paper otherwise)
+    #[serde(default)]
+    pub executor: Option<ExecutorKind>,
+    #[serde(default)]
+    pub paper: PaperExecutionConfig,
+    #[serde(default)]
+    pub maker: MakerConfig,
+    #[serde(default)]
+    pub slicing: SlicingConfig,
+}
+
+/// When large taker entries are split into child orders
+#[derive(Debug, Clone, Deserialize)]
+pub struct SlicingConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Stakes above this USDC notional are sliced
+    #[serde(default = "default_slicing_min_notional")]
+    pub min_notional: f64,
+    #[serde(default = "default_slicing_mode")]
+    pub mode: SliceMode,
+    #[serde(default = "default_slicing_slices")]
+    pub slices: u32,
+    /// Seconds between children
+    #[serde(default = "default_slicing_interval_secs")]
+    pub interval_secs: u64,
+    /// How far above the signal's price, in basis points, children may pay;
+    /// children wait while the ask is higher
+    #[serde(default = "default_slicing_max_price_drift_bps")]
+    pub max_price_drift_bps: f64,
+    /// Parent orders still short of their notional after this are cancelled
+    #[serde(default = "default_slicing_max_duration_secs")]
+    pub max_duration_secs: u64,
+}
+
+impl Default for SlicingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_notional: default_slicing_min_notional(),
+            mode: default_slicing_mode(),
+            slices: default_slicing_slices(),
+            interval_secs: default_slicing_interval_secs(),
+            max_price_drift_bps: default_slicing_max_price_drift_bps(),
+            max_duration_secs: default_slicing_max_duration_secs(),
+        }
+    }
+}
+
+fn default_slicing_min_notional() -> f64 {
+    1000.0
+}
+
+fn default_slicing_mode() -> SliceMode {
+    SliceMode::Twap
+}
+
+fn default_slicing_slices() -> u32 {
+    4
+}
+
+fn default_slicing_interval_secs() -> u64 {
+    30
+}
+
+fn default_slicing_max_price_drift_bps() -> f64 {
+    150.0
+}
+
+fn default_slicing_max_duration_secs() -> u64 {
+    600
+}
+
+/// When entries rest inside the spread instead of crossing it
+#[derive(Debug, Clone, Deserialize)]
+pub struct MakerConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Longest an entry order rests before it is cancelled
+    #[serde(default = "default_maker_max_rest_secs")]
+    pub max_rest_secs: u64,
+    /// Entry orders are cancelled this long before the event starts
+    #[serde(default = "default_maker_cancel_before_start_secs")]
+    pub cancel_before_start_secs: u64,
+    /// Resting windows shorter than this always take
+    #[serde(default = "default_maker_min_rest_secs")]
+    pub min_rest_secs: u64,
+    /// Taker edge per share at or above which an entry always takes, since
+    /// large edges rarely wait
+    #[serde(default = "default_maker_take_above_edge")]
+    pub take_above_edge: f64,
+    /// Expected maker edge per share must beat the taker edge by this much
+    #[serde(default = "default_maker_min_edge_improvement")]
+    pub min_edge_improvement: f64,
+    #[serde(default)]
+    pub fill_model: FillModelConfig,
+}
+
+/// Fill probability model for entry orders, fitted from price history
+#[derive(Debug, Clone, Deserialize)]
+pub struct FillModelConfig {
+    /// Markets whose events started within this many days are fitted on
+    #[serde(default = "default_fill_model_lookback_days")]
+    pub lookback_days: u64,
+    #[serde(default = "default_fill_model_refit_hours")]
+    pub refit_hours: u64,
+    /// Farthest distance below the mid fitted, in ticks
+    #[serde(default = "default_fill_model_max_ticks")]
+    pub max_ticks: usize,
+    /// Below this many samples the order flow estimate is used instead
+    #[serde(default = "default_fill_model_min_samples")]
+    pub min_samples: u64,
+}
+
+impl Default for FillModelConfig {
+    fn default() -> Self {
+        Self {
+            lookback_days: default_fill_model_lookback_days(),
+            refit_hours: default_fill_model_refit_hours(),
+            max_ticks: default_fill_model_max_ticks(),
+            min_samples: default_fill_model_min_samples(),
+        }
+    }
+}
+
+fn default_fill_model_lookback_days() -> u64 {
+    30
+}
+
+fn default_fill_model_refit_hours() -> u64 {
+    6
+}
+
+fn default_fill_model_max_ticks() -> usize {
+    10
+}
+
+fn default_fill_model_min_samples() -> u64 {
+    200
+}
+
+impl Default for MakerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_rest_secs: default_maker_max_rest_secs(),
+            cancel_before_start_secs: default_maker_cancel_before_start_secs(),
+            min_rest_secs: default_maker_min_rest_secs(),
+            take_above_edge: default_maker_take_above_edge(),
+            min_edge_improvement: default_maker_min_edge_improvement(),
+            fill_model: FillModelConfig::default(),
+        }
+    }
+}
+
+fn default_maker_max_rest_secs() -> u64 {
+    900
+}
+
+fn default_maker_cancel_before_start_secs() -> u64 {
+    600
+}
+
+fn default_maker_min_rest_secs() -> u64 {
+    60
+}
+
+fn default_maker_take_above_edge() -> f64 {
+    0.08
+}
+
+fn default_maker_min_edge_improvement() -> f64 {
+    0.005
+}
+
+/// Where the execution engine sends orders
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecutorKind {
+    /// Signed orders on the CTF Exchange
+    Live,
+    /// Filled at stored quotes with modeled slippage
+    Paper,
+    /// Filled at the limit price, keeping every call for replay and tests
+    Recording,
+}
+
+/// Fill model of the paper executor
+#[derive(Debug, Clone, Deserialize)]
+pub struct PaperExecutionConfig {
+    /// Slippage past the stored ask (buys) or bid (sells), in basis points of price
+    #[serde(default = "default_paper_slippage_bps")]
+    pub slippage_bps: f64,
+    /// USDC of gas charged per simulated transaction
+    #[serde(default = "default_paper_gas_cost_usd")]
+    pub gas_cost_usd: f64,
+}
+
+impl Default for PaperExecutionConfig {
+    fn default() -> Self {
+        Self {
+            slippage_bps: default_paper_slippage_bps(),
+            gas_cost_usd: default_paper_gas_cost_usd(),
+        }
+    }
+}
+
+fn default_paper_slippage_bps() -> f64 {
+    20.0
+}
+
+fn default_paper_gas_cost_usd() -> f64 {
+    0.01
+}
+
+impl Default for ExecutionConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_secs: default_execution_poll_interval_secs(),
+            signal_lease_secs: default_signal_lease_secs(),
+            market_lock_secs: default_market_lock_secs(),
+            max_submit_attempts: default_max_submit_attempts(),
+            retry_backoff_secs: default_retry_backoff_secs(),
+            max_retry_backoff_secs: default_max_retry_backoff_secs(),
+            max_fill_slippage: default_max_fill_slippage(),
+            flatten_on_slippage: false,
+            resting_exits: false,
+            resting_stop_distance: default_resting_stop_distance(),
+            resting_target_distance: default_resting_target_distance(),
+            order_rules: OrderRulesConfig::default(),
+            ev_gated_exits: default_ev_gated_exits(),
+            rate_limits: default_rate_limits(),
+            executor: None,
+            paper: PaperExecutionConfig::default(),
+            maker: MakerConfig::default(),
+            slicing: SlicingConfig::default(),
+        }
+    }
+}
+
+fn default_execution_poll_interval_secs() -> u64 {
+    10
+}
+
+fn default_signal_lease_secs() -> u64 {
+    300
+}
+
+fn default_market_lock_secs() -> u64 {
+    300
+}
+
+fn default_max_submit_attempts() -> u32 {
+    4
+}
+
+fn default_retry_backoff_secs() -> u64 {
+    5
+}
+
+fn default_max_retry_backoff_secs() -> u64 {
+    60
+}
+
+fn default_max_fill_slippage() -> f64 {
+    0.02
+}
+
+fn default_resting_stop_distance() -> f64 {
+    0.15
+}
+
+fn default_resting_target_distance() -> f64 {
+    0.20
+}
+
+fn default_ev_gated_exits() -> bool {
+    true
+}
+
+fn default_rate_limits() -> HashMap<String, RateLimitConfig> {
+    HashMap::from([(
+        "polymarket".to_string(),
+        RateLimitConfig { orders_per_minute: 60, burst: Some(10) },
+    )])
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateLimitConfig {
+    pub orders_per_minute: u32,
+    /// Orders that may go out back to back; defaults to `orders_per_minute`
+    pub burst: Option<u32>,
+}
+
+/// Venue constraints every order's price and size must satisfy
+#[derive(Debug, Clone, Deserialize)]
+pub struct OrderRulesConfig {
+    /// Price increment
+    #[serde(default = "default_tick_size")]
+    pub tick_size: f64,
+    /// Share quantity increment
+    #[serde(default = "default_lot_size")]
+    pub lot_size: f64,
+    /// Fewest shares in an order
+    #[serde(default = "default_min_order_size")]
+    pub min_order_size: f64,
+    /// Smallest order value in USDC
+    #[serde(default = "default_min_order_notional")]
+    pub min_notional: f64,
+}
+
+impl Default for OrderRulesConfig {
+    fn default() -> Self {
+        Self {
+            tick_size: default_tick_size(),
+            lot_size: default_lot_size(),
+            min_order_size: default_min_order_size(),
+            min_notional: default_min_order_notional(),
+        }
+    }
+}
+
+fn default_tick_size() -> f64 {
+    0.01
+}
+
+fn default_lot_size() -> f64 {
+    0.01
+}
+
+fn default_min_order_size() -> f64 {
+    5.0
+}
+
+fn default_min_order_notional() -> f64 {
+    1.0
+}
+
+/// Referee and umpire assignments feeding the totals model
+#[derive(Debug, Clone, Deserialize)]
+pub struct OfficialsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_officials_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    #[serde(default = "default_mlb_stats_url")]
+    pub mlb_stats_url: String,
+    /// ESPN's site API, for NFL and NBA crews
+    #[serde(default = "default_espn_url")]
+    pub espn_url: String,
+    /// Days of finished games whose crews are read at startup, so officials
+    /// have a record before their next assignment
+    #[serde(default = "default_officials_backfill_days")]
+    pub backfill_days: i64,
+}
+
+impl Default for OfficialsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            poll_interval_secs: default_officials_poll_interval_secs(),
+            mlb_stats_url: default_mlb_stats_url(),
+            espn_url: default_espn_url(),
+            backfill_days: default_officials_backfill_days(),
+        }
+    }
+}
+
+fn default_officials_poll_interval_secs() -> u64 {
+    900
+}
+
+fn default_espn_url() -> String {
+    "https://site.api.espn.com/apis/site/v2/sports".to_string()
+}
+
+fn default_officials_backfill_days() -> i64 {
+    120
+}
+
+/// MLB probable pitchers and bullpen usage feeding the MLB model
+#[derive(Debug, Clone, Deserialize)]
+pub struct PitchersConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_officials_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    #[serde(default = "default_mlb_stats_url")]
+    pub mlb_stats_url: String,
+}
+
+impl Default for PitchersConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            poll_interval_secs: default_officials_poll_interval_secs(),
+            mlb_stats_url: default_mlb_stats_url(),
+        }
+    }
+}
+
+fn default_mlb_stats_url() -> String {
+    "https://statsapi.mlb.com/api/v1".to_string()
+}
+
+/// Order book and taker flow features recorded each market poll, and the
+/// filter that keeps entries out of heavy one-sided selling
+#[derive(Debug, Clone, Deserialize)]
+pub struct MicrostructureConfig {
+    #[serde(default = "default_microstructure_enabled")]
+    pub enabled: bool,
+    /// Book levels per side counted as depth
+    #[serde(default = "default_book_levels")]
+    pub book_levels: usize,
+    /// Seconds of recent trades counted as taker flow
+    #[serde(default = "default_flow_window_secs")]
+    pub flow_window_secs: u64,
+    /// Public trades API, for taker flow
+    #[serde(default = "default_data_api_url")]
+    pub data_api_url: String,
+    /// Defer entries while flow or the book leans against them past these
+    #[serde(default = "default_microstructure_enabled")]
+    pub filter_entries: bool,
+    /// Taker flow against an entry, in [0, 1], past which it is deferred
+    #[serde(default = "default_max_adverse_flow")]
+    pub max_adverse_flow: f64,
+    /// Book imbalance against an entry, in [0, 1], past which it is deferred
+    #[serde(default = "default_max_adverse_imbalance")]
+    pub max_adverse_imbalance: f64,
+    /// Taker volume in shares below which flow is ignored
+    #[serde(default = "default_min_flow_volume")]
+    pub min_flow_volume: f64,
+    /// Features older than this are ignored by the filter
+    #[serde(default = "default_microstructure_max_age_secs")]
+    pub max_age_secs: u64,
+}
+
+impl Default for MicrostructureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_microstructure_enabled(),
+            book_levels: default_book_levels(),
+            flow_window_secs: default_flow_window_secs(),
+            data_api_url: default_data_api_url(),
+            filter_entries: default_microstructure_enabled(),
+            max_adverse_flow: default_max_adverse_flow(),
+            max_adverse_imbalance: default_max_adverse_imbalance(),
+            min_flow_volume: default_min_flow_volume(),
+            max_age_secs: default_microstructure_max_age_secs(),
+        }
+    }
+}
+
+fn default_microstructure_enabled() -> bool {
+    true
+}
+
+fn default_book_levels() -> usize {
+    5
+}
+
+fn default_flow_window_secs() -> u64 {
+    300
+}
+
+fn default_data_api_url() -> String {
+    "https://data-api.polymarket.com".to_string()
+}
+
+fn default_max_adverse_flow() -> f64 {
+    0.6
+}
+
+fn default_max_adverse_imbalance() -> f64 {
+    0.6
+}
+
+fn default_min_flow_volume() -> f64 {
+    500.0
+}
+
+fn default_microstructure_max_age_secs() -> u64 {
+    120
+}
+
+/// Unusually large taker trades on tracked markets, stored and alerted on
+#[derive(Debug, Clone, Deserialize)]
+pub struct WhalesConfig {
+    #[serde(default = "default_whales_enabled")]
+    pub enabled: bool,
+    /// USDC notional below which no trade counts as large
+    #[serde(default = "default_whale_min_notional_usd")]
+    pub min_notional_usd: f64,
+    /// Multiple of the market's median recent trade a trade must reach
+    #[serde(default = "default_whale_size_multiple")]
+    pub size_multiple: f64,
+}
+
+impl Default for WhalesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_whales_enabled(),
+            min_notional_usd: default_whale_min_notional_usd(),
+            size_multiple: default_whale_size_multiple(),
+        }
+    }
+}
+
+fn default_whales_enabled() -> bool {
+    true
+}
+
+fn default_whale_min_notional_usd() -> f64 {
+    10_000.0
+}
+
+fn default_whale_size_multiple() -> f64 {
+    20.0
+}
+
+/// One-off import of closed Polymarket sports markets, their price history
+/// and resolutions, run at startup
+#[derive(Debug, Clone, Deserialize)]
+pub struct BackfillConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Markets that ended within this many days are imported
+    #[serde(default = "default_backfill_lookback_days")]
+    pub lookback_days: i64,
+    /// Closed markets requested per page
+    #[serde(default = "default_backfill_page_size")]
+    pub page_size: usize,
+    /// Minutes between price history points
+    #[serde(default = "default_backfill_fidelity_mins")]
+    pub fidelity_mins: u32,
+    /// Pause between markets, to stay inside the public APIs' rate limits
+    #[serde(default = "default_backfill_request_delay_ms")]
+    pub request_delay_ms: u64,
+}
+
+impl Default for BackfillConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            lookback_days: default_backfill_lookback_days(),
+            page_size: default_backfill_page_size(),
+            fidelity_mins: default_backfill_fidelity_mins(),
+            request_delay_ms: default_backfill_request_delay_ms(),
+        }
+    }
+}
+
+fn default_backfill_lookback_days() -> i64 {
+    1095
+}
+
+fn default_backfill_page_size() -> usize {
+    500
+}
+
+fn default_backfill_fidelity_mins() -> u32 {
+    60
+}
+
+fn default_backfill_request_delay_ms() -> u64 {
+    250
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TheOddsApiConfig {
+    pub api_key: String,
+    #[serde(default = "default_the_odds_api_url")]
+    pub api_url: String,
+    /// Comma-separated bookmaker regions, e.g. "us,eu"
+    #[serde(default = "default_the_odds_api_regions")]
+    pub regions: String,
+}
+
+fn default_the_odds_api_url() -> String {
+    "https://api.the-odds-api.com".to_string()
+}
+
+fn default_the_odds_api_regions() -> String {
+    "us,eu".to_string()
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PinnacleConfig {
+    pub username: String,
+    pub password: String,
+    #[serde(default = "default_pinnacle_api_url")]
+    pub api_url: String,
+}
+
+fn default_pinnacle_api_url() -> String {
+    "https://api.pinnacle.com".to_string()
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BetfairConfig {
+    pub app_key: String,
+    pub session_token: String,
+    #[serde(default = "default_betfair_api_url")]
+    pub api_url: String,
+}
+
+fn default_betfair_api_url() -> String {
+    "https://api.betfair.com/exchange/betting/rest/v1.0".to_string()
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StrategiesConfig {
+    pub clv_arb: ClvArbConfig,
+    pub poisson_ev: PoissonEvConfig,
+    pub news_scalp: NewsScalpConfig,
+    #[serde(default)]
+    pub combo: ComboConfig,
+    #[serde(default)]
+    pub futures: FuturesConfig,
+    #[serde(default)]
+    pub middle: MiddleConfig,
+    #[serde(default)]
+    pub mlb: MlbConfig,
+    #[serde(default)]
+    pub scripted: ScriptedConfig,
+    #[serde(default)]
+    pub remote_model: RemoteModelConfig,
+    #[serde(default)]
+    pub incremental: IncrementalConfig,
+    #[serde(default)]
+    pub cadence: CadenceConfig,
+    pub enabled_strategies: Vec<String>,
+    /// Sports each strategy is routed, keyed like `enabled_strategies`;
+    /// a strategy without an entry sees every sport
+    #[serde(default = "default_strategy_sports")]
+    pub sports: HashMap<String, Vec<Sport>>,
+    /// Seconds after generation a strategy's signals may still be executed,
+    /// keyed like `enabled_strategies`; others get `DEFAULT_MAX_EXECUTION_AGE_SECS`
+    #[serde(default = "default_max_execution_age")]
+    pub max_execution_age: HashMap<String, u64>,
+    /// How long each strategy's orders stay on the book, keyed like
+    /// `enabled_strategies`; others take fill-or-kill and rest good-til-date
+    #[serde(default = "default_time_in_force")]
+    pub time_in_force: HashMap<String, StrategyTimeInForce>,
+    /// Gas and fees each strategy's entries may pay, keyed like
+    /// `enabled_strategies`; others get `CostTolerance::default()`
+    #[serde(default = "default_cost_tolerance")]
+    pub cost_tolerance: HashMap<String, CostTolerance>,
+}
+
+/// Execution costs a strategy's entry may carry before it is not sent
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+pub struct CostTolerance {
+    /// USDC of gas an entry transaction may cost; dearer ones wait for gas
+    /// to come down
+    #[serde(default = "default_max_gas_usd")]
+    pub max_gas_usd: f64,
+    /// Share of the signal's edge per share that fees and gas together may
+    /// take, in (0, 1]
+    #[serde(default = "default_max_cost_share")]
+    pub max_cost_share: f64,
+    /// How hard entry transactions bid for the next blocks
+    #[serde(default)]
+    pub urgency: GasUrgency,
+}
+
+impl Default for CostTolerance {
+    fn default() -> Self {
+        Self {
+            max_gas_usd: default_max_gas_usd(),
+            max_cost_share: default_max_cost_share(),
+            urgency: GasUrgency::default(),
+        }
+    }
+}
+
+fn default_max_gas_usd() -> f64 {
+    1.0
+}
+
+fn default_max_cost_share() -> f64 {
+    1.0
+}
+
+/// Time in force of a strategy's taking and resting entries
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+pub struct StrategyTimeInForce {
+    /// `fok` or `ioc`
+    #[serde(default = "default_taker_time_in_force")]
+    pub taker: TimeInForce,
+    /// `gtd`, expiring with the entry before the event starts, or `gtc`,
+    /// left to the engine to cancel
+    #[serde(default = "default_maker_time_in_force")]
+    pub maker: TimeInForce,
+}
+
+impl Default for StrategyTimeInForce {
+    fn default() -> Self {
+        Self {
+            taker: default_taker_time_in_force(),
+            maker: default_maker_time_in_force(),
+        }
+    }
+}
+
+fn default_taker_time_in_force() -> TimeInForce {
+    TimeInForce::Fok
+}
+
+fn default_maker_time_in_force() -> TimeInForce {
+    TimeInForce::Gtd
+}
+
+/// Execution deadline for strategies without a `max_execution_age` entry
+const DEFAULT_MAX_EXECUTION_AGE_SECS: u64 = 300;
+
+impl StrategiesConfig {
+    /// Sports routed to a strategy, `None` if unrestricted
+    pub fn sports_for(&self, strategy: &str) -> Option<&[Sport]> {
+        self.sports.get(strategy).map(Vec::as_slice)
+    }
+
+    /// How long a strategy's signals stay executable
+    pub fn max_execution_age(&self, strategy: &str) -> chrono::Duration {
+        let secs = self.max_execution_age.get(strategy).copied().unwrap_or(DEFAULT_MAX_EXECUTION_AGE_SECS);
+        chrono::Duration::seconds(secs as i64)
+    }
+
+    /// The longest deadline of any strategy
+    pub fn longest_execution_age(&self) -> chrono::Duration {
+        let secs = self.max_execution_age.values().copied().fold(DEFAULT_MAX_EXECUTION_AGE_SECS, u64::max);
+        chrono::Duration::seconds(secs as i64)
+    }
+
+    /// Time in force of a strategy's orders
+    pub fn time_in_force(&self, strategy: &str) -> StrategyTimeInForce {
+        self.time_in_force.get(strategy).copied().unwrap_or_default()
+    }
+
+    /// Cost limits of a strategy's entries
+    pub fn cost_tolerance(&self, strategy: &str) -> CostTolerance {
+        self.cost_tolerance.get(strategy).copied().unwrap_or_default()
+    }
+
+    pub fn validate_cost_tolerance(&self) -> Result<()> {
+        for (strategy, tolerance) in &self.cost_tolerance {
+            if !(tolerance.max_gas_usd >= 0.0) {
+                bail!("strategies.cost_tolerance.{}.max_gas_usd must not be negative", strategy);
+            }
+            if !(tolerance.max_cost_share > 0.0 && tolerance.max_cost_share <= 1.0) {
+                bail!("strategies.cost_tolerance.{}.max_cost_share must be in (0, 1]", strategy);
+            }
+        }
+        Ok(())
+    }
+
+    /// Every strategy takes with an immediate order and rests with a
+    /// cancellable one
+    pub fn validate_time_in_force(&self) -> Result<()> {
+        for (strategy, tif) in &self.time_in_force {
+            if !tif.taker.is_immediate() {
+                bail!("strategies.time_in_force.{}.taker must be fok or ioc, not {}", strategy, tif.taker.as_str());
+            }
+            if tif.maker.is_immediate() {
+                bail!("strategies.time_in_force.{}.maker must be gtc or gtd, not {}", strategy, tif.maker.as_str());
+            }
+        }
+        Ok(())
+    }
+}
+
+fn default_strategy_sports() -> HashMap<String, Vec<Sport>> {
+    // The Poisson totals model is only calibrated for NFL and NBA scoring
+    HashMap::from([("poisson_ev".to_string(), vec![Sport::NFL, Sport::NBA])])
+}
+
+fn default_max_execution_age() -> HashMap<String, u64> {
+    // News edges are gone within a minute
+    HashMap::from([("news_scalp".to_string(), 60)])
+}
+
+fn default_cost_tolerance() -> HashMap<String, CostTolerance> {
+    HashMap::from([
+        // News edges are large and gone within a minute, so paying up for gas beats missing them
+        ("news_scalp".to_string(), CostTolerance { max_gas_usd: 5.0, max_cost_share: 1.0, urgency: GasUrgency::High }),
+        // CLV edges are thin and keep; wait out gas spikes rather than give half the edge away
+        ("clv_arb".to_string(), CostTolerance { max_gas_usd: 0.25, max_cost_share: 0.5, urgency: GasUrgency::Low }),
+    ])
+}
+
+fn default_time_in_force() -> HashMap<String, StrategyTimeInForce> {
+    // A bookmaker divergence outlives the order, so part of it is still worth having
+    HashMap::from([(
+        "clv_arb".to_string(),
+        StrategyTimeInForce { taker: TimeInForce::Ioc, ..StrategyTimeInForce::default() },
+    )])
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClvArbConfig {
+    pub min_divergence_pct: f64,
+    pub exit_on_convergence: bool,
+    pub max_hold_hours: u64,
+    /// Bookmaker lines up to this old (by the bookmaker's own timestamp when
+    /// known) count fully; older ones are discounted
+    #[serde(default = "default_fresh_odds_secs")]
+    pub fresh_odds_secs: u64,
+    /// Lines older than this are ignored
+    #[serde(default = "default_max_odds_age_secs")]
+    pub max_odds_age_secs: u64,
+    /// Skip signals fading a large trade made within this many seconds;
+    /// 0 disables
+    #[serde(default = "default_large_trade_lookback_secs")]
+    pub large_trade_lookback_secs: u64,
+}
+
+fn default_fresh_odds_secs() -> u64 {
+    60
+}
+
+fn default_max_odds_age_secs() -> u64 {
+    300
+}
+
+fn default_large_trade_lookback_secs() -> u64 {
+    900
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PoissonEvConfig {
+    pub min_edge_pct: f64,
+    pub simulation_count: u32,
+    pub min_significance: f64,
+    #[serde(default)]
+    pub lambda_source: LambdaSource,
+    #[serde(default)]
+    pub adaptive: AdaptiveSimulationConfig,
+}
+
+/// Sequential stopping of the Poisson simulation: draws continue in batches
+/// only while the edge decision is still in doubt
+#[derive(Debug, Clone, Deserialize)]
+pub struct AdaptiveSimulationConfig {
+    /// Off draws the fixed `simulation_count` for every market
+    #[serde(default)]
+    pub enabled: bool,
+    /// Draws between checks of the sequential tests
+    #[serde(default = "default_adaptive_batch_size")]
+    pub batch_size: u32,
+    /// Most draws for one market
+    #[serde(default = "default_adaptive_max_simulations")]
+    pub max_simulations: u32,
+    /// Milliseconds one market may simulate before deciding on the draws so far
+    #[serde(default = "default_adaptive_time_budget_ms")]
+    pub time_budget_ms: u64,
+    /// Probability either side of an edge threshold within which either
+    /// answer is acceptable
+    #[serde(default = "default_adaptive_indifference")]
+    pub indifference: f64,
+    /// Chance of calling an edge that is not there, and of missing one that is
+    #[serde(default = "default_adaptive_error_rate")]
+    pub error_rate: f64,
+}
+
+impl Default for AdaptiveSimulationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            batch_size: default_adaptive_batch_size(),
+            max_simulations: default_adaptive_max_simulations(),
+            time_budget_ms: default_adaptive_time_budget_ms(),
+            indifference: default_adaptive_indifference(),
+            error_rate: default_adaptive_error_rate(),
+        }
+    }
+}
+
+fn default_adaptive_batch_size() -> u32 {
+    500
+}
+
+fn default_adaptive_max_simulations() -> u32 {
+    100_000
+}
+
+fn default_adaptive_time_budget_ms() -> u64 {
+    50
+}
+
+fn default_adaptive_indifference() -> f64 {
+    0.005
+}
+
+fn default_adaptive_error_rate() -> f64 {
+    0.01
+}
+
+/// Where the Poisson strategy gets each side's expected score
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LambdaSource {
+    /// League-typical scoring for every game
+    #[default]
+    Fixed,
+    /// Partially pooled attack/defense ratings fitted to stored results
+    Hierarchical,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ComboConfig {
+    pub min_edge_pct: f64,
+    pub simulation_count: u32,
+}
+
+impl Default for ComboConfig {
+    fn default() -> Self {
+        Self {
+            min_edge_pct: 4.0,
+            simulation_count: 20000,
+        }
+    }
+}
+
+/// A user-written Rhai script run as a strategy, and the limits it runs under
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScriptedConfig {
+    /// Script defining `generate_signal(market, context)`
+    #[serde(default = "default_script_path")]
+    pub script_path: String,
+    /// Interpreter operations per call before the call is aborted
+    #[serde(default = "default_script_max_operations")]
+    pub max_operations: u64,
+    #[serde(default = "default_script_max_call_levels")]
+    pub max_call_levels: usize,
+    /// Longest string, array or map a script may build
+    #[serde(default = "default_script_max_collection_size")]
+    pub max_collection_size: usize,
+    /// Edge at the ask, in percent, a script's signal must clear to trade
+    #[serde(default = "default_script_min_edge_pct")]
+    pub min_edge_pct: f64,
+}
+
+impl Default for ScriptedConfig {
+    fn default() -> Self {
+        Self {
+            script_path: default_script_path(),
+            max_operations: default_script_max_operations(),
+            max_call_levels: default_script_max_call_levels(),
+            max_collection_size: default_script_max_collection_size(),
+            min_edge_pct: default_script_min_edge_pct(),
+        }
+    }
+}
+
+fn default_script_path() -> String {
+    "strategies/custom.rhai".to_string()
+}
+
+fn default_script_max_operations() -> u64 {
+    100_000
+}
+
+fn default_script_max_call_levels() -> usize {
+    32
+}
+
+fn default_script_max_collection_size() -> usize {
+    10_000
+}
+
+fn default_script_min_edge_pct() -> f64 {
+    3.0
+}
+
+/// An external model server providing fair values
+#[derive(Debug, Clone, Deserialize)]
+pub struct RemoteModelConfig {
+    /// Base URL; markets are posted to `{url}/predict`
+    #[serde(default = "default_remote_model_url")]
+    pub url: String,
+    #[serde(default = "default_remote_model_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Markets per request
+    #[serde(default = "default_remote_model_batch_size")]
+    pub batch_size: usize,
+    #[serde(default = "default_remote_model_min_edge_pct")]
+    pub min_edge_pct: f64,
+}
+
+impl Default for RemoteModelConfig {
+    fn default() -> Self {
+        Self {
+            url: default_remote_model_url(),
+            timeout_ms: default_remote_model_timeout_ms(),
+            batch_size: default_remote_model_batch_size(),
+            min_edge_pct: default_remote_model_min_edge_pct(),
+        }
+    }
+}
+
+fn default_remote_model_url() -> String {
+    "http://127.0.0.1:8500".to_string()
+}
+
+fn default_remote_model_timeout_ms() -> u64 {
+    2000
+}
+
+fn default_remote_model_batch_size() -> usize {
+    200
+}
+
+fn default_remote_model_min_edge_pct() -> f64 {
+    4.0
+}
+
+/// Re-evaluating only markets that moved, between periodic full sweeps
+#[derive(Debug, Clone, Deserialize)]
+pub struct IncrementalConfig {
+    #[serde(default = "default_incremental_enabled")]
+    pub enabled: bool,
+    /// Move in YES price or bookmaker YES probability that marks a market for re-evaluation
+    #[serde(default = "default_dirty_threshold")]
+    pub price_move_threshold: f64,
+    /// Every market is re-evaluated at least this often
+    #[serde(default = "default_full_sweep_interval_secs")]
+    pub full_sweep_interval_secs: u64,
+}
+
+fn default_incremental_enabled() -> bool {
+    true
+}
+
+fn default_dirty_threshold() -> f64 {
+    0.005
+}
+
+fn default_full_sweep_interval_secs() -> u64 {
+    600
+}
+
+impl Default for IncrementalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_incremental_enabled(),
+            price_move_threshold: default_dirty_threshold(),
+            full_sweep_interval_secs: default_full_sweep_interval_secs(),
+        }
+    }
+}
+
+/// How often each market is evaluated, by sport and time to its start
+#[derive(Debug, Clone, Deserialize)]
+pub struct CadenceConfig {
+    /// Checked in order; the first tier covering a market applies
+    #[serde(default = "default_cadence_tiers")]
+    pub tiers: Vec<CadenceTierConfig>,
+    /// Interval for markets no tier covers
+    #[serde(default = "default_cadence_interval_secs")]
+    pub default_interval_secs: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CadenceTierConfig {
+    /// Applies to markets starting within this many minutes (or already started)
+    pub within_mins: i64,
+    pub interval_secs: u64,
+    /// Sports the tier applies to; all when absent
+    #[serde(default)]
+    pub sports: Option<Vec<Sport>>,
+}
+
+fn default_cadence_tiers() -> Vec<CadenceTierConfig> {
+    let tier = |within_mins, interval_secs| CadenceTierConfig { within_mins, interval_secs, sports: None };
+    vec![tier(60, 15), tier(24 * 60, 60), tier(3 * 24 * 60, 300)]
+}
+
+fn default_cadence_interval_secs() -> u64 {
+    600
+}
+
+impl Default for CadenceConfig {
+    fn default() -> Self {
+        Self {
+            tiers: default_cadence_tiers(),
+            default_interval_secs: default_cadence_interval_secs(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FuturesConfig {
+    pub min_edge_pct: f64,
+    /// Seasons simulated per run; runs are reused until ratings, results or injuries change
+    pub simulation_count: u32,
+}
+
+impl Default for FuturesConfig {
+    fn default() -> Self {
+        Self {
+            min_edge_pct: 6.0,
+            simulation_count: 10000,
+        }
+    }
+}
+
+/// Pairs of spread or total markets bought either side of a gap between
+/// Polymarket's lines and the bookmakers'
+#[derive(Debug, Clone, Deserialize)]
+pub struct MiddleConfig {
+    /// Expected profit per pair of shares, in percent, after both legs' cost
+    pub min_ev_pct: f64,
+    /// Points a leg's Polymarket-implied mean must sit off the bookmaker
+    /// consensus
+    pub min_line_gap: f64,
+}
+
+impl Default for MiddleConfig {
+    fn default() -> Self {
+        Self {
+            min_ev_pct: 3.0,
+            min_line_gap: 1.0,
+        }
+    }
+}
+
+/// MLB moneylines and totals priced off the announced starters, the park
+/// and how much each bullpen has thrown lately
+#[derive(Debug, Clone, Deserialize)]
+pub struct MlbConfig {
+    #[serde(default = "default_mlb_min_edge_pct")]
+    pub min_edge_pct: f64,
+    /// League earned run average starters are measured against
+    #[serde(default = "default_league_era")]
+    pub league_era: f64,
+    /// Innings of league-average pitching blended into every starter's ERA
+    #[serde(default = "default_era_prior_innings")]
+    pub era_prior_innings: f64,
+    /// Innings a starter is expected to cover; the bullpen takes the rest
+    #[serde(default = "default_starter_innings")]
+    pub starter_innings: f64,
+    /// Days of relief innings counted toward bullpen fatigue
+    #[serde(default = "default_bullpen_window_days")]
+    pub bullpen_window_days: i64,
+    /// Runs allowed by the bullpen rise this much per relief inning thrown
+    /// in the window
+    #[serde(default = "default_bullpen_fatigue_per_inning")]
+    pub bullpen_fatigue_per_inning: f64,
+    #[serde(default = "default_max_bullpen_fatigue")]
+    pub max_bullpen_fatigue: f64,
+    /// Run factor of each home team's park, 1.0 for neutral; unlisted parks
+    /// are neutral
+    #[serde(default = "default_park_factors")]
+    pub park_factors: HashMap<String, f64>,
+}
+
+impl Default for MlbConfig {
+    fn default() -> Self {
+        Self {
+            min_edge_pct: default_mlb_min_edge_pct(),
+            league_era: default_league_era(),
+            era_prior_innings: default_era_prior_innings(),
+            starter_innings: default_starter_innings(),
+            bullpen_window_days: default_bullpen_window_days(),
+            bullpen_fatigue_per_inning: default_bullpen_fatigue_per_inning(),
+            max_bullpen_fatigue: default_max_bullpen_fatigue(),
+            park_factors: default_park_factors(),
+        }
+    }
+}
+
+fn default_mlb_min_edge_pct() -> f64 {
+    5.0
+}
+
+fn default_league_era() -> f64 {
+    4.2
+}
+
+fn default_era_prior_innings() -> f64 {
+    40.0
+}
+
+fn default_starter_innings() -> f64 {
+    5.5
+}
+
+fn default_bullpen_window_days() -> i64 {
+    3
+}
+
+fn default_bullpen_fatigue_per_inning() -> f64 {
+    0.01
+}
+
+fn default_max_bullpen_fatigue() -> f64 {
+    0.15
+}
+
+fn default_park_factors() -> HashMap<String, f64> {
+    // The parks furthest from neutral; the rest are close enough to 1.0
+    HashMap::from([
+        ("Colorado Rockies".to_string(), 1.12),
+        ("Cincinnati Reds".to_string(), 1.06),
+        ("Boston Red Sox".to_string(), 1.05),
+        ("Seattle Mariners".to_string(), 0.92),
+        ("San Diego Padres".to_string(), 0.94),
+        ("Oakland Athletics".to_string(), 0.95),
+    ])
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewsScalpConfig {
+    pub execution_timeout_seconds: u64,
+    pub exit_after_minutes: u64,
+    pub twitter_bearer_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RiskConfig {
+    pub starting_capital: f64,
+    pub max_position_size_pct: f64,
+    pub daily_drawdown_limit_pct: f64,
+    pub max_correlation: f64,
+    pub min_market_liquidity: f64,
+    pub max_daily_trades: i32,
+    pub kelly_fraction: f64,
+    /// USDC that must be offered at a signal's executable price for it to be traded
+    #[serde(default = "default_min_executable_notional")]
+    pub min_executable_notional: f64,
+    /// Smallest position worth opening, in USDC; smaller stakes are skipped
+    /// rather than letting gas and fees eat them
+    #[serde(default = "default_min_trade_notional")]
+    pub min_trade_notional: f64,
+    /// Largest position opened in one trade, in USDC, whatever Kelly says
+    #[serde(default = "default_max_trade_notional")]
+    pub max_trade_notional: f64,
+    /// Per-market overrides of the trade notional bounds, keyed by market id
+    #[serde(default)]
+    pub market_notional: HashMap<String, TradeNotionalConfig>,
+    /// USDC of gas and fees per day after which new entries are blocked
+    #[serde(default = "default_daily_execution_cost_budget")]
+    pub daily_execution_cost_budget: f64,
+    /// Seconds between marks of open positions to market
+    #[serde(default = "default_position_mark_interval_secs")]
+    pub position_mark_interval_secs: u64,
+    /// Unrealized loss, as a percentage of cost, at which an open position alerts
+    #[serde(default = "default_position_loss_alert_pct")]
+    pub position_loss_alert_pct: f64,
+    /// Seconds between samples of open CLV trades' divergence from the sharp line
+    #[serde(default = "default_convergence_sample_interval_secs")]
+    pub convergence_sample_interval_secs: u64,
+    /// Seconds between checks of open trades on resolved or disputed markets
+    #[serde(default = "default_settlement_check_interval_secs")]
+    pub settlement_check_interval_secs: u64,
+    /// Largest gap between the reported and on-chain payout per share that
+    /// still settles without review
+    #[serde(default = "default_settlement_payout_tolerance")]
+    pub settlement_payout_tolerance: f64,
+    /// Seconds between raising the bankroll positions are sized from to total
+    /// capital; a falling bankroll is followed at once. 0 follows every change
+    #[serde(default = "default_sizing_rebase_interval_secs")]
+    pub sizing_rebase_interval_secs: u64,
+    /// USDC the wallet may hold below the book before an alert, in live mode
+    #[serde(default = "default_balance_reconcile_tolerance_usd")]
+    pub balance_reconcile_tolerance_usd: f64,
+    /// Capital split and limits per venue; when empty all capital sits on Polymarket
+    #[serde(default)]
+    pub venues: HashMap<String, VenueRiskConfig>,
+    #[serde(default)]
+    pub exposure_limits: ExposureLimitsConfig,
+    #[serde(default)]
+    pub depth_sizing: DepthSizingConfig,
+    #[serde(default)]
+    pub exits: ExitThresholdsConfig,
+    #[serde(default)]
+    pub drift: DriftConfig,
+    #[serde(default)]
+    pub throttle: ThrottleConfig,
+    #[serde(default)]
+    pub weights: WeightsConfig,
+    #[serde(default)]
+    pub edge_decay: EdgeDecayConfig,
+    #[serde(default)]
+    pub consistency: ConsistencyConfig,
+    #[serde(default)]
+    pub escalation: EscalationConfig,
+}
+
+fn default_min_executable_notional() -> f64 {
+    100.0
+}
+
+fn default_min_trade_notional() -> f64 {
+    25.0
+}
+
+fn default_max_trade_notional() -> f64 {
+    2500.0
+}
+
+/// Trade notional bounds for one market; unset bounds fall back to the global ones
+#[derive(Debug, Clone, Deserialize)]
+pub struct TradeNotionalConfig {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+fn default_daily_execution_cost_budget() -> f64 {
+    250.0
+}
+
+fn default_position_mark_interval_secs() -> u64 {
+    5
+}
+
+fn default_position_loss_alert_pct() -> f64 {
+    30.0
+}
+
+fn default_convergence_sample_interval_secs() -> u64 {
+    300
+}
+
+fn default_settlement_check_interval_secs() -> u64 {
+    300
+}
+
+fn default_settlement_payout_tolerance() -> f64 {
+    0.01
+}
+
+fn default_balance_reconcile_tolerance_usd() -> f64 {
+    5.0
+}
+
+fn default_sizing_rebase_interval_secs() -> u64 {
+    86400
+}
+
+/// Concentration limits on open exposure, as percentages of total capital
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExposureLimitsConfig {
+    #[serde(default = "default_sport_exposure_pct")]
+    pub sport_pct: f64,
+    #[serde(default = "default_event_exposure_pct")]
+    pub event_pct: f64,
+    #[serde(default = "default_strategy_exposure_pct")]
+    pub strategy_pct: f64,
+}
+
+impl Default for ExposureLimitsConfig {
+    fn default() -> Self {
+        Self {
+            sport_pct: default_sport_exposure_pct(),
+            event_pct: default_event_exposure_pct(),
+            strategy_pct: default_strategy_exposure_pct(),
+        }
+    }
+}
+
+fn default_sport_exposure_pct() -> f64 {
+    30.0
+}
+
+fn default_event_exposure_pct() -> f64 {
+    10.0
+}
+
+fn default_strategy_exposure_pct() -> f64 {
+    40.0
+}
+
+/// How a game's moneyline, spreads and totals are checked against each other
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConsistencyConfig {
+    #[serde(default = "default_consistency_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_consistency_check_interval_secs")]
+    pub check_interval_secs: u64,
+    /// Locked-in profit per share, at the asks, that flags two markets as arbitrage
+    #[serde(default = "default_min_arbitrage_edge")]
+    pub min_arbitrage_edge: f64,
+    /// Distance from the probability the game's other markets imply that
+    /// flags a market as inconsistent
+    #[serde(default = "default_max_model_gap")]
+    pub max_model_gap: f64,
+}
+
+impl Default for ConsistencyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_consistency_enabled(),
+            check_interval_secs: default_consistency_check_interval_secs(),
+            min_arbitrage_edge: default_min_arbitrage_edge(),
+            max_model_gap: default_max_model_gap(),
+        }
+    }
+}
+
+fn default_consistency_enabled() -> bool {
+    true
+}
+
+fn default_consistency_check_interval_secs() -> u64 {
+    300
+}
+
+fn default_min_arbitrage_edge() -> f64 {
+    0.01
+}
+
+fn default_max_model_gap() -> f64 {
+    0.10
+}
+
+/// Caps each stake at what the stored order book can fill near its top
+#[derive(Debug, Clone, Deserialize)]
+pub struct DepthSizingConfig {
+    #[serde(default = "default_depth_sizing_enabled")]
+    pub enabled: bool,
+    /// How far above the best offer, in basis points of price, a stake may
+    /// fill into the book
+    #[serde(default = "default_depth_sizing_max_slippage_bps")]
+    pub max_slippage_bps: f64,
+    /// Books older than this are not trusted and leave the stake uncapped
+    #[serde(default = "default_depth_sizing_max_book_age_secs")]
+    pub max_book_age_secs: u64,
+}
+
+impl Default for DepthSizingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_depth_sizing_enabled(),
+            max_slippage_bps: default_depth_sizing_max_slippage_bps(),
+            max_book_age_secs: default_depth_sizing_max_book_age_secs(),
+        }
+    }
+}
+
+fn default_depth_sizing_enabled() -> bool {
+    true
+}
+
+fn default_depth_sizing_max_slippage_bps() -> f64 {
+    100.0
+}
+
+fn default_depth_sizing_max_book_age_secs() -> u64 {
+    300
+}
+
+/// Stop-loss and take-profit levels the position monitor closes trades at,
+/// as percentages of cost; unset levels are not traded on
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ExitThresholdsConfig {
+    /// Loss at the bid, as a percentage of cost, at which a position is closed
+    #[serde(default)]
+    pub stop_loss_pct: Option<f64>,
+    /// Gain at the bid, as a percentage of cost, at which a position is closed
+    #[serde(default)]
+    pub take_profit_pct: Option<f64>,
+    /// Per-strategy overrides, keyed like `strategies.enabled_strategies`;
+    /// unset levels fall back to the global ones
+    #[serde(default)]
+    pub strategies: HashMap<String, StrategyExitConfig>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StrategyExitConfig {
+    #[serde(default)]
+    pub stop_loss_pct: Option<f64>,
+    #[serde(default)]
+    pub take_profit_pct: Option<f64>,
+}
+
+/// When a strategy's recent calibration and CLV have drifted far enough from
+/// its own history to stop trading it
+#[derive(Debug, Clone, Deserialize)]
+pub struct DriftConfig {
+    #[serde(default = "default_drift_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_drift_check_interval_secs")]
+    pub check_interval_secs: u64,
+    /// Most recent closed trades compared against the baseline
+    #[serde(default = "default_drift_recent_trades")]
+    pub recent_trades: usize,
+    /// How far back the baseline reaches
+    #[serde(default = "default_drift_baseline_days")]
+    pub baseline_days: i64,
+    /// Baseline trades (older than the recent window) needed before drift is judged
+    #[serde(default = "default_drift_min_baseline_trades")]
+    pub min_baseline_trades: usize,
+    /// Rise in mean Brier score over the baseline that counts as drift
+    #[serde(default = "default_drift_max_brier_increase")]
+    pub max_brier_increase: f64,
+    /// Fall in mean CLV, in probability points, below the baseline that counts as drift
+    #[serde(default = "default_drift_max_clv_drop")]
+    pub max_clv_drop: f64,
+}
+
+impl Default for DriftConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_drift_enabled(),
+            check_interval_secs: default_drift_check_interval_secs(),
+            recent_trades: default_drift_recent_trades(),
+            baseline_days: default_drift_baseline_days(),
+            min_baseline_trades: default_drift_min_baseline_trades(),
+            max_brier_increase: default_drift_max_brier_increase(),
+            max_clv_drop: default_drift_max_clv_drop(),
+        }
+    }
+}
+
+fn default_drift_enabled() -> bool {
+    true
+}
+
+fn default_drift_check_interval_secs() -> u64 {
+    3600
+}
+
+fn default_drift_recent_trades() -> usize {
+    30
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RedisConfig {
-    pub url: String,
-    pub pool_size: u32,
+fn default_drift_baseline_days() -> i64 {
+    180
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct BlockchainConfig {
-    pub polygon_rpc_url: String,
-    pub polygon_ws_url: String,
-    pub private_key: String,
-    pub gas_limit: u64,
-    pub max_gas_price_gwei: u64,
+fn default_drift_min_baseline_trades() -> usize {
+    50
 }
 
+fn default_drift_max_brier_increase() -> f64 {
+    0.03
+}
+
+fn default_drift_max_clv_drop() -> f64 {
+    0.02
+}
+
+/// Scaling a strategy's size and daily signal cap down while its realized
+/// edge is negative, and back up as it recovers
 #[derive(Debug, Clone, Deserialize)]
-pub struct PolymarketConfig {
-    pub api_url: String,
-    pub ws_url: String,
-    pub api_key: Option<String>,
-    pub ctf_exchange_address: String,
-    pub conditional_tokens_address: String,
+pub struct ThrottleConfig {
+    #[serde(default = "default_throttle_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_throttle_check_interval_secs")]
+    pub check_interval_secs: u64,
+    /// Most recent closed trades the rolling realized edge is measured over
+    #[serde(default = "default_throttle_window_trades")]
+    pub window_trades: usize,
+    /// Closed trades needed before a strategy is throttled or restored
+    #[serde(default = "default_throttle_min_trades")]
+    pub min_trades: usize,
+    /// How far back closed trades are looked for
+    #[serde(default = "default_throttle_lookback_days")]
+    pub lookback_days: i64,
+    /// Factor applied to size per step down (and undone per step up)
+    #[serde(default = "default_throttle_step")]
+    pub step: f64,
+    /// Smallest fraction of normal size a strategy is throttled to
+    #[serde(default = "default_throttle_min_size_multiplier")]
+    pub min_size_multiplier: f64,
+    /// Realized edge needed to step back up; between zero and this the level holds
+    #[serde(default = "default_throttle_recover_edge")]
+    pub recover_edge: f64,
+}
+
+impl Default for ThrottleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_throttle_enabled(),
+            check_interval_secs: default_throttle_check_interval_secs(),
+            window_trades: default_throttle_window_trades(),
+            min_trades: default_throttle_min_trades(),
+            lookback_days: default_throttle_lookback_days(),
+            step: default_throttle_step(),
+            min_size_multiplier: default_throttle_min_size_multiplier(),
+            recover_edge: default_throttle_recover_edge(),
+        }
+    }
 }
 
+fn default_throttle_enabled() -> bool {
+    true
+}
+
+fn default_throttle_check_interval_secs() -> u64 {
+    3600
+}
+
+fn default_throttle_window_trades() -> usize {
+    30
+}
+
+fn default_throttle_min_trades() -> usize {
+    10
+}
+
+fn default_throttle_lookback_days() -> i64 {
+    90
+}
+
+fn default_throttle_step() -> f64 {
+    0.5
+}
+
+fn default_throttle_min_size_multiplier() -> f64 {
+    0.25
+}
+
+fn default_throttle_recover_edge() -> f64 {
+    0.01
+}
+
+/// Solving for the strategy capital weights that maximize portfolio Sharpe,
+/// each capped at `exposure_limits.strategy_pct`
 #[derive(Debug, Clone, Deserialize)]
-pub struct StrategiesConfig {
-    pub clv_arb: ClvArbConfig,
-    pub poisson_ev: PoissonEvConfig,
-    pub news_scalp: NewsScalpConfig,
-    pub enabled_strategies: Vec<String>,
+pub struct WeightsConfig {
+    #[serde(default = "default_weights_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_weights_check_interval_secs")]
+    pub check_interval_secs: u64,
+    /// Days of closed-trade history the return series cover
+    #[serde(default = "default_weights_lookback_days")]
+    pub lookback_days: i64,
+    /// Days with closed trades needed before a strategy is weighted
+    #[serde(default = "default_weights_min_days")]
+    pub min_days: usize,
+    /// Least size multiplier of a weighted strategy, so one weighted to zero
+    /// keeps trading and earning the returns it is judged on
+    #[serde(default = "default_weights_min_multiplier")]
+    pub min_multiplier: f64,
+    /// Size multiplier of strategies without a weight, being new or short
+    /// of `min_days`, once any strategy has one
+    #[serde(default = "default_weights_unweighted_multiplier")]
+    pub unweighted_multiplier: f64,
+}
+
+impl Default for WeightsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_weights_enabled(),
+            check_interval_secs: default_weights_check_interval_secs(),
+            lookback_days: default_weights_lookback_days(),
+            min_days: default_weights_min_days(),
+            min_multiplier: default_weights_min_multiplier(),
+            unweighted_multiplier: default_weights_unweighted_multiplier(),
+        }
+    }
+}
+
+fn default_weights_enabled() -> bool {
+    true
+}
+
+fn default_weights_check_interval_secs() -> u64 {
+    86400
+}
+
+fn default_weights_lookback_days() -> i64 {
+    90
+}
+
+fn default_weights_min_days() -> usize {
+    20
+}
+
+fn default_weights_min_multiplier() -> f64 {
+    0.1
+}
+
+fn default_weights_unweighted_multiplier() -> f64 {
+    0.25
 }
 
+/// Fitting how fast each strategy's edge decays with signal age, so late
+/// executions trade on what is left of it
 #[derive(Debug, Clone, Deserialize)]
-pub struct ClvArbConfig {
-    pub min_divergence_pct: f64,
-    pub exit_on_convergence: bool,
-    pub max_hold_hours: u64,
+pub struct EdgeDecayConfig {
+    #[serde(default = "default_edge_decay_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_edge_decay_check_interval_secs")]
+    pub check_interval_secs: u64,
+    /// Days of signals taken up the fit covers
+    #[serde(default = "default_edge_decay_lookback_days")]
+    pub lookback_days: i64,
+    /// Signals taken up needed before a strategy's decay is fitted
+    #[serde(default = "default_edge_decay_min_samples")]
+    pub min_samples: usize,
+}
+
+impl Default for EdgeDecayConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_edge_decay_enabled(),
+            check_interval_secs: default_edge_decay_check_interval_secs(),
+            lookback_days: default_edge_decay_lookback_days(),
+            min_samples: default_edge_decay_min_samples(),
+        }
+    }
 }
 
+/// Staged size increases for large edges that persist into the event start,
+/// once fresh prices and a second bookmaker confirm them
 #[derive(Debug, Clone, Deserialize)]
-pub struct PoissonEvConfig {
-    pub min_edge_pct: f64,
-    pub simulation_count: u32,
-    pub min_significance: f64,
+pub struct EscalationConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Edge, in probability points, below which signals are sized as usual
+    #[serde(default = "default_escalation_min_edge")]
+    pub min_edge: f64,
+    /// Minutes before the event start within which edges may be escalated
+    #[serde(default = "default_escalation_window_minutes")]
+    pub window_minutes: i64,
+    /// Longest gap between signals on the market still counted as the same edge
+    #[serde(default = "default_escalation_max_gap_secs")]
+    pub max_gap_secs: i64,
+    /// Market prices older than this fail the freshness check
+    #[serde(default = "default_escalation_max_price_age_secs")]
+    pub max_price_age_secs: i64,
+    /// Bookmaker lines older than this do not count as confirmation
+    #[serde(default = "default_escalation_max_odds_age_secs")]
+    pub max_odds_age_secs: i64,
+    /// Bookmakers whose fresh lines must each show `min_edge`
+    #[serde(default = "default_escalation_min_sources")]
+    pub min_sources: usize,
+    /// Rungs of the ladder by how long the edge has persisted
+    #[serde(default = "default_escalation_stages")]
+    pub stages: Vec<EscalationStageConfig>,
 }
 
+/// Stakes of an edge persisting `after_secs` are multiplied by `multiplier`
 #[derive(Debug, Clone, Deserialize)]
-pub struct NewsScalpConfig {
-    pub execution_timeout_seconds: u64,
-    pub exit_after_minutes: u64,
-    pub twitter_bearer_token: Option<String>,
+pub struct EscalationStageConfig {
+    pub after_secs: i64,
+    pub multiplier: f64,
+}
+
+impl Default for EscalationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_edge: default_escalation_min_edge(),
+            window_minutes: default_escalation_window_minutes(),
+            max_gap_secs: default_escalation_max_gap_secs(),
+            max_price_age_secs: default_escalation_max_price_age_secs(),
+            max_odds_age_secs: default_escalation_max_odds_age_secs(),
+            min_sources: default_escalation_min_sources(),
+            stages: default_escalation_stages(),
+        }
+    }
+}
+
+fn default_escalation_min_edge() -> f64 {
+    0.08
+}
+
+fn default_escalation_window_minutes() -> i64 {
+    120
+}
+
+fn default_escalation_max_gap_secs() -> i64 {
+    600
+}
+
+fn default_escalation_max_price_age_secs() -> i64 {
+    60
+}
+
+fn default_escalation_max_odds_age_secs() -> i64 {
+    120
+}
+
+fn default_escalation_min_sources() -> usize {
+    2
+}
+
+fn default_escalation_stages() -> Vec<EscalationStageConfig> {
+    vec![
+        EscalationStageConfig { after_secs: 300, multiplier: 1.25 },
+        EscalationStageConfig { after_secs: 900, multiplier: 1.5 },
+        EscalationStageConfig { after_secs: 1800, multiplier: 2.0 },
+    ]
+}
+
+fn default_edge_decay_enabled() -> bool {
+    true
+}
+
+fn default_edge_decay_check_interval_secs() -> u64 {
+    21600
+}
+
+fn default_edge_decay_lookback_days() -> i64 {
+    60
+}
+
+fn default_edge_decay_min_samples() -> usize {
+    30
 }
 
 #[derive(Debug, Clone, Deserialize)]
-pub struct RiskConfig {
+pub struct VenueRiskConfig {
+    /// Portion of `starting_capital` allocated to this venue
     pub starting_capital: f64,
-    pub max_position_size_pct: f64,
-    pub daily_drawdown_limit_pct: f64,
-    pub max_correlation: f64,
-    pub min_market_liquidity: f64,
-    pub max_daily_trades: i32,
-    pub kelly_fraction: f64,
+    /// Falls back to the portfolio-wide `daily_drawdown_limit_pct`
+    pub daily_drawdown_limit_pct: Option<f64>,
+}
+
+impl RiskConfig {
+    /// Venue limits, defaulting to a single Polymarket venue holding all capital
+    pub fn venue_limits(&self) -> HashMap<String, VenueRiskConfig> {
+        if !self.venues.is_empty() {
+            return self.venues.clone();
+        }
+
+        HashMap::from([(
+            POLYMARKET_VENUE.to_string(),
+            VenueRiskConfig {
+                starting_capital: self.starting_capital,
+                daily_drawdown_limit_pct: None,
+            },
+        )])
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -93,6 +2641,182 @@ pub struct MonitoringConfig {
     pub dashboard_port: u16,
     pub telegram_bot_token: Option<String>,
     pub telegram_chat_id: Option<String>,
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+    #[serde(default)]
+    pub incidents: IncidentsConfig,
+    #[serde(default)]
+    pub latency: LatencyConfig,
+    #[serde(default)]
+    pub calibration: CalibrationConfig,
+    #[serde(default)]
+    pub dead_letters: DeadLettersConfig,
+}
+
+/// Counts and digest of rows that failed to map
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeadLettersConfig {
+    /// Hours of dead letters the exported counts cover
+    #[serde(default = "default_dead_letter_window_hours")]
+    pub window_hours: i64,
+    /// Seconds between digests of newly seen dead letters
+    #[serde(default = "default_dead_letter_digest_interval_secs")]
+    pub digest_interval_secs: u64,
+}
+
+impl Default for DeadLettersConfig {
+    fn default() -> Self {
+        Self {
+            window_hours: default_dead_letter_window_hours(),
+            digest_interval_secs: default_dead_letter_digest_interval_secs(),
+        }
+    }
+}
+
+fn default_dead_letter_window_hours() -> i64 {
+    24
+}
+
+fn default_dead_letter_digest_interval_secs() -> u64 {
+    86400
+}
+
+/// Reliability diagram and edge-vs-return exports for the calibration dashboard
+#[derive(Debug, Clone, Deserialize)]
+pub struct CalibrationConfig {
+    /// Days of closed trades the diagrams are drawn from
+    #[serde(default = "default_calibration_lookback_days")]
+    pub lookback_days: i64,
+    /// Probability bins in the reliability diagram, and edge bins in the scatter
+    #[serde(default = "default_calibration_bins")]
+    pub bins: usize,
+}
+
+impl Default for CalibrationConfig {
+    fn default() -> Self {
+        Self {
+            lookback_days: default_calibration_lookback_days(),
+            bins: default_calibration_bins(),
+        }
+    }
+}
+
+fn default_calibration_lookback_days() -> i64 {
+    180
+}
+
+fn default_calibration_bins() -> usize {
+    10
+}
+
+/// Per-stage latency budgets, alerted on when the p95 goes over
+#[derive(Debug, Clone, Deserialize)]
+pub struct LatencyConfig {
+    /// Seconds of recent samples the p95 is taken over
+    #[serde(default = "default_latency_window_secs")]
+    pub window_secs: u64,
+    /// Fewer samples than this in the window are not judged
+    #[serde(default = "default_latency_min_samples")]
+    pub min_samples: usize,
+    /// Bookmaker update to odds received
+    #[serde(default = "default_odds_ingest_budget_ms")]
+    pub odds_ingest_ms: u64,
+    /// Market marked moved to signal stored
+    #[serde(default = "default_signal_budget_ms")]
+    pub signal_ms: u64,
+    /// Signal generated to order filled
+    #[serde(default = "default_execution_budget_ms")]
+    pub execution_ms: u64,
+}
+
+impl Default for LatencyConfig {
+    fn default() -> Self {
+        Self {
+            window_secs: default_latency_window_secs(),
+            min_samples: default_latency_min_samples(),
+            odds_ingest_ms: default_odds_ingest_budget_ms(),
+            signal_ms: default_signal_budget_ms(),
+            execution_ms: default_execution_budget_ms(),
+        }
+    }
+}
+
+fn default_latency_window_secs() -> u64 {
+    900
+}
+
+fn default_latency_min_samples() -> usize {
+    20
+}
+
+fn default_odds_ingest_budget_ms() -> u64 {
+    5_000
+}
+
+fn default_signal_budget_ms() -> u64 {
+    2_000
+}
+
+fn default_execution_budget_ms() -> u64 {
+    15_000
+}
+
+/// Incident-grade paging for conditions that need a human now
+#[derive(Debug, Clone, Deserialize)]
+pub struct IncidentsConfig {
+    /// Events API v2 integration key
+    #[serde(default)]
+    pub pagerduty_routing_key: Option<String>,
+    #[serde(default)]
+    pub opsgenie_api_key: Option<String>,
+    /// Seconds a service loop may go without completing a cycle before it is stalled
+    #[serde(default = "default_stall_after_secs")]
+    pub stall_after_secs: u64,
+}
+
+impl Default for IncidentsConfig {
+    fn default() -> Self {
+        Self {
+            pagerduty_routing_key: None,
+            opsgenie_api_key: None,
+            stall_after_secs: default_stall_after_secs(),
+        }
+    }
+}
+
+fn default_stall_after_secs() -> u64 {
+    300
+}
+
+/// When operators hear about things: critical alerts go out at once,
+/// informational ones are batched into digests
+#[derive(Debug, Clone, Deserialize)]
+pub struct NotificationsConfig {
+    #[serde(default = "default_digest_interval_secs")]
+    pub digest_interval_secs: u64,
+    /// Window in which informational notifications are dropped
+    #[serde(default)]
+    pub quiet_hours: Option<QuietHoursConfig>,
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            digest_interval_secs: default_digest_interval_secs(),
+            quiet_hours: None,
+        }
+    }
+}
+
+fn default_digest_interval_secs() -> u64 {
+    3600
+}
+
+/// `HH:MM` times in UTC; the window may wrap past midnight
+#[derive(Debug, Clone, Deserialize)]
+pub struct QuietHoursConfig {
+    pub start: String,
+    pub end: String,
 }
 
 impl Config {
@@ -106,7 +2830,168 @@ impl Config {
             .add_source(config::Environment::with_prefix("TRADING_BOT"))
             .build()?;
         
-        Ok(config.try_deserialize()?)
+        let config: Self = config.try_deserialize()?;
+        config.validate_network()?;
+        Ok(config)
+    }
+
+    /// The profile of the configured network, from `blockchain.chains` or
+    /// the built-in ones
+    pub fn network_profile(&self) -> Result<NetworkProfile> {
+        let network = &self.blockchain.network;
+        self.blockchain.chains.get(network.as_str()).cloned()
+            .or_else(|| network.builtin_profile())
+            .ok_or_else(|| anyhow!(
+                "blockchain.network {} is neither built in nor described under blockchain.chains",
+                network
+            ))
+    }
+
+    /// Reject a market lock that could lapse while a transaction sent under
+    /// it is still being replaced, letting another engine trade the market
+    pub fn validate_market_lock(&self) -> Result<()> {
+        let longest_send = self.blockchain.fees.longest_send_secs();
+        if self.execution.market_lock_secs <= longest_send {
+            bail!(
+                "execution.market_lock_secs ({}) must be over the {}s a transaction may be resent for",
+                self.execution.market_lock_secs, longest_send
+            );
+        }
+        Ok(())
+    }
+
+    /// Reject a signal lease that could lapse while the signal's transaction
+    /// is still being replaced, letting another engine execute it again
+    pub fn validate_signal_lease(&self) -> Result<()> {
+        let longest_send = self.blockchain.fees.longest_send_secs();
+        if self.execution.signal_lease_secs <= longest_send {
+            bail!(
+                "execution.signal_lease_secs ({}) must be over the {}s a transaction may be resent for",
+                self.execution.signal_lease_secs, longest_send
+            );
+        }
+        Ok(())
+    }
+
+    /// Reject configs whose sections disagree on the network, or that point
+    /// at another network's contracts, so a testnet rehearsal can never send
+    /// to mainnet contracts (or the reverse)
+    pub fn validate_network(&self) -> Result<()> {
+        let network = &self.blockchain.network;
+        if self.polymarket.network != *network {
+            bail!(
+                "blockchain.network is {} but polymarket.network is {}",
+                network, self.polymarket.network
+            );
+        }
+
+        let profile = self.network_profile()?;
+        let mismatched: Vec<&str> = [
+            ("blockchain.usdc_address", self.blockchain.usdc_address.as_str(), profile.usdc_address.as_str()),
+            ("polymarket.ctf_exchange_address", self.polymarket.ctf_exchange_address.as_str(), profile.ctf_exchange_address.as_str()),
+            ("polymarket.conditional_tokens_address", self.polymarket.conditional_tokens_address.as_str(), profile.conditional_tokens_address.as_str()),
+        ]
+        .into_iter()
+        .filter(|(_, configured, expected)| !configured.eq_ignore_ascii_case(expected))
+        .map(|(key, _, _)| key)
+        .collect();
+
+        if !mismatched.is_empty() {
+            bail!("{} do not match the {} contracts", mismatched.join(", "), network);
+        }
+        if self.polymarket.clob_url.trim_end_matches('/') != profile.clob_url.trim_end_matches('/') {
+            bail!("polymarket.clob_url must be {} on {}", profile.clob_url, network);
+        }
+        Ok(())
+    }
+
+    /// The chain Polymarket is traded on, with its addresses parsed
+    pub fn chain(&self) -> Result<Chain> {
+        let profile = self.network_profile()?;
+        let address = |key: &str, value: &str| value.parse::<Address>().with_context(|| key.to_string());
+
+        Ok(Chain {
+            network: self.blockchain.network.clone(),
+            chain_id: profile.chain_id,
+            testnet: profile.testnet,
+            rpc_urls: std::iter::once(&self.blockchain.rpc_url)
+                .chain(&self.blockchain.fallback_rpc_urls)
+                .cloned()
+                .collect(),
+            ws_urls: std::iter::once(&self.blockchain.ws_url)
+                .chain(&self.blockchain.fallback_ws_urls)
+                .cloned()
+                .collect(),
+            usdc: address("blockchain.usdc_address", &self.blockchain.usdc_address)?,
+            ctf_exchange: address("polymarket.ctf_exchange_address", &self.polymarket.ctf_exchange_address)?,
+            conditional_tokens: address("polymarket.conditional_tokens_address", &self.polymarket.conditional_tokens_address)?,
+            gas_token_usd_feed: address("blockchain.gas_token_usd_feed", &self.blockchain.gas_token_usd_feed)?,
+        })
+    }
+
+    /// Create the storage backend selected in `database.backend`
+    ///
+    /// SQLite is only meant for paper trading and backtests; live trading
+    /// always runs against Postgres.
+    pub async fn create_storage(&self) -> Result<Arc<dyn Storage>> {
+        match self.database.backend {
+            StorageBackend::Postgres => {
+                let pool = self.create_db_pool().await?;
+                Ok(Arc::new(PostgresStorage::new(pool)))
+            }
+            StorageBackend::Sqlite => {
+                if self.mode == TradingMode::Live {
+                    bail!("SQLite storage is not supported in live mode; use Postgres");
+                }
+                let storage = SqliteStorage::connect(
+                    &self.database.url,
+                    self.database.max_connections,
+                ).await?;
+                Ok(Arc::new(storage))
+            }
+        }
+    }
+
+    /// Connection to the paper run's database, only read from, when
+    /// `api.paper_comparison.database_url` is set
+    pub async fn create_paper_storage(&self) -> Result<Option<Arc<dyn Storage>>> {
+        let Some(url) = &self.api.paper_comparison.database_url else {
+            return Ok(None);
+        };
+        if url == &self.database.url {
+            bail!("api.paper_comparison.database_url must be the paper run's database, not this one");
+        }
+
+        if url.starts_with("sqlite:") {
+            return Ok(Some(Arc::new(SqliteStorage::connect(url, 2).await?)));
+        }
+        let pool = PgPoolOptions::new()
+            .max_connections(2)
+            .acquire_timeout(Duration::from_secs(self.database.connection_timeout))
+            .connect(url)
+            .await?;
+        Ok(Some(Arc::new(PostgresStorage::new(pool))))
+    }
+
+    /// Create the order executor selected in `execution.executor`, or by `mode`
+    ///
+    /// Only live mode may send orders to the chain.
+    pub async fn create_executor(&self, storage: Arc<dyn Storage>, rpc: &ChainRpc) -> Result<Arc<dyn TradeExecutor>> {
+        let kind = self.execution.executor.unwrap_or(match self.mode {
+            TradingMode::Live => ExecutorKind::Live,
+            TradingMode::Paper | TradingMode::Backtest => ExecutorKind::Paper,
+        });
+
+        match kind {
+            ExecutorKind::Live => {
+                if self.mode != TradingMode::Live {
+                    bail!("execution.executor live requires mode live");
+                }
+                Ok(Arc::new(LiveExecutor::connect(self, rpc).await?))
+            }
+            ExecutorKind::Paper => Ok(Arc::new(PaperExecutor::new(storage, &self.execution.paper)?)),
+            ExecutorKind::Recording => Ok(Arc::new(RecordingExecutor::new())),
+        }
     }
 
     /// Create database connection pool
@@ -121,6 +3006,16 @@ impl Config {
         Ok(pool)
     }
 
+    /// Create the cache/pub-sub layer, falling back to in-process when Redis is disabled
+    pub async fn create_cache(&self) -> Result<Arc<dyn Cache>> {
+        if !self.redis.enabled {
+            return Ok(Arc::new(InProcessCache::new(self.redis.local_cache_capacity)));
+        }
+
+        let client = self.create_redis_client().await?;
+        Ok(Arc::new(RedisCache::new(client).await?))
+    }
+
     /// Create Redis client
     pub async fn create_redis_client(&self) -> Result<RedisClient> {
         let client = RedisClient::open(self.redis.url.clone())?;
@@ -134,50 +3029,101 @@ impl Config {
 impl Default for Config {
     fn default() -> Self {
         Self {
+            mode: TradingMode::Live,
             database: DatabaseConfig {
+                backend: StorageBackend::Postgres,
                 url: "postgresql://localhost/polymarket_bot".to_string(),
                 max_connections: 10,
                 min_connections: 2,
                 connection_timeout: 30,
             },
             redis: RedisConfig {
+                enabled: true,
                 url: "redis://localhost:6379".to_string(),
                 pool_size: 10,
+                local_cache_capacity: 10_000,
             },
+            reference_cache: ReferenceCacheConfig::default(),
             blockchain: BlockchainConfig {
-                polygon_rpc_url: "https://polygon-rpc.com".to_string(),
-                polygon_ws_url: "wss://polygon-rpc.com".to_string(),
+                network: Network::polygon(),
+                rpc_url: "https://polygon-rpc.com".to_string(),
+                ws_url: "wss://polygon-rpc.com".to_string(),
+                fallback_rpc_urls: Vec::new(),
+                fallback_ws_urls: Vec::new(),
+                failover: RpcFailoverConfig::default(),
                 private_key: String::new(),
                 gas_limit: 500000,
                 max_gas_price_gwei: 100,
+                entry_gas_units: default_entry_gas_units(),
+                usdc_address: default_usdc_address(),
+                gas_token_usd_feed: default_gas_token_usd_feed(),
+                chains: HashMap::new(),
+                fees: GasFeeConfig::default(),
             },
             polymarket: PolymarketConfig {
+                network: Network::polygon(),
                 api_url: "https://api.polymarket.com".to_string(),
-                ws_url: "wss://ws.polymarket.com".to_string(),
+                clob_url: default_clob_url(),
+                ws_url: "wss://ws-subscriptions-clob.polymarket.com/ws/market".to_string(),
                 api_key: None,
                 ctf_exchange_address: "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E".to_string(),
                 conditional_tokens_address: "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045".to_string(),
+                poll_interval_secs: 30,
+                stream: StreamConfig::default(),
+            },
+            fees: FeesConfig {
+                venues: HashMap::from([(
+                    "polymarket".to_string(),
+                    FeeScheduleConfig::default(),
+                )]),
             },
+            api: ApiConfig::default(),
+            odds: OddsConfig::default(),
+            officials: OfficialsConfig::default(),
+            pitchers: PitchersConfig::default(),
+            microstructure: MicrostructureConfig::default(),
+            whales: WhalesConfig::default(),
+            backfill: BackfillConfig::default(),
+            simulation: SimulationConfig::default(),
+            model_parameters: ModelParametersConfig::default(),
+            execution: ExecutionConfig::default(),
             strategies: StrategiesConfig {
                 clv_arb: ClvArbConfig {
                     min_divergence_pct: 3.0,
                     exit_on_convergence: true,
                     max_hold_hours: 24,
+                    fresh_odds_secs: default_fresh_odds_secs(),
+                    max_odds_age_secs: default_max_odds_age_secs(),
+                    large_trade_lookback_secs: default_large_trade_lookback_secs(),
                 },
                 poisson_ev: PoissonEvConfig {
                     min_edge_pct: 5.0,
                     simulation_count: 10000,
                     min_significance: 0.95,
+                    lambda_source: LambdaSource::Fixed,
+                    adaptive: AdaptiveSimulationConfig::default(),
                 },
                 news_scalp: NewsScalpConfig {
                     execution_timeout_seconds: 60,
                     exit_after_minutes: 15,
                     twitter_bearer_token: None,
                 },
+                combo: ComboConfig::default(),
+                futures: FuturesConfig::default(),
+                middle: MiddleConfig::default(),
+                mlb: MlbConfig::default(),
+                scripted: ScriptedConfig::default(),
+                remote_model: RemoteModelConfig::default(),
+                incremental: IncrementalConfig::default(),
+                cadence: CadenceConfig::default(),
                 enabled_strategies: vec![
                     "clv_arb".to_string(),
                     "poisson_ev".to_string(),
                 ],
+                sports: default_strategy_sports(),
+                max_execution_age: default_max_execution_age(),
+                time_in_force: default_time_in_force(),
+                cost_tolerance: default_cost_tolerance(),
             },
             risk: RiskConfig {
                 starting_capital: 50000.0,
@@ -187,13 +3133,113 @@ impl Default for Config {
                 min_market_liquidity: 5000.0,
                 max_daily_trades: 20,
                 kelly_fraction: 0.5,
+                min_executable_notional: default_min_executable_notional(),
+                min_trade_notional: default_min_trade_notional(),
+                max_trade_notional: default_max_trade_notional(),
+                market_notional: HashMap::new(),
+                daily_execution_cost_budget: default_daily_execution_cost_budget(),
+                position_mark_interval_secs: default_position_mark_interval_secs(),
+                position_loss_alert_pct: default_position_loss_alert_pct(),
+                convergence_sample_interval_secs: default_convergence_sample_interval_secs(),
+                settlement_check_interval_secs: default_settlement_check_interval_secs(),
+                settlement_payout_tolerance: default_settlement_payout_tolerance(),
+                sizing_rebase_interval_secs: default_sizing_rebase_interval_secs(),
+                balance_reconcile_tolerance_usd: default_balance_reconcile_tolerance_usd(),
+                venues: HashMap::new(),
+                exposure_limits: ExposureLimitsConfig::default(),
+                depth_sizing: DepthSizingConfig::default(),
+                exits: ExitThresholdsConfig::default(),
+                drift: DriftConfig::default(),
+                throttle: ThrottleConfig::default(),
+                weights: WeightsConfig::default(),
+                edge_decay: EdgeDecayConfig::default(),
+                consistency: ConsistencyConfig::default(),
+                escalation: EscalationConfig::default(),
             },
             monitoring: MonitoringConfig {
                 metrics_port: 9090,
                 dashboard_port: 3000,
                 telegram_bot_token: None,
                 telegram_chat_id: None,
+                notifications: NotificationsConfig::default(),
+                incidents: IncidentsConfig::default(),
+                latency: LatencyConfig::default(),
+                calibration: CalibrationConfig::default(),
+                dead_letters: DeadLettersConfig::default(),
             },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_mixed_network_profiles() {
+        let mut config = Config::default();
+        config.validate_network().unwrap();
+
+        config.blockchain.network = Network::amoy();
+        assert!(config.validate_network().is_err());
+
+        config.polymarket.network = Network::amoy();
+        assert!(config.validate_network().is_err(), "mainnet contracts on amoy");
+
+        let amoy = Network::amoy().builtin_profile().unwrap();
+        config.blockchain.usdc_address = amoy.usdc_address.to_string();
+        config.polymarket.ctf_exchange_address = amoy.ctf_exchange_address.to_string();
+        config.polymarket.conditional_tokens_address = amoy.conditional_tokens_address.to_string();
+        config.polymarket.clob_url = amoy.clob_url.to_string();
+        config.validate_network().unwrap();
+        assert_eq!(config.chain().unwrap().chain_id, 80002);
+
+        // A network the bot does not know needs a profile
+        config.blockchain.network = Network::new("base");
+        config.polymarket.network = Network::new("base");
+        assert!(config.validate_network().is_err());
+        config.blockchain.chains.insert("base".to_string(), NetworkProfile { chain_id: 8453, testnet: false, ..amoy });
+        config.validate_network().unwrap();
+        assert_eq!(config.chain().unwrap().chain_id, 8453);
+    }
+
+    #[test]
+    fn market_locks_outlast_every_replacement() {
+        let mut config = Config::default();
+        config.validate_market_lock().unwrap();
+        // 30s for each of the first send and three replacements
+        config.execution.market_lock_secs = 120;
+        assert!(config.validate_market_lock().is_err());
+    }
+
+    #[test]
+    fn signal_leases_outlast_every_replacement() {
+        let mut config = Config::default();
+        config.validate_signal_lease().unwrap();
+        config.execution.signal_lease_secs = 120;
+        assert!(config.validate_signal_lease().is_err());
+    }
+
+    #[test]
+    fn strategies_fall_back_to_default_cost_tolerance() {
+        let mut strategies = Config::default().strategies;
+        strategies.validate_cost_tolerance().unwrap();
+        assert_eq!(strategies.cost_tolerance("clv_arb").max_cost_share, 0.5);
+        assert_eq!(strategies.cost_tolerance("combo"), CostTolerance::default());
+
+        strategies.cost_tolerance.insert("combo".to_string(), CostTolerance { max_cost_share: 0.0, ..CostTolerance::default() });
+        assert!(strategies.validate_cost_tolerance().is_err());
+    }
+
+    #[test]
+    fn strategies_fall_back_to_the_default_execution_deadline() {
+        let mut strategies = Config::default().strategies;
+        assert_eq!(strategies.max_execution_age("news_scalp"), chrono::Duration::seconds(60));
+        assert_eq!(strategies.max_execution_age("combo"), chrono::Duration::seconds(300));
+        assert_eq!(strategies.longest_execution_age(), chrono::Duration::seconds(300));
+
+        // Pending signals are read back as far as the longest deadline
+        strategies.max_execution_age.insert("futures".to_string(), 3600);
+        assert_eq!(strategies.longest_execution_age(), chrono::Duration::seconds(3600));
+    }
+}
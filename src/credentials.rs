@@ -0,0 +1,181 @@
+use anyhow::{anyhow, bail, Context, Result};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::storage::Storage;
+use crate::types::SealedCredential;
+
+/// Environment variable holding the 32-byte master key, hex encoded
+pub const MASTER_KEY_ENV: &str = "TRADING_BOT_MASTER_KEY";
+
+/// A stored credential without its value
+#[derive(Debug, Clone, Serialize)]
+pub struct CredentialInfo {
+    pub provider: String,
+    pub name: String,
+    pub version: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Seals and opens API credentials kept in the `credentials` table
+///
+/// Values are encrypted with XChaCha20-Poly1305 under the master key, with
+/// provider, name and version as associated data so a ciphertext cannot be
+/// moved to another row. The master key never touches the database.
+#[derive(Clone)]
+pub struct CredentialVault {
+    storage: Arc<dyn Storage>,
+    cipher: XChaCha20Poly1305,
+}
+
+impl CredentialVault {
+    pub fn new(storage: Arc<dyn Storage>, master_key: &[u8]) -> Result<Self> {
+        if master_key.len() != 32 {
+            bail!("master key must be 32 bytes, got {}", master_key.len());
+        }
+        Ok(Self {
+            storage,
+            cipher: XChaCha20Poly1305::new_from_slice(master_key).map_err(|e| anyhow!("{}", e))?,
+        })
+    }
+
+    /// Vault keyed from `TRADING_BOT_MASTER_KEY`; `None` when it is unset
+    pub fn from_env(storage: Arc<dyn Storage>) -> Result<Option<Self>> {
+        let Ok(encoded) = std::env::var(MASTER_KEY_ENV) else {
+            return Ok(None);
+        };
+        let key = hex::decode(encoded.trim()).with_context(|| format!("{} is not hex", MASTER_KEY_ENV))?;
+        Self::new(storage, &key).map(Some)
+    }
+
+    fn associated_data(provider: &str, name: &str, version: i32) -> String {
+        format!("{}/{}/{}", provider, name, version)
+    }
+
+    fn seal(&self, provider: &str, name: &str, version: i32, value: &str) -> Result<SealedCredential> {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let aad = Self::associated_data(provider, name, version);
+        let ciphertext = self.cipher
+            .encrypt(&nonce, Payload { msg: value.as_bytes(), aad: aad.as_bytes() })
+            .map_err(|_| anyhow!("encrypting {}/{}", provider, name))?;
+
+        Ok(SealedCredential {
+            provider: provider.to_string(),
+            name: name.to_string(),
+            version,
+            nonce: nonce.to_vec(),
+            ciphertext,
+            created_at: Utc::now(),
+        })
+    }
+
+    fn open(&self, sealed: &SealedCredential) -> Result<String> {
+        if sealed.nonce.len() != 24 {
+            bail!("credential {}/{} has a malformed nonce", sealed.provider, sealed.name);
+        }
+        let aad = Self::associated_data(&sealed.provider, &sealed.name, sealed.version);
+        let plaintext = self.cipher
+            .decrypt(XNonce::from_slice(&sealed.nonce), Payload { msg: &sealed.ciphertext, aad: aad.as_bytes() })
+            .map_err(|_| anyhow!(
+                "cannot decrypt {}/{} v{}; wrong master key or tampered row",
+                sealed.provider, sealed.name, sealed.version
+            ))?;
+        Ok(String::from_utf8(plaintext)?)
+    }
+
+    /// Store a credential as a new version; returns the version
+    pub async fn store(&self, provider: &str, name: &str, value: &str) -> Result<i32> {
+        if provider.is_empty() || name.is_empty() || value.is_empty() {
+            bail!("provider, name and value must all be set");
+        }
+        let version = self.storage.fetch_credentials().await?
+            .iter()
+            .find(|c| c.provider == provider && c.name == name)
+            .map_or(1, |c| c.version + 1);
+
+        self.storage.insert_credential(&self.seal(provider, name, version, value)?).await?;
+        info!("🔐 Stored credential {}/{} v{}", provider, name, version);
+        Ok(version)
+    }
+
+    /// Current credentials, without their values
+    pub async fn list(&self) -> Result<Vec<CredentialInfo>> {
+        Ok(self.storage.fetch_credentials().await?
+            .into_iter()
+            .map(|c| CredentialInfo { provider: c.provider, name: c.name, version: c.version, created_at: c.created_at })
+            .collect())
+    }
+
+    /// Overlay stored credentials on the config, so every client built from
+    /// it uses them; values in the config files are kept where none is stored
+    pub async fn apply(&self, config: &mut Config) -> Result<usize> {
+        let mut applied = 0;
+        for sealed in self.storage.fetch_credentials().await? {
+            let value = self.open(&sealed)?;
+            if apply_credential(config, &sealed.provider, &sealed.name, value) {
+                applied += 1;
+            } else {
+                warn!("Stored credential {}/{} is not used by any configured client", sealed.provider, sealed.name);
+            }
+        }
+        Ok(applied)
+    }
+}
+
+/// Set the config field a credential belongs to; false if there is none,
+/// or its provider is not configured
+fn apply_credential(config: &mut Config, provider: &str, name: &str, value: String) -> bool {
+    let field: Option<&mut String> = match (provider, name) {
+        ("polygon", "private_key") => Some(&mut config.blockchain.private_key),
+        ("the_odds_api", "api_key") => config.odds.the_odds_api.as_mut().map(|p| &mut p.api_key),
+        ("pinnacle", "username") => config.odds.pinnacle.as_mut().map(|p| &mut p.username),
+        ("pinnacle", "password") => config.odds.pinnacle.as_mut().map(|p| &mut p.password),
+        ("betfair", "app_key") => config.odds.betfair.as_mut().map(|p| &mut p.app_key),
+        ("betfair", "session_token") => config.odds.betfair.as_mut().map(|p| &mut p.session_token),
+        ("polymarket", "api_key") => Some(config.polymarket.api_key.get_or_insert_with(String::new)),
+        ("telegram", "bot_token") => Some(config.monitoring.telegram_bot_token.get_or_insert_with(String::new)),
+        ("pagerduty", "routing_key") => Some(config.monitoring.incidents.pagerduty_routing_key.get_or_insert_with(String::new)),
+        ("opsgenie", "api_key") => Some(config.monitoring.incidents.opsgenie_api_key.get_or_insert_with(String::new)),
+        _ => None,
+    };
+
+    match field {
+        Some(field) => {
+            *field = value;
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+
+    #[tokio::test]
+    async fn rotates_and_rejects_moved_or_foreign_ciphertexts() {
+        let storage: Arc<dyn Storage> = Arc::new(MemoryStorage::new());
+        let vault = CredentialVault::new(storage.clone(), &[7u8; 32]).unwrap();
+
+        assert_eq!(vault.store("polygon", "private_key", "0xold").await.unwrap(), 1);
+        assert_eq!(vault.store("polygon", "private_key", "0xnew").await.unwrap(), 2);
+
+        let mut config = Config::default();
+        assert_eq!(vault.apply(&mut config).await.unwrap(), 1);
+        assert_eq!(config.blockchain.private_key, "0xnew");
+
+        let sealed = storage.fetch_credentials().await.unwrap().remove(0);
+        assert!(sealed.ciphertext.windows(5).all(|w| w != b"0xnew"));
+
+        let moved = SealedCredential { name: "other".to_string(), ..sealed.clone() };
+        assert!(vault.open(&moved).is_err());
+        let foreign = CredentialVault::new(storage, &[8u8; 32]).unwrap();
+        assert!(foreign.open(&sealed).is_err());
+    }
+}
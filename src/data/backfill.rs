@@ -0,0 +1,150 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use tokio::time::{sleep, Duration};
+use tracing::{debug, info, warn};
+
+use crate::config::Config;
+use crate::storage::Storage;
+use crate::types::{Market, Price, PricePoint, Resolution, Sport};
+use super::PolymarketClient;
+
+const SPORTS: [Sport; 4] = [Sport::NFL, Sport::NBA, Sport::PremierLeague, Sport::MLB];
+
+/// What a backfill run imported
+#[derive(Debug, Clone, Default)]
+pub struct BackfillSummary {
+    pub markets: usize,
+    pub resolved: usize,
+    pub price_points: u64,
+}
+
+/// History points newer than `after`, dropping prices outside [0, 1]
+pub fn price_points(market_id: &str, history: &[(DateTime<Utc>, Decimal)], after: Option<DateTime<Utc>>) -> Vec<PricePoint> {
+    history.iter()
+        .filter(|(at, _)| after.map_or(true, |after| *at > after))
+        .filter_map(|(at, price)| {
+            Some(PricePoint {
+                market_id: market_id.to_string(),
+                yes_price: Price::new(*price).ok()?,
+                observed_at: *at,
+            })
+        })
+        .collect()
+}
+
+/// Imports closed Polymarket sports markets with their price history and
+/// resolutions, so backtests have past seasons to run over
+///
+/// Markets are upserted like live ones, so a market already tracked live
+/// just gains its history. Price history resumes after the last stored
+/// point, so an interrupted run can be restarted.
+pub struct Backfiller {
+    storage: Arc<dyn Storage>,
+    polymarket: PolymarketClient,
+    since: DateTime<Utc>,
+    page_size: usize,
+    fidelity_mins: u32,
+    request_delay: Duration,
+}
+
+impl Backfiller {
+    pub fn new(storage: Arc<dyn Storage>, config: &Config) -> Result<Self> {
+        let backfill = &config.backfill;
+
+        Ok(Self {
+            storage,
+            polymarket: PolymarketClient::new(config)?,
+            since: Utc::now() - ChronoDuration::days(backfill.lookback_days),
+            page_size: backfill.page_size.max(1),
+            fidelity_mins: backfill.fidelity_mins.max(1),
+            request_delay: Duration::from_millis(backfill.request_delay_ms),
+        })
+    }
+
+    pub async fn run(&self) -> Result<BackfillSummary> {
+        info!("📥 Backfilling closed markets since {}", self.since.date_naive());
+
+        let mut summary = BackfillSummary::default();
+        for sport in SPORTS {
+            self.backfill_sport(sport, &mut summary).await?;
+        }
+
+        info!(
+            "📥 Backfill done: {} markets, {} resolved, {} price points",
+            summary.markets, summary.resolved, summary.price_points
+        );
+        Ok(summary)
+    }
+
+    async fn backfill_sport(&self, sport: Sport, summary: &mut BackfillSummary) -> Result<()> {
+        let mut offset = 0;
+        loop {
+            let (fetched, markets) = self.polymarket
+                .fetch_closed_markets(sport, self.since, offset, self.page_size)
+                .await?;
+
+            for (market, resolution) in markets {
+                match self.backfill_market(&market, resolution).await {
+                    Ok(points) => {
+                        summary.markets += 1;
+                        summary.resolved += resolution.is_some() as usize;
+                        summary.price_points += points;
+                    }
+                    Err(e) => warn!("Failed to backfill {}: {}", market.market_id, e),
+                }
+                sleep(self.request_delay).await;
+            }
+
+            debug!("Backfilled {} {} markets from offset {}", fetched, sport.as_str(), offset);
+            if fetched < self.page_size {
+                return Ok(());
+            }
+            offset += fetched;
+        }
+    }
+
+    /// Store a market, its resolution and its new price history points
+    async fn backfill_market(&self, market: &Market, resolution: Option<Resolution>) -> Result<u64> {
+        self.storage.upsert_market(market).await?;
+        if let Some(resolution) = resolution {
+            self.storage.set_market_resolution(&market.market_id, resolution, market.event_time).await?;
+        }
+
+        let Some(tokens) = market.tokens.as_ref() else {
+            return Ok(0);
+        };
+        let last = self.storage.last_price_history_at(&market.market_id).await?;
+        let history = self.polymarket
+            .fetch_price_history(&tokens.yes_token_id, last.unwrap_or(self.since), self.fidelity_mins)
+            .await?;
+
+        self.storage.insert_price_history(&price_points(&market.market_id, &history, last)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn resumes_after_the_last_stored_point() {
+        let start = Utc::now();
+        let at = |hours| start + ChronoDuration::hours(hours);
+        let history = vec![
+            (at(0), dec!(0.41)),
+            (at(1), dec!(0.44)),
+            (at(2), dec!(1.20)),
+            (at(3), dec!(0.52)),
+        ];
+
+        let points = price_points("0xmarket", &history, Some(at(0)));
+        let prices: Vec<_> = points.iter().map(|p| (p.observed_at, p.yes_price.value())).collect();
+        // The stored point is skipped and the out-of-range price dropped
+        assert_eq!(prices, vec![(at(1), dec!(0.44)), (at(3), dec!(0.52))]);
+
+        assert_eq!(price_points("0xmarket", &history, None).len(), 3);
+    }
+}
@@ -0,0 +1,511 @@
+use anyhow::{bail, Context, Result};
+use futures::{SinkExt, StreamExt};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use tokio::time::{interval, sleep, Duration};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{debug, info, warn};
+
+use crate::config::Config;
+use crate::monitoring::{Heartbeats, Notifier};
+use crate::numeric;
+use crate::storage::Storage;
+use crate::types::{BookSnapshot, DepthLevel, Price, Quote};
+use super::polymarket::BookLevel;
+use super::PolymarketClient;
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Streamed quotes are written to storage at most this often
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The market channel drops connections that stay silent
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Markets subscribed per connection
+const MAX_SUBSCRIBED: i64 = 500;
+
+/// One event on the CLOB market channel
+#[derive(Debug, Deserialize)]
+#[serde(tag = "event_type", rename_all = "snake_case")]
+enum StreamEvent {
+    /// Full book for a token, sent on subscribing and after trades
+    Book {
+        asset_id: String,
+        #[serde(default, alias = "buys")]
+        bids: Vec<BookLevel>,
+        #[serde(default, alias = "sells")]
+        asks: Vec<BookLevel>,
+    },
+    /// Levels whose resting size changed; a size of zero removes the level
+    PriceChange {
+        asset_id: Option<String>,
+        #[serde(default, alias = "price_changes")]
+        changes: Vec<LevelChange>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct LevelChange {
+    /// Set per change when one event covers several tokens
+    asset_id: Option<String>,
+    price: Decimal,
+    size: Decimal,
+    side: BookSide,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+enum BookSide {
+    Buy,
+    Sell,
+}
+
+/// Events in a frame, which carries one event or an array of them
+fn parse_events(text: &str) -> Vec<StreamEvent> {
+    let values = match serde_json::from_str::<serde_json::Value>(text) {
+        Ok(serde_json::Value::Array(values)) => values,
+        Ok(value) => vec![value],
+        // Keepalive replies and other plain-text frames
+        Err(_) => return Vec::new(),
+    };
+
+    values.into_iter()
+        .filter_map(|value| match serde_json::from_value(value) {
+            Ok(event) => Some(event),
+            Err(e) => {
+                debug!("Unreadable book stream event: {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// A token's book as rebuilt from the stream, size by price
+#[derive(Debug, Default)]
+struct TokenBook {
+    bids: BTreeMap<Decimal, Decimal>,
+    asks: BTreeMap<Decimal, Decimal>,
+}
+
+impl TokenBook {
+    fn replace(&mut self, bids: &[BookLevel], asks: &[BookLevel]) {
+        self.bids = bids.iter().filter(|l| l.size > Decimal::ZERO).map(|l| (l.price, l.size)).collect();
+        self.asks = asks.iter().filter(|l| l.size > Decimal::ZERO).map(|l| (l.price, l.size)).collect();
+    }
+
+    fn set(&mut self, side: BookSide, price: Decimal, size: Decimal) {
+        let levels = match side {
+            BookSide::Buy => &mut self.bids,
+            BookSide::Sell => &mut self.asks,
+        };
+        if size > Decimal::ZERO {
+            levels.insert(price, size);
+        } else {
+            levels.remove(&price);
+        }
+    }
+
+    fn snapshot(&self, market_id: &str) -> BookSnapshot {
+        let levels = |levels: &BTreeMap<Decimal, Decimal>| {
+            levels.iter().map(|(price, size)| DepthLevel { price: *price, size: *size }).collect()
+        };
+        BookSnapshot {
+            market_id: market_id.to_string(),
+            bids: levels(&self.bids),
+            asks: levels(&self.asks),
+            captured_at: chrono::Utc::now(),
+        }
+    }
+
+    /// Top of book with depth; `None` if either side is empty or the book is crossed
+    fn quote(&self) -> Option<Quote> {
+        let (bid, bid_size) = self.bids.iter().next_back()?;
+        let (ask, ask_size) = self.asks.iter().next()?;
+        if bid > ask {
+            return None;
+        }
+
+        Some(Quote {
+            bid: Price::new(*bid).ok()?,
+            ask: Price::new(*ask).ok()?,
+            bid_size: Some(*bid_size),
+            ask_size: Some(*ask_size),
+        })
+    }
+}
+
+/// Apply an event to the books, noting the tokens it touched
+fn apply_event(books: &mut HashMap<String, TokenBook>, changed: &mut HashSet<String>, event: StreamEvent) {
+    match event {
+        StreamEvent::Book { asset_id, bids, asks } => {
+            books.entry(asset_id.clone()).or_default().replace(&bids, &asks);
+            changed.insert(asset_id);
+        }
+        StreamEvent::PriceChange { asset_id, changes } => {
+            for change in changes {
+                let Some(token) = change.asset_id.as_ref().or(asset_id.as_ref()) else {
+                    continue;
+                };
+                // Changes before the token's snapshot would build a partial book
+                if let Some(book) = books.get_mut(token) {
+                    book.set(change.side, change.price, change.size);
+                    changed.insert(token.clone());
+                }
+            }
+        }
+        StreamEvent::Other => {}
+    }
+}
+
+/// Largest gap between two quotes' bids or asks
+fn divergence(streamed: &Quote, rest: &Quote) -> Decimal {
+    let bid = (streamed.bid.value() - rest.bid.value()).abs();
+    let ask = (streamed.ask.value() - rest.ask.value()).abs();
+    bid.max(ask)
+}
+
+/// How a cross-check changed whether streamed quotes are used
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HealthChange {
+    None,
+    /// First agreeing check on a connection not yet trusted
+    Trusted,
+    /// Agreeing again after a failover
+    Recovered,
+    /// Failed enough checks in a row to fail over
+    Desynced,
+    /// Still failing after a failover, already alerted on
+    StillDesynced,
+}
+
+/// Whether streamed quotes are trusted, from the cross-checks so far
+#[derive(Debug, Default)]
+struct StreamHealth {
+    trusted: bool,
+    /// Failed over and not yet agreeing again
+    desynced: bool,
+    failed_checks: u32,
+}
+
+impl StreamHealth {
+    fn record(&mut self, failed: bool, max_failed_checks: u32) -> HealthChange {
+        if !failed {
+            self.failed_checks = 0;
+            if self.trusted {
+                return HealthChange::None;
+            }
+            self.trusted = true;
+            return if std::mem::take(&mut self.desynced) { HealthChange::Recovered } else { HealthChange::Trusted };
+        }
+
+        self.failed_checks += 1;
+        if self.failed_checks < max_failed_checks {
+            return HealthChange::None;
+        }
+        self.failed_checks = 0;
+        self.trusted = false;
+        if std::mem::replace(&mut self.desynced, true) {
+            HealthChange::StillDesynced
+        } else {
+            HealthChange::Desynced
+        }
+    }
+}
+
+/// Streams order books of tradable markets and writes their top of book
+/// between the data pipeline's REST polls
+///
+/// A rotating sample of streamed books is compared with the REST books on
+/// an interval. Streamed quotes are only written once a check on the
+/// connection agrees; after enough failed checks in a row the stream is
+/// dropped and resubscribed, leaving the REST polls as the only source until
+/// a check agrees again, so a silently desynced stream cannot feed stale
+/// prices to the strategies.
+pub struct BookStream {
+    storage: Arc<dyn Storage>,
+    polymarket: PolymarketClient,
+    ws_url: String,
+    cross_check_interval: Duration,
+    resubscribe_interval: Duration,
+    sample_size: usize,
+    max_divergence: Decimal,
+    max_divergent_share: Decimal,
+    max_divergent_checks: u32,
+    heartbeats: Heartbeats,
+    notifier: Notifier,
+}
+
+impl BookStream {
+    pub fn new(storage: Arc<dyn Storage>, config: &Config, heartbeats: Heartbeats, notifier: Notifier) -> Result<Self> {
+        let stream = &config.polymarket.stream;
+
+        Ok(Self {
+            storage,
+            polymarket: PolymarketClient::new(config)?,
+            ws_url: config.polymarket.ws_url.clone(),
+            cross_check_interval: Duration::from_secs(stream.cross_check_interval_secs.max(1)),
+            resubscribe_interval: Duration::from_secs(stream.resubscribe_secs.max(1)),
+            sample_size: stream.sample_size.max(1),
+            max_divergence: numeric::to_decimal(stream.max_divergence)
+                .context("polymarket.stream.max_divergence")?,
+            max_divergent_share: numeric::to_decimal(stream.max_divergent_share)
+                .context("polymarket.stream.max_divergent_share")?,
+            max_divergent_checks: stream.max_divergent_checks.max(1),
+            heartbeats,
+            notifier,
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let mut health = StreamHealth::default();
+
+        info!("📶 Book stream started ({})", self.ws_url);
+
+        loop {
+            if let Err(e) = self.listen(&mut health).await {
+                warn!("Book stream dropped: {}", e);
+            }
+            sleep(RECONNECT_DELAY).await;
+        }
+    }
+
+    /// YES token to market, for markets open for entry
+    async fn subscribable_markets(&self) -> Result<HashMap<String, String>> {
+        Ok(self.storage.fetch_active_markets(Decimal::ZERO, MAX_SUBSCRIBED).await?
+            .into_iter()
+            .filter(|m| m.status.allows_entry())
+            .filter_map(|m| m.tokens.map(|t| (t.yes_token_id, m.market_id)))
+            .collect())
+    }
+
+    /// Subscribe and keep the books until the connection drops, the stream
+    /// fails over or the tradable markets change
+    async fn listen(&self, health: &mut StreamHealth) -> Result<()> {
+        self.heartbeats.beat("book_stream");
+        let markets = self.subscribable_markets().await?;
+        if markets.is_empty() {
+            sleep(self.cross_check_interval).await;
+            return Ok(());
+        }
+
+        let (mut ws, _) = connect_async(self.ws_url.as_str()).await
+            .context("connecting to polymarket.ws_url")?;
+        let assets: Vec<&String> = markets.keys().collect();
+        let subscription = serde_json::json!({ "type": "market", "assets_ids": assets });
+        ws.send(Message::Text(subscription.to_string())).await?;
+        info!("📶 Streaming books of {} markets", markets.len());
+
+        let mut books: HashMap<String, TokenBook> = HashMap::new();
+        let mut changed = HashSet::new();
+        let mut cursor = 0;
+
+        let mut flush = interval(FLUSH_INTERVAL);
+        let mut keepalive = interval(KEEPALIVE_INTERVAL);
+        let mut cross_check = interval(self.cross_check_interval);
+        let mut resubscribe = interval(self.resubscribe_interval);
+        // Both fire at once; give the snapshots time to arrive first
+        cross_check.tick().await;
+        resubscribe.tick().await;
+
+        loop {
+            tokio::select! {
+                message = ws.next() => match message {
+                    Some(Ok(Message::Text(text))) => {
+                        for event in parse_events(&text) {
+                            apply_event(&mut books, &mut changed, event);
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | None => bail!("connection closed"),
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => return Err(e.into()),
+                },
+                _ = flush.tick() => {
+                    if health.trusted {
+                        self.flush(&markets, &books, &mut changed).await;
+                    } else {
+                        changed.clear();
+                    }
+                }
+                _ = keepalive.tick() => ws.send(Message::Text("PING".to_string())).await?,
+                _ = cross_check.tick() => {
+                    self.heartbeats.beat("book_stream");
+                    if !self.cross_check(&markets, &books, &mut cursor, health).await {
+                        return Ok(());
+                    }
+                }
+                _ = resubscribe.tick() => {
+                    let current = self.subscribable_markets().await?;
+                    if current.len() != markets.len() || current.keys().any(|token| !markets.contains_key(token)) {
+                        debug!("Tradable markets changed; resubscribing the book stream");
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Write the top of book and depth of tokens changed since the last flush
+    async fn flush(&self, markets: &HashMap<String, String>, books: &HashMap<String, TokenBook>, changed: &mut HashSet<String>) {
+        for token in changed.drain() {
+            let (Some(market_id), Some(book)) = (markets.get(&token), books.get(&token)) else {
+                continue;
+            };
+            let Some(quote) = book.quote() else {
+                continue;
+            };
+            if let Err(e) = self.storage.update_market_quotes(market_id, &quote, &quote.complement()).await {
+                warn!("Failed to store streamed quote for {}: {}", market_id, e);
+            }
+            if let Err(e) = self.storage.upsert_book_snapshot(&book.snapshot(market_id)).await {
+                warn!("Failed to store streamed book for {}: {}", market_id, e);
+            }
+        }
+    }
+
+    /// Compare the next sample of streamed books with the REST books;
+    /// false when the stream has failed over and should be resubscribed
+    async fn cross_check(
+        &self,
+        markets: &HashMap<String, String>,
+        books: &HashMap<String, TokenBook>,
+        cursor: &mut usize,
+        health: &mut StreamHealth,
+    ) -> bool {
+        let mut tokens: Vec<&String> = books.keys().filter(|token| markets.contains_key(*token)).collect();
+        if tokens.is_empty() {
+            return true;
+        }
+        tokens.sort();
+
+        let sample = self.sample_size.min(tokens.len());
+        let (mut compared, mut divergent) = (0u32, 0u32);
+        let mut worst: Option<(&str, Decimal)> = None;
+        for i in 0..sample {
+            let token = tokens[(*cursor + i) % tokens.len()];
+            let Some(streamed) = books[token].quote() else {
+                continue;
+            };
+            let rest = match self.polymarket.fetch_book(token).await.and_then(|book| book.quote()) {
+                Ok(Some(rest)) => rest,
+                Ok(None) => continue,
+                Err(e) => {
+                    debug!("No REST book to cross-check {}: {}", markets[token], e);
+                    continue;
+                }
+            };
+
+            compared += 1;
+            let gap = divergence(&streamed, &rest);
+            if gap > self.max_divergence {
+                divergent += 1;
+                if worst.map_or(true, |(_, largest)| gap > largest) {
+                    worst = Some((markets[token].as_str(), gap));
+                }
+            }
+        }
+        *cursor = (*cursor + sample) % tokens.len();
+        if compared == 0 {
+            return true;
+        }
+
+        let failed = divergent > 0 && Decimal::from(divergent) >= self.max_divergent_share * Decimal::from(compared);
+        if failed {
+            warn!(
+                "📶 Streamed books diverge from REST on {}/{} sampled markets (worst {} by {})",
+                divergent, compared, worst.map_or("", |(m, _)| m), worst.map_or(Decimal::ZERO, |(_, g)| g)
+            );
+        }
+
+        match health.record(failed, self.max_divergent_checks) {
+            HealthChange::None => true,
+            HealthChange::Trusted => {
+                info!("📶 Streamed books agree with REST; using streamed quotes");
+                true
+            }
+            HealthChange::Recovered => {
+                self.notifier.info("📶 Streamed books agree with REST again; using streamed quotes");
+                true
+            }
+            HealthChange::Desynced => {
+                let (market_id, gap) = worst.unwrap_or(("", Decimal::ZERO));
+                self.notifier.critical(format!(
+                    "📶 Streamed books diverged from REST for {} checks in a row ({}/{} sampled markets, worst {} by {}); failed over to REST polling and resubscribing",
+                    self.max_divergent_checks, divergent, compared, market_id, gap
+                ));
+                false
+            }
+            HealthChange::StillDesynced => {
+                warn!("📶 Streamed books still diverge from REST; resubscribing");
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn books_are_rebuilt_from_snapshots_and_changes() {
+        let mut books = HashMap::new();
+        let mut changed = HashSet::new();
+
+        // Changes ahead of the snapshot are dropped
+        let early = r#"{"event_type":"price_change","asset_id":"yes","changes":[{"price":"0.40","size":"10","side":"BUY"}]}"#;
+        for event in parse_events(early) {
+            apply_event(&mut books, &mut changed, event);
+        }
+        assert!(books.is_empty());
+
+        let frames = [
+            r#"[{"event_type":"book","asset_id":"yes","market":"0xc","bids":[{"price":"0.45","size":"100"},{"price":"0.47","size":"50"}],"asks":[{"price":"0.50","size":"80"}]}]"#,
+            // The best bid is pulled and a better ask joins
+            r#"{"event_type":"price_change","market":"0xc","price_changes":[{"asset_id":"yes","price":"0.47","size":"0","side":"BUY"},{"asset_id":"yes","price":"0.49","size":"20","side":"SELL"}]}"#,
+            r#"{"event_type":"last_trade_price","asset_id":"yes","price":"0.49"}"#,
+            "PONG",
+        ];
+        for frame in frames {
+            for event in parse_events(frame) {
+                apply_event(&mut books, &mut changed, event);
+            }
+        }
+        assert_eq!(changed, HashSet::from(["yes".to_string()]));
+
+        let streamed = books["yes"].quote().unwrap();
+        assert_eq!((streamed.bid.value(), streamed.ask.value()), (dec!(0.45), dec!(0.49)));
+        assert_eq!((streamed.bid_size, streamed.ask_size), (Some(dec!(100)), Some(dec!(20))));
+
+        let rest = Quote { bid: Price::new(dec!(0.47)).unwrap(), ask: Price::new(dec!(0.52)).unwrap(), bid_size: None, ask_size: None };
+        assert_eq!(divergence(&streamed, &rest), dec!(0.03));
+    }
+
+    #[test]
+    fn fails_over_after_consecutive_failed_checks() {
+        let mut health = StreamHealth::default();
+
+        // Untrusted until a check agrees
+        assert_eq!(health.record(true, 2), HealthChange::None);
+        assert_eq!(health.record(false, 2), HealthChange::Trusted);
+        assert!(health.trusted);
+
+        // A single failure in between agreeing checks is tolerated
+        assert_eq!(health.record(true, 2), HealthChange::None);
+        assert_eq!(health.record(false, 2), HealthChange::None);
+        assert_eq!(health.record(true, 2), HealthChange::None);
+        assert_eq!(health.record(true, 2), HealthChange::Desynced);
+        assert!(!health.trusted);
+
+        // Alerted once until it agrees again
+        assert_eq!(health.record(true, 2), HealthChange::None);
+        assert_eq!(health.record(true, 2), HealthChange::StillDesynced);
+        assert_eq!(health.record(false, 2), HealthChange::Recovered);
+        assert!(health.trusted);
+    }
+}
@@ -0,0 +1,115 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::types::{CoveredMarket, MarketListing, Sport};
+
+/// How much of a sport's Polymarket listings make it through each stage of
+/// the pipeline
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SportCoverage {
+    pub sport: Sport,
+    /// Markets Polymarket listed on the last poll, open or in play
+    pub listed: usize,
+    /// Of those, the ones that parsed into markets we store
+    pub ingested: usize,
+    /// Stored markets that are active and not yet started
+    pub tracked: usize,
+    /// Tracked markets bookmaker odds were matched to
+    pub matched: usize,
+    /// Tracked markets at least one strategy evaluated
+    pub analyzed: usize,
+    /// Tracked markets each strategy evaluated, by strategy name
+    pub by_strategy: BTreeMap<String, usize>,
+    /// Listings dropped because they did not parse
+    pub unparsed: usize,
+    /// Tracked markets no bookmaker line was matched to
+    pub unmatched: usize,
+    /// Tracked markets no strategy looked at
+    pub unanalyzed: usize,
+}
+
+impl SportCoverage {
+    fn new(sport: Sport) -> Self {
+        Self {
+            sport,
+            listed: 0,
+            ingested: 0,
+            tracked: 0,
+            matched: 0,
+            analyzed: 0,
+            by_strategy: BTreeMap::new(),
+            unparsed: 0,
+            unmatched: 0,
+            unanalyzed: 0,
+        }
+    }
+}
+
+/// Coverage per sport from the last listing counts and the tracked markets
+pub fn coverage_report(listings: &[MarketListing], markets: &[CoveredMarket]) -> Vec<SportCoverage> {
+    let mut report: BTreeMap<&str, SportCoverage> = BTreeMap::new();
+
+    for listing in listings {
+        let coverage = report.entry(listing.sport.as_str())
+            .or_insert_with(|| SportCoverage::new(listing.sport));
+        coverage.listed = listing.listed.max(0) as usize;
+        coverage.ingested = listing.ingested.max(0) as usize;
+        coverage.unparsed = coverage.listed.saturating_sub(coverage.ingested);
+    }
+
+    for market in markets {
+        let coverage = report.entry(market.sport.as_str())
+            .or_insert_with(|| SportCoverage::new(market.sport));
+        coverage.tracked += 1;
+        if market.matched {
+            coverage.matched += 1;
+        } else {
+            coverage.unmatched += 1;
+        }
+        if market.strategies.is_empty() {
+            coverage.unanalyzed += 1;
+        } else {
+            coverage.analyzed += 1;
+        }
+        for strategy in &market.strategies {
+            *coverage.by_strategy.entry(strategy.clone()).or_default() += 1;
+        }
+    }
+
+    report.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn market(id: &str, matched: bool, strategies: &[&str]) -> CoveredMarket {
+        CoveredMarket {
+            market_id: id.to_string(),
+            sport: Sport::NBA,
+            matched,
+            strategies: strategies.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn counts_what_each_stage_drops() {
+        let listings = vec![MarketListing { sport: Sport::NBA, listed: 10, ingested: 7, polled_at: Utc::now() }];
+        let markets = vec![
+            market("a", true, &["CLV Arbitrage", "Poisson Expected Value"]),
+            market("b", true, &["Poisson Expected Value"]),
+            market("c", false, &["Poisson Expected Value"]),
+            market("d", false, &[]),
+        ];
+
+        let report = coverage_report(&listings, &markets);
+        assert_eq!(report.len(), 1);
+        let nba = &report[0];
+        assert_eq!((nba.listed, nba.ingested, nba.unparsed), (10, 7, 3));
+        assert_eq!((nba.tracked, nba.matched, nba.unmatched), (4, 2, 2));
+        assert_eq!((nba.analyzed, nba.unanalyzed), (3, 1));
+        assert_eq!(nba.by_strategy["Poisson Expected Value"], 3);
+        assert_eq!(nba.by_strategy["CLV Arbitrage"], 1);
+    }
+}
@@ -0,0 +1,105 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use crate::config::IncrementalConfig;
+use crate::numeric;
+use crate::types::Bookmaker;
+
+#[derive(Default)]
+struct DirtyState {
+    /// YES price each market was last marked at
+    prices: HashMap<String, Decimal>,
+    /// YES implied probability each bookmaker line was last marked at
+    odds: HashMap<(String, Bookmaker), Decimal>,
+    /// When each market was first marked since the last take
+    dirty: HashMap<String, DateTime<Utc>>,
+}
+
+/// Markets whose price, odds or status moved since signal generation last looked
+///
+/// Ingestion reports what it sees; a market is marked dirty the first time
+/// it is seen and whenever its YES price or a bookmaker's YES probability has
+/// moved at least `threshold` from the value it was last marked at, so slow
+/// drift still adds up to a re-evaluation.
+#[derive(Clone)]
+pub struct DirtyMarkets {
+    threshold: Decimal,
+    state: Arc<Mutex<DirtyState>>,
+}
+
+impl DirtyMarkets {
+    pub fn new(threshold: Decimal) -> Self {
+        Self {
+            threshold,
+            state: Arc::new(Mutex::new(DirtyState::default())),
+        }
+    }
+
+    pub fn from_config(config: &IncrementalConfig) -> Result<Self> {
+        Ok(Self::new(numeric::to_decimal(config.price_move_threshold)?))
+    }
+
+    /// Record a market's YES price
+    pub fn observe_price(&self, market_id: &str, yes_price: Decimal) {
+        let mut state = self.state.lock().unwrap();
+        let moved = state.prices.get(market_id).map_or(true, |last| (yes_price - last).abs() >= self.threshold);
+        if moved {
+            state.prices.insert(market_id.to_string(), yes_price);
+            state.dirty.entry(market_id.to_string()).or_insert_with(Utc::now);
+        }
+    }
+
+    /// Record a bookmaker's YES implied probability for a market
+    pub fn observe_odds(&self, market_id: &str, bookmaker: Bookmaker, yes_prob: Decimal) {
+        let mut state = self.state.lock().unwrap();
+        let key = (market_id.to_string(), bookmaker);
+        let moved = state.odds.get(&key).map_or(true, |last| (yes_prob - last).abs() >= self.threshold);
+        if moved {
+            state.odds.insert(key, yes_prob);
+            state.dirty.entry(market_id.to_string()).or_insert_with(Utc::now);
+        }
+    }
+
+    /// Mark a market regardless of movement, e.g. on a status change
+    pub fn mark(&self, market_id: &str) {
+        self.state.lock().unwrap().dirty.entry(market_id.to_string()).or_insert_with(Utc::now);
+    }
+
+    /// Markets marked since the last call
+    pub fn take(&self) -> HashSet<String> {
+        self.take_marked().into_keys().collect()
+    }
+
+    /// Markets marked since the last call, with when each was first marked
+    pub fn take_marked(&self) -> HashMap<String, DateTime<Utc>> {
+        std::mem::take(&mut self.state.lock().unwrap().dirty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn marks_first_sight_and_moves_past_the_threshold() {
+        let dirty = DirtyMarkets::new(dec!(0.01));
+
+        dirty.observe_price("0xa", dec!(0.50));
+        assert_eq!(dirty.take(), HashSet::from(["0xa".to_string()]));
+
+        // Two small ticks only mark once they add up to the threshold
+        dirty.observe_price("0xa", dec!(0.505));
+        assert!(dirty.take().is_empty());
+        dirty.observe_price("0xa", dec!(0.51));
+        assert_eq!(dirty.take().len(), 1);
+
+        dirty.observe_odds("0xb", Bookmaker::Pinnacle, dec!(0.40));
+        dirty.observe_odds("0xb", Bookmaker::Pinnacle, dec!(0.405));
+        dirty.mark("0xc");
+        assert_eq!(dirty.take(), HashSet::from(["0xb".to_string(), "0xc".to_string()]));
+    }
+}
@@ -0,0 +1,166 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+
+use crate::config::MicrostructureConfig;
+use crate::numeric;
+use crate::types::{MarketMicrostructure, Position};
+use super::polymarket::{OrderBook, TakerTrade};
+
+/// Shares bid and offered within the best `levels` levels of a book, and
+/// their imbalance in [-1, 1]
+pub fn book_depth(book: &OrderBook, levels: usize) -> (Decimal, Decimal, Decimal) {
+    let mut bids: Vec<_> = book.bids.iter().collect();
+    let mut asks: Vec<_> = book.asks.iter().collect();
+    bids.sort_by(|a, b| b.price.cmp(&a.price));
+    asks.sort_by(|a, b| a.price.cmp(&b.price));
+
+    let bid_depth: Decimal = bids.iter().take(levels).map(|l| l.size).sum();
+    let ask_depth: Decimal = asks.iter().take(levels).map(|l| l.size).sum();
+    let total = bid_depth + ask_depth;
+    let imbalance = if total > Decimal::ZERO {
+        (bid_depth - ask_depth) / total
+    } else {
+        Decimal::ZERO
+    };
+
+    (bid_depth, ask_depth, imbalance)
+}
+
+/// Taker (buy, sell) volume in YES shares of trades since `since`; a taker
+/// buying NO counts as selling YES
+pub fn taker_volume(trades: &[TakerTrade], yes_token_id: &str, since: DateTime<Utc>) -> (Decimal, Decimal) {
+    let mut bought = Decimal::ZERO;
+    let mut sold = Decimal::ZERO;
+    for trade in trades.iter().filter(|t| t.timestamp >= since.timestamp()) {
+        let buy = trade.side.eq_ignore_ascii_case("buy");
+        if buy == (trade.asset == yes_token_id) {
+            bought += trade.size;
+        } else {
+            sold += trade.size;
+        }
+    }
+    (bought, sold)
+}
+
+/// A market's features from its YES book and recent trades, with depth
+/// changes measured against the `previous` poll
+pub fn market_features(
+    market_id: &str,
+    book: &OrderBook,
+    levels: usize,
+    taker_volume: (Decimal, Decimal),
+    previous: Option<&MarketMicrostructure>,
+    now: DateTime<Utc>,
+) -> MarketMicrostructure {
+    let (bid_depth, ask_depth, imbalance) = book_depth(book, levels);
+
+    MarketMicrostructure {
+        market_id: market_id.to_string(),
+        bid_depth,
+        ask_depth,
+        imbalance,
+        bid_depth_change: previous.map(|p| bid_depth - p.bid_depth),
+        ask_depth_change: previous.map(|p| ask_depth - p.ask_depth),
+        taker_buy_volume: taker_volume.0,
+        taker_sell_volume: taker_volume.1,
+        observed_at: now,
+    }
+}
+
+/// Keeps entries out of markets where takers or the book are heavily
+/// one-sided against them
+pub struct FlowFilter {
+    max_adverse_flow: Decimal,
+    max_adverse_imbalance: Decimal,
+    min_flow_volume: Decimal,
+    max_age: Duration,
+}
+
+impl FlowFilter {
+    /// `None` when microstructure features or the entry filter are disabled
+    pub fn from_config(config: &MicrostructureConfig) -> Result<Option<Self>> {
+        if !config.enabled || !config.filter_entries {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            max_adverse_flow: numeric::to_decimal(config.max_adverse_flow)
+                .context("microstructure.max_adverse_flow")?,
+            max_adverse_imbalance: numeric::to_decimal(config.max_adverse_imbalance)
+                .context("microstructure.max_adverse_imbalance")?,
+            min_flow_volume: numeric::to_decimal(config.min_flow_volume)
+                .context("microstructure.min_flow_volume")?,
+            max_age: Duration::seconds(config.max_age_secs as i64),
+        }))
+    }
+
+    /// Why buying `position` now would be buying into one-sided selling, if it would
+    pub fn adverse(&self, features: &MarketMicrostructure, position: Position, now: DateTime<Utc>) -> Option<String> {
+        if now - features.observed_at > self.max_age {
+            return None;
+        }
+        // Features lean positive toward YES buyers; buying NO is hurt by the opposite
+        let against = |value: Decimal| match position {
+            Position::Yes => -value,
+            Position::No => value,
+        };
+
+        let volume = features.taker_buy_volume + features.taker_sell_volume;
+        if let Some(flow) = features.taker_flow().filter(|_| volume >= self.min_flow_volume) {
+            if against(flow) >= self.max_adverse_flow {
+                return Some(format!("taker flow {} against {} on {} shares", flow.round_dp(2), position.as_str(), volume));
+            }
+        }
+        if against(features.imbalance) >= self.max_adverse_imbalance {
+            return Some(format!("book imbalance {} against {}", features.imbalance.round_dp(2), position.as_str()));
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::polymarket::BookLevel;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn defers_buying_into_one_sided_selling_only() {
+        let level = |price, size| BookLevel { price, size };
+        let book = OrderBook {
+            bids: vec![level(dec!(0.48), dec!(100)), level(dec!(0.49), dec!(100)), level(dec!(0.40), dec!(5000))],
+            asks: vec![level(dec!(0.51), dec!(1200)), level(dec!(0.52), dec!(400))],
+        };
+        // Two levels a side: 200 bid against 1600 offered
+        let (bid, ask, imbalance) = book_depth(&book, 2);
+        assert_eq!((bid, ask), (dec!(200), dec!(1600)));
+        assert_eq!(imbalance.round_dp(4), dec!(-0.7778));
+
+        let now = Utc::now();
+        let trade = |asset: &str, side: &str, size| TakerTrade {
+            asset: asset.to_string(),
+            side: side.to_string(),
+            size,
+            price: dec!(0.5),
+            timestamp: now.timestamp(),
+            transaction_hash: String::new(),
+            proxy_wallet: String::new(),
+        };
+        // Selling YES and buying NO are both YES selling; the stale trade is ignored
+        let mut trades = vec![trade("yes", "SELL", dec!(600)), trade("no", "BUY", dec!(300)), trade("yes", "BUY", dec!(100))];
+        trades.push(TakerTrade { timestamp: now.timestamp() - 3600, ..trade("yes", "BUY", dec!(5000)) });
+        let volume = taker_volume(&trades, "yes", now - Duration::minutes(5));
+        assert_eq!(volume, (dec!(100), dec!(900)));
+
+        let features = market_features("0xflow", &book, 2, volume, None, now);
+        assert_eq!(features.taker_flow(), Some(dec!(-0.8)));
+
+        let filter = FlowFilter::from_config(&MicrostructureConfig::default()).unwrap().unwrap();
+        assert!(filter.adverse(&features, Position::Yes, now).is_some());
+        assert!(filter.adverse(&features, Position::No, now).is_none());
+        // Too old to say anything about the book now
+        assert!(filter.adverse(&features, Position::Yes, now + Duration::minutes(10)).is_none());
+    }
+}
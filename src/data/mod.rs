@@ -0,0 +1,23 @@
+mod backfill;
+mod book_stream;
+mod coverage;
+mod dirty;
+mod microstructure;
+mod odds;
+mod officials;
+mod pipeline;
+mod pitchers;
+mod polymarket;
+mod whales;
+
+pub use backfill::Backfiller;
+pub use book_stream::BookStream;
+pub use coverage::{coverage_report, SportCoverage};
+pub use dirty::DirtyMarkets;
+pub use microstructure::FlowFilter;
+pub use odds::OddsIngestor;
+pub use officials::OfficialsIngestor;
+pub use pipeline::DataPipeline;
+pub use pitchers::PitcherIngestor;
+pub use polymarket::PolymarketClient;
+pub use whales::WhaleDetector;
@@ -0,0 +1,246 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::config::BetfairConfig;
+use crate::types::{Bookmaker, Sport};
+use super::{EventOdds, OddsEvent, OddsProvider};
+
+/// Betfair Exchange betting API; best available back prices on MATCH_ODDS markets
+///
+/// Each MATCH_ODDS market is treated as one event, keyed by market id.
+pub struct BetfairProvider {
+    http: Client,
+    api_url: String,
+    app_key: String,
+    session_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MarketCatalogue {
+    market_id: String,
+    market_start_time: DateTime<Utc>,
+    event: CatalogueEvent,
+    #[serde(default)]
+    runners: Vec<RunnerDescription>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CatalogueEvent {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RunnerDescription {
+    selection_id: i64,
+    runner_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MarketBook {
+    market_id: String,
+    #[serde(default)]
+    runners: Vec<Runner>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Runner {
+    selection_id: i64,
+    ex: Option<ExchangePrices>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExchangePrices {
+    #[serde(default)]
+    available_to_back: Vec<PriceSize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PriceSize {
+    price: Decimal,
+}
+
+impl BetfairProvider {
+    pub fn new(config: &BetfairConfig) -> Result<Self> {
+        let http = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()?;
+
+        Ok(Self {
+            http,
+            api_url: config.api_url.trim_end_matches('/').to_string(),
+            app_key: config.app_key.clone(),
+            session_token: config.session_token.clone(),
+        })
+    }
+
+    /// (event type id, competition id)
+    fn ids(sport: Sport) -> (&'static str, &'static str) {
+        match sport {
+            Sport::NFL => ("6423", "12282733"),
+            Sport::NBA => ("7522", "10547864"),
+            Sport::PremierLeague => ("1", "10932509"),
+            Sport::MLB => ("7511", "11196870"),
+        }
+    }
+
+    /// (home, away) from "Home v Away" or US-style "Away @ Home"
+    fn teams(event_name: &str) -> Option<(String, String)> {
+        if let Some((away, home)) = event_name.split_once(" @ ") {
+            Some((home.trim().to_string(), away.trim().to_string()))
+        } else {
+            let (home, away) = event_name.split_once(" v ")?;
+            Some((home.trim().to_string(), away.trim().to_string()))
+        }
+    }
+
+    async fn call<T: DeserializeOwned>(&self, operation: &str, body: serde_json::Value) -> Result<T> {
+        let response = self.http
+            .post(format!("{}/{}/", self.api_url, operation))
+            .header("X-Application", &self.app_key)
+            .header("X-Authentication", &self.session_token)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .with_context(|| format!("decoding Betfair {}", operation))?;
+
+        Ok(response)
+    }
+
+    async fn catalogue(&self, sport: Sport) -> Result<Vec<MarketCatalogue>> {
+        let (event_type_id, competition_id) = Self::ids(sport);
+
+        self.call("listMarketCatalogue", serde_json::json!({
+            "filter": {
+                "eventTypeIds": [event_type_id],
+                "competitionIds": [competition_id],
+                "marketTypeCodes": ["MATCH_ODDS"],
+            },
+            "marketProjection": ["EVENT", "RUNNER_DESCRIPTION", "MARKET_START_TIME"],
+            "maxResults": 200,
+        })).await
+    }
+}
+
+#[async_trait]
+impl OddsProvider for BetfairProvider {
+    fn name(&self) -> &'static str {
+        "betfair"
+    }
+
+    async fn fetch_events(&self, sport: Sport) -> Result<Vec<OddsEvent>> {
+        Ok(self.catalogue(sport).await?
+            .into_iter()
+            .filter_map(|m| {
+                let (home_team, away_team) = Self::teams(&m.event.name)?;
+                Some(OddsEvent {
+                    event_id: m.market_id,
+                    sport,
+                    home_team,
+                    away_team,
+                    commence_time: m.market_start_time,
+                })
+            })
+            .collect())
+    }
+
+    async fn fetch_odds(&self, sport: Sport) -> Result<Vec<EventOdds>> {
+        let catalogue = self.catalogue(sport).await?;
+        if catalogue.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Books are a live snapshot; the last match time says nothing of how
+        // fresh the prices are in a quiet market, or how stale in a busy one
+        let market_ids: Vec<&str> = catalogue.iter().map(|m| m.market_id.as_str()).collect();
+        let fetched_at = Utc::now();
+        let books: Vec<MarketBook> = self.call("listMarketBook", serde_json::json!({
+            "marketIds": market_ids,
+            "priceProjection": { "priceData": ["EX_BEST_OFFERS"] },
+        })).await?;
+
+        let catalogue: HashMap<&str, &MarketCatalogue> = catalogue.iter()
+            .map(|m| (m.market_id.as_str(), m))
+            .collect();
+
+        Ok(books.iter()
+            .filter_map(|book| {
+                let market = catalogue.get(book.market_id.as_str())?;
+                let (home_team, away_team) = Self::teams(&market.event.name)?;
+
+                let best_back = |name: &str| {
+                    let selection = market.runners.iter().find(|r| r.runner_name == name)?.selection_id;
+                    let runner = book.runners.iter().find(|r| r.selection_id == selection)?;
+                    runner.ex.as_ref()?.available_to_back.first().map(|p| p.price)
+                };
+
+                Some(EventOdds {
+                    event_id: book.market_id.clone(),
+                    bookmaker: Bookmaker::Betfair,
+                    home: best_back(&home_team)?,
+                    away: best_back(&away_team)?,
+                    draw: best_back("The Draw"),
+                    updated_at: Some(fetched_at),
+                })
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn odds_are_as_of_the_book_request() {
+        let mut server = mockito::Server::new_async().await;
+        let _catalogue = server.mock("POST", "/listMarketCatalogue/")
+            .with_body(serde_json::json!([{
+                "marketId": "1.234",
+                "marketStartTime": "2026-10-18T17:00:00Z",
+                "event": { "name": "Chiefs @ Bills" },
+                "runners": [
+                    { "selectionId": 1, "runnerName": "Bills" },
+                    { "selectionId": 2, "runnerName": "Chiefs" },
+                ],
+            }]).to_string())
+            .create_async().await;
+        // Nothing matched for hours, but these are the prices on offer now
+        let _book = server.mock("POST", "/listMarketBook/")
+            .with_body(serde_json::json!([{
+                "marketId": "1.234",
+                "lastMatchTime": "2026-10-16T06:00:00Z",
+                "runners": [
+                    { "selectionId": 1, "ex": { "availableToBack": [{ "price": 1.8, "size": 40 }] } },
+                    { "selectionId": 2, "ex": { "availableToBack": [{ "price": 2.2, "size": 25 }] } },
+                ],
+            }]).to_string())
+            .create_async().await;
+        let provider = BetfairProvider::new(&BetfairConfig {
+            app_key: "app".to_string(),
+            session_token: "session".to_string(),
+            api_url: server.url(),
+        }).unwrap();
+
+        let before = Utc::now();
+        let odds = provider.fetch_odds(Sport::NFL).await.unwrap();
+        assert_eq!(odds.len(), 1);
+        assert_eq!((odds[0].home, odds[0].away), (Decimal::new(18, 1), Decimal::new(22, 1)));
+        let updated_at = odds[0].updated_at.unwrap();
+        assert!(updated_at >= before && updated_at <= Utc::now(), "{}", updated_at);
+    }
+}
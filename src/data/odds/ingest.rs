@@ -0,0 +1,327 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn, error};
+
+use crate::config::Config;
+use crate::storage::Storage;
+use crate::types::{BookmakerOdds, DeadLetter, FeedLatency, Market, MarketType, Probability, Sport};
+use crate::data::DirtyMarkets;
+use crate::monitoring::{Latencies, LatencyStage};
+use super::{providers_from_config, EventOdds, OddsEvent, OddsProvider};
+
+const SPORTS: [Sport; 4] = [Sport::NFL, Sport::NBA, Sport::PremierLeague, Sport::MLB];
+
+/// Provider event times may differ from Polymarket's by this much
+const MAX_START_TIME_DRIFT_MINUTES: i64 = 180;
+
+/// Re-link events to markets at least this often, to pick up new listings
+const LINK_REFRESH: Duration = Duration::from_secs(15 * 60);
+
+/// Which team a market's YES outcome backs
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Side {
+    Home,
+    Away,
+}
+
+#[derive(Debug, Clone)]
+struct MarketLink {
+    market_id: String,
+    yes_side: Side,
+}
+
+/// Provider event -> market links for one (provider, sport)
+struct LinkCache {
+    links: HashMap<String, Option<MarketLink>>,
+    refreshed_at: Instant,
+}
+
+/// Streams odds from every configured provider into `bookmaker_odds`
+pub struct OddsIngestor {
+    storage: Arc<dyn Storage>,
+    providers: Vec<Box<dyn OddsProvider>>,
+    poll_interval: Duration,
+    dirty: DirtyMarkets,
+    latencies: Latencies,
+}
+
+impl OddsIngestor {
+    pub fn new(storage: Arc<dyn Storage>, dirty: DirtyMarkets, latencies: Latencies, config: &Config) -> Result<Self> {
+        Ok(Self {
+            storage,
+            providers: providers_from_config(&config.odds)?,
+            poll_interval: Duration::from_secs(config.odds.poll_interval_secs),
+            dirty,
+            latencies,
+        })
+    }
+
+    pub fn has_providers(&self) -> bool {
+        !self.providers.is_empty()
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let streams = self.providers.iter().enumerate().flat_map(|(i, provider)| {
+            SPORTS.into_iter().map(move |sport| {
+                provider.stream_updates(sport, self.poll_interval)
+                    .map(move |update| (i, sport, update))
+                    .boxed()
+            })
+        });
+        let mut updates = stream::select_all(streams);
+        let mut caches: HashMap<(usize, Sport), LinkCache> = HashMap::new();
+
+        info!("📚 Odds ingestion started ({} providers)", self.providers.len());
+
+        while let Some((i, sport, update)) = updates.next().await {
+            let provider = self.providers[i].as_ref();
+
+            let result = match update {
+                Ok(odds) => self.ingest(provider, sport, &odds, caches.get(&(i, sport))).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(Some(cache)) => {
+                    caches.insert((i, sport), cache);
+                }
+                Ok(None) => {}
+                Err(e) => error!("Error ingesting {} {} odds: {}", provider.name(), sport.as_str(), e),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Store a batch of odds; returns a rebuilt link cache if the old one was missing or stale
+    async fn ingest(
+        &self,
+        provider: &dyn OddsProvider,
+        sport: Sport,
+        odds: &[EventOdds],
+        cache: Option<&LinkCache>,
+    ) -> Result<Option<LinkCache>> {
+        let received_at = Utc::now();
+        for updated_at in odds.iter().filter_map(|o| o.updated_at) {
+            self.latencies.record(LatencyStage::OddsIngest, received_at - updated_at);
+        }
+        if let Some(latency) = feed_latency(provider.name(), odds, received_at) {
+            debug!("{} feed latency: median {}ms, max {}ms", latency.provider, latency.median_ms, latency.max_ms);
+            self.storage.insert_feed_latency(&latency).await?;
+        }
+
+        let stale = cache.map_or(true, |c| {
+            c.refreshed_at.elapsed() >= LINK_REFRESH
+                || odds.iter().any(|o| !c.links.contains_key(&o.event_id))
+        });
+        let rebuilt = if stale {
+            Some(self.link_events(provider, sport).await?)
+        } else {
+            None
+        };
+        let Some(current) = rebuilt.as_ref().or(cache) else {
+            return Ok(None);
+        };
+
+        let mut stored = 0;
+        for event_odds in odds {
+            let Some(Some(link)) = current.links.get(&event_odds.event_id) else {
+                continue;
+            };
+            match to_bookmaker_odds(link, event_odds, received_at) {
+                Ok(row) => {
+                    self.storage.insert_bookmaker_odds(&row).await?;
+                    self.dirty.observe_odds(&row.market_id, row.bookmaker, row.yes_implied_prob.value());
+                    stored += 1;
+                }
+                Err(reason) => self.dead_letter(provider, link, event_odds, reason, received_at).await,
+            }
+        }
+
+        debug!("Stored {} {} {} odds", stored, provider.name(), sport.as_str());
+        Ok(rebuilt)
+    }
+
+    /// Record a linked line that could not be converted, keyed by provider
+    /// event and bookmaker
+    async fn dead_letter(&self, provider: &dyn OddsProvider, link: &MarketLink, odds: &EventOdds, reason: &str, seen_at: DateTime<Utc>) {
+        let key = format!("{}:{}:{}", provider.name(), odds.event_id, odds.bookmaker.as_str());
+        debug!("{} odds for {} rejected: {}", key, link.market_id, reason);
+        let raw = serde_json::json!({ "market_id": link.market_id, "odds": odds });
+        if let Err(e) = self.storage.record_dead_letter(DeadLetter::BOOKMAKER_ODDS, &key, reason, &raw, seen_at).await {
+            warn!("Failed to record dead letter for {}: {}", key, e);
+        }
+    }
+
+    async fn link_events(&self, provider: &dyn OddsProvider, sport: Sport) -> Result<LinkCache> {
+        let events = provider.fetch_events(sport).await?;
+        let markets = self.storage.fetch_active_markets(Decimal::ZERO, 1000).await?;
+
+        let links = events.iter()
+            .map(|event| (event.event_id.clone(), link_market(event, &markets)))
+            .collect();
+
+        Ok(LinkCache { links, refreshed_at: Instant::now() })
+    }
+}
+
+/// Find the moneyline market for an event and which side its YES backs
+///
+/// The team named first in the question is taken as YES ("Chiefs vs. Bills",
+/// "Will Arsenal beat Chelsea?"); a question naming one team backs that team.
+fn link_market(event: &OddsEvent, markets: &[Market]) -> Option<MarketLink> {
+    markets.iter()
+        .filter(|m| m.sport == event.sport && m.market_type == MarketType::Moneyline)
+        .filter(|m| (m.event_time - event.commence_time).num_minutes().abs() <= MAX_START_TIME_DRIFT_MINUTES)
+        .find_map(|m| {
+            let question = m.event_name.to_lowercase();
+            let yes_side = match (find_team(&question, &event.home_team), find_team(&question, &event.away_team)) {
+                (Some(home), Some(away)) if home < away => Side::Home,
+                (Some(_), Some(_)) => Side::Away,
+                (Some(_), None) => Side::Home,
+                (None, Some(_)) => Side::Away,
+                (None, None) => return None,
+            };
+
+            Some(MarketLink { market_id: m.market_id.clone(), yes_side })
+        })
+}
+
+/// Position of a team in a lowercased question, by full name or nickname
+fn find_team(question: &str, team: &str) -> Option<usize> {
+    let team = team.to_lowercase();
+    question.find(&team)
+        .or_else(|| question.find(team.split_whitespace().last()?))
+}
+
+/// Delay between the bookmaker updating each line and `received_at`, over
+/// the lines in a batch whose feed reports an update time
+fn feed_latency(provider: &str, odds: &[EventOdds], received_at: DateTime<Utc>) -> Option<FeedLatency> {
+    let mut delays: Vec<i64> = odds.iter()
+        .filter_map(|o| o.updated_at)
+        .map(|updated_at| (received_at - updated_at).num_milliseconds().max(0))
+        .collect();
+    if delays.is_empty() {
+        return None;
+    }
+    delays.sort_unstable();
+
+    Some(FeedLatency {
+        provider: provider.to_string(),
+        lines: delays.len() as i64,
+        median_ms: delays[delays.len() / 2],
+        max_ms: delays[delays.len() - 1],
+        measured_at: received_at,
+    })
+}
+
+/// De-vigged YES/NO view of an event's odds
+///
+/// For three-way markets NO covers both the other team and the draw.
+fn to_bookmaker_odds(link: &MarketLink, odds: &EventOdds, received_at: DateTime<Utc>) -> Result<BookmakerOdds, &'static str> {
+    let (yes, other) = match link.yes_side {
+        Side::Home => (odds.home, odds.away),
+        Side::Away => (odds.away, odds.home),
+    };
+
+    let implied = |price: Decimal, reason| Probability::from_decimal_odds(price)
+        .map(|p| p.value())
+        .map_err(|_| reason);
+    let yes_raw = implied(yes, "invalid YES-side odds")?;
+    let other_raw = implied(other, "invalid NO-side odds")?;
+    let draw_raw = match odds.draw {
+        Some(draw) => implied(draw, "invalid draw odds")?,
+        None => Decimal::ZERO,
+    };
+    let no_raw = other_raw + draw_raw;
+    let overround = yes_raw + no_raw;
+    if overround <= Decimal::ZERO || no_raw <= Decimal::ZERO {
+        return Err("non-positive overround");
+    }
+
+    let yes_implied_prob = Probability::new(yes_raw / overround).map_err(|_| "de-vigged probability out of range")?;
+
+    Ok(BookmakerOdds {
+        bookmaker: odds.bookmaker,
+        market_id: link.market_id.clone(),
+        yes_odds: yes,
+        no_odds: Decimal::ONE / no_raw,
+        yes_implied_prob,
+        no_implied_prob: yes_implied_prob.complement(),
+        timestamp: received_at,
+        // Clock skew can put a bookmaker's stamp slightly in our future
+        source_timestamp: odds.updated_at.map(|t| t.min(received_at)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::market_fixture;
+    use crate::types::Bookmaker;
+    use rust_decimal_macros::dec;
+
+    fn event(home: &str, away: &str, market: &Market) -> OddsEvent {
+        OddsEvent {
+            event_id: "evt".to_string(),
+            sport: market.sport,
+            home_team: home.to_string(),
+            away_team: away.to_string(),
+            commence_time: market.event_time,
+        }
+    }
+
+    #[test]
+    fn first_named_team_is_yes() {
+        let mut market = market_fixture("0xlink", dec!(0.5));
+        market.event_name = "Chiefs vs. Bills".to_string();
+
+        let link = link_market(&event("Buffalo Bills", "Kansas City Chiefs", &market), &[market.clone()]).unwrap();
+        assert_eq!(link.yes_side, Side::Away);
+
+        let link = link_market(&event("Kansas City Chiefs", "Buffalo Bills", &market), &[market]).unwrap();
+        assert_eq!(link.yes_side, Side::Home);
+    }
+
+    #[test]
+    fn three_way_odds_put_the_draw_on_no() {
+        let link = MarketLink { market_id: "0xlink".to_string(), yes_side: Side::Home };
+        let odds = EventOdds {
+            event_id: "evt".to_string(),
+            bookmaker: Bookmaker::Pinnacle,
+            home: dec!(2.5),
+            away: dec!(4),
+            draw: Some(dec!(4)),
+            updated_at: Some(Utc::now()),
+        };
+
+        // Implied 0.4 / 0.25 / 0.25, overround 0.9
+        let row = to_bookmaker_odds(&link, &odds, Utc::now()).unwrap();
+        assert_eq!(row.yes_odds, dec!(2.5));
+        assert_eq!(row.no_odds, dec!(2));
+        assert_eq!(row.yes_implied_prob.value().round_dp(6), dec!(0.444444));
+        assert_eq!(row.yes_implied_prob.value() + row.no_implied_prob.value(), dec!(1));
+    }
+
+    #[test]
+    fn feed_latency_covers_lines_with_a_bookmaker_timestamp() {
+        let received_at = Utc::now();
+        let line = |delay_ms: Option<i64>| EventOdds {
+            event_id: "evt".to_string(),
+            bookmaker: Bookmaker::Pinnacle,
+            home: dec!(2),
+            away: dec!(2),
+            draw: None,
+            updated_at: delay_ms.map(|ms| received_at - chrono::Duration::milliseconds(ms)),
+        };
+
+        assert_eq!(feed_latency("pinnacle", &[line(None)], received_at), None);
+
+        let latency = feed_latency("the_odds_api", &[line(Some(900)), line(None), line(Some(200)), line(Some(4000))], received_at).unwrap();
+        assert_eq!((latency.lines, latency.median_ms, latency.max_ms), (3, 900, 4000));
+    }
+}
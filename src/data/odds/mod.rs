@@ -0,0 +1,89 @@
+mod betfair;
+mod ingest;
+mod pinnacle;
+mod the_odds_api;
+
+pub use ingest::OddsIngestor;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, BoxStream, StreamExt};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::time::Duration;
+
+use crate::config::OddsConfig;
+use crate::types::{Bookmaker, Sport};
+use betfair::BetfairProvider;
+use pinnacle::PinnacleProvider;
+use the_odds_api::TheOddsApiProvider;
+
+/// A fixture as listed by an odds provider
+#[derive(Debug, Clone)]
+pub struct OddsEvent {
+    /// Provider-specific id, stable across `fetch_events` and `fetch_odds`
+    pub event_id: String,
+    pub sport: Sport,
+    pub home_team: String,
+    pub away_team: String,
+    pub commence_time: DateTime<Utc>,
+}
+
+/// Moneyline decimal odds from one bookmaker for one event
+#[derive(Debug, Clone, Serialize)]
+pub struct EventOdds {
+    pub event_id: String,
+    pub bookmaker: Bookmaker,
+    pub home: Decimal,
+    pub away: Decimal,
+    /// Three-way markets (soccer) only
+    pub draw: Option<Decimal>,
+    /// When the bookmaker last changed the line; `None` if the feed does not say
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Source of reference bookmaker odds
+///
+/// Implementations only translate a provider's API into `OddsEvent` and
+/// `EventOdds`; matching to Polymarket markets and storage happen in
+/// `OddsIngestor`, so strategies never see provider specifics.
+#[async_trait]
+pub trait OddsProvider: Send + Sync {
+    /// Short name used in logs and the event-link cache
+    fn name(&self) -> &'static str;
+
+    async fn fetch_events(&self, sport: Sport) -> Result<Vec<OddsEvent>>;
+
+    async fn fetch_odds(&self, sport: Sport) -> Result<Vec<EventOdds>>;
+
+    /// Odds updates for a sport
+    ///
+    /// Polls `fetch_odds` by default; providers with a push feed can override.
+    fn stream_updates(&self, sport: Sport, poll_interval: Duration) -> BoxStream<'_, Result<Vec<EventOdds>>> {
+        let tick = tokio::time::interval(poll_interval);
+
+        stream::unfold(tick, move |mut tick| async move {
+            tick.tick().await;
+            Some((self.fetch_odds(sport).await, tick))
+        })
+        .boxed()
+    }
+}
+
+/// Build a provider for every section present in the `odds` config
+pub fn providers_from_config(config: &OddsConfig) -> Result<Vec<Box<dyn OddsProvider>>> {
+    let mut providers: Vec<Box<dyn OddsProvider>> = Vec::new();
+
+    if let Some(c) = &config.the_odds_api {
+        providers.push(Box::new(TheOddsApiProvider::new(c)?));
+    }
+    if let Some(c) = &config.pinnacle {
+        providers.push(Box::new(PinnacleProvider::new(c)?));
+    }
+    if let Some(c) = &config.betfair {
+        providers.push(Box::new(BetfairProvider::new(c)?));
+    }
+
+    Ok(providers)
+}
@@ -0,0 +1,160 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::config::PinnacleConfig;
+use crate::types::{Bookmaker, Sport};
+use super::{EventOdds, OddsEvent, OddsProvider};
+
+/// Pinnacle's line API (requires a funded account)
+pub struct PinnacleProvider {
+    http: Client,
+    api_url: String,
+    username: String,
+    password: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct FixturesResponse {
+    #[serde(default)]
+    league: Vec<FixtureLeague>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FixtureLeague {
+    events: Vec<Fixture>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Fixture {
+    id: i64,
+    starts: DateTime<Utc>,
+    home: String,
+    away: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OddsResponse {
+    #[serde(default)]
+    leagues: Vec<OddsLeague>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OddsLeague {
+    events: Vec<OddsEventLine>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OddsEventLine {
+    id: i64,
+    #[serde(default)]
+    periods: Vec<Period>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Period {
+    number: i32,
+    moneyline: Option<Moneyline>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Moneyline {
+    home: Decimal,
+    away: Decimal,
+    draw: Option<Decimal>,
+}
+
+impl PinnacleProvider {
+    pub fn new(config: &PinnacleConfig) -> Result<Self> {
+        let http = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()?;
+
+        Ok(Self {
+            http,
+            api_url: config.api_url.trim_end_matches('/').to_string(),
+            username: config.username.clone(),
+            password: config.password.clone(),
+        })
+    }
+
+    /// (sport id, league id)
+    fn ids(sport: Sport) -> (&'static str, &'static str) {
+        match sport {
+            Sport::NFL => ("15", "889"),
+            Sport::NBA => ("4", "487"),
+            Sport::PremierLeague => ("29", "1980"),
+            Sport::MLB => ("3", "246"),
+        }
+    }
+}
+
+#[async_trait]
+impl OddsProvider for PinnacleProvider {
+    fn name(&self) -> &'static str {
+        "pinnacle"
+    }
+
+    async fn fetch_events(&self, sport: Sport) -> Result<Vec<OddsEvent>> {
+        let (sport_id, league_id) = Self::ids(sport);
+        let fixtures: FixturesResponse = self.http
+            .get(format!("{}/v1/fixtures", self.api_url))
+            .basic_auth(&self.username, Some(&self.password))
+            .query(&[("sportId", sport_id), ("leagueIds", league_id)])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("decoding Pinnacle fixtures")?;
+
+        Ok(fixtures.league.into_iter()
+            .flat_map(|league| league.events)
+            .map(|e| OddsEvent {
+                event_id: e.id.to_string(),
+                sport,
+                home_team: e.home,
+                away_team: e.away,
+                commence_time: e.starts,
+            })
+            .collect())
+    }
+
+    async fn fetch_odds(&self, sport: Sport) -> Result<Vec<EventOdds>> {
+        let (sport_id, league_id) = Self::ids(sport);
+        let odds: OddsResponse = self.http
+            .get(format!("{}/v1/odds", self.api_url))
+            .basic_auth(&self.username, Some(&self.password))
+            .query(&[("sportId", sport_id), ("leagueIds", league_id), ("oddsFormat", "Decimal")])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("decoding Pinnacle odds")?;
+
+        Ok(odds.leagues.into_iter()
+            .flat_map(|league| league.events)
+            .filter_map(|event| {
+                // Period 0 is the full game
+                let moneyline = event.periods.into_iter()
+                    .find(|p| p.number == 0)?
+                    .moneyline?;
+
+                Some(EventOdds {
+                    event_id: event.id.to_string(),
+                    bookmaker: Bookmaker::Pinnacle,
+                    home: moneyline.home,
+                    away: moneyline.away,
+                    draw: moneyline.draw,
+                    // The odds feed carries no per-line update time
+                    updated_at: None,
+                })
+            })
+            .collect())
+    }
+}
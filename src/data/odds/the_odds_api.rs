@@ -0,0 +1,149 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::config::TheOddsApiConfig;
+use crate::types::{Bookmaker, Sport};
+use super::{EventOdds, OddsEvent, OddsProvider};
+
+/// The Odds API (the-odds-api.com): aggregated odds from many books
+pub struct TheOddsApiProvider {
+    http: Client,
+    api_url: String,
+    api_key: String,
+    regions: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiEvent {
+    id: String,
+    commence_time: DateTime<Utc>,
+    home_team: String,
+    away_team: String,
+    #[serde(default)]
+    bookmakers: Vec<ApiBookmaker>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiBookmaker {
+    key: String,
+    last_update: DateTime<Utc>,
+    markets: Vec<ApiMarket>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiMarket {
+    key: String,
+    outcomes: Vec<ApiOutcome>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiOutcome {
+    name: String,
+    price: Decimal,
+}
+
+impl TheOddsApiProvider {
+    pub fn new(config: &TheOddsApiConfig) -> Result<Self> {
+        let http = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()?;
+
+        Ok(Self {
+            http,
+            api_url: config.api_url.trim_end_matches('/').to_string(),
+            api_key: config.api_key.clone(),
+            regions: config.regions.clone(),
+        })
+    }
+
+    fn sport_key(sport: Sport) -> &'static str {
+        match sport {
+            Sport::NFL => "americanfootball_nfl",
+            Sport::NBA => "basketball_nba",
+            Sport::PremierLeague => "soccer_epl",
+            Sport::MLB => "baseball_mlb",
+        }
+    }
+
+    /// Books we track; the API lists many more
+    fn bookmaker(key: &str) -> Option<Bookmaker> {
+        match key {
+            "pinnacle" => Some(Bookmaker::Pinnacle),
+            "betfair_ex_eu" | "betfair_ex_uk" | "betfair_ex_au" => Some(Bookmaker::Betfair),
+            "draftkings" => Some(Bookmaker::DraftKings),
+            "fanduel" => Some(Bookmaker::FanDuel),
+            _ => None,
+        }
+    }
+
+    fn event_odds(event: &ApiEvent, book: &ApiBookmaker) -> Option<EventOdds> {
+        let h2h = book.markets.iter().find(|m| m.key == "h2h")?;
+        let price = |name: &str| h2h.outcomes.iter().find(|o| o.name == name).map(|o| o.price);
+
+        Some(EventOdds {
+            event_id: event.id.clone(),
+            bookmaker: Self::bookmaker(&book.key)?,
+            home: price(&event.home_team)?,
+            away: price(&event.away_team)?,
+            draw: price("Draw"),
+            updated_at: Some(book.last_update),
+        })
+    }
+}
+
+#[async_trait]
+impl OddsProvider for TheOddsApiProvider {
+    fn name(&self) -> &'static str {
+        "the_odds_api"
+    }
+
+    async fn fetch_events(&self, sport: Sport) -> Result<Vec<OddsEvent>> {
+        let events: Vec<ApiEvent> = self.http
+            .get(format!("{}/v4/sports/{}/events", self.api_url, Self::sport_key(sport)))
+            .query(&[("apiKey", self.api_key.as_str())])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("decoding The Odds API events")?;
+
+        Ok(events.into_iter()
+            .map(|e| OddsEvent {
+                event_id: e.id,
+                sport,
+                home_team: e.home_team,
+                away_team: e.away_team,
+                commence_time: e.commence_time,
+            })
+            .collect())
+    }
+
+    async fn fetch_odds(&self, sport: Sport) -> Result<Vec<EventOdds>> {
+        let events: Vec<ApiEvent> = self.http
+            .get(format!("{}/v4/sports/{}/odds", self.api_url, Self::sport_key(sport)))
+            .query(&[
+                ("apiKey", self.api_key.as_str()),
+                ("regions", self.regions.as_str()),
+                ("markets", "h2h"),
+                ("oddsFormat", "decimal"),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("decoding The Odds API odds")?;
+
+        Ok(events.iter()
+            .flat_map(|event| {
+                event.bookmakers.iter().filter_map(move |book| Self::event_odds(event, book))
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,445 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDate, NaiveDateTime, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Deserializer};
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use tracing::{debug, info, error};
+
+use crate::cache::ReferenceCache;
+use crate::config::Config;
+use crate::storage::Storage;
+use crate::types::{Game, OfficialAssignment, Sport};
+
+/// Role recorded for the umpire calling balls and strikes
+const HOME_PLATE: &str = "home_plate";
+
+/// Sports whose crews are read from ESPN's game summaries
+const ESPN_SPORTS: [Sport; 2] = [Sport::NFL, Sport::NBA];
+
+/// Polls announced officials into `official_assignments`
+///
+/// MLB home-plate umpires come from the MLB Stats API schedule; NFL
+/// referees and NBA crews from ESPN's game summaries. Assignments for
+/// finished games are kept, which is what builds each official's history in
+/// `v_official_tendencies`, and on startup the crews of the last
+/// `backfill_days` of games are read so that history exists from the first
+/// assignment on.
+pub struct OfficialsIngestor {
+    storage: Arc<dyn Storage>,
+    reference: Arc<ReferenceCache>,
+    http: Client,
+    mlb_stats_url: String,
+    espn_url: String,
+    backfill_days: i64,
+    poll_interval: Duration,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiSchedule {
+    #[serde(default)]
+    dates: Vec<ApiDate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiDate {
+    #[serde(default)]
+    games: Vec<ApiGame>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiGame {
+    game_date: DateTime<Utc>,
+    teams: ApiTeams,
+    /// Empty until the crew is announced
+    #[serde(default)]
+    officials: Vec<ApiOfficialAssignment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiTeams {
+    home: ApiSide,
+    away: ApiSide,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiSide {
+    team: ApiTeam,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiTeam {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiOfficialAssignment {
+    official: ApiOfficial,
+    official_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiOfficial {
+    full_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct EspnScoreboard {
+    #[serde(default)]
+    events: Vec<EspnEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EspnEvent {
+    id: String,
+    #[serde(deserialize_with = "espn_time")]
+    date: DateTime<Utc>,
+    #[serde(default)]
+    competitions: Vec<EspnCompetition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EspnCompetition {
+    #[serde(default)]
+    competitors: Vec<EspnCompetitor>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EspnCompetitor {
+    home_away: String,
+    team: EspnTeam,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EspnTeam {
+    display_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EspnSummary {
+    #[serde(default)]
+    game_info: Option<EspnGameInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EspnGameInfo {
+    /// Empty until the crew is announced
+    #[serde(default)]
+    officials: Vec<EspnOfficial>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EspnOfficial {
+    display_name: String,
+    position: EspnPosition,
+}
+
+#[derive(Debug, Deserialize)]
+struct EspnPosition {
+    name: String,
+}
+
+/// ESPN writes event times to the minute ("2025-01-15T01:00Z")
+fn espn_time<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime<Utc>, D::Error> {
+    let raw = String::deserialize(deserializer)?;
+    DateTime::parse_from_rfc3339(&raw)
+        .map(|t| t.with_timezone(&Utc))
+        .or_else(|_| NaiveDateTime::parse_from_str(&raw, "%Y-%m-%dT%H:%MZ").map(|t| t.and_utc()))
+        .map_err(serde::de::Error::custom)
+}
+
+/// Path of the sport under ESPN's site API
+fn espn_path(sport: Sport) -> Option<&'static str> {
+    match sport {
+        Sport::NFL => Some("football/nfl"),
+        Sport::NBA => Some("basketball/nba"),
+        _ => None,
+    }
+}
+
+impl OfficialsIngestor {
+    pub fn new(storage: Arc<dyn Storage>, reference: Arc<ReferenceCache>, config: &Config) -> Result<Self> {
+        let http = Client::builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .build()?;
+
+        Ok(Self {
+            storage,
+            reference,
+            http,
+            mlb_stats_url: config.officials.mlb_stats_url.trim_end_matches('/').to_string(),
+            espn_url: config.officials.espn_url.trim_end_matches('/').to_string(),
+            backfill_days: config.officials.backfill_days,
+            poll_interval: Duration::from_secs(config.officials.poll_interval_secs),
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let mut tick = interval(self.poll_interval);
+
+        info!("🧑‍⚖️ Officials ingestion started");
+        self.backfill().await;
+
+        loop {
+            tick.tick().await;
+
+            // Yesterday through two days out: late crew changes land on finished games too
+            let today = Utc::now().date_naive();
+            let (start, end) = (today - ChronoDuration::days(1), today + ChronoDuration::days(2));
+            if let Err(e) = self.ingest_mlb(start, end).await {
+                error!("Error ingesting MLB officials: {}", e);
+            }
+            for sport in ESPN_SPORTS {
+                if let Err(e) = self.ingest_espn(sport, start, end, true).await {
+                    error!("Error ingesting {} officials: {}", sport.as_str(), e);
+                }
+            }
+        }
+    }
+
+    /// Read the crews of finished games, which the tendencies are fitted from
+    async fn backfill(&self) {
+        if self.backfill_days <= 0 {
+            return;
+        }
+        let today = Utc::now().date_naive();
+        let (start, end) = (today - ChronoDuration::days(self.backfill_days), today - ChronoDuration::days(2));
+
+        if let Err(e) = self.ingest_mlb(start, end).await {
+            error!("Error backfilling MLB officials: {}", e);
+        }
+        for sport in ESPN_SPORTS {
+            if let Err(e) = self.ingest_espn(sport, start, end, false).await {
+                error!("Error backfilling {} officials: {}", sport.as_str(), e);
+            }
+        }
+        info!("🧑‍⚖️ Officials backfilled from {}", start);
+    }
+
+    /// Crews of `sport`'s games from `start` through `end`
+    ///
+    /// Each game's crew is a summary request of its own, so unless
+    /// `reread` games already crewed are skipped.
+    async fn ingest_espn(&self, sport: Sport, start: NaiveDate, end: NaiveDate, reread: bool) -> Result<()> {
+        let path = espn_path(sport).with_context(|| format!("no ESPN feed for {}", sport.as_str()))?;
+        let mut games = self.storage.fetch_game_history(sport).await?;
+        games.extend(self.storage.fetch_season_games(sport).await?.into_iter().filter(|g| !g.is_played()));
+
+        let mut stored = 0;
+        for day in start.iter_days().take_while(|day| *day <= end) {
+            let scoreboard: EspnScoreboard = self.http
+                .get(format!("{}/{}/scoreboard", self.espn_url, path))
+                .query(&[("dates", day.format("%Y%m%d").to_string())])
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await
+                .with_context(|| format!("decoding {} scoreboard of {}", sport.as_str(), day))?;
+
+            for event in &scoreboard.events {
+                let Some(game) = espn_game(event, &games) else {
+                    continue;
+                };
+                if !reread && !self.storage.fetch_official_tendencies(&game.game_id).await?.is_empty() {
+                    continue;
+                }
+
+                let summary: EspnSummary = self.http
+                    .get(format!("{}/{}/summary", self.espn_url, path))
+                    .query(&[("event", event.id.as_str())])
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await
+                    .with_context(|| format!("decoding {} summary of event {}", sport.as_str(), event.id))?;
+
+                let assignments = espn_assignments(sport, game, &summary);
+                for assignment in &assignments {
+                    self.storage.upsert_official_assignment(assignment).await?;
+                }
+                if !assignments.is_empty() {
+                    self.reference.invalidate_officials(&game.game_id).await;
+                    stored += assignments.len();
+                }
+            }
+        }
+
+        debug!("Stored {} {} official assignments", stored, sport.as_str());
+        Ok(())
+    }
+
+    async fn ingest_mlb(&self, start: NaiveDate, end: NaiveDate) -> Result<()> {
+        let (start, end) = (start.to_string(), end.to_string());
+
+        let schedule: ApiSchedule = self.http
+            .get(format!("{}/schedule", self.mlb_stats_url))
+            .query(&[
+                ("sportId", "1"),
+                ("startDate", start.as_str()),
+                ("endDate", end.as_str()),
+                ("hydrate", "officials"),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("decoding MLB schedule response")?;
+
+        let mut games = self.storage.fetch_game_history(Sport::MLB).await?;
+        games.extend(self.storage.fetch_season_games(Sport::MLB).await?.into_iter().filter(|g| !g.is_played()));
+
+        let mut stored = 0;
+        for api_game in schedule.dates.iter().flat_map(|d| &d.games) {
+            let Some(assignment) = to_assignment(api_game, &games) else {
+                continue;
+            };
+            self.storage.upsert_official_assignment(&assignment).await?;
+            self.reference.invalidate_officials(&assignment.game_id).await;
+            stored += 1;
+        }
+
+        debug!("Stored {} MLB umpire assignments", stored);
+        Ok(())
+    }
+}
+
+/// The home-plate assignment for one of our games, once announced
+fn to_assignment(api_game: &ApiGame, games: &[Game]) -> Option<OfficialAssignment> {
+    let umpire = api_game.officials.iter().find(|o| o.official_type == "Home Plate")?;
+    let fixture = format!("{} vs. {}", api_game.teams.home.team.name, api_game.teams.away.team.name);
+    let game = games.iter().find(|g| g.matches_event(&fixture, api_game.game_date))?;
+
+    Some(OfficialAssignment {
+        game_id: game.game_id.clone(),
+        official: umpire.official.full_name.clone(),
+        role: HOME_PLATE.to_string(),
+    })
+}
+
+/// One of our games an ESPN event is
+fn espn_game<'a>(event: &EspnEvent, games: &'a [Game]) -> Option<&'a Game> {
+    let competitors = &event.competitions.first()?.competitors;
+    let side = |home_away: &str| competitors.iter().find(|c| c.home_away == home_away).map(|c| c.team.display_name.as_str());
+    let fixture = format!("{} vs. {}", side("home")?, side("away")?);
+    games.iter().find(|g| g.matches_event(&fixture, event.date))
+}
+
+/// The crew of `game` worth a tendency, once announced
+///
+/// An NFL crew works together all season, so its referee stands for it; an
+/// NBA crew is drawn each night, so its three members are kept by role.
+fn espn_assignments(sport: Sport, game: &Game, summary: &EspnSummary) -> Vec<OfficialAssignment> {
+    let Some(info) = &summary.game_info else {
+        return Vec::new();
+    };
+
+    info.officials.iter()
+        .filter(|o| sport != Sport::NFL || o.position.name == "Referee")
+        .map(|o| OfficialAssignment {
+            game_id: game.game_id.clone(),
+            official: o.display_name.clone(),
+            role: o.position.name.to_lowercase().replace(' ', "_"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{MemoryStorage, SeasonRepo};
+
+    #[test]
+    fn maps_home_plate_umpire_to_our_game() {
+        let response = r#"{"dates": [{"games": [{
+            "gamePk": 745001,
+            "gameDate": "2025-06-01T23:05:00Z",
+            "teams": {
+                "home": {"team": {"name": "New York Yankees"}},
+                "away": {"team": {"name": "Boston Red Sox"}}
+            },
+            "officials": [
+                {"official": {"fullName": "Pat Hoberg"}, "officialType": "First Base"},
+                {"official": {"fullName": "Lance Barksdale"}, "officialType": "Home Plate"}
+            ]
+        }]}]}"#;
+        let schedule: ApiSchedule = serde_json::from_str(response).unwrap();
+        let game = Game {
+            game_id: "mlb-2025-nyy-bos-0601".to_string(),
+            sport: Sport::MLB,
+            season: 2025,
+            home_team: "New York Yankees".to_string(),
+            away_team: "Boston Red Sox".to_string(),
+            start_time: "2025-06-01T23:05:00Z".parse().unwrap(),
+            home_score: None,
+            away_score: None,
+        };
+
+        let assignment = to_assignment(&schedule.dates[0].games[0], &[game]).unwrap();
+        assert_eq!(assignment.game_id, "mlb-2025-nyy-bos-0601");
+        assert_eq!(assignment.official, "Lance Barksdale");
+        assert_eq!(assignment.role, HOME_PLATE);
+    }
+
+    #[tokio::test]
+    async fn fits_nba_crews_from_espn_summaries() {
+        let scoreboard = r#"{"events": [{
+            "id": "401705001",
+            "date": "2025-01-15T01:00Z",
+            "competitions": [{"competitors": [
+                {"homeAway": "home", "team": {"displayName": "Denver Nuggets"}},
+                {"homeAway": "away", "team": {"displayName": "Boston Celtics"}}
+            ]}]
+        }]}"#;
+        let summary = r#"{"gameInfo": {"officials": [
+            {"displayName": "Scott Foster", "position": {"name": "Crew Chief"}, "order": 1},
+            {"displayName": "Tony Brothers", "position": {"name": "Referee"}, "order": 2},
+            {"displayName": "Ben Taylor", "position": {"name": "Umpire"}, "order": 3}
+        ]}}"#;
+        let scoreboard: EspnScoreboard = serde_json::from_str(scoreboard).unwrap();
+        let summary: EspnSummary = serde_json::from_str(summary).unwrap();
+        let start: DateTime<Utc> = "2025-01-15T01:00:00Z".parse().unwrap();
+        let game = |game_id: &str, days: i64, scores: Option<(i32, i32)>| Game {
+            game_id: game_id.to_string(),
+            sport: Sport::NBA,
+            season: 2025,
+            home_team: "Denver Nuggets".to_string(),
+            away_team: "Boston Celtics".to_string(),
+            start_time: start + ChronoDuration::days(days),
+            home_score: scores.map(|s| s.0),
+            away_score: scores.map(|s| s.1),
+        };
+        let played = game("nba-played", 0, Some((130, 120)));
+        let average = game("nba-average", -7, Some((110, 100)));
+        let upcoming = game("nba-upcoming", 7, None);
+
+        let games = vec![average, played, upcoming.clone()];
+        let matched = espn_game(&scoreboard.events[0], &games).unwrap();
+        assert_eq!(matched.game_id, "nba-played");
+        let assignments = espn_assignments(Sport::NBA, matched, &summary);
+        let roles: Vec<&str> = assignments.iter().map(|a| a.role.as_str()).collect();
+        assert_eq!(roles, vec!["crew_chief", "referee", "umpire"]);
+        // An NFL crew is kept by its referee
+        assert_eq!(espn_assignments(Sport::NFL, matched, &summary).len(), 1);
+
+        // The crew's finished game gives each member a record once they are assigned again
+        let storage = MemoryStorage::new();
+        storage.seed_season(Vec::new(), games.clone()).await;
+        for assignment in &assignments {
+            storage.upsert_official_assignment(assignment).await.unwrap();
+            storage.upsert_official_assignment(&OfficialAssignment { game_id: upcoming.game_id.clone(), ..assignment.clone() }).await.unwrap();
+        }
+        let tendencies = storage.fetch_official_tendencies(&upcoming.game_id).await.unwrap();
+        assert_eq!(tendencies.len(), 3);
+        assert!(tendencies.iter().all(|t| t.games == 1 && (t.total_delta - 20.0).abs() < 1e-9));
+    }
+}
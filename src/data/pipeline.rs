@@ -0,0 +1,284 @@
+use anyhow::Result;
+use chrono::Utc;
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use tracing::{debug, info, warn, error};
+
+use crate::cache::ReferenceCache;
+use crate::config::Config;
+use crate::events::{Event, EventBus};
+use crate::models::schedule_spots;
+use crate::monitoring::{Heartbeats, Notifier};
+use crate::storage::Storage;
+use crate::types::{DeadLetter, GameFeature, Market, MarketListing, Sport};
+use super::microstructure::{market_features, taker_volume};
+use super::polymarket::{OrderBook, TakerTrade};
+use super::{DirtyMarkets, PolymarketClient, WhaleDetector};
+
+/// Prices seen this close to the start are kept as the market's closing line
+const CLOSING_LINE_WINDOW_MINUTES: i64 = 15;
+
+const SPORTS: [Sport; 4] = [Sport::NFL, Sport::NBA, Sport::PremierLeague, Sport::MLB];
+
+/// Keeps markets, prices, statuses and schedule features in storage current
+pub struct DataPipeline {
+    storage: Arc<dyn Storage>,
+    events: EventBus,
+    reference: Arc<ReferenceCache>,
+    dirty: DirtyMarkets,
+    polymarket: PolymarketClient,
+    poll_interval: Duration,
+    /// Book levels and trade window of microstructure features, when recorded
+    microstructure: Option<(usize, chrono::Duration)>,
+    whales: Option<WhaleDetector>,
+    heartbeats: Heartbeats,
+    notifier: Notifier,
+}
+
+impl DataPipeline {
+    pub async fn new(
+        storage: Arc<dyn Storage>,
+        events: EventBus,
+        reference: Arc<ReferenceCache>,
+        dirty: DirtyMarkets,
+        config: &Config,
+        heartbeats: Heartbeats,
+        notifier: Notifier,
+    ) -> Result<Self> {
+        Ok(Self {
+            storage,
+            events,
+            reference,
+            dirty,
+            polymarket: PolymarketClient::new(config)?,
+            poll_interval: Duration::from_secs(config.polymarket.poll_interval_secs),
+            microstructure: config.microstructure.enabled.then(|| (
+                config.microstructure.book_levels.max(1),
+                chrono::Duration::seconds(config.microstructure.flow_window_secs as i64),
+            )),
+            whales: WhaleDetector::from_config(&config.whales)?,
+            heartbeats,
+            notifier,
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let mut tick = interval(self.poll_interval);
+
+        info!("📡 Data pipeline started");
+
+        loop {
+            tick.tick().await;
+            self.heartbeats.beat("data_pipeline");
+
+            for sport in SPORTS {
+                if let Err(e) = self.refresh_markets(sport).await {
+                    error!("Error refreshing {} markets: {}", sport.as_str(), e);
+                }
+                if let Err(e) = self.refresh_schedule_features(sport).await {
+                    error!("Error refreshing {} schedule features: {}", sport.as_str(), e);
+                }
+            }
+        }
+    }
+
+    async fn refresh_markets(&self, sport: Sport) -> Result<()> {
+        let (markets, rejected) = self.polymarket.fetch_markets(sport).await?;
+        let now = Utc::now();
+        for listing in &rejected {
+            let key = listing.market_id.as_deref().unwrap_or("unknown");
+            debug!("{} listing {} did not parse: {}", sport.as_str(), key, listing.reason);
+            if let Err(e) = self.storage.record_dead_letter(DeadLetter::POLYMARKET_LISTING, key, &listing.reason, &listing.raw, now).await {
+                warn!("Failed to record dead letter for listing {}: {}", key, e);
+            }
+        }
+        self.storage.upsert_market_listing(&MarketListing {
+            sport,
+            listed: (markets.len() + rejected.len()) as i64,
+            ingested: markets.len() as i64,
+            polled_at: now,
+        }).await?;
+
+        for mut market in markets {
+            let book = self.attach_depth(&mut market).await;
+            self.ingest_market(&market).await?;
+            let Some(book) = book else {
+                continue;
+            };
+            if let Err(e) = self.storage.upsert_book_snapshot(&book.snapshot(&market.market_id, Utc::now())).await {
+                warn!("Failed to store order book for {}: {}", market.market_id, e);
+            }
+
+            let trades = match self.fetch_trades(&market).await {
+                Ok(trades) => trades,
+                Err(e) => {
+                    debug!("No recent trades for {}: {}", market.market_id, e);
+                    continue;
+                }
+            };
+            if let Err(e) = self.record_microstructure(&market, &book, &trades).await {
+                debug!("No microstructure for {}: {}", market.market_id, e);
+            }
+            if let Err(e) = self.record_large_trades(&market, &trades).await {
+                warn!("Failed to record large trades for {}: {}", market.market_id, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Store rest and travel features for games not yet played
+    ///
+    /// Played games keep the values last written before they started, so
+    /// the feature store holds what was known at the time for training.
+    async fn refresh_schedule_features(&self, sport: Sport) -> Result<()> {
+        let teams = self.storage.fetch_team_ratings(sport).await?;
+        let games = self.storage.fetch_season_games(sport).await?;
+        let now = Utc::now();
+
+        let mut stored = 0;
+        for (game, (home_spot, away_spot)) in games.iter().zip(schedule_spots(&teams, &games)) {
+            if game.is_played() || game.start_time <= now {
+                continue;
+            }
+
+            for (team, spot) in [(&game.home_team, home_spot), (&game.away_team, away_spot)] {
+                for (name, value) in spot.features() {
+                    let feature = GameFeature {
+                        game_id: game.game_id.clone(),
+                        team: team.clone(),
+                        name: name.to_string(),
+                        value,
+                        computed_at: now,
+                    };
+                    self.storage.upsert_game_feature(&feature).await?;
+                    stored += 1;
+                }
+            }
+        }
+
+        debug!("Stored {} {} schedule features", stored, sport.as_str());
+        Ok(())
+    }
+
+    /// Replace the listing's top of book with the CLOB's, which carries
+    /// sizes, returning the YES book
+    ///
+    /// Only tradable markets are worth the extra request; on failure the
+    /// listing's quote (without depth) is kept.
+    async fn attach_depth(&self, market: &mut Market) -> Option<OrderBook> {
+        let tokens = market.tokens.as_ref().filter(|_| market.status.allows_entry())?;
+
+        let book = match self.polymarket.fetch_book(&tokens.yes_token_id).await {
+            Ok(book) => book,
+            Err(e) => {
+                debug!("No order book for {}: {}", market.market_id, e);
+                return None;
+            }
+        };
+        match book.quote() {
+            Ok(Some(quote)) => {
+                market.yes_quote = quote;
+                market.no_quote = quote.complement();
+            }
+            Ok(None) => {}
+            Err(e) => debug!("Unusable order book for {}: {}", market.market_id, e),
+        }
+
+        Some(book)
+    }
+
+    /// The market's recent trades, when microstructure features or large
+    /// trades use them
+    async fn fetch_trades(&self, market: &Market) -> Result<Vec<TakerTrade>> {
+        match market.tokens.as_ref() {
+            Some(tokens) if self.microstructure.is_some() || self.whales.is_some() => {
+                self.polymarket.fetch_recent_trades(&tokens.condition_id).await
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Store the market's book depth and recent taker flow
+    async fn record_microstructure(&self, market: &Market, book: &OrderBook, trades: &[TakerTrade]) -> Result<()> {
+        let (Some((levels, window)), Some(tokens)) = (self.microstructure, market.tokens.as_ref()) else {
+            return Ok(());
+        };
+
+        let now = Utc::now();
+        let volume = taker_volume(trades, &tokens.yes_token_id, now - window);
+        let previous = self.storage.latest_microstructure(&market.market_id).await?;
+
+        let features = market_features(&market.market_id, book, levels, volume, previous.as_ref(), now);
+        self.storage.insert_microstructure(&features).await
+    }
+
+    /// Store and alert on large trades among the market's recent trades not
+    /// seen on an earlier poll
+    async fn record_large_trades(&self, market: &Market, trades: &[TakerTrade]) -> Result<()> {
+        let (Some(whales), Some(tokens)) = (self.whales.as_ref(), market.tokens.as_ref()) else {
+            return Ok(());
+        };
+
+        for trade in whales.large_trades(&market.market_id, tokens, trades) {
+            if !self.storage.insert_large_trade(&trade).await? {
+                continue;
+            }
+
+            let summary = format!(
+                "Large trade on {}: {} {} {} shares at {} (${}, {}x median) by {}",
+                market.market_id,
+                trade.side.as_str(),
+                trade.position.as_str(),
+                trade.shares.round_dp(2),
+                trade.price,
+                trade.notional.round_dp(0),
+                trade.size_multiple.round_dp(1),
+                trade.wallet,
+            );
+            info!("🐋 {}", summary);
+            self.notifier.info(summary);
+        }
+
+        Ok(())
+    }
+
+    /// Store a market and announce status transitions
+    pub(crate) async fn ingest_market(&self, market: &Market) -> Result<()> {
+        let previous = self.storage.fetch_market_status(&market.market_id).await?;
+
+        self.storage.upsert_market(market).await?;
+        self.dirty.observe_price(&market.market_id, market.yes_price.value());
+
+        // Overwritten each poll until the event starts, leaving the last pre-start prices
+        let now = Utc::now();
+        let until_start = market.event_time - now;
+        if until_start > chrono::Duration::zero() && until_start <= chrono::Duration::minutes(CLOSING_LINE_WINDOW_MINUTES) {
+            self.storage.upsert_closing_line(&market.market_id, market.yes_price, market.no_price, now).await?;
+        }
+
+        if let Some(previous) = previous.filter(|p| *p != market.status) {
+            info!(
+                "🔄 Market {} status {} -> {}",
+                market.market_id,
+                previous.as_str(),
+                market.status.as_str()
+            );
+
+            // Parsed questions are re-derived, and a market leaving play
+            // usually means a result, which moves ratings and strengths
+            self.reference.invalidate_market(&market.market_id);
+            self.dirty.mark(&market.market_id);
+            if !market.status.allows_entry() {
+                self.reference.invalidate_sport(market.sport).await;
+            }
+
+            self.events.publish(Event::MarketUpdated {
+                market_id: market.market_id.clone(),
+                status: market.status,
+            }).await;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,340 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use tracing::{debug, info, error};
+
+use crate::config::Config;
+use crate::storage::Storage;
+use crate::types::{BullpenUsage, Game, ProbablePitcher, Sport};
+
+/// Polls MLB probable pitchers into `probable_pitchers` and finished games'
+/// relief innings into `bullpen_usage`
+///
+/// Both come from the MLB Stats API: starters from the schedule with their
+/// season line from the player's stats, relief innings from the box score.
+/// Box scores are only fetched for games not yet recorded.
+pub struct PitcherIngestor {
+    storage: Arc<dyn Storage>,
+    http: Client,
+    mlb_stats_url: String,
+    poll_interval: Duration,
+    bullpen_window: ChronoDuration,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiSchedule {
+    #[serde(default)]
+    dates: Vec<ApiDate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiDate {
+    #[serde(default)]
+    games: Vec<ApiGame>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiGame {
+    game_pk: i64,
+    game_date: DateTime<Utc>,
+    status: ApiStatus,
+    teams: ApiTeams,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiStatus {
+    abstract_game_state: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiTeams {
+    home: ApiSide,
+    away: ApiSide,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiSide {
+    team: ApiTeam,
+    /// Absent until the club announces its starter
+    probable_pitcher: Option<ApiPerson>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiTeam {
+    name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiPerson {
+    id: i64,
+    full_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiPlayerStats {
+    #[serde(default)]
+    stats: Vec<ApiStatGroup>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiStatGroup {
+    #[serde(default)]
+    splits: Vec<ApiSplit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiSplit {
+    stat: ApiPitching,
+}
+
+/// Pitching line; the API sends figures as strings ("3.45", "98.2", "-.--")
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiPitching {
+    era: Option<String>,
+    innings_pitched: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiBoxscore {
+    teams: ApiBoxTeams,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiBoxTeams {
+    home: ApiBoxTeam,
+    away: ApiBoxTeam,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiBoxTeam {
+    /// Pitchers used, the starter first
+    #[serde(default)]
+    pitchers: Vec<i64>,
+    /// Keyed "ID<person id>"
+    #[serde(default)]
+    players: HashMap<String, ApiBoxPlayer>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiBoxPlayer {
+    #[serde(default)]
+    stats: ApiBoxStats,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ApiBoxStats {
+    #[serde(default)]
+    pitching: ApiPitching,
+}
+
+/// Innings from the API's "outs after the point" notation, e.g. "5.2" is 5⅔
+fn innings(value: &str) -> Option<f64> {
+    let (whole, outs) = value.split_once('.').unwrap_or((value, "0"));
+    Some(whole.parse::<f64>().ok()? + outs.parse::<f64>().ok()? / 3.0)
+}
+
+/// Innings thrown by everyone after the starter
+fn relief_innings(team: &ApiBoxTeam) -> f64 {
+    team.pitchers.iter()
+        .skip(1)
+        .filter_map(|id| team.players.get(&format!("ID{}", id)))
+        .filter_map(|player| innings(player.stats.pitching.innings_pitched.as_deref()?))
+        .sum()
+}
+
+/// Our game the API's game is
+fn our_game<'a>(api_game: &ApiGame, games: &'a [Game]) -> Option<&'a Game> {
+    let fixture = format!("{} vs. {}", api_game.teams.home.team.name, api_game.teams.away.team.name);
+    games.iter().find(|g| g.matches_event(&fixture, api_game.game_date))
+}
+
+/// Announced starters of one of our games, each with our name for the team
+fn announced_starters<'a>(api_game: &'a ApiGame, games: &[Game]) -> Vec<(String, String, &'a ApiPerson)> {
+    let Some(game) = our_game(api_game, games) else {
+        return Vec::new();
+    };
+
+    [(&api_game.teams.home, &game.home_team), (&api_game.teams.away, &game.away_team)]
+        .into_iter()
+        .filter_map(|(side, team)| Some((game.game_id.clone(), team.clone(), side.probable_pitcher.as_ref()?)))
+        .collect()
+}
+
+impl PitcherIngestor {
+    pub fn new(storage: Arc<dyn Storage>, config: &Config) -> Result<Self> {
+        let http = Client::builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .build()?;
+
+        Ok(Self {
+            storage,
+            http,
+            mlb_stats_url: config.pitchers.mlb_stats_url.trim_end_matches('/').to_string(),
+            poll_interval: Duration::from_secs(config.pitchers.poll_interval_secs),
+            bullpen_window: ChronoDuration::days(config.strategies.mlb.bullpen_window_days),
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let mut tick = interval(self.poll_interval);
+
+        info!("⚾ Pitcher ingestion started");
+
+        loop {
+            tick.tick().await;
+
+            if let Err(e) = self.ingest().await {
+                error!("Error ingesting MLB pitchers: {}", e);
+            }
+        }
+    }
+
+    async fn ingest(&self) -> Result<()> {
+        // Finished games back through the bullpen window, and two days of announcements
+        let now = Utc::now();
+        let since = now - self.bullpen_window;
+        let start = since.date_naive().to_string();
+        let end = (now.date_naive() + ChronoDuration::days(2)).to_string();
+
+        let schedule: ApiSchedule = self.http
+            .get(format!("{}/schedule", self.mlb_stats_url))
+            .query(&[
+                ("sportId", "1"),
+                ("startDate", start.as_str()),
+                ("endDate", end.as_str()),
+                ("hydrate", "probablePitcher"),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("decoding MLB schedule response")?;
+
+        let games = self.storage.fetch_season_games(Sport::MLB).await?;
+        let recorded: HashSet<(String, DateTime<Utc>)> = self.storage.fetch_bullpen_usage(since).await?
+            .into_iter()
+            .map(|u| (u.team, u.game_time))
+            .collect();
+
+        let (mut starters, mut bullpens) = (0, 0);
+        for api_game in schedule.dates.iter().flat_map(|d| &d.games) {
+            if api_game.status.abstract_game_state == "Final" {
+                // Our names for the teams where we have the game, so the model finds them
+                let (home, away) = match our_game(api_game, &games) {
+                    Some(game) => (game.home_team.clone(), game.away_team.clone()),
+                    None => (api_game.teams.home.team.name.clone(), api_game.teams.away.team.name.clone()),
+                };
+                if api_game.game_date >= since && !recorded.contains(&(home.clone(), api_game.game_date)) {
+                    bullpens += self.ingest_boxscore(api_game, [home, away]).await?;
+                }
+            } else if api_game.game_date > now {
+                for (game_id, team, person) in announced_starters(api_game, &games) {
+                    let (era, innings_pitched) = self.season_line(person.id, api_game.game_date.year()).await?;
+                    self.storage.upsert_probable_pitcher(&ProbablePitcher {
+                        game_id,
+                        team,
+                        pitcher: person.full_name.clone(),
+                        era,
+                        innings_pitched,
+                    }).await?;
+                    starters += 1;
+                }
+            }
+        }
+
+        debug!("Stored {} MLB probable pitchers and {} bullpen lines", starters, bullpens);
+        Ok(())
+    }
+
+    /// (ERA, innings) of a pitcher's season so far
+    async fn season_line(&self, person_id: i64, season: i32) -> Result<(Option<f64>, f64)> {
+        let season = season.to_string();
+        let stats: ApiPlayerStats = self.http
+            .get(format!("{}/people/{}/stats", self.mlb_stats_url, person_id))
+            .query(&[("stats", "season"), ("group", "pitching"), ("season", season.as_str())])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("decoding MLB pitcher stats response")?;
+
+        let line = stats.stats.into_iter().flat_map(|g| g.splits).next().map(|s| s.stat).unwrap_or_default();
+        let innings_pitched = line.innings_pitched.as_deref().and_then(innings).unwrap_or(0.0);
+        Ok((line.era.and_then(|era| era.parse().ok()), innings_pitched))
+    }
+
+    /// Record both bullpens of a finished game under the (home, away) team
+    /// names given; returns the lines stored
+    async fn ingest_boxscore(&self, api_game: &ApiGame, teams: [String; 2]) -> Result<usize> {
+        let boxscore: ApiBoxscore = self.http
+            .get(format!("{}/game/{}/boxscore", self.mlb_stats_url, api_game.game_pk))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("decoding MLB boxscore response")?;
+
+        for (side, team) in [&boxscore.teams.home, &boxscore.teams.away].into_iter().zip(teams) {
+            self.storage.upsert_bullpen_usage(&BullpenUsage {
+                team,
+                game_time: api_game.game_date,
+                innings: relief_innings(side),
+            }).await?;
+        }
+        Ok(2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_starters_and_relief_innings() {
+        let response = r#"{"dates": [{"games": [{
+            "gamePk": 745001,
+            "gameDate": "2025-06-01T23:05:00Z",
+            "status": {"abstractGameState": "Preview"},
+            "teams": {
+                "home": {"team": {"name": "New York Yankees"}, "probablePitcher": {"id": 543037, "fullName": "Gerrit Cole"}},
+                "away": {"team": {"name": "Boston Red Sox"}}
+            }
+        }]}]}"#;
+        let schedule: ApiSchedule = serde_json::from_str(response).unwrap();
+        let game = Game {
+            game_id: "mlb-2025-nyy-bos-0601".to_string(),
+            sport: Sport::MLB,
+            season: 2025,
+            home_team: "New York Yankees".to_string(),
+            away_team: "Boston Red Sox".to_string(),
+            start_time: "2025-06-01T23:05:00Z".parse().unwrap(),
+            home_score: None,
+            away_score: None,
+        };
+
+        // Only the side that has announced
+        let starters = announced_starters(&schedule.dates[0].games[0], &[game]);
+        assert_eq!(starters.len(), 1);
+        assert_eq!((starters[0].1.as_str(), starters[0].2.full_name.as_str()), ("New York Yankees", "Gerrit Cole"));
+
+        let boxscore = r#"{"team": {"name": "New York Yankees"}, "pitchers": [1, 2, 3], "players": {
+            "ID1": {"stats": {"pitching": {"inningsPitched": "6.0"}}},
+            "ID2": {"stats": {"pitching": {"inningsPitched": "1.2"}}},
+            "ID3": {"stats": {"pitching": {"inningsPitched": "1.1"}}}
+        }}"#;
+        let team: ApiBoxTeam = serde_json::from_str(boxscore).unwrap();
+        assert!((relief_innings(&team) - 3.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,411 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::types::{BookSnapshot, DepthLevel, Market, MarketStatus, MarketTokens, MarketType, Price, Quote, Resolution, Sport};
+
+/// Phrases naming a season-long prize ("win the AFC West", "win Super Bowl LX")
+/// or a win total ("win 11+ games")
+const FUTURES_TERMS: [&str; 14] = [
+    "super bowl", "finals", "championship", "world series", "pennant",
+    "division", "conference", " east", " west", " north", " south", " central",
+    "+ games", "regular season",
+];
+
+/// Polymarket markets API client
+pub struct PolymarketClient {
+    http: Client,
+    api_url: String,
+    clob_url: String,
+    data_api_url: String,
+}
+
+/// Market as returned by the markets endpoint (only the fields we use)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiMarket {
+    id: String,
+    question: String,
+    description: Option<String>,
+    resolution_source: Option<String>,
+    end_date: Option<DateTime<Utc>>,
+    game_start_time: Option<DateTime<Utc>>,
+    /// JSON-encoded array of outcome prices, e.g. "[\"0.52\", \"0.48\"]"
+    outcome_prices: Option<String>,
+    /// Top of the YES book; the NO book mirrors it
+    best_bid: Option<Decimal>,
+    best_ask: Option<Decimal>,
+    liquidity_num: Option<Decimal>,
+    #[serde(default)]
+    active: bool,
+    #[serde(default)]
+    closed: bool,
+    #[serde(default)]
+    accepting_orders: bool,
+    uma_resolution_status: Option<String>,
+    condition_id: Option<String>,
+    #[serde(rename = "questionID")]
+    question_id: Option<String>,
+    /// JSON-encoded array of outcome token ids, YES first
+    clob_token_ids: Option<String>,
+}
+
+/// A listing that did not become a market, kept as received
+#[derive(Debug, Clone)]
+pub struct RejectedListing {
+    /// The listing's id, when it got far enough to read one
+    pub market_id: Option<String>,
+    pub reason: String,
+    pub raw: serde_json::Value,
+}
+
+/// Order book for one outcome token from the CLOB
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OrderBook {
+    #[serde(default)]
+    pub bids: Vec<BookLevel>,
+    #[serde(default)]
+    pub asks: Vec<BookLevel>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BookLevel {
+    pub price: Decimal,
+    pub size: Decimal,
+}
+
+impl OrderBook {
+    /// Top of book with depth; `None` if either side is empty or the book is crossed
+    pub fn quote(&self) -> Result<Option<Quote>> {
+        // Levels are not guaranteed to be sorted best-first
+        let best_bid = self.bids.iter().max_by(|a, b| a.price.cmp(&b.price));
+        let best_ask = self.asks.iter().min_by(|a, b| a.price.cmp(&b.price));
+        let (Some(bid), Some(ask)) = (best_bid, best_ask) else {
+            return Ok(None);
+        };
+        if bid.price > ask.price {
+            return Ok(None);
+        }
+
+        Ok(Some(Quote {
+            bid: Price::new(bid.price)?,
+            ask: Price::new(ask.price)?,
+            bid_size: Some(bid.size),
+            ask_size: Some(ask.size),
+        }))
+    }
+
+    /// The book for storing against `market_id`
+    pub fn snapshot(&self, market_id: &str, captured_at: DateTime<Utc>) -> BookSnapshot {
+        let levels = |levels: &[BookLevel]| levels.iter().map(|l| DepthLevel { price: l.price, size: l.size }).collect();
+        BookSnapshot {
+            market_id: market_id.to_string(),
+            bids: levels(&self.bids),
+            asks: levels(&self.asks),
+            captured_at,
+        }
+    }
+}
+
+/// A trade on one outcome token, from the taker's side
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TakerTrade {
+    /// Outcome token traded
+    pub asset: String,
+    /// "BUY" or "SELL"
+    pub side: String,
+    pub size: Decimal,
+    #[serde(default)]
+    pub price: Decimal,
+    /// Unix seconds
+    pub timestamp: i64,
+    #[serde(default)]
+    pub transaction_hash: String,
+    /// Taker's proxy wallet
+    #[serde(default)]
+    pub proxy_wallet: String,
+}
+
+/// Price history of an outcome token from the CLOB
+#[derive(Debug, Deserialize)]
+struct PriceHistory {
+    #[serde(default)]
+    history: Vec<HistoryPoint>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryPoint {
+    /// Unix seconds
+    t: i64,
+    p: Decimal,
+}
+
+impl PolymarketClient {
+    pub fn new(config: &Config) -> Result<Self> {
+        let http = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()?;
+
+        Ok(Self {
+            http,
+            api_url: config.polymarket.api_url.trim_end_matches('/').to_string(),
+            clob_url: config.polymarket.clob_url.trim_end_matches('/').to_string(),
+            data_api_url: config.microstructure.data_api_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Fetch open markets for a sport, with the listings that did not parse
+    ///
+    /// Listings are decoded one at a time so a malformed one is rejected on
+    /// its own rather than failing the whole response.
+    pub async fn fetch_markets(&self, sport: Sport) -> Result<(Vec<Market>, Vec<RejectedListing>)> {
+        let listings: Vec<serde_json::Value> = self.http
+            .get(format!("{}/markets", self.api_url))
+            .query(&[("tag_slug", Self::tag_slug(sport)), ("closed", "false")])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("decoding markets response")?;
+
+        let now = Utc::now();
+        let mut markets = Vec::new();
+        let mut rejected = Vec::new();
+        for raw in listings {
+            let market_id = raw.get("id").and_then(|id| id.as_str()).map(str::to_string);
+            let parsed = serde_json::from_value::<ApiMarket>(raw.clone())
+                .map_err(|e| format!("undecodable listing: {}", e))
+                .and_then(|m| m.into_market(sport, now).map_err(str::to_string));
+            match parsed {
+                Ok(market) => markets.push(market),
+                Err(reason) => rejected.push(RejectedListing { market_id, reason, raw }),
+            }
+        }
+        Ok((markets, rejected))
+    }
+
+    /// A page of closed markets for a sport that ended after `since`, with
+    /// how each resolved once its final prices are in, and how many markets
+    /// the page held before unusable ones were dropped
+    pub async fn fetch_closed_markets(
+        &self,
+        sport: Sport,
+        since: DateTime<Utc>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(usize, Vec<(Market, Option<Resolution>)>)> {
+        let markets: Vec<ApiMarket> = self.http
+            .get(format!("{}/markets", self.api_url))
+            .query(&[("tag_slug", Self::tag_slug(sport)), ("closed", "true")])
+            .query(&[("end_date_min", since.to_rfc3339())])
+            .query(&[("offset", offset), ("limit", limit)])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("decoding closed markets response")?;
+
+        let fetched = markets.len();
+        let now = Utc::now();
+        let markets = markets.into_iter()
+            .filter_map(|m| {
+                let resolution = m.resolution();
+                Some((m.into_market(sport, now).ok()?, resolution))
+            })
+            .collect();
+        Ok((fetched, markets))
+    }
+
+    /// One market by id, open or closed, with how it resolved once its final
+    /// prices are in; `None` if the listing does not parse
+    pub async fn fetch_market(&self, market_id: &str, sport: Sport) -> Result<Option<(Market, Option<Resolution>)>> {
+        let market: ApiMarket = self.http
+            .get(format!("{}/markets/{}", self.api_url, market_id))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("decoding market response")?;
+
+        let resolution = market.resolution();
+        Ok(market.into_market(sport, Utc::now()).ok().map(|m| (m, resolution)))
+    }
+
+    /// Price history of an outcome token since `since`, one point per
+    /// `fidelity_mins` minutes
+    pub async fn fetch_price_history(
+        &self,
+        token_id: &str,
+        since: DateTime<Utc>,
+        fidelity_mins: u32,
+    ) -> Result<Vec<(DateTime<Utc>, Decimal)>> {
+        let history: PriceHistory = self.http
+            .get(format!("{}/prices-history", self.clob_url))
+            .query(&[("market", token_id)])
+            .query(&[("startTs", since.timestamp()), ("endTs", Utc::now().timestamp())])
+            .query(&[("fidelity", fidelity_mins)])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("decoding price history response")?;
+
+        Ok(history.history.into_iter()
+            .filter_map(|point| Some((Utc.timestamp_opt(point.t, 0).single()?, point.p)))
+            .collect())
+    }
+
+    /// Full order book for an outcome token
+    pub async fn fetch_book(&self, token_id: &str) -> Result<OrderBook> {
+        self.http
+            .get(format!("{}/book", self.clob_url))
+            .query(&[("token_id", token_id)])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("decoding order book response")
+    }
+
+    /// Most recent trades in a market (both outcome tokens), newest first
+    pub async fn fetch_recent_trades(&self, condition_id: &str) -> Result<Vec<TakerTrade>> {
+        self.http
+            .get(format!("{}/trades", self.data_api_url))
+            .query(&[("market", condition_id), ("limit", "500")])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("decoding trades response")
+    }
+
+    fn tag_slug(sport: Sport) -> &'static str {
+        match sport {
+            Sport::NFL => "nfl",
+            Sport::NBA => "nba",
+            Sport::PremierLeague => "epl",
+            Sport::MLB => "mlb",
+        }
+    }
+}
+
+impl ApiMarket {
+    /// Map the API flags onto our status
+    ///
+    /// Order matters: a disputed market is also closed, and a suspended book
+    /// may belong to a game that has already started.
+    fn status(&self, now: DateTime<Utc>) -> MarketStatus {
+        match self.uma_resolution_status.as_deref() {
+            Some("disputed") => return MarketStatus::Disputed,
+            Some("resolved") => return MarketStatus::Resolved,
+            _ => {}
+        }
+
+        if self.closed || !self.active {
+            MarketStatus::Closed
+        } else if !self.accepting_orders {
+            MarketStatus::Suspended
+        } else if self.game_start_time.map_or(false, |start| start <= now) {
+            MarketStatus::InPlay
+        } else {
+            MarketStatus::Active
+        }
+    }
+
+    /// How a closed market resolved, from its final YES price: 1 or 0, or
+    /// 0.5 for a market resolved 50/50; `None` until prices settle there
+    fn resolution(&self) -> Option<Resolution> {
+        if !self.closed {
+            return None;
+        }
+        let prices: Vec<String> = serde_json::from_str(self.outcome_prices.as_deref()?).ok()?;
+        let yes: Decimal = prices.first()?.parse().ok()?;
+
+        if yes == Decimal::ONE {
+            Some(Resolution::Yes)
+        } else if yes.is_zero() {
+            Some(Resolution::No)
+        } else if yes == Decimal::new(5, 1) {
+            Some(Resolution::Invalid)
+        } else {
+            None
+        }
+    }
+
+    fn market_type(&self) -> MarketType {
+        let question = self.question.to_lowercase();
+        let joins_legs = question.contains(" and ") || question.contains(" & ");
+        if joins_legs && (question.contains("over") || question.contains("under")) {
+            MarketType::Combo
+        } else if question.contains("win") && FUTURES_TERMS.iter().any(|t| question.contains(t)) {
+            MarketType::Futures
+        } else if question.contains("o/u") || question.contains("over/under") || question.contains("total") {
+            MarketType::Total
+        } else if question.contains("spread") || question.contains('(') {
+            MarketType::Spread
+        } else {
+            MarketType::Moneyline
+        }
+    }
+
+    fn tokens(&self) -> Option<MarketTokens> {
+        let token_ids: Vec<String> = serde_json::from_str(self.clob_token_ids.as_deref()?).ok()?;
+        let [yes_token_id, no_token_id]: [String; 2] = token_ids.try_into().ok()?;
+
+        Some(MarketTokens {
+            condition_id: self.condition_id.clone()?,
+            question_id: self.question_id.clone()?,
+            yes_token_id,
+            no_token_id,
+        })
+    }
+
+    /// The market this listing describes, or why it cannot be used
+    fn into_market(self, sport: Sport, now: DateTime<Utc>) -> Result<Market, &'static str> {
+        let prices: Vec<String> = self.outcome_prices.as_deref()
+            .and_then(|p| serde_json::from_str(p).ok())
+            .ok_or("missing or malformed outcome prices")?;
+        let price = |i: usize| prices.get(i)
+            .and_then(|p| p.parse().ok())
+            .and_then(|p| Price::new(p).ok());
+        let yes_price = price(0).ok_or("invalid YES price")?;
+        let no_price = price(1).ok_or("invalid NO price")?;
+        let event_time = self.game_start_time.or(self.end_date).ok_or("no start or end time")?;
+
+        let yes_quote = Quote::from_book(self.best_bid, self.best_ask, yes_price);
+        let status = self.status(now);
+        let market_type = self.market_type();
+        let tokens = self.tokens();
+
+        Ok(Market {
+            market_id: self.id,
+            sport,
+            event_name: self.question,
+            event_time,
+            market_type,
+            description: self.description,
+            resolution_source: self.resolution_source,
+            min_liquidity: Decimal::ZERO,
+            current_liquidity: self.liquidity_num.unwrap_or(Decimal::ZERO),
+            yes_price,
+            no_price,
+            yes_quote,
+            no_quote: yes_quote.complement(),
+            status,
+            tokens,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+}
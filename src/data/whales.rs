@@ -0,0 +1,131 @@
+use anyhow::{Context, Result};
+use chrono::{TimeZone, Utc};
+use rust_decimal::Decimal;
+
+use crate::config::WhalesConfig;
+use crate::numeric;
+use crate::types::{FillSide, LargeTrade, MarketTokens, Position};
+use super::polymarket::TakerTrade;
+
+/// Median notional of `trades`, in USDC
+fn median_notional(trades: &[TakerTrade]) -> Option<Decimal> {
+    let mut notionals: Vec<_> = trades.iter().map(|t| t.size * t.price).collect();
+    if notionals.is_empty() {
+        return None;
+    }
+    notionals.sort();
+
+    let mid = notionals.len() / 2;
+    Some(if notionals.len() % 2 == 0 {
+        (notionals[mid - 1] + notionals[mid]) / Decimal::TWO
+    } else {
+        notionals[mid]
+    })
+}
+
+/// Flags trades that are large both in absolute terms and against the
+/// market's typical trade
+pub struct WhaleDetector {
+    min_notional: Decimal,
+    size_multiple: Decimal,
+}
+
+impl WhaleDetector {
+    /// `None` when large-trade detection is disabled
+    pub fn from_config(config: &WhalesConfig) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            min_notional: numeric::to_decimal(config.min_notional_usd)
+                .context("whales.min_notional_usd")?,
+            size_multiple: numeric::to_decimal(config.size_multiple)
+                .context("whales.size_multiple")?,
+        }))
+    }
+
+    /// Trades among a market's recent `trades` at or above both the notional
+    /// floor and `size_multiple` times their median notional
+    pub fn large_trades(&self, market_id: &str, tokens: &MarketTokens, trades: &[TakerTrade]) -> Vec<LargeTrade> {
+        let Some(median) = median_notional(trades).filter(|m| *m > Decimal::ZERO) else {
+            return Vec::new();
+        };
+
+        trades.iter()
+            .filter_map(|trade| {
+                let notional = trade.size * trade.price;
+                let size_multiple = notional / median;
+                if notional < self.min_notional || size_multiple < self.size_multiple {
+                    return None;
+                }
+                let position = if trade.asset == tokens.yes_token_id {
+                    Position::Yes
+                } else if trade.asset == tokens.no_token_id {
+                    Position::No
+                } else {
+                    return None;
+                };
+
+                Some(LargeTrade {
+                    tx_hash: trade.transaction_hash.clone(),
+                    market_id: market_id.to_string(),
+                    wallet: trade.proxy_wallet.clone(),
+                    position,
+                    side: FillSide::parse(&trade.side.to_ascii_lowercase())?,
+                    shares: trade.size,
+                    price: trade.price,
+                    notional,
+                    size_multiple: size_multiple.round_dp(4),
+                    traded_at: Utc.timestamp_opt(trade.timestamp, 0).single()?,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn flags_trades_large_in_absolute_and_relative_terms() {
+        let tokens = MarketTokens {
+            condition_id: "0xcond".to_string(),
+            question_id: "0xq".to_string(),
+            yes_token_id: "yes".to_string(),
+            no_token_id: "no".to_string(),
+        };
+        let trade = |asset: &str, side: &str, size, hash: &str| TakerTrade {
+            asset: asset.to_string(),
+            side: side.to_string(),
+            size,
+            price: dec!(0.5),
+            timestamp: 1_700_000_000,
+            transaction_hash: hash.to_string(),
+            proxy_wallet: "0xwhale".to_string(),
+        };
+
+        // $100 trades, one $30k NO sale, one $12k YES buy
+        let mut trades: Vec<_> = (0..20).map(|i| trade("yes", "BUY", dec!(200), &format!("0x{}", i))).collect();
+        trades.push(trade("no", "SELL", dec!(60000), "0xbig"));
+        trades.push(trade("yes", "BUY", dec!(24000), "0xbig2"));
+
+        let detector = WhaleDetector::from_config(&WhalesConfig::default()).unwrap().unwrap();
+        let large = detector.large_trades("0xmarket", &tokens, &trades);
+        assert_eq!(large.len(), 2);
+        assert_eq!(large[0].tx_hash, "0xbig");
+        assert_eq!(large[0].notional, dec!(30000));
+        assert_eq!(large[0].size_multiple, dec!(300));
+        // Selling NO backs YES
+        assert_eq!(large[0].backs(), Position::Yes);
+        assert_eq!(large[1].backs(), Position::Yes);
+
+        // Big against the market, but under the notional floor
+        let small: Vec<_> = (0..20).map(|i| trade("yes", "BUY", dec!(2), &format!("0x{}", i)))
+            .chain([trade("yes", "BUY", dec!(2000), "0xmid")])
+            .collect();
+        assert!(detector.large_trades("0xmarket", &tokens, &small).is_empty());
+    }
+}
@@ -0,0 +1,261 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use futures::stream::{select_all, BoxStream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::cache::Cache;
+use crate::types::{MarketStatus, OnchainFill, Signal, Trade};
+
+/// Pub/sub channel carrying each stored signal as JSON
+pub const SIGNALS_CHANNEL: &str = "signals";
+
+/// Channel carrying `{market_id, status}` whenever a market changes status
+pub const MARKET_STATUS_CHANNEL: &str = "market_status";
+
+/// Pub/sub channel carrying trades opened and closed by the engine as JSON
+pub const ORDERS_CHANNEL: &str = "orders";
+
+/// Pub/sub channel carrying each newly recorded fill as JSON
+pub const FILLS_CHANNEL: &str = "fills";
+
+/// Channel carrying `{reason, triggered_at}` whenever a circuit breaker trips
+pub const BREAKERS_CHANNEL: &str = "breakers";
+
+/// What happened to a trade in an `OrderUpdated` event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderUpdate {
+    /// The engine entered it
+    Opened,
+    /// It was sold or settled
+    Closed,
+    /// Its entry was corrected to what the chain filled
+    Corrected,
+}
+
+impl OrderUpdate {
+    pub fn as_str(&self) -> &str {
+        match self {
+            OrderUpdate::Opened => "opened",
+            OrderUpdate::Closed => "closed",
+            OrderUpdate::Corrected => "corrected",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "opened" => Some(OrderUpdate::Opened),
+            "closed" => Some(OrderUpdate::Closed),
+            "corrected" => Some(OrderUpdate::Corrected),
+            _ => None,
+        }
+    }
+}
+
+/// Something one service did that others may react to
+#[derive(Debug, Clone)]
+pub enum Event {
+    /// A market changed status
+    MarketUpdated { market_id: String, status: MarketStatus },
+    /// A signal was stored and awaits execution
+    SignalGenerated(Signal),
+    /// A trade was opened, closed or corrected; `trade` is as it stands after
+    OrderUpdated { update: OrderUpdate, trade: Box<Trade> },
+    /// A fill of one of our orders was recorded from the chain
+    OrderFilled(OnchainFill),
+    /// A circuit breaker tripped, halting new entries
+    BreakerTriggered { reason: String, triggered_at: DateTime<Utc> },
+}
+
+/// Which events a subscriber wants
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    MarketUpdated,
+    SignalGenerated,
+    OrderUpdated,
+    OrderFilled,
+    BreakerTriggered,
+}
+
+impl EventKind {
+    /// Pub/sub channel the kind travels on
+    pub fn channel(&self) -> &'static str {
+        match self {
+            EventKind::MarketUpdated => MARKET_STATUS_CHANNEL,
+            EventKind::SignalGenerated => SIGNALS_CHANNEL,
+            EventKind::OrderUpdated => ORDERS_CHANNEL,
+            EventKind::OrderFilled => FILLS_CHANNEL,
+            EventKind::BreakerTriggered => BREAKERS_CHANNEL,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct MarketStatusMessage {
+    market_id: String,
+    status: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct OrderMessage {
+    event: String,
+    trade: Trade,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BreakerMessage {
+    reason: String,
+    triggered_at: DateTime<Utc>,
+}
+
+impl Event {
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Event::MarketUpdated { .. } => EventKind::MarketUpdated,
+            Event::SignalGenerated(_) => EventKind::SignalGenerated,
+            Event::OrderUpdated { .. } => EventKind::OrderUpdated,
+            Event::OrderFilled(_) => EventKind::OrderFilled,
+            Event::BreakerTriggered { .. } => EventKind::BreakerTriggered,
+        }
+    }
+
+    /// The event's message on its channel; the payloads external
+    /// subscribers (gRPC clients, other processes on Redis) already read
+    fn encode(&self) -> Result<String> {
+        Ok(match self {
+            Event::MarketUpdated { market_id, status } => serde_json::to_string(&MarketStatusMessage {
+                market_id: market_id.clone(),
+                status: status.as_str().to_string(),
+            })?,
+            Event::SignalGenerated(signal) => serde_json::to_string(signal)?,
+            Event::OrderUpdated { update, trade } => serde_json::to_string(&OrderMessage {
+                event: update.as_str().to_string(),
+                trade: (**trade).clone(),
+            })?,
+            Event::OrderFilled(fill) => serde_json::to_string(fill)?,
+            Event::BreakerTriggered { reason, triggered_at } => serde_json::to_string(&BreakerMessage {
+                reason: reason.clone(),
+                triggered_at: *triggered_at,
+            })?,
+        })
+    }
+
+    fn decode(kind: EventKind, message: &str) -> Result<Self> {
+        Ok(match kind {
+            EventKind::MarketUpdated => {
+                let message: MarketStatusMessage = serde_json::from_str(message)?;
+                let status = MarketStatus::parse(&message.status)
+                    .with_context(|| format!("unknown market status {}", message.status))?;
+                Event::MarketUpdated { market_id: message.market_id, status }
+            }
+            EventKind::SignalGenerated => Event::SignalGenerated(serde_json::from_str(message)?),
+            EventKind::OrderUpdated => {
+                let message: OrderMessage = serde_json::from_str(message)?;
+                let update = OrderUpdate::parse(&message.event)
+                    .with_context(|| format!("unknown order event {}", message.event))?;
+                Event::OrderUpdated { update, trade: Box::new(message.trade) }
+            }
+            EventKind::OrderFilled => Event::OrderFilled(serde_json::from_str(message)?),
+            EventKind::BreakerTriggered => {
+                let message: BreakerMessage = serde_json::from_str(message)?;
+                Event::BreakerTriggered { reason: message.reason, triggered_at: message.triggered_at }
+            }
+        })
+    }
+}
+
+/// Typed events between services, carried over the cache's pub/sub
+///
+/// Each kind keeps its own channel and JSON payload, so with Redis the
+/// events reach services in other processes and external subscribers, and
+/// with the in-process cache they stay in memory. Delivery is best effort:
+/// services still sweep storage on their own interval for anything missed.
+#[derive(Clone)]
+pub struct EventBus {
+    cache: Arc<dyn Cache>,
+}
+
+impl EventBus {
+    pub fn new(cache: Arc<dyn Cache>) -> Self {
+        Self { cache }
+    }
+
+    /// Publish `event` to every current subscriber of its kind; failures
+    /// are logged rather than returned
+    pub async fn publish(&self, event: Event) {
+        let channel = event.kind().channel();
+        let result = match event.encode() {
+            Ok(message) => self.cache.publish(channel, &message).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            warn!("Failed to publish to {}: {}", channel, e);
+        }
+    }
+
+    /// Stream of events of `kinds` published after subscribing
+    ///
+    /// Messages that do not decode are logged and skipped.
+    pub async fn subscribe(&self, kinds: &[EventKind]) -> Result<BoxStream<'static, Event>> {
+        let mut streams = Vec::with_capacity(kinds.len());
+        for &kind in kinds {
+            let messages = self.cache.subscribe(kind.channel()).await
+                .with_context(|| format!("subscribing to {}", kind.channel()))?;
+            streams.push(messages.filter_map(move |message| async move {
+                match Event::decode(kind, &message) {
+                    Ok(event) => Some(event),
+                    Err(e) => {
+                        warn!("Dropping undecodable {} message: {}", kind.channel(), e);
+                        None
+                    }
+                }
+            }).boxed());
+        }
+
+        Ok(select_all(streams).boxed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::InProcessCache;
+    use crate::test_support::trade_fixture;
+    use rust_decimal_macros::dec;
+
+    #[tokio::test]
+    async fn subscribers_receive_only_the_kinds_they_asked_for() {
+        let bus = EventBus::new(Arc::new(InProcessCache::new(100)));
+        let mut events = bus.subscribe(&[EventKind::MarketUpdated, EventKind::BreakerTriggered]).await.unwrap();
+
+        let trade = trade_fixture("0xabc", dec!(0.40), dec!(100));
+        bus.publish(Event::OrderUpdated { update: OrderUpdate::Opened, trade: Box::new(trade) }).await;
+        bus.publish(Event::MarketUpdated { market_id: "0xabc".to_string(), status: MarketStatus::Closed }).await;
+        bus.publish(Event::BreakerTriggered { reason: "drawdown".to_string(), triggered_at: Utc::now() }).await;
+
+        let mut received = vec![events.next().await.unwrap(), events.next().await.unwrap()];
+        received.sort_by_key(|event| event.kind() == EventKind::BreakerTriggered);
+        assert!(matches!(
+            &received[0],
+            Event::MarketUpdated { market_id, status: MarketStatus::Closed } if market_id == "0xabc"
+        ));
+        assert!(matches!(&received[1], Event::BreakerTriggered { reason, .. } if reason == "drawdown"));
+    }
+
+    #[tokio::test]
+    async fn order_updates_carry_the_trade_as_it_stands() {
+        let bus = EventBus::new(Arc::new(InProcessCache::new(100)));
+        let mut events = bus.subscribe(&[EventKind::OrderUpdated]).await.unwrap();
+        let trade = trade_fixture("0xabc", dec!(0.40), dec!(100));
+
+        bus.publish(Event::OrderUpdated { update: OrderUpdate::Corrected, trade: Box::new(trade.clone()) }).await;
+
+        let Some(Event::OrderUpdated { update, trade: received }) = events.next().await else {
+            panic!("expected an order update");
+        };
+        assert_eq!(update, OrderUpdate::Corrected);
+        assert_eq!((received.trade_id, received.quantity), (trade.trade_id, trade.quantity));
+        assert!(Event::decode(EventKind::OrderUpdated, r#"{"event": "amended", "trade": {}}"#).is_err());
+    }
+}
@@ -1,51 +1,164 @@
-use anyhow::Result;
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use ethers::abi::parse_abi;
 use ethers::prelude::*;
+use ethers::types::transaction::eip2718::TypedTransaction;
 use rust_decimal::Decimal;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use crate::config::Config;
-use crate::types::Position;
+use crate::types::{EntryOrder, FillSide, GasUrgency, MarketTokens, Position, Price, RestingOrderKind, Shares, TimeInForce, Usdc};
+use super::executor::{Fill, TradeExecutor};
+use super::gas::{Eip1559Fees, FeeEstimator};
+use super::rpc::{ChainRpc, Connect, RpcFailover};
 
-pub struct BlockchainClient {
-    provider: Arc<Provider<Ws>>,
+/// Wei per POL
+const WEI_DECIMALS: u32 = 18;
+
+/// USD cost of `gas` units at `gas_price` wei each, with POL at `pol_usd`
+pub fn gas_cost_usd(gas: U256, gas_price: U256, pol_usd: Decimal) -> Option<Decimal> {
+    let wei = Decimal::from_str(&gas.checked_mul(gas_price)?.to_string()).ok()?;
+    Some(wei / Decimal::from(10u64.pow(WEI_DECIMALS)) * pol_usd)
+}
+
+/// USDC held in an on-chain amount of base units
+pub fn usdc_from_chain(units: U256) -> Result<Usdc> {
+    let units = u128::try_from(units).map_err(|_| anyhow!("{} USDC base units overflow", units))?;
+    Ok(Usdc::from_base_units(units)?)
+}
+
+/// Outcome tokens held in an on-chain amount of base units
+pub fn shares_from_chain(units: U256) -> Result<Shares> {
+    let units = u128::try_from(units).map_err(|_| anyhow!("{} outcome token base units overflow", units))?;
+    Ok(Shares::from_base_units(units)?)
+}
+
+/// Maker and taker amounts of a limit order for `shares` at `price`
+///
+/// A buy gives USDC for shares and a sell gives shares for USDC; each leg is
+/// encoded in its own asset's base units.
+pub fn order_amounts(side: FillSide, shares: Shares, price: Price) -> Result<(U256, U256)> {
+    let usdc = U256::from(shares.cost(price).to_base_units()?);
+    let shares = U256::from(shares.to_base_units()?);
+    Ok(match side {
+        FillSide::Buy => (usdc, shares),
+        FillSide::Sell => (shares, usdc),
+    })
+}
+
+/// The Chainlink feed of POL's USD price
+pub struct GasTokenFeed {
+    address: Address,
+    abi: BaseContract,
+}
+
+impl GasTokenFeed {
+    pub fn new(address: Address) -> Result<Self> {
+        Ok(Self {
+            address,
+            abi: BaseContract::from(parse_abi(&[
+                "function decimals() external view returns (uint8)",
+                "function latestRoundData() external view returns (uint80, int256, uint256, uint256, uint80)",
+            ])?),
+        })
+    }
+
+    /// USD price of POL
+    pub async fn usd<P: Connect>(&self, rpc: &RpcFailover<P>) -> Result<Decimal> {
+        let call = |function: &str| -> Result<TypedTransaction> {
+            let data = self.abi.encode(function, ())?;
+            Ok(TransactionRequest::new().to(self.address).data(data).into())
+        };
+
+        let decimals = call("decimals")?;
+        let decimals = rpc.call(|provider| async move { Ok(provider.call(&decimals, None).await?) }).await?;
+        let decimals: u8 = self.abi.decode_output("decimals", decimals)?;
+        let round = call("latestRoundData")?;
+        let round = rpc.call(|provider| async move { Ok(provider.call(&round, None).await?) }).await?;
+        let (_, answer, _, _, _): (U256, I256, U256, U256, U256) = self.abi.decode_output("latestRoundData", round)?;
+        if answer <= I256::zero() {
+            bail!("POL/USD feed answered {}", answer);
+        }
+
+        let answer = Decimal::from_str(&answer.to_string()).context("POL/USD answer")?;
+        Ok(answer / Decimal::from(10u64.pow(decimals.into())))
+    }
+}
+
+/// Sends orders to Polymarket's CTF Exchange on Polygon
+pub struct LiveExecutor {
+    rpc: Arc<RpcFailover<Ws>>,
     wallet: LocalWallet,
     chain_id: u64,
+    /// Gas units an entry is priced at
+    entry_gas: U256,
+    gas_token: GasTokenFeed,
+    fees: FeeEstimator,
 }
 
-impl BlockchainClient {
-    pub fn new(config: &Config) -> Result<Self> {
-        // Note: This is a simplified implementation
-        // In production, implement proper blockchain integration
-        
+impl LiveExecutor {
+    pub async fn connect(config: &Config, rpc: &ChainRpc) -> Result<Self> {
+        let chain = config.chain()?;
+        let chain_id = chain.chain_id;
         let wallet = config.blockchain.private_key
-            .parse::<LocalWallet>()?
-            .with_chain_id(137u64); // Polygon mainnet
+            .parse::<LocalWallet>()
+            .context("blockchain.private_key")?
+            .with_chain_id(chain_id);
+
+        // Fail at startup rather than on the first order when no endpoint
+        // is reachable on the configured chain
+        rpc.ws.provider().await
+            .with_context(|| format!("connecting to blockchain.network {} (chain {})", chain.network, chain_id))?;
 
-        // For now, create a placeholder
-        // In production, connect to actual WebSocket provider
-        
         Ok(Self {
-            provider: Arc::new(Provider::new(Ws::connect_with_reconnects("wss://polygon-rpc.com", 0).await?)),
+            rpc: rpc.ws.clone(),
             wallet,
-            chain_id: 137,
+            chain_id,
+            entry_gas: U256::from(config.blockchain.entry_gas_units),
+            gas_token: GasTokenFeed::new(chain.gas_token_usd_feed)?,
+            fees: FeeEstimator::from_config(&config.blockchain)?,
         })
     }
 
-    /// Execute a trade on Polymarket
-    pub async fn execute_trade(
+    /// EIP-1559 fees a transaction sent now at `urgency` would bid
+    ///
+    /// Fails while the base fee and tip are over `max_gas_price_gwei`.
+    pub async fn gas_fees(&self, urgency: GasUrgency) -> Result<Eip1559Fees> {
+        self.rpc.call(|provider| async move { self.fees.fees(provider.as_ref(), urgency).await }).await
+    }
+}
+
+#[async_trait]
+impl TradeExecutor for LiveExecutor {
+    async fn execute_trade(
         &self,
-        market_id: &str,
+        _market_id: &str,
+        tokens: &MarketTokens,
         position: Position,
-        amount: Decimal,
-        max_price: Decimal,
-    ) -> Result<String> {
+        quantity: Decimal,
+        max_price: Price,
+        time_in_force: TimeInForce,
+        urgency: GasUrgency,
+    ) -> Result<Fill> {
+        // The CTF Exchange trades ERC-1155 outcome tokens, so orders are
+        // addressed by token id rather than by market
+        let _token_id = tokens.token_id(position);
+        let _amounts = order_amounts(FillSide::Buy, Shares::new(quantity), max_price)?;
+        if !time_in_force.is_immediate() {
+            bail!("takers are FOK or IOC, not {}", time_in_force.as_str());
+        }
+        let _fees = self.gas_fees(urgency).await?;
+
         // Note: This is a placeholder implementation
         // In production, this would:
-        // 1. Build the transaction to interact with Polymarket's CTF Exchange
-        // 2. Sign the transaction with the wallet
-        // 3. Send the transaction to the blockchain
-        // 4. Wait for confirmation
-        // 5. Return the transaction hash
+        // 1. Sign the order with the wallet for the CTF Exchange
+        // 2. Post it to the CLOB with order type FOK or IOC; the operator
+        //    matches it and settles the fill on-chain
+        // 3. Wait for the settlement's confirmation
+        // 4. Return the transaction hash, the average fill price and the
+        //    shares matched, which IOC may leave short of quantity
 
         // Simulate transaction
         let tx_hash = format!(
@@ -53,19 +166,131 @@ impl BlockchainClient {
             rand::random::<u64>()
         );
 
-        Ok(tx_hash)
+        Ok(Fill { tx_hash, price: max_price.value(), quantity })
     }
 
-    /// Get current gas price
-    pub async fn get_gas_price(&self) -> Result<U256> {
-        let gas_price = self.provider.get_gas_price().await?;
-        Ok(gas_price)
+    async fn execute_exit(
+        &self,
+        _market_id: &str,
+        tokens: &MarketTokens,
+        position: Position,
+        quantity: Decimal,
+        min_price: Price,
+    ) -> Result<Fill> {
+        let _token_id = tokens.token_id(position);
+        let _amounts = order_amounts(FillSide::Sell, Shares::new(quantity), min_price)?;
+
+        // Note: This is a placeholder implementation
+        // In production, this would sign a marketable sell limited at
+        // min_price and wait for its settlement on the CTF Exchange
+
+        let tx_hash = format!(
+            "0x{:064x}",
+            rand::random::<u64>()
+        );
+
+        Ok(Fill { tx_hash, price: min_price.value(), quantity })
     }
 
-    /// Check if gas price is acceptable
-    pub async fn is_gas_price_acceptable(&self, max_gas_gwei: u64) -> Result<bool> {
-        let current_gas = self.get_gas_price().await?;
-        let max_gas = U256::from(max_gas_gwei) * U256::exp10(9); // Convert gwei to wei
-        Ok(current_gas <= max_gas)
+    async fn estimate_trade_gas(
+        &self,
+        _market_id: &str,
+        _tokens: &MarketTokens,
+        _position: Position,
+        _quantity: Decimal,
+        _max_price: Price,
+        urgency: GasUrgency,
+    ) -> Result<Decimal> {
+        // The exchange operator settles matched orders, so the wallet has no
+        // transaction to simulate; the configured units are priced instead
+        let gas = self.entry_gas;
+        let gas_price = self.gas_fees(urgency).await?.expected_gas_price();
+        let pol_usd = self.gas_token.usd(&self.rpc).await?;
+
+        gas_cost_usd(gas, gas_price, pol_usd)
+            .with_context(|| format!("gas cost of {} at {} wei overflows", gas, gas_price))
+    }
+
+    async fn place_resting_order(
+        &self,
+        _market_id: &str,
+        tokens: &MarketTokens,
+        position: Position,
+        kind: RestingOrderKind,
+        quantity: Decimal,
+        price: Price,
+    ) -> Result<String> {
+        let _token_id = tokens.token_id(position);
+        // A sell limited below the bid would fill at once rather than wait
+        if kind == RestingOrderKind::Stop {
+            bail!("the CLOB has no stop orders; stops are triggered by the position monitor");
+        }
+        let _amounts = order_amounts(FillSide::Sell, Shares::new(quantity), price)?;
+
+        // Note: This is a placeholder implementation
+        // In production, this would sign the order with the wallet and post
+        // it to the CLOB as good-till-cancelled
+
+        Ok(format!("0x{:064x}", rand::random::<u64>()))
+    }
+
+    async fn place_entry_order(
+        &self,
+        _market_id: &str,
+        tokens: &MarketTokens,
+        position: Position,
+        quantity: Decimal,
+        price: Price,
+        expiration: Option<DateTime<Utc>>,
+    ) -> Result<String> {
+        let _token_id = tokens.token_id(position);
+        let _amounts = order_amounts(FillSide::Buy, Shares::new(quantity), price)?;
+        // Signed into the order in unix seconds; zero never expires
+        let _expiration = expiration.map_or(0, |at| at.timestamp().max(0) as u64);
+
+        // Note: This is a placeholder implementation
+        // In production, this would sign a post-only limit buy with the
+        // wallet and post it to the CLOB as GTD when it has an expiration
+        // and GTC otherwise
+
+        Ok(format!("0x{:064x}", rand::random::<u64>()))
+    }
+
+    /// Fills of live orders are recorded from the chain by the fill watcher
+    async fn match_entry_order(&self, _order: &EntryOrder) -> Result<()> {
+        Ok(())
+    }
+
+    async fn cancel_order(&self, _order_id: &str) -> Result<()> {
+        // Note: This is a placeholder implementation
+        // In production, this would send a signed cancel to the CLOB
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn gas_is_priced_in_usd() {
+        // 250k units at 50 gwei is 0.0125 POL
+        let gwei = U256::exp10(9);
+        assert_eq!(gas_cost_usd(U256::from(250_000u64), gwei * 50, dec!(0.40)), Some(dec!(0.005)));
+        assert_eq!(gas_cost_usd(U256::MAX, U256::from(2u64), dec!(0.40)), None);
+    }
+
+    #[test]
+    fn order_legs_are_encoded_in_their_own_asset() {
+        let price = Price::new(dec!(0.45)).unwrap();
+        let (maker, taker) = order_amounts(FillSide::Buy, Shares::new(dec!(10)), price).unwrap();
+
+        // Buying 10 shares at 0.45 gives 4.5 USDC for 10 shares
+        assert_eq!(maker, U256::from(4_500_000u64));
+        assert_eq!(taker, U256::from(10_000_000u64));
+        assert_eq!(order_amounts(FillSide::Sell, Shares::new(dec!(10)), price).unwrap(), (taker, maker));
+        assert!(order_amounts(FillSide::Buy, Shares::new(dec!(-1)), price).is_err());
     }
 }
@@ -1,44 +1,209 @@
-use anyhow::Result;
-use sqlx::PgPool;
+use anyhow::{anyhow, bail, Context, Result};
+use futures::stream::BoxStream;
+use futures::{FutureExt, StreamExt};
+use std::collections::HashSet;
+use std::sync::Arc;
 use tokio::time::{interval, Duration};
-use tracing::{info, warn, error};
+use tracing::{debug, info, warn, error};
 use uuid::Uuid;
 use rust_decimal::Decimal;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 
-use crate::config::Config;
-use crate::types::{Signal, Trade, TradeStatus, Position};
+use crate::cache::Cache;
+use crate::config::{Config, StrategiesConfig};
+use crate::data::FlowFilter;
+use crate::events::{Event, EventBus, EventKind, OrderUpdate};
+use crate::monitoring::{Heartbeats, Latencies, LatencyStage, Notifier};
+use crate::storage::Storage;
+use crate::numeric;
+use crate::types::{
+    ChildOrder, EntryOrder, ExecutionCost, ExecutionCostKind, ExecutionFailureStatus, FailedExecution, Market, MarketTokens, ParentOrder, RestingOrder, RestingOrderKind, RestingOrderStatus, Signal,
+    SliceMode, SlippageEvent, Trade, TradeOrigin, TimeInForce, TradeSizing, TradeStatus, Position, Price, Probability, Usdc, POLYMARKET_VENUE,
+};
 use crate::risk::RiskManager;
-use super::BlockchainClient;
+use super::executor::{Fill, TradeExecutor};
+use super::exit::ExitValue;
+use super::fees::{FeeModel, FeeSchedule, Liquidity};
+use super::fill_model::{FillModelRefit, SharedFillModel};
+use super::gas::Broadcast;
+use super::fills::{completed_fill, corrected_entry, entry_fill};
+use super::maker::{EntryExecution, EntryQuote, MakerPolicy};
+use super::market_lock::MarketLocks;
+use super::orders::OrderRules;
+use super::rate_limit::OrderRateLimiter;
+use super::resting::ExitBracket;
+use super::slicing::SlicingPolicy;
+use super::stops::{ExitThresholds, ExitTrigger};
+use super::retry::{is_transient, RetryQueue};
+
+/// The parts of a sized, priced entry the maker policy weighs
+struct MakerEntry<'a> {
+    event_time: DateTime<Utc>,
+    tokens: &'a MarketTokens,
+    fees: &'a FeeSchedule,
+    quantity: Decimal,
+    gas_cost: Decimal,
+    taker_edge: Decimal,
+}
 
 pub struct ExecutionEngine {
-    db_pool: PgPool,
-    blockchain_client: BlockchainClient,
+    storage: Arc<dyn Storage>,
+    events: EventBus,
+    executor: Arc<dyn TradeExecutor>,
     risk_manager: RiskManager,
+    fee_model: FeeModel,
+    strategies: StrategiesConfig,
+    retries: RetryQueue,
+    /// Sweep for pending signals and open positions this often
+    poll_interval: Duration,
+    /// Identifies this engine's claims on the signals it is processing
+    consumer_id: String,
+    signal_lease: chrono::Duration,
+    /// Keeps engines from trading one market at once
+    market_locks: MarketLocks,
+    /// Adverse fill slippage past which a fill is recorded and alerted on
+    max_fill_slippage: Decimal,
+    flatten_on_slippage: bool,
+    /// Target left on the book and stop watched after entry, when enabled
+    exit_bracket: Option<ExitBracket>,
+    /// Stop-loss and take-profit levels the position monitor closes at
+    exit_thresholds: ExitThresholds,
+    order_rules: OrderRules,
+    /// Rests entries inside the spread when that beats taking, when enabled
+    maker_policy: Option<MakerPolicy>,
+    /// Splits large taker entries into child orders, when enabled
+    slicing: Option<SlicingPolicy>,
+    /// Fill chances of resting entries, refitted from price history
+    fill_model: SharedFillModel,
+    /// Spawned by `run` while maker entries are enabled
+    fill_model_refit: Option<FillModelRefit>,
+    rate_limiter: OrderRateLimiter,
+    /// Hold instead of exiting when resolution is worth more than the bid
+    ev_gated_exits: bool,
+    /// Defers entries into one-sided order flow, when enabled
+    flow_filter: Option<FlowFilter>,
+    notifier: Notifier,
+    heartbeats: Heartbeats,
+    latencies: Latencies,
 }
 
 impl ExecutionEngine {
     pub async fn new(
-        db_pool: PgPool,
+        storage: Arc<dyn Storage>,
+        cache: Arc<dyn Cache>,
+        events: EventBus,
+        executor: Arc<dyn TradeExecutor>,
         config: &Config,
         risk_manager: RiskManager,
+        notifier: Notifier,
+        heartbeats: Heartbeats,
+        latencies: Latencies,
     ) -> Result<Self> {
-        let blockchain_client = BlockchainClient::new(config)?;
+        let fee_model = FeeModel::from_config(&config.fees)?;
+        let max_fill_slippage = numeric::to_decimal(config.execution.max_fill_slippage)
+            .context("execution.max_fill_slippage")?;
+        let order_rules = OrderRules::from_config(&config.execution.order_rules)?;
+        config.strategies.validate_time_in_force()?;
+        config.strategies.validate_cost_tolerance()?;
+        config.validate_market_lock()?;
+        config.validate_signal_lease()?;
+        let consumer_id = Uuid::new_v4().to_string();
+        let market_locks = MarketLocks::new(
+            cache.clone(),
+            consumer_id.clone(),
+            Duration::from_secs(config.execution.market_lock_secs.max(1)),
+        );
+        let maker_policy = MakerPolicy::from_config(
+            &config.execution.maker,
+            &config.microstructure,
+            order_rules.tick_size(),
+        )?;
+        let fill_model = SharedFillModel::default();
+        let fill_model_refit = maker_policy.as_ref().map(|_| FillModelRefit::new(
+            storage.clone(),
+            config.execution.maker.fill_model.clone(),
+            order_rules.tick_size(),
+            fill_model.clone(),
+        ));
 
         Ok(Self {
-            db_pool,
-            blockchain_client,
+            storage,
+            events,
+            executor,
             risk_manager,
+            fee_model,
+            strategies: config.strategies.clone(),
+            retries: RetryQueue::new(&config.execution),
+            poll_interval: Duration::from_secs(config.execution.poll_interval_secs),
+            consumer_id,
+            signal_lease: chrono::Duration::seconds(config.execution.signal_lease_secs.max(1) as i64),
+            market_locks,
+            max_fill_slippage,
+            flatten_on_slippage: config.execution.flatten_on_slippage,
+            exit_bracket: ExitBracket::from_config(&config.execution)?,
+            exit_thresholds: ExitThresholds::from_config(&config.risk.exits)?,
+            order_rules,
+            maker_policy,
+            slicing: SlicingPolicy::from_config(&config.execution.slicing)?,
+            fill_model,
+            fill_model_refit,
+            rate_limiter: OrderRateLimiter::from_config(cache, &config.execution)?,
+            ev_gated_exits: config.execution.ev_gated_exits,
+            flow_filter: FlowFilter::from_config(&config.microstructure)?,
+            notifier,
+            heartbeats,
+            latencies,
         })
     }
 
     pub async fn run(&self) -> Result<()> {
-        let mut tick = interval(Duration::from_secs(10)); // Check every 10 seconds
+        let mut tick = interval(self.poll_interval);
+        let mut signals = None;
 
         info!("⚡ Execution engine started");
+        if let Some(refit) = self.fill_model_refit.clone() {
+            tokio::spawn(refit.run());
+        }
 
         loop {
-            tick.tick().await;
+            if signals.is_none() {
+                signals = self.subscribe_signals().await;
+            }
+
+            // A published signal or entry correction wakes the engine straight
+            // away; the tick sweeps up anything missed while unsubscribed, due
+            // retries and open positions
+            let published = match signals.as_mut() {
+                Some(stream) => tokio::select! {
+                    _ = tick.tick() => None,
+                    message = stream.next() => Some(message),
+                },
+                None => {
+                    tick.tick().await;
+                    None
+                }
+            };
+            match published {
+                Some(Some(event)) => {
+                    // One pass covers every signal of a generator cycle
+                    let mut events = vec![event];
+                    if let Some(stream) = signals.as_mut() {
+                        while let Some(Some(event)) = stream.next().now_or_never() {
+                            events.push(event);
+                        }
+                    }
+                    self.handle_events(events).await;
+                    continue;
+                }
+                Some(None) => {
+                    warn!("Signal subscription closed, polling every {}s until resubscribed", self.poll_interval.as_secs());
+                    signals = None;
+                    continue;
+                }
+                None => {}
+            }
+
+            self.heartbeats.beat("execution_engine");
 
             // Process pending signals
             if let Err(e) = self.process_pending_signals().await {
@@ -52,66 +217,171 @@ impl ExecutionEngine {
         }
     }
 
-    async fn process_pending_signals(&self) -> Result<()> {
-        // Fetch unexecuted signals
-        let signals = self.fetch_pending_signals().await?;
+    async fn subscribe_signals(&self) -> Option<BoxStream<'static, Event>> {
+        match self.events.subscribe(&[EventKind::SignalGenerated, EventKind::OrderUpdated]).await {
+            Ok(stream) => Some(stream),
+            Err(e) => {
+                warn!("Failed to subscribe to signals, polling every {}s: {}", self.poll_interval.as_secs(), e);
+                None
+            }
+        }
+    }
+
+    /// Re-size the exits of corrected entries, then trade any new signals
+    pub(crate) async fn handle_events(&self, events: Vec<Event>) {
+        let mut signalled = false;
+        for event in events {
+            match event {
+                Event::SignalGenerated(_) => signalled = true,
+                Event::OrderUpdated { update: OrderUpdate::Corrected, trade } => {
+                    // The exits went in for the size the engine booked, not what filled
+                    if let Err(e) = self.sync_resting_exits(&trade).await {
+                        error!("Failed to re-size resting exits of corrected trade {}: {}", trade.trade_id, e);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if signalled {
+            if let Err(e) = self.process_pending_signals().await {
+                error!("Error processing signals: {}", e);
+            }
+        }
+    }
+
+    pub(crate) async fn process_pending_signals(&self) -> Result<()> {
+        if let Err(e) = self.sync_entry_orders().await {
+            error!("Failed to sync entry orders: {}", e);
+        }
+        if let Err(e) = self.work_parent_orders().await {
+            error!("Failed to work parent orders: {}", e);
+        }
+
+        // Claim unexecuted signals, so no other sweep or engine takes them too
+        let signals = self.claim_pending_signals().await?;
 
         for signal in signals {
-            if let Err(e) = self.execute_signal(&signal).await {
-                error!("Failed to execute signal {}: {}", signal.signal_id, e);
+            // Earlier signals in the sweep may have outlasted this one's lease;
+            // renewing it confirms no other engine has retaken the signal
+            let now = Utc::now();
+            if !self.storage.renew_signal_claim(signal.signal_id, &self.consumer_id, now + self.signal_lease).await? {
+                warn!("Lost the claim on signal {} before executing it, leaving it", signal.signal_id);
+                continue;
+            }
+            let max_age = self.strategies.max_execution_age(signal.strategy.as_str());
+            if now - signal.generated_at > max_age {
+                warn!(
+                    "Signal {} from {} expired after {}s, discarding",
+                    signal.signal_id, signal.strategy.as_str(), max_age.num_seconds()
+                );
+                // How far the price went while it waited still counts toward its edge decay
+                if let Some(market) = self.storage.fetch_market(&signal.market_id).await? {
+                    if market.status.allows_entry() {
+                        self.record_execution_quote(&signal, &market).await;
+                    }
+                }
+                self.mark_signal_executed(signal.signal_id, None).await?;
+                continue;
+            }
+            let failure = self.storage.fetch_failed_execution(signal.signal_id).await?;
+            if RetryQueue::is_due(failure.as_ref(), now) {
+                // Another engine is sending orders on the market; the next sweep retakes the signal
+                if self.market_locks.try_lock(&signal.market_id).await {
+                    if let Err(e) = self.execute_signal(&signal).await {
+                        error!("Failed to execute signal {}: {}", signal.signal_id, e);
+                    }
+                    self.market_locks.unlock(&signal.market_id).await;
+                } else {
+                    debug!("Market {} is locked by another engine, deferring signal {}", signal.market_id, signal.signal_id);
+                }
+            }
+
+            // Signals still pending, awaiting a retry or untradeable for now,
+            // are open to the next sweep; otherwise the claim lapses on its own
+            if let Err(e) = self.storage.release_signal(signal.signal_id, &self.consumer_id).await {
+                warn!("Failed to release signal {}: {}", signal.signal_id, e);
             }
         }
 
         Ok(())
     }
 
-    async fn fetch_pending_signals(&self) -> Result<Vec<Signal>> {
-        let rows = sqlx::query!(
-            r#"
-            SELECT 
-                signal_id, market_id, strategy, signal_type,
-                confidence, edge_size, recommended_size,
-                current_price, fair_value, generated_at, metadata
-            FROM signals
-            WHERE executed = FALSE
-                AND generated_at > NOW() - INTERVAL '5 minutes'
-            ORDER BY confidence DESC, edge_size DESC
-            LIMIT 10
-            "#
-        )
-        .fetch_all(&self.db_pool)
-        .await?;
-
-        let signals: Vec<Signal> = rows.into_iter()
-            .filter_map(|row| {
-                Some(Signal {
-                    signal_id: row.signal_id,
-                    market_id: row.market_id,
-                    strategy: match row.strategy.as_str() {
-                        "clv_arb" => crate::types::Strategy::ClvArbitrage,
-                        "poisson_ev" => crate::types::Strategy::PoissonExpectedValue,
-                        _ => return None,
-                    },
-                    signal_type: serde_json::from_str(&row.signal_type).ok()?,
-                    confidence: row.confidence,
-                    edge_size: row.edge_size,
-                    recommended_size: row.recommended_size,
-                    current_price: row.current_price,
-                    fair_value: row.fair_value,
-                    generated_at: row.generated_at,
-                    metadata: row.metadata,
-                })
-            })
-            .collect();
-
-        Ok(signals)
+    async fn claim_pending_signals(&self) -> Result<Vec<Signal>> {
+        // Per-strategy deadlines are applied in process_pending_signals
+        let now = Utc::now();
+        let since = now - self.strategies.longest_execution_age();
+        self.storage.claim_pending_signals(since, 10, &self.consumer_id, now + self.signal_lease).await
     }
 
     async fn execute_signal(&self, signal: &Signal) -> Result<()> {
         info!("⚡ Executing signal {} for market {}", signal.signal_id, signal.market_id);
 
+        // Shadowed strategies keep their signals on record but are not traded
+        let shadowed = self.storage.fetch_shadowed_strategies().await?;
+        if shadowed.iter().any(|s| s.strategy == signal.strategy) {
+            info!("👤 Strategy {} is in shadow mode, not trading signal {}", signal.strategy.as_str(), signal.signal_id);
+            self.mark_signal_executed(signal.signal_id, None).await?;
+            return Ok(());
+        }
+
+        let market = match self.storage.fetch_market(&signal.market_id).await? {
+            Some(market) => market,
+            None => {
+                warn!("Unknown market {}, discarding signal {}", signal.market_id, signal.signal_id);
+                self.mark_signal_executed(signal.signal_id, None).await?;
+                return Ok(());
+            }
+        };
+
+        // Market may have been suspended or gone in-play since the signal was generated
+        let status = market.status;
+        if !status.allows_entry() {
+            if status.is_temporary() {
+                // Leave pending; the signal expires on its own if trading does not resume
+                debug!("Market {} is {}, deferring signal {}", signal.market_id, status.as_str(), signal.signal_id);
+            } else {
+                warn!("Market {} is {}, discarding signal {}", signal.market_id, status.as_str(), signal.signal_id);
+                self.mark_signal_executed(signal.signal_id, None).await?;
+            }
+            return Ok(());
+        }
+        self.record_execution_quote(signal, &market).await;
+
+        // Don't buy into heavy one-sided selling; leave pending until it eases or the signal expires
+        if let Some(filter) = &self.flow_filter {
+            if let Some(features) = self.storage.latest_microstructure(&signal.market_id).await? {
+                if let Some(reason) = filter.adverse(&features, signal.signal_type.to_position(), Utc::now()) {
+                    debug!("Deferring signal {}: {}", signal.signal_id, reason);
+                    return Ok(());
+                }
+            }
+        }
+
+        // A late signal trades on the share of its edge the strategy's
+        // executions say is left at its age
+        let signal = &self.decayed(signal).await?;
+
+        // Validate and size on the edge left after fees; each strategy keeps
+        // at least its own share of the edge clear of costs
+        let tolerance = self.strategies.cost_tolerance(signal.strategy.as_str());
+        let max_cost_share = numeric::to_decimal(tolerance.max_cost_share)
+            .context("strategies.cost_tolerance.max_cost_share")?;
+        let min_edge_left = signal.edge_size * (Decimal::ONE - max_cost_share);
+        let fees = self.fee_model.schedule(POLYMARKET_VENUE, &market.market_type);
+        let net_edge = fees.net_edge(signal.edge_size, signal.current_price.value(), Liquidity::Taker);
+        if net_edge <= min_edge_left {
+            warn!(
+                "Signal {} edge {} consumed by fees past the {} {} tolerates",
+                signal.signal_id, signal.edge_size, max_cost_share, signal.strategy.as_str()
+            );
+            self.mark_signal_executed(signal.signal_id, None).await?;
+            return Ok(());
+        }
+        let signal = &Signal { edge_size: net_edge, ..signal.clone() };
+
         // Validate signal through risk management
-        if !self.risk_manager.validate_signal(signal).await? {
+        if !self.risk_manager.validate_signal(signal, POLYMARKET_VENUE).await? {
             warn!("Signal {} failed risk validation", signal.signal_id);
             self.mark_signal_executed(signal.signal_id, None).await?;
             return Ok(());
@@ -126,226 +396,1225 @@ impl ExecutionEngine {
             return Ok(());
         }
 
-        // Execute trade on blockchain
+        let tokens = match market.tokens {
+            Some(tokens) => tokens,
+            None => {
+                warn!("No outcome token ids for market {}, discarding signal {}", signal.market_id, signal.signal_id);
+                self.mark_signal_executed(signal.signal_id, None).await?;
+                return Ok(());
+            }
+        };
+
+        // Shares and limit price on the venue's tick and lot grid
+        let order = match self.order_rules.buy(position_size, signal.current_price) {
+            Ok(order) => order,
+            Err(e) => {
+                warn!("Signal {} not sent: {}", signal.signal_id, e);
+                self.mark_signal_executed(signal.signal_id, None).await?;
+                return Ok(());
+            }
+        };
+
+        // Gas is flat per transaction, so it comes off the edge per share of
+        // this order's size; a spike leaves the signal pending until it passes
         let position = signal.signal_type.to_position();
-        match self.blockchain_client.execute_trade(
+        let gas_cost = match self.executor
+            .estimate_trade_gas(&signal.market_id, &tokens, position, order.quantity, order.price, tolerance.urgency)
+            .await
+        {
+            Ok(gas_cost) => gas_cost,
+            Err(e) => {
+                warn!("Signal {} gas not estimated, deferring: {}", signal.signal_id, e);
+                return Ok(());
+            }
+        };
+        let max_gas = numeric::to_decimal(tolerance.max_gas_usd).context("strategies.cost_tolerance.max_gas_usd")?;
+        if gas_cost > max_gas {
+            warn!(
+                "Signal {} gas ${} is over the ${} {} tolerates, deferring",
+                signal.signal_id, gas_cost.round_dp(4), max_gas, signal.strategy.as_str()
+            );
+            return Ok(());
+        }
+        let edge_after_gas = net_edge - gas_cost / order.quantity;
+        if edge_after_gas <= min_edge_left {
+            warn!(
+                "Signal {} edge {} per share consumed by ${} gas on {} shares, deferring",
+                signal.signal_id, net_edge, gas_cost.round_dp(4), order.quantity
+            );
+            return Ok(());
+        }
+
+        // Over the venue's order budget: leave pending for the next pass
+        if !self.rate_limiter.try_acquire(POLYMARKET_VENUE).await {
+            debug!("Order rate limit reached, deferring signal {}", signal.signal_id);
+            return Ok(());
+        }
+
+        // Rest inside the spread instead when the wait is worth more than crossing now
+        if let Some(policy) = &self.maker_policy {
+            let entry = MakerEntry {
+                event_time: market.event_time,
+                tokens: &tokens,
+                fees: &fees,
+                quantity: order.quantity,
+                gas_cost,
+                taker_edge: edge_after_gas,
+            };
+            if self.post_entry(policy, signal, position_size, entry).await? {
+                return Ok(());
+            }
+        }
+
+        // A large stake goes out as child orders rather than walking a thin book at once
+        if let Some(slicing) = &self.slicing {
+            if slicing.applies(position_size) {
+                let mut parent = slicing.parent(signal, position_size, Utc::now());
+                self.storage.insert_parent_order(&parent).await?;
+
+                // The parent now carries the signal; its children each open a trade
+                self.close_failure(signal.signal_id, ExecutionFailureStatus::Recovered).await?;
+                self.mark_signal_executed(signal.signal_id, None).await?;
+                info!(
+                    "🧩 Signal {} sliced into {} parent order {} for ${} at no more than {}",
+                    signal.signal_id, parent.mode.as_str(), parent.parent_id, position_size, parent.limit_price
+                );
+
+                return self.work_parent_order(slicing, &mut parent).await;
+            }
+        }
+
+        // Execute trade on blockchain
+        let time_in_force = self.strategies.time_in_force(signal.strategy.as_str()).taker;
+        match self.executor.execute_trade(
             &signal.market_id,
+            &tokens,
             position,
-            position_size,
-            signal.current_price,
+            order.quantity,
+            order.price,
+            time_in_force,
+            tolerance.urgency,
         ).await {
-            Ok(tx_hash) => {
-                info!("✅ Trade executed: {}", tx_hash);
+            Ok(fill) => {
+                info!("✅ Trade executed: {}", fill.tx_hash);
+                self.latencies.record(LatencyStage::Execution, Utc::now() - signal.generated_at);
+                if fill.quantity < order.quantity {
+                    info!(
+                        "Signal {} {} filled {} of {} shares, rest cancelled",
+                        signal.signal_id, time_in_force.as_str(), fill.quantity, order.quantity
+                    );
+                }
 
                 // Record trade in database
-                let trade_id = self.record_trade(signal, position_size, tx_hash).await?;
+                let quantity = fill.quantity;
+                let entry_fees = fees.fee(quantity * fill.price, Liquidity::Taker);
+                let trade = self.record_trade(signal, quantity, entry_fees, gas_cost, fill).await?;
 
                 // Mark signal as executed
-                self.mark_signal_executed(signal.signal_id, Some(trade_id)).await?;
+                self.mark_signal_executed(signal.signal_id, Some(trade.trade_id)).await?;
+
+                info!("💼 Trade {} recorded for signal {}", trade.trade_id, signal.signal_id);
+                self.notifier.info(format!(
+                    "💼 {} bought {} {} shares of {} at {} (${})",
+                    trade.strategy.as_str(),
+                    trade.quantity,
+                    trade.position.as_str(),
+                    trade.market_id,
+                    trade.entry_price,
+                    trade.position_size_usd().round_dp(2)
+                ));
 
-                info!("💼 Trade {} recorded for signal {}", trade_id, signal.signal_id);
+                // Protect the position before anything else can go wrong
+                if let Err(e) = self.place_resting_exits(&trade, &tokens).await {
+                    error!("Failed to place resting exits for trade {}: {}", trade.trade_id, e);
+                }
+
+                // The market moved between signal and fill
+                if trade.slippage.map_or(false, |slippage| slippage > self.max_fill_slippage) {
+                    self.handle_slippage(signal, &trade).await?;
+                }
             }
             Err(e) => {
-                error!("❌ Trade execution failed: {}", e);
-                // Mark signal as executed to avoid retry (with failure noted)
-                self.mark_signal_executed(signal.signal_id, None).await?;
+                self.record_failed_entry_gas(&e, gas_cost).await?;
+                self.record_submission_failure(signal, &e).await?;
             }
         }
 
         Ok(())
     }
 
-    async fn record_trade(
+    /// Book the gas of an entry that failed once its transaction had gone
+    /// out; a version of it may have been mined and paid for all the same
+    async fn record_failed_entry_gas(&self, error: &anyhow::Error, gas_cost: Decimal) -> Result<()> {
+        let Some(broadcast) = error.downcast_ref::<Broadcast>() else {
+            return Ok(());
+        };
+
+        self.storage.insert_execution_cost(&ExecutionCost {
+            cost_id: Uuid::new_v4(),
+            kind: ExecutionCostKind::FailedEntry,
+            amount: gas_cost,
+            trade_id: None,
+            tx_hash: broadcast.sent.last().map(|hash| format!("{:?}", hash)),
+            incurred_at: Utc::now(),
+        }).await
+    }
+
+    /// Record a failed submission; the signal stays pending until its
+    /// backoff elapses while the failure is transient and attempts remain,
+    /// and is closed out as failed otherwise
+    async fn record_submission_failure(&self, signal: &Signal, error: &anyhow::Error) -> Result<()> {
+        let now = Utc::now();
+        let previous = self.storage.fetch_failed_execution(signal.signal_id).await?;
+        let attempts = previous.as_ref().map_or(0, |f| f.attempts) + 1;
+        let next_attempt_at = self.retries.next_attempt(attempts, is_transient(error), now);
+        let failure = FailedExecution {
+            signal_id: signal.signal_id,
+            market_id: signal.market_id.clone(),
+            strategy: signal.strategy,
+            attempts,
+            last_error: format!("{:#}", error),
+            status: if next_attempt_at.is_some() {
+                ExecutionFailureStatus::Retrying
+            } else {
+                ExecutionFailureStatus::Failed
+            },
+            next_attempt_at,
+            first_failed_at: previous.map_or(now, |f| f.first_failed_at),
+            updated_at: now,
+        };
+        self.storage.upsert_failed_execution(&failure).await?;
+
+        match next_attempt_at {
+            Some(retry_at) => {
+                warn!("Submission {} of signal {} failed, retrying at {}: {}", attempts, signal.signal_id, retry_at, error);
+            }
+            None => {
+                error!("❌ Signal {} failed after {} attempts: {}", signal.signal_id, attempts, error);
+                self.notifier.info(format!(
+                    "❌ {} signal on {} failed after {} attempts: {}",
+                    signal.strategy.as_str(), signal.market_id, attempts, error
+                ));
+                self.mark_signal_executed(signal.signal_id, None).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Settle a retrying signal's failure record once it leaves the queue;
+    /// signals that never failed have none
+    async fn close_failure(&self, signal_id: Uuid, status: ExecutionFailureStatus) -> Result<()> {
+        let Some(mut failure) = self.storage.fetch_failed_execution(signal_id).await? else {
+            return Ok(());
+        };
+        if failure.status != ExecutionFailureStatus::Retrying {
+            return Ok(());
+        }
+
+        failure.status = status;
+        failure.next_attempt_at = None;
+        failure.updated_at = Utc::now();
+        self.storage.upsert_failed_execution(&failure).await
+    }
+
+    /// Post the entry as a resting bid if the maker policy prefers it;
+    /// returns whether it was posted
+    async fn post_entry(
         &self,
+        policy: &MakerPolicy,
         signal: &Signal,
-        quantity: Decimal,
-        tx_hash: String,
-    ) -> Result<Uuid> {
-        let trade_id = Uuid::new_v4();
+        position_size: Decimal,
+        entry: MakerEntry<'_>,
+    ) -> Result<bool> {
         let position = signal.signal_type.to_position();
+        let (yes_quote, no_quote) = self.storage.fetch_market_quotes(&signal.market_id).await?;
+        let bid = match position {
+            Position::Yes => yes_quote.bid,
+            Position::No => no_quote.bid,
+        };
+        let features = self.storage.latest_microstructure(&signal.market_id).await?;
+
+        let now = Utc::now();
+        let quote = EntryQuote {
+            position,
+            fair_value: signal.fair_value.value(),
+            bid,
+            ask: signal.current_price,
+            taker_edge: entry.taker_edge,
+            quantity: entry.quantity,
+            maker_fee_rate: entry.fees.rate(Liquidity::Maker),
+            gas_per_share: entry.gas_cost / entry.quantity,
+            features: features.as_ref(),
+            event_time: entry.event_time,
+            signal_deadline: signal.generated_at + self.strategies.max_execution_age(signal.strategy.as_str()),
+        };
+        let fill_model = self.fill_model.read().unwrap().clone();
+        let EntryExecution::Post { price, fill_probability, maker_edge, expires_at } =
+            policy.decide(&quote, fill_model.as_deref(), now)
+        else {
+            return Ok(false);
+        };
+
+        // Same stake at the lower price buys more shares
+        let order = match self.order_rules.buy(position_size, price) {
+            Ok(order) => order,
+            Err(e) => {
+                debug!("Taking signal {}, no resting entry: {}", signal.signal_id, e);
+                return Ok(false);
+            }
+        };
+
+        // Good-til-date leaves the venue to pull the order if we never get to
+        let expiration = match self.strategies.time_in_force(signal.strategy.as_str()).maker {
+            TimeInForce::Gtd => Some(expires_at),
+            _ => None,
+        };
+        let order_id = self.executor
+            .place_entry_order(&signal.market_id, entry.tokens, position, order.quantity, order.price, expiration)
+            .await?;
+        self.storage.insert_entry_order(&EntryOrder {
+            order_id,
+            signal_id: signal.signal_id,
+            market_id: signal.market_id.clone(),
+            position,
+            price: order.price.value(),
+            quantity: order.quantity,
+            gas_cost: entry.gas_cost,
+            placed_at: now,
+            expires_at,
+        }).await?;
+
+        // The order now carries the signal; its trade is linked once it fills
+        self.close_failure(signal.signal_id, ExecutionFailureStatus::Recovered).await?;
+        self.mark_signal_executed(signal.signal_id, None).await?;
 
-        sqlx::query!(
-            r#"
-            INSERT INTO trades (
-                trade_id, market_id, strategy, position, quantity,
-                entry_price, entry_time, tx_hash_entry, status
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
-            "#,
-            trade_id,
-            signal.market_id,
-            signal.strategy.as_str(),
+        info!(
+            "📥 Resting entry for signal {}: {} {} shares at {} (ask {}, fill chance {}, edge {} vs {} taking) until {}",
+            signal.signal_id,
+            order.quantity,
             position.as_str(),
-            quantity,
+            order.price,
             signal.current_price,
-            Utc::now(),
-            tx_hash,
-            "open",
-        )
-        .execute(&self.db_pool)
-        .await?;
+            fill_probability,
+            maker_edge.round_dp(4),
+            entry.taker_edge.round_dp(4),
+            expires_at
+        );
+        Ok(true)
+    }
 
-        Ok(trade_id)
+    /// Record what the signal's position can be bought at now, traded or
+    /// not, for the edge decay fit; a failure only costs the fit a sample
+    async fn record_execution_quote(&self, signal: &Signal, market: &Market) {
+        let price = market.entry_price(signal.signal_type.to_position());
+        if let Err(e) = self.storage.record_execution_quote(signal.signal_id, price, Utc::now()).await {
+            warn!("Failed to record the quote for signal {}: {}", signal.signal_id, e);
+        }
     }
 
-    async fn mark_signal_executed(&self, signal_id: Uuid, trade_id: Option<Uuid>) -> Result<()> {
-        sqlx::query!(
-            r#"
-            UPDATE signals
-            SET executed = TRUE, executed_trade_id = $2
-            WHERE signal_id = $1
-            "#,
-            signal_id,
-            trade_id,
-        )
-        .execute(&self.db_pool)
-        .await?;
+    /// `signal` with its edge and fair value scaled down to the share its
+    /// strategy's fitted edge decay leaves at the signal's age
+    async fn decayed(&self, signal: &Signal) -> Result<Signal> {
+        let decays = self.storage.fetch_edge_decays().await?;
+        let Some(decay) = decays.iter().find(|d| d.strategy == signal.strategy) else {
+            return Ok(signal.clone());
+        };
+
+        let remaining = numeric::to_decimal(decay.remaining(Utc::now() - signal.generated_at))?.round_dp(4);
+        let price = signal.current_price.value();
+        let fair_value = Probability::new(price + (signal.fair_value.value() - price) * remaining)?;
+        debug!(
+            "Signal {} keeps {} of its edge at {}s old ({}s half-life)",
+            signal.signal_id, remaining, (Utc::now() - signal.generated_at).num_seconds(), decay.half_life_secs
+        );
+
+        Ok(Signal { edge_size: signal.edge_size * remaining, fair_value, ..signal.clone() })
+    }
+
+    /// Book resting entries that filled and pull those past their expiry
+    async fn sync_entry_orders(&self) -> Result<()> {
+        let now = Utc::now();
+        for order in self.storage.fetch_open_entry_orders().await? {
+            if let Err(e) = self.executor.match_entry_order(&order).await {
+                warn!("Failed to match resting entry {}: {}", order.order_id, e);
+            }
+            let fills = self.storage.fetch_order_fills(&order.order_id).await?;
+            if let Some(fill) = completed_fill(&fills, order.quantity) {
+                self.storage.set_entry_order_status(&order.order_id, RestingOrderStatus::Filled).await?;
+                info!("📥 Resting entry {} filled at {}", order.order_id, fill.price);
+                self.open_from_entry(&order, order.quantity, fill).await?;
+                continue;
+            }
+            if now < order.expires_at {
+                continue;
+            }
+
+            // Left open, the cancel is tried again on the next sync
+            if let Err(e) = self.executor.cancel_order(&order.order_id).await {
+                error!("Failed to cancel expired resting entry {}: {}", order.order_id, e);
+                continue;
+            }
+            self.storage.set_entry_order_status(&order.order_id, RestingOrderStatus::Cancelled).await?;
+
+            // Whatever filled before the cancel is a position all the same
+            match completed_fill(&fills, Decimal::ZERO) {
+                Some(fill) => {
+                    info!("📥 Resting entry {} expired {} of {} shares filled", order.order_id, fill.quantity, order.quantity);
+                    self.open_from_entry(&order, fill.quantity, fill).await?;
+                }
+                // Strategies signal again if the edge is still there
+                None => info!("📤 Resting entry {} for signal {} expired unfilled", order.order_id, order.signal_id),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Send the due children of open parent orders
+    async fn work_parent_orders(&self) -> Result<()> {
+        let Some(slicing) = &self.slicing else {
+            return Ok(());
+        };
+        let markets: HashSet<String> = self.storage.fetch_open_parent_orders().await?
+            .into_iter()
+            .map(|parent| parent.market_id)
+            .collect();
+        for market_id in markets {
+            if !self.market_locks.try_lock(&market_id).await {
+                continue;
+            }
+            // Read again under the lock, since another engine may have sent a child since
+            match self.storage.fetch_open_parent_orders().await {
+                Ok(parents) => {
+                    for mut parent in parents.into_iter().filter(|parent| parent.market_id == market_id) {
+                        if let Err(e) = self.work_parent_order(slicing, &mut parent).await {
+                            error!("Failed to work parent order {}: {}", parent.parent_id, e);
+                        }
+                    }
+                }
+                Err(e) => error!("Failed to fetch parent orders of market {}: {}", market_id, e),
+            }
+            self.market_locks.unlock(&market_id).await;
+        }
+
+        Ok(())
+    }
+
+    /// Send the parent's next child if it is due and the ask is within its
+    /// limit, recording the child's fill as a trade of the parent's signal
+    async fn work_parent_order(&self, slicing: &SlicingPolicy, parent: &mut ParentOrder) -> Result<()> {
+        let now = Utc::now();
+        if now >= parent.expires_at {
+            return self.finish_parent_order(parent, RestingOrderStatus::Cancelled).await;
+        }
+        if now < parent.next_slice_at {
+            return Ok(());
+        }
+
+        let market = self.storage.fetch_market(&parent.market_id).await?
+            .ok_or_else(|| anyhow!("Unknown market {}", parent.market_id))?;
+        if !market.status.allows_entry() {
+            if market.status.is_temporary() {
+                return Ok(());
+            }
+            return self.finish_parent_order(parent, RestingOrderStatus::Cancelled).await;
+        }
+        let Some(tokens) = market.tokens.as_ref() else {
+            return self.finish_parent_order(parent, RestingOrderStatus::Cancelled).await;
+        };
+
+        // The book may not have recovered from the last child yet
+        let (yes_quote, no_quote) = self.storage.fetch_market_quotes(&parent.market_id).await?;
+        let ask = match parent.position {
+            Position::Yes => yes_quote.ask,
+            Position::No => no_quote.ask,
+        };
+        if ask.value() > parent.limit_price {
+            debug!("Parent order {} waiting, ask {} is above its limit {}", parent.parent_id, ask, parent.limit_price);
+            parent.next_slice_at = slicing.next_slice_at(now);
+            return self.storage.update_parent_order(parent).await;
+        }
+
+        let best_offer = match parent.mode {
+            SliceMode::Iceberg => self.storage.fetch_book_snapshot(&parent.market_id).await?
+                .map(|book| book.fillable_notional(parent.position, Decimal::ZERO)),
+            SliceMode::Twap => None,
+        };
+        let remaining = parent.target_notional - parent.filled_notional;
+        let notional = slicing.child_notional(parent, best_offer);
+        let order = match self.order_rules.buy(notional, ask) {
+            Ok(order) => order,
+            // Too little on offer for now; wait for the book to refill
+            Err(e) if notional < remaining => {
+                debug!("Parent order {} waiting: {}", parent.parent_id, e);
+                parent.next_slice_at = slicing.next_slice_at(now);
+                return self.storage.update_parent_order(parent).await;
+            }
+            // What is left is under the venue's minimum order
+            Err(_) => return self.finish_parent_order(parent, RestingOrderStatus::Filled).await,
+        };
+
+        // A breaker, drawdown or capital shortfall since the last child holds
+        // the rest back; the parent expires if it does not clear in time
+        let notional = order.quantity * order.price.value();
+        if !self.risk_manager.validate_child_order(POLYMARKET_VENUE, notional, parent.filled_notional, remaining).await? {
+            warn!("Parent order {} child held by risk checks", parent.parent_id);
+            parent.next_slice_at = slicing.next_slice_at(now);
+            return self.storage.update_parent_order(parent).await;
+        }
+
+        // Over the venue's order budget: try again next pass
+        if !self.rate_limiter.try_acquire(POLYMARKET_VENUE).await {
+            return Ok(());
+        }
+
+        let signal = self.storage.fetch_signal(parent.signal_id).await?
+            .ok_or_else(|| anyhow!("Unknown signal {} of parent order {}", parent.signal_id, parent.parent_id))?;
+        let tolerance = self.strategies.cost_tolerance(signal.strategy.as_str());
+        // Gas not priced: try again next pass
+        let gas_cost = match self.executor
+            .estimate_trade_gas(&parent.market_id, tokens, parent.position, order.quantity, order.price, tolerance.urgency)
+            .await
+        {
+            Ok(gas_cost) => gas_cost,
+            Err(e) => {
+                warn!("Parent order {} gas not estimated: {}", parent.parent_id, e);
+                return Ok(());
+            }
+        };
+        let time_in_force = self.strategies.time_in_force(signal.strategy.as_str()).taker;
+
+        let slice = parent.children_sent + 1;
+        parent.children_sent = slice;
+        parent.next_slice_at = slicing.next_slice_at(now);
+        match self.executor.execute_trade(
+            &parent.market_id,
+            tokens,
+            parent.position,
+            order.quantity,
+            order.price,
+            time_in_force,
+            tolerance.urgency,
+        ).await {
+            Ok(fill) => {
+                let (quantity, price, tx_hash) = (fill.quantity, fill.price, fill.tx_hash.clone());
+                let entry_fees = self.fee_model
+                    .schedule(POLYMARKET_VENUE, &market.market_type)
+                    .fee(quantity * price, Liquidity::Taker);
+                let trade = self.record_trade(&signal, quantity, entry_fees, gas_cost, fill).await?;
+                self.storage.insert_child_order(&ChildOrder {
+                    parent_id: parent.parent_id,
+                    slice,
+                    trade_id: trade.trade_id,
+                    quantity,
+                    price,
+                    tx_hash,
+                    sent_at: now,
+                }).await?;
+                parent.filled_quantity += quantity;
+                parent.filled_notional += quantity * price;
+
+                info!(
+                    "🧩 Parent order {} child {} bought {} {} shares at {} (${} of ${})",
+                    parent.parent_id,
+                    slice,
+                    quantity,
+                    parent.position.as_str(),
+                    price,
+                    parent.filled_notional.round_dp(2),
+                    parent.target_notional.round_dp(2)
+                );
+
+                if let Err(e) = self.place_resting_exits(&trade, tokens).await {
+                    error!("Failed to place resting exits for trade {}: {}", trade.trade_id, e);
+                }
+            }
+            // The next child goes as planned; a TWAP's last one picks up the shortfall
+            Err(e) => {
+                warn!("Parent order {} child {} failed: {:#}", parent.parent_id, slice, e);
+                self.record_failed_entry_gas(&e, gas_cost).await?;
+            }
+        }
+
+        if slicing.done(parent) {
+            let status = if parent.filled_notional >= parent.target_notional {
+                RestingOrderStatus::Filled
+            } else {
+                RestingOrderStatus::Cancelled
+            };
+            return self.finish_parent_order(parent, status).await;
+        }
+        self.storage.update_parent_order(parent).await
+    }
+
+    /// Close a parent order; whatever its children bought stays open as trades
+    async fn finish_parent_order(&self, parent: &mut ParentOrder, status: RestingOrderStatus) -> Result<()> {
+        parent.status = status;
+        self.storage.update_parent_order(parent).await?;
+
+        info!(
+            "🧩 Parent order {} {}: ${} of ${} bought in {} children",
+            parent.parent_id,
+            status.as_str(),
+            parent.filled_notional.round_dp(2),
+            parent.target_notional.round_dp(2),
+            parent.children_sent
+        );
+        Ok(())
+    }
+
+    /// Record the trade a resting entry filled into and protect it
+    async fn open_from_entry(&self, order: &EntryOrder, quantity: Decimal, fill: Fill) -> Result<()> {
+        let signal = self.storage.fetch_signal(order.signal_id).await?
+            .ok_or_else(|| anyhow!("Unknown signal {} of entry order {}", order.signal_id, order.order_id))?;
+        let market = self.storage.fetch_market(&order.market_id).await?
+            .ok_or_else(|| anyhow!("Unknown market {}", order.market_id))?;
+
+        let entry_fees = self.fee_model
+            .schedule(POLYMARKET_VENUE, &market.market_type)
+            .fee(quantity * fill.price, Liquidity::Maker);
+        let trade = self.record_trade(&signal, quantity, entry_fees, order.gas_cost, fill).await?;
+        self.mark_signal_executed(signal.signal_id, Some(trade.trade_id)).await?;
+
+        info!("💼 Trade {} recorded for signal {} from a resting entry", trade.trade_id, signal.signal_id);
+        self.notifier.info(format!(
+            "💼 {} bought {} {} shares of {} at {} resting (${})",
+            trade.strategy.as_str(),
+            trade.quantity,
+            trade.position.as_str(),
+            trade.market_id,
+            trade.entry_price,
+            trade.position_size_usd().round_dp(2)
+        ));
+
+        match &market.tokens {
+            Some(tokens) => {
+                if let Err(e) = self.place_resting_exits(&trade, tokens).await {
+                    error!("Failed to place resting exits for trade {}: {}", trade.trade_id, e);
+                }
+            }
+            None => warn!("No outcome token ids for market {}, trade {} has no resting exits", trade.market_id, trade.trade_id),
+        }
 
         Ok(())
     }
 
-    async fn monitor_positions(&self) -> Result<()> {
+    async fn record_trade(
+        &self,
+        signal: &Signal,
+        quantity: Decimal,
+        fees: Decimal,
+        gas_cost: Decimal,
+        fill: Fill,
+    ) -> Result<Trade> {
+        // The fill listener may have seen the transaction first
+        let onchain = self.storage.fetch_tx_fills(&fill.tx_hash).await?;
+        let mut trade = Trade {
+            trade_id: Uuid::new_v4(),
+            market_id: signal.market_id.clone(),
+            strategy: signal.strategy,
+            position: signal.signal_type.to_position(),
+            quantity,
+            entry_price: fill.price,
+            exit_price: None,
+            entry_time: Utc::now(),
+            exit_time: None,
+            gas_cost: Some(gas_cost),
+            fees: Some(Usdc::new(fees)),
+            slippage: Some(fill.price - signal.current_price.value()),
+            pnl: None,
+            pnl_percent: None,
+            max_adverse_excursion: None,
+            max_favorable_excursion: None,
+            status: TradeStatus::Open,
+            tx_hash_entry: Some(fill.tx_hash),
+            tx_hash_exit: None,
+            venue: POLYMARKET_VENUE.to_string(),
+            origin: TradeOrigin::Bot,
+        };
+        if let Some(corrected) = entry_fill(&onchain).and_then(|actual| corrected_entry(&trade, &actual)) {
+            info!(
+                "Trade {} filled {} shares at {} on chain, executor reported {} at {}",
+                trade.trade_id, corrected.quantity, corrected.entry_price.round_dp(4), trade.quantity, trade.entry_price
+            );
+            trade = corrected;
+        }
+
+        self.storage.insert_trade(&trade).await?;
+        self.storage.insert_trade_sizing(&TradeSizing {
+            trade_id: trade.trade_id,
+            bankroll: self.risk_manager.sizing_bankroll().await,
+            stake: trade.position_size_usd(),
+            sized_at: trade.entry_time,
+        }).await?;
+        self.publish_order(OrderUpdate::Opened, &trade).await;
+
+        Ok(trade)
+    }
+
+    /// Announce a trade opening or closing to event subscribers; best effort
+    async fn publish_order(&self, update: OrderUpdate, trade: &Trade) {
+        self.events.publish(Event::OrderUpdated { update, trade: Box::new(trade.clone()) }).await;
+    }
+
+    /// Record and alert on a fill past tolerance, closing it if configured
+    async fn handle_slippage(&self, signal: &Signal, trade: &Trade) -> Result<()> {
+        let slippage = trade.slippage.unwrap_or_default();
+        let message = format!(
+            "🚨 Trade {} filled at {} against signal price {} (slippage {})",
+            trade.trade_id, trade.entry_price, signal.current_price.value(), slippage
+        );
+        error!("{}", message);
+        self.notifier.critical(message);
+
+        let flattened = if self.flatten_on_slippage {
+            match self.close_position(trade).await {
+                Ok(()) => true,
+                Err(e) => {
+                    error!("Failed to flatten slipped position {}: {}", trade.trade_id, e);
+                    false
+                }
+            }
+        } else {
+            false
+        };
+
+        self.storage.insert_slippage_event(&SlippageEvent {
+            event_id: Uuid::new_v4(),
+            trade_id: trade.trade_id,
+            signal_id: signal.signal_id,
+            market_id: trade.market_id.clone(),
+            expected_price: signal.current_price.value(),
+            fill_price: trade.entry_price,
+            slippage,
+            flattened,
+            occurred_at: Utc::now(),
+        }).await
+    }
+
+    /// Take a signal out of the pending set; a signal that was being retried
+    /// counts as recovered if it traded and failed if it was dropped
+    async fn mark_signal_executed(&self, signal_id: Uuid, trade_id: Option<Uuid>) -> Result<()> {
+        let status = match trade_id {
+            Some(_) => ExecutionFailureStatus::Recovered,
+            None => ExecutionFailureStatus::Failed,
+        };
+        self.close_failure(signal_id, status).await?;
+        self.storage.mark_signal_executed(signal_id, trade_id).await
+    }
+
+    pub(crate) async fn monitor_positions(&self) -> Result<()> {
         // Fetch open positions
         let positions = self.fetch_open_positions().await?;
 
-        for trade in positions {
+        // Manual positions are managed by whoever opened them
+        for trade in positions.into_iter().filter(|t| t.origin == TradeOrigin::Bot) {
+            match self.sync_resting_exits(&trade).await {
+                // Exited on the book
+                Ok(true) => continue,
+                Ok(false) => {}
+                Err(e) => error!("Failed to sync resting exits for trade {}: {}", trade.trade_id, e),
+            }
+
             // Check for exit conditions
-            if self.should_exit_position(&trade).await? {
-                if let Err(e) = self.close_position(&trade).await {
-                    error!("Failed to close position {}: {}", trade.trade_id, e);
+            let trigger = match self.should_exit_position(&trade).await {
+                Ok(trigger) => trigger,
+                Err(e) => {
+                    error!("Failed to check exit levels of trade {}: {}", trade.trade_id, e);
+                    continue;
+                }
+            };
+            if let Some(trigger) = trigger {
+                // Suspended or disputed markets cannot be traded out of; retry next cycle
+                let status = self.storage.fetch_market_status(&trade.market_id).await?;
+                if !status.map_or(false, |s| s.allows_exit()) {
+                    debug!("Market {} not accepting exits, holding {}", trade.market_id, trade.trade_id);
+                    continue;
                 }
+
+                // A stop limits the loss whatever the model thinks; profit is
+                // only taken when the bid beats holding
+                if trigger == ExitTrigger::TakeProfit && self.ev_gated_exits && !self.exit_favored(&trade).await? {
+                    continue;
+                }
+
+                // Left for the next cycle while another engine trades the market
+                if !self.market_locks.try_lock(&trade.market_id).await {
+                    debug!("Market {} is locked by another engine, holding {}", trade.market_id, trade.trade_id);
+                    continue;
+                }
+                // The engine that last held the lock may have closed it since this pass began
+                let open = matches!(self.storage.fetch_trade(trade.trade_id).await, Ok(Some(t)) if t.status == TradeStatus::Open);
+                if open {
+                    info!("🛑 {} hit for trade {}", trigger.as_str(), trade.trade_id);
+                    if let Err(e) = self.close_position(&trade).await {
+                        error!("Failed to close position {}: {}", trade.trade_id, e);
+                    }
+                }
+                self.market_locks.unlock(&trade.market_id).await;
             }
         }
 
         Ok(())
     }
 
-    async fn fetch_open_positions(&self) -> Result<Vec<Trade>> {
-        let rows = sqlx::query!(
-            r#"
-            SELECT 
-                trade_id, market_id, strategy, position, quantity,
-                entry_price, entry_time, tx_hash_entry, gas_cost
-            FROM trades
-            WHERE status = 'open'
-            "#
-        )
-        .fetch_all(&self.db_pool)
-        .await?;
-
-        let trades: Vec<Trade> = rows.into_iter()
-            .filter_map(|row| {
-                Some(Trade {
-                    trade_id: row.trade_id,
-                    market_id: row.market_id,
-                    strategy: match row.strategy.as_str() {
-                        "clv_arb" => crate::types::Strategy::ClvArbitrage,
-                        "poisson_ev" => crate::types::Strategy::PoissonExpectedValue,
-                        _ => return None,
-                    },
-                    position: match row.position.as_str() {
-                        "yes" => Position::Yes,
-                        "no" => Position::No,
-                        _ => return None,
-                    },
-                    quantity: row.quantity,
-                    entry_price: row.entry_price,
-                    exit_price: None,
-                    entry_time: row.entry_time,
-                    exit_time: None,
-                    gas_cost: row.gas_cost,
-                    slippage: None,
-                    pnl: None,
-                    pnl_percent: None,
-                    status: TradeStatus::Open,
-                    tx_hash_entry: row.tx_hash_entry,
-                    tx_hash_exit: None,
-                })
-            })
-            .collect();
+    /// Leave the target exit on the book for a newly entered trade; its stop
+    /// is triggered by the position monitor, as the CLOB has no stop orders
+    async fn place_resting_exits(&self, trade: &Trade, tokens: &MarketTokens) -> Result<()> {
+        let Some(bracket) = &self.exit_bracket else {
+            return Ok(());
+        };
+
+        for (kind, price) in bracket.levels(trade.entry_price) {
+            if kind == RestingOrderKind::Stop {
+                continue;
+            }
+            let order = match self.order_rules.sell(trade.quantity, price) {
+                Ok(order) => order,
+                Err(e) => {
+                    warn!("No resting {} for trade {}: {}", kind.as_str(), trade.trade_id, e);
+                    continue;
+                }
+            };
+
+            self.rate_limiter.acquire(POLYMARKET_VENUE).await;
+            let order_id = self.executor
+                .place_resting_order(&trade.market_id, tokens, trade.position, kind, order.quantity, order.price)
+                .await?;
+
+            self.storage.insert_resting_order(&RestingOrder {
+                order_id,
+                trade_id: trade.trade_id,
+                kind,
+                price: order.price.value(),
+                quantity: order.quantity,
+                placed_at: Utc::now(),
+            }).await?;
 
-        Ok(trades)
+            info!("📌 Resting {} for trade {} at {}", kind.as_str(), trade.trade_id, order.price);
+        }
+
+        Ok(())
     }
 
-    async fn should_exit_position(&self, _trade: &Trade) -> Result<bool> {
-        // Implement exit logic:
-        // 1. Check if market is resolved
-        // 2. Check stop-loss conditions
-        // 3. Check take-profit conditions
-        // 4. Check time-based exits
-        
-        // For now, return false (hold until resolution)
+    /// Book a trade's resting exit fills and keep the orders sized to the
+    /// position; returns whether the trade was closed by one of them
+    async fn sync_resting_exits(&self, trade: &Trade) -> Result<bool> {
+        let orders = self.storage.fetch_resting_orders(trade.trade_id).await?;
+        if orders.is_empty() {
+            return Ok(false);
+        }
+
+        let market = self.storage.fetch_market(&trade.market_id).await?
+            .ok_or_else(|| anyhow!("Unknown market {}", trade.market_id))?;
+        let tokens = market.tokens
+            .ok_or_else(|| anyhow!("No outcome token ids for market {}", trade.market_id))?;
+
+        for order in &orders {
+            // Fills come from the chain; the order id is the order's hash
+            let fills = self.storage.fetch_order_fills(&order.order_id).await?;
+            if let Some(fill) = completed_fill(&fills, order.quantity) {
+                self.storage.set_resting_order_status(&order.order_id, RestingOrderStatus::Filled).await?;
+                // The other side of the bracket must not sell shares we no longer hold
+                self.cancel_resting_exits(trade).await?;
+
+                // Targets rest on the book; stops take liquidity once triggered
+                let liquidity = match order.kind {
+                    RestingOrderKind::Target => Liquidity::Maker,
+                    RestingOrderKind::Stop => Liquidity::Taker,
+                };
+                let exit_fees = self.fee_model
+                    .schedule(POLYMARKET_VENUE, &market.market_type)
+                    .fee(trade.quantity * fill.price, liquidity);
+
+                info!("🎯 Resting {} filled for trade {} at {}", order.kind.as_str(), trade.trade_id, fill.price);
+                self.record_exit(trade, &tokens, fill, exit_fees).await?;
+                return Ok(true);
+            }
+        }
+
+        // The position was scaled since the orders went in; re-place them at its new size
+        let quantity = self.order_rules.snap_quantity(trade.quantity);
+        if orders.iter().any(|o| o.quantity != quantity) {
+            info!("📐 Resizing resting exits of trade {} to {} shares", trade.trade_id, trade.quantity);
+            self.cancel_resting_exits(trade).await?;
+            self.place_resting_exits(trade, &tokens).await?;
+        }
+
         Ok(false)
     }
 
+    async fn cancel_resting_exits(&self, trade: &Trade) -> Result<()> {
+        for order in self.storage.fetch_resting_orders(trade.trade_id).await? {
+            self.executor.cancel_order(&order.order_id).await?;
+            self.storage.set_resting_order_status(&order.order_id, RestingOrderStatus::Cancelled).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn fetch_open_positions(&self) -> Result<Vec<Trade>> {
+        self.storage.fetch_open_trades().await
+    }
+
+    /// The stop-loss or take-profit level, if any, the trade's shares have
+    /// reached at the current bid; otherwise it is held to resolution
+    ///
+    /// The exit bracket's stop counts as a stop-loss here, since it does not
+    /// rest on the book.
+    async fn should_exit_position(&self, trade: &Trade) -> Result<Option<ExitTrigger>> {
+        let bid = self.get_exit_price(&trade.market_id, trade.position).await?;
+        if self.exit_bracket.as_ref().map_or(false, |bracket| bracket.stop_hit(trade.entry_price, bid)) {
+            return Ok(Some(ExitTrigger::StopLoss));
+        }
+        Ok(self.exit_thresholds.triggered(trade.strategy, trade.entry_price, bid))
+    }
+
+    /// Whether selling now is worth at least holding to resolution at the
+    /// model's latest probability; without a model view the exit goes ahead
+    async fn exit_favored(&self, trade: &Trade) -> Result<bool> {
+        let Some(win_probability) = self.storage.latest_fair_value(&trade.market_id, trade.position).await? else {
+            return Ok(true);
+        };
+
+        let market = self.storage.fetch_market(&trade.market_id).await?
+            .ok_or_else(|| anyhow!("Unknown market {}", trade.market_id))?;
+        let bid = self.get_exit_price(&trade.market_id, trade.position).await?;
+        let fees = self.fee_model.schedule(POLYMARKET_VENUE, &market.market_type);
+
+        let value = ExitValue::of(trade.quantity, bid, win_probability, &fees);
+        if !value.favors_exit() {
+            info!(
+                "⏳ Holding {} to resolution: exit worth ${} vs ${} expected at {}",
+                trade.trade_id, value.exit.round_dp(2), value.hold.round_dp(2), win_probability
+            );
+        }
+
+        Ok(value.favors_exit())
+    }
+
     async fn close_position(&self, trade: &Trade) -> Result<()> {
         info!("🔻 Closing position {}", trade.trade_id);
 
-        // Execute exit trade on blockchain
-        let opposite_position = match trade.position {
-            Position::Yes => Position::No,
-            Position::No => Position::Yes,
-        };
+        let market = self.storage.fetch_market(&trade.market_id).await?
+            .ok_or_else(|| anyhow!("Unknown market {}", trade.market_id))?;
+        // Ended markets have no book to sell into; the settlement monitor
+        // books and redeems them at the payout
+        if market.status.awaits_settlement() {
+            bail!("Market {} is {}, trade {} settles at resolution", trade.market_id, market.status.as_str(), trade.trade_id);
+        }
+
+        // Resting exits would otherwise sell shares this exit already sold
+        self.cancel_resting_exits(trade).await?;
 
-        // Get current market price
-        let current_price = self.get_current_price(&trade.market_id, trade.position).await?;
+        // Exit at the best bid for our shares
+        let current_price = self.get_exit_price(&trade.market_id, trade.position).await?;
 
-        match self.blockchain_client.execute_trade(
+        let tokens = market.tokens
+            .ok_or_else(|| anyhow!("No outcome token ids for market {}", trade.market_id))?;
+        let exit_fees = self.fee_model
+            .schedule(POLYMARKET_VENUE, &market.market_type)
+            .fee(trade.quantity * current_price.value(), Liquidity::Taker);
+
+        self.rate_limiter.acquire(POLYMARKET_VENUE).await;
+        let fill = self.executor.execute_exit(
             &trade.market_id,
-            opposite_position,
+            &tokens,
+            trade.position,
             trade.quantity,
             current_price,
-        ).await {
-            Ok(tx_hash) => {
-                let pnl = (current_price - trade.entry_price) * trade.quantity;
-                
-                // Update trade in database
-                sqlx::query!(
-                    r#"
-                    UPDATE trades
-                    SET exit_price = $2,
-                        exit_time = $3,
-                        pnl = $4,
-                        status = 'closed',
-                        tx_hash_exit = $5
-                    WHERE trade_id = $1
-                    "#,
-                    trade.trade_id,
-                    current_price,
-                    Utc::now(),
-                    pnl,
-                    tx_hash,
-                )
-                .execute(&self.db_pool)
-                .await?;
+        ).await?;
+
+        self.record_exit(trade, &tokens, fill, exit_fees).await
+    }
 
-                // Update portfolio
-                self.risk_manager.update_portfolio(pnl).await?;
+    /// Gas of selling the trade's shares at `price`, priced as an entry's
+    /// is; nothing when it cannot be estimated
+    async fn exit_gas(&self, trade: &Trade, tokens: &MarketTokens, price: Decimal) -> Decimal {
+        let urgency = self.strategies.cost_tolerance(trade.strategy.as_str()).urgency;
+        let estimate = match Price::new(price) {
+            Ok(price) => self.executor
+                .estimate_trade_gas(&trade.market_id, tokens, trade.position, trade.quantity, price, urgency)
+                .await,
+            Err(e) => Err(e.into()),
+        };
 
-                info!("✅ Position closed with PnL: {}", pnl);
-            }
-            Err(e) => {
-                error!("Failed to close position: {}", e);
-            }
-        }
+        estimate.unwrap_or_else(|e| {
+            warn!("Exit gas of trade {} not estimated: {}", trade.trade_id, e);
+            Decimal::ZERO
+        })
+    }
+
+    async fn record_exit(&self, trade: &Trade, tokens: &MarketTokens, fill: Fill, exit_fees: Decimal) -> Result<()> {
+        // Exits spend gas and fees like entries do, against the same budget
+        let exit_costs = Usdc::new(exit_fees + self.exit_gas(trade, tokens, fill.price).await);
+        let pnl = trade.realized_pnl(fill.price, exit_costs);
+        let exit_time = Utc::now();
+
+        // Update trade in database
+        self.storage.close_trade(
+            trade.trade_id,
+            fill.price,
+            exit_time,
+            pnl,
+            &fill.tx_hash,
+        ).await?;
+        self.storage.insert_execution_cost(&ExecutionCost {
+            cost_id: Uuid::new_v4(),
+            kind: ExecutionCostKind::Exit,
+            amount: exit_costs.value(),
+            trade_id: Some(trade.trade_id),
+            tx_hash: Some(fill.tx_hash.clone()),
+            incurred_at: exit_time,
+        }).await?;
+
+        self.publish_order(OrderUpdate::Closed, &Trade {
+            exit_price: Some(fill.price),
+            exit_time: Some(exit_time),
+            pnl: Some(pnl),
+            status: TradeStatus::Closed,
+            tx_hash_exit: Some(fill.tx_hash.clone()),
+            ..trade.clone()
+        }).await;
+
+        // Update portfolio
+        self.risk_manager.update_portfolio(pnl).await?;
+
+        info!("✅ Position closed with PnL: {}", pnl);
+        self.notifier.info(format!(
+            "✅ Closed {} {} in {} at {}, PnL ${}",
+            trade.strategy.as_str(),
+            trade.position.as_str(),
+            trade.market_id,
+            fill.price,
+            pnl.round_dp(2)
+        ));
 
         Ok(())
     }
 
-    async fn get_current_price(&self, market_id: &str, position: Position) -> Result<Decimal> {
-        let market = sqlx::query!(
-            r#"
-            SELECT yes_price, no_price
-            FROM markets
-            WHERE market_id = $1
-            "#,
-            market_id
-        )
-        .fetch_one(&self.db_pool)
-        .await?;
+    async fn get_exit_price(&self, market_id: &str, position: Position) -> Result<Price> {
+        let (yes_quote, no_quote) = self.storage.fetch_market_quotes(market_id).await?;
 
         Ok(match position {
-            Position::Yes => market.yes_price,
-            Position::No => market.no_price,
+            Position::Yes => yes_quote.bid,
+            Position::No => no_quote.bid,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    use crate::execution::{PaperExecutor, RecordingExecutor};
+    use crate::storage::{MarketRepo, MemoryStorage, SignalRepo, TradeRepo};
+    use super::super::executor::ExecutorCall;
+    use crate::test_support::{execution_engine, market_fixture, signal_fixture, test_config};
+    use crate::types::Strategy;
+
+    #[tokio::test]
+    async fn paper_orders_fill_through_the_default_slippage() {
+        let config = test_config("sqlite::memory:");
+        let storage = Arc::new(MemoryStorage::new());
+        let market = market_fixture("0xpaper", dec!(0.40));
+        storage.upsert_market(&market).await.unwrap();
+        let signal = signal_fixture(&market, dec!(0.50));
+        storage.insert_signal(&signal).await.unwrap();
+
+        let executor = Arc::new(PaperExecutor::new(storage.clone(), &config.execution.paper).unwrap());
+        let engine = execution_engine(storage.clone(), executor, &config).await.unwrap();
+
+        // Limited at the ask the signal saw, and filled 20 bps through it
+        engine.process_pending_signals().await.unwrap();
+        let trade_id = storage.executed_trade_for(signal.signal_id).await.expect("signal traded");
+        let trade = storage.fetch_trade(trade_id).await.unwrap().unwrap();
+        assert_eq!(trade.entry_price, dec!(0.4008));
+
+        // Exits are limited at the bid and fill 20 bps under it
+        engine.close_position(&trade).await.unwrap();
+        let closed = storage.fetch_trade(trade_id).await.unwrap().unwrap();
+        assert_eq!(closed.status, TradeStatus::Closed);
+        assert_eq!(closed.exit_price, Some(dec!(0.3992)));
+    }
+
+    #[tokio::test]
+    async fn fills_past_tolerance_are_recorded_and_flattened() {
+        let mut config = test_config("sqlite::memory:");
+        config.execution.max_fill_slippage = 0.0005;
+        config.execution.flatten_on_slippage = true;
+        let storage = Arc::new(MemoryStorage::new());
+        let market = market_fixture("0xslipped", dec!(0.40));
+        storage.upsert_market(&market).await.unwrap();
+        let signal = signal_fixture(&market, dec!(0.50));
+        storage.insert_signal(&signal).await.unwrap();
+
+        let executor = Arc::new(PaperExecutor::new(storage.clone(), &config.execution.paper).unwrap());
+        let engine = execution_engine(storage.clone(), executor, &config).await.unwrap();
+        engine.process_pending_signals().await.unwrap();
+
+        // 20 bps through a 0.40 ask fills 0.0008 past the signal price
+        let trade_id = storage.executed_trade_for(signal.signal_id).await.expect("signal traded");
+        let events = storage.slippage_events().await;
+        assert_eq!(events.len(), 1);
+        assert_eq!((events[0].trade_id, events[0].signal_id), (trade_id, signal.signal_id));
+        assert_eq!((events[0].expected_price, events[0].fill_price, events[0].slippage), (dec!(0.40), dec!(0.4008), dec!(0.0008)));
+        assert!(events[0].flattened);
+        assert_eq!(storage.fetch_trade(trade_id).await.unwrap().unwrap().status, TradeStatus::Closed);
+    }
+
+    #[tokio::test]
+    async fn fills_within_tolerance_record_no_slippage_event() {
+        let mut config = test_config("sqlite::memory:");
+        config.execution.max_fill_slippage = 0.001;
+        config.execution.flatten_on_slippage = true;
+        let storage = Arc::new(MemoryStorage::new());
+        let market = market_fixture("0xtolerated", dec!(0.40));
+        storage.upsert_market(&market).await.unwrap();
+        let signal = signal_fixture(&market, dec!(0.50));
+        storage.insert_signal(&signal).await.unwrap();
+
+        let executor = Arc::new(PaperExecutor::new(storage.clone(), &config.execution.paper).unwrap());
+        let engine = execution_engine(storage.clone(), executor, &config).await.unwrap();
+        engine.process_pending_signals().await.unwrap();
+
+        let trade_id = storage.executed_trade_for(signal.signal_id).await.expect("signal traded");
+        assert!(storage.slippage_events().await.is_empty());
+        assert_eq!(storage.fetch_trade(trade_id).await.unwrap().unwrap().status, TradeStatus::Open);
+    }
+
+    #[tokio::test]
+    async fn signals_past_their_strategy_deadline_are_discarded() {
+        let config = test_config("sqlite::memory:");
+        let storage = Arc::new(MemoryStorage::new());
+        let aged = |market_id, strategy| {
+            let market = market_fixture(market_id, dec!(0.40));
+            let signal = Signal {
+                strategy,
+                generated_at: Utc::now() - chrono::Duration::seconds(90),
+                ..signal_fixture(&market, dec!(0.50))
+            };
+            (market, signal)
+        };
+        // News edges expire after a minute, CLV ones after five
+        let (news_market, news) = aged("0xnews", Strategy::NewsScalping);
+        let (clv_market, clv) = aged("0xclv", Strategy::ClvArbitrage);
+        for (market, signal) in [(&news_market, &news), (&clv_market, &clv)] {
+            storage.upsert_market(market).await.unwrap();
+            storage.insert_signal(signal).await.unwrap();
+        }
+
+        let executor = Arc::new(RecordingExecutor::new());
+        let engine = execution_engine(storage.clone(), executor.clone(), &config).await.unwrap();
+        engine.process_pending_signals().await.unwrap();
+
+        let bought: Vec<String> = executor.calls().into_iter()
+            .filter_map(|call| match call {
+                ExecutorCall::Buy { market_id, .. } => Some(market_id),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(bought, vec!["0xclv".to_string()]);
+        assert!(storage.executed_trade_for(news.signal_id).await.is_none());
+        assert!(storage.executed_trade_for(clv.signal_id).await.is_some());
+        // The expired signal is done with, not left pending
+        let pending = storage.fetch_pending_signals(Utc::now() - chrono::Duration::hours(1), 10).await.unwrap();
+        assert!(pending.is_empty(), "{:?}", pending);
+    }
+
+    #[tokio::test]
+    async fn exits_count_against_the_daily_cost_budget() {
+        let config = test_config("sqlite::memory:");
+        let storage = Arc::new(MemoryStorage::new());
+        let market = market_fixture("0xcosts", dec!(0.40));
+        storage.upsert_market(&market).await.unwrap();
+        let signal = signal_fixture(&market, dec!(0.50));
+        storage.insert_signal(&signal).await.unwrap();
+
+        let executor = Arc::new(PaperExecutor::new(storage.clone(), &config.execution.paper).unwrap());
+        let engine = execution_engine(storage.clone(), executor, &config).await.unwrap();
+        engine.process_pending_signals().await.unwrap();
+        let trade_id = storage.executed_trade_for(signal.signal_id).await.expect("signal traded");
+        let trade = storage.fetch_trade(trade_id).await.unwrap().unwrap();
+        let entry_costs = trade.gas_cost.unwrap_or_default() + trade.fees.unwrap_or_default().value();
+        assert_eq!(storage.execution_costs_today().await.unwrap(), entry_costs);
+
+        // The exit's gas and fees come off its PnL and count toward today's spend
+        engine.close_position(&trade).await.unwrap();
+        let closed = storage.fetch_trade(trade_id).await.unwrap().unwrap();
+        let exit_costs = (trade.realized_pnl(closed.exit_price.unwrap(), Usdc::ZERO) - closed.pnl.unwrap()).value();
+        assert!(exit_costs >= numeric::to_decimal(config.execution.paper.gas_cost_usd).unwrap());
+        assert_eq!(storage.execution_costs_today().await.unwrap(), entry_costs + exit_costs);
+    }
+
+    #[tokio::test]
+    async fn engine_sends_the_sized_buy_to_the_executor() {
+        let mut config = test_config("sqlite::memory:");
+        config.execution.resting_exits = true;
+        let storage = Arc::new(MemoryStorage::new());
+        let market = market_fixture("0xrecorded", dec!(0.40));
+        storage.upsert_market(&market).await.unwrap();
+        let signal = signal_fixture(&market, dec!(0.50));
+        storage.insert_signal(&signal).await.unwrap();
+
+        let executor = Arc::new(RecordingExecutor::new());
+        let engine = execution_engine(storage.clone(), executor.clone(), &config).await.unwrap();
+        engine.process_pending_signals().await.unwrap();
+
+        let calls = executor.calls();
+        let Some(ExecutorCall::Buy { market_id, position, quantity, price, time_in_force }) = calls.first() else {
+            panic!("expected a buy first, got {:?}", calls);
+        };
+        assert_eq!((market_id.as_str(), *position), ("0xrecorded", Position::Yes));
+        assert_eq!(price.value(), dec!(0.40));
+        assert_eq!(*time_in_force, config.strategies.time_in_force(signal.strategy.as_str()).taker);
+
+        // The trade is booked at the recorded fill, then its target rests for the same shares; the stop stays client-side
+        let trade_id = storage.executed_trade_for(signal.signal_id).await.expect("signal traded");
+        let trade = storage.fetch_trade(trade_id).await.unwrap().unwrap();
+        assert_eq!((trade.entry_price, trade.quantity), (dec!(0.40), *quantity));
+        let rested: Vec<RestingOrderKind> = calls[1..].iter()
+            .filter_map(|call| match call {
+                ExecutorCall::Rest { kind, quantity: q, .. } if q == quantity => Some(*kind),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(rested, vec![RestingOrderKind::Target]);
+
+        // Once the bid falls to the stop the position is sold at it
+        let stop = trade.entry_price - numeric::to_decimal(config.execution.resting_stop_distance).unwrap();
+        storage.upsert_market(&market_fixture("0xrecorded", stop)).await.unwrap();
+        engine.monitor_positions().await.unwrap();
+        assert!(matches!(executor.calls().last(), Some(ExecutorCall::Sell { .. })), "{:?}", executor.calls());
+        assert_eq!(storage.fetch_trade(trade_id).await.unwrap().unwrap().status, TradeStatus::Closed);
+    }
+
+    #[tokio::test]
+    async fn a_short_fill_correction_resizes_the_resting_exits() {
+        let mut config = test_config("sqlite::memory:");
+        config.execution.resting_exits = true;
+        let storage = Arc::new(MemoryStorage::new());
+        let market = market_fixture("0xshort", dec!(0.40));
+        storage.upsert_market(&market).await.unwrap();
+        let signal = signal_fixture(&market, dec!(0.50));
+        storage.insert_signal(&signal).await.unwrap();
+
+        let executor = Arc::new(RecordingExecutor::new());
+        let engine = execution_engine(storage.clone(), executor.clone(), &config).await.unwrap();
+        engine.process_pending_signals().await.unwrap();
+        let trade_id = storage.executed_trade_for(signal.signal_id).await.expect("signal traded");
+        let trade = storage.fetch_trade(trade_id).await.unwrap().unwrap();
+        let target = storage.fetch_resting_orders(trade_id).await.unwrap();
+        assert_eq!(target.iter().map(|o| o.quantity).collect::<Vec<_>>(), vec![trade.quantity]);
+
+        // The chain says only 10 shares filled
+        storage.correct_trade_entry(trade_id, trade.entry_price, dec!(10), trade.slippage).await.unwrap();
+        let corrected = storage.fetch_trade(trade_id).await.unwrap().unwrap();
+        engine.handle_events(vec![Event::OrderUpdated { update: OrderUpdate::Corrected, trade: Box::new(corrected) }]).await;
+
+        assert!(executor.calls().contains(&ExecutorCall::Cancel { order_id: target[0].order_id.clone() }));
+        let resting = storage.fetch_resting_orders(trade_id).await.unwrap();
+        assert_eq!(resting.iter().map(|o| o.quantity).collect::<Vec<_>>(), vec![dec!(10)]);
+    }
+}
@@ -0,0 +1,557 @@
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::sync::{Arc, Mutex};
+
+use crate::config::PaperExecutionConfig;
+use crate::numeric;
+use crate::storage::Storage;
+use crate::types::{
+    EntryOrder, FillSide, GasUrgency, MarketTokens, OnchainFill, Position, Price, RestingOrderKind, TimeInForce, Usdc,
+};
+
+/// A confirmed order
+pub struct Fill {
+    pub tx_hash: String,
+    /// Average price paid per share
+    pub price: Decimal,
+    /// Shares filled; short of the order only for immediate-or-cancel takers
+    pub quantity: Decimal,
+}
+
+/// Sends the execution engine's orders to a venue
+///
+/// Orders carry both the market id and its outcome token ids: the chain
+/// addresses shares by token, while paper fills look quotes up by market.
+#[async_trait]
+pub trait TradeExecutor: Send + Sync {
+    /// Buy `quantity` shares of `position` at no more than `max_price`
+    ///
+    /// `time_in_force` is `Fok`, failing unless every share fills, or `Ioc`,
+    /// returning a fill of whatever part could be taken. `urgency` sets the
+    /// priority fee bid for the transaction.
+    async fn execute_trade(
+        &self,
+        market_id: &str,
+        tokens: &MarketTokens,
+        position: Position,
+        quantity: Decimal,
+        max_price: Price,
+        time_in_force: TimeInForce,
+        urgency: GasUrgency,
+    ) -> Result<Fill>;
+
+    /// Sell `quantity` shares of `position` at no less than `min_price`
+    async fn execute_exit(
+        &self,
+        market_id: &str,
+        tokens: &MarketTokens,
+        position: Position,
+        quantity: Decimal,
+        min_price: Price,
+    ) -> Result<Fill>;
+
+    /// USD gas cost of the transaction `execute_trade` would send at `urgency`
+    async fn estimate_trade_gas(
+        &self,
+        market_id: &str,
+        tokens: &MarketTokens,
+        position: Position,
+        quantity: Decimal,
+        max_price: Price,
+        urgency: GasUrgency,
+    ) -> Result<Decimal>;
+
+    /// Leave an order on the CLOB selling `quantity` shares of `position`
+    /// at `price`; returns the venue order id
+    ///
+    /// A target rests as a plain limit sell. A stop is held by the venue
+    /// and becomes a marketable sell once the bid trades down to `price`.
+    async fn place_resting_order(
+        &self,
+        market_id: &str,
+        tokens: &MarketTokens,
+        position: Position,
+        kind: RestingOrderKind,
+        quantity: Decimal,
+        price: Price,
+    ) -> Result<String>;
+
+    /// Leave a limit buy of `quantity` shares of `position` at `price` on the
+    /// CLOB, as maker; returns the venue order id
+    ///
+    /// With an `expiration` the order is good-til-date and the venue pulls
+    /// it then; without one it is good-til-cancelled.
+    async fn place_entry_order(
+        &self,
+        market_id: &str,
+        tokens: &MarketTokens,
+        position: Position,
+        quantity: Decimal,
+        price: Price,
+        expiration: Option<DateTime<Utc>>,
+    ) -> Result<String>;
+
+    /// Record fills of a resting entry the chain will not report, before
+    /// the engine reads the order's fills; live fills are recorded by the
+    /// fill watcher, so only simulated venues record any here
+    async fn match_entry_order(&self, order: &EntryOrder) -> Result<()>;
+
+    /// Cancel a resting order; cancelling one that already filled or was
+    /// cancelled is not an error
+    async fn cancel_order(&self, order_id: &str) -> Result<()>;
+}
+
+fn simulated_id() -> String {
+    format!("0x{:064x}", rand::random::<u64>())
+}
+
+/// Price a paper order fills at: the stored touch moved against us by
+/// `slippage` (a fraction of price)
+///
+/// Orders are priced at the touch, so the slippage is what the fill costs
+/// beyond it and may go past `limit`; only a touch that has itself moved
+/// through `limit` since the order was priced is rejected.
+pub fn paper_fill_price(touch: Price, slippage: Decimal, limit: Price, buy: bool) -> Result<Decimal> {
+    let moved = if buy { touch > limit } else { touch < limit };
+    if moved {
+        bail!("touch {} is through the limit {}", touch.value(), limit.value());
+    }
+
+    let price = if buy {
+        touch.saturating_add(touch.value() * slippage)
+    } else {
+        touch.saturating_add(-touch.value() * slippage)
+    };
+    Ok(price.value())
+}
+
+/// Shares a paper taker fills against the `available` shares offered up to
+/// its limit, when known: all or nothing for fill-or-kill, as many as there
+/// are for immediate-or-cancel
+pub fn paper_fill_quantity(quantity: Decimal, available: Option<Decimal>, time_in_force: TimeInForce) -> Result<Decimal> {
+    let Some(available) = available.filter(|a| *a < quantity) else {
+        return Ok(quantity);
+    };
+    match time_in_force {
+        TimeInForce::Ioc if available > Decimal::ZERO => Ok(available),
+        TimeInForce::Ioc | TimeInForce::Fok => {
+            bail!("{} killed: {} of {} shares up to the limit", time_in_force.as_str(), available, quantity)
+        }
+        TimeInForce::Gtc | TimeInForce::Gtd => bail!("takers are FOK or IOC, not {}", time_in_force.as_str()),
+    }
+}
+
+/// Fills orders against the stored top of book, for paper trading
+///
+/// Buys fill at the ask and sells at the bid, each moved against us by
+/// `slippage_bps`, and every transaction is charged a flat gas cost. Buys
+/// are sized against the stored book's offers up to their limit, or the
+/// shares at the ask without one, and pay the average of the levels they
+/// sweep when that is worse than the slipped touch.
+/// Resting entries fill at their own price against each stored book taken
+/// after they were placed, as offers come down to them; resting exits are
+/// acknowledged but never fill.
+pub struct PaperExecutor {
+    storage: Arc<dyn Storage>,
+    slippage: Decimal,
+    gas_cost: Decimal,
+}
+
+impl PaperExecutor {
+    pub fn new(storage: Arc<dyn Storage>, config: &PaperExecutionConfig) -> Result<Self> {
+        Ok(Self {
+            storage,
+            slippage: numeric::to_decimal(config.slippage_bps).context("execution.paper.slippage_bps")?
+                / Decimal::from(10_000),
+            gas_cost: numeric::to_decimal(config.gas_cost_usd).context("execution.paper.gas_cost_usd")?,
+        })
+    }
+}
+
+#[async_trait]
+impl TradeExecutor for PaperExecutor {
+    async fn execute_trade(
+        &self,
+        market_id: &str,
+        _tokens: &MarketTokens,
+        position: Position,
+        quantity: Decimal,
+        max_price: Price,
+        time_in_force: TimeInForce,
+        _urgency: GasUrgency,
+    ) -> Result<Fill> {
+        let (yes, no) = self.storage.fetch_market_quotes(market_id).await?;
+        let quote = match position {
+            Position::Yes => yes,
+            Position::No => no,
+        };
+        let touch = paper_fill_price(quote.ask, self.slippage, max_price, true)?;
+
+        // A taker sweeps every level up to its limit, not just the touch
+        let book = self.storage.fetch_book_snapshot(market_id).await?;
+        let available = book.as_ref()
+            .map(|book| book.offered_shares(position, max_price.value()))
+            .or(quote.ask_size);
+        let quantity = paper_fill_quantity(quantity, available, time_in_force)?;
+        let price = book
+            .and_then(|book| book.average_offer_price(position, quantity, max_price.value()))
+            .map_or(touch, |swept| swept.max(touch));
+
+        Ok(Fill {
+            tx_hash: simulated_id(),
+            price,
+            quantity,
+        })
+    }
+
+    async fn execute_exit(
+        &self,
+        market_id: &str,
+        _tokens: &MarketTokens,
+        position: Position,
+        quantity: Decimal,
+        min_price: Price,
+    ) -> Result<Fill> {
+        let (yes, no) = self.storage.fetch_market_quotes(market_id).await?;
+        let bid = match position {
+            Position::Yes => yes.bid,
+            Position::No => no.bid,
+        };
+
+        Ok(Fill {
+            tx_hash: simulated_id(),
+            price: paper_fill_price(bid, self.slippage, min_price, false)?,
+            quantity,
+        })
+    }
+
+    async fn estimate_trade_gas(
+        &self,
+        _market_id: &str,
+        _tokens: &MarketTokens,
+        _position: Position,
+        _quantity: Decimal,
+        _max_price: Price,
+        _urgency: GasUrgency,
+    ) -> Result<Decimal> {
+        Ok(self.gas_cost)
+    }
+
+    async fn place_resting_order(
+        &self,
+        _market_id: &str,
+        _tokens: &MarketTokens,
+        _position: Position,
+        _kind: RestingOrderKind,
+        _quantity: Decimal,
+        _price: Price,
+    ) -> Result<String> {
+        Ok(simulated_id())
+    }
+
+    async fn place_entry_order(
+        &self,
+        _market_id: &str,
+        _tokens: &MarketTokens,
+        _position: Position,
+        _quantity: Decimal,
+        _price: Price,
+        _expiration: Option<DateTime<Utc>>,
+    ) -> Result<String> {
+        Ok(simulated_id())
+    }
+
+    /// Fill the bid from what the stored book offers at or under it; each
+    /// book is matched once, so an offer is not filled again on every sweep
+    async fn match_entry_order(&self, order: &EntryOrder) -> Result<()> {
+        let Some(book) = self.storage.fetch_book_snapshot(&order.market_id).await? else {
+            return Ok(());
+        };
+        let fills = self.storage.fetch_order_fills(&order.order_id).await?;
+        let matched_at = fills.iter().map(|f| f.observed_at).max().unwrap_or(order.placed_at);
+        if book.captured_at <= matched_at {
+            return Ok(());
+        }
+
+        let filled: Decimal = fills.iter().map(|f| f.shares).sum();
+        let shares = book.offered_shares(order.position, order.price).min(order.quantity - filled);
+        if shares <= Decimal::ZERO {
+            return Ok(());
+        }
+        let token_id = self.storage.fetch_market(&order.market_id).await?
+            .and_then(|market| market.tokens)
+            .map(|tokens| tokens.token_id(order.position).to_string())
+            .unwrap_or_default();
+        self.storage.insert_onchain_fill(&OnchainFill {
+            tx_hash: simulated_id(),
+            log_index: 0,
+            order_hash: order.order_id.clone(),
+            token_id,
+            side: FillSide::Buy,
+            shares,
+            price: order.price,
+            fee: Usdc::ZERO,
+            block_number: 0,
+            observed_at: book.captured_at,
+        }).await?;
+
+        Ok(())
+    }
+
+    async fn cancel_order(&self, _order_id: &str) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// An order sent to a `RecordingExecutor`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExecutorCall {
+    Buy { market_id: String, position: Position, quantity: Decimal, price: Price, time_in_force: TimeInForce },
+    Sell { market_id: String, position: Position, quantity: Decimal, price: Price },
+    Rest { market_id: String, position: Position, kind: RestingOrderKind, quantity: Decimal, price: Price, order_id: String },
+    Post {
+        market_id: String,
+        position: Position,
+        quantity: Decimal,
+        price: Price,
+        expiration: Option<DateTime<Utc>>,
+        order_id: String,
+    },
+    Cancel { order_id: String },
+}
+
+/// Fills every order at its limit price and keeps the calls it was sent,
+/// for replaying a session and testing the engine without a chain
+#[derive(Default)]
+pub struct RecordingExecutor {
+    calls: Mutex<Vec<ExecutorCall>>,
+}
+
+impl RecordingExecutor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every call so far, in order
+    #[cfg(test)]
+    pub fn calls(&self) -> Vec<ExecutorCall> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn record(&self, call: ExecutorCall) {
+        self.calls.lock().unwrap().push(call);
+    }
+}
+
+#[async_trait]
+impl TradeExecutor for RecordingExecutor {
+    async fn execute_trade(
+        &self,
+        market_id: &str,
+        _tokens: &MarketTokens,
+        position: Position,
+        quantity: Decimal,
+        max_price: Price,
+        time_in_force: TimeInForce,
+        _urgency: GasUrgency,
+    ) -> Result<Fill> {
+        self.record(ExecutorCall::Buy {
+            market_id: market_id.to_string(),
+            position,
+            quantity,
+            price: max_price,
+            time_in_force,
+        });
+        Ok(Fill { tx_hash: simulated_id(), price: max_price.value(), quantity })
+    }
+
+    async fn execute_exit(
+        &self,
+        market_id: &str,
+        _tokens: &MarketTokens,
+        position: Position,
+        quantity: Decimal,
+        min_price: Price,
+    ) -> Result<Fill> {
+        self.record(ExecutorCall::Sell { market_id: market_id.to_string(), position, quantity, price: min_price });
+        Ok(Fill { tx_hash: simulated_id(), price: min_price.value(), quantity })
+    }
+
+    async fn estimate_trade_gas(
+        &self,
+        _market_id: &str,
+        _tokens: &MarketTokens,
+        _position: Position,
+        _quantity: Decimal,
+        _max_price: Price,
+        _urgency: GasUrgency,
+    ) -> Result<Decimal> {
+        Ok(Decimal::ZERO)
+    }
+
+    async fn place_resting_order(
+        &self,
+        market_id: &str,
+        _tokens: &MarketTokens,
+        position: Position,
+        kind: RestingOrderKind,
+        quantity: Decimal,
+        price: Price,
+    ) -> Result<String> {
+        let order_id = simulated_id();
+        self.record(ExecutorCall::Rest {
+            market_id: market_id.to_string(),
+            position,
+            kind,
+            quantity,
+            price,
+            order_id: order_id.clone(),
+        });
+        Ok(order_id)
+    }
+
+    async fn place_entry_order(
+        &self,
+        market_id: &str,
+        _tokens: &MarketTokens,
+        position: Position,
+        quantity: Decimal,
+        price: Price,
+        expiration: Option<DateTime<Utc>>,
+    ) -> Result<String> {
+        let order_id = simulated_id();
+        self.record(ExecutorCall::Post {
+            market_id: market_id.to_string(),
+            position,
+            quantity,
+            price,
+            expiration,
+            order_id: order_id.clone(),
+        });
+        Ok(order_id)
+    }
+
+    async fn match_entry_order(&self, _order: &EntryOrder) -> Result<()> {
+        Ok(())
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        self.record(ExecutorCall::Cancel { order_id: order_id.to_string() });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::storage::{MarketRepo, MemoryStorage, TradeRepo};
+    use crate::test_support::market_fixture;
+    use crate::types::{BookSnapshot, DepthLevel};
+    use chrono::Duration;
+    use rust_decimal_macros::dec;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn paper_bids_fill_from_each_later_book_at_their_price() {
+        let storage = Arc::new(MemoryStorage::new());
+        let executor = PaperExecutor::new(storage.clone(), &Config::default().execution.paper).unwrap();
+        let placed_at = Utc::now();
+        let order = EntryOrder {
+            order_id: "0xbid".to_string(),
+            signal_id: Uuid::new_v4(),
+            market_id: "0xmarket".to_string(),
+            position: Position::Yes,
+            price: dec!(0.40),
+            quantity: dec!(1000),
+            gas_cost: dec!(0.01),
+            placed_at,
+            expires_at: placed_at + Duration::minutes(15),
+        };
+        let book = |secs, asks: &[(Decimal, Decimal)]| BookSnapshot {
+            market_id: "0xmarket".to_string(),
+            bids: vec![],
+            asks: asks.iter().map(|&(price, size)| DepthLevel { price, size }).collect(),
+            captured_at: placed_at + Duration::seconds(secs),
+        };
+        let filled = || async { storage.fetch_order_fills("0xbid").await.unwrap() };
+
+        // Only the offers at or under the bid trade with it
+        storage.upsert_book_snapshot(&book(5, &[(dec!(0.39), dec!(200)), (dec!(0.40), dec!(300)), (dec!(0.45), dec!(900))])).await.unwrap();
+        executor.match_entry_order(&order).await.unwrap();
+        let fills = filled().await;
+        assert_eq!((fills.len(), fills[0].shares, fills[0].price), (1, dec!(500), dec!(0.40)));
+
+        // The same book is not matched twice
+        executor.match_entry_order(&order).await.unwrap();
+        assert_eq!(filled().await.len(), 1);
+
+        // A later book fills what is left, and no more
+        storage.upsert_book_snapshot(&book(10, &[(dec!(0.40), dec!(800))])).await.unwrap();
+        executor.match_entry_order(&order).await.unwrap();
+        let shares: Decimal = filled().await.iter().map(|f| f.shares).sum();
+        assert_eq!(shares, dec!(1000));
+    }
+
+    #[test]
+    fn paper_fills_slip_against_us_and_respect_the_limit() {
+        let price = |p| Price::new(p).unwrap();
+        let slippage = dec!(0.01);
+
+        assert_eq!(paper_fill_price(price(dec!(0.50)), slippage, price(dec!(0.52)), true).unwrap(), dec!(0.505));
+        assert_eq!(paper_fill_price(price(dec!(0.50)), slippage, price(dec!(0.48)), false).unwrap(), dec!(0.495));
+
+        // Orders limited at the touch, as the engine sends them, still fill
+        assert_eq!(paper_fill_price(price(dec!(0.50)), slippage, price(dec!(0.50)), true).unwrap(), dec!(0.505));
+        assert_eq!(paper_fill_price(price(dec!(0.50)), slippage, price(dec!(0.50)), false).unwrap(), dec!(0.495));
+
+        // The book moved away from the limit since the order was priced
+        assert!(paper_fill_price(price(dec!(0.51)), slippage, price(dec!(0.50)), true).is_err());
+        assert!(paper_fill_price(price(dec!(0.49)), slippage, price(dec!(0.50)), false).is_err());
+    }
+
+    #[tokio::test]
+    async fn paper_takers_sweep_the_book_up_to_their_limit() {
+        let storage = Arc::new(MemoryStorage::new());
+        let executor = PaperExecutor::new(storage.clone(), &Config::default().execution.paper).unwrap();
+        let mut market = market_fixture("0xdeep", dec!(0.50));
+        market.yes_quote = market.yes_quote.with_depth(None, Some(dec!(40)));
+        storage.upsert_market(&market).await.unwrap();
+        let tokens = market.tokens.clone().unwrap();
+        storage.upsert_book_snapshot(&BookSnapshot {
+            market_id: "0xdeep".to_string(),
+            bids: vec![],
+            asks: vec![
+                DepthLevel { price: dec!(0.51), size: dec!(100) },
+                DepthLevel { price: dec!(0.50), size: dec!(40) },
+                DepthLevel { price: dec!(0.60), size: dec!(500) },
+            ],
+            captured_at: Utc::now(),
+        }).await.unwrap();
+        let buy = |quantity, limit, time_in_force| executor.execute_trade(
+            "0xdeep", &tokens, Position::Yes, quantity, Price::new(limit).unwrap(), time_in_force, GasUrgency::Normal,
+        );
+
+        // Only 40 sit at the touch, but 140 are offered up to 0.52
+        let fill = buy(dec!(100), dec!(0.52), TimeInForce::Fok).await.unwrap();
+        assert_eq!((fill.quantity, fill.price), (dec!(100), dec!(0.506)));
+
+        // Past what the limit reaches, FOK is killed and IOC keeps the rest
+        assert!(buy(dec!(200), dec!(0.52), TimeInForce::Fok).await.is_err());
+        assert_eq!(buy(dec!(200), dec!(0.52), TimeInForce::Ioc).await.unwrap().quantity, dec!(140));
+    }
+
+    #[test]
+    fn fill_or_kill_needs_the_whole_size_up_to_the_limit() {
+        assert_eq!(paper_fill_quantity(dec!(100), None, TimeInForce::Fok).unwrap(), dec!(100));
+        assert_eq!(paper_fill_quantity(dec!(100), Some(dec!(250)), TimeInForce::Fok).unwrap(), dec!(100));
+        assert!(paper_fill_quantity(dec!(100), Some(dec!(40)), TimeInForce::Fok).is_err());
+
+        // Immediate-or-cancel keeps the part that fills
+        assert_eq!(paper_fill_quantity(dec!(100), Some(dec!(40)), TimeInForce::Ioc).unwrap(), dec!(40));
+        assert!(paper_fill_quantity(dec!(100), Some(dec!(0)), TimeInForce::Ioc).is_err());
+        assert!(paper_fill_quantity(dec!(100), Some(dec!(40)), TimeInForce::Gtc).is_err());
+    }
+}
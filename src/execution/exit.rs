@@ -0,0 +1,49 @@
+use rust_decimal::Decimal;
+
+use crate::types::Price;
+use super::fees::{FeeSchedule, Liquidity};
+
+/// What a position is worth sold now versus held to resolution
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExitValue {
+    /// Proceeds of selling at the bid, after taker fees
+    pub exit: Decimal,
+    /// Expected payout at resolution: one dollar per share when the position wins
+    pub hold: Decimal,
+}
+
+impl ExitValue {
+    /// Value `quantity` shares at `bid` against the model's `win_probability`
+    pub fn of(quantity: Decimal, bid: Price, win_probability: Decimal, fees: &FeeSchedule) -> Self {
+        let proceeds = quantity * bid.value();
+        Self {
+            exit: proceeds - fees.fee(proceeds, Liquidity::Taker),
+            hold: quantity * win_probability,
+        }
+    }
+
+    /// Exiting gives up the spread and fees, so it must be worth at least holding
+    pub fn favors_exit(&self) -> bool {
+        self.exit >= self.hold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn exits_only_when_the_bid_after_fees_beats_the_model() {
+        let fees = FeeSchedule { maker_rate: dec!(0), taker_rate: dec!(0.02), relayer_fee: dec!(0) };
+        let bid = Price::new(dec!(0.60)).unwrap();
+
+        // 100 shares: $60 less $1.20 fees against $55 expected at resolution
+        let value = ExitValue::of(dec!(100), bid, dec!(0.55), &fees);
+        assert_eq!(value, ExitValue { exit: dec!(58.80), hold: dec!(55.00) });
+        assert!(value.favors_exit());
+
+        // The model still rates the position above the bid
+        assert!(!ExitValue::of(dec!(100), bid, dec!(0.59), &fees).favors_exit());
+    }
+}
@@ -0,0 +1,122 @@
+use anyhow::{Context, Result};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::collections::HashMap;
+
+use crate::config::{FeeOverrideConfig, FeeScheduleConfig, FeesConfig};
+use crate::numeric;
+use crate::types::MarketType;
+
+/// Which side of the book an order takes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Liquidity {
+    Maker,
+    Taker,
+}
+
+/// Fees charged on a single order
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeeSchedule {
+    /// Fraction of notional charged to resting orders
+    pub maker_rate: Decimal,
+    /// Fraction of notional charged to crossing orders
+    pub taker_rate: Decimal,
+    /// Flat USD fee per order (relayer / gasless execution)
+    pub relayer_fee: Decimal,
+}
+
+impl FeeSchedule {
+    pub const ZERO: FeeSchedule = FeeSchedule {
+        maker_rate: Decimal::ZERO,
+        taker_rate: Decimal::ZERO,
+        relayer_fee: Decimal::ZERO,
+    };
+
+    pub fn rate(&self, liquidity: Liquidity) -> Decimal {
+        match liquidity {
+            Liquidity::Maker => self.maker_rate,
+            Liquidity::Taker => self.taker_rate,
+        }
+    }
+
+    /// Total USD fee for an order of `notional` USD
+    pub fn fee(&self, notional: Decimal, liquidity: Liquidity) -> Decimal {
+        notional * self.rate(liquidity) + self.relayer_fee
+    }
+
+    /// Edge per share left after paying the percentage fee at `price`
+    ///
+    /// The flat relayer fee depends on order size and is charged in PnL
+    /// accounting instead.
+    pub fn net_edge(&self, edge: Decimal, price: Decimal, liquidity: Liquidity) -> Decimal {
+        edge - price * self.rate(liquidity)
+    }
+
+    fn with_override(mut self, o: &FeeOverrideConfig) -> Result<Self> {
+        if let Some(bps) = o.maker_bps {
+            self.maker_rate = bps_to_rate(bps)?;
+        }
+        if let Some(bps) = o.taker_bps {
+            self.taker_rate = bps_to_rate(bps)?;
+        }
+        if let Some(usd) = o.relayer_fee_usd {
+            self.relayer_fee = numeric::to_decimal(usd)?;
+        }
+        Ok(self)
+    }
+}
+
+struct VenueFees {
+    base: FeeSchedule,
+    by_market_type: HashMap<String, FeeSchedule>,
+}
+
+/// Fee schedules per venue, with optional per-market-type overrides
+///
+/// Venues missing from the configuration are treated as fee-free.
+pub struct FeeModel {
+    venues: HashMap<String, VenueFees>,
+}
+
+impl FeeModel {
+    pub fn from_config(config: &FeesConfig) -> Result<Self> {
+        let mut venues = HashMap::new();
+
+        for (venue, schedule) in &config.venues {
+            let fees = Self::venue_fees(schedule)
+                .with_context(|| format!("fees.venues.{}", venue))?;
+            venues.insert(venue.clone(), fees);
+        }
+
+        Ok(Self { venues })
+    }
+
+    fn venue_fees(config: &FeeScheduleConfig) -> Result<VenueFees> {
+        let base = FeeSchedule {
+            maker_rate: bps_to_rate(config.maker_bps)?,
+            taker_rate: bps_to_rate(config.taker_bps)?,
+            relayer_fee: numeric::to_decimal(config.relayer_fee_usd)?,
+        };
+
+        let mut by_market_type = HashMap::new();
+        for (market_type, o) in &config.market_types {
+            by_market_type.insert(market_type.to_lowercase(), base.with_override(o)?);
+        }
+
+        Ok(VenueFees { base, by_market_type })
+    }
+
+    pub fn schedule(&self, venue: &str, market_type: &MarketType) -> FeeSchedule {
+        match self.venues.get(venue) {
+            Some(fees) => fees.by_market_type
+                .get(market_type.as_str())
+                .copied()
+                .unwrap_or(fees.base),
+            None => FeeSchedule::ZERO,
+        }
+    }
+}
+
+fn bps_to_rate(bps: f64) -> Result<Decimal> {
+    Ok(numeric::to_decimal(bps)? / dec!(10000))
+}
@@ -0,0 +1,270 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use tracing::{error, info};
+
+use crate::config::FillModelConfig;
+use crate::numeric;
+use crate::storage::Storage;
+use crate::types::PricePoint;
+
+/// Resting times the model is fitted at; others interpolate between them
+const HORIZONS_SECS: [i64; 5] = [60, 300, 900, 3_600, 14_400];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct FillCell {
+    samples: u64,
+    fills: u64,
+}
+
+impl FillCell {
+    fn rate(&self, min_samples: u64) -> Option<f64> {
+        (self.samples >= min_samples.max(1)).then(|| self.fills as f64 / self.samples as f64)
+    }
+}
+
+/// Chance a resting bid fills before it is pulled, by its distance below
+/// the mid and how long it can rest
+///
+/// Fitted from the recorded price paths of markets whose events have
+/// started, taking each recorded price as the mid. From each point, a bid
+/// `k` ticks below counts as filled within a horizon if the price trades
+/// through it before the horizon ends; windows running past the event's
+/// start are not counted. A NO bid fills as YES trades up, so both sides are
+/// pooled. Trading through rather than touching allows for the queue ahead
+/// of the order.
+#[derive(Debug, Clone)]
+pub struct FillModel {
+    tick_size: Decimal,
+    min_samples: u64,
+    /// Per distance in ticks from 0, per horizon
+    cells: Vec<[FillCell; HORIZONS_SECS.len()]>,
+    markets: usize,
+}
+
+impl FillModel {
+    /// `points` by market then time; markets without an event time are skipped
+    pub fn fit(
+        points: &[PricePoint],
+        event_times: &HashMap<String, DateTime<Utc>>,
+        tick_size: Decimal,
+        max_ticks: usize,
+        min_samples: u64,
+    ) -> Self {
+        let mut model = Self {
+            tick_size,
+            min_samples,
+            cells: vec![[FillCell::default(); HORIZONS_SECS.len()]; max_ticks + 1],
+            markets: 0,
+        };
+        let Some(tick) = tick_size.to_f64().filter(|t| *t > 0.0) else {
+            return model;
+        };
+
+        let mut paths: HashMap<&str, Vec<(DateTime<Utc>, f64)>> = HashMap::new();
+        for point in points {
+            if let Some(price) = point.yes_price.value().to_f64() {
+                paths.entry(point.market_id.as_str()).or_default().push((point.observed_at, price));
+            }
+        }
+        for (market_id, path) in paths {
+            if let Some(event_time) = event_times.get(market_id) {
+                model.markets += 1;
+                model.add_path(&path, *event_time, tick);
+            }
+        }
+
+        model
+    }
+
+    fn add_path(&mut self, path: &[(DateTime<Utc>, f64)], event_time: DateTime<Utc>, tick: f64) {
+        let through = tick * 1e-6;
+        for (i, &(start, mid)) in path.iter().enumerate() {
+            let (mut low, mut high) = (mid, mid);
+            let mut next = i + 1;
+            for (h, secs) in HORIZONS_SECS.iter().enumerate() {
+                let end = start + Duration::seconds(*secs);
+                if end > event_time {
+                    break;
+                }
+                while next < path.len() && path[next].0 <= end {
+                    low = low.min(path[next].1);
+                    high = high.max(path[next].1);
+                    next += 1;
+                }
+                // Recording stopped inside the window; what happened is unknown
+                if next >= path.len() {
+                    break;
+                }
+
+                for (k, row) in self.cells.iter_mut().enumerate() {
+                    let distance = k as f64 * tick;
+                    let cell = &mut row[h];
+                    // YES bid below the mid, and NO bid below the NO mid
+                    if mid - distance >= tick {
+                        cell.samples += 1;
+                        cell.fills += (low < mid - distance - through) as u64;
+                    }
+                    if mid + distance <= 1.0 - tick {
+                        cell.samples += 1;
+                        cell.fills += (high > mid + distance + through) as u64;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Markets the model was fitted on
+    pub fn markets(&self) -> usize {
+        self.markets
+    }
+
+    /// Samples behind all cells
+    pub fn samples(&self) -> u64 {
+        self.cells.iter().flatten().map(|c| c.samples).sum()
+    }
+
+    /// Chance a bid `distance` below the mid fills within `horizon`; `None`
+    /// past the fitted distances or with too few samples
+    pub fn probability(&self, distance: Decimal, horizon: Duration) -> Option<Decimal> {
+        let ticks = (distance.max(Decimal::ZERO) / self.tick_size).ceil().to_usize()?;
+        let row = self.cells.get(ticks)?;
+        let secs = horizon.num_seconds();
+        if secs <= 0 {
+            return Some(Decimal::ZERO);
+        }
+
+        let rate = |h: usize| row[h].rate(self.min_samples);
+        let probability = match HORIZONS_SECS.iter().position(|h| *h >= secs) {
+            // From nothing at no time to the first fitted horizon
+            Some(0) => rate(0)? * secs as f64 / HORIZONS_SECS[0] as f64,
+            Some(h) => {
+                let (from, to) = (HORIZONS_SECS[h - 1] as f64, HORIZONS_SECS[h] as f64);
+                let weight = (secs as f64 - from) / (to - from);
+                rate(h - 1)? * (1.0 - weight) + rate(h)? * weight
+            }
+            // No more than over the longest fitted horizon
+            None => rate(HORIZONS_SECS.len() - 1)?,
+        };
+
+        numeric::to_decimal(probability).ok().map(|p| p.round_dp(4))
+    }
+}
+
+/// The latest fitted model, shared by the engine and its refit task
+pub type SharedFillModel = Arc<RwLock<Option<Arc<FillModel>>>>;
+
+/// Wait before retrying a refit that failed
+const RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Refits the shared fill model on its own task, off the execution loop
+#[derive(Clone)]
+pub struct FillModelRefit {
+    storage: Arc<dyn Storage>,
+    config: FillModelConfig,
+    tick_size: Decimal,
+    model: SharedFillModel,
+}
+
+impl FillModelRefit {
+    pub fn new(storage: Arc<dyn Storage>, config: FillModelConfig, tick_size: Decimal, model: SharedFillModel) -> Self {
+        Self { storage, config, tick_size, model }
+    }
+
+    /// Fit now, then every `refit_hours`
+    pub async fn run(self) {
+        loop {
+            let wait = match self.refit().await {
+                Ok(()) => std::time::Duration::from_secs(self.config.refit_hours.max(1) * 3_600),
+                Err(e) => {
+                    error!("Failed to fit the fill model: {}", e);
+                    RETRY_DELAY
+                }
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Fit from the price history of events started in the last `lookback_days`
+    pub async fn refit(&self) -> Result<()> {
+        let since = Utc::now() - Duration::days(self.config.lookback_days as i64);
+        let history = self.storage.fetch_pre_event_price_history(since).await?;
+
+        let (tick_size, max_ticks, min_samples) = (self.tick_size, self.config.max_ticks, self.config.min_samples);
+        let model = tokio::task::spawn_blocking(move || {
+            FillModel::fit(&history.points, &history.event_times, tick_size, max_ticks, min_samples)
+        })
+        .await?;
+
+        info!("📈 Fill model fitted on {} markets ({} samples)", model.markets(), model.samples());
+        *self.model.write().unwrap() = Some(Arc::new(model));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MarketRepo;
+    use crate::types::Price;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn fills_fall_off_with_distance_and_grow_with_time() {
+        let start = Utc::now() - Duration::days(1);
+        // A minute-by-minute path swinging two ticks either side of 0.50
+        let points: Vec<PricePoint> = (0..600)
+            .map(|i| PricePoint {
+                market_id: "0xmarket".to_string(),
+                yes_price: Price::new(dec!(0.50) + Decimal::from([0, 1, 2, 1, 0, -1, -2, -1][i % 8]) * dec!(0.01)).unwrap(),
+                observed_at: start + Duration::minutes(i as i64),
+            })
+            .collect();
+        let event_times = HashMap::from([("0xmarket".to_string(), start + Duration::minutes(600))]);
+
+        let model = FillModel::fit(&points, &event_times, dec!(0.01), 5, 50);
+        assert_eq!(model.markets(), 1);
+
+        // Over 15 minutes the path bottoms at 0.48 and tops at 0.52, so a bid
+        // one tick below the mid fills from 5 of the 8 starting prices a side,
+        // and one four ticks below never does
+        assert_eq!(model.probability(dec!(0.01), Duration::minutes(15)), Some(dec!(0.625)));
+        assert_eq!(model.probability(dec!(0.04), Duration::minutes(15)), Some(dec!(0)));
+        // A minute is too short to trade two ticks through
+        assert_eq!(model.probability(dec!(0.01), Duration::minutes(1)), Some(dec!(0)));
+        assert!(model.probability(dec!(0.01), Duration::minutes(5)).unwrap() > Decimal::ZERO);
+        // Past the fitted distances, and on too few samples
+        assert_eq!(model.probability(dec!(0.06), Duration::minutes(15)), None);
+        let sparse = FillModel::fit(&points, &event_times, dec!(0.01), 5, 100_000);
+        assert_eq!(sparse.probability(dec!(0.01), Duration::minutes(15)), None);
+    }
+
+    #[tokio::test]
+    async fn refits_from_history_joined_with_event_times() {
+        let storage = Arc::new(crate::storage::MemoryStorage::new());
+        let mut market = crate::test_support::market_fixture("0xstarted", dec!(0.50));
+        market.event_time = Utc::now() - Duration::hours(1);
+        storage.upsert_market(&market).await.unwrap();
+        let upcoming = crate::test_support::market_fixture("0xupcoming", dec!(0.50));
+        storage.upsert_market(&upcoming).await.unwrap();
+        let path = |market_id: &str| (0..120).map(|i| PricePoint {
+            market_id: market_id.to_string(),
+            yes_price: Price::new(dec!(0.50) + Decimal::from([0, 1, 0, -1][i % 4]) * dec!(0.01)).unwrap(),
+            observed_at: market.event_time - Duration::minutes(120 - i as i64),
+        }).collect::<Vec<_>>();
+        storage.insert_price_history(&path("0xstarted")).await.unwrap();
+        storage.insert_price_history(&path("0xupcoming")).await.unwrap();
+
+        let model = SharedFillModel::default();
+        let config = FillModelConfig { min_samples: 1, ..FillModelConfig::default() };
+        FillModelRefit::new(storage, config, dec!(0.01), model.clone()).refit().await.unwrap();
+
+        // Only the market whose event has started is fitted
+        let fitted = model.read().unwrap().clone().unwrap();
+        assert_eq!(fitted.markets(), 1);
+        assert!(fitted.probability(dec!(0.01), Duration::minutes(5)).is_some());
+    }
+}
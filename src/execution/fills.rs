@@ -0,0 +1,468 @@
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use ethers::abi::{decode, ParamType};
+use ethers::prelude::*;
+use futures::stream::{select_all, StreamExt};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+use tokio::sync::watch;
+use tokio::time::{sleep, timeout, Duration};
+use tracing::{error, info, warn};
+
+use crate::config::Config;
+use crate::events::{Event, EventBus, OrderUpdate};
+use crate::monitoring::{IncidentManager, Notifier};
+use crate::storage::Storage;
+use crate::types::{FillSide, OnchainFill, Trade, TradeStatus};
+use super::blockchain::{shares_from_chain, usdc_from_chain};
+use super::executor::Fill;
+use super::rpc::RpcFailover;
+
+const ORDER_FILLED: &str = "OrderFilled(bytes32,address,address,uint256,uint256,uint256,uint256,uint256)";
+const TRANSFER_SINGLE: &str = "TransferSingle(address,address,address,uint256,uint256)";
+
+/// Quiet time after which the logs received for the latest block are processed
+const BLOCK_SETTLE: Duration = Duration::from_secs(3);
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Our side of an `OrderFilled` event, `None` if the wallet is on neither side
+///
+/// Asset id 0 is USDC. The maker gives its maker asset and the taker gives
+/// the taker asset, so which side bought shares depends on who holds USDC.
+pub fn decode_order_filled(log: &Log, wallet: Address) -> Option<OnchainFill> {
+    if log.topics.len() < 4 {
+        return None;
+    }
+    let maker = Address::from(log.topics[2]);
+    let taker = Address::from(log.topics[3]);
+
+    let words: Vec<U256> = decode(&vec![ParamType::Uint(256); 5], &log.data).ok()?
+        .into_iter()
+        .map(|token| token.into_uint())
+        .collect::<Option<_>>()?;
+    let (maker_asset, taker_asset, maker_amount, taker_amount, fee) = (words[0], words[1], words[2], words[3], words[4]);
+
+    let (side, token_id, shares, usdc) = match (maker == wallet, taker == wallet, maker_asset.is_zero()) {
+        (true, _, true) => (FillSide::Buy, taker_asset, taker_amount, maker_amount),
+        (true, _, false) => (FillSide::Sell, maker_asset, maker_amount, taker_amount),
+        (false, true, true) => (FillSide::Sell, taker_asset, taker_amount, maker_amount),
+        (false, true, false) => (FillSide::Buy, maker_asset, maker_amount, taker_amount),
+        (false, false, _) => return None,
+    };
+    let shares = shares_from_chain(shares).ok()?.value();
+    if shares <= Decimal::ZERO {
+        return None;
+    }
+
+    Some(OnchainFill {
+        tx_hash: format!("{:?}", log.transaction_hash?),
+        log_index: log.log_index?.as_u64() as i64,
+        order_hash: format!("{:?}", log.topics[1]),
+        token_id: token_id.to_string(),
+        side,
+        shares,
+        price: usdc_from_chain(usdc).ok()?.value() / shares,
+        fee: usdc_from_chain(fee).ok()?,
+        block_number: log.block_number?.as_u64() as i64,
+        observed_at: Utc::now(),
+    })
+}
+
+/// (tx hash, token id, shares into the wallet) of a `TransferSingle` event
+fn decode_transfer(log: &Log, wallet: Address) -> Option<(String, String, Decimal)> {
+    if log.topics.len() < 4 {
+        return None;
+    }
+    let words: Vec<U256> = decode(&vec![ParamType::Uint(256); 2], &log.data).ok()?
+        .into_iter()
+        .map(|token| token.into_uint())
+        .collect::<Option<_>>()?;
+    let value = shares_from_chain(words[1]).ok()?.value();
+
+    let delta = if Address::from(log.topics[3]) == wallet {
+        value
+    } else if Address::from(log.topics[2]) == wallet {
+        -value
+    } else {
+        return None;
+    };
+    Some((format!("{:?}", log.transaction_hash?), words[0].to_string(), delta))
+}
+
+/// A transaction whose fills disagree with the outcome tokens that moved
+#[derive(Debug, Clone, PartialEq)]
+pub struct FillMismatch {
+    pub tx_hash: String,
+    pub token_id: String,
+    /// Net shares into the wallet by the fills
+    pub filled: Decimal,
+    /// Net shares into the wallet by the transfers
+    pub moved: Decimal,
+}
+
+impl std::fmt::Display for FillMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "tx {} filled {} shares of token {} but moved {}", self.tx_hash, self.filled, self.token_id, self.moved)
+    }
+}
+
+/// Fills in `logs`, plus every transaction whose fills disagree with the
+/// outcome tokens that actually moved in or out of the wallet
+///
+/// Transfers without fills (redemptions, splits, merges) are not trades and
+/// are left alone.
+pub fn reconcile(logs: &[Log], wallet: Address, order_filled: H256) -> (Vec<OnchainFill>, Vec<FillMismatch>) {
+    let mut fills = Vec::new();
+    // Net shares into the wallet per (tx, token): (from fills, from transfers)
+    let mut net: BTreeMap<(String, String), (Option<Decimal>, Option<Decimal>)> = BTreeMap::new();
+
+    for log in logs {
+        if log.topics.first() == Some(&order_filled) {
+            let Some(fill) = decode_order_filled(log, wallet) else {
+                continue;
+            };
+            let shares = match fill.side {
+                FillSide::Buy => fill.shares,
+                FillSide::Sell => -fill.shares,
+            };
+            *net.entry((fill.tx_hash.clone(), fill.token_id.clone())).or_default().0.get_or_insert(Decimal::ZERO) += shares;
+            fills.push(fill);
+        } else if let Some((tx, token, delta)) = decode_transfer(log, wallet) {
+            *net.entry((tx, token)).or_default().1.get_or_insert(Decimal::ZERO) += delta;
+        }
+    }
+
+    let mismatches = net.into_iter()
+        .filter_map(|((tx, token), (filled, moved))| {
+            let filled = filled?;
+            let moved = moved.unwrap_or_default();
+            (filled != moved).then(|| FillMismatch { tx_hash: tx, token_id: token, filled, moved })
+        })
+        .collect();
+
+    (fills, mismatches)
+}
+
+/// The fill of an order once its on-chain fills cover `quantity`, at their
+/// volume-weighted price
+pub fn completed_fill(fills: &[OnchainFill], quantity: Decimal) -> Option<Fill> {
+    let shares: Decimal = fills.iter().map(|f| f.shares).sum();
+    if shares <= Decimal::ZERO || shares < quantity {
+        return None;
+    }
+    let cost: Decimal = fills.iter().map(|f| f.shares * f.price).sum();
+
+    Some(Fill {
+        tx_hash: fills.last()?.tx_hash.clone(),
+        price: cost / shares,
+        quantity: shares,
+    })
+}
+
+/// What an entry transaction bought on chain, at the volume-weighted price
+/// of its buy fills
+pub fn entry_fill(fills: &[OnchainFill]) -> Option<Fill> {
+    let buys: Vec<OnchainFill> = fills.iter().filter(|f| f.side == FillSide::Buy).cloned().collect();
+    completed_fill(&buys, Decimal::ZERO)
+}
+
+/// `trade` entered at what `actual` filled, `None` if it already was or
+/// the trade has closed
+///
+/// Slippage moves with the price, as it is measured from the signal's.
+pub fn corrected_entry(trade: &Trade, actual: &Fill) -> Option<Trade> {
+    if trade.status != TradeStatus::Open
+        || (trade.entry_price == actual.price && trade.quantity == actual.quantity)
+    {
+        return None;
+    }
+    Some(Trade {
+        entry_price: actual.price,
+        quantity: actual.quantity,
+        slippage: trade.slippage.map(|s| s + actual.price - trade.entry_price),
+        ..trade.clone()
+    })
+}
+
+/// Records CTF Exchange fills of our orders from the chain
+///
+/// Subscribes over the WS provider to `OrderFilled` events with our wallet
+/// as maker or taker, and to outcome token `TransferSingle` events into or
+/// out of it. Logs are processed a block at a time so each transaction's
+/// fills can be checked against the tokens that moved; after a reconnect
+/// the blocks missed are fetched before subscribing again. A trade entered
+/// by a transaction is corrected to the price and shares it actually
+/// filled; the engine does the same when it records a trade whose fills
+/// were seen first.
+pub struct FillListener {
+    storage: Arc<dyn Storage>,
+    events: EventBus,
+    notifier: Notifier,
+    incidents: IncidentManager,
+    rpc: Arc<RpcFailover<Ws>>,
+    exchange: Address,
+    ctf: Address,
+    wallet: Address,
+}
+
+impl FillListener {
+    pub fn new(
+        storage: Arc<dyn Storage>,
+        events: EventBus,
+        rpc: Arc<RpcFailover<Ws>>,
+        config: &Config,
+        notifier: Notifier,
+    ) -> Result<Self> {
+        let chain = config.chain()?;
+
+        Ok(Self {
+            storage,
+            events,
+            notifier,
+            incidents: IncidentManager::new(&config.monitoring.incidents),
+            rpc,
+            exchange: chain.ctf_exchange,
+            ctf: chain.conditional_tokens,
+            wallet: config.blockchain.private_key
+                .parse::<LocalWallet>()
+                .context("blockchain.private_key")?
+                .address(),
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let mut last_block: Option<U64> = None;
+
+        info!("🧾 Fill listener started for {:?}", self.wallet);
+
+        loop {
+            // Taken before connecting, so a failover while subscribing is seen
+            let mut changes = self.rpc.changes();
+            match self.rpc.provider().await {
+                Ok(provider) => {
+                    if let Err(e) = self.listen(&provider, &mut changes, &mut last_block).await {
+                        error!("Fill subscription dropped: {}", e);
+                        self.rpc.suspect(&provider).await;
+                    }
+                }
+                Err(e) => error!("Fill listener cannot connect: {}", e),
+            }
+            sleep(RECONNECT_DELAY).await;
+        }
+    }
+
+    fn filters(&self) -> Vec<Filter> {
+        let wallet = H256::from(self.wallet);
+        let filled = Filter::new().address(self.exchange).event(ORDER_FILLED);
+        let transfers = Filter::new().address(self.ctf).event(TRANSFER_SINGLE);
+
+        vec![
+            filled.clone().topic2(wallet),
+            filled.topic3(wallet),
+            transfers.clone().topic2(wallet),
+            transfers.topic3(wallet),
+        ]
+    }
+
+    /// Follow our fills on `provider` until the subscription ends or the
+    /// endpoint in use changes
+    async fn listen(
+        &self,
+        provider: &Provider<Ws>,
+        changes: &mut watch::Receiver<u64>,
+        last_block: &mut Option<U64>,
+    ) -> Result<()> {
+        // Catch up on blocks missed while disconnected
+        let head = provider.get_block_number().await?;
+        if let Some(last) = *last_block {
+            if head > last {
+                let mut missed = Vec::new();
+                for filter in self.filters() {
+                    missed.extend(provider.get_logs(&filter.from_block(last + 1).to_block(head)).await?);
+                }
+                self.process(&missed).await?;
+            }
+        }
+        *last_block = Some(head);
+
+        let mut streams = Vec::new();
+        for filter in self.filters() {
+            streams.push(provider.subscribe_logs(&filter).await?.boxed());
+        }
+        let mut logs = select_all(streams);
+
+        let mut pending: BTreeMap<U64, Vec<Log>> = BTreeMap::new();
+        loop {
+            let settling = !pending.is_empty();
+            let wait = async {
+                if settling {
+                    timeout(BLOCK_SETTLE, logs.next()).await.ok()
+                } else {
+                    Some(logs.next().await)
+                }
+            };
+            let next = tokio::select! {
+                _ = changes.changed() => {
+                    self.flush(&mut pending, None, last_block).await?;
+                    bail!("RPC endpoint replaced, resubscribing");
+                }
+                next = wait => match next {
+                    Some(next) => next,
+                    None => {
+                        self.flush(&mut pending, None, last_block).await?;
+                        continue;
+                    }
+                },
+            };
+
+            let Some(log) = next else {
+                self.flush(&mut pending, None, last_block).await?;
+                bail!("log subscription ended");
+            };
+            // Reorged out; the replacement block's logs arrive separately
+            if log.removed == Some(true) {
+                continue;
+            }
+            let Some(block) = log.block_number else {
+                continue;
+            };
+
+            // Earlier blocks are complete once a later one shows up
+            self.flush(&mut pending, Some(block), last_block).await?;
+            pending.entry(block).or_default().push(log);
+        }
+    }
+
+    /// Process buffered blocks before `before`, or all of them
+    async fn flush(
+        &self,
+        pending: &mut BTreeMap<U64, Vec<Log>>,
+        before: Option<U64>,
+        last_block: &mut Option<U64>,
+    ) -> Result<()> {
+        let ready: Vec<U64> = pending.keys()
+            .copied()
+            .filter(|b| before.map_or(true, |before| *b < before))
+            .collect();
+
+        for block in ready {
+            if let Some(logs) = pending.remove(&block) {
+                self.process(&logs).await?;
+                *last_block = Some(last_block.map_or(block, |last| last.max(block)));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn process(&self, logs: &[Log]) -> Result<()> {
+        let (fills, mismatches) = reconcile(logs, self.wallet, H256::from(ethers::utils::keccak256(ORDER_FILLED)));
+
+        let mut transactions = BTreeSet::new();
+        for fill in fills {
+            if self.storage.insert_onchain_fill(&fill).await? {
+                transactions.insert(fill.tx_hash.clone());
+                info!(
+                    "🧾 {} {} shares of {} at {} (order {})",
+                    fill.side.as_str(), fill.shares, fill.token_id, fill.price.round_dp(4), fill.order_hash
+                );
+                self.events.publish(Event::OrderFilled(fill)).await;
+            }
+        }
+
+        for tx_hash in transactions {
+            self.correct_entry(&tx_hash).await?;
+        }
+
+        // Left open for an operator; a transaction's tokens do not move again
+        for mismatch in mismatches {
+            warn!("🧾 Fill/transfer mismatch: {}", mismatch);
+            self.notifier.critical(format!("🧾 Fill/transfer mismatch: {}", mismatch));
+            let key = format!("fill-mismatch-{}", mismatch.tx_hash);
+            self.incidents.update(&key, Some(format!("Fill/transfer mismatch: {}", mismatch))).await;
+        }
+
+        Ok(())
+    }
+
+    /// Correct the trade entered by `tx_hash` to what the chain says it
+    /// filled; transactions the engine has not recorded yet are left to it
+    async fn correct_entry(&self, tx_hash: &str) -> Result<()> {
+        let Some(trade) = self.storage.fetch_trade_by_entry_tx(tx_hash).await? else {
+            return Ok(());
+        };
+        let fills = self.storage.fetch_tx_fills(tx_hash).await?;
+        let Some(corrected) = entry_fill(&fills).and_then(|actual| corrected_entry(&trade, &actual)) else {
+            return Ok(());
+        };
+
+        self.storage.correct_trade_entry(
+            corrected.trade_id,
+            corrected.entry_price,
+            corrected.quantity,
+            corrected.slippage,
+        ).await?;
+        warn!(
+            "🧾 Trade {} filled {} shares at {} on chain, recorded as {} at {}",
+            trade.trade_id,
+            corrected.quantity,
+            corrected.entry_price.round_dp(4),
+            trade.quantity,
+            trade.entry_price,
+        );
+        self.events.publish(Event::OrderUpdated { update: OrderUpdate::Corrected, trade: Box::new(corrected) }).await;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::abi::{encode, Token};
+    use rust_decimal_macros::dec;
+
+    fn log(topics: Vec<H256>, words: &[u64], tx: u64, index: u64) -> Log {
+        Log {
+            topics,
+            data: encode(&words.iter().map(|w| Token::Uint(U256::from(*w))).collect::<Vec<_>>()).into(),
+            transaction_hash: Some(H256::from_low_u64_be(tx)),
+            log_index: Some(U256::from(index)),
+            block_number: Some(U64::from(100)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reads_our_side_of_fills_and_checks_them_against_transfers() {
+        let wallet = Address::from_low_u64_be(7);
+        let other = H256::from(Address::from_low_u64_be(9));
+        let us = H256::from(wallet);
+        let filled = H256::from(ethers::utils::keccak256(ORDER_FILLED));
+        let transfer = H256::from(ethers::utils::keccak256(TRANSFER_SINGLE));
+        let order = H256::from_low_u64_be(1);
+
+        // Our maker order paid 40 USDC for 100 shares of token 55
+        let buy = log(vec![filled, order, us, other], &[0, 55, 40_000_000, 100_000_000, 0], 1, 0);
+        let received = log(vec![transfer, other, other, us], &[55, 100_000_000], 1, 1);
+        // Filled as taker selling 50 shares, but only 30 left the wallet
+        let sell = log(vec![filled, order, other, us], &[0, 55, 30_000_000, 50_000_000, 0], 2, 0);
+        let sent = log(vec![transfer, other, us, other], &[55, 30_000_000], 2, 1);
+
+        let (fills, mismatches) = reconcile(&[buy, received, sell, sent], wallet, filled);
+        assert_eq!(fills.len(), 2);
+        assert_eq!((fills[0].side, fills[0].shares, fills[0].price), (FillSide::Buy, dec!(100), dec!(0.4)));
+        assert_eq!((fills[1].side, fills[1].shares, fills[1].price), (FillSide::Sell, dec!(50), dec!(0.6)));
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!((mismatches[0].filled, mismatches[0].moved), (dec!(-50), dec!(-30)));
+
+        assert!(completed_fill(&fills[..1], dec!(120)).is_none());
+        assert_eq!(completed_fill(&fills[..1], dec!(100)).unwrap().price, dec!(0.4));
+
+        // An entry is what its transaction bought, not what it sold
+        let entry = entry_fill(&fills).unwrap();
+        assert_eq!((entry.quantity, entry.price), (dec!(100), dec!(0.4)));
+    }
+}
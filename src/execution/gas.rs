@@ -0,0 +1,291 @@
+use anyhow::{anyhow, bail, Context, Result};
+use ethers::prelude::*;
+use std::fmt;
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+use tracing::warn;
+
+use crate::config::BlockchainConfig;
+use crate::types::GasUrgency;
+
+/// Wei per gwei
+const GWEI: u64 = 1_000_000_000;
+
+/// Receipts of sent transactions are polled this often while they wait
+const RECEIPT_POLL: Duration = Duration::from_secs(2);
+
+/// Fees of an EIP-1559 transaction, per gas
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Eip1559Fees {
+    /// Base fee of the next block, when the bid was made
+    pub base_fee: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub max_fee_per_gas: U256,
+}
+
+impl Eip1559Fees {
+    /// Price per gas paid if mined in the next block
+    pub fn expected_gas_price(&self) -> U256 {
+        (self.base_fee + self.max_priority_fee_per_gas).min(self.max_fee_per_gas)
+    }
+}
+
+/// Bids EIP-1559 fees from recent blocks' base fees and tips, and replaces
+/// transactions that sit unmined with higher bids on the same nonce
+pub struct FeeEstimator {
+    history_blocks: u64,
+    /// Low, normal and high, the order `eth_feeHistory` is asked for them in
+    percentiles: [f64; 3],
+    min_priority_fee: U256,
+    /// `base_fee_multiplier` in thousandths
+    base_fee_permille: U256,
+    max_fee: U256,
+    replace_after: Duration,
+    /// `replacement_bump_pct` in thousandths
+    bump_permille: U256,
+    max_replacements: u32,
+}
+
+impl FeeEstimator {
+    pub fn from_config(config: &BlockchainConfig) -> Result<Self> {
+        let fees = &config.fees;
+        let percentiles = [
+            fees.priority_fee_percentiles.get(GasUrgency::Low),
+            fees.priority_fee_percentiles.get(GasUrgency::Normal),
+            fees.priority_fee_percentiles.get(GasUrgency::High),
+        ];
+        if !percentiles.iter().all(|p| (0.0..=100.0).contains(p)) || !percentiles.windows(2).all(|w| w[0] <= w[1]) {
+            bail!("blockchain.fees.priority_fee_percentiles must rise from low to high within [0, 100]");
+        }
+        if fees.history_blocks == 0 {
+            bail!("blockchain.fees.history_blocks must be positive");
+        }
+        if !(fees.min_priority_fee_gwei >= 0.0) {
+            bail!("blockchain.fees.min_priority_fee_gwei must not be negative");
+        }
+        if !(fees.base_fee_multiplier >= 1.0) {
+            bail!("blockchain.fees.base_fee_multiplier must be at least 1");
+        }
+        // Nodes only accept a replacement that raises both fees by 10%
+        if !(fees.replacement_bump_pct >= 10.0) {
+            bail!("blockchain.fees.replacement_bump_pct must be at least 10");
+        }
+
+        Ok(Self {
+            history_blocks: fees.history_blocks,
+            percentiles,
+            min_priority_fee: U256::from((fees.min_priority_fee_gwei * GWEI as f64).round() as u64),
+            base_fee_permille: U256::from((fees.base_fee_multiplier * 1000.0).round() as u64),
+            max_fee: U256::from(config.max_gas_price_gwei) * U256::from(GWEI),
+            replace_after: Duration::from_secs(fees.replace_after_secs),
+            bump_permille: U256::from((fees.replacement_bump_pct * 10.0).ceil() as u64),
+            max_replacements: fees.max_replacements,
+        })
+    }
+
+    fn percentile_index(urgency: GasUrgency) -> usize {
+        match urgency {
+            GasUrgency::Low => 0,
+            GasUrgency::Normal => 1,
+            GasUrgency::High => 2,
+        }
+    }
+
+    /// Fees to bid at `urgency` given the chain's recent fee history
+    ///
+    /// The tip is the median over the history's blocks of the urgency's tip
+    /// percentile, raised to the minimum tip. Fails when the next base fee and
+    /// that tip already cost more than the max gas price.
+    pub fn estimate(&self, history: &FeeHistory, urgency: GasUrgency) -> Result<Eip1559Fees> {
+        let base_fee = *history.base_fee_per_gas.last().context("fee history has no base fee")?;
+
+        let index = Self::percentile_index(urgency);
+        let mut tips: Vec<U256> = history.reward.iter().filter_map(|block| block.get(index).copied()).collect();
+        tips.sort();
+        let tip = tips.get(tips.len() / 2).copied().unwrap_or_default().max(self.min_priority_fee);
+
+        if base_fee + tip > self.max_fee {
+            bail!(
+                "base fee {} gwei plus {} gwei {} tip is over blockchain.max_gas_price_gwei",
+                base_fee / GWEI, tip / GWEI, urgency.as_str()
+            );
+        }
+        let max_fee = (base_fee * self.base_fee_permille / 1000 + tip).min(self.max_fee);
+
+        Ok(Eip1559Fees { base_fee, max_priority_fee_per_gas: tip, max_fee_per_gas: max_fee })
+    }
+
+    /// Fees of a replacement for a transaction sent at `fees`, or `None` when
+    /// the raise would go over the max gas price
+    pub fn bump(&self, fees: &Eip1559Fees) -> Option<Eip1559Fees> {
+        let raise = |fee: U256| (fee * (U256::from(1000) + self.bump_permille) + 999) / 1000;
+        let max_fee = raise(fees.max_fee_per_gas);
+        if max_fee > self.max_fee {
+            return None;
+        }
+
+        Some(Eip1559Fees {
+            base_fee: fees.base_fee,
+            max_priority_fee_per_gas: raise(fees.max_priority_fee_per_gas),
+            max_fee_per_gas: max_fee,
+        })
+    }
+
+    /// Current fees to bid at `urgency`
+    pub async fn fees<M: Middleware + 'static>(&self, client: &M, urgency: GasUrgency) -> Result<Eip1559Fees> {
+        let history = client.fee_history(self.history_blocks, BlockNumber::Latest, &self.percentiles).await?;
+        self.estimate(&history, urgency)
+    }
+
+    /// Send `tx` at `urgency` and wait for it to be mined, resending it on
+    /// the same nonce with raised fees each time it waits `replace_after_secs`
+    ///
+    /// Whichever of the sent versions is mined first is the receipt returned.
+    pub async fn send<M: Middleware + 'static>(
+        &self,
+        client: &SignerMiddleware<M, LocalWallet>,
+        tx: Eip1559TransactionRequest,
+        urgency: GasUrgency,
+    ) -> Result<TransactionReceipt> {
+        let mut fees = self.fees(client, urgency).await?;
+        let nonce = client.get_transaction_count(client.address(), Some(BlockNumber::Pending.into())).await?;
+        let tx = tx.from(client.address()).nonce(nonce);
+        let mut sent: Vec<TxHash> = Vec::new();
+
+        loop {
+            let bid = tx.clone()
+                .max_priority_fee_per_gas(fees.max_priority_fee_per_gas)
+                .max_fee_per_gas(fees.max_fee_per_gas);
+            match client.send_transaction(bid, None).await {
+                Ok(pending) => sent.push(*pending),
+                Err(e) if sent.is_empty() => return Err(e.into()),
+                // A replacement is refused once an earlier version is mined
+                Err(e) => return after_broadcast(client, &sent, anyhow!(e).context("replacing")).await,
+            }
+
+            let deadline = Instant::now() + self.replace_after;
+            while Instant::now() < deadline {
+                for hash in &sent {
+                    match client.get_transaction_receipt(*hash).await {
+                        Ok(Some(receipt)) => return Ok(receipt),
+                        Ok(None) => {}
+                        Err(e) => warn!("Receipt of {:?} not read: {}", hash, e),
+                    }
+                }
+                sleep(RECEIPT_POLL).await;
+            }
+
+            let replacements = sent.len() as u32 - 1;
+            let bumped = match self.bump(&fees) {
+                Some(bumped) if replacements < self.max_replacements => bumped,
+                _ => {
+                    let error = anyhow!("nonce {} not mined after {} replacements", nonce, replacements);
+                    return after_broadcast(client, &sent, error).await;
+                }
+            };
+            warn!(
+                "Transaction {:?} not mined in {}s, replacing with a {} gwei tip",
+                sent.last().copied().unwrap_or_default(),
+                self.replace_after.as_secs(),
+                bumped.max_priority_fee_per_gas / GWEI
+            );
+            fees = bumped;
+        }
+    }
+}
+
+/// A failure once a transaction has gone out: one of its versions may still
+/// be mined, so sending it again could fill twice and it is not retried
+#[derive(Debug)]
+pub struct Broadcast {
+    pub sent: Vec<TxHash>,
+}
+
+impl fmt::Display for Broadcast {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "after broadcasting {:?}", self.sent)
+    }
+}
+
+/// The receipt of whichever of `sent` was mined, else `error` marked as
+/// coming after their broadcast
+async fn after_broadcast<M: Middleware>(client: &M, sent: &[TxHash], error: anyhow::Error) -> Result<TransactionReceipt> {
+    for hash in sent {
+        if let Ok(Some(receipt)) = client.get_transaction_receipt(*hash).await {
+            return Ok(receipt);
+        }
+    }
+    Err(error.context(Broadcast { sent: sent.to_vec() }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn gwei(g: u64) -> U256 {
+        U256::from(g) * U256::from(GWEI)
+    }
+
+    fn history(next_base_fee: u64, tips: &[[u64; 3]]) -> FeeHistory {
+        FeeHistory {
+            base_fee_per_gas: vec![gwei(next_base_fee); tips.len() + 1],
+            gas_used_ratio: vec![0.5; tips.len()],
+            oldest_block: U256::zero(),
+            reward: tips.iter().map(|block| block.iter().map(|&t| gwei(t)).collect()).collect(),
+        }
+    }
+
+    #[test]
+    fn urgency_bids_higher_tip_percentiles() {
+        let estimator = FeeEstimator::from_config(&Config::default().blockchain).unwrap();
+        let recent = history(10, &[[31, 40, 60], [32, 45, 80], [35, 50, 70]]);
+
+        let low = estimator.estimate(&recent, GasUrgency::Low).unwrap();
+        let normal = estimator.estimate(&recent, GasUrgency::Normal).unwrap();
+        let high = estimator.estimate(&recent, GasUrgency::High).unwrap();
+        assert_eq!(low.max_priority_fee_per_gas, gwei(32));
+        assert_eq!(normal.max_priority_fee_per_gas, gwei(45));
+        assert_eq!(high.max_priority_fee_per_gas, gwei(70));
+        assert_eq!(high.max_fee_per_gas, gwei(2 * 10 + 70));
+        assert_eq!(high.expected_gas_price(), gwei(80));
+        // Headroom over the base fee stops at the cap
+        assert_eq!(estimator.estimate(&history(40, &[[31, 40, 60]]), GasUrgency::High).unwrap().max_fee_per_gas, gwei(100));
+
+        // Tips below the validators' floor are raised to it
+        let quiet = estimator.estimate(&history(20, &[[1, 1, 2]]), GasUrgency::Low).unwrap();
+        assert_eq!(quiet.max_priority_fee_per_gas, gwei(30));
+
+        // A base fee over the cap cannot be bid for at all
+        assert!(estimator.estimate(&history(90, &[[31, 40, 60]]), GasUrgency::Low).is_err());
+    }
+
+    #[test]
+    fn replacements_raise_both_fees_until_the_cap() {
+        let estimator = FeeEstimator::from_config(&Config::default().blockchain).unwrap();
+        let fees = Eip1559Fees { base_fee: gwei(20), max_priority_fee_per_gas: gwei(40), max_fee_per_gas: gwei(80) };
+
+        let bumped = estimator.bump(&fees).unwrap();
+        assert_eq!(bumped.max_priority_fee_per_gas, gwei(45));
+        assert_eq!(bumped.max_fee_per_gas, gwei(90));
+        assert!(estimator.bump(&bumped).is_none(), "101.25 gwei is over the 100 gwei cap");
+    }
+
+    #[tokio::test]
+    async fn a_failure_after_broadcast_returns_a_mined_version() {
+        let (provider, mock) = Provider::mocked();
+        let (first, second) = (TxHash::repeat_byte(1), TxHash::repeat_byte(2));
+        let mined = TransactionReceipt { transaction_hash: first, status: Some(U64::one()), ..Default::default() };
+        // The replacement was refused because the first version got mined
+        mock.push(mined.clone()).unwrap();
+        mock.push::<Option<TransactionReceipt>, _>(None).unwrap();
+
+        let receipt = after_broadcast(&provider, &[second, first], anyhow!("nonce too low")).await.unwrap();
+        assert_eq!(receipt.transaction_hash, first);
+
+        // Neither mined: the error is marked so the order is not resent
+        mock.push::<Option<TransactionReceipt>, _>(None).unwrap();
+        let error = after_broadcast(&provider, &[first], anyhow!("nonce too low")).await.unwrap_err();
+        assert_eq!(error.downcast_ref::<Broadcast>().unwrap().sent, vec![first]);
+    }
+}
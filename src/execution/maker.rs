@@ -0,0 +1,209 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+use crate::config::{MakerConfig, MicrostructureConfig};
+use crate::numeric;
+use crate::types::{MarketMicrostructure, Position, Price};
+use super::fill_model::FillModel;
+
+/// How an entry goes to the book
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntryExecution {
+    /// Cross the spread now
+    Take,
+    /// Rest a bid at `price` until `expires_at`
+    Post {
+        price: Price,
+        fill_probability: Decimal,
+        /// Edge per share if it fills, after maker fees and gas
+        maker_edge: Decimal,
+        expires_at: DateTime<Utc>,
+    },
+}
+
+/// What the maker/taker choice for one entry is based on
+pub struct EntryQuote<'a> {
+    pub position: Position,
+    pub fair_value: Decimal,
+    /// Best bid of the outcome bought
+    pub bid: Price,
+    /// Price a taker pays, the signal's executable price
+    pub ask: Price,
+    /// Edge per share crossing now, after taker fees and gas
+    pub taker_edge: Decimal,
+    pub quantity: Decimal,
+    pub maker_fee_rate: Decimal,
+    pub gas_per_share: Decimal,
+    pub features: Option<&'a MarketMicrostructure>,
+    pub event_time: DateTime<Utc>,
+    /// When the signal stops being executable
+    pub signal_deadline: DateTime<Utc>,
+}
+
+/// Decides per entry whether to take or rest one tick inside the spread
+///
+/// Resting earns the spread but may not fill. Its chance of filling comes
+/// from the fill model, by the bid's distance below the mid and the time it
+/// can rest. Where the model has too little history, it is estimated from
+/// the recent taker volume that would trade against the bid: taker sells
+/// of YES for a YES bid, taker buys of YES for a NO bid, treated as a
+/// Poisson stream against the order's size. Entries post when that chance
+/// times the maker edge beats taking by `min_edge_improvement`. They always
+/// take when the taker edge is already large or the event is too close.
+pub struct MakerPolicy {
+    max_rest: Duration,
+    cancel_before_start: Duration,
+    min_rest: Duration,
+    take_above_edge: Decimal,
+    min_edge_improvement: Decimal,
+    tick_size: Decimal,
+    flow_window_secs: f64,
+    max_feature_age: Duration,
+}
+
+impl MakerPolicy {
+    /// `None` when maker entries are disabled
+    pub fn from_config(
+        config: &MakerConfig,
+        microstructure: &MicrostructureConfig,
+        tick_size: Decimal,
+    ) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            max_rest: Duration::seconds(config.max_rest_secs as i64),
+            cancel_before_start: Duration::seconds(config.cancel_before_start_secs as i64),
+            min_rest: Duration::seconds(config.min_rest_secs as i64),
+            take_above_edge: numeric::to_decimal(config.take_above_edge)
+                .context("execution.maker.take_above_edge")?,
+            min_edge_improvement: numeric::to_decimal(config.min_edge_improvement)
+                .context("execution.maker.min_edge_improvement")?,
+            tick_size,
+            flow_window_secs: microstructure.flow_window_secs.max(1) as f64,
+            max_feature_age: Duration::seconds(microstructure.max_age_secs as i64),
+        }))
+    }
+
+    pub fn decide(&self, quote: &EntryQuote, fill_model: Option<&FillModel>, now: DateTime<Utc>) -> EntryExecution {
+        if quote.taker_edge >= self.take_above_edge {
+            return EntryExecution::Take;
+        }
+
+        let expires_at = (now + self.max_rest)
+            .min(quote.event_time - self.cancel_before_start)
+            .min(quote.signal_deadline);
+        if expires_at - now < self.min_rest {
+            return EntryExecution::Take;
+        }
+
+        // One tick better than the bid, and still short of the ask
+        let price = quote.bid.value() + self.tick_size;
+        let Ok(price) = Price::new(price) else {
+            return EntryExecution::Take;
+        };
+        if price >= quote.ask {
+            return EntryExecution::Take;
+        }
+
+        let horizon = expires_at - now;
+        let mid = (quote.bid.value() + quote.ask.value()) / Decimal::TWO;
+        let Some(fill_probability) = fill_model
+            .and_then(|model| model.probability(mid - price.value(), horizon))
+            .or_else(|| self.flow_fill_probability(quote, horizon, now))
+        else {
+            return EntryExecution::Take;
+        };
+        let maker_edge = quote.fair_value - price.value() * (Decimal::ONE + quote.maker_fee_rate) - quote.gas_per_share;
+        if fill_probability * maker_edge < quote.taker_edge.max(Decimal::ZERO) + self.min_edge_improvement {
+            return EntryExecution::Take;
+        }
+
+        EntryExecution::Post { price, fill_probability, maker_edge, expires_at }
+    }
+
+    /// Chance the order fills within `horizon` from recent taker flow; `None`
+    /// without recent features
+    fn flow_fill_probability(&self, quote: &EntryQuote, horizon: Duration, now: DateTime<Utc>) -> Option<Decimal> {
+        let features = quote.features.filter(|f| now - f.observed_at <= self.max_feature_age)?;
+        let against_bid = match quote.position {
+            Position::Yes => features.taker_sell_volume,
+            Position::No => features.taker_buy_volume,
+        };
+
+        let rate = against_bid.to_f64()? / self.flow_window_secs;
+        let expected_fills = rate * horizon.num_seconds() as f64 / quote.quantity.to_f64()?.max(f64::EPSILON);
+        numeric::to_decimal(1.0 - (-expected_fills).exp()).ok().map(|p| p.round_dp(4))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn features(taker_sell_volume: Decimal, now: DateTime<Utc>) -> MarketMicrostructure {
+        MarketMicrostructure {
+            market_id: "0xmarket".to_string(),
+            bid_depth: dec!(1000),
+            ask_depth: dec!(1000),
+            imbalance: Decimal::ZERO,
+            bid_depth_change: None,
+            ask_depth_change: None,
+            taker_buy_volume: Decimal::ZERO,
+            taker_sell_volume,
+            observed_at: now,
+        }
+    }
+
+    #[test]
+    fn rests_on_slow_wide_markets_and_takes_large_or_late_edges() {
+        let policy = MakerPolicy {
+            max_rest: Duration::minutes(15),
+            cancel_before_start: Duration::minutes(10),
+            min_rest: Duration::minutes(1),
+            take_above_edge: dec!(0.08),
+            min_edge_improvement: dec!(0.005),
+            tick_size: dec!(0.01),
+            flow_window_secs: 300.0,
+            max_feature_age: Duration::minutes(5),
+        };
+        let now = Utc::now();
+        // 500 shares sold into bids every 5 minutes against a 100 share order
+        let active = features(dec!(500), now);
+        let quote = EntryQuote {
+            position: Position::Yes,
+            fair_value: dec!(0.50),
+            bid: Price::new(dec!(0.40)).unwrap(),
+            ask: Price::new(dec!(0.46)).unwrap(),
+            taker_edge: dec!(0.04),
+            quantity: dec!(100),
+            maker_fee_rate: Decimal::ZERO,
+            gas_per_share: Decimal::ZERO,
+            features: Some(&active),
+            event_time: now + Duration::hours(3),
+            signal_deadline: now + Duration::hours(1),
+        };
+
+        match policy.decide(&quote, None, now) {
+            EntryExecution::Post { price, fill_probability, maker_edge, expires_at } => {
+                assert_eq!(price, Price::new(dec!(0.41)).unwrap());
+                assert_eq!(maker_edge, dec!(0.09));
+                assert!(fill_probability > dec!(0.99));
+                assert_eq!(expires_at, now + Duration::minutes(15));
+            }
+            EntryExecution::Take => panic!("expected a resting entry"),
+        }
+
+        assert_eq!(policy.decide(&EntryQuote { taker_edge: dec!(0.10), ..quote }, None, now), EntryExecution::Take);
+        let late = EntryQuote { event_time: now + Duration::seconds(630), ..quote };
+        assert_eq!(policy.decide(&late, None, now), EntryExecution::Take);
+        let quiet = features(dec!(1), now);
+        assert_eq!(policy.decide(&EntryQuote { features: Some(&quiet), ..quote }, None, now), EntryExecution::Take);
+        let tight = EntryQuote { ask: Price::new(dec!(0.41)).unwrap(), ..quote };
+        assert_eq!(policy.decide(&tight, None, now), EntryExecution::Take);
+    }
+}
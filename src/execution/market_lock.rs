@@ -0,0 +1,116 @@
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::cache::Cache;
+
+/// Per-market execution locks, shared across instances
+///
+/// Signal claims keep two engines off the same signal, but two signals on
+/// one market, or an entry and an exit, can still race. Each market is
+/// locked in the shared cache under the engine's id while its orders go
+/// out, and a lock left by an engine that died lapses after `ttl`. While the
+/// cache is unreachable no market can be locked, so work waits rather than
+/// risk another instance trading the same market.
+pub struct MarketLocks {
+    cache: Arc<dyn Cache>,
+    /// Identifies this engine's locks
+    owner: String,
+    ttl: Duration,
+}
+
+impl MarketLocks {
+    pub fn new(cache: Arc<dyn Cache>, owner: String, ttl: Duration) -> Self {
+        Self { cache, owner, ttl }
+    }
+
+    fn key(market_id: &str) -> String {
+        format!("lock:market:{}", market_id)
+    }
+
+    /// Lock `market_id` for this engine if no one else holds it; never while
+    /// the shared cache is unreachable
+    pub async fn try_lock(&self, market_id: &str) -> bool {
+        match self.cache.try_lock(&Self::key(market_id), &self.owner, self.ttl).await {
+            Ok(locked) => locked,
+            Err(e) => {
+                warn!("Shared market locks unavailable, deferring work on {}: {}", market_id, e);
+                false
+            }
+        }
+    }
+
+    /// Release this engine's lock on `market_id`
+    pub async fn unlock(&self, market_id: &str) {
+        if let Err(e) = self.cache.unlock(&Self::key(market_id), &self.owner).await {
+            warn!("Failed to release lock on market {}, it lapses in {}s: {}", market_id, self.ttl.as_secs(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::InProcessCache;
+    use anyhow::{bail, Result};
+    use async_trait::async_trait;
+    use futures::stream::BoxStream;
+
+    /// A cache whose server is down
+    struct Unreachable;
+
+    #[async_trait]
+    impl Cache for Unreachable {
+        async fn get(&self, _: &str) -> Result<Option<String>> {
+            bail!("connection refused")
+        }
+        async fn set(&self, _: &str, _: &str, _: Duration) -> Result<()> {
+            bail!("connection refused")
+        }
+        async fn delete(&self, _: &str) -> Result<()> {
+            bail!("connection refused")
+        }
+        async fn publish(&self, _: &str, _: &str) -> Result<()> {
+            bail!("connection refused")
+        }
+        async fn subscribe(&self, _: &str) -> Result<BoxStream<'static, String>> {
+            bail!("connection refused")
+        }
+        async fn take_token(&self, _: &str, _: u32, _: f64) -> Result<bool> {
+            bail!("connection refused")
+        }
+        async fn try_lock(&self, _: &str, _: &str, _: Duration) -> Result<bool> {
+            bail!("connection refused")
+        }
+        async fn unlock(&self, _: &str, _: &str) -> Result<()> {
+            bail!("connection refused")
+        }
+    }
+
+    #[tokio::test]
+    async fn a_market_is_held_by_one_engine_at_a_time() {
+        let cache: Arc<dyn Cache> = Arc::new(InProcessCache::new(100));
+        let first = MarketLocks::new(cache.clone(), "engine-a".to_string(), Duration::from_secs(60));
+        let second = MarketLocks::new(cache, "engine-b".to_string(), Duration::from_secs(60));
+
+        assert!(first.try_lock("0xabc").await);
+        assert!(!second.try_lock("0xabc").await);
+        assert!(second.try_lock("0xdef").await);
+
+        // Only the holder's release frees the market
+        second.unlock("0xabc").await;
+        assert!(!second.try_lock("0xabc").await);
+        first.unlock("0xabc").await;
+        assert!(second.try_lock("0xabc").await);
+    }
+
+    #[tokio::test]
+    async fn no_market_is_locked_while_the_cache_is_down() {
+        let cache: Arc<dyn Cache> = Arc::new(Unreachable);
+        let first = MarketLocks::new(cache.clone(), "engine-a".to_string(), Duration::from_secs(60));
+        let second = MarketLocks::new(cache, "engine-b".to_string(), Duration::from_secs(60));
+
+        assert!(!first.try_lock("0xabc").await);
+        assert!(!second.try_lock("0xabc").await);
+    }
+}
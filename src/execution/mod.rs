@@ -1,5 +1,25 @@
 mod engine;
+mod exit;
 mod blockchain;
+mod executor;
+mod fees;
+mod fill_model;
+mod fills;
+mod gas;
+mod maker;
+mod market_lock;
+mod orders;
+mod rate_limit;
+mod redemption;
+mod resting;
+mod retry;
+mod rpc;
+mod slicing;
+mod stops;
 
 pub use engine::ExecutionEngine;
-pub use blockchain::BlockchainClient;
+pub use blockchain::{usdc_from_chain, LiveExecutor};
+pub use executor::{PaperExecutor, RecordingExecutor, TradeExecutor};
+pub use fills::FillListener;
+pub use redemption::{Redeemer, Redemption, RedemptionGas};
+pub use rpc::{ChainRpc, RpcFailover};
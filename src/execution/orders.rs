@@ -0,0 +1,165 @@
+use anyhow::{anyhow, Context, Result};
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+use crate::config::OrderRulesConfig;
+use crate::numeric;
+use crate::types::Price;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+/// An order's price and size once snapped to the venue's increments
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrderSize {
+    pub quantity: Decimal,
+    pub price: Price,
+}
+
+impl OrderSize {
+    pub fn notional(&self) -> Decimal {
+        self.quantity * self.price.value()
+    }
+}
+
+/// Why an order was not sent
+#[derive(Debug, Error, PartialEq)]
+pub enum OrderRejection {
+    #[error("{quantity} shares (${notional}) is below the venue minimum")]
+    Dust { quantity: Decimal, notional: Decimal },
+
+    #[error("price {0} is off the book once rounded to the tick")]
+    PriceOffBook(Decimal),
+}
+
+/// Venue tick, lot and minimum-size constraints
+///
+/// Prices round away from the market, so a buy never pays more and a sell
+/// never takes less than intended, and sizes round down to the lot.
+#[derive(Debug, Clone)]
+pub struct OrderRules {
+    tick_size: Decimal,
+    lot_size: Decimal,
+    min_order_size: Decimal,
+    min_notional: Decimal,
+}
+
+impl OrderRules {
+    pub fn from_config(config: &OrderRulesConfig) -> Result<Self> {
+        let increment = |value: f64, key: &str| -> Result<Decimal> {
+            let value = numeric::to_decimal(value)
+                .with_context(|| format!("execution.order_rules.{}", key))?;
+            if value <= Decimal::ZERO {
+                return Err(anyhow!("execution.order_rules.{} must be positive", key));
+            }
+            Ok(value)
+        };
+
+        Ok(Self {
+            tick_size: increment(config.tick_size, "tick_size")?,
+            lot_size: increment(config.lot_size, "lot_size")?,
+            min_order_size: numeric::to_decimal(config.min_order_size)
+                .context("execution.order_rules.min_order_size")?,
+            min_notional: numeric::to_decimal(config.min_notional)
+                .context("execution.order_rules.min_notional")?,
+        })
+    }
+
+    pub fn tick_size(&self) -> Decimal {
+        self.tick_size
+    }
+
+    /// Limit price on the tick grid, strictly inside the book
+    pub fn snap_price(&self, price: Price, side: Side) -> Result<Price, OrderRejection> {
+        let ticks = price.value() / self.tick_size;
+        let ticks = match side {
+            Side::Buy => ticks.floor(),
+            Side::Sell => ticks.ceil(),
+        };
+        let snapped = ticks * self.tick_size;
+
+        if snapped <= Decimal::ZERO || snapped >= Decimal::ONE {
+            return Err(OrderRejection::PriceOffBook(snapped));
+        }
+        Price::new(snapped).map_err(|_| OrderRejection::PriceOffBook(snapped))
+    }
+
+    /// Shares rounded down to the lot
+    pub fn snap_quantity(&self, quantity: Decimal) -> Decimal {
+        ((quantity / self.lot_size).floor() * self.lot_size).max(Decimal::ZERO)
+    }
+
+    /// A buy spending up to `notional` USDC at no more than `limit`
+    pub fn buy(&self, notional: Decimal, limit: Price) -> Result<OrderSize, OrderRejection> {
+        let price = self.snap_price(limit, Side::Buy)?;
+        self.checked(notional / price.value(), price)
+    }
+
+    /// A sell of `quantity` shares at no less than `limit`
+    pub fn sell(&self, quantity: Decimal, limit: Price) -> Result<OrderSize, OrderRejection> {
+        let price = self.snap_price(limit, Side::Sell)?;
+        self.checked(quantity, price)
+    }
+
+    fn checked(&self, quantity: Decimal, price: Price) -> Result<OrderSize, OrderRejection> {
+        let order = OrderSize { quantity: self.snap_quantity(quantity), price };
+
+        if order.quantity < self.min_order_size || order.notional() < self.min_notional {
+            return Err(OrderRejection::Dust { quantity: order.quantity, notional: order.notional() });
+        }
+        Ok(order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn rules() -> OrderRules {
+        OrderRules {
+            tick_size: dec!(0.01),
+            lot_size: dec!(0.01),
+            min_order_size: dec!(5),
+            min_notional: dec!(1),
+        }
+    }
+
+    fn price(value: Decimal) -> Price {
+        Price::new(value).unwrap()
+    }
+
+    #[test]
+    fn prices_round_away_from_the_market_and_sizes_down_to_the_lot() {
+        let rules = rules();
+
+        let buy = rules.buy(dec!(100), price(dec!(0.4567))).unwrap();
+        assert_eq!(buy.price, price(dec!(0.45)));
+        // 100 / 0.45 = 222.222...
+        assert_eq!(buy.quantity, dec!(222.22));
+        assert!(buy.notional() <= dec!(100));
+
+        let sell = rules.sell(dec!(222.229), price(dec!(0.6012))).unwrap();
+        assert_eq!(sell.price, price(dec!(0.61)));
+        assert_eq!(sell.quantity, dec!(222.22));
+
+        // Already on the grid
+        assert_eq!(rules.snap_price(price(dec!(0.37)), Side::Sell), Ok(price(dec!(0.37))));
+    }
+
+    #[test]
+    fn rejects_dust_and_prices_that_round_off_the_book() {
+        let rules = rules();
+
+        // Enough USDC for only 4 shares
+        assert!(matches!(rules.buy(dec!(2), price(dec!(0.50))), Err(OrderRejection::Dust { .. })));
+        // 10 shares at 5 cents is under a dollar
+        assert!(matches!(rules.sell(dec!(10), price(dec!(0.05))), Err(OrderRejection::Dust { .. })));
+
+        assert_eq!(rules.snap_price(price(dec!(0.004)), Side::Buy), Err(OrderRejection::PriceOffBook(dec!(0))));
+        assert_eq!(rules.snap_price(price(dec!(0.995)), Side::Sell), Err(OrderRejection::PriceOffBook(dec!(1))));
+    }
+}
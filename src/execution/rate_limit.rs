@@ -0,0 +1,99 @@
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+use crate::cache::{Cache, TokenBucket};
+use crate::config::ExecutionConfig;
+
+#[derive(Debug, Clone, Copy)]
+struct VenueLimit {
+    capacity: u32,
+    refill_per_sec: f64,
+}
+
+/// Orders-per-minute limit per venue, shared across instances
+///
+/// Tokens are taken from a bucket in the shared cache, so every instance and
+/// wallet on a venue draws from one budget. While the cache is unreachable
+/// each instance falls back to a local bucket of the same size, which keeps
+/// it within the limit on its own.
+pub struct OrderRateLimiter {
+    cache: Arc<dyn Cache>,
+    limits: HashMap<String, VenueLimit>,
+    local: Mutex<HashMap<String, TokenBucket>>,
+}
+
+impl OrderRateLimiter {
+    pub fn from_config(cache: Arc<dyn Cache>, config: &ExecutionConfig) -> Result<Self> {
+        let mut limits = HashMap::new();
+        for (venue, limit) in &config.rate_limits {
+            if limit.orders_per_minute == 0 {
+                bail!("execution.rate_limits.{}.orders_per_minute must be positive", venue);
+            }
+            limits.insert(venue.clone(), VenueLimit {
+                capacity: limit.burst.unwrap_or(limit.orders_per_minute).max(1),
+                refill_per_sec: limit.orders_per_minute as f64 / 60.0,
+            });
+        }
+
+        Ok(Self {
+            cache,
+            limits,
+            local: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Take an order slot on `venue` if one is free; venues without a limit always have one
+    pub async fn try_acquire(&self, venue: &str) -> bool {
+        let Some(limit) = self.limits.get(venue).copied() else {
+            return true;
+        };
+
+        let key = format!("ratelimit:orders:{}", venue);
+        match self.cache.take_token(&key, limit.capacity, limit.refill_per_sec).await {
+            Ok(taken) => taken,
+            Err(e) => {
+                warn!("Shared rate limiter unavailable, limiting {} locally: {}", venue, e);
+                let now = Instant::now();
+                self.local.lock().unwrap()
+                    .entry(venue.to_string())
+                    .or_insert_with(|| TokenBucket::full(limit.capacity, now))
+                    .take(limit.capacity, limit.refill_per_sec, now)
+            }
+        }
+    }
+
+    /// Wait for an order slot on `venue`, for orders that can't be skipped
+    pub async fn acquire(&self, venue: &str) {
+        while !self.try_acquire(venue).await {
+            let wait = self.limits.get(venue).map_or(Duration::ZERO, |l| Duration::from_secs_f64(1.0 / l.refill_per_sec));
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::InProcessCache;
+    use crate::config::RateLimitConfig;
+
+    #[tokio::test]
+    async fn limits_configured_venues_only() {
+        let config = ExecutionConfig {
+            rate_limits: HashMap::from([(
+                "polymarket".to_string(),
+                RateLimitConfig { orders_per_minute: 1, burst: Some(2) },
+            )]),
+            ..ExecutionConfig::default()
+        };
+        let limiter = OrderRateLimiter::from_config(Arc::new(InProcessCache::new(100)), &config).unwrap();
+
+        assert!(limiter.try_acquire("polymarket").await);
+        assert!(limiter.try_acquire("polymarket").await);
+        assert!(!limiter.try_acquire("polymarket").await);
+        assert!(limiter.try_acquire("kalshi").await);
+    }
+}
@@ -0,0 +1,203 @@
+use anyhow::{bail, Context, Result};
+use ethers::abi::parse_abi;
+use ethers::prelude::*;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::config::Config;
+use crate::types::GasUrgency;
+use super::blockchain::{gas_cost_usd, GasTokenFeed};
+use super::gas::FeeEstimator;
+use super::rpc::RpcFailover;
+
+/// Index sets of a binary condition's outcome slots: YES is 0b01, NO is 0b10
+const BINARY_INDEX_SETS: [u64; 2] = [1, 2];
+
+/// What happened to one condition's redemption
+#[derive(Debug, Clone, PartialEq)]
+pub enum Redemption {
+    /// Redeemed in this transaction
+    Redeemed(String),
+    /// Not redeemed; left for the next pass
+    Failed(String),
+}
+
+/// Gas a mined redemption paid, whether or not it reverted
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedemptionGas {
+    pub tx_hash: String,
+    /// USD
+    pub cost: Decimal,
+}
+
+/// Redeems the outcome tokens of resolved conditions for USDC
+///
+/// Orders are signed by the wallet itself, so its outcome tokens sit in the
+/// wallet and each condition is redeemed by a `redeemPositions` call from it,
+/// one transaction per condition. A redemption is simulated first and only
+/// counts once the wallet's USDC balance has gone up: redeeming a condition
+/// the wallet holds nothing of succeeds but pays nothing.
+pub struct Redeemer {
+    rpc: Arc<RpcFailover<Http>>,
+    wallet: LocalWallet,
+    ctf: Address,
+    usdc: Address,
+    ctf_abi: BaseContract,
+    erc20_abi: BaseContract,
+    gas_token: GasTokenFeed,
+    fees: FeeEstimator,
+}
+
+impl Redeemer {
+    pub fn new(rpc: Arc<RpcFailover<Http>>, config: &Config) -> Result<Self> {
+        let chain = config.chain()?;
+        let wallet = config.blockchain.private_key
+            .parse::<LocalWallet>()
+            .context("blockchain.private_key")?
+            .with_chain_id(chain.chain_id);
+
+        Ok(Self {
+            rpc,
+            wallet,
+            ctf: chain.conditional_tokens,
+            usdc: chain.usdc,
+            ctf_abi: BaseContract::from(parse_abi(&[
+                "function redeemPositions(address collateralToken, bytes32 parentCollectionId, bytes32 conditionId, uint256[] indexSets) external",
+            ])?),
+            erc20_abi: BaseContract::from(parse_abi(&[
+                "function balanceOf(address owner) external view returns (uint256)",
+            ])?),
+            gas_token: GasTokenFeed::new(chain.gas_token_usd_feed)?,
+            fees: FeeEstimator::from_config(&config.blockchain)?,
+        })
+    }
+
+    fn transaction(&self, condition: H256) -> Result<Eip1559TransactionRequest> {
+        let index_sets: Vec<U256> = BINARY_INDEX_SETS.iter().map(|&i| U256::from(i)).collect();
+        let data = self.ctf_abi.encode("redeemPositions", (self.usdc, H256::zero(), condition, index_sets))?;
+
+        Ok(Eip1559TransactionRequest::new()
+            .from(self.wallet.address())
+            .to(self.ctf)
+            .data(data))
+    }
+
+    /// Why redeeming `condition` would revert, if it would
+    async fn simulate(&self, condition: H256) -> Option<String> {
+        let tx = match self.transaction(condition) {
+            Ok(tx) => tx.into(),
+            Err(e) => return Some(e.to_string()),
+        };
+        self.rpc.call(|provider| async move { Ok(provider.call(&tx, None).await?) }).await
+            .err()
+            .map(|e| e.to_string())
+    }
+
+    /// USDC base units held by the wallet
+    async fn usdc_balance(&self) -> Result<U256> {
+        let data = self.erc20_abi.encode("balanceOf", self.wallet.address())?;
+        let tx: TypedTransaction = TransactionRequest::new().to(self.usdc).data(data).into();
+        let balance = self.rpc.call(|provider| async move { Ok(provider.call(&tx, None).await?) }).await?;
+        Ok(self.erc20_abi.decode_output("balanceOf", balance)?)
+    }
+
+    /// USD the wallet paid for a mined transaction; none when it can't be priced
+    async fn gas_paid(&self, receipt: &TransactionReceipt) -> Option<RedemptionGas> {
+        let tx_hash = format!("{:?}", receipt.transaction_hash);
+        let (Some(gas), Some(gas_price)) = (receipt.gas_used, receipt.effective_gas_price) else {
+            warn!("Receipt of redemption {} has no gas used or price", tx_hash);
+            return None;
+        };
+        let pol_usd = match self.gas_token.usd(&self.rpc).await {
+            Ok(pol_usd) => pol_usd,
+            Err(e) => {
+                warn!("Gas of redemption {} not priced: {}", tx_hash, e);
+                return None;
+            }
+        };
+
+        let cost = gas_cost_usd(gas, gas_price, pol_usd)?;
+        Some(RedemptionGas { tx_hash, cost })
+    }
+
+    /// Redeem `condition`; the transaction's hash once it is mined and has
+    /// paid USDC into the wallet, with the gas it paid once mined
+    ///
+    /// Redemptions can wait, so they bid a low tip and are replaced if stuck.
+    async fn send(&self, condition: H256) -> (Result<String>, Option<RedemptionGas>) {
+        let sent = async {
+            let tx = self.transaction(condition)?;
+            let before = self.usdc_balance().await?;
+            let receipt = self.rpc.call(|provider| async move {
+                let client = SignerMiddleware::new(provider.as_ref().clone(), self.wallet.clone());
+                self.fees.send(&client, tx, GasUrgency::Low).await
+            }).await?;
+            Ok((before, receipt))
+        };
+        let (before, receipt) = match sent.await {
+            Ok(sent) => sent,
+            Err(e) => return (Err(e), None),
+        };
+
+        // A reverted redemption still pays for its gas
+        let gas = self.gas_paid(&receipt).await;
+        (self.confirm(&receipt, before).await, gas)
+    }
+
+    /// The mined redemption's hash once it has paid USDC into the wallet
+    async fn confirm(&self, receipt: &TransactionReceipt, before: U256) -> Result<String> {
+        let tx_hash = format!("{:?}", receipt.transaction_hash);
+        if receipt.status != Some(U64::one()) {
+            bail!("redemption {} reverted", tx_hash);
+        }
+        let after = self.usdc_balance().await?;
+        if after <= before {
+            bail!("redemption {} paid no USDC into {:?}", tx_hash, self.wallet.address());
+        }
+        Ok(tx_hash)
+    }
+
+    /// Redeem every condition in `conditions`, with each one's outcome and
+    /// the gas its transaction paid, if one was mined
+    pub async fn redeem(&self, conditions: &[H256]) -> Vec<(H256, Redemption, Option<RedemptionGas>)> {
+        let mut outcomes = Vec::with_capacity(conditions.len());
+        for &condition in conditions {
+            let (outcome, gas) = match self.simulate(condition).await {
+                Some(reason) => (Redemption::Failed(reason), None),
+                None => match self.send(condition).await {
+                    (Ok(tx_hash), gas) => (Redemption::Redeemed(tx_hash), gas),
+                    (Err(e), gas) => (Redemption::Failed(e.to_string()), gas),
+                },
+            };
+            outcomes.push((condition, outcome, gas));
+        }
+
+        outcomes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RpcFailoverConfig;
+
+    #[test]
+    fn redeems_from_the_wallet_that_holds_the_tokens() {
+        let mut config = Config::default();
+        config.blockchain.private_key = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".to_string();
+        let rpc = RpcFailover::new("blockchain.rpc_url", vec!["http://localhost:8545".to_string()], 137, &RpcFailoverConfig::default());
+        let redeemer = Redeemer::new(Arc::new(rpc.unwrap()), &config).unwrap();
+        let chain = config.chain().unwrap();
+        let condition = H256::repeat_byte(7);
+
+        let tx = redeemer.transaction(condition).unwrap();
+        assert_eq!(tx.from, Some(redeemer.wallet.address()));
+        assert_eq!(tx.to, Some(chain.conditional_tokens.into()));
+        let (collateral, parent, redeemed, index_sets): (Address, H256, H256, Vec<U256>) =
+            redeemer.ctf_abi.decode("redeemPositions", tx.data.unwrap()).unwrap();
+        assert_eq!((collateral, parent, redeemed), (chain.usdc, H256::zero(), condition));
+        assert_eq!(index_sets, vec![U256::from(1u64), U256::from(2u64)]);
+    }
+}
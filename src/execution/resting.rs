@@ -0,0 +1,89 @@
+use anyhow::{Context, Result};
+use rust_decimal::Decimal;
+
+use crate::config::ExecutionConfig;
+use crate::numeric;
+use crate::types::{Price, RestingOrderKind};
+
+/// Where stop and target exits sit relative to a trade's entry price
+///
+/// The CLOB has no stop orders: a sell limited below the bid fills at once.
+/// Only the target rests on the book; the stop is watched by the position
+/// monitor and sold at the bid once reached.
+pub struct ExitBracket {
+    stop_distance: Decimal,
+    target_distance: Decimal,
+}
+
+impl ExitBracket {
+    /// `None` when resting exits are disabled
+    pub fn from_config(config: &ExecutionConfig) -> Result<Option<Self>> {
+        if !config.resting_exits {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            stop_distance: numeric::to_decimal(config.resting_stop_distance)
+                .context("execution.resting_stop_distance")?,
+            target_distance: numeric::to_decimal(config.resting_target_distance)
+                .context("execution.resting_target_distance")?,
+        }))
+    }
+
+    /// Stop and target prices for a position entered at `entry_price`
+    ///
+    /// A level that would sit at or beyond the bounds of the book is left
+    /// out: a stop at zero never triggers before resolution and a target at
+    /// one never fills before it.
+    pub fn levels(&self, entry_price: Decimal) -> Vec<(RestingOrderKind, Price)> {
+        [
+            (RestingOrderKind::Stop, entry_price - self.stop_distance),
+            (RestingOrderKind::Target, entry_price + self.target_distance),
+        ]
+        .into_iter()
+        .filter(|(_, price)| *price > Decimal::ZERO && *price < Decimal::ONE)
+        .filter_map(|(kind, price)| Some((kind, Price::new(price).ok()?)))
+        .collect()
+    }
+
+    /// Whether the bid has fallen to the stop of a position entered at `entry_price`
+    pub fn stop_hit(&self, entry_price: Decimal, bid: Price) -> bool {
+        // No bid means no one to sell to, as with the exit thresholds
+        bid.value() > Decimal::ZERO
+            && self.levels(entry_price).iter().any(|(kind, stop)| *kind == RestingOrderKind::Stop && bid <= *stop)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn levels_straddle_entry_within_the_book() {
+        let bracket = ExitBracket { stop_distance: dec!(0.15), target_distance: dec!(0.20) };
+
+        let levels = bracket.levels(dec!(0.50));
+        assert_eq!(levels, vec![
+            (RestingOrderKind::Stop, Price::new(dec!(0.35)).unwrap()),
+            (RestingOrderKind::Target, Price::new(dec!(0.70)).unwrap()),
+        ]);
+
+        // Longshot: no stop below zero
+        assert_eq!(bracket.levels(dec!(0.10)), vec![(RestingOrderKind::Target, Price::new(dec!(0.30)).unwrap())]);
+        // Favourite: no target at one
+        assert_eq!(bracket.levels(dec!(0.80)), vec![(RestingOrderKind::Stop, Price::new(dec!(0.65)).unwrap())]);
+    }
+
+    #[test]
+    fn stops_trigger_once_the_bid_reaches_them() {
+        let bracket = ExitBracket { stop_distance: dec!(0.15), target_distance: dec!(0.20) };
+        let bid = |price| Price::new(price).unwrap();
+
+        assert!(!bracket.stop_hit(dec!(0.50), bid(dec!(0.36))));
+        assert!(bracket.stop_hit(dec!(0.50), bid(dec!(0.35))));
+        assert!(!bracket.stop_hit(dec!(0.50), bid(dec!(0))));
+        // No stop below zero to reach
+        assert!(!bracket.stop_hit(dec!(0.10), bid(dec!(0.01))));
+    }
+}
@@ -0,0 +1,137 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::config::ExecutionConfig;
+use crate::types::{ExecutionFailureStatus, FailedExecution};
+use super::gas::Broadcast;
+
+/// Error text of failures that may succeed on resubmission: RPC timeouts
+/// and outages, rate limits, and nonce races with our own pending
+/// transactions. Anything else (reverts, rejected orders, bad inputs)
+/// fails the same way every time.
+const TRANSIENT_MARKERS: &[&str] = &[
+    "timeout",
+    "timed out",
+    "connection",
+    "rate limit",
+    "too many requests",
+    "temporarily unavailable",
+    "bad gateway",
+    "service unavailable",
+    "nonce too low",
+    "nonce too high",
+    "replacement transaction underpriced",
+    "already known",
+];
+
+/// Whether a submission failure is worth retrying; never once the
+/// transaction was broadcast, since it may yet be mined
+pub fn is_transient(error: &anyhow::Error) -> bool {
+    if error.downcast_ref::<Broadcast>().is_some() {
+        return false;
+    }
+    error.chain().any(|cause| {
+        if cause.downcast_ref::<tokio::time::error::Elapsed>().is_some() {
+            return true;
+        }
+        if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+            return e.is_timeout() || e.is_connect();
+        }
+        let message = cause.to_string().to_lowercase();
+        TRANSIENT_MARKERS.iter().any(|marker| message.contains(marker))
+    })
+}
+
+/// Backoff schedule for signals whose submission failed
+///
+/// Failures are kept in `failed_executions`, so retries survive a restart.
+/// A retrying signal stays pending in storage and is skipped until its next
+/// attempt is due; its strategy's execution deadline still applies.
+pub struct RetryQueue {
+    max_attempts: u32,
+    backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl RetryQueue {
+    pub fn new(config: &ExecutionConfig) -> Self {
+        Self {
+            max_attempts: config.max_submit_attempts.max(1),
+            backoff: Duration::seconds(config.retry_backoff_secs as i64),
+            max_backoff: Duration::seconds(config.max_retry_backoff_secs as i64),
+        }
+    }
+
+    /// Whether a signal may be submitted now, given its failure record
+    pub fn is_due(failure: Option<&FailedExecution>, now: DateTime<Utc>) -> bool {
+        failure.map_or(true, |failure| {
+            failure.status != ExecutionFailureStatus::Retrying
+                || failure.next_attempt_at.map_or(true, |at| at <= now)
+        })
+    }
+
+    /// When to resubmit after `attempts` failed submissions, doubling the
+    /// delay each time; `None` once attempts are exhausted or the failure
+    /// would repeat
+    pub fn next_attempt(&self, attempts: i32, transient: bool, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if !transient || attempts.max(0) as u32 >= self.max_attempts {
+            return None;
+        }
+
+        let delay = (1..attempts).fold(self.backoff, |delay, _| (delay * 2).min(self.max_backoff));
+        Some(now + delay.min(self.max_backoff))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Strategy;
+    use anyhow::anyhow;
+    use ethers::types::TxHash;
+    use uuid::Uuid;
+
+    #[test]
+    fn backs_off_exponentially_then_gives_up() {
+        let queue = RetryQueue::new(&ExecutionConfig {
+            max_submit_attempts: 5,
+            retry_backoff_secs: 5,
+            max_retry_backoff_secs: 15,
+            ..ExecutionConfig::default()
+        });
+        let now = Utc::now();
+
+        assert_eq!(queue.next_attempt(1, true, now), Some(now + Duration::seconds(5)));
+        assert_eq!(queue.next_attempt(2, true, now), Some(now + Duration::seconds(10)));
+        assert_eq!(queue.next_attempt(3, true, now), Some(now + Duration::seconds(15)));
+        assert_eq!(queue.next_attempt(4, true, now), Some(now + Duration::seconds(15)));
+        assert_eq!(queue.next_attempt(5, true, now), None);
+        assert_eq!(queue.next_attempt(1, false, now), None);
+
+        let mut failure = FailedExecution {
+            signal_id: Uuid::new_v4(),
+            market_id: "0xretry".to_string(),
+            strategy: Strategy::ClvArbitrage,
+            attempts: 1,
+            last_error: "request timed out".to_string(),
+            status: ExecutionFailureStatus::Retrying,
+            next_attempt_at: queue.next_attempt(1, true, now),
+            first_failed_at: now,
+            updated_at: now,
+        };
+        assert!(RetryQueue::is_due(None, now));
+        assert!(!RetryQueue::is_due(Some(&failure), now));
+        assert!(RetryQueue::is_due(Some(&failure), now + Duration::seconds(5)));
+        failure.status = ExecutionFailureStatus::Failed;
+        failure.next_attempt_at = None;
+        assert!(RetryQueue::is_due(Some(&failure), now));
+    }
+
+    #[test]
+    fn only_transient_errors_are_retried() {
+        assert!(is_transient(&anyhow!("request timed out").context("submitting order")));
+        assert!(is_transient(&anyhow!("nonce too low")));
+        assert!(!is_transient(&anyhow!("execution reverted: insufficient allowance")));
+        assert!(!is_transient(&anyhow!("order rejected: price outside tick size")));
+        assert!(!is_transient(&anyhow!("nonce too low").context(Broadcast { sent: vec![TxHash::zero()] })));
+    }
+}
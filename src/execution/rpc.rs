@@ -0,0 +1,265 @@
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use ethers::prelude::*;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::{watch, RwLock};
+use tokio::time::{interval, timeout, Duration};
+use tracing::{info, warn};
+
+use crate::config::{Chain, RpcFailoverConfig};
+
+/// A JSON-RPC transport that can be opened from an endpoint URL
+#[async_trait]
+pub trait Connect: JsonRpcClient + Sized + 'static {
+    async fn connect(url: &str) -> Result<Self>;
+}
+
+#[async_trait]
+impl Connect for Ws {
+    async fn connect(url: &str) -> Result<Self> {
+        Ok(Ws::connect(url).await?)
+    }
+}
+
+#[async_trait]
+impl Connect for Http {
+    async fn connect(url: &str) -> Result<Self> {
+        Ok(Http::from_str(url)?)
+    }
+}
+
+struct Active<P: JsonRpcClient> {
+    index: usize,
+    provider: Arc<Provider<P>>,
+}
+
+/// Index of the most preferred endpoint whose head is within `max_lag`
+/// blocks of the highest head seen, `None` when none answered
+fn preferred_endpoint(heads: &[Option<u64>], max_lag: u64) -> Option<usize> {
+    let best = heads.iter().flatten().max()?;
+    heads.iter().position(|head| matches!(head, Some(head) if head + max_lag >= *best))
+}
+
+/// Polygon endpoints in order of preference, with a connection to the one
+/// in use kept healthy
+///
+/// Connections are opened lazily, trying each endpoint in turn, and are
+/// checked against the chain id. A call that fails and whose endpoint then
+/// misses a probe drops the connection, so the next call fails over. The
+/// periodic health check moves to the most preferred endpoint whose head is
+/// within `max_block_lag` blocks of the highest, which also returns to the
+/// primary once it recovers. Holders of subscriptions watch `changes()` to
+/// resubscribe on the new connection.
+pub struct RpcFailover<P: Connect> {
+    /// Config key of the endpoints, for messages
+    key: &'static str,
+    urls: Vec<String>,
+    chain_id: u64,
+    request_timeout: Duration,
+    max_block_lag: u64,
+    check_interval: Duration,
+    active: RwLock<Option<Active<P>>>,
+    generation: watch::Sender<u64>,
+}
+
+impl<P: Connect> RpcFailover<P> {
+    pub fn new(key: &'static str, urls: Vec<String>, chain_id: u64, config: &RpcFailoverConfig) -> Result<Self> {
+        if urls.is_empty() {
+            bail!("{} has no endpoints", key);
+        }
+        if config.request_timeout_secs == 0 {
+            bail!("blockchain.failover.request_timeout_secs must be positive");
+        }
+
+        Ok(Self {
+            key,
+            urls,
+            chain_id,
+            request_timeout: Duration::from_secs(config.request_timeout_secs),
+            max_block_lag: config.max_block_lag,
+            check_interval: Duration::from_secs(config.health_check_interval_secs.max(1)),
+            active: RwLock::new(None),
+            generation: watch::channel(0).0,
+        })
+    }
+
+    async fn connect(&self, index: usize) -> Result<Arc<Provider<P>>> {
+        let url = &self.urls[index];
+        let provider = timeout(self.request_timeout, async {
+            let provider = Provider::new(P::connect(url).await?);
+            // An endpoint on another chain would have every order signed for the wrong one
+            let chain_id = provider.get_chainid().await?;
+            if chain_id != U256::from(self.chain_id) {
+                bail!("on chain {}, not chain {}", chain_id, self.chain_id);
+            }
+            Ok(provider)
+        })
+        .await
+        .context("timed out")??;
+
+        Ok(Arc::new(provider))
+    }
+
+    async fn head(&self, provider: &Provider<P>) -> Option<u64> {
+        match timeout(self.request_timeout, provider.get_block_number()).await {
+            Ok(Ok(head)) => Some(head.as_u64()),
+            _ => None,
+        }
+    }
+
+    fn switch(&self, active: &mut Option<Active<P>>, next: Option<Active<P>>) {
+        *active = next;
+        self.generation.send_modify(|generation| *generation += 1);
+    }
+
+    /// Provider of the endpoint in use, connecting to the first reachable
+    /// endpoint when there is none
+    pub async fn provider(&self) -> Result<Arc<Provider<P>>> {
+        if let Some(active) = self.active.read().await.as_ref() {
+            return Ok(active.provider.clone());
+        }
+
+        let mut active = self.active.write().await;
+        if let Some(active) = active.as_ref() {
+            return Ok(active.provider.clone());
+        }
+        let mut errors = Vec::new();
+        for index in 0..self.urls.len() {
+            match self.connect(index).await {
+                Ok(provider) => {
+                    if index > 0 {
+                        warn!("🔌 {} failed over to endpoint {} ({})", self.key, index, errors.join("; "));
+                    }
+                    self.switch(&mut active, Some(Active { index, provider: provider.clone() }));
+                    return Ok(provider);
+                }
+                Err(e) => errors.push(format!("{}: {:#}", self.urls[index], e)),
+            }
+        }
+
+        bail!("no {} endpoint reachable: {}", self.key, errors.join("; "))
+    }
+
+    /// Run `call` on the endpoint in use; when it fails and the endpoint
+    /// also misses a probe, the connection is dropped for the next call to
+    /// fail over
+    pub async fn call<T, F, Fut>(&self, call: F) -> Result<T>
+    where
+        F: FnOnce(Arc<Provider<P>>) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let provider = self.provider().await?;
+        let result = call(provider.clone()).await;
+        if result.is_err() {
+            self.suspect(&provider).await;
+        }
+        result
+    }
+
+    /// Probe `provider` after something failed on it, dropping its
+    /// connection when it does not answer, unless it was already replaced
+    pub async fn suspect(&self, provider: &Arc<Provider<P>>) {
+        if self.head(provider).await.is_some() {
+            return;
+        }
+
+        let mut active = self.active.write().await;
+        if let Some(current) = active.as_ref().filter(|a| Arc::ptr_eq(&a.provider, provider)) {
+            warn!("🔌 {} endpoint {} failed, reconnecting", self.key, current.index);
+            self.switch(&mut active, None);
+        }
+    }
+
+    /// Changes each time the connection in use is replaced or dropped
+    pub fn changes(&self) -> watch::Receiver<u64> {
+        self.generation.subscribe()
+    }
+
+    /// Probe every endpoint's head and move to the preferred one
+    ///
+    /// Endpoints other than the one in use are probed on a fresh connection.
+    pub async fn check(&self) -> Result<()> {
+        let current = self.active.read().await.as_ref().map(|a| (a.index, a.provider.clone()));
+
+        let mut probed = Vec::with_capacity(self.urls.len());
+        for index in 0..self.urls.len() {
+            let provider = match &current {
+                Some((active, provider)) if *active == index => Some(provider.clone()),
+                _ => self.connect(index).await.ok(),
+            };
+            let head = match &provider {
+                Some(provider) => self.head(provider).await,
+                None => None,
+            };
+            probed.push((provider, head));
+        }
+
+        let heads: Vec<Option<u64>> = probed.iter().map(|(_, head)| *head).collect();
+        let Some(preferred) = preferred_endpoint(&heads, self.max_block_lag) else {
+            bail!("no {} endpoint answered", self.key);
+        };
+        if current.as_ref().map(|(index, _)| *index) == Some(preferred) {
+            return Ok(());
+        }
+
+        let mut active = self.active.write().await;
+        let provider = probed.swap_remove(preferred).0.context("probed endpoint has no provider")?;
+        match &current {
+            Some((index, _)) => warn!(
+                "🔌 {} moving from endpoint {} (head {:?}) to {} (head {:?})",
+                self.key, index, heads[*index], preferred, heads[preferred]
+            ),
+            None => info!("🔌 {} connected to endpoint {}", self.key, preferred),
+        }
+        self.switch(&mut active, Some(Active { index: preferred, provider }));
+
+        Ok(())
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let mut tick = interval(self.check_interval);
+
+        loop {
+            tick.tick().await;
+
+            if let Err(e) = self.check().await {
+                warn!("🔌 {} health check failed: {}", self.key, e);
+            }
+        }
+    }
+}
+
+/// The HTTP and WebSocket endpoints of the chain, shared by every service
+/// that reads or writes it
+#[derive(Clone)]
+pub struct ChainRpc {
+    pub http: Arc<RpcFailover<Http>>,
+    pub ws: Arc<RpcFailover<Ws>>,
+}
+
+impl ChainRpc {
+    pub fn new(chain: &Chain, config: &RpcFailoverConfig) -> Result<Self> {
+        Ok(Self {
+            http: Arc::new(RpcFailover::new("blockchain.rpc_url", chain.rpc_urls.clone(), chain.chain_id, config)?),
+            ws: Arc::new(RpcFailover::new("blockchain.ws_url", chain.ws_urls.clone(), chain.chain_id, config)?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefers_the_first_endpoint_keeping_up_with_the_chain() {
+        // The primary trails by more than the allowed lag
+        assert_eq!(preferred_endpoint(&[Some(90), Some(100), Some(101)], 5), Some(1));
+        // Within the lag the primary is kept
+        assert_eq!(preferred_endpoint(&[Some(97), Some(101)], 5), Some(0));
+        // Endpoints that did not answer are passed over
+        assert_eq!(preferred_endpoint(&[None, None, Some(50)], 5), Some(2));
+        assert_eq!(preferred_endpoint(&[None, None], 5), None);
+    }
+}
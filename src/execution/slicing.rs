@@ -0,0 +1,151 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::config::SlicingConfig;
+use crate::numeric;
+use crate::types::{ParentOrder, RestingOrderStatus, Signal, SliceMode};
+
+/// Splits large taker entries into child orders, so a thin book is not
+/// walked by one order
+///
+/// TWAP sends `slices` equal children `interval` apart, the last taking
+/// whatever is left. Iceberg sends one child each `interval` no larger than
+/// the notional offered at the best price, nor than the target over
+/// `slices`, for as long as it takes the book to refill. Either way a child
+/// only goes while the ask is within `max_drift` of the signal's price, and
+/// the parent is dropped with what it bought once `max_duration` passes.
+#[derive(Debug, Clone)]
+pub struct SlicingPolicy {
+    min_notional: Decimal,
+    mode: SliceMode,
+    slices: i32,
+    interval: Duration,
+    /// Fraction of the signal's price above it children may pay
+    max_drift: Decimal,
+    max_duration: Duration,
+}
+
+impl SlicingPolicy {
+    /// `None` when slicing is disabled
+    pub fn from_config(config: &SlicingConfig) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        if config.slices < 2 {
+            bail!("execution.slicing.slices must be at least 2");
+        }
+        let bps = numeric::to_decimal(config.max_price_drift_bps).context("execution.slicing.max_price_drift_bps")?;
+        if bps < Decimal::ZERO {
+            bail!("execution.slicing.max_price_drift_bps must not be negative");
+        }
+
+        Ok(Some(Self {
+            min_notional: numeric::to_decimal(config.min_notional).context("execution.slicing.min_notional")?,
+            mode: config.mode,
+            slices: config.slices as i32,
+            interval: Duration::seconds(config.interval_secs as i64),
+            max_drift: bps / Decimal::from(10_000),
+            max_duration: Duration::seconds(config.max_duration_secs as i64),
+        }))
+    }
+
+    /// Whether a stake is large enough to slice
+    pub fn applies(&self, notional: Decimal) -> bool {
+        notional > self.min_notional
+    }
+
+    /// A parent order for `notional` of `signal`, its first child due now
+    pub fn parent(&self, signal: &Signal, notional: Decimal, now: DateTime<Utc>) -> ParentOrder {
+        let limit_price = (signal.current_price.value() * (Decimal::ONE + self.max_drift)).min(Decimal::ONE);
+        ParentOrder {
+            parent_id: Uuid::new_v4(),
+            signal_id: signal.signal_id,
+            market_id: signal.market_id.clone(),
+            position: signal.signal_type.to_position(),
+            mode: self.mode,
+            target_notional: notional,
+            limit_price,
+            slices: self.slices,
+            children_sent: 0,
+            filled_quantity: Decimal::ZERO,
+            filled_notional: Decimal::ZERO,
+            status: RestingOrderStatus::Open,
+            next_slice_at: now,
+            created_at: now,
+            expires_at: now + self.max_duration,
+        }
+    }
+
+    /// When the child after one sent at `now` is due
+    pub fn next_slice_at(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now + self.interval
+    }
+
+    /// USDC the parent's next child should spend; `best_offer` is the
+    /// notional offered at the best price, when the book is known
+    pub fn child_notional(&self, parent: &ParentOrder, best_offer: Option<Decimal>) -> Decimal {
+        let remaining = (parent.target_notional - parent.filled_notional).max(Decimal::ZERO);
+        let even = parent.target_notional / Decimal::from(parent.slices.max(1));
+        match parent.mode {
+            SliceMode::Twap if parent.children_sent + 1 >= parent.slices => remaining,
+            SliceMode::Twap => even.min(remaining),
+            SliceMode::Iceberg => even.min(remaining).min(best_offer.unwrap_or(Decimal::MAX)),
+        }
+    }
+
+    /// Whether the parent has nothing left to send
+    pub fn done(&self, parent: &ParentOrder) -> bool {
+        parent.filled_notional >= parent.target_notional
+            || (parent.mode == SliceMode::Twap && parent.children_sent >= parent.slices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Price, Probability, SignalType, Strategy};
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn twap_children_are_even_with_the_last_taking_the_rest() {
+        let policy = SlicingPolicy::from_config(&SlicingConfig {
+            enabled: true,
+            slices: 4,
+            ..SlicingConfig::default()
+        }).unwrap().unwrap();
+        let signal = Signal {
+            signal_id: Uuid::new_v4(),
+            market_id: "0xabc".to_string(),
+            strategy: Strategy::ClvArbitrage,
+            signal_type: SignalType::BuyYes,
+            confidence: dec!(0.8),
+            edge_size: dec!(0.05),
+            recommended_size: dec!(2000),
+            current_price: Price::new(dec!(0.40)).unwrap(),
+            executable_size: None,
+            quoted_edge: dec!(0.05),
+            fair_value: Probability::new(dec!(0.45)).unwrap(),
+            generated_at: Utc::now(),
+            metadata: serde_json::Value::Null,
+        };
+        let mut parent = policy.parent(&signal, dec!(2000), Utc::now());
+        assert_eq!(parent.limit_price, dec!(0.406));
+        assert_eq!(policy.child_notional(&parent, None), dec!(500));
+
+        // A short fill leaves the shortfall to the last child
+        parent.children_sent = 3;
+        parent.filled_notional = dec!(1400);
+        assert_eq!(policy.child_notional(&parent, None), dec!(600));
+        parent.children_sent = 4;
+        assert!(policy.done(&parent));
+
+        // Iceberg children take no more than the best offer shows
+        parent.mode = SliceMode::Iceberg;
+        parent.children_sent = 1;
+        parent.filled_notional = dec!(500);
+        assert_eq!(policy.child_notional(&parent, Some(dec!(320))), dec!(320));
+        assert!(!policy.done(&parent));
+    }
+}
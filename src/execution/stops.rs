@@ -0,0 +1,118 @@
+use anyhow::{bail, Context, Result};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+use crate::config::ExitThresholdsConfig;
+use crate::numeric;
+use crate::types::{Price, Strategy};
+
+/// Why the position monitor closes a trade
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitTrigger {
+    StopLoss,
+    TakeProfit,
+}
+
+impl ExitTrigger {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExitTrigger::StopLoss => "stop-loss",
+            ExitTrigger::TakeProfit => "take-profit",
+        }
+    }
+}
+
+/// Stop-loss and take-profit levels, as fractions of cost
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Levels {
+    stop_loss: Option<Decimal>,
+    take_profit: Option<Decimal>,
+}
+
+/// Stop-loss and take-profit levels per strategy, over global defaults
+pub struct ExitThresholds {
+    default: Levels,
+    by_strategy: HashMap<Strategy, Levels>,
+}
+
+fn fraction(pct: Option<f64>, key: &str) -> Result<Option<Decimal>> {
+    pct.map(|pct| {
+        if !(pct > 0.0) {
+            bail!("{} must be positive", key);
+        }
+        Ok(numeric::to_decimal(pct).with_context(|| key.to_string())? / Decimal::ONE_HUNDRED)
+    })
+    .transpose()
+}
+
+impl ExitThresholds {
+    pub fn from_config(config: &ExitThresholdsConfig) -> Result<Self> {
+        let default = Levels {
+            stop_loss: fraction(config.stop_loss_pct, "risk.exits.stop_loss_pct")?,
+            take_profit: fraction(config.take_profit_pct, "risk.exits.take_profit_pct")?,
+        };
+
+        let mut by_strategy = HashMap::new();
+        for (name, levels) in &config.strategies {
+            let strategy = Strategy::parse(name)
+                .with_context(|| format!("risk.exits.strategies: unknown strategy {}", name))?;
+            let stop_loss = fraction(levels.stop_loss_pct, &format!("risk.exits.strategies.{}.stop_loss_pct", name))?;
+            let take_profit = fraction(levels.take_profit_pct, &format!("risk.exits.strategies.{}.take_profit_pct", name))?;
+            by_strategy.insert(strategy, Levels {
+                stop_loss: stop_loss.or(default.stop_loss),
+                take_profit: take_profit.or(default.take_profit),
+            });
+        }
+
+        Ok(Self { default, by_strategy })
+    }
+
+    /// The level, if any, a position entered at `entry_price` has reached
+    /// with its shares now bid at `bid`
+    pub fn triggered(&self, strategy: Strategy, entry_price: Decimal, bid: Price) -> Option<ExitTrigger> {
+        // No bid means no one to sell to, not a total loss
+        if entry_price <= Decimal::ZERO || bid.value() <= Decimal::ZERO {
+            return None;
+        }
+
+        let levels = self.by_strategy.get(&strategy).unwrap_or(&self.default);
+        let change = (bid.value() - entry_price) / entry_price;
+        if levels.stop_loss.map_or(false, |stop| change <= -stop) {
+            Some(ExitTrigger::StopLoss)
+        } else if levels.take_profit.map_or(false, |target| change >= target) {
+            Some(ExitTrigger::TakeProfit)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::StrategyExitConfig;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn strategy_levels_override_the_global_ones() {
+        let thresholds = ExitThresholds::from_config(&ExitThresholdsConfig {
+            stop_loss_pct: Some(50.0),
+            take_profit_pct: Some(100.0),
+            strategies: HashMap::from([("clv_arb".to_string(), StrategyExitConfig {
+                stop_loss_pct: None,
+                take_profit_pct: Some(20.0),
+            })]),
+        })
+        .unwrap();
+        let bid = |p| Price::new(p).unwrap();
+
+        // Entered at 0.40: a bid of 0.48 is up 20%
+        assert_eq!(thresholds.triggered(Strategy::ClvArbitrage, dec!(0.40), bid(dec!(0.48))), Some(ExitTrigger::TakeProfit));
+        assert_eq!(thresholds.triggered(Strategy::PoissonExpectedValue, dec!(0.40), bid(dec!(0.48))), None);
+        assert_eq!(thresholds.triggered(Strategy::PoissonExpectedValue, dec!(0.40), bid(dec!(0.80))), Some(ExitTrigger::TakeProfit));
+
+        // The CLV stop falls back to the global 50%
+        assert_eq!(thresholds.triggered(Strategy::ClvArbitrage, dec!(0.40), bid(dec!(0.21))), None);
+        assert_eq!(thresholds.triggered(Strategy::ClvArbitrage, dec!(0.40), bid(dec!(0.20))), Some(ExitTrigger::StopLoss));
+    }
+}
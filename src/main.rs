@@ -1,21 +1,39 @@
 use anyhow::Result;
+use std::sync::Arc;
 use tracing::{info, error};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+mod api;
+mod cache;
 mod config;
+mod credentials;
 mod types;
 mod data;
+mod events;
 mod strategies;
 mod execution;
 mod risk;
 mod monitoring;
 mod models;
+mod numeric;
+mod storage;
 
-use config::Config;
-use data::DataPipeline;
-use execution::ExecutionEngine;
-use risk::RiskManager;
-use monitoring::MonitoringService;
+#[cfg(test)]
+mod test_support;
+
+use api::{AdminApi, GrpcApi};
+use cache::ReferenceCache;
+use config::{Config, TradingMode};
+use credentials::CredentialVault;
+use data::{Backfiller, BookStream, DataPipeline, DirtyMarkets, OddsIngestor, OfficialsIngestor, PitcherIngestor};
+use events::EventBus;
+use execution::{ChainRpc, ExecutionEngine, FillListener};
+use risk::{
+    ConsistencyMonitor, ConvergenceTracker, DriftMonitor, EdgeDecayEstimator, FundingMonitor, PositionMonitor, RiskManager, SettlementMonitor, StrategyThrottler,
+    WeightOptimizer,
+};
+use models::ModelParameters;
+use monitoring::{EventAlerts, Heartbeats, Latencies, MonitoringService, NotificationService};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -31,39 +49,283 @@ async fn main() -> Result<()> {
     info!("🚀 Starting Polymarket Trading Bot v2.0");
 
     // Load configuration
-    let config = Config::load()?;
-    info!("✅ Configuration loaded");
+    let mut config = Config::load()?;
+    let chain = config.chain()?;
+    info!("✅ Configuration loaded ({} network, chain {})", chain.network, chain.chain_id);
+    if chain.testnet {
+        info!("🧪 Running against testnet contracts and the staging CLOB; no real funds are at risk");
+    }
+
+    // Per-league parameter sheets; a sheet that fails validation stops startup
+    if !config.model_parameters.sheets.is_empty() {
+        let parameters = ModelParameters::load(&config.model_parameters.sheets)?;
+        info!("✅ Model parameters loaded ({} leagues from {} sheets)", parameters.leagues(), config.model_parameters.sheets.len());
+        parameters.install()?;
+    }
 
-    // Initialize database connection pool
-    let db_pool = config.create_db_pool().await?;
-    info!("✅ Database connected");
+    // Initialize storage (Postgres for live trading, SQLite allowed for paper/backtest)
+    let storage = config.create_storage().await?;
+    info!("✅ Database connected ({:?}, {:?} mode)", config.database.backend, config.mode);
+
+    // Stored credentials override the config files before any external client is built
+    let vault = CredentialVault::from_env(storage.clone())?.map(Arc::new);
+    match &vault {
+        Some(vault) => info!("✅ Credentials applied ({} from the encrypted store)", vault.apply(&mut config).await?),
+        None => info!("Credential store disabled ({} not set)", credentials::MASTER_KEY_ENV),
+    }
+
+    // Initialize cache/pub-sub (Redis, or in-process when disabled)
+    let cache = config.create_cache().await?;
+    if config.redis.enabled {
+        info!("✅ Redis connected");
+    } else {
+        info!("✅ In-process cache initialized (Redis disabled)");
+    }
 
-    // Initialize Redis connection
-    let redis_client = config.create_redis_client().await?;
-    info!("✅ Redis connected");
+    // Typed events between services (signals, orders, fills, status changes, breakers), over the cache's pub/sub
+    let events = EventBus::new(cache.clone());
+
+    // Initialize components; each service loop reports a heartbeat so stalls can be paged
+    let heartbeats = Heartbeats::new();
+    // Per-stage latency, from odds ingest to fills, checked against budgets by monitoring
+    let latencies = Latencies::new();
+
+    // Ratings, schedules, officials and parsed questions shared by the strategies
+    let reference = Arc::new(ReferenceCache::new(storage.clone(), &config.reference_cache));
+
+    // Markets ingestion saw move, for signal generation to re-evaluate between full sweeps
+    let dirty = DirtyMarkets::from_config(&config.strategies.incremental)?;
+
+    let (notifications, notifier) = NotificationService::new(&config)?;
+    info!("✅ Notifications initialized");
 
-    // Initialize components
     let data_pipeline = DataPipeline::new(
-        db_pool.clone(),
-        redis_client.clone(),
+        storage.clone(),
+        events.clone(),
+        reference.clone(),
+        dirty.clone(),
         &config,
+        heartbeats.clone(),
+        notifier.clone(),
     ).await?;
     info!("✅ Data pipeline initialized");
 
-    let risk_manager = RiskManager::new(db_pool.clone(), &config).await?;
+    let risk_manager = RiskManager::new(storage.clone(), events.clone(), &config, notifier.clone()).await?;
     info!("✅ Risk manager initialized");
 
+    // Polygon endpoints with failover between them; the WebSocket ones are only used in live mode
+    let rpc = ChainRpc::new(&config.chain()?, &config.blockchain.failover)?;
+    let http_rpc = rpc.http.clone();
+    tokio::spawn(async move {
+        if let Err(e) = http_rpc.run().await {
+            error!("RPC health check error: {}", e);
+        }
+    });
+    if config.mode == TradingMode::Live {
+        let ws_rpc = rpc.ws.clone();
+        tokio::spawn(async move {
+            if let Err(e) = ws_rpc.run().await {
+                error!("RPC health check error: {}", e);
+            }
+        });
+    }
+
+    let executor = config.create_executor(storage.clone(), &rpc).await?;
     let execution_engine = ExecutionEngine::new(
-        db_pool.clone(),
+        storage.clone(),
+        cache.clone(),
+        events.clone(),
+        executor,
         &config,
         risk_manager.clone(),
+        notifier.clone(),
+        heartbeats.clone(),
+        latencies.clone(),
     ).await?;
     info!("✅ Execution engine initialized");
 
-    let monitoring = MonitoringService::new(db_pool.clone(), &config)?;
+    let monitoring = MonitoringService::new(
+        storage.clone(),
+        events.clone(),
+        &config,
+        heartbeats.clone(),
+        latencies.clone(),
+        notifier.clone(),
+    )?;
     info!("✅ Monitoring service initialized");
 
+    let event_alerts = EventAlerts::new(events.clone(), notifier.clone());
+    tokio::spawn(async move {
+        if let Err(e) = event_alerts.run().await {
+            error!("Event alerts error: {}", e);
+        }
+    });
+
+    let position_monitor = PositionMonitor::new(storage.clone(), &config, notifier.clone(), heartbeats.clone())?;
+    info!("✅ Position monitor initialized");
+
+    // Optional services: admin API, gRPC event stream, historical backfill, streamed order books, odds, officials and pitcher feeds, on-chain funding detection in live mode, drift and consistency monitoring and throttling
+    if config.api.enabled {
+        let paper_storage = config.create_paper_storage().await?;
+        let admin_api = AdminApi::new(storage.clone(), risk_manager.clone(), vault.clone(), paper_storage, &config)?;
+        tokio::spawn(async move {
+            if let Err(e) = admin_api.run().await {
+                error!("Admin API error: {}", e);
+            }
+        });
+    }
+
+    if config.api.grpc.enabled {
+        let grpc_api = GrpcApi::new(storage.clone(), cache.clone(), &config.api.grpc)?;
+        tokio::spawn(async move {
+            if let Err(e) = grpc_api.run().await {
+                error!("gRPC event stream error: {}", e);
+            }
+        });
+        info!("✅ gRPC event stream initialized");
+    }
+
+    if config.backfill.enabled {
+        let backfiller = Backfiller::new(storage.clone(), &config)?;
+        tokio::spawn(async move {
+            if let Err(e) = backfiller.run().await {
+                error!("Backfill error: {}", e);
+            }
+        });
+        info!("✅ Backfill started");
+    }
+
+    if config.polymarket.stream.enabled {
+        let book_stream = BookStream::new(storage.clone(), &config, heartbeats.clone(), notifier.clone())?;
+        tokio::spawn(async move {
+            if let Err(e) = book_stream.run().await {
+                error!("Book stream error: {}", e);
+            }
+        });
+        info!("✅ Book stream initialized");
+    }
+
+    let odds_ingestor = OddsIngestor::new(storage.clone(), dirty.clone(), latencies.clone(), &config)?;
+    if odds_ingestor.has_providers() {
+        tokio::spawn(async move {
+            if let Err(e) = odds_ingestor.run().await {
+                error!("Odds ingestion error: {}", e);
+            }
+        });
+        info!("✅ Odds ingestion initialized");
+    }
+
+    if config.officials.enabled {
+        let officials_ingestor = OfficialsIngestor::new(storage.clone(), reference.clone(), &config)?;
+        tokio::spawn(async move {
+            if let Err(e) = officials_ingestor.run().await {
+                error!("Officials ingestion error: {}", e);
+            }
+        });
+        info!("✅ Officials ingestion initialized");
+    }
+
+    if config.pitchers.enabled {
+        let pitcher_ingestor = PitcherIngestor::new(storage.clone(), &config)?;
+        tokio::spawn(async move {
+            if let Err(e) = pitcher_ingestor.run().await {
+                error!("Pitcher ingestion error: {}", e);
+            }
+        });
+        info!("✅ Pitcher ingestion initialized");
+    }
+
+    if config.mode == TradingMode::Live {
+        let funding_monitor = FundingMonitor::new(storage.clone(), rpc.http.clone(), &config, notifier.clone(), heartbeats.clone())?;
+        tokio::spawn(async move {
+            if let Err(e) = funding_monitor.run().await {
+                error!("Funding monitor error: {}", e);
+            }
+        });
+        info!("✅ Funding monitor initialized");
+
+        let fill_listener = FillListener::new(storage.clone(), events.clone(), rpc.ws.clone(), &config, notifier.clone())?;
+        tokio::spawn(async move {
+            if let Err(e) = fill_listener.run().await {
+                error!("Fill listener error: {}", e);
+            }
+        });
+        info!("✅ Fill listener initialized");
+    }
+
+    if config.risk.drift.enabled {
+        let drift_monitor = DriftMonitor::new(storage.clone(), &config, notifier.clone())?;
+        tokio::spawn(async move {
+            if let Err(e) = drift_monitor.run().await {
+                error!("Drift monitor error: {}", e);
+            }
+        });
+        info!("✅ Drift monitor initialized");
+    }
+
+    if config.risk.consistency.enabled {
+        let consistency_monitor = ConsistencyMonitor::new(storage.clone(), &config, notifier.clone())?;
+        tokio::spawn(async move {
+            if let Err(e) = consistency_monitor.run().await {
+                error!("Consistency monitor error: {}", e);
+            }
+        });
+        info!("✅ Consistency monitor initialized");
+    }
+
+    if config.risk.throttle.enabled {
+        let throttler = StrategyThrottler::new(storage.clone(), &config, notifier.clone())?;
+        tokio::spawn(async move {
+            if let Err(e) = throttler.run().await {
+                error!("Strategy throttler error: {}", e);
+            }
+        });
+        info!("✅ Strategy throttler initialized");
+    }
+
+    if config.risk.weights.enabled {
+        let optimizer = WeightOptimizer::new(storage.clone(), &config, notifier.clone())?;
+        tokio::spawn(async move {
+            if let Err(e) = optimizer.run().await {
+                error!("Strategy weight optimizer error: {}", e);
+            }
+        });
+        info!("✅ Strategy weight optimizer initialized");
+    }
+
+    if config.risk.edge_decay.enabled {
+        let estimator = EdgeDecayEstimator::new(storage.clone(), &config);
+        tokio::spawn(async move {
+            if let Err(e) = estimator.run().await {
+                error!("Edge decay estimator error: {}", e);
+            }
+        });
+        info!("✅ Edge decay estimator initialized");
+    }
+
+    let convergence_tracker = ConvergenceTracker::new(storage.clone(), &config)?;
+    tokio::spawn(async move {
+        if let Err(e) = convergence_tracker.run().await {
+            error!("Convergence tracker error: {}", e);
+        }
+    });
+    info!("✅ Convergence tracker initialized");
+
+    let settlement_monitor = SettlementMonitor::new(storage.clone(), risk_manager, &rpc, &config, notifier, heartbeats)?;
+    tokio::spawn(async move {
+        if let Err(e) = settlement_monitor.run().await {
+            error!("Settlement monitor error: {}", e);
+        }
+    });
+    info!("✅ Settlement monitor initialized");
+
     // Start all services
+    tokio::spawn(async move {
+        if let Err(e) = notifications.run().await {
+            error!("Notification service error: {}", e);
+        }
+    });
+
     let data_handle = tokio::spawn(async move {
         if let Err(e) = data_pipeline.run().await {
             error!("Data pipeline error: {}", e);
@@ -76,6 +338,12 @@ async fn main() -> Result<()> {
         }
     });
 
+    let position_handle = tokio::spawn(async move {
+        if let Err(e) = position_monitor.run().await {
+            error!("Position monitor error: {}", e);
+        }
+    });
+
     let monitoring_handle = tokio::spawn(async move {
         if let Err(e) = monitoring.run().await {
             error!("Monitoring service error: {}", e);
@@ -90,6 +358,7 @@ async fn main() -> Result<()> {
     tokio::select! {
         _ = data_handle => error!("Data pipeline stopped"),
         _ = execution_handle => error!("Execution engine stopped"),
+        _ = position_handle => error!("Position monitor stopped"),
         _ = monitoring_handle => error!("Monitoring service stopped"),
         _ = tokio::signal::ctrl_c() => {
             info!("🛑 Shutdown signal received");
@@ -0,0 +1,192 @@
+use anyhow::{anyhow, bail, Result};
+use bytemuck::{Pod, Zeroable};
+use std::sync::mpsc;
+use wgpu::util::DeviceExt;
+
+use super::{fold_seed, threshold, BatchSimulator};
+
+const SHADER: &str = include_str!("gpu.wgsl");
+
+/// Threads per workgroup, as declared in the shader
+const WORKGROUP_SIZE: u32 = 64;
+
+/// Uniform block shared by both kernels (see `gpu.wgsl`)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    seed: u32,
+    streams: u32,
+    count: u32,
+    _pad: u32,
+}
+
+/// wgpu compute path: one GPU thread per draw
+///
+/// Uses the same counter-based hash as the SIMD path, so Bernoulli draws
+/// match it exactly; Poisson draws run in `f32` and split large rates into
+/// pieces, so they agree in distribution only.
+pub struct GpuBackend {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    layout: wgpu::BindGroupLayout,
+    bernoulli: wgpu::ComputePipeline,
+    poisson: wgpu::ComputePipeline,
+}
+
+impl GpuBackend {
+    pub fn new() -> Result<Self> {
+        pollster::block_on(Self::connect())
+    }
+
+    async fn connect() -> Result<Self> {
+        let instance = wgpu::Instance::default();
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                ..Default::default()
+            })
+            .await
+            .ok_or_else(|| anyhow!("no GPU adapter available"))?;
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor::default(), None)
+            .await?;
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("batch simulation"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+
+        let entry = |binding, ty| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer { ty, has_dynamic_offset: false, min_binding_size: None },
+            count: None,
+        };
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("batch simulation"),
+            entries: &[
+                entry(0, wgpu::BufferBindingType::Uniform),
+                entry(1, wgpu::BufferBindingType::Storage { read_only: true }),
+                entry(2, wgpu::BufferBindingType::Storage { read_only: false }),
+            ],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("batch simulation"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = |entry_point| device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some(entry_point),
+            layout: Some(&pipeline_layout),
+            module: &module,
+            entry_point,
+        });
+        let bernoulli = pipeline("bernoulli");
+        let poisson = pipeline("poisson");
+
+        Ok(Self { device, queue, layout, bernoulli, poisson })
+    }
+
+    /// Run a kernel over a `count` x `streams` grid and read back one `u32`
+    /// per cell
+    fn dispatch(&self, pipeline: &wgpu::ComputePipeline, params: Params, input: &[u32]) -> Result<Vec<u32>> {
+        let cells = params.count as u64 * params.streams as u64;
+        if cells == 0 {
+            return Ok(Vec::new());
+        }
+
+        let groups_x = params.count.div_ceil(WORKGROUP_SIZE);
+        let limits = self.device.limits();
+        if groups_x > limits.max_compute_workgroups_per_dimension
+            || params.streams > limits.max_compute_workgroups_per_dimension
+        {
+            bail!("batch of {} x {} exceeds GPU dispatch limits", params.count, params.streams);
+        }
+        let size = cells * 4;
+        if size > limits.max_storage_buffer_binding_size as u64 {
+            bail!("batch of {} draws exceeds GPU buffer limits", cells);
+        }
+
+        let uniform = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("params"),
+            contents: bytemuck::bytes_of(&params),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        // Storage bindings may not be empty
+        let input = if input.is_empty() { &[0][..] } else { input };
+        let input = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("input"),
+            contents: bytemuck::cast_slice(input),
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+        let output = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("output"),
+            size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("readback"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("batch simulation"),
+            layout: &self.layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: uniform.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: input.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 2, resource: output.as_entire_binding() },
+            ],
+        });
+
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(groups_x, params.streams, 1);
+        }
+        encoder.copy_buffer_to_buffer(&output, 0, &readback, 0, size);
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        let (sender, receiver) = mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        receiver.recv()??;
+
+        let view = slice.get_mapped_range();
+        let values = bytemuck::cast_slice::<u8, u32>(&view).to_vec();
+        drop(view);
+        readback.unmap();
+        Ok(values)
+    }
+}
+
+impl BatchSimulator for GpuBackend {
+    fn name(&self) -> &'static str {
+        "gpu"
+    }
+
+    fn bernoulli(&self, probabilities: &[f64], runs: u32, seed: u64) -> Result<Vec<bool>> {
+        let thresholds: Vec<u32> = probabilities.iter().map(|&p| threshold(p)).collect();
+        let params = Params { seed: fold_seed(seed), streams: runs, count: thresholds.len() as u32, _pad: 0 };
+
+        Ok(self.dispatch(&self.bernoulli, params, &thresholds)?
+            .into_iter()
+            .map(|flag| flag != 0)
+            .collect())
+    }
+
+    fn poisson(&self, rates: &[f64], runs: u32, seed: u64) -> Result<Vec<u32>> {
+        let bits: Vec<u32> = rates.iter().map(|&rate| (rate as f32).to_bits()).collect();
+        let params = Params { seed: fold_seed(seed), streams: rates.len() as u32, count: runs, _pad: 0 };
+
+        self.dispatch(&self.poisson, params, &bits)
+    }
+}
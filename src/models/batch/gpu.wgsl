@@ -0,0 +1,81 @@
+// Batch simulation kernels; hashing mirrors `pcg_hash`/`stream_key` in mod.rs
+
+struct Params {
+    seed: u32,
+    // Runs for bernoulli, rates for poisson
+    streams: u32,
+    // Events per run for bernoulli, runs per rate for poisson
+    count: u32,
+    _pad: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+// Thresholds for bernoulli, f32 rate bits for poisson
+@group(0) @binding(1) var<storage, read> input: array<u32>;
+@group(0) @binding(2) var<storage, read_write> output: array<u32>;
+
+fn pcg_hash(v: u32) -> u32 {
+    let state = v * 747796405u + 2891336453u;
+    let word = ((state >> ((state >> 28u) + 4u)) ^ state) * 277803737u;
+    return (word >> 22u) ^ word;
+}
+
+fn stream_key(stream: u32) -> u32 {
+    return pcg_hash(stream ^ pcg_hash(params.seed));
+}
+
+fn unit(bits: u32) -> f32 {
+    return (f32(bits) + 0.5) / 4294967296.0;
+}
+
+// x: event, y: run
+@compute @workgroup_size(64)
+fn bernoulli(@builtin(global_invocation_id) id: vec3<u32>) {
+    let event = id.x;
+    let run = id.y;
+    if (event >= params.count || run >= params.streams) {
+        return;
+    }
+
+    let bits = pcg_hash(event ^ stream_key(run));
+    output[run * params.count + event] = select(0u, 1u, bits < input[event]);
+}
+
+// Rates above this are split into pieces: exp(-rate) underflows in f32
+const MAX_PIECE_RATE: f32 = 60.0;
+
+// x: run, y: rate
+@compute @workgroup_size(64)
+fn poisson(@builtin(global_invocation_id) id: vec3<u32>) {
+    let run = id.x;
+    let stream = id.y;
+    if (run >= params.count || stream >= params.streams) {
+        return;
+    }
+
+    // A sum of independent Poissons is Poisson in the summed rate
+    let rate = bitcast<f32>(input[stream]);
+    let pieces = max(u32(ceil(rate / MAX_PIECE_RATE)), 1u);
+    let piece_rate = rate / f32(pieces);
+    let cap = u32(piece_rate + 50.0 * sqrt(piece_rate) + 50.0);
+    let base = pcg_hash(run ^ stream_key(stream));
+
+    var total = 0u;
+    for (var piece = 0u; piece < pieces; piece++) {
+        let u = unit(pcg_hash(base + piece));
+        var k = 0u;
+        var p = exp(-piece_rate);
+        var s = p;
+        loop {
+            if (u <= s || k >= cap) {
+                break;
+            }
+            k += 1u;
+            p *= piece_rate / f32(k);
+            s += p;
+        }
+        total += k;
+    }
+
+    output[stream * params.count + run] = total;
+}
@@ -0,0 +1,160 @@
+use anyhow::Result;
+use std::sync::Arc;
+
+use crate::config::{SimulationBackend, SimulationConfig};
+
+#[cfg(feature = "gpu")]
+mod gpu;
+mod scalar;
+mod simd;
+
+#[cfg(feature = "gpu")]
+pub use gpu::GpuBackend;
+pub use scalar::ScalarBackend;
+pub use simd::SimdBackend;
+
+/// Executes the random draws behind the Monte Carlo models in bulk
+///
+/// Models describe what to draw (fixture win probabilities, scoring rates)
+/// and keep their own bookkeeping; backends only decide how the draws are
+/// produced. Results are deterministic for a given seed within a backend,
+/// not across backends.
+pub trait BatchSimulator: Send + Sync {
+    /// Short name used in logs and benchmarks
+    fn name(&self) -> &'static str;
+
+    /// Whether each event happened in each run, run-major
+    /// (`runs * probabilities.len()` flags)
+    fn bernoulli(&self, probabilities: &[f64], runs: u32, seed: u64) -> Result<Vec<bool>>;
+
+    /// Poisson draws for each rate in each run, rate-major
+    /// (`rates.len() * runs` counts)
+    fn poisson(&self, rates: &[f64], runs: u32, seed: u64) -> Result<Vec<u32>>;
+}
+
+/// Build the backend named in the `simulation` config
+pub fn backend_from_config(config: &SimulationConfig) -> Result<Arc<dyn BatchSimulator>> {
+    Ok(match config.backend {
+        SimulationBackend::Scalar => Arc::new(ScalarBackend),
+        SimulationBackend::Simd => Arc::new(SimdBackend),
+        #[cfg(feature = "gpu")]
+        SimulationBackend::Gpu => Arc::new(GpuBackend::new()?),
+        #[cfg(not(feature = "gpu"))]
+        SimulationBackend::Gpu => anyhow::bail!("simulation.backend is gpu but the gpu feature is not enabled"),
+    })
+}
+
+/// PCG output hash: a counter-based generator, so any draw can be computed
+/// independently of the others (one lane or GPU thread per draw)
+///
+/// Mirrored in `gpu.wgsl`; keep the two in step.
+#[inline(always)]
+fn pcg_hash(v: u32) -> u32 {
+    let state = v.wrapping_mul(747_796_405).wrapping_add(2_891_336_453);
+    let word = ((state >> ((state >> 28) + 4)) ^ state).wrapping_mul(277_803_737);
+    (word >> 22) ^ word
+}
+
+/// Key for one stream of draws (a run, or a rate) under a seed
+#[inline(always)]
+fn stream_key(seed: u32, stream: u32) -> u32 {
+    pcg_hash(stream ^ pcg_hash(seed))
+}
+
+/// Draws below this many `u32` values happen with probability `p`
+fn threshold(p: f64) -> u32 {
+    (p.clamp(0.0, 1.0) * 4_294_967_296.0).min(u32::MAX as f64) as u32
+}
+
+/// Uniform in (0, 1) from 32 random bits
+#[inline(always)]
+fn unit(bits: u32) -> f64 {
+    (bits as f64 + 0.5) / 4_294_967_296.0
+}
+
+fn fold_seed(seed: u64) -> u32 {
+    (seed ^ (seed >> 32)) as u32
+}
+
+/// Largest count an inversion sampler will walk to for `rate`
+///
+/// Far in the tail; guards against rounding leaving the cumulative
+/// probability just short of the uniform.
+fn poisson_cap(rate: f64) -> u32 {
+    (rate + 50.0 * rate.sqrt() + 50.0) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn backends() -> Vec<Box<dyn BatchSimulator>> {
+        #[cfg_attr(not(feature = "gpu"), allow(unused_mut))]
+        let mut backends: Vec<Box<dyn BatchSimulator>> = vec![Box::new(ScalarBackend), Box::new(SimdBackend)];
+        #[cfg(feature = "gpu")]
+        if let Ok(gpu) = GpuBackend::new() {
+            backends.push(Box::new(gpu));
+        }
+        backends
+    }
+
+    #[test]
+    fn draws_match_their_distributions() {
+        let probabilities = [0.0, 0.1, 0.5, 0.9, 1.0, 0.3, 0.7, 0.5, 0.25];
+        let rates = [0.5, 24.0, 225.0];
+        let runs = 20_000;
+
+        for backend in backends() {
+            let flags = backend.bernoulli(&probabilities, runs, 7).unwrap();
+            assert_eq!(flags.len(), probabilities.len() * runs as usize);
+            for (event, &p) in probabilities.iter().enumerate() {
+                let hits = flags.iter().skip(event).step_by(probabilities.len()).filter(|&&f| f).count();
+                let share = hits as f64 / runs as f64;
+                assert!((share - p).abs() < 0.015, "{} event {}: {} vs {}", backend.name(), event, share, p);
+            }
+
+            let counts = backend.poisson(&rates, runs, 7).unwrap();
+            for (i, &rate) in rates.iter().enumerate() {
+                let draws = &counts[i * runs as usize..][..runs as usize];
+                let mean = draws.iter().map(|&c| c as f64).sum::<f64>() / runs as f64;
+                assert!((mean - rate).abs() < 0.05 * rate + 0.05, "{} rate {}: mean {}", backend.name(), rate, mean);
+            }
+        }
+    }
+
+    #[test]
+    fn draws_are_reproducible_per_seed() {
+        for backend in backends() {
+            let first = backend.poisson(&[3.0, 45.0], 500, 11).unwrap();
+            assert_eq!(first, backend.poisson(&[3.0, 45.0], 500, 11).unwrap());
+            assert_ne!(first, backend.poisson(&[3.0, 45.0], 500, 12).unwrap());
+        }
+    }
+
+    /// `cargo test --release [--features gpu] batch_throughput -- --ignored --nocapture`
+    #[test]
+    #[ignore = "benchmark"]
+    fn batch_throughput() {
+        // A full MLB regular season, and a slate of NBA totals
+        let fixtures: Vec<f64> = (0..2_430).map(|i| 0.35 + 0.3 * (i % 7) as f64 / 6.0).collect();
+        let rates: Vec<f64> = (0..100).map(|i| 200.0 + i as f64 * 0.5).collect();
+
+        for backend in backends() {
+            let start = Instant::now();
+            backend.bernoulli(&fixtures, 1_024, 1).unwrap();
+            let bernoulli = start.elapsed();
+
+            let start = Instant::now();
+            backend.poisson(&rates, 10_000, 1).unwrap();
+            let poisson = start.elapsed();
+
+            println!(
+                "{:>6}: bernoulli {:>5.1} M draws/s, poisson {:>5.1} M draws/s",
+                backend.name(),
+                (fixtures.len() * 1_024) as f64 / bernoulli.as_secs_f64() / 1e6,
+                (rates.len() * 10_000) as f64 / poisson.as_secs_f64() / 1e6,
+            );
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use anyhow::Result;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::{poisson_cap, BatchSimulator};
+
+/// One draw at a time from a seeded `StdRng`
+///
+/// The reference the other backends are benchmarked and checked against.
+pub struct ScalarBackend;
+
+impl BatchSimulator for ScalarBackend {
+    fn name(&self) -> &'static str {
+        "scalar"
+    }
+
+    fn bernoulli(&self, probabilities: &[f64], runs: u32, seed: u64) -> Result<Vec<bool>> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut flags = Vec::with_capacity(probabilities.len() * runs as usize);
+        for _ in 0..runs {
+            flags.extend(probabilities.iter().map(|&p| rng.gen_bool(p.clamp(0.0, 1.0))));
+        }
+        Ok(flags)
+    }
+
+    fn poisson(&self, rates: &[f64], runs: u32, seed: u64) -> Result<Vec<u32>> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut counts = Vec::with_capacity(rates.len() * runs as usize);
+        for &rate in rates {
+            counts.extend((0..runs).map(|_| sample_poisson(rate, &mut rng)));
+        }
+        Ok(counts)
+    }
+}
+
+/// Inversion: walk the CDF until it passes a uniform
+fn sample_poisson<R: Rng>(rate: f64, rng: &mut R) -> u32 {
+    let cap = poisson_cap(rate);
+    let mut k = 0;
+    let mut p = (-rate).exp();
+    let mut s = p;
+    let u: f64 = rng.gen();
+
+    while u > s && k < cap {
+        k += 1;
+        p *= rate / k as f64;
+        s += p;
+    }
+
+    k
+}
@@ -0,0 +1,102 @@
+use anyhow::Result;
+
+use super::{fold_seed, pcg_hash, poisson_cap, stream_key, threshold, unit, BatchSimulator};
+
+/// Draws processed together; wide enough for AVX2 on `u32`/`f64` pairs
+const LANES: usize = 8;
+
+/// Counter-based draws laid out in fixed-width lanes
+///
+/// Every draw is a hash of (seed, stream, index), so a block of `LANES`
+/// draws is straight-line arithmetic over arrays, which the compiler turns
+/// into vector instructions; the Poisson walk advances all lanes together
+/// with selects instead of branches. Portable: no intrinsics or nightly
+/// features, and falls back to scalar code where vectors are unavailable.
+pub struct SimdBackend;
+
+impl BatchSimulator for SimdBackend {
+    fn name(&self) -> &'static str {
+        "simd"
+    }
+
+    fn bernoulli(&self, probabilities: &[f64], runs: u32, seed: u64) -> Result<Vec<bool>> {
+        let seed = fold_seed(seed);
+        let thresholds: Vec<u32> = probabilities.iter().map(|&p| threshold(p)).collect();
+        let n = thresholds.len();
+        let mut flags = vec![false; n * runs as usize];
+
+        for (run, row) in flags.chunks_exact_mut(n.max(1)).enumerate() {
+            let key = stream_key(seed, run as u32);
+            let mut event = 0;
+            while event + LANES <= n {
+                let bits = hash_lanes(key, event as u32);
+                for lane in 0..LANES {
+                    row[event + lane] = bits[lane] < thresholds[event + lane];
+                }
+                event += LANES;
+            }
+            for event in event..n {
+                row[event] = pcg_hash(event as u32 ^ key) < thresholds[event];
+            }
+        }
+
+        Ok(flags)
+    }
+
+    fn poisson(&self, rates: &[f64], runs: u32, seed: u64) -> Result<Vec<u32>> {
+        let seed = fold_seed(seed);
+        let runs = runs as usize;
+        let mut counts = vec![0; rates.len() * runs];
+
+        for (i, &rate) in rates.iter().enumerate() {
+            let key = stream_key(seed, i as u32);
+            let out = &mut counts[i * runs..][..runs];
+            let start = (-rate).exp();
+            let cap = poisson_cap(rate);
+
+            let mut run = 0;
+            while run < runs {
+                let bits = hash_lanes(key, run as u32);
+                let width = LANES.min(runs - run);
+                let draws = poisson_lanes(rate, start, cap, &bits);
+                out[run..run + width].copy_from_slice(&draws[..width]);
+                run += LANES;
+            }
+        }
+
+        Ok(counts)
+    }
+}
+
+#[inline(always)]
+fn hash_lanes(key: u32, first: u32) -> [u32; LANES] {
+    let mut bits = [0; LANES];
+    for (lane, b) in bits.iter_mut().enumerate() {
+        *b = pcg_hash(first.wrapping_add(lane as u32) ^ key);
+    }
+    bits
+}
+
+/// Inversion sampling in every lane at once
+#[inline(always)]
+fn poisson_lanes(rate: f64, start: f64, cap: u32, bits: &[u32; LANES]) -> [u32; LANES] {
+    let u = bits.map(unit);
+    let mut k = [0u32; LANES];
+    let mut p = [start; LANES];
+    let mut s = [start; LANES];
+
+    loop {
+        let mut active = 0;
+        for lane in 0..LANES {
+            let step = u[lane] > s[lane] && k[lane] < cap;
+            k[lane] += step as u32;
+            let next = p[lane] * rate / k[lane].max(1) as f64;
+            p[lane] = if step { next } else { p[lane] };
+            s[lane] += if step { next } else { 0.0 };
+            active += step as u32;
+        }
+        if active == 0 {
+            return k;
+        }
+    }
+}
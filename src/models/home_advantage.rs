@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+
+use crate::types::{Game, Sport, TeamRating};
+use super::ModelParameters;
+
+/// Pseudo-games of the league prior behind the league-wide estimate
+const LEAGUE_PRIOR_GAMES: f64 = 500.0;
+
+/// Pseudo-games of the structural estimate behind each venue's, and the
+/// ridge penalty on the altitude and roof terms
+const VENUE_PRIOR_GAMES: f64 = 80.0;
+
+/// Home edge assumed before any results, in points (runs for MLB), unless
+/// a parameter sheet sets one
+fn league_prior(sport: Sport) -> f64 {
+    if let Some(home_advantage) = ModelParameters::for_sport(sport).home_advantage {
+        return home_advantage;
+    }
+
+    match sport {
+        Sport::NFL => 1.5,
+        Sport::NBA => 2.5,
+        Sport::MLB => 0.2,
+        Sport::PremierLeague => 0.3,
+    }
+}
+
+/// Home advantage at one team's home venue
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VenueAdvantage {
+    /// Effective games behind `raw`
+    pub games: f64,
+    /// From this team's home and away margins alone; `None` without both
+    pub raw: Option<f64>,
+    /// League edge plus this venue's altitude and roof terms
+    pub structural: f64,
+    /// `raw` shrunk toward `structural`
+    pub estimate: f64,
+}
+
+/// Home advantage per league and per venue, estimated from past results
+///
+/// The league edge is the mean home margin shrunk toward a prior. A team's
+/// own venue edge is its home margin less its away margin less the league
+/// edge (its strength cancels; opponents' home edge is the league's), which
+/// is noisy, so each venue is shrunk toward a structural estimate: the
+/// league edge plus altitude and indoor terms, fitted by ridge regression to
+/// how far venues stand above or below the league.
+#[derive(Debug, Clone)]
+pub struct HomeAdvantage {
+    pub league: f64,
+    /// Extra edge per 1000 m of venue elevation
+    pub altitude_per_km: f64,
+    /// Extra edge for domes and closed roofs
+    pub indoor: f64,
+    venues: HashMap<String, VenueAdvantage>,
+}
+
+impl HomeAdvantage {
+    pub fn estimate(sport: Sport, teams: &[TeamRating], history: &[Game]) -> Self {
+        let margins: Vec<(&Game, f64)> = history.iter()
+            .filter_map(|g| Some((g, (g.home_score? - g.away_score?) as f64)))
+            .collect();
+
+        let n = margins.len() as f64;
+        let league = (margins.iter().map(|(_, m)| m).sum::<f64>() + league_prior(sport) * LEAGUE_PRIOR_GAMES)
+            / (n + LEAGUE_PRIOR_GAMES);
+
+        // (raw edge, effective games) per team with both home and away results
+        let raw: HashMap<&str, (f64, f64)> = teams.iter()
+            .filter_map(|team| {
+                let home: Vec<f64> = margins.iter()
+                    .filter(|(g, _)| g.home_team == team.team)
+                    .map(|(_, m)| *m)
+                    .collect();
+                let away: Vec<f64> = margins.iter()
+                    .filter(|(g, _)| g.away_team == team.team)
+                    .map(|(_, m)| -m)
+                    .collect();
+                if home.is_empty() || away.is_empty() {
+                    return None;
+                }
+
+                let mean = |v: &[f64]| v.iter().sum::<f64>() / v.len() as f64;
+                let edge = mean(&home) - mean(&away) - league;
+                // Harmonic mean: the edge is only as precise as the scarcer side
+                let games = 2.0 / (1.0 / home.len() as f64 + 1.0 / away.len() as f64);
+                Some((team.team.as_str(), (edge, games)))
+            })
+            .collect();
+
+        let features = |team: &TeamRating| [
+            team.elevation_m.unwrap_or(0.0) / 1000.0,
+            if team.indoor { 1.0 } else { 0.0 },
+        ];
+        // The structural terms are shrunk toward a venue like any other
+        let [altitude_per_km, indoor] = fit_ridge(teams.iter().filter_map(|team| {
+            let (edge, games) = raw.get(team.team.as_str())?;
+            Some((features(team), edge - league, *games))
+        }));
+
+        let venues = teams.iter()
+            .map(|team| {
+                let [km, roof] = features(team);
+                let structural = league + altitude_per_km * km + indoor * roof;
+                let (raw, games) = match raw.get(team.team.as_str()) {
+                    Some(&(edge, games)) => (Some(edge), games),
+                    None => (None, 0.0),
+                };
+                let estimate = raw.map_or(structural, |raw| {
+                    (raw * games + structural * VENUE_PRIOR_GAMES) / (games + VENUE_PRIOR_GAMES)
+                });
+
+                (team.team.clone(), VenueAdvantage { games, raw, structural, estimate })
+            })
+            .collect();
+
+        Self { league, altitude_per_km, indoor, venues }
+    }
+
+    /// Edge for games at this team's venue; the league edge if it is unknown
+    pub fn for_venue(&self, team: &str) -> f64 {
+        self.venues.get(team).map_or(self.league, |v| v.estimate)
+    }
+
+    pub fn venues(&self) -> impl Iterator<Item = (&String, &VenueAdvantage)> {
+        self.venues.iter()
+    }
+}
+
+/// Weighted ridge regression without intercept on two features
+fn fit_ridge(rows: impl Iterator<Item = ([f64; 2], f64, f64)>) -> [f64; 2] {
+    // Normal equations (X'WX + λI) β = X'Wy
+    let (mut a, mut b, mut c) = (VENUE_PRIOR_GAMES, 0.0, VENUE_PRIOR_GAMES);
+    let (mut y0, mut y1) = (0.0, 0.0);
+    for ([x0, x1], y, w) in rows {
+        a += w * x0 * x0;
+        b += w * x0 * x1;
+        c += w * x1 * x1;
+        y0 += w * x0 * y;
+        y1 += w * x1 * y;
+    }
+
+    let det = a * c - b * b;
+    [(c * y0 - b * y1) / det, (a * y1 - b * y0) / det]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn team(name: &str, elevation_m: f64) -> TeamRating {
+        TeamRating {
+            sport: Sport::NFL,
+            team: name.to_string(),
+            conference: "AFC".to_string(),
+            division: "AFC West".to_string(),
+            rating: 0.0,
+            latitude: None,
+            longitude: None,
+            elevation_m: Some(elevation_m),
+            indoor: false,
+        }
+    }
+
+    fn game(home: &str, away: &str, home_score: i32, away_score: i32) -> Game {
+        Game {
+            game_id: format!("{}-{}", home, away),
+            sport: Sport::NFL,
+            season: 2024,
+            home_team: home.to_string(),
+            away_team: away.to_string(),
+            start_time: Utc::now() - Duration::days(30),
+            home_score: Some(home_score),
+            away_score: Some(away_score),
+        }
+    }
+
+    #[test]
+    fn falls_back_to_the_league_prior() {
+        let teams = vec![team("Chiefs", 250.0)];
+        let home = HomeAdvantage::estimate(Sport::NFL, &teams, &[]);
+
+        assert_eq!(home.league, 1.5);
+        assert_eq!(home.for_venue("Chiefs"), 1.5);
+        assert_eq!(home.for_venue("Unknown"), 1.5);
+    }
+
+    #[test]
+    fn an_average_venue_gets_the_league_edge() {
+        let teams = vec![team("Chiefs", 250.0), team("Raiders", 600.0), team("Chargers", 30.0)];
+        // Evenly matched teams that all win by 2 at home
+        let mut history = Vec::new();
+        for _ in 0..1000 {
+            for (home, away) in [
+                ("Chiefs", "Raiders"), ("Raiders", "Chiefs"), ("Chiefs", "Chargers"),
+                ("Chargers", "Chiefs"), ("Raiders", "Chargers"), ("Chargers", "Raiders"),
+            ] {
+                history.push(game(home, away, 22, 20));
+            }
+        }
+        let home = HomeAdvantage::estimate(Sport::NFL, &teams, &history);
+
+        assert!((home.league - 2.0).abs() < 0.1, "{}", home.league);
+        for (team, venue) in home.venues() {
+            assert!((venue.raw.unwrap() - home.league).abs() < 0.1, "{} {:?}", team, venue);
+            assert!((venue.estimate - home.league).abs() < 0.1, "{} {:?}", team, venue);
+        }
+    }
+
+    #[test]
+    fn altitude_venues_earn_a_shrunk_extra_edge() {
+        let teams = vec![team("Broncos", 1600.0), team("Chiefs", 250.0), team("Raiders", 600.0)];
+        // Denver wins big at home and the others split evenly
+        let mut history = Vec::new();
+        for _ in 0..40 {
+            history.push(game("Broncos", "Chiefs", 27, 17));
+            history.push(game("Broncos", "Raiders", 27, 17));
+            history.push(game("Chiefs", "Broncos", 20, 20));
+            history.push(game("Raiders", "Broncos", 20, 20));
+            history.push(game("Chiefs", "Raiders", 22, 20));
+            history.push(game("Raiders", "Chiefs", 22, 20));
+        }
+        let home = HomeAdvantage::estimate(Sport::NFL, &teams, &history);
+        let denver = home.venues().find(|(t, _)| *t == "Broncos").unwrap().1;
+
+        assert!(home.altitude_per_km > 0.0);
+        assert!(home.for_venue("Broncos") > home.for_venue("Chiefs"));
+        // Between the structural estimate and Denver's own results
+        assert!(denver.estimate > denver.structural && denver.estimate < denver.raw.unwrap());
+    }
+}
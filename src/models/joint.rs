@@ -0,0 +1,189 @@
+use anyhow::{bail, Result};
+use rand::Rng;
+use statrs::distribution::{ContinuousCDF, Normal};
+
+use crate::types::Sport;
+use super::ModelParameters;
+
+/// Condition on a game's final score, from one team's point of view
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Leg {
+    /// The team wins outright
+    Win,
+    Lose,
+    /// Combined score above the line
+    Over(f64),
+    Under(f64),
+}
+
+impl Leg {
+    fn holds(&self, margin: f64, total: f64) -> bool {
+        match *self {
+            Leg::Win => margin > 0.0,
+            Leg::Lose => margin < 0.0,
+            Leg::Over(line) => total > line,
+            Leg::Under(line) => total < line,
+        }
+    }
+}
+
+/// Final-score dispersion for a sport
+///
+/// Margin and total are modelled as correlated normals. `correlation` is
+/// between the favorite's margin and the total: favorites tend to win by more
+/// in high-scoring games.
+#[derive(Debug, Clone, Copy)]
+pub struct ScoringProfile {
+    pub margin_sd: f64,
+    pub total_sd: f64,
+    pub correlation: f64,
+}
+
+impl ScoringProfile {
+    /// Historical dispersion, with any parameter sheet overrides; `None` for
+    /// sports a normal score model does not fit (draws)
+    pub fn for_sport(sport: Sport) -> Option<Self> {
+        let profile = match sport {
+            Sport::NFL => Self { margin_sd: 13.5, total_sd: 13.8, correlation: 0.12 },
+            Sport::NBA => Self { margin_sd: 12.5, total_sd: 18.5, correlation: 0.08 },
+            Sport::MLB => Self { margin_sd: 4.2, total_sd: 4.5, correlation: 0.10 },
+            Sport::PremierLeague => return None,
+        };
+
+        let sheet = ModelParameters::for_sport(sport);
+        Some(Self {
+            margin_sd: sheet.margin_sd.unwrap_or(profile.margin_sd),
+            total_sd: sheet.total_sd.unwrap_or(profile.total_sd),
+            correlation: sheet.correlation.unwrap_or(profile.correlation),
+        })
+    }
+}
+
+/// Joint distribution of one team's margin and the game total
+#[derive(Debug, Clone, Copy)]
+pub struct ScoreDistribution {
+    margin_mean: f64,
+    margin_sd: f64,
+    total_mean: f64,
+    total_sd: f64,
+    /// Correlation between this team's margin and the total
+    correlation: f64,
+}
+
+impl ScoreDistribution {
+    /// Fit means so the model reproduces the market's win and over probabilities
+    pub fn calibrate(profile: ScoringProfile, win_probability: f64, total_line: f64, over_probability: f64) -> Result<Self> {
+        for p in [win_probability, over_probability] {
+            if !(p > 0.0 && p < 1.0) {
+                bail!("calibration probability {} outside (0, 1)", p);
+            }
+        }
+
+        let standard = Normal::new(0.0, 1.0)?;
+        let margin_mean = profile.margin_sd * standard.inverse_cdf(win_probability);
+        let total_mean = total_line + profile.total_sd * standard.inverse_cdf(over_probability);
+
+        // The favorite's margin rises with the total, so the underdog's falls
+        let correlation = if margin_mean >= 0.0 { profile.correlation } else { -profile.correlation };
+
+        Ok(Self {
+            margin_mean,
+            margin_sd: profile.margin_sd,
+            total_mean,
+            total_sd: profile.total_sd,
+            correlation,
+        })
+    }
+
+    /// Draw (margin, total)
+    fn sample<R: Rng>(&self, standard: &Normal, rng: &mut R) -> (f64, f64) {
+        let z1: f64 = rng.sample(standard);
+        let z2: f64 = rng.sample(standard);
+        let z_total = self.correlation * z1 + (1.0 - self.correlation.powi(2)).sqrt() * z2;
+
+        (
+            self.margin_mean + self.margin_sd * z1,
+            self.total_mean + self.total_sd * z_total,
+        )
+    }
+}
+
+/// Probability that every leg holds, next to what independence would give
+#[derive(Debug, Clone, Copy)]
+pub struct ComboEstimate {
+    pub joint: f64,
+    /// Product of the legs' marginal probabilities
+    pub independent: f64,
+    pub simulations: u32,
+}
+
+/// Monte Carlo pricing of combination ("A wins AND over X") markets
+pub fn price_combo<R: Rng>(distribution: &ScoreDistribution, legs: &[Leg], simulations: u32, rng: &mut R) -> Result<ComboEstimate> {
+    if legs.is_empty() || simulations == 0 {
+        bail!("combo needs at least one leg and one simulation");
+    }
+
+    let standard = Normal::new(0.0, 1.0)?;
+    let mut joint_hits = 0u32;
+    let mut leg_hits = vec![0u32; legs.len()];
+
+    for _ in 0..simulations {
+        let (margin, total) = distribution.sample(&standard, rng);
+
+        let mut all = true;
+        for (hits, leg) in leg_hits.iter_mut().zip(legs) {
+            if leg.holds(margin, total) {
+                *hits += 1;
+            } else {
+                all = false;
+            }
+        }
+        if all {
+            joint_hits += 1;
+        }
+    }
+
+    let n = simulations as f64;
+    Ok(ComboEstimate {
+        joint: joint_hits as f64 / n,
+        independent: leg_hits.iter().map(|&h| h as f64 / n).product(),
+        simulations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn nfl(win: f64, over: f64) -> ScoreDistribution {
+        ScoreDistribution::calibrate(ScoringProfile::for_sport(Sport::NFL).unwrap(), win, 45.5, over).unwrap()
+    }
+
+    #[test]
+    fn calibration_reproduces_marginals() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let distribution = nfl(0.7, 0.5);
+
+        let win = price_combo(&distribution, &[Leg::Win], 50_000, &mut rng).unwrap();
+        let over = price_combo(&distribution, &[Leg::Over(45.5)], 50_000, &mut rng).unwrap();
+
+        assert!((win.joint - 0.7).abs() < 0.01);
+        assert!((over.joint - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn favorite_and_over_is_positively_correlated() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let estimate = price_combo(&nfl(0.7, 0.5), &[Leg::Win, Leg::Over(45.5)], 50_000, &mut rng).unwrap();
+
+        assert!(estimate.joint > estimate.independent);
+    }
+
+    #[test]
+    fn rejects_degenerate_marginals() {
+        let profile = ScoringProfile::for_sport(Sport::NBA).unwrap();
+        assert!(ScoreDistribution::calibrate(profile, 1.0, 220.5, 0.5).is_err());
+    }
+}
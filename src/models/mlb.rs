@@ -0,0 +1,176 @@
+use anyhow::{bail, Result};
+use statrs::distribution::{Discrete, DiscreteCDF, Poisson};
+use std::collections::HashMap;
+
+use crate::config::MlbConfig;
+use crate::types::{BullpenUsage, ProbablePitcher};
+
+/// Runs per side past which the score grid is cut off
+const MAX_RUNS: u64 = 30;
+
+/// Runs per team per game for sides without fitted ratings
+pub const LEAGUE_RUNS: f64 = 4.4;
+
+/// MLB scoring rates adjusted for who pitches and where
+///
+/// Team ratings barely separate MLB sides; the starters do. Each side's
+/// expected runs are split between the innings the opposing starter is
+/// expected to cover, at that starter's ERA shrunk toward the league by
+/// innings pitched, and the rest at the opposing bullpen's rate, raised by
+/// the relief innings it has thrown lately. Both sides are then scaled by
+/// the home park's run factor.
+#[derive(Debug, Clone)]
+pub struct MlbModel {
+    league_era: f64,
+    era_prior_innings: f64,
+    /// Share of the game the starter pitches
+    starter_share: f64,
+    fatigue_per_inning: f64,
+    max_fatigue: f64,
+    park_factors: HashMap<String, f64>,
+}
+
+/// Expected runs of one game and what they imply
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MlbGameOdds {
+    pub home_runs: f64,
+    pub away_runs: f64,
+    /// Extra innings included
+    pub home_win: f64,
+}
+
+impl MlbGameOdds {
+    /// Probability the game's total runs go over `line`
+    pub fn over(&self, line: f64) -> Result<f64> {
+        if !(line >= 0.0) {
+            bail!("invalid total line {}", line);
+        }
+        let total = Poisson::new(self.home_runs + self.away_runs)?;
+        Ok(1.0 - total.cdf(line.floor() as u64))
+    }
+}
+
+/// Relief innings `team` threw across `usage`
+pub fn bullpen_innings(usage: &[BullpenUsage], team: &str) -> f64 {
+    usage.iter()
+        .filter(|u| u.team.eq_ignore_ascii_case(team))
+        .map(|u| u.innings)
+        .sum()
+}
+
+impl MlbModel {
+    pub fn from_config(config: &MlbConfig) -> Result<Self> {
+        if !(config.league_era > 0.0) {
+            bail!("strategies.mlb.league_era must be positive");
+        }
+        if !(0.0..=9.0).contains(&config.starter_innings) {
+            bail!("strategies.mlb.starter_innings must be between 0 and 9");
+        }
+        if config.park_factors.values().any(|factor| !(*factor > 0.0)) {
+            bail!("strategies.mlb.park_factors must be positive");
+        }
+
+        Ok(Self {
+            league_era: config.league_era,
+            era_prior_innings: config.era_prior_innings.max(0.0),
+            starter_share: config.starter_innings / 9.0,
+            fatigue_per_inning: config.bullpen_fatigue_per_inning.max(0.0),
+            max_fatigue: config.max_bullpen_fatigue.max(0.0),
+            park_factors: config.park_factors.clone(),
+        })
+    }
+
+    /// Runs a starter allows relative to the league, 1.0 before a first start
+    fn starter_factor(&self, pitcher: &ProbablePitcher) -> f64 {
+        let Some(era) = pitcher.era else {
+            return 1.0;
+        };
+        let innings = pitcher.innings_pitched.max(0.0);
+        if innings + self.era_prior_innings <= 0.0 {
+            return 1.0;
+        }
+        let shrunk = (era * innings + self.league_era * self.era_prior_innings) / (innings + self.era_prior_innings);
+        shrunk / self.league_era
+    }
+
+    /// Runs a bullpen allows relative to the league after throwing `innings` lately
+    fn bullpen_factor(&self, innings: f64) -> f64 {
+        1.0 + (self.fatigue_per_inning * innings.max(0.0)).min(self.max_fatigue)
+    }
+
+    pub fn park_factor(&self, home_team: &str) -> f64 {
+        self.park_factors.get(home_team).copied().unwrap_or(1.0)
+    }
+
+    /// Expected runs and the home win probability from the teams' baseline
+    /// (home, away) runs, the starters and each bullpen's recent innings
+    pub fn game_odds(
+        &self,
+        home_team: &str,
+        baseline: (f64, f64),
+        (home_starter, away_starter): (&ProbablePitcher, &ProbablePitcher),
+        (home_bullpen, away_bullpen): (f64, f64),
+    ) -> Result<MlbGameOdds> {
+        let park = self.park_factor(home_team);
+        let allowed = |starter: &ProbablePitcher, bullpen: f64| {
+            self.starter_share * self.starter_factor(starter) + (1.0 - self.starter_share) * self.bullpen_factor(bullpen)
+        };
+        let home_runs = baseline.0 * park * allowed(away_starter, away_bullpen);
+        let away_runs = baseline.1 * park * allowed(home_starter, home_bullpen);
+
+        let home = Poisson::new(home_runs)?;
+        let away = Poisson::new(away_runs)?;
+        let (mut win, mut tie, mut away_below) = (0.0, 0.0, 0.0);
+        for runs in 0..=MAX_RUNS {
+            let (h, a) = (home.pmf(runs), away.pmf(runs));
+            win += h * away_below;
+            tie += h * a;
+            away_below += a;
+        }
+
+        // Extra innings go to each side in proportion to its scoring rate
+        Ok(MlbGameOdds {
+            home_runs,
+            away_runs,
+            home_win: win + tie * home_runs / (home_runs + away_runs),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn starter(era: Option<f64>, innings_pitched: f64) -> ProbablePitcher {
+        ProbablePitcher {
+            game_id: "mlb-nyy-bos".to_string(),
+            team: String::new(),
+            pitcher: String::new(),
+            era,
+            innings_pitched,
+        }
+    }
+
+    #[test]
+    fn starters_park_and_bullpens_move_the_runs() {
+        let model = MlbModel::from_config(&MlbConfig::default()).unwrap();
+        let average = starter(None, 0.0);
+        let even = model.game_odds("New York Yankees", (4.4, 4.4), (&average, &average), (0.0, 0.0)).unwrap();
+        assert!((even.home_runs - 4.4).abs() < 1e-9);
+        assert!((even.home_win - 0.5).abs() < 1e-9);
+
+        // An ace at home holds the visitors down, more so with more innings behind the ERA
+        let ace = starter(Some(2.4), 160.0);
+        let odds = model.game_odds("New York Yankees", (4.4, 4.4), (&ace, &average), (0.0, 0.0)).unwrap();
+        assert!(odds.away_runs < 3.8 && odds.home_win > 0.55);
+        let early = model.game_odds("New York Yankees", (4.4, 4.4), (&starter(Some(2.4), 12.0), &average), (0.0, 0.0)).unwrap();
+        assert!(early.away_runs > odds.away_runs);
+
+        // Coors inflates both sides; a spent bullpen costs runs, up to the cap
+        let coors = model.game_odds("Colorado Rockies", (4.4, 4.4), (&average, &average), (0.0, 0.0)).unwrap();
+        assert!((coors.home_runs - 4.4 * 1.12).abs() < 1e-9);
+        assert!(coors.over(9.5).unwrap() > even.over(9.5).unwrap());
+        let tired = model.game_odds("New York Yankees", (4.4, 4.4), (&average, &average), (40.0, 0.0)).unwrap();
+        assert!((tired.away_runs - 4.4 * (1.0 + 0.15 * (1.0 - 5.5 / 9.0))).abs() < 1e-9);
+    }
+}
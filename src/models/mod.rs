@@ -3,3 +3,27 @@
 // - Model loading and inference
 // - Feature engineering
 // - Model versioning
+
+mod batch;
+mod home_advantage;
+mod joint;
+mod mlb;
+mod officials;
+mod parameters;
+mod schedule;
+mod season;
+mod sequential;
+mod simulator;
+mod team_strength;
+
+pub use batch::{backend_from_config, BatchSimulator};
+pub use home_advantage::HomeAdvantage;
+pub use joint::{price_combo, Leg, ScoreDistribution, ScoringProfile};
+pub use mlb::{bullpen_innings, MlbModel, LEAGUE_RUNS};
+pub use officials::{adjust_lambdas, officiating_total_shift};
+pub use parameters::ModelParameters;
+pub use schedule::schedule_spots;
+pub use season::{Prize, SeasonModel, SeasonOutcomes};
+pub use sequential::{AdaptiveSampling, SamplingStop};
+pub use simulator::{SeasonRun, SeasonSimulator};
+pub use team_strength::TeamStrengths;
@@ -0,0 +1,71 @@
+use crate::types::OfficialTendency;
+
+/// Games of league-average officiating blended into every tendency
+///
+/// An umpire with a handful of high-scoring games is mostly noise; with this
+/// prior, half of a delta is trusted after 50 games in the role.
+const PRIOR_GAMES: f64 = 50.0;
+
+/// Expected shift in a game's total from its assigned officials
+///
+/// Each official's average delta is shrunk toward zero by sample size, and
+/// the crew's shifts are averaged: deltas are measured on the same games, so
+/// summing them would count a crew's shared games once per member.
+pub fn officiating_total_shift(tendencies: &[OfficialTendency]) -> f64 {
+    if tendencies.is_empty() {
+        return 0.0;
+    }
+
+    let shrunk: f64 = tendencies.iter()
+        .map(|t| {
+            let games = t.games.max(0) as f64;
+            t.total_delta * games / (games + PRIOR_GAMES)
+        })
+        .sum();
+
+    shrunk / tendencies.len() as f64
+}
+
+/// Scoring rates moved by a total shift, split in proportion to each side's rate
+///
+/// Rates are floored just above zero so a large negative shift still leaves
+/// a valid Poisson.
+pub fn adjust_lambdas((home, away): (f64, f64), shift: f64) -> (f64, f64) {
+    let total = home + away;
+    if total <= 0.0 {
+        return (home, away);
+    }
+
+    let scale = ((total + shift) / total).max(0.01);
+    (home * scale, away * scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tendency(games: i64, total_delta: f64) -> OfficialTendency {
+        OfficialTendency {
+            official: "Angel Hernandez".to_string(),
+            role: "home_plate".to_string(),
+            games,
+            total_delta,
+        }
+    }
+
+    #[test]
+    fn shrinks_small_samples_toward_zero() {
+        assert_eq!(officiating_total_shift(&[]), 0.0);
+        assert_eq!(officiating_total_shift(&[tendency(0, 3.0)]), 0.0);
+        assert!((officiating_total_shift(&[tendency(50, 1.2)]) - 0.6).abs() < 1e-9);
+        assert!(officiating_total_shift(&[tendency(500, 1.2)]) > 1.0);
+    }
+
+    #[test]
+    fn scales_lambdas_proportionally() {
+        let (home, away) = adjust_lambdas((5.0, 3.0), 0.8);
+        assert!((home + away - 8.8).abs() < 1e-9);
+        assert!((home / away - 5.0 / 3.0).abs() < 1e-9);
+        assert!(adjust_lambdas((5.0, 3.0), -20.0).0 > 0.0);
+    }
+}
@@ -0,0 +1,186 @@
+use anyhow::{bail, Context, Result};
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::types::Sport;
+
+/// Parameter sheets installed at startup; built-in values apply without them
+static INSTALLED: OnceCell<ModelParameters> = OnceCell::new();
+
+/// Overrides for one league; unset columns keep the built-in value
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LeagueParameters {
+    /// Home edge assumed before any results, in points (runs for MLB)
+    pub home_advantage: Option<f64>,
+    /// Final-score dispersion, see `ScoringProfile`
+    pub margin_sd: Option<f64>,
+    pub total_sd: Option<f64>,
+    pub correlation: Option<f64>,
+    /// Multiplier on both teams' expected scoring in the totals model
+    pub pace: Option<f64>,
+}
+
+impl LeagueParameters {
+    /// Later sheets win column by column
+    fn merge(&mut self, other: LeagueParameters) {
+        self.home_advantage = other.home_advantage.or(self.home_advantage);
+        self.margin_sd = other.margin_sd.or(self.margin_sd);
+        self.total_sd = other.total_sd.or(self.total_sd);
+        self.correlation = other.correlation.or(self.correlation);
+        self.pace = other.pace.or(self.pace);
+    }
+
+    fn validate(&self) -> Result<()> {
+        if let Some(home) = self.home_advantage {
+            if !home.is_finite() || home.abs() > 10.0 {
+                bail!("home_advantage {} outside [-10, 10]", home);
+            }
+        }
+        for (name, sd) in [("margin_sd", self.margin_sd), ("total_sd", self.total_sd)] {
+            if let Some(sd) = sd {
+                if !(sd > 0.0 && sd.is_finite()) {
+                    bail!("{} must be positive, got {}", name, sd);
+                }
+            }
+        }
+        if let Some(correlation) = self.correlation {
+            if !(correlation > -1.0 && correlation < 1.0) {
+                bail!("correlation {} outside (-1, 1)", correlation);
+            }
+        }
+        if let Some(pace) = self.pace {
+            if !(0.5..=1.5).contains(&pace) {
+                bail!("pace {} outside [0.5, 1.5]", pace);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// CSV row: `league` plus the `LeagueParameters` columns
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SheetRow {
+    league: String,
+    home_advantage: Option<f64>,
+    margin_sd: Option<f64>,
+    total_sd: Option<f64>,
+    correlation: Option<f64>,
+    pace: Option<f64>,
+}
+
+impl SheetRow {
+    fn into_parts(self) -> (String, LeagueParameters) {
+        (self.league, LeagueParameters {
+            home_advantage: self.home_advantage,
+            margin_sd: self.margin_sd,
+            total_sd: self.total_sd,
+            correlation: self.correlation,
+            pace: self.pace,
+        })
+    }
+}
+
+/// Per-league model parameters from analyst-maintained CSV or TOML sheets
+///
+/// CSV sheets have a `league` column and one row per league; TOML sheets
+/// have one table per league (`[NFL]`, `["Premier League"]`). Leagues use
+/// the names in `Sport::as_str`. Blank cells and missing keys keep the
+/// built-in value.
+#[derive(Debug, Clone, Default)]
+pub struct ModelParameters {
+    leagues: HashMap<Sport, LeagueParameters>,
+}
+
+impl ModelParameters {
+    /// Load and validate sheets in order, later sheets overriding earlier ones
+    pub fn load<P: AsRef<Path>>(paths: &[P]) -> Result<Self> {
+        let mut parameters = Self::default();
+        for path in paths {
+            let path = path.as_ref();
+            let sheet = read_sheet(path)
+                .with_context(|| format!("model parameter sheet {}", path.display()))?;
+            for (sport, league) in sheet {
+                parameters.leagues.entry(sport).or_default().merge(league);
+            }
+        }
+        Ok(parameters)
+    }
+
+    /// Make these parameters the ones the models use; only the first install counts
+    pub fn install(self) -> Result<()> {
+        INSTALLED.set(self).map_err(|_| anyhow::anyhow!("model parameters already installed"))
+    }
+
+    /// Installed overrides for a league; empty when no sheet covers it
+    pub fn for_sport(sport: Sport) -> LeagueParameters {
+        INSTALLED.get()
+            .and_then(|installed| installed.leagues.get(&sport).copied())
+            .unwrap_or_default()
+    }
+
+    pub fn leagues(&self) -> usize {
+        self.leagues.len()
+    }
+}
+
+fn read_sheet(path: &Path) -> Result<Vec<(Sport, LeagueParameters)>> {
+    let rows: Vec<(String, LeagueParameters)> = match path.extension().and_then(|e| e.to_str()) {
+        Some("csv") => {
+            let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_path(path)?;
+            reader.deserialize::<SheetRow>()
+                .map(|row| row.map(SheetRow::into_parts))
+                .collect::<Result<_, _>>()?
+        }
+        Some("toml") => {
+            let tables: HashMap<String, LeagueParameters> = ::config::Config::builder()
+                .add_source(::config::File::new(&path.to_string_lossy(), ::config::FileFormat::Toml))
+                .build()?
+                .try_deserialize()?;
+            tables.into_iter().collect()
+        }
+        _ => bail!("unsupported sheet format; use .csv or .toml"),
+    };
+
+    rows.into_iter()
+        .map(|(league, parameters)| {
+            let sport = Sport::parse(&league)
+                .with_context(|| format!("unknown league {:?}", league))?;
+            parameters.validate().with_context(|| format!("league {}", league))?;
+            Ok((sport, parameters))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn sheet(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("{}-{}", uuid::Uuid::new_v4(), name));
+        std::fs::File::create(&path).unwrap().write_all(contents.as_bytes()).unwrap();
+        path
+    }
+
+    #[test]
+    fn later_sheets_override_column_by_column() {
+        let csv = sheet("leagues.csv", "league,home_advantage,margin_sd,total_sd,correlation,pace\nNFL,1.8,13.0,,,\nNBA,,,,,1.04\n");
+        let toml = sheet("overrides.toml", "[NFL]\nmargin_sd = 12.5\n");
+
+        let parameters = ModelParameters::load(&[&csv, &toml]).unwrap();
+        let nfl = parameters.leagues[&Sport::NFL];
+        assert_eq!(nfl.home_advantage, Some(1.8));
+        assert_eq!(nfl.margin_sd, Some(12.5));
+        assert_eq!(nfl.total_sd, None);
+        assert_eq!(parameters.leagues[&Sport::NBA].pace, Some(1.04));
+
+        let invalid = sheet("bad.csv", "league,pace\nMLB,3.0\n");
+        assert!(ModelParameters::load(&[&invalid]).is_err());
+        let unknown = sheet("bad.toml", "[NHL]\npace = 1.0\n");
+        assert!(ModelParameters::load(&[&unknown]).is_err());
+    }
+}
@@ -0,0 +1,174 @@
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+
+use crate::types::{Game, Sport, TeamRating};
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// A team's schedule situation going into a game
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScheduleSpot {
+    /// Full days off since the team's last game; `None` for its first game
+    pub rest_days: Option<i64>,
+    /// Played the previous day (or earlier the same day)
+    pub back_to_back: bool,
+    /// Games in the seven days before this one
+    pub games_last_7_days: u32,
+    /// Distance from the last game's venue, or from home for a first game
+    pub travel_km: f64,
+}
+
+impl ScheduleSpot {
+    /// Rating change for this spot, in the sport's margin units
+    ///
+    /// Rough league-wide priors: NBA back-to-backs, NFL short weeks and byes,
+    /// and congested EPL weeks cost (or earn) points; travel is charged per
+    /// 1000 km everywhere.
+    pub fn rating_adjustment(&self, sport: Sport) -> f64 {
+        let (rest, travel_per_1000_km) = match sport {
+            Sport::NBA => (if self.back_to_back { -1.5 } else { 0.0 }, -0.3),
+            Sport::NFL => (
+                match self.rest_days {
+                    Some(days) if days <= 4 => -1.0,
+                    Some(days) if days >= 12 => 1.0,
+                    _ => 0.0,
+                },
+                -0.2,
+            ),
+            Sport::PremierLeague => (if self.rest_days.map_or(false, |d| d <= 2) { -0.15 } else { 0.0 }, -0.05),
+            // Daily games are the norm; only travel is charged
+            Sport::MLB => (0.0, -0.05),
+        };
+
+        rest + travel_per_1000_km * self.travel_km / 1000.0
+    }
+
+    /// Named values as stored in the feature store
+    pub fn features(&self) -> Vec<(&'static str, f64)> {
+        let mut features = vec![
+            ("back_to_back", if self.back_to_back { 1.0 } else { 0.0 }),
+            ("games_last_7_days", self.games_last_7_days as f64),
+            ("travel_km", self.travel_km),
+        ];
+        if let Some(days) = self.rest_days {
+            features.push(("rest_days", days as f64));
+        }
+        features
+    }
+}
+
+/// Home and away schedule spots for each game, in the order given
+///
+/// Games are played at the home team's venue; teams without a location
+/// travel zero distance.
+pub fn schedule_spots(teams: &[TeamRating], games: &[Game]) -> Vec<(ScheduleSpot, ScheduleSpot)> {
+    let locations: HashMap<&str, (f64, f64)> = teams.iter()
+        .filter_map(|t| Some((t.team.as_str(), (t.latitude?, t.longitude?))))
+        .collect();
+
+    let mut order: Vec<usize> = (0..games.len()).collect();
+    order.sort_by_key(|&i| games[i].start_time);
+
+    // Per team: start times so far and where the last game was played
+    let mut history: HashMap<String, (Vec<DateTime<Utc>>, Option<(f64, f64)>)> = HashMap::new();
+    let mut spots = vec![None; games.len()];
+    for i in order {
+        let game = &games[i];
+        let venue = locations.get(game.home_team.as_str()).copied();
+
+        let mut spot_for = |team: &str| {
+            let (starts, last_venue) = history.entry(team.to_string())
+                .or_insert_with(|| (Vec::new(), locations.get(team).copied()));
+            let days_since = starts.last().map(|&last| ((game.start_time - last).num_hours() as f64 / 24.0).round() as i64);
+            let travel_km = match (*last_venue, venue) {
+                (Some(from), Some(to)) => distance_km(from, to),
+                _ => 0.0,
+            };
+            let spot = ScheduleSpot {
+                rest_days: days_since.map(|days| (days - 1).max(0)),
+                back_to_back: days_since.map_or(false, |days| days <= 1),
+                games_last_7_days: starts.iter().filter(|&&s| game.start_time - s <= Duration::days(7)).count() as u32,
+                travel_km,
+            };
+
+            starts.push(game.start_time);
+            *last_venue = venue.or(*last_venue);
+            spot
+        };
+
+        spots[i] = Some((spot_for(&game.home_team), spot_for(&game.away_team)));
+    }
+
+    spots.into_iter().flatten().collect()
+}
+
+/// Great-circle distance between two (latitude, longitude) points
+fn distance_km((lat1, lon1): (f64, f64), (lat2, lon2): (f64, f64)) -> f64 {
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let d_phi = (lat2 - lat1).to_radians();
+    let d_lambda = (lon2 - lon1).to_radians();
+
+    let a = (d_phi / 2.0).sin().powi(2) + phi1.cos() * phi2.cos() * (d_lambda / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn team(name: &str, location: (f64, f64)) -> TeamRating {
+        TeamRating {
+            sport: Sport::NBA,
+            team: name.to_string(),
+            conference: "Eastern".to_string(),
+            division: "Atlantic".to_string(),
+            rating: 0.0,
+            latitude: Some(location.0),
+            longitude: Some(location.1),
+            elevation_m: None,
+            indoor: false,
+        }
+    }
+
+    fn game(home: &str, away: &str, start_time: DateTime<Utc>) -> Game {
+        Game {
+            game_id: format!("{}-{}", home, away),
+            sport: Sport::NBA,
+            season: 2025,
+            home_team: home.to_string(),
+            away_team: away.to_string(),
+            start_time,
+            home_score: None,
+            away_score: None,
+        }
+    }
+
+    #[test]
+    fn tracks_rest_and_travel_between_games() {
+        let teams = vec![
+            team("Celtics", (42.366, -71.062)),
+            team("Lakers", (34.043, -118.267)),
+            team("Knicks", (40.751, -73.993)),
+        ];
+        let tipoff: DateTime<Utc> = "2025-11-01T00:00:00Z".parse().unwrap();
+        let games = vec![
+            // Out of order on purpose: spots follow start times
+            game("Celtics", "Lakers", tipoff + Duration::days(1)),
+            game("Knicks", "Lakers", tipoff),
+            game("Celtics", "Knicks", tipoff + Duration::days(4)),
+        ];
+
+        let spots = schedule_spots(&teams, &games);
+        let (celtics, lakers) = spots[0];
+        assert_eq!(celtics.rest_days, None);
+        assert_eq!(celtics.travel_km, 0.0);
+        assert!(lakers.back_to_back);
+        assert_eq!(lakers.rest_days, Some(0));
+        assert!((lakers.travel_km - 306.0).abs() < 10.0);
+
+        let (_, knicks) = spots[2];
+        assert_eq!(knicks.rest_days, Some(3));
+        assert_eq!(knicks.games_last_7_days, 1);
+        assert!(lakers.rating_adjustment(Sport::NBA) < knicks.rating_adjustment(Sport::NBA));
+    }
+}
@@ -0,0 +1,566 @@
+use anyhow::{anyhow, bail, Result};
+use rand::Rng;
+use statrs::distribution::{ContinuousCDF, Normal};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::types::{Game, InjuryAdjustment, Sport, TeamRating};
+use super::{schedule_spots, BatchSimulator, HomeAdvantage, ScoringProfile};
+
+/// Seasons whose regular-season results are drawn in one backend call;
+/// bounds memory to a few MB even for a full MLB schedule
+const RUNS_PER_BATCH: u32 = 1_024;
+
+/// Season-long prizes a futures market can pay out on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Prize {
+    Division,
+    Conference,
+    Championship,
+}
+
+/// How a league's regular season feeds its playoffs
+#[derive(Debug, Clone, Copy)]
+pub struct PlayoffFormat {
+    /// Teams per conference that qualify
+    pub teams_per_conference: usize,
+    /// Division winners take the top seeds regardless of record
+    pub division_winners_seeded: bool,
+    /// Playoff rounds are best-of this many games
+    pub series_length: u32,
+}
+
+impl PlayoffFormat {
+    /// `None` for leagues without playoffs
+    pub fn for_sport(sport: Sport) -> Option<Self> {
+        match sport {
+            Sport::NFL => Some(Self { teams_per_conference: 7, division_winners_seeded: true, series_length: 1 }),
+            // Play-in tournament not modelled
+            Sport::NBA => Some(Self { teams_per_conference: 8, division_winners_seeded: false, series_length: 7 }),
+            Sport::MLB => Some(Self { teams_per_conference: 6, division_winners_seeded: true, series_length: 7 }),
+            Sport::PremierLeague => None,
+        }
+    }
+}
+
+/// Fraction of simulated seasons in which each team took each prize
+#[derive(Debug, Clone)]
+pub struct SeasonOutcomes {
+    pub simulations: u32,
+    teams: HashMap<String, usize>,
+    division: Vec<u32>,
+    conference: Vec<u32>,
+    championship: Vec<u32>,
+    playoffs: Vec<u32>,
+    /// Seasons ending on each regular-season win total, per team
+    win_totals: Vec<Vec<u32>>,
+}
+
+impl SeasonOutcomes {
+    pub fn probability(&self, team: &str, prize: Prize) -> Option<f64> {
+        let &i = self.teams.get(team)?;
+        let count = match prize {
+            Prize::Division => self.division[i],
+            Prize::Conference => self.conference[i],
+            Prize::Championship => self.championship[i],
+        };
+        Some(self.share(count))
+    }
+
+    pub fn playoff_probability(&self, team: &str) -> Option<f64> {
+        let &i = self.teams.get(team)?;
+        Some(self.share(self.playoffs[i]))
+    }
+
+    /// P(team finishes the regular season with at least `wins` wins)
+    pub fn wins_at_least(&self, team: &str, wins: u32) -> Option<f64> {
+        let &i = self.teams.get(team)?;
+        let count = self.win_totals[i].iter().skip(wins as usize).sum();
+        Some(self.share(count))
+    }
+
+    pub fn expected_wins(&self, team: &str) -> Option<f64> {
+        let &i = self.teams.get(team)?;
+        let total: u64 = self.win_totals[i].iter().enumerate()
+            .map(|(wins, &count)| wins as u64 * count as u64)
+            .sum();
+        Some(total as f64 / self.simulations as f64)
+    }
+
+    fn share(&self, count: u32) -> f64 {
+        count as f64 / self.simulations as f64
+    }
+}
+
+/// An unplayed game with its home win probability
+#[derive(Debug, Clone, Copy)]
+struct Fixture {
+    home: usize,
+    away: usize,
+    home_probability: f64,
+}
+
+/// Wins and head-to-head results of one simulated season
+#[derive(Clone)]
+struct Standings {
+    n: usize,
+    wins: Vec<u32>,
+    /// `h2h[winner * n + loser]`
+    h2h: Vec<u32>,
+}
+
+impl Standings {
+    fn new(n: usize) -> Self {
+        Self { n, wins: vec![0; n], h2h: vec![0; n * n] }
+    }
+
+    fn record(&mut self, winner: usize, loser: usize) {
+        self.wins[winner] += 1;
+        self.h2h[winner * self.n + loser] += 1;
+    }
+
+    /// Win percentage against `opponents`; 0.5 if they never met
+    fn win_pct_against(&self, team: usize, opponents: &[usize]) -> f64 {
+        let (won, lost) = opponents.iter()
+            .filter(|&&o| o != team)
+            .fold((0, 0), |(w, l), &o| (w + self.h2h[team * self.n + o], l + self.h2h[o * self.n + team]));
+
+        if won + lost == 0 { 0.5 } else { won as f64 / (won + lost) as f64 }
+    }
+}
+
+/// Rolls team ratings through the rest of a season and its playoffs
+///
+/// Unplayed games use each team's rating net of injuries that overlap the
+/// game and of its rest and travel going in; playoffs use ratings net of injuries lasting past the last
+/// scheduled game.
+pub struct SeasonModel {
+    teams: Vec<TeamRating>,
+    format: PlayoffFormat,
+    margin_sd: f64,
+    /// Home edge at each team's venue
+    venue_advantage: Vec<f64>,
+    /// Results banked from played games
+    played: Standings,
+    remaining: Vec<Fixture>,
+    playoff_ratings: Vec<f64>,
+    /// Most wins a team can finish with
+    max_wins: usize,
+    divisions: Vec<Vec<usize>>,
+    conferences: Vec<Vec<usize>>,
+    division_of: Vec<usize>,
+    conference_of: Vec<usize>,
+}
+
+impl SeasonModel {
+    pub fn new(
+        sport: Sport,
+        teams: Vec<TeamRating>,
+        games: &[Game],
+        injuries: &[InjuryAdjustment],
+        home_advantage: &HomeAdvantage,
+    ) -> Result<Self> {
+        let (Some(format), Some(profile)) = (PlayoffFormat::for_sport(sport), ScoringProfile::for_sport(sport)) else {
+            bail!("no season model for {}", sport.as_str());
+        };
+        if teams.is_empty() {
+            bail!("no team ratings for {}", sport.as_str());
+        }
+
+        let index: HashMap<&str, usize> = teams.iter().enumerate()
+            .map(|(i, t)| (t.team.as_str(), i))
+            .collect();
+        let lookup = |team: &str| index.get(team).copied()
+            .ok_or_else(|| anyhow!("game references unrated team {}", team));
+
+        let injuries = injuries.iter()
+            .filter_map(|injury| Some((*index.get(injury.team.as_str())?, injury)))
+            .collect::<Vec<_>>();
+        let season_end = games.iter().map(|g| g.start_time).max();
+        // Rating for a game, or for the playoffs when `game` is `None`
+        let rating_at = |team: usize, game: Option<&Game>| {
+            let out = injuries.iter()
+                .filter(|(t, injury)| {
+                    *t == team && match (injury.out_until, game) {
+                        (Some(until), Some(game)) => game.start_time < until,
+                        (Some(until), None) => season_end.map_or(false, |end| end < until),
+                        (None, _) => true,
+                    }
+                })
+                .map(|(_, injury)| injury.rating_delta)
+                .sum::<f64>();
+            teams[team].rating + out
+        };
+
+        let standard = Normal::new(0.0, 1.0)?;
+        let mut played = Standings::new(teams.len());
+        let mut remaining = Vec::new();
+        let mut games_per_team = vec![0; teams.len()];
+        for (game, (home_spot, away_spot)) in games.iter().zip(schedule_spots(&teams, games)) {
+            let (home, away) = (lookup(&game.home_team)?, lookup(&game.away_team)?);
+            games_per_team[home] += 1;
+            games_per_team[away] += 1;
+
+            if game.is_played() {
+                match game.winner() {
+                    Some(winner) if winner == game.home_team => played.record(home, away),
+                    Some(_) => played.record(away, home),
+                    None => {}
+                }
+            } else {
+                let home_rating = rating_at(home, Some(game)) + home_spot.rating_adjustment(sport);
+                let away_rating = rating_at(away, Some(game)) + away_spot.rating_adjustment(sport);
+                let margin = home_rating - away_rating + home_advantage.for_venue(&game.home_team);
+                remaining.push(Fixture { home, away, home_probability: standard.cdf(margin / profile.margin_sd) });
+            }
+        }
+
+        let playoff_ratings = (0..teams.len()).map(|t| rating_at(t, None)).collect();
+        let (divisions, division_of) = group_by(&teams, |t| &t.division);
+        let (conferences, conference_of) = group_by(&teams, |t| &t.conference);
+
+        Ok(Self {
+            format,
+            margin_sd: profile.margin_sd,
+            venue_advantage: teams.iter().map(|t| home_advantage.for_venue(&t.team)).collect(),
+            played,
+            remaining,
+            playoff_ratings,
+            max_wins: games_per_team.into_iter().max().unwrap_or(0),
+            divisions,
+            conferences,
+            division_of,
+            conference_of,
+            teams,
+        })
+    }
+
+    /// Play out the season `simulations` times
+    ///
+    /// Regular-season results are drawn in batches by `backend`; tiebreak
+    /// lots and playoffs, which depend on each run's standings, use `rng`.
+    pub fn simulate<R: Rng>(
+        &self,
+        simulations: u32,
+        backend: &dyn BatchSimulator,
+        rng: &mut R,
+    ) -> Result<SeasonOutcomes> {
+        if simulations == 0 {
+            bail!("season simulation needs at least one run");
+        }
+
+        let standard = Normal::new(0.0, 1.0)?;
+        let n = self.teams.len();
+        let mut division = vec![0; n];
+        let mut conference = vec![0; n];
+        let mut championship = vec![0; n];
+        let mut playoffs = vec![0; n];
+        let mut win_totals = vec![vec![0; self.max_wins + 1]; n];
+
+        let probabilities: Vec<f64> = self.remaining.iter().map(|f| f.home_probability).collect();
+        let fixtures = probabilities.len();
+        let mut simulated = 0;
+        while simulated < simulations {
+            let runs = (simulations - simulated).min(RUNS_PER_BATCH);
+            let home_wins = backend.bernoulli(&probabilities, runs, rng.gen())?;
+            simulated += runs;
+
+            for run in 0..runs as usize {
+                let mut standings = self.played.clone();
+                let results = &home_wins[run * fixtures..][..fixtures];
+                for (fixture, &home_won) in self.remaining.iter().zip(results) {
+                    if home_won {
+                        standings.record(fixture.home, fixture.away);
+                    } else {
+                        standings.record(fixture.away, fixture.home);
+                    }
+                }
+                for (team, &wins) in standings.wins.iter().enumerate() {
+                    win_totals[team][wins as usize] += 1;
+                }
+
+                // Last resort when every tiebreaker is level
+                let lot: Vec<f64> = (0..n).map(|_| rng.gen()).collect();
+
+                let division_winners: Vec<usize> = self.divisions.iter()
+                    .map(|d| self.rank(d, &standings, &lot)[0])
+                    .collect();
+                for &team in &division_winners {
+                    division[team] += 1;
+                }
+
+                let mut champions = Vec::with_capacity(self.conferences.len());
+                for teams in &self.conferences {
+                    let seeds = self.seed(&self.rank(teams, &standings, &lot), &division_winners);
+                    for &team in &seeds {
+                        playoffs[team] += 1;
+                    }
+
+                    let champion = self.play_bracket(&standard, seeds, rng);
+                    conference[champion] += 1;
+                    champions.push(champion);
+                }
+
+                let finalists = self.rank(&champions, &standings, &lot);
+                championship[self.play_bracket(&standard, finalists, rng)] += 1;
+            }
+        }
+
+        Ok(SeasonOutcomes {
+            simulations,
+            teams: self.teams.iter().enumerate().map(|(i, t)| (t.team.clone(), i)).collect(),
+            division,
+            conference,
+            championship,
+            playoffs,
+            win_totals,
+        })
+    }
+
+    /// Order teams by wins, breaking ties the way leagues broadly do
+    ///
+    /// Tied teams are split by their record against each other, then by
+    /// division record if they share a division (conference record if not),
+    /// then by lot. League-specific steps such as strength of schedule are
+    /// not modelled.
+    fn rank(&self, teams: &[usize], standings: &Standings, lot: &[f64]) -> Vec<usize> {
+        let mut ranked = teams.to_vec();
+        ranked.sort_by(|&a, &b| standings.wins[b].cmp(&standings.wins[a]));
+
+        let mut start = 0;
+        while start < ranked.len() {
+            let wins = standings.wins[ranked[start]];
+            let end = ranked[start..].iter()
+                .position(|&t| standings.wins[t] != wins)
+                .map_or(ranked.len(), |offset| start + offset);
+
+            if end - start > 1 {
+                let tied = ranked[start..end].to_vec();
+                let same_division = tied.iter().all(|&t| self.division_of[t] == self.division_of[tied[0]]);
+                let scope = |team: usize| if same_division {
+                    &self.divisions[self.division_of[team]]
+                } else {
+                    &self.conferences[self.conference_of[team]]
+                };
+
+                ranked[start..end].sort_by(|&a, &b| {
+                    let by_pct = |pct: &dyn Fn(usize) -> f64| pct(b).partial_cmp(&pct(a)).unwrap_or(Ordering::Equal);
+                    by_pct(&|t| standings.win_pct_against(t, &tied))
+                        .then_with(|| by_pct(&|t| standings.win_pct_against(t, scope(t))))
+                        .then_with(|| lot[a].total_cmp(&lot[b]))
+                });
+            }
+            start = end;
+        }
+
+        ranked
+    }
+
+    /// Playoff seeds for a conference, from its teams in standings order
+    fn seed(&self, standings: &[usize], division_winners: &[usize]) -> Vec<usize> {
+        let size = self.format.teams_per_conference.min(standings.len());
+        if !self.format.division_winners_seeded {
+            return standings[..size].to_vec();
+        }
+
+        let mut seeds: Vec<usize> = standings.iter().copied().filter(|t| division_winners.contains(t)).collect();
+        seeds.extend(standings.iter().copied().filter(|t| !division_winners.contains(t)));
+        seeds.truncate(size);
+        seeds
+    }
+
+    /// Single-elimination bracket, re-seeded each round; top seeds take byes
+    fn play_bracket<R: Rng>(&self, standard: &Normal, mut seeds: Vec<usize>, rng: &mut R) -> usize {
+        while seeds.len() > 1 {
+            let byes = seeds.len().next_power_of_two() - seeds.len();
+            let (advancing, playing) = seeds.split_at(byes);
+
+            let mut next = advancing.to_vec();
+            for i in 0..playing.len() / 2 {
+                let (higher, lower) = (playing[i], playing[playing.len() - 1 - i]);
+                next.push(self.play_series(standard, higher, lower, rng));
+            }
+            // Keep seed order so re-seeding pairs best against worst
+            next.sort_by_key(|team| seeds.iter().position(|s| s == team));
+            seeds = next;
+        }
+
+        seeds[0]
+    }
+
+    /// Best-of-n series; the higher seed hosts every game
+    fn play_series<R: Rng>(&self, standard: &Normal, higher: usize, lower: usize, rng: &mut R) -> usize {
+        let margin = self.playoff_ratings[higher] - self.playoff_ratings[lower] + self.venue_advantage[higher];
+        let p = standard.cdf(margin / self.margin_sd);
+        let needed = self.format.series_length / 2 + 1;
+        let (mut higher_wins, mut lower_wins) = (0, 0);
+
+        while higher_wins < needed && lower_wins < needed {
+            if rng.gen_bool(p) {
+                higher_wins += 1;
+            } else {
+                lower_wins += 1;
+            }
+        }
+
+        if higher_wins == needed { higher } else { lower }
+    }
+}
+
+/// Group team indices by `key`; also returns each team's group index
+fn group_by(teams: &[TeamRating], key: impl Fn(&TeamRating) -> &String) -> (Vec<Vec<usize>>, Vec<usize>) {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut names: HashMap<&String, usize> = HashMap::new();
+    let mut group_of = Vec::with_capacity(teams.len());
+
+    for (i, team) in teams.iter().enumerate() {
+        let g = *names.entry(key(team)).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[g].push(i);
+        group_of.push(g);
+    }
+
+    (groups, group_of)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::batch::ScalarBackend;
+    use chrono::{Duration, Utc};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn team(name: &str, conference: &str, division: &str, rating: f64) -> TeamRating {
+        TeamRating {
+            sport: Sport::NFL,
+            team: name.to_string(),
+            conference: conference.to_string(),
+            division: division.to_string(),
+            rating,
+            latitude: None,
+            longitude: None,
+            elevation_m: None,
+            indoor: false,
+        }
+    }
+
+    fn game(home: &str, away: &str, score: Option<(i32, i32)>) -> Game {
+        Game {
+            game_id: format!("{}-{}", home, away),
+            sport: Sport::NFL,
+            season: 2025,
+            home_team: home.to_string(),
+            away_team: away.to_string(),
+            start_time: Utc::now(),
+            home_score: score.map(|s| s.0),
+            away_score: score.map(|s| s.1),
+        }
+    }
+
+    /// League-prior home edge at every venue
+    fn neutral() -> HomeAdvantage {
+        HomeAdvantage::estimate(Sport::NFL, &league(), &[])
+    }
+
+    fn league() -> Vec<TeamRating> {
+        vec![
+            team("Chiefs", "AFC", "AFC West", 7.0),
+            team("Raiders", "AFC", "AFC West", -4.0),
+            team("Bills", "AFC", "AFC East", 5.0),
+            team("Jets", "AFC", "AFC East", -2.0),
+            team("Eagles", "NFC", "NFC East", 6.0),
+            team("Giants", "NFC", "NFC East", -5.0),
+        ]
+    }
+
+    #[test]
+    fn prizes_are_shared_out_once_per_season() {
+        let games = vec![
+            game("Chiefs", "Raiders", None),
+            game("Raiders", "Chiefs", None),
+            game("Bills", "Jets", None),
+            game("Eagles", "Giants", None),
+            game("Chiefs", "Eagles", None),
+        ];
+        let model = SeasonModel::new(Sport::NFL, league(), &games, &[], &neutral()).unwrap();
+        let outcomes = model.simulate(2_000, &ScalarBackend, &mut StdRng::seed_from_u64(3)).unwrap();
+
+        let total = |names: &[&str], prize| -> f64 {
+            names.iter().map(|t| outcomes.probability(t, prize).unwrap()).sum()
+        };
+        assert!((total(&["Chiefs", "Raiders"], Prize::Division) - 1.0).abs() < 1e-9);
+        assert!((total(&["Chiefs", "Raiders", "Bills", "Jets"], Prize::Conference) - 1.0).abs() < 1e-9);
+        let everyone = ["Chiefs", "Raiders", "Bills", "Jets", "Eagles", "Giants"];
+        assert!((total(&everyone, Prize::Championship) - 1.0).abs() < 1e-9);
+
+        // Stronger teams win more often
+        assert!(outcomes.probability("Chiefs", Prize::Division) > outcomes.probability("Raiders", Prize::Division));
+    }
+
+    #[test]
+    fn clinched_division_is_certain() {
+        let games = vec![
+            game("Raiders", "Chiefs", Some((24, 10))),
+            game("Chiefs", "Raiders", Some((3, 17))),
+        ];
+        let model = SeasonModel::new(Sport::NFL, league(), &games, &[], &neutral()).unwrap();
+        let outcomes = model.simulate(500, &ScalarBackend, &mut StdRng::seed_from_u64(3)).unwrap();
+
+        assert_eq!(outcomes.probability("Raiders", Prize::Division), Some(1.0));
+        assert_eq!(outcomes.probability("Chiefs", Prize::Division), Some(0.0));
+        assert_eq!(outcomes.wins_at_least("Raiders", 2), Some(1.0));
+    }
+
+    #[test]
+    fn head_to_head_breaks_level_records() {
+        // Both 1-1; the Raiders won the meeting
+        let games = vec![
+            game("Raiders", "Chiefs", Some((20, 17))),
+            game("Chiefs", "Jets", Some((30, 3))),
+            game("Bills", "Raiders", Some((27, 13))),
+        ];
+        let model = SeasonModel::new(Sport::NFL, league(), &games, &[], &neutral()).unwrap();
+        let outcomes = model.simulate(200, &ScalarBackend, &mut StdRng::seed_from_u64(3)).unwrap();
+
+        assert_eq!(outcomes.probability("Raiders", Prize::Division), Some(1.0));
+    }
+
+    #[test]
+    fn injuries_weaken_a_team_while_they_last() {
+        let now = Utc::now();
+        let games: Vec<Game> = (0..8)
+            .map(|week| Game {
+                start_time: now + Duration::weeks(week),
+                ..game("Chiefs", "Raiders", None)
+            })
+            .collect();
+        let injury = |out_until| InjuryAdjustment {
+            sport: Sport::NFL,
+            team: "Chiefs".to_string(),
+            player: "QB1".to_string(),
+            rating_delta: -10.0,
+            out_until,
+        };
+
+        let expected_wins = |injuries: &[InjuryAdjustment]| {
+            let model = SeasonModel::new(Sport::NFL, league(), &games, injuries, &neutral()).unwrap();
+            let outcomes = model.simulate(4_000, &ScalarBackend, &mut StdRng::seed_from_u64(3)).unwrap();
+            outcomes.expected_wins("Chiefs").unwrap()
+        };
+
+        let healthy = expected_wins(&[]);
+        let short_term = expected_wins(&[injury(Some(now + Duration::days(10)))]);
+        let season = expected_wins(&[injury(None)]);
+        assert!(healthy > short_term && short_term > season);
+    }
+
+    #[test]
+    fn rejects_unrated_teams_and_leagues_without_playoffs() {
+        let unrated = [game("Chiefs", "Browns", None)];
+        assert!(SeasonModel::new(Sport::NFL, league(), &unrated, &[], &neutral()).is_err());
+        assert!(SeasonModel::new(Sport::PremierLeague, league(), &[], &[], &neutral()).is_err());
+    }
+}
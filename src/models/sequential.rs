@@ -0,0 +1,172 @@
+use anyhow::{bail, Result};
+use std::time::{Duration, Instant};
+
+use crate::config::AdaptiveSimulationConfig;
+
+/// Why a simulation stopped drawing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SamplingStop {
+    /// The configured fixed count was drawn
+    Fixed,
+    /// Every sequential test reached a conclusion
+    Decided,
+    /// The draw cap was reached with a test still open
+    Cap,
+    /// The time budget ran out with a test still open
+    Budget,
+}
+
+impl SamplingStop {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SamplingStop::Fixed => "fixed",
+            SamplingStop::Decided => "decided",
+            SamplingStop::Cap => "cap",
+            SamplingStop::Budget => "budget",
+        }
+    }
+}
+
+/// Wald's sequential probability ratio test of a Bernoulli rate against a
+/// threshold
+///
+/// Tests `p = threshold - indifference` against `p = threshold + indifference`
+/// with equal error rates, stopping once the log-likelihood ratio leaves the
+/// continuation region. Rates well away from the threshold decide in a few
+/// hundred draws; marginal ones take many more.
+#[derive(Debug, Clone)]
+pub struct Sprt {
+    llr: f64,
+    success_step: f64,
+    failure_step: f64,
+    boundary: f64,
+    decision: Option<bool>,
+}
+
+impl Sprt {
+    pub fn new(threshold: f64, indifference: f64, error_rate: f64) -> Self {
+        let (below, above) = (threshold - indifference, threshold + indifference);
+        // A hypothesis outside (0, 1) cannot hold, so the other is settled
+        let decision = if above >= 1.0 {
+            Some(false)
+        } else if below <= 0.0 {
+            Some(true)
+        } else {
+            None
+        };
+
+        Self {
+            llr: 0.0,
+            success_step: (above / below).ln(),
+            failure_step: ((1.0 - above) / (1.0 - below)).ln(),
+            boundary: ((1.0 - error_rate) / error_rate).ln(),
+            decision,
+        }
+    }
+
+    pub fn observe(&mut self, successes: u64, trials: u64) {
+        if self.decision.is_some() {
+            return;
+        }
+
+        self.llr += successes as f64 * self.success_step + (trials - successes) as f64 * self.failure_step;
+        if self.llr >= self.boundary {
+            self.decision = Some(true);
+        } else if self.llr <= -self.boundary {
+            self.decision = Some(false);
+        }
+    }
+
+    /// Whether the rate is above the threshold, once the test has concluded
+    pub fn decision(&self) -> Option<bool> {
+        self.decision
+    }
+}
+
+/// Batched sampling that stops once the sequential tests conclude
+pub struct AdaptiveSampling {
+    batch_size: u32,
+    max_simulations: u32,
+    time_budget: Duration,
+    indifference: f64,
+    error_rate: f64,
+}
+
+impl AdaptiveSampling {
+    /// `None` when adaptive sampling is disabled
+    pub fn from_config(config: &AdaptiveSimulationConfig) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        if config.batch_size == 0 || config.max_simulations < config.batch_size {
+            bail!("adaptive simulation needs 0 < batch_size <= max_simulations");
+        }
+        if !(config.indifference > 0.0 && config.indifference < 0.5) {
+            bail!("adaptive simulation indifference must be in (0, 0.5)");
+        }
+        if !(config.error_rate > 0.0 && config.error_rate < 0.5) {
+            bail!("adaptive simulation error_rate must be in (0, 0.5)");
+        }
+
+        Ok(Some(Self {
+            batch_size: config.batch_size,
+            max_simulations: config.max_simulations,
+            time_budget: Duration::from_millis(config.time_budget_ms),
+            indifference: config.indifference,
+            error_rate: config.error_rate,
+        }))
+    }
+
+    pub fn batch_size(&self) -> u32 {
+        self.batch_size
+    }
+
+    pub fn test(&self, threshold: f64) -> Sprt {
+        Sprt::new(threshold, self.indifference, self.error_rate)
+    }
+
+    /// Why to stop after `draws` draws begun at `started`, or `None` to draw
+    /// another batch
+    pub fn stop(&self, tests: &[Sprt], draws: u32, started: Instant) -> Option<SamplingStop> {
+        if tests.iter().all(|t| t.decision().is_some()) {
+            Some(SamplingStop::Decided)
+        } else if draws >= self.max_simulations {
+            Some(SamplingStop::Cap)
+        } else if started.elapsed() >= self.time_budget {
+            Some(SamplingStop::Budget)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Draws until the test concludes on a rate observed exactly, one batch of 100 at a time
+    fn draws_to_decide(rate: f64, threshold: f64) -> (u64, Option<bool>) {
+        let mut test = Sprt::new(threshold, 0.005, 0.01);
+        let mut draws = 0;
+        while test.decision().is_none() && draws < 1_000_000 {
+            test.observe((rate * 100.0).round() as u64, 100);
+            draws += 100;
+        }
+        (draws, test.decision())
+    }
+
+    #[test]
+    fn clear_cases_stop_sooner_than_marginal_ones() {
+        let (clear, above) = draws_to_decide(0.70, 0.55);
+        assert_eq!(above, Some(true));
+        let (marginal, above) = draws_to_decide(0.57, 0.55);
+        assert_eq!(above, Some(true));
+        assert!(clear * 5 < marginal, "clear {} vs marginal {}", clear, marginal);
+
+        assert_eq!(draws_to_decide(0.40, 0.55).1, Some(false));
+
+        // A threshold at the edge of (0, 1) is settled without drawing
+        assert_eq!(Sprt::new(0.999, 0.005, 0.01).decision(), Some(false));
+        assert_eq!(Sprt::new(0.001, 0.005, 0.01).decision(), Some(true));
+    }
+}
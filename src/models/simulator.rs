@@ -0,0 +1,170 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::storage::Storage;
+use crate::types::{Game, HomeAdvantageEstimate, InjuryAdjustment, Sport, TeamRating};
+use super::{BatchSimulator, HomeAdvantage, SeasonModel, SeasonOutcomes};
+
+/// One simulation of a sport's season and the inputs it was run on
+#[derive(Debug)]
+pub struct SeasonRun {
+    pub run_id: Uuid,
+    pub teams: Vec<TeamRating>,
+    pub outcomes: SeasonOutcomes,
+    pub simulated_at: DateTime<Utc>,
+    fingerprint: u64,
+}
+
+/// Shared season simulations, re-run only when their inputs change
+///
+/// Strategies pricing futures or win totals ask for the latest run of a
+/// sport; ratings, schedule, results and injuries are re-read on every
+/// request, and the cached run is reused while they are unchanged. Each new
+/// run re-estimates home advantage from all stored results and records the
+/// estimates for audit.
+pub struct SeasonSimulator {
+    storage: Arc<dyn Storage>,
+    backend: Arc<dyn BatchSimulator>,
+    simulation_count: u32,
+    runs: Mutex<HashMap<Sport, Arc<SeasonRun>>>,
+}
+
+impl SeasonSimulator {
+    pub fn new(storage: Arc<dyn Storage>, backend: Arc<dyn BatchSimulator>, simulation_count: u32) -> Self {
+        Self {
+            storage,
+            backend,
+            simulation_count,
+            runs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn run(&self, sport: Sport) -> Result<Arc<SeasonRun>> {
+        let teams = self.storage.fetch_team_ratings(sport).await?;
+        let games = self.storage.fetch_season_games(sport).await?;
+        let injuries = self.storage.fetch_injury_adjustments(sport).await?;
+        let fingerprint = fingerprint(&teams, &games, &injuries);
+
+        // Held across the simulation so concurrent callers share one run
+        let mut runs = self.runs.lock().await;
+        if let Some(run) = runs.get(&sport).filter(|r| r.fingerprint == fingerprint) {
+            return Ok(run.clone());
+        }
+
+        let home_advantage = self.estimate_home_advantage(sport, &teams).await?;
+        let model = SeasonModel::new(sport, teams.clone(), &games, &injuries, &home_advantage)?;
+        let outcomes = model.simulate(self.simulation_count, self.backend.as_ref(), &mut rand::thread_rng())?;
+        let run = Arc::new(SeasonRun {
+            run_id: Uuid::new_v4(),
+            teams,
+            outcomes,
+            simulated_at: Utc::now(),
+            fingerprint,
+        });
+
+        debug!(
+            "Simulated {} season {} ({} runs, {} backend)",
+            sport.as_str(),
+            run.run_id,
+            self.simulation_count,
+            self.backend.name()
+        );
+        runs.insert(sport, run.clone());
+        Ok(run)
+    }
+
+    /// Estimate home advantage from all stored results and record it for audit
+    async fn estimate_home_advantage(&self, sport: Sport, teams: &[TeamRating]) -> Result<HomeAdvantage> {
+        let history = self.storage.fetch_game_history(sport).await?;
+        let home_advantage = HomeAdvantage::estimate(sport, teams, &history);
+
+        let estimated_at = Utc::now();
+        for (team, venue) in home_advantage.venues() {
+            self.storage.upsert_home_advantage(&HomeAdvantageEstimate {
+                sport,
+                team: team.clone(),
+                league: home_advantage.league,
+                games: venue.games.round() as i64,
+                raw: venue.raw,
+                structural: venue.structural,
+                estimate: venue.estimate,
+                estimated_at,
+            }).await?;
+        }
+
+        debug!(
+            "{} home edge {:.2} (+{:.2} per 1000 m, {:+.2} indoors) from {} games",
+            sport.as_str(),
+            home_advantage.league,
+            home_advantage.altitude_per_km,
+            home_advantage.indoor,
+            history.len()
+        );
+        Ok(home_advantage)
+    }
+}
+
+fn fingerprint(teams: &[TeamRating], games: &[Game], injuries: &[InjuryAdjustment]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for team in teams {
+        (&team.team, &team.conference, &team.division, team.rating.to_bits()).hash(&mut hasher);
+        (team.latitude.map(f64::to_bits), team.longitude.map(f64::to_bits)).hash(&mut hasher);
+        (team.elevation_m.map(f64::to_bits), team.indoor).hash(&mut hasher);
+    }
+    for game in games {
+        (&game.game_id, game.start_time, game.home_score, game.away_score).hash(&mut hasher);
+    }
+    for injury in injuries {
+        (&injury.team, &injury.player, injury.rating_delta.to_bits(), injury.out_until).hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::batch::ScalarBackend;
+    use crate::storage::MemoryStorage;
+
+    fn team(name: &str, rating: f64) -> TeamRating {
+        TeamRating {
+            sport: Sport::NBA,
+            team: name.to_string(),
+            conference: "Eastern".to_string(),
+            division: "Atlantic".to_string(),
+            rating,
+            latitude: None,
+            longitude: None,
+            elevation_m: None,
+            indoor: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn reuses_runs_until_inputs_change() {
+        let storage = Arc::new(MemoryStorage::new());
+        storage.seed_season(vec![team("Celtics", 6.0), team("Knicks", 2.0)], Vec::new()).await;
+        let simulator = SeasonSimulator::new(storage.clone(), Arc::new(ScalarBackend), 100);
+
+        let first = simulator.run(Sport::NBA).await.unwrap();
+        let cached = simulator.run(Sport::NBA).await.unwrap();
+        assert_eq!(first.run_id, cached.run_id);
+
+        storage.insert_injury(InjuryAdjustment {
+            sport: Sport::NBA,
+            team: "Celtics".to_string(),
+            player: "Jayson Tatum".to_string(),
+            rating_delta: -4.0,
+            out_until: None,
+        }).await;
+        let rerun = simulator.run(Sport::NBA).await.unwrap();
+        assert_ne!(first.run_id, rerun.run_id);
+    }
+}
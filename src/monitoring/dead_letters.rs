@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+
+use crate::types::DeadLetter;
+
+/// Most frequent failures named per source in the digest
+const TOP_REASONS: usize = 3;
+
+/// Dead letters per source, as (distinct items, total occurrences)
+pub fn dead_letter_counts(letters: &[DeadLetter]) -> BTreeMap<&str, (i64, i64)> {
+    let mut counts: BTreeMap<&str, (i64, i64)> = BTreeMap::new();
+    for letter in letters {
+        let count = counts.entry(letter.source.as_str()).or_default();
+        count.0 += 1;
+        count.1 += letter.occurrences;
+    }
+    counts
+}
+
+/// Summary of the dead letters seen since the last digest, one line per
+/// source with its most frequent reasons; `None` when nothing was dropped
+pub fn dead_letter_digest(letters: &[DeadLetter]) -> Option<String> {
+    if letters.is_empty() {
+        return None;
+    }
+
+    let mut reasons: BTreeMap<&str, BTreeMap<&str, i64>> = BTreeMap::new();
+    for letter in letters {
+        *reasons.entry(letter.source.as_str())
+            .or_default()
+            .entry(letter.reason.as_str())
+            .or_default() += 1;
+    }
+
+    let mut lines = vec![format!("🪦 {} rows dead-lettered", letters.len())];
+    for (source, (items, _)) in dead_letter_counts(letters) {
+        let mut top: Vec<(&str, i64)> = reasons[source].iter().map(|(r, n)| (*r, *n)).collect();
+        top.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        let top: Vec<String> = top.iter()
+            .take(TOP_REASONS)
+            .map(|(reason, n)| format!("{} ×{}", reason, n))
+            .collect();
+        lines.push(format!("  {}: {} ({})", source, items, top.join(", ")));
+    }
+    Some(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn letter(source: &str, key: &str, reason: &str, occurrences: i64) -> DeadLetter {
+        DeadLetter {
+            source: source.to_string(),
+            item_key: key.to_string(),
+            reason: reason.to_string(),
+            raw: serde_json::Value::Null,
+            occurrences,
+            first_seen: Utc::now(),
+            last_seen: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn digest_groups_by_source_with_top_reasons() {
+        assert_eq!(dead_letter_digest(&[]), None);
+
+        let letters = vec![
+            letter(DeadLetter::POLYMARKET_LISTING, "1", "invalid YES price", 40),
+            letter(DeadLetter::POLYMARKET_LISTING, "2", "invalid YES price", 2),
+            letter(DeadLetter::POLYMARKET_LISTING, "3", "no start or end time", 1),
+            letter(DeadLetter::MARKET_ROW, "0xa", "unknown sport", 5),
+        ];
+
+        let counts = dead_letter_counts(&letters);
+        assert_eq!(counts[DeadLetter::POLYMARKET_LISTING], (3, 43));
+        assert_eq!(counts[DeadLetter::MARKET_ROW], (1, 5));
+
+        let digest = dead_letter_digest(&letters).unwrap();
+        assert_eq!(digest, "🪦 4 rows dead-lettered\n  market_row: 1 (unknown sport ×1)\n  polymarket_listing: 3 (invalid YES price ×2, no start or end time ×1)");
+    }
+}
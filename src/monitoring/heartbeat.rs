@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Last sign of life from each long-running service loop
+#[derive(Clone, Default)]
+pub struct Heartbeats {
+    beats: Arc<Mutex<HashMap<&'static str, Beat>>>,
+}
+
+#[derive(Clone, Copy)]
+struct Beat {
+    last: Instant,
+    /// How often the service cycles when healthy
+    every: Duration,
+}
+
+impl Heartbeats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that `service` completed a cycle (or started)
+    pub fn beat(&self, service: &'static str) {
+        self.beat_every(service, Duration::ZERO);
+    }
+
+    /// Record a cycle of a service that only cycles every `every`, so it is
+    /// not taken for stalled until it has missed two cycles
+    pub fn beat_every(&self, service: &'static str, every: Duration) {
+        self.beats.lock().unwrap().insert(service, Beat { last: Instant::now(), every });
+    }
+
+    /// Every service seen so far, with whether it has gone quiet for longer than `max_silence`
+    pub fn check(&self, max_silence: Duration) -> Vec<(&'static str, Option<Duration>)> {
+        self.check_at(Instant::now(), max_silence)
+    }
+
+    fn check_at(&self, now: Instant, max_silence: Duration) -> Vec<(&'static str, Option<Duration>)> {
+        self.beats.lock().unwrap()
+            .iter()
+            .map(|(service, beat)| {
+                let silence = now.duration_since(beat.last);
+                (*service, (silence > max_silence.max(beat.every * 2)).then_some(silence))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slow_services_get_two_of_their_cycles_before_they_stall() {
+        let heartbeats = Heartbeats::new();
+        heartbeats.beat("data_pipeline");
+        heartbeats.beat_every("settlement_monitor", Duration::from_secs(300));
+        let stalled = |after: u64| {
+            let mut stalled: Vec<&str> = heartbeats.check_at(Instant::now() + Duration::from_secs(after), Duration::from_secs(300))
+                .into_iter()
+                .filter_map(|(service, silence)| silence.map(|_| service))
+                .collect();
+            stalled.sort();
+            stalled
+        };
+
+        assert!(stalled(60).is_empty());
+        assert_eq!(stalled(400), vec!["data_pipeline"]);
+        assert_eq!(stalled(700), vec!["data_pipeline", "settlement_monitor"]);
+    }
+}
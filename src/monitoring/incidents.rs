@@ -0,0 +1,222 @@
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+use tracing::{error, info};
+
+use crate::config::IncidentsConfig;
+
+const PAGERDUTY_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+const OPSGENIE_ALERTS_URL: &str = "https://api.opsgenie.com/v2/alerts";
+const SOURCE: &str = "polymarket-trading-bot";
+
+/// Pages an on-call operator through PagerDuty and/or Opsgenie
+///
+/// Incidents are keyed by condition (e.g. `circuit-breaker`), which doubles
+/// as the provider's deduplication key, so re-raising an open incident
+/// adds nothing and a restart picks up the incident it left open. An
+/// incident is resolved as soon as its condition is seen to have cleared.
+pub struct IncidentManager {
+    client: Client,
+    pagerduty_routing_key: Option<String>,
+    opsgenie_api_key: Option<String>,
+    pagerduty_url: String,
+    opsgenie_url: String,
+    /// Last reported state per key; unknown keys are reported either way
+    open: Mutex<HashMap<String, bool>>,
+}
+
+impl IncidentManager {
+    pub fn new(config: &IncidentsConfig) -> Self {
+        let non_empty = |key: &Option<String>| key.clone().filter(|k| !k.is_empty());
+
+        Self {
+            client: Client::new(),
+            pagerduty_routing_key: non_empty(&config.pagerduty_routing_key),
+            opsgenie_api_key: non_empty(&config.opsgenie_api_key),
+            pagerduty_url: PAGERDUTY_EVENTS_URL.to_string(),
+            opsgenie_url: OPSGENIE_ALERTS_URL.to_string(),
+            open: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Send to `base_url` instead of the providers
+    #[cfg(test)]
+    fn at(self, base_url: &str) -> Self {
+        Self {
+            pagerduty_url: format!("{}/pagerduty", base_url),
+            opsgenie_url: format!("{}/opsgenie", base_url),
+            ..self
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.pagerduty_routing_key.is_some() || self.opsgenie_api_key.is_some()
+    }
+
+    /// Bring the incident `key` in line with its condition: raised with
+    /// `summary` while the condition holds, resolved once it returns `None`
+    pub async fn update(&self, key: &str, summary: Option<String>) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let active = summary.is_some();
+        let mut open = self.open.lock().await;
+        if open.get(key) == Some(&active) {
+            return;
+        }
+
+        let result = match &summary {
+            Some(summary) => self.trigger(key, summary).await,
+            None => self.resolve(key).await,
+        };
+        match result {
+            Ok(()) => {
+                info!("📟 Incident {} {}", key, if active { "raised" } else { "resolved" });
+                open.insert(key.to_string(), active);
+            }
+            // Left unrecorded so the next check tries again
+            Err(e) => error!("Failed to update incident {}: {}", key, e),
+        }
+    }
+
+    async fn trigger(&self, key: &str, summary: &str) -> Result<()> {
+        if let Some(routing_key) = &self.pagerduty_routing_key {
+            self.pagerduty(serde_json::json!({
+                "routing_key": routing_key,
+                "event_action": "trigger",
+                "dedup_key": key,
+                "payload": {
+                    "summary": summary,
+                    "source": SOURCE,
+                    "severity": "critical",
+                },
+            })).await?;
+        }
+
+        if let Some(api_key) = &self.opsgenie_api_key {
+            let response = self.client
+                .post(&self.opsgenie_url)
+                .header("Authorization", format!("GenieKey {}", api_key))
+                .json(&serde_json::json!({
+                    "message": summary,
+                    "alias": key,
+                    "source": SOURCE,
+                    "priority": "P1",
+                }))
+                .send()
+                .await?;
+            check(response, "Opsgenie")?;
+        }
+
+        Ok(())
+    }
+
+    async fn resolve(&self, key: &str) -> Result<()> {
+        if let Some(routing_key) = &self.pagerduty_routing_key {
+            self.pagerduty(serde_json::json!({
+                "routing_key": routing_key,
+                "event_action": "resolve",
+                "dedup_key": key,
+            })).await?;
+        }
+
+        if let Some(api_key) = &self.opsgenie_api_key {
+            let response = self.client
+                .post(format!("{}/{}/close", self.opsgenie_url, key))
+                .query(&[("identifierType", "alias")])
+                .header("Authorization", format!("GenieKey {}", api_key))
+                .json(&serde_json::json!({ "source": SOURCE }))
+                .send()
+                .await?;
+            check(response, "Opsgenie")?;
+        }
+
+        Ok(())
+    }
+
+    async fn pagerduty(&self, event: serde_json::Value) -> Result<()> {
+        let response = self.client.post(&self.pagerduty_url).json(&event).send().await?;
+        check(response, "PagerDuty")
+    }
+}
+
+fn check(response: reqwest::Response, provider: &str) -> Result<()> {
+    if !response.status().is_success() {
+        return Err(anyhow!("{} returned {}", provider, response.status()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    fn config() -> IncidentsConfig {
+        IncidentsConfig {
+            pagerduty_routing_key: Some("routing".to_string()),
+            opsgenie_api_key: Some("genie".to_string()),
+            ..IncidentsConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn conditions_page_once_and_resolve_when_cleared() {
+        let mut server = mockito::Server::new_async().await;
+        let mut pagerduty = |action: &str| server.mock("POST", "/pagerduty")
+            .match_body(Matcher::PartialJson(serde_json::json!({ "event_action": action, "dedup_key": "circuit-breaker" })))
+            .with_status(202)
+            .expect(1);
+        let triggered = pagerduty("trigger").create_async().await;
+        let resolved = pagerduty("resolve").create_async().await;
+        let opened = server.mock("POST", "/opsgenie")
+            .match_header("Authorization", "GenieKey genie")
+            .match_body(Matcher::PartialJson(serde_json::json!({ "alias": "circuit-breaker", "priority": "P1" })))
+            .with_status(202)
+            .expect(1)
+            .create_async().await;
+        let closed = server.mock("POST", "/opsgenie/circuit-breaker/close")
+            .match_query(Matcher::UrlEncoded("identifierType".to_string(), "alias".to_string()))
+            .with_status(202)
+            .expect(1)
+            .create_async().await;
+
+        let incidents = IncidentManager::new(&config()).at(&server.url());
+        // A condition that persists is raised once, and cleared once
+        incidents.update("circuit-breaker", Some("1 circuit breaker(s) active".to_string())).await;
+        incidents.update("circuit-breaker", Some("1 circuit breaker(s) active".to_string())).await;
+        incidents.update("circuit-breaker", None).await;
+        incidents.update("circuit-breaker", None).await;
+
+        triggered.assert_async().await;
+        resolved.assert_async().await;
+        opened.assert_async().await;
+        closed.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn failed_pages_are_retried_on_the_next_check() {
+        let mut server = mockito::Server::new_async().await;
+        let refused = server.mock("POST", "/pagerduty").with_status(500).expect(2).create_async().await;
+        let config = IncidentsConfig { opsgenie_api_key: None, ..config() };
+        let incidents = IncidentManager::new(&config).at(&server.url());
+
+        incidents.update("stalled-data_pipeline", Some("stalled".to_string())).await;
+        incidents.update("stalled-data_pipeline", Some("stalled".to_string())).await;
+        refused.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn without_keys_nothing_is_sent() {
+        let mut server = mockito::Server::new_async().await;
+        let nothing = server.mock("POST", Matcher::Any).expect(0).create_async().await;
+        let config = IncidentsConfig { pagerduty_routing_key: Some(String::new()), ..IncidentsConfig::default() };
+        let incidents = IncidentManager::new(&config).at(&server.url());
+
+        assert!(!incidents.is_enabled());
+        incidents.update("circuit-breaker", Some("active".to_string())).await;
+        nothing.assert_async().await;
+    }
+}
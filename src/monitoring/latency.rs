@@ -0,0 +1,139 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use crate::config::LatencyConfig;
+
+/// Samples kept per stage; older ones are dropped first
+const MAX_SAMPLES: usize = 10_000;
+
+/// A hop on the way from a bookmaker's price move to our order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LatencyStage {
+    /// Bookmaker update to the odds being received
+    OddsIngest,
+    /// A market being marked moved to a signal on it being stored
+    Signal,
+    /// A signal being generated to its order filling
+    Execution,
+}
+
+impl LatencyStage {
+    pub const ALL: [LatencyStage; 3] = [LatencyStage::OddsIngest, LatencyStage::Signal, LatencyStage::Execution];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LatencyStage::OddsIngest => "odds_ingest",
+            LatencyStage::Signal => "signal",
+            LatencyStage::Execution => "execution",
+        }
+    }
+}
+
+fn budget_ms(config: &LatencyConfig, stage: LatencyStage) -> u64 {
+    match stage {
+        LatencyStage::OddsIngest => config.odds_ingest_ms,
+        LatencyStage::Signal => config.signal_ms,
+        LatencyStage::Execution => config.execution_ms,
+    }
+}
+
+/// Recent latency of one stage against its budget
+#[derive(Debug, Clone, Serialize)]
+pub struct StageLatency {
+    pub stage: LatencyStage,
+    pub samples: usize,
+    pub p50_ms: Option<i64>,
+    pub p95_ms: Option<i64>,
+    pub max_ms: Option<i64>,
+    pub budget_ms: u64,
+    /// p95 over budget on enough samples to judge
+    pub over_budget: bool,
+}
+
+/// Latency samples per pipeline stage, shared by the services that take them
+#[derive(Clone, Default)]
+pub struct Latencies {
+    samples: Arc<Mutex<HashMap<LatencyStage, VecDeque<(DateTime<Utc>, i64)>>>>,
+}
+
+impl Latencies {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, stage: LatencyStage, latency: Duration) {
+        self.record_at(stage, latency, Utc::now());
+    }
+
+    fn record_at(&self, stage: LatencyStage, latency: Duration, at: DateTime<Utc>) {
+        let mut samples = self.samples.lock().unwrap();
+        let stage = samples.entry(stage).or_default();
+        if stage.len() >= MAX_SAMPLES {
+            stage.pop_front();
+        }
+        stage.push_back((at, latency.num_milliseconds().max(0)));
+    }
+
+    /// Each stage over the samples of the last `config.window_secs`
+    pub fn report(&self, config: &LatencyConfig, now: DateTime<Utc>) -> Vec<StageLatency> {
+        let since = now - Duration::seconds(config.window_secs as i64);
+        let mut samples = self.samples.lock().unwrap();
+
+        LatencyStage::ALL.iter()
+            .map(|stage| {
+                let recent = samples.entry(*stage).or_default();
+                while recent.front().map_or(false, |(at, _)| *at < since) {
+                    recent.pop_front();
+                }
+                let mut ms: Vec<i64> = recent.iter().map(|(_, ms)| *ms).collect();
+                ms.sort_unstable();
+
+                let percentile = |p: f64| (!ms.is_empty()).then(|| ms[((ms.len() - 1) as f64 * p).ceil() as usize]);
+                let budget = budget_ms(config, *stage);
+                let p95_ms = percentile(0.95);
+                StageLatency {
+                    stage: *stage,
+                    samples: ms.len(),
+                    p50_ms: percentile(0.5),
+                    p95_ms,
+                    max_ms: ms.last().copied(),
+                    budget_ms: budget,
+                    over_budget: ms.len() >= config.min_samples
+                        && p95_ms.map_or(false, |p95| p95 > budget as i64),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn p95_over_the_window_is_checked_against_the_budget() {
+        let config = LatencyConfig::default();
+        let latencies = Latencies::new();
+        let now = Utc::now();
+        // 94 quick fills and 6 slow ones put p95 over the execution budget
+        for i in 0..100 {
+            let ms = if i < 94 { 500 } else { 60_000 };
+            latencies.record_at(LatencyStage::Execution, Duration::milliseconds(ms), now);
+        }
+        // Slow samples outside the window are forgotten
+        latencies.record_at(LatencyStage::Signal, Duration::seconds(60), now - Duration::hours(2));
+        latencies.record_at(LatencyStage::Signal, Duration::milliseconds(200), now);
+
+        let report = latencies.report(&config, now);
+        let execution = report.iter().find(|s| s.stage == LatencyStage::Execution).unwrap();
+        assert_eq!((execution.samples, execution.p50_ms, execution.p95_ms), (100, Some(500), Some(60_000)));
+        assert!(execution.over_budget);
+
+        let signal = report.iter().find(|s| s.stage == LatencyStage::Signal).unwrap();
+        assert_eq!((signal.samples, signal.max_ms), (1, Some(200)));
+        assert!(!signal.over_budget);
+    }
+}
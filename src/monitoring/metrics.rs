@@ -1,9 +1,16 @@
 use anyhow::Result;
-use prometheus::{Registry, Gauge, Counter, IntGauge};
+use prometheus::{Registry, Gauge, GaugeVec, Counter, IntGauge, IntGaugeVec, Opts};
 use lazy_static::lazy_static;
 use rust_decimal::Decimal;
+use std::collections::BTreeMap;
 
 use crate::config::Config;
+use crate::numeric;
+use crate::risk::{ExposureReport, StrategyCalibration};
+use super::StageLatency;
+use crate::data::SportCoverage;
+use crate::storage::VenueBalances;
+use crate::types::{HomeAdvantageEstimate, TradeMark};
 
 lazy_static! {
     static ref REGISTRY: Registry = Registry::new();
@@ -13,6 +20,11 @@ lazy_static! {
         "Total portfolio value in USD"
     ).unwrap();
     
+    static ref SETTLEMENT_PENDING: Gauge = Gauge::new(
+        "settlement_pending_capital",
+        "Cost in USD of open positions on ended markets awaiting settlement"
+    ).unwrap();
+    
     static ref OPEN_POSITIONS: IntGauge = IntGauge::new(
         "open_positions_count",
         "Number of open positions"
@@ -27,6 +39,76 @@ lazy_static! {
         "signals_generated_total",
         "Total signals generated"
     ).unwrap();
+
+    static ref EXECUTION_COSTS_TODAY: Gauge = Gauge::new(
+        "execution_costs_today",
+        "Gas and fees of trades entered today in USD"
+    ).unwrap();
+
+    static ref WORST_POSITION_PNL: Gauge = Gauge::new(
+        "worst_open_position_pnl",
+        "Unrealized PnL in USD of the worst open position"
+    ).unwrap();
+
+    static ref VENUE_PNL: GaugeVec = GaugeVec::new(
+        Opts::new("venue_pnl", "Realized and unrealized PnL in USD by venue"),
+        &["venue", "kind"]
+    ).unwrap();
+
+    static ref VENUE_INVESTED: GaugeVec = GaugeVec::new(
+        Opts::new("venue_invested_capital", "Capital in open positions in USD by venue"),
+        &["venue"]
+    ).unwrap();
+
+    static ref VENUE_OPEN_POSITIONS: IntGaugeVec = IntGaugeVec::new(
+        Opts::new("venue_open_positions_count", "Number of open positions by venue"),
+        &["venue"]
+    ).unwrap();
+
+    static ref EXPOSURE: GaugeVec = GaugeVec::new(
+        Opts::new("open_exposure", "Gross and net open exposure in USD by sport, event, strategy and time to event"),
+        &["dimension", "key", "kind"]
+    ).unwrap();
+
+    static ref EXPOSURE_UTILIZATION: GaugeVec = GaugeVec::new(
+        Opts::new("exposure_limit_utilization_pct", "Gross open exposure as a percentage of its concentration limit"),
+        &["dimension", "key"]
+    ).unwrap();
+
+    static ref STAGE_LATENCY: GaugeVec = GaugeVec::new(
+        Opts::new("stage_latency_ms", "Recent p50 and p95 latency in milliseconds by pipeline stage, with its budget"),
+        &["stage", "kind"]
+    ).unwrap();
+
+    static ref MARKET_COVERAGE: IntGaugeVec = IntGaugeVec::new(
+        Opts::new("market_coverage", "Markets by sport at each pipeline stage: listed, ingested, tracked, matched and analyzed"),
+        &["sport", "stage"]
+    ).unwrap();
+
+    static ref DEAD_LETTERS: IntGaugeVec = IntGaugeVec::new(
+        Opts::new("dead_letters", "Rows that failed to map over the dead-letter window, by source: distinct items and total occurrences"),
+        &["source", "kind"]
+    ).unwrap();
+
+    static ref CALIBRATION: GaugeVec = GaugeVec::new(
+        Opts::new("prediction_calibration", "Mean predicted probability, realized frequency and trade count by strategy and probability bin"),
+        &["strategy", "bin", "kind"]
+    ).unwrap();
+
+    static ref EDGE_RETURN: GaugeVec = GaugeVec::new(
+        Opts::new("edge_realized_return", "Mean edge at entry, realized return and trade count by strategy and edge bin"),
+        &["strategy", "bin", "kind"]
+    ).unwrap();
+
+    static ref BRIER_SCORE: GaugeVec = GaugeVec::new(
+        Opts::new("prediction_brier_score", "Mean Brier score of closed trades' predictions by strategy"),
+        &["strategy"]
+    ).unwrap();
+
+    static ref HOME_ADVANTAGE: GaugeVec = GaugeVec::new(
+        Opts::new("home_advantage", "League, raw, structural and shrunk home edge and games behind it by sport and home team"),
+        &["sport", "team", "kind"]
+    ).unwrap();
 }
 
 pub struct MetricsCollector {
@@ -37,9 +119,24 @@ impl MetricsCollector {
     pub fn new(_config: &Config) -> Result<Self> {
         // Register metrics
         REGISTRY.register(Box::new(PORTFOLIO_VALUE.clone()))?;
+        REGISTRY.register(Box::new(SETTLEMENT_PENDING.clone()))?;
         REGISTRY.register(Box::new(OPEN_POSITIONS.clone()))?;
         REGISTRY.register(Box::new(DAILY_TRADES.clone()))?;
         REGISTRY.register(Box::new(SIGNALS_GENERATED.clone()))?;
+        REGISTRY.register(Box::new(EXECUTION_COSTS_TODAY.clone()))?;
+        REGISTRY.register(Box::new(WORST_POSITION_PNL.clone()))?;
+        REGISTRY.register(Box::new(VENUE_PNL.clone()))?;
+        REGISTRY.register(Box::new(VENUE_INVESTED.clone()))?;
+        REGISTRY.register(Box::new(VENUE_OPEN_POSITIONS.clone()))?;
+        REGISTRY.register(Box::new(EXPOSURE.clone()))?;
+        REGISTRY.register(Box::new(EXPOSURE_UTILIZATION.clone()))?;
+        REGISTRY.register(Box::new(STAGE_LATENCY.clone()))?;
+        REGISTRY.register(Box::new(MARKET_COVERAGE.clone()))?;
+        REGISTRY.register(Box::new(DEAD_LETTERS.clone()))?;
+        REGISTRY.register(Box::new(CALIBRATION.clone()))?;
+        REGISTRY.register(Box::new(EDGE_RETURN.clone()))?;
+        REGISTRY.register(Box::new(BRIER_SCORE.clone()))?;
+        REGISTRY.register(Box::new(HOME_ADVANTAGE.clone()))?;
 
         Ok(Self {
             _registry: &REGISTRY,
@@ -47,11 +144,17 @@ impl MetricsCollector {
     }
 
     pub fn record_portfolio_value(&self, value: Decimal) {
-        if let Some(value_f64) = value.to_f64() {
+        if let Ok(value_f64) = numeric::to_f64(value) {
             PORTFOLIO_VALUE.set(value_f64);
         }
     }
 
+    pub fn record_settlement_pending(&self, value: Decimal) {
+        if let Ok(value_f64) = numeric::to_f64(value) {
+            SETTLEMENT_PENDING.set(value_f64);
+        }
+    }
+
     pub fn record_open_positions(&self, count: i64) {
         OPEN_POSITIONS.set(count);
     }
@@ -63,6 +166,149 @@ impl MetricsCollector {
         }
     }
 
+    pub fn record_execution_costs(&self, costs: Decimal) {
+        if let Ok(costs) = numeric::to_f64(costs) {
+            EXECUTION_COSTS_TODAY.set(costs);
+        }
+    }
+
+    pub fn record_trade_marks(&self, marks: &[TradeMark]) {
+        let worst = marks.iter().map(|m| m.unrealized_pnl).min().unwrap_or_default();
+        if let Ok(worst) = numeric::to_f64(worst) {
+            WORST_POSITION_PNL.set(worst);
+        }
+    }
+
+    pub fn record_venue_balances(&self, balances: &VenueBalances) {
+        let venue = balances.venue.as_str();
+        if let Ok(pnl) = numeric::to_f64(balances.realized_pnl) {
+            VENUE_PNL.with_label_values(&[venue, "realized"]).set(pnl);
+        }
+        if let Ok(pnl) = numeric::to_f64(balances.unrealized_pnl) {
+            VENUE_PNL.with_label_values(&[venue, "unrealized"]).set(pnl);
+        }
+        if let Ok(invested) = numeric::to_f64(balances.invested_capital) {
+            VENUE_INVESTED.with_label_values(&[venue]).set(invested);
+        }
+        VENUE_OPEN_POSITIONS.with_label_values(&[venue]).set(balances.open_positions);
+    }
+
+    pub fn record_exposure(&self, report: &ExposureReport) {
+        // Slices come and go with positions; drop the ones no longer held
+        EXPOSURE.reset();
+        EXPOSURE_UTILIZATION.reset();
+
+        let slices = [
+            ("sport", &report.by_sport),
+            ("event", &report.by_event),
+            ("strategy", &report.by_strategy),
+            ("time_to_event", &report.by_time_to_event),
+        ];
+        for (dimension, slices) in slices {
+            for slice in slices {
+                let key = slice.key.as_str();
+                if let Ok(gross) = numeric::to_f64(slice.gross) {
+                    EXPOSURE.with_label_values(&[dimension, key, "gross"]).set(gross);
+                }
+                if let Ok(net) = numeric::to_f64(slice.net) {
+                    EXPOSURE.with_label_values(&[dimension, key, "net"]).set(net);
+                }
+                if let Some(Ok(utilization)) = slice.utilization_pct.map(numeric::to_f64) {
+                    EXPOSURE_UTILIZATION.with_label_values(&[dimension, key]).set(utilization);
+                }
+            }
+        }
+        if let Some(Ok(utilization)) = report.utilization_pct.map(numeric::to_f64) {
+            EXPOSURE_UTILIZATION.with_label_values(&["total", "total"]).set(utilization);
+        }
+    }
+
+    pub fn record_coverage(&self, report: &[SportCoverage]) {
+        for coverage in report {
+            let sport = coverage.sport.as_str();
+            let stages = [
+                ("listed", coverage.listed),
+                ("ingested", coverage.ingested),
+                ("tracked", coverage.tracked),
+                ("matched", coverage.matched),
+                ("analyzed", coverage.analyzed),
+            ];
+            for (stage, count) in stages {
+                MARKET_COVERAGE.with_label_values(&[sport, stage]).set(count as i64);
+            }
+        }
+    }
+
+    pub fn record_dead_letters(&self, counts: &BTreeMap<&str, (i64, i64)>) {
+        // Sources drop out once their dead letters age past the window
+        DEAD_LETTERS.reset();
+        for (source, (items, occurrences)) in counts {
+            DEAD_LETTERS.with_label_values(&[*source, "items"]).set(*items);
+            DEAD_LETTERS.with_label_values(&[*source, "occurrences"]).set(*occurrences);
+        }
+    }
+
+    pub fn record_calibration(&self, report: &[StrategyCalibration]) {
+        // Bins empty out as trades age past the lookback
+        CALIBRATION.reset();
+        EDGE_RETURN.reset();
+        BRIER_SCORE.reset();
+
+        for calibration in report {
+            let strategy = calibration.strategy.as_str();
+            if let Ok(brier) = numeric::to_f64(calibration.brier) {
+                BRIER_SCORE.with_label_values(&[strategy]).set(brier);
+            }
+            // Bins are labelled by their lower bound so they sort on the x axis
+            for bin in &calibration.reliability {
+                let label = bin.lower.to_string();
+                if let (Ok(predicted), Ok(realized)) = (numeric::to_f64(bin.predicted), numeric::to_f64(bin.realized)) {
+                    CALIBRATION.with_label_values(&[strategy, &label, "predicted"]).set(predicted);
+                    CALIBRATION.with_label_values(&[strategy, &label, "realized"]).set(realized);
+                }
+                CALIBRATION.with_label_values(&[strategy, &label, "trades"]).set(bin.trades as f64);
+            }
+            for (i, bin) in calibration.edge_returns.iter().enumerate() {
+                let label = i.to_string();
+                if let (Ok(edge), Ok(realized)) = (numeric::to_f64(bin.edge), numeric::to_f64(bin.realized_return)) {
+                    EDGE_RETURN.with_label_values(&[strategy, &label, "edge"]).set(edge);
+                    EDGE_RETURN.with_label_values(&[strategy, &label, "return"]).set(realized);
+                }
+                EDGE_RETURN.with_label_values(&[strategy, &label, "trades"]).set(bin.trades as f64);
+            }
+        }
+    }
+
+    pub fn record_home_advantage(&self, estimates: &[HomeAdvantageEstimate]) {
+        // A venue without results since its last estimate keeps no raw edge
+        HOME_ADVANTAGE.reset();
+        for estimate in estimates {
+            let labels = |kind| [estimate.sport.as_str(), estimate.team.as_str(), kind];
+            HOME_ADVANTAGE.with_label_values(&labels("league")).set(estimate.league);
+            HOME_ADVANTAGE.with_label_values(&labels("structural")).set(estimate.structural);
+            HOME_ADVANTAGE.with_label_values(&labels("estimate")).set(estimate.estimate);
+            HOME_ADVANTAGE.with_label_values(&labels("games")).set(estimate.games as f64);
+            if let Some(raw) = estimate.raw {
+                HOME_ADVANTAGE.with_label_values(&labels("raw")).set(raw);
+            }
+        }
+    }
+
+    pub fn record_stage_latency(&self, stages: &[StageLatency]) {
+        for stage in stages {
+            let name = stage.stage.as_str();
+            for (kind, ms) in [("p50", stage.p50_ms), ("p95", stage.p95_ms)] {
+                match ms {
+                    Some(ms) => STAGE_LATENCY.with_label_values(&[name, kind]).set(ms as f64),
+                    None => {
+                        let _ = STAGE_LATENCY.remove_label_values(&[name, kind]);
+                    }
+                }
+            }
+            STAGE_LATENCY.with_label_values(&[name, "budget"]).set(stage.budget_ms as f64);
+        }
+    }
+
     pub fn record_signal_generated(&self) {
         SIGNALS_GENERATED.inc();
     }
@@ -1,5 +1,14 @@
+mod dead_letters;
+mod heartbeat;
+mod incidents;
+mod latency;
 mod service;
 mod metrics;
+mod notifications;
 
+pub use heartbeat::Heartbeats;
+pub use incidents::IncidentManager;
+pub use latency::{Latencies, LatencyStage, StageLatency};
 pub use service::MonitoringService;
 pub use metrics::MetricsCollector;
+pub use notifications::{EventAlerts, NotificationService, Notifier};
@@ -0,0 +1,312 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, NaiveTime, Utc};
+use futures::StreamExt;
+use reqwest::Client;
+use tokio::sync::mpsc;
+use tokio::time::{interval, sleep, Duration, MissedTickBehavior};
+use tracing::{error, info, warn};
+
+use crate::config::Config;
+use crate::events::{Event, EventBus, EventKind};
+use crate::types::MarketStatus;
+
+/// Events operators are told about
+const ALERTED: [EventKind; 3] = [EventKind::BreakerTriggered, EventKind::OrderFilled, EventKind::MarketUpdated];
+
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+
+/// How urgently a notification must reach an operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Sent straight away, quiet hours or not
+    Critical,
+    /// Batched into the next digest; dropped during quiet hours
+    Info,
+}
+
+struct Notification {
+    severity: Severity,
+    text: String,
+    at: DateTime<Utc>,
+}
+
+/// Handle for raising notifications; cheap to clone into every service
+#[derive(Clone)]
+pub struct Notifier {
+    tx: mpsc::UnboundedSender<Notification>,
+}
+
+impl Notifier {
+    pub fn notify(&self, severity: Severity, text: impl Into<String>) {
+        // Only fails once the service has stopped, when there is no one to tell
+        let _ = self.tx.send(Notification { severity, text: text.into(), at: Utc::now() });
+    }
+
+    pub fn critical(&self, text: impl Into<String>) {
+        self.notify(Severity::Critical, text);
+    }
+
+    pub fn info(&self, text: impl Into<String>) {
+        self.notify(Severity::Info, text);
+    }
+
+    /// A notifier with no service behind it
+    #[cfg(test)]
+    pub fn detached() -> Self {
+        let (tx, _) = mpsc::unbounded_channel();
+        Self { tx }
+    }
+}
+
+/// Daily window, in UTC, in which informational notifications are dropped
+#[derive(Debug, Clone, Copy)]
+struct QuietHours {
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl QuietHours {
+    fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            // Wraps past midnight, e.g. 23:00-07:00
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// Delivers notifications: critical ones at once, the rest as periodic
+/// digests outside quiet hours
+///
+/// Messages go to Telegram when a bot token and chat id are configured and
+/// to the log otherwise.
+pub struct NotificationService {
+    rx: mpsc::UnboundedReceiver<Notification>,
+    telegram: Option<Telegram>,
+    digest_interval: Duration,
+    quiet_hours: Option<QuietHours>,
+    digest: Vec<Notification>,
+    /// Informational notifications dropped in quiet hours since the last digest
+    suppressed: usize,
+}
+
+impl NotificationService {
+    pub fn new(config: &Config) -> Result<(Self, Notifier)> {
+        let settings = &config.monitoring.notifications;
+        let quiet_hours = match &settings.quiet_hours {
+            Some(window) => Some(QuietHours {
+                start: parse_time(&window.start).context("monitoring.notifications.quiet_hours.start")?,
+                end: parse_time(&window.end).context("monitoring.notifications.quiet_hours.end")?,
+            }),
+            None => None,
+        };
+
+        let telegram = match (&config.monitoring.telegram_bot_token, &config.monitoring.telegram_chat_id) {
+            (Some(token), Some(chat_id)) if !token.is_empty() && !chat_id.is_empty() => Some(Telegram {
+                client: Client::new(),
+                token: token.clone(),
+                chat_id: chat_id.clone(),
+            }),
+            _ => None,
+        };
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let service = Self {
+            rx,
+            telegram,
+            digest_interval: Duration::from_secs(settings.digest_interval_secs.max(1)),
+            quiet_hours,
+            digest: Vec::new(),
+            suppressed: 0,
+        };
+
+        Ok((service, Notifier { tx }))
+    }
+
+    pub async fn run(mut self) -> Result<()> {
+        let mut digest_tick = interval(self.digest_interval);
+        digest_tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        // The first tick completes immediately
+        digest_tick.tick().await;
+
+        info!("📣 Notification service started");
+
+        loop {
+            tokio::select! {
+                notification = self.rx.recv() => {
+                    let Some(notification) = notification else {
+                        return Ok(());
+                    };
+                    self.accept(notification).await;
+                }
+                _ = digest_tick.tick() => {
+                    if let Some(digest) = self.take_digest() {
+                        self.deliver(&digest).await;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn accept(&mut self, notification: Notification) {
+        match notification.severity {
+            Severity::Critical => self.deliver(&notification.text).await,
+            Severity::Info if self.is_quiet(notification.at) => self.suppressed += 1,
+            Severity::Info => self.digest.push(notification),
+        }
+    }
+
+    fn is_quiet(&self, at: DateTime<Utc>) -> bool {
+        self.quiet_hours.map_or(false, |quiet| quiet.contains(at.time()))
+    }
+
+    /// The pending digest as one message, or `None` when there is nothing to say
+    fn take_digest(&mut self) -> Option<String> {
+        if self.digest.is_empty() && self.suppressed == 0 {
+            return None;
+        }
+
+        let mut lines = vec![format!("📋 Digest: {} notifications", self.digest.len())];
+        lines.extend(self.digest.drain(..).map(|n| format!("{} {}", n.at.format("%H:%M"), n.text)));
+        if self.suppressed > 0 {
+            lines.push(format!("({} suppressed during quiet hours)", self.suppressed));
+            self.suppressed = 0;
+        }
+
+        Some(lines.join("\n"))
+    }
+
+    async fn deliver(&self, text: &str) {
+        let Some(telegram) = &self.telegram else {
+            info!("📣 {}", text);
+            return;
+        };
+
+        if let Err(e) = telegram.send(text).await {
+            error!("Failed to send Telegram notification: {}", e);
+            info!("📣 {}", text);
+        }
+    }
+}
+
+struct Telegram {
+    client: Client,
+    token: String,
+    chat_id: String,
+}
+
+impl Telegram {
+    async fn send(&self, text: &str) -> Result<()> {
+        let response = self.client
+            .post(format!("https://api.telegram.org/bot{}/sendMessage", self.token))
+            .json(&serde_json::json!({ "chat_id": self.chat_id, "text": text }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Telegram returned {}", response.status()));
+        }
+
+        Ok(())
+    }
+}
+
+/// The notification an event raises, if any
+pub fn alert(event: &Event) -> Option<(Severity, String)> {
+    match event {
+        Event::BreakerTriggered { reason, .. } => Some((Severity::Critical, format!("🚨 CIRCUIT BREAKER TRIGGERED: {}", reason))),
+        Event::OrderFilled(fill) => Some((Severity::Info, format!(
+            "🧾 {} {} shares of {} at {}",
+            fill.side.as_str(), fill.shares, fill.token_id, fill.price.round_dp(4)
+        ))),
+        Event::MarketUpdated { market_id, status: MarketStatus::Suspended } => {
+            Some((Severity::Info, format!("⏸️ Market {} suspended", market_id)))
+        }
+        Event::MarketUpdated { market_id, status: MarketStatus::Disputed } => {
+            Some((Severity::Info, format!("⚖️ Resolution of market {} disputed", market_id)))
+        }
+        _ => None,
+    }
+}
+
+/// Raises notifications for what other services publish on the event bus,
+/// so they report breakers, fills and market changes without polling
+pub struct EventAlerts {
+    events: EventBus,
+    notifier: Notifier,
+}
+
+impl EventAlerts {
+    pub fn new(events: EventBus, notifier: Notifier) -> Self {
+        Self { events, notifier }
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        info!("📣 Event alerts started");
+
+        loop {
+            match self.events.subscribe(&ALERTED).await {
+                Ok(mut events) => {
+                    while let Some(event) = events.next().await {
+                        if let Some((severity, text)) = alert(&event) {
+                            self.notifier.notify(severity, text);
+                        }
+                    }
+                    warn!("Event subscription for alerts closed, resubscribing");
+                }
+                Err(e) => warn!("Failed to subscribe alerts to events: {}", e),
+            }
+            sleep(RESUBSCRIBE_DELAY).await;
+        }
+    }
+}
+
+fn parse_time(value: &str) -> Result<NaiveTime> {
+    Ok(NaiveTime::parse_from_str(value, "%H:%M")?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(value: &str) -> NaiveTime {
+        parse_time(value).unwrap()
+    }
+
+    #[test]
+    fn quiet_hours_may_wrap_past_midnight() {
+        let overnight = QuietHours { start: time("23:00"), end: time("07:00") };
+        assert!(overnight.contains(time("23:30")));
+        assert!(overnight.contains(time("03:00")));
+        assert!(!overnight.contains(time("07:00")));
+        assert!(!overnight.contains(time("12:00")));
+
+        let lunch = QuietHours { start: time("12:00"), end: time("13:00") };
+        assert!(lunch.contains(time("12:30")));
+        assert!(!lunch.contains(time("23:30")));
+    }
+
+    #[tokio::test]
+    async fn published_breakers_reach_operators_at_once() {
+        let events = EventBus::new(std::sync::Arc::new(crate::cache::InProcessCache::new(100)));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let alerts = EventAlerts::new(events.clone(), Notifier { tx });
+        tokio::spawn(async move { alerts.run().await });
+
+        // Published until the relay has subscribed; only a tripped breaker is alerted
+        let received = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                events.publish(Event::MarketUpdated { market_id: "0xabc".to_string(), status: MarketStatus::Closed }).await;
+                events.publish(Event::BreakerTriggered { reason: "drawdown".to_string(), triggered_at: Utc::now() }).await;
+                if let Ok(Some(notification)) = tokio::time::timeout(Duration::from_millis(50), rx.recv()).await {
+                    return notification;
+                }
+            }
+        }).await.unwrap();
+
+        assert_eq!(received.severity, Severity::Critical);
+        assert!(received.text.contains("drawdown"));
+        assert!(alert(&Event::MarketUpdated { market_id: "0xabc".to_string(), status: MarketStatus::Disputed }).is_some());
+    }
+}
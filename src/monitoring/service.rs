@@ -1,31 +1,82 @@
 use anyhow::Result;
-use sqlx::PgPool;
-use tokio::time::{interval, Duration};
-use tracing::{info, error};
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use futures::StreamExt;
+use tokio::time::{interval, sleep, Duration};
+use tracing::{info, error, warn};
 
-use crate::config::Config;
-use super::MetricsCollector;
+use crate::config::{CalibrationConfig, Config, DeadLettersConfig, LatencyConfig};
+use crate::data::coverage_report;
+use crate::events::{Event, EventBus, EventKind};
+use crate::risk::{calibration_report, current_exposure, ExposureLimits};
+use crate::storage::Storage;
+use super::dead_letters::{dead_letter_counts, dead_letter_digest};
+use super::incidents::IncidentManager;
+use super::{Heartbeats, Latencies, LatencyStage, MetricsCollector, Notifier};
+
+/// Events the dashboard follows between its periodic sweeps
+const FOLLOWED: [EventKind; 3] = [EventKind::BreakerTriggered, EventKind::OrderFilled, EventKind::OrderUpdated];
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
 
 pub struct MonitoringService {
-    db_pool: PgPool,
+    storage: Arc<dyn Storage>,
+    events: EventBus,
     metrics_collector: MetricsCollector,
+    exposure_limits: ExposureLimits,
+    heartbeats: Heartbeats,
+    incidents: IncidentManager,
+    stall_after: Duration,
+    latencies: Latencies,
+    latency: LatencyConfig,
+    calibration: CalibrationConfig,
+    dead_letters: DeadLettersConfig,
+    /// When dead letters were last digested
+    last_dead_letter_digest: Mutex<DateTime<Utc>>,
+    /// Stages whose p95 was over budget at the last check
+    over_budget: Mutex<HashSet<LatencyStage>>,
+    notifier: Notifier,
 }
 
 impl MonitoringService {
-    pub fn new(db_pool: PgPool, config: &Config) -> Result<Self> {
+    pub fn new(
+        storage: Arc<dyn Storage>,
+        events: EventBus,
+        config: &Config,
+        heartbeats: Heartbeats,
+        latencies: Latencies,
+        notifier: Notifier,
+    ) -> Result<Self> {
         let metrics_collector = MetricsCollector::new(config)?;
 
         Ok(Self {
-            db_pool,
+            storage,
+            events,
             metrics_collector,
+            exposure_limits: ExposureLimits::from_config(&config.risk.exposure_limits)?,
+            heartbeats,
+            incidents: IncidentManager::new(&config.monitoring.incidents),
+            stall_after: Duration::from_secs(config.monitoring.incidents.stall_after_secs),
+            latencies,
+            latency: config.monitoring.latency.clone(),
+            calibration: config.monitoring.calibration.clone(),
+            dead_letters: config.monitoring.dead_letters.clone(),
+            last_dead_letter_digest: Mutex::new(Utc::now()),
+            over_budget: Mutex::new(HashSet::new()),
+            notifier,
         })
     }
 
     pub async fn run(&self) -> Result<()> {
-        let mut tick = interval(Duration::from_secs(60)); // Update every minute
-
         info!("📊 Monitoring service started");
 
+        tokio::join!(self.sweep(), self.follow_events());
+        Ok(())
+    }
+
+    async fn sweep(&self) {
+        let mut tick = interval(Duration::from_secs(60)); // Update every minute
+
         loop {
             tick.tick().await;
 
@@ -36,43 +87,174 @@ impl MonitoringService {
             if let Err(e) = self.update_performance_metrics().await {
                 error!("Error updating performance: {}", e);
             }
+
+            self.check_latency().await;
+
+            if let Err(e) = self.digest_dead_letters().await {
+                error!("Error digesting dead letters: {}", e);
+            }
+
+            if let Err(e) = self.check_incidents().await {
+                error!("Error checking incidents: {}", e);
+            }
+        }
+    }
+
+    /// Apply trade and breaker events as they are published, rather than
+    /// waiting for the next sweep to read them back from storage
+    async fn follow_events(&self) {
+        loop {
+            match self.events.subscribe(&FOLLOWED).await {
+                Ok(mut stream) => {
+                    while let Some(event) = stream.next().await {
+                        self.apply_event(&event).await;
+                    }
+                    warn!("Event subscription for monitoring closed, resubscribing");
+                }
+                Err(e) => warn!("Failed to subscribe monitoring to events: {}", e),
+            }
+            sleep(RESUBSCRIBE_DELAY).await;
+        }
+    }
+
+    async fn apply_event(&self, event: &Event) {
+        match event {
+            Event::BreakerTriggered { reason, .. } => {
+                let summary = format!("circuit breaker tripped - trading halted: {}", reason);
+                self.incidents.update("circuit-breaker", Some(summary)).await;
+            }
+            Event::OrderFilled(_) | Event::OrderUpdated { .. } => {
+                if let Err(e) = self.collect_trade_metrics().await {
+                    error!("Error collecting trade metrics: {}", e);
+                }
+            }
+            _ => {}
         }
     }
 
+    /// Open positions and today's trade count, refreshed on each fill
+    async fn collect_trade_metrics(&self) -> Result<()> {
+        let open = self.storage.fetch_open_trades().await?;
+        self.metrics_collector.record_open_positions(open.len() as i64);
+        self.metrics_collector.record_daily_trades(self.storage.count_trades_today().await?);
+        Ok(())
+    }
+
     async fn collect_metrics(&self) -> Result<()> {
         // Collect portfolio metrics
-        let portfolio = sqlx::query!(
-            r#"
-            SELECT * FROM v_portfolio_summary
-            LIMIT 1
-            "#
-        )
-        .fetch_optional(&self.db_pool)
-        .await?;
-
-        if let Some(p) = portfolio {
-            self.metrics_collector.record_portfolio_value(
-                p.total_capital.unwrap_or_default()
-            );
-            self.metrics_collector.record_open_positions(
-                p.open_positions.unwrap_or(0) as i64
-            );
+        let portfolio = self.storage.latest_portfolio_snapshot().await?;
+
+        if let Some(p) = &portfolio {
+            self.metrics_collector.record_portfolio_value(p.total_capital);
+            self.metrics_collector.record_settlement_pending(p.settlement_pending);
+            self.metrics_collector.record_open_positions(p.open_positions as i64);
         }
 
         // Collect trade metrics
-        let trades = sqlx::query!(
-            r#"
-            SELECT COUNT(*) as count
-            FROM trades
-            WHERE DATE(entry_time) = CURRENT_DATE
-            "#
-        )
-        .fetch_one(&self.db_pool)
-        .await?;
-
-        self.metrics_collector.record_daily_trades(
-            trades.count.unwrap_or(0)
-        );
+        let trades_today = self.storage.count_trades_today().await?;
+
+        self.metrics_collector.record_daily_trades(trades_today);
+        self.metrics_collector.record_execution_costs(self.storage.execution_costs_today().await?);
+        self.metrics_collector.record_trade_marks(&self.storage.fetch_trade_marks().await?);
+
+        // Attribute PnL and exposure to venues
+        for venue in self.storage.calculate_venue_balances().await? {
+            self.metrics_collector.record_venue_balances(&venue);
+        }
+
+        // Concentration by sport, event, strategy and time to event
+        if let Some(p) = &portfolio {
+            let exposure = current_exposure(self.storage.as_ref(), p.total_capital, &self.exposure_limits).await?;
+            self.metrics_collector.record_exposure(&exposure);
+        }
+
+        // How much of what Polymarket lists reaches the strategies
+        let listings = self.storage.fetch_market_listings().await?;
+        let covered = self.storage.fetch_covered_markets(Utc::now() - chrono::Duration::hours(1)).await?;
+        self.metrics_collector.record_coverage(&coverage_report(&listings, &covered));
+
+        // Predicted probability against what happened, for the calibration dashboard
+        let since = Utc::now() - chrono::Duration::days(self.calibration.lookback_days);
+        let outcomes = self.storage.fetch_prediction_outcomes(since).await?;
+        self.metrics_collector.record_calibration(&calibration_report(&outcomes, self.calibration.bins));
+
+        // Venue home edges behind the season model, for audit
+        self.metrics_collector.record_home_advantage(&self.storage.fetch_home_advantage(None).await?);
+
+        // Rows dropped for failing to map, so data loss shows up
+        let since = Utc::now() - chrono::Duration::hours(self.dead_letters.window_hours);
+        let letters = self.storage.fetch_dead_letters(since).await?;
+        self.metrics_collector.record_dead_letters(&dead_letter_counts(&letters));
+
+        Ok(())
+    }
+
+    /// Send a digest of the dead letters seen since the last one, once the
+    /// digest interval has passed
+    async fn digest_dead_letters(&self) -> Result<()> {
+        let now = Utc::now();
+        let since = *self.last_dead_letter_digest.lock().unwrap();
+        if (now - since).num_seconds() < self.dead_letters.digest_interval_secs as i64 {
+            return Ok(());
+        }
+
+        let letters = self.storage.fetch_dead_letters(since).await?;
+        if let Some(digest) = dead_letter_digest(&letters) {
+            info!("{}", digest);
+            self.notifier.info(digest);
+        }
+        *self.last_dead_letter_digest.lock().unwrap() = now;
+        Ok(())
+    }
+
+    /// Export each stage's recent latency, and alert as its p95 goes over or
+    /// comes back within budget
+    async fn check_latency(&self) {
+        let stages = self.latencies.report(&self.latency, Utc::now());
+        self.metrics_collector.record_stage_latency(&stages);
+
+        for stage in &stages {
+            let name = stage.stage.as_str();
+            let changed = {
+                let mut over_budget = self.over_budget.lock().unwrap();
+                if stage.over_budget {
+                    over_budget.insert(stage.stage)
+                } else {
+                    over_budget.remove(&stage.stage)
+                }
+            };
+
+            let summary = stage.over_budget.then(|| format!(
+                "{} latency p95 {}ms over its {}ms budget ({} samples in {}s)",
+                name, stage.p95_ms.unwrap_or_default(), stage.budget_ms, stage.samples, self.latency.window_secs
+            ));
+            if changed {
+                match &summary {
+                    Some(summary) => {
+                        warn!("⏱️ {}", summary);
+                        self.notifier.critical(format!("⏱️ {}", summary));
+                    }
+                    None => self.notifier.info(format!("⏱️ {} latency back within its {}ms budget", name, stage.budget_ms)),
+                }
+            }
+            self.incidents.update(&format!("latency-{}", name), summary).await;
+        }
+    }
+
+    /// Raise or resolve incidents for circuit breakers and stalled services
+    async fn check_incidents(&self) -> Result<()> {
+        if !self.incidents.is_enabled() {
+            return Ok(());
+        }
+
+        let breakers = self.storage.count_active_circuit_breakers().await?;
+        let summary = (breakers > 0).then(|| format!("{} circuit breaker(s) active - trading halted", breakers));
+        self.incidents.update("circuit-breaker", summary).await;
+
+        for (service, silence) in self.heartbeats.check(self.stall_after) {
+            let summary = silence.map(|s| format!("{} has not completed a cycle in {}s", service, s.as_secs()));
+            self.incidents.update(&format!("stalled-{}", service), summary).await;
+        }
 
         Ok(())
     }
@@ -0,0 +1,130 @@
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::{Decimal, RoundingStrategy};
+use rust_decimal_macros::dec;
+use thiserror::Error;
+
+/// Failures when moving values between f64 and Decimal
+#[derive(Debug, Error, PartialEq)]
+pub enum NumericError {
+    #[error("non-finite value {0} cannot be represented as a decimal")]
+    NonFinite(f64),
+
+    #[error("value {0} is outside the decimal range")]
+    OutOfRange(f64),
+
+    #[error("decimal {0} cannot be represented as f64")]
+    Unrepresentable(Decimal),
+
+    #[error("probability {0} is outside [0, 1]")]
+    InvalidProbability(Decimal),
+
+    #[error("decimal odds {0} are below 1.0")]
+    InvalidOdds(Decimal),
+
+    #[error("{0} USDC base units are outside the decimal range")]
+    UsdcOutOfRange(u128),
+
+    #[error("USDC amount {0} cannot be sent on chain")]
+    InvalidUsdc(Decimal),
+
+    #[error("{0} outcome token base units are outside the decimal range")]
+    SharesOutOfRange(u128),
+
+    #[error("share quantity {0} cannot be sent on chain")]
+    InvalidShares(Decimal),
+}
+
+/// Convert an f64 to a Decimal, rejecting NaN, infinities and overflow
+///
+/// Uses the shortest decimal representation, so `0.1` becomes `0.1` rather
+/// than the full binary expansion `from_f64_retain` would keep.
+pub fn to_decimal(value: f64) -> Result<Decimal, NumericError> {
+    if !value.is_finite() {
+        return Err(NumericError::NonFinite(value));
+    }
+
+    Decimal::from_f64(value).ok_or(NumericError::OutOfRange(value))
+}
+
+/// Convert a Decimal to f64 for statistics and metrics
+pub fn to_f64(value: Decimal) -> Result<f64, NumericError> {
+    value.to_f64().ok_or(NumericError::Unrepresentable(value))
+}
+
+/// Validate that a value lies in [0, 1]
+pub fn probability(value: Decimal) -> Result<Decimal, NumericError> {
+    if value < dec!(0.0) || value > dec!(1.0) {
+        return Err(NumericError::InvalidProbability(value));
+    }
+
+    Ok(value)
+}
+
+/// Convert a model probability (e.g. a simulation frequency) to a Decimal
+pub fn probability_from_f64(value: f64) -> Result<Decimal, NumericError> {
+    probability(to_decimal(value)?).map(|p| RoundingPolicy::Probability.apply(p))
+}
+
+/// How a value is rounded before it is stored or sent to the exchange
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingPolicy {
+    /// USD amounts we commit: cents, toward zero so limits are never exceeded
+    Usd,
+    /// Share prices: 4 decimal places, banker's rounding
+    Price,
+    /// Probabilities and edges: 6 decimal places, banker's rounding
+    Probability,
+}
+
+impl RoundingPolicy {
+    pub fn scale(&self) -> u32 {
+        match self {
+            RoundingPolicy::Usd => 2,
+            RoundingPolicy::Price => 4,
+            RoundingPolicy::Probability => 6,
+        }
+    }
+
+    pub fn strategy(&self) -> RoundingStrategy {
+        match self {
+            RoundingPolicy::Usd => RoundingStrategy::ToZero,
+            RoundingPolicy::Price | RoundingPolicy::Probability => RoundingStrategy::MidpointNearestEven,
+        }
+    }
+
+    pub fn apply(&self, value: Decimal) -> Decimal {
+        value.round_dp_with_strategy(self.scale(), self.strategy())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_non_finite_values() {
+        assert_eq!(to_decimal(f64::NAN).unwrap_err().to_string(), "non-finite value NaN cannot be represented as a decimal");
+        assert!(matches!(to_decimal(f64::INFINITY), Err(NumericError::NonFinite(_))));
+        assert!(matches!(to_decimal(1e300), Err(NumericError::OutOfRange(_))));
+    }
+
+    #[test]
+    fn converts_to_shortest_representation() {
+        assert_eq!(to_decimal(0.1).unwrap(), dec!(0.1));
+        assert_eq!(to_decimal(2.5).unwrap(), dec!(2.5));
+    }
+
+    #[test]
+    fn probabilities_must_be_in_unit_interval() {
+        assert_eq!(probability_from_f64(0.5).unwrap(), dec!(0.5));
+        assert!(matches!(probability_from_f64(1.01), Err(NumericError::InvalidProbability(_))));
+        assert!(matches!(probability_from_f64(-0.01), Err(NumericError::InvalidProbability(_))));
+    }
+
+    #[test]
+    fn usd_rounds_toward_zero() {
+        assert_eq!(RoundingPolicy::Usd.apply(dec!(10.999)), dec!(10.99));
+        assert_eq!(RoundingPolicy::Price.apply(dec!(0.12345)), dec!(0.1234));
+        assert_eq!(RoundingPolicy::Probability.apply(dec!(0.1234565)), dec!(0.123456));
+    }
+}
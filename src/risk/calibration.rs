@@ -0,0 +1,194 @@
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::types::{PredictionOutcome, Strategy};
+
+/// Resolved trades whose predicted probability fell in one bin
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ReliabilityBin {
+    pub lower: Decimal,
+    pub upper: Decimal,
+    pub trades: usize,
+    /// Mean predicted probability of the position winning
+    pub predicted: Decimal,
+    /// Share of them that won
+    pub realized: Decimal,
+}
+
+/// Closed trades whose edge at entry fell in one bin
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct EdgeReturnBin {
+    pub trades: usize,
+    /// Mean fair value over the entry price
+    pub edge: Decimal,
+    /// Mean return per dollar staked, before costs
+    pub realized_return: Decimal,
+}
+
+/// One closed trade's edge at entry and what it returned
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct EdgeReturnPoint {
+    pub trade_id: uuid::Uuid,
+    pub edge: Decimal,
+    pub realized_return: Decimal,
+}
+
+/// How one strategy's predictions held up against what happened
+#[derive(Debug, Clone, Serialize)]
+pub struct StrategyCalibration {
+    pub strategy: Strategy,
+    pub trades: usize,
+    /// Trades settled as won or lost; sells before resolution, stops and
+    /// take-profits say nothing about how often a prediction comes true
+    pub resolved: usize,
+    /// Mean Brier score of the resolved trades
+    pub brier: Decimal,
+    /// Predicted probability against realized frequency of the resolved
+    /// trades, in equal-width bins; empty bins are left out
+    pub reliability: Vec<ReliabilityBin>,
+    /// Mean edge against mean return, in bins of equal trade counts
+    pub edge_returns: Vec<EdgeReturnBin>,
+    pub points: Vec<EdgeReturnPoint>,
+}
+
+fn mean(values: impl Iterator<Item = Decimal>) -> Decimal {
+    let (sum, count) = values.fold((Decimal::ZERO, 0u32), |(sum, count), v| (sum + v, count + 1));
+    if count == 0 {
+        Decimal::ZERO
+    } else {
+        sum / Decimal::from(count)
+    }
+}
+
+/// Reliability diagram and edge-vs-return data per strategy from closed
+/// trades' predictions, split into `bins` bins each
+pub fn calibration_report(outcomes: &[PredictionOutcome], bins: usize) -> Vec<StrategyCalibration> {
+    let bins = bins.max(1);
+    let mut by_strategy: BTreeMap<&str, (Strategy, Vec<&PredictionOutcome>)> = BTreeMap::new();
+    for outcome in outcomes.iter().filter(|o| o.entry_price > Decimal::ZERO) {
+        by_strategy.entry(outcome.strategy.as_str())
+            .or_insert_with(|| (outcome.strategy, Vec::new()))
+            .1
+            .push(outcome);
+    }
+
+    by_strategy.into_values()
+        .map(|(strategy, outcomes)| {
+            let resolved: Vec<(Decimal, Decimal)> = outcomes.iter()
+                .filter_map(|o| Some((o.fair_value, o.payout?)))
+                .collect();
+            let width = Decimal::ONE / Decimal::from(bins);
+            let reliability = (0..bins)
+                .filter_map(|i| {
+                    let (lower, upper) = (width * Decimal::from(i), width * Decimal::from(i + 1));
+                    // The last bin takes certainty too
+                    let in_bin: Vec<&(Decimal, Decimal)> = resolved.iter()
+                        .filter(|(fair_value, _)| *fair_value >= lower && (*fair_value < upper || i + 1 == bins))
+                        .collect();
+                    (!in_bin.is_empty()).then(|| ReliabilityBin {
+                        lower,
+                        upper,
+                        trades: in_bin.len(),
+                        predicted: mean(in_bin.iter().map(|(fair_value, _)| *fair_value)),
+                        realized: mean(in_bin.iter().map(|(_, payout)| *payout)),
+                    })
+                })
+                .collect();
+
+            let mut points: Vec<EdgeReturnPoint> = outcomes.iter()
+                .map(|o| EdgeReturnPoint {
+                    trade_id: o.trade_id,
+                    edge: o.fair_value - o.entry_price,
+                    realized_return: (o.exit_price - o.entry_price) / o.entry_price,
+                })
+                .collect();
+            points.sort_by(|a, b| a.edge.cmp(&b.edge));
+            let edge_returns = points.chunks(points.len().div_ceil(bins).max(1))
+                .map(|chunk| EdgeReturnBin {
+                    trades: chunk.len(),
+                    edge: mean(chunk.iter().map(|p| p.edge)),
+                    realized_return: mean(chunk.iter().map(|p| p.realized_return)),
+                })
+                .collect();
+
+            StrategyCalibration {
+                strategy,
+                trades: outcomes.len(),
+                resolved: resolved.len(),
+                brier: mean(resolved.iter().map(|(fair_value, payout)| (fair_value - payout) * (fair_value - payout))),
+                reliability,
+                edge_returns,
+                points,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+    use uuid::Uuid;
+
+    fn outcome(fair_value: Decimal, entry_price: Decimal, exit_price: Decimal) -> PredictionOutcome {
+        PredictionOutcome {
+            trade_id: Uuid::new_v4(),
+            strategy: Strategy::ClvArbitrage,
+            fair_value,
+            entry_price,
+            exit_price,
+            payout: (exit_price.is_zero() || exit_price == Decimal::ONE).then_some(exit_price),
+            closing_price: None,
+            exit_time: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn overconfident_predictions_realize_below_the_diagonal() {
+        // Predicted 70% but won one in four; predicted 20% and lost both
+        let outcomes = vec![
+            outcome(dec!(0.70), dec!(0.60), dec!(1)),
+            outcome(dec!(0.72), dec!(0.60), dec!(0)),
+            outcome(dec!(0.74), dec!(0.60), dec!(0)),
+            outcome(dec!(0.76), dec!(0.60), dec!(0)),
+            outcome(dec!(0.20), dec!(0.02), dec!(0)),
+            outcome(dec!(0.20), dec!(0.02), dec!(0)),
+        ];
+
+        let report = calibration_report(&outcomes, 5);
+        assert_eq!(report.len(), 1);
+        let clv = &report[0];
+        assert_eq!(clv.trades, 6);
+
+        assert_eq!(clv.reliability.len(), 2);
+        let high = &clv.reliability[1];
+        assert_eq!((high.lower, high.upper, high.trades), (dec!(0.6), dec!(0.8), 4));
+        assert_eq!((high.predicted, high.realized), (dec!(0.73), dec!(0.25)));
+
+        // Smallest edges first, two trades a bin; the largest edges lost everything
+        assert_eq!(clv.edge_returns.len(), 3);
+        assert_eq!(clv.edge_returns[2].edge, dec!(0.18));
+        assert_eq!(clv.edge_returns[2].realized_return, dec!(-1));
+        assert_eq!(clv.points.len(), 6);
+    }
+
+    #[test]
+    fn only_resolved_trades_count_toward_realized_frequency() {
+        // Both won at resolution; the take-profit at 0.90 and the stop at 0.40 did not resolve
+        let outcomes = vec![
+            outcome(dec!(0.70), dec!(0.60), dec!(1)),
+            outcome(dec!(0.70), dec!(0.60), dec!(1)),
+            outcome(dec!(0.70), dec!(0.60), dec!(0.90)),
+            outcome(dec!(0.70), dec!(0.60), dec!(0.40)),
+        ];
+
+        let clv = &calibration_report(&outcomes, 5)[0];
+        assert_eq!((clv.trades, clv.resolved), (4, 2));
+        assert_eq!((clv.reliability[0].trades, clv.reliability[0].realized), (2, dec!(1)));
+        assert_eq!(clv.brier, dec!(0.09));
+        // Early exits still returned what they returned
+        assert_eq!(clv.points.len(), 4);
+    }
+}
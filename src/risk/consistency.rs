@@ -0,0 +1,420 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use statrs::distribution::{ContinuousCDF, Normal};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use tracing::{error, info, warn};
+
+use crate::config::{Config, ConsistencyConfig};
+use crate::models::ScoringProfile;
+use crate::monitoring::Notifier;
+use crate::numeric;
+use crate::storage::Storage;
+use crate::types::{Game, Market, MarketType, Position, Sport};
+
+const SPORTS: [Sport; 4] = [Sport::NFL, Sport::NBA, Sport::PremierLeague, Sport::MLB];
+
+/// Score quantity a game's markets are lines on
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Ladder {
+    /// Home margin: the moneyline (a line of zero) and spreads
+    Margin,
+    /// Combined score: totals
+    Total,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum InconsistencyKind {
+    /// Two markets that can be bought for less than the share they are
+    /// certain to pay between them
+    Arbitrage,
+    /// A market far from what the game's other markets imply
+    Outlier,
+}
+
+/// Markets of one game whose prices contradict each other
+#[derive(Debug, Clone, Serialize)]
+pub struct Inconsistency {
+    pub game_id: String,
+    pub ladder: Ladder,
+    pub kind: InconsistencyKind,
+    pub market_ids: Vec<String>,
+    /// Locked-in profit per share for arbitrage; distance from the implied
+    /// probability for an outlier
+    pub gap: Decimal,
+    pub detail: String,
+}
+
+impl Inconsistency {
+    /// Identifies the same inconsistency across scans
+    fn key(&self) -> String {
+        format!("{:?}:{}", self.kind, self.market_ids.join(","))
+    }
+}
+
+/// How far apart related markets may be before they are flagged
+#[derive(Debug, Clone)]
+pub struct ConsistencyThresholds {
+    min_arbitrage_edge: Decimal,
+    max_model_gap: Decimal,
+}
+
+impl ConsistencyThresholds {
+    pub fn from_config(config: &ConsistencyConfig) -> Result<Self> {
+        Ok(Self {
+            min_arbitrage_edge: numeric::to_decimal(config.min_arbitrage_edge)
+                .context("risk.consistency.min_arbitrage_edge")?,
+            max_model_gap: numeric::to_decimal(config.max_model_gap)
+                .context("risk.consistency.max_model_gap")?,
+        })
+    }
+}
+
+/// A market read as "the ladder's quantity ends above `line`"
+pub struct Rung<'a> {
+    pub market: &'a Market,
+    pub line: f64,
+    /// Outcome that pays when the quantity ends above the line
+    pub above: Position,
+}
+
+impl Rung<'_> {
+    /// Mid-price probability the quantity ends above the line
+    pub fn probability(&self) -> Decimal {
+        self.market.quote(self.above).mid().value()
+    }
+}
+
+/// The line in "(-3.5)" of a spread question, and where it starts
+fn spread_line(question: &str) -> Option<(usize, f64)> {
+    let open = question.find('(')?;
+    let close = open + question[open..].find(')')?;
+    Some((open, question[open + 1..close].trim().parse().ok()?))
+}
+
+/// The line after "o/u", "over/under", "over" or "total" in a total question
+fn total_line(question: &str) -> Option<f64> {
+    let words: Vec<&str> = question.split_whitespace().collect();
+    words.windows(2).find_map(|w| {
+        if !matches!(w[0].trim_end_matches(':'), "o/u" | "over/under" | "over" | "total") {
+            return None;
+        }
+        w[1].trim_end_matches(|c: char| !c.is_ascii_digit()).parse().ok()
+    })
+}
+
+/// Where a market of `game` sits on its ladder
+///
+/// The moneyline's YES backs the team named first; a spread's line belongs
+/// to the team named just before it; a total's YES is the over.
+pub fn ladder_rung<'a>(game: &Game, market: &'a Market) -> Option<(Ladder, Rung<'a>)> {
+    if market.sport != game.sport || !game.starts_near(market.event_time) {
+        return None;
+    }
+    let question = market.event_name.to_lowercase();
+    let (home, away) = game.team_positions(&question);
+
+    match market.market_type {
+        MarketType::Moneyline => {
+            let home_first = match (home, away) {
+                (Some(h), Some(a)) => h < a,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => return None,
+            };
+            let above = if home_first { Position::Yes } else { Position::No };
+            Some((Ladder::Margin, Rung { market, line: 0.0, above }))
+        }
+        MarketType::Spread => {
+            let (open, line) = spread_line(&question)?;
+            let home_named = match (home.filter(|&h| h < open), away.filter(|&a| a < open)) {
+                (Some(h), Some(a)) => h > a,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => return None,
+            };
+            // YES pays when the named team's margin plus the line is positive
+            let rung = if home_named {
+                Rung { market, line: -line, above: Position::Yes }
+            } else {
+                Rung { market, line, above: Position::No }
+            };
+            Some((Ladder::Margin, rung))
+        }
+        MarketType::Total => {
+            home.or(away)?;
+            Some((Ladder::Total, Rung { market, line: total_line(&question)?, above: Position::Yes }))
+        }
+        _ => None,
+    }
+}
+
+/// Pairs of rungs where buying above the lower line and below the higher
+/// one costs less than the share one of them must pay
+fn arbitrage(game: &Game, ladder: Ladder, rungs: &[Rung], min_edge: Decimal) -> Vec<Inconsistency> {
+    let mut found = Vec::new();
+    for (i, low) in rungs.iter().enumerate() {
+        for (j, high) in rungs.iter().enumerate() {
+            if i == j || low.line > high.line {
+                continue;
+            }
+            let cost = low.market.entry_price(low.above).value()
+                + high.market.entry_price(high.above.opposite()).value();
+            let edge = Decimal::ONE - cost;
+            if edge < min_edge {
+                continue;
+            }
+
+            found.push(Inconsistency {
+                game_id: game.game_id.clone(),
+                ladder,
+                kind: InconsistencyKind::Arbitrage,
+                market_ids: vec![low.market.market_id.clone(), high.market.market_id.clone()],
+                gap: edge,
+                detail: format!(
+                    "{} {} + {} {} cost {}: above {} or below {} always pays",
+                    low.market.event_name,
+                    low.above.as_str(),
+                    high.market.event_name,
+                    high.above.opposite().as_str(),
+                    cost,
+                    low.line,
+                    high.line,
+                ),
+            });
+        }
+    }
+    found
+}
+
+/// Rungs whose probability is more than `max_gap` from a normal fitted to
+/// the other rungs with the sport's dispersion `sd`
+///
+/// Each rung is judged against the others only, so one bad market can't
+/// pull the fit toward itself; at least two others are needed.
+fn outliers(game: &Game, ladder: Ladder, rungs: &[Rung], sd: f64, max_gap: Decimal) -> Result<Vec<Inconsistency>> {
+    let standard = Normal::new(0.0, 1.0)?;
+    // P(quantity > line) = Φ((mean - line) / sd)
+    let means: Vec<Option<f64>> = rungs.iter()
+        .map(|rung| {
+            let p = numeric::to_f64(rung.probability()).ok().filter(|p| *p > 0.0 && *p < 1.0)?;
+            Some(rung.line + sd * standard.inverse_cdf(p))
+        })
+        .collect();
+
+    let mut found = Vec::new();
+    for (i, rung) in rungs.iter().enumerate() {
+        let others: Vec<f64> = means.iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .filter_map(|(_, m)| *m)
+            .collect();
+        if others.len() < 2 {
+            continue;
+        }
+
+        let mean = others.iter().sum::<f64>() / others.len() as f64;
+        let implied = numeric::probability_from_f64(standard.cdf((mean - rung.line) / sd))?;
+        let gap = rung.probability() - implied;
+        if gap.abs() <= max_gap {
+            continue;
+        }
+
+        found.push(Inconsistency {
+            game_id: game.game_id.clone(),
+            ladder,
+            kind: InconsistencyKind::Outlier,
+            market_ids: vec![rung.market.market_id.clone()],
+            gap: gap.abs(),
+            detail: format!(
+                "{} prices above {} at {} where the game's other markets imply {}",
+                rung.market.event_name,
+                rung.line,
+                rung.probability().round_dp(3),
+                implied.round_dp(3),
+            ),
+        });
+    }
+    Ok(found)
+}
+
+/// Arbitrage candidates and outliers among a game's moneyline, spread and
+/// total markets
+pub fn check_game(game: &Game, markets: &[Market], thresholds: &ConsistencyThresholds) -> Result<Vec<Inconsistency>> {
+    let mut found = Vec::new();
+    let profile = ScoringProfile::for_sport(game.sport);
+
+    for ladder in [Ladder::Margin, Ladder::Total] {
+        let rungs: Vec<Rung> = markets.iter()
+            .filter_map(|m| ladder_rung(game, m))
+            .filter(|(l, _)| *l == ladder)
+            .map(|(_, rung)| rung)
+            .collect();
+
+        found.extend(arbitrage(game, ladder, &rungs, thresholds.min_arbitrage_edge));
+        // Draws break the normal score model, so sports without a profile
+        // only get the model-free check
+        if let Some(profile) = profile {
+            let sd = match ladder {
+                Ladder::Margin => profile.margin_sd,
+                Ladder::Total => profile.total_sd,
+            };
+            found.extend(outliers(game, ladder, &rungs, sd, thresholds.max_model_gap)?);
+        }
+    }
+
+    Ok(found)
+}
+
+/// Inconsistencies across the related markets of every upcoming game
+pub async fn scan_consistency(storage: &dyn Storage, thresholds: &ConsistencyThresholds) -> Result<Vec<Inconsistency>> {
+    let markets: Vec<Market> = storage.fetch_active_markets(Decimal::ZERO, 1000).await?
+        .into_iter()
+        .filter(|m| m.status.allows_entry())
+        .collect();
+    let now = Utc::now();
+
+    let mut found = Vec::new();
+    for sport in SPORTS {
+        for game in storage.fetch_season_games(sport).await? {
+            if game.is_played() || game.start_time <= now {
+                continue;
+            }
+            found.extend(check_game(&game, &markets, thresholds)?);
+        }
+    }
+    Ok(found)
+}
+
+/// Periodically checks that each game's moneyline, spreads and totals agree
+///
+/// Arbitrage candidates are alerted on; outliers are logged as data-quality
+/// warnings. Each is reported once while it persists.
+pub struct ConsistencyMonitor {
+    storage: Arc<dyn Storage>,
+    notifier: Notifier,
+    thresholds: ConsistencyThresholds,
+    check_interval: Duration,
+}
+
+impl ConsistencyMonitor {
+    pub fn new(storage: Arc<dyn Storage>, config: &Config, notifier: Notifier) -> Result<Self> {
+        let consistency = &config.risk.consistency;
+
+        Ok(Self {
+            storage,
+            notifier,
+            thresholds: ConsistencyThresholds::from_config(consistency)?,
+            check_interval: Duration::from_secs(consistency.check_interval_secs.max(1)),
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let mut tick = interval(self.check_interval);
+        let mut reported = HashSet::new();
+
+        info!("🔗 Consistency monitor started");
+
+        loop {
+            tick.tick().await;
+
+            match scan_consistency(self.storage.as_ref(), &self.thresholds).await {
+                Ok(found) => reported = self.report(found, &reported),
+                Err(e) => error!("Error checking market consistency: {}", e),
+            }
+        }
+    }
+
+    /// Report inconsistencies not seen on the previous scan; returns the keys
+    /// of this scan's
+    fn report(&self, found: Vec<Inconsistency>, previous: &HashSet<String>) -> HashSet<String> {
+        let mut current = HashSet::new();
+        for inconsistency in found {
+            let key = inconsistency.key();
+            if !previous.contains(&key) {
+                match inconsistency.kind {
+                    InconsistencyKind::Arbitrage => {
+                        let message = format!(
+                            "Cross-market arbitrage on {} ({} edge): {}",
+                            inconsistency.game_id,
+                            inconsistency.gap.round_dp(3),
+                            inconsistency.detail,
+                        );
+                        warn!("🔗 {}", message);
+                        self.notifier.info(message);
+                    }
+                    InconsistencyKind::Outlier => {
+                        warn!("🔗 Inconsistent market on {}: {}", inconsistency.game_id, inconsistency.detail);
+                    }
+                }
+            }
+            current.insert(key);
+        }
+        current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::market_fixture;
+    use crate::types::{Price, Quote};
+    use rust_decimal_macros::dec;
+
+    fn priced(market_id: &str, market_type: MarketType, event_name: &str, yes_mid: Decimal, game: &Game) -> Market {
+        let mut market = market_fixture(market_id, yes_mid);
+        market.sport = game.sport;
+        market.event_time = game.start_time;
+        market.market_type = market_type;
+        market.event_name = event_name.to_string();
+        let yes = Price::new(yes_mid).unwrap();
+        market.yes_quote = Quote::from_book(Some(yes_mid - dec!(0.01)), Some(yes_mid + dec!(0.01)), yes);
+        market.no_quote = market.yes_quote.complement();
+        market
+    }
+
+    #[test]
+    fn flags_crossed_lines_and_off_model_markets() {
+        let game = Game {
+            game_id: "nfl-kc-buf".to_string(),
+            sport: Sport::NFL,
+            season: 2026,
+            home_team: "Kansas City Chiefs".to_string(),
+            away_team: "Buffalo Bills".to_string(),
+            start_time: Utc::now() + chrono::Duration::days(1),
+            home_score: None,
+            away_score: None,
+        };
+        let thresholds = ConsistencyThresholds { min_arbitrage_edge: dec!(0.01), max_model_gap: dec!(0.10) };
+
+        let markets = vec![
+            priced("ml", MarketType::Moneyline, "Chiefs vs. Bills", dec!(0.60), &game),
+            // The Chiefs covering -3.5 priced above them winning outright
+            priced("kc-3.5", MarketType::Spread, "Spread: Chiefs (-3.5)", dec!(0.66), &game),
+            // Bills +7.5 is the Chiefs winning by less than 7.5
+            priced("buf+7.5", MarketType::Spread, "Spread: Bills (+7.5)", dec!(0.62), &game),
+            priced("o44.5", MarketType::Total, "Chiefs vs. Bills O/U 44.5", dec!(0.55), &game),
+            priced("o45.5", MarketType::Total, "Chiefs vs. Bills O/U 45.5", dec!(0.52), &game),
+            priced("o47.5", MarketType::Total, "Chiefs vs. Bills O/U 47.5", dec!(0.47), &game),
+        ];
+
+        let found = check_game(&game, &markets, &thresholds).unwrap();
+
+        // Chiefs ML YES at 0.61 and Chiefs -3.5 NO at 0.35 always pay one
+        let arbitrage: Vec<_> = found.iter().filter(|i| i.kind == InconsistencyKind::Arbitrage).collect();
+        assert_eq!(arbitrage.len(), 1);
+        assert_eq!(arbitrage[0].market_ids, vec!["ml".to_string(), "kc-3.5".to_string()]);
+        assert_eq!(arbitrage[0].gap, dec!(0.04));
+
+        // Consistent totals are left alone
+        assert!(found.iter().all(|i| i.ladder == Ladder::Margin));
+        let outliers: Vec<_> = found.iter().filter(|i| i.kind == InconsistencyKind::Outlier).collect();
+        assert_eq!(outliers.len(), 1);
+        assert_eq!(outliers[0].market_ids, vec!["kc-3.5".to_string()]);
+    }
+}
@@ -0,0 +1,161 @@
+use anyhow::Result;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use tracing::{debug, error, info};
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::storage::Storage;
+use crate::strategies::{sharp_fair_value, OddsFreshness};
+use crate::types::{ConvergenceSample, Position, Strategy, Trade, Usdc};
+
+/// Samples how far each open CLV trade's market sits from the sharp line,
+/// so analytics can show whether prices actually converge after entry
+pub struct ConvergenceTracker {
+    storage: Arc<dyn Storage>,
+    sample_interval: Duration,
+    /// Same staleness weighting the strategy enters on
+    freshness: OddsFreshness,
+}
+
+impl ConvergenceTracker {
+    pub fn new(storage: Arc<dyn Storage>, config: &Config) -> Result<Self> {
+        Ok(Self {
+            storage,
+            sample_interval: Duration::from_secs(config.risk.convergence_sample_interval_secs),
+            freshness: OddsFreshness::from_config(&config.strategies.clv_arb)?,
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let mut tick = interval(self.sample_interval);
+
+        info!("📐 Convergence tracker started");
+
+        loop {
+            tick.tick().await;
+
+            if let Err(e) = self.sample_open_trades().await {
+                error!("Error sampling CLV convergence: {}", e);
+            }
+        }
+    }
+
+    async fn sample_open_trades(&self) -> Result<()> {
+        let trades = self.storage.fetch_open_trades().await?;
+
+        for trade in trades.iter().filter(|t| t.strategy == Strategy::ClvArbitrage) {
+            let odds = self.storage.fetch_bookmaker_odds(&trade.market_id).await?;
+            let Some((fair_yes, fair_no)) = sharp_fair_value(&odds, &self.freshness, Utc::now()) else {
+                debug!("No sharp line for {}, skipping convergence sample", trade.market_id);
+                continue;
+            };
+
+            let (yes_quote, no_quote) = self.storage.fetch_market_quotes(&trade.market_id).await?;
+            let (fair_value, market_price) = match trade.position {
+                Position::Yes => (fair_yes.value(), yes_quote.mid().value()),
+                Position::No => (fair_no.value(), no_quote.mid().value()),
+            };
+
+            self.storage.insert_convergence_sample(&ConvergenceSample {
+                trade_id: trade.trade_id,
+                market_price,
+                fair_value,
+                divergence: fair_value - market_price,
+                sampled_at: Utc::now(),
+            }).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// How much of a CLV trade's entry divergence closed while it was held
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ConvergenceSummary {
+    pub trade_id: Uuid,
+    pub samples: usize,
+    pub entry_divergence: Decimal,
+    pub final_divergence: Decimal,
+    /// Smallest divergence seen, i.e. the best exit the convergence offered
+    pub min_divergence: Decimal,
+    /// Share of the entry divergence that closed by the last sample;
+    /// above 1 the market overshot the sharp line
+    pub converged_fraction: Option<Decimal>,
+    pub pnl: Option<Usdc>,
+}
+
+/// Summarize a trade's samples, `None` if it was never sampled
+pub fn convergence_summary(trade: &Trade, samples: &[ConvergenceSample]) -> Option<ConvergenceSummary> {
+    let first = samples.first()?;
+    let last = samples.last()?;
+
+    Some(ConvergenceSummary {
+        trade_id: trade.trade_id,
+        samples: samples.len(),
+        entry_divergence: first.divergence,
+        final_divergence: last.divergence,
+        min_divergence: samples.iter().map(|s| s.divergence).min().unwrap_or(last.divergence),
+        converged_fraction: (first.divergence > Decimal::ZERO)
+            .then(|| (first.divergence - last.divergence) / first.divergence),
+        pnl: trade.pnl,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{TradeOrigin, TradeStatus, POLYMARKET_VENUE};
+    use rust_decimal_macros::dec;
+
+    fn sample(trade_id: Uuid, divergence: Decimal) -> ConvergenceSample {
+        ConvergenceSample {
+            trade_id,
+            market_price: dec!(0.50),
+            fair_value: dec!(0.50) + divergence,
+            divergence,
+            sampled_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn summarizes_how_much_of_the_divergence_closed() {
+        let trade = Trade {
+            trade_id: Uuid::new_v4(),
+            market_id: "0xmarket".to_string(),
+            strategy: Strategy::ClvArbitrage,
+            position: Position::Yes,
+            quantity: dec!(100),
+            entry_price: dec!(0.50),
+            exit_price: Some(dec!(0.53)),
+            entry_time: Utc::now(),
+            exit_time: Some(Utc::now()),
+            gas_cost: None,
+            fees: None,
+            slippage: None,
+            pnl: Some(Usdc::new(dec!(3))),
+            pnl_percent: None,
+            max_adverse_excursion: None,
+            max_favorable_excursion: None,
+            status: TradeStatus::Closed,
+            tx_hash_entry: None,
+            tx_hash_exit: None,
+            venue: POLYMARKET_VENUE.to_string(),
+            origin: TradeOrigin::Bot,
+        };
+        assert_eq!(convergence_summary(&trade, &[]), None);
+
+        let samples: Vec<ConvergenceSample> = [dec!(0.04), dec!(0.02), dec!(0.005), dec!(0.01)]
+            .into_iter()
+            .map(|d| sample(trade.trade_id, d))
+            .collect();
+        let summary = convergence_summary(&trade, &samples).unwrap();
+
+        assert_eq!(summary.samples, 4);
+        assert_eq!(summary.min_divergence, dec!(0.005));
+        assert_eq!(summary.converged_fraction, Some(dec!(0.75)));
+    }
+}
@@ -0,0 +1,217 @@
+use anyhow::{Context, Result};
+use chrono::{Duration as ChronoDuration, Utc};
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use tracing::{error, info, warn};
+
+use crate::config::{Config, DriftConfig};
+use crate::monitoring::Notifier;
+use crate::numeric;
+use crate::storage::Storage;
+use crate::types::{PredictionOutcome, Strategy};
+
+/// How much worse recent predictions may be than a strategy's own history
+#[derive(Debug, Clone)]
+pub struct DriftThresholds {
+    recent_trades: usize,
+    min_baseline_trades: usize,
+    max_brier_increase: Decimal,
+    max_clv_drop: Decimal,
+}
+
+impl DriftThresholds {
+    pub fn from_config(config: &DriftConfig) -> Result<Self> {
+        Ok(Self {
+            recent_trades: config.recent_trades.max(1),
+            min_baseline_trades: config.min_baseline_trades,
+            max_brier_increase: numeric::to_decimal(config.max_brier_increase)
+                .context("risk.drift.max_brier_increase")?,
+            max_clv_drop: numeric::to_decimal(config.max_clv_drop).context("risk.drift.max_clv_drop")?,
+        })
+    }
+}
+
+/// A strategy whose recent predictions have drifted from its baseline
+#[derive(Debug, Clone)]
+pub struct Drift {
+    pub strategy: Strategy,
+    pub reason: String,
+}
+
+/// Strategies whose last `recent_trades` outcomes are calibrated worse, or
+/// beat the close by less, than the outcomes before them
+///
+/// `outcomes` must be oldest first. A strategy is only judged once it has a
+/// full recent window and `min_baseline_trades` before it; CLV is compared
+/// over the outcomes with a captured closing line.
+pub fn detect_drift(outcomes: &[PredictionOutcome], thresholds: &DriftThresholds) -> Vec<Drift> {
+    let mut by_strategy: BTreeMap<&str, (Strategy, Vec<&PredictionOutcome>)> = BTreeMap::new();
+    for outcome in outcomes {
+        by_strategy.entry(outcome.strategy.as_str())
+            .or_insert_with(|| (outcome.strategy, Vec::new()))
+            .1
+            .push(outcome);
+    }
+
+    let mut drifts = Vec::new();
+    for (strategy, outcomes) in by_strategy.into_values() {
+        if outcomes.len() < thresholds.recent_trades + thresholds.min_baseline_trades {
+            continue;
+        }
+        let (baseline, recent) = outcomes.split_at(outcomes.len() - thresholds.recent_trades);
+
+        let mut reasons = Vec::new();
+
+        let brier = |window: &[&PredictionOutcome]| mean(window.iter().map(|o| o.brier()));
+        if let (Some(recent_brier), Some(baseline_brier)) = (brier(recent), brier(baseline)) {
+            if recent_brier - baseline_brier > thresholds.max_brier_increase {
+                reasons.push(format!(
+                    "Brier {:.4} vs baseline {:.4}",
+                    recent_brier, baseline_brier
+                ));
+            }
+        }
+
+        let clv = |window: &[&PredictionOutcome]| mean(window.iter().filter_map(|o| o.clv()));
+        if let (Some(recent_clv), Some(baseline_clv)) = (clv(recent), clv(baseline)) {
+            if baseline_clv - recent_clv > thresholds.max_clv_drop {
+                reasons.push(format!(
+                    "CLV {:.4} vs baseline {:.4}",
+                    recent_clv, baseline_clv
+                ));
+            }
+        }
+
+        if !reasons.is_empty() {
+            drifts.push(Drift {
+                strategy,
+                reason: format!("last {} trades: {}", recent.len(), reasons.join(", ")),
+            });
+        }
+    }
+
+    drifts
+}
+
+fn mean(values: impl Iterator<Item = Decimal>) -> Option<Decimal> {
+    let (sum, count) = values.fold((Decimal::ZERO, 0u32), |(sum, count), v| (sum + v, count + 1));
+    (count > 0).then(|| sum / Decimal::from(count))
+}
+
+/// Moves strategies whose predictions have drifted into shadow mode
+///
+/// A shadowed strategy keeps generating and storing signals but the
+/// execution engine no longer trades them; an operator reinstates it
+/// through the admin API.
+pub struct DriftMonitor {
+    storage: Arc<dyn Storage>,
+    notifier: Notifier,
+    thresholds: DriftThresholds,
+    baseline_days: i64,
+    check_interval: Duration,
+}
+
+impl DriftMonitor {
+    pub fn new(storage: Arc<dyn Storage>, config: &Config, notifier: Notifier) -> Result<Self> {
+        let drift = &config.risk.drift;
+
+        Ok(Self {
+            storage,
+            notifier,
+            thresholds: DriftThresholds::from_config(drift)?,
+            baseline_days: drift.baseline_days,
+            check_interval: Duration::from_secs(drift.check_interval_secs.max(1)),
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let mut tick = interval(self.check_interval);
+
+        info!("🧭 Drift monitor started");
+
+        loop {
+            tick.tick().await;
+
+            if let Err(e) = self.check().await {
+                error!("Error checking model drift: {}", e);
+            }
+        }
+    }
+
+    async fn check(&self) -> Result<()> {
+        let since = Utc::now() - ChronoDuration::days(self.baseline_days);
+        let outcomes = self.storage.fetch_prediction_outcomes(since).await?;
+
+        let shadowed = self.storage.fetch_shadowed_strategies().await?;
+
+        for drift in detect_drift(&outcomes, &self.thresholds) {
+            if shadowed.iter().any(|s| s.strategy == drift.strategy) {
+                continue;
+            }
+
+            self.storage.shadow_strategy(drift.strategy, &drift.reason).await?;
+
+            let message = format!(
+                "🚨 Strategy {} moved to shadow mode: {}",
+                drift.strategy.as_str(),
+                drift.reason
+            );
+            warn!("{}", message);
+            self.notifier.critical(message);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use uuid::Uuid;
+
+    fn outcome(strategy: Strategy, fair_value: Decimal, exit_price: Decimal, clv: Decimal) -> PredictionOutcome {
+        PredictionOutcome {
+            trade_id: Uuid::new_v4(),
+            strategy,
+            fair_value,
+            entry_price: dec!(0.50),
+            exit_price,
+            payout: None,
+            closing_price: Some(dec!(0.50) + clv),
+            exit_time: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn flags_strategies_whose_recent_window_falls_behind_their_baseline() {
+        let thresholds = DriftThresholds {
+            recent_trades: 2,
+            min_baseline_trades: 4,
+            max_brier_increase: dec!(0.03),
+            max_clv_drop: dec!(0.02),
+        };
+
+        let mut outcomes = Vec::new();
+        for _ in 0..4 {
+            outcomes.push(outcome(Strategy::ClvArbitrage, dec!(0.8), dec!(1), dec!(0.03)));
+            outcomes.push(outcome(Strategy::PoissonExpectedValue, dec!(0.6), dec!(1), dec!(0.02)));
+        }
+        for _ in 0..2 {
+            // Calibration holds but the market stops moving toward the entries
+            outcomes.push(outcome(Strategy::ClvArbitrage, dec!(0.8), dec!(1), dec!(0.0)));
+            // Unchanged
+            outcomes.push(outcome(Strategy::PoissonExpectedValue, dec!(0.6), dec!(1), dec!(0.02)));
+        }
+
+        let drifts = detect_drift(&outcomes, &thresholds);
+        assert_eq!(drifts.len(), 1);
+        assert_eq!(drifts[0].strategy, Strategy::ClvArbitrage);
+        assert!(drifts[0].reason.contains("CLV"));
+
+        // Too little history to judge
+        assert!(detect_drift(&outcomes[..8], &thresholds).is_empty());
+    }
+}
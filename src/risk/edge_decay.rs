@@ -0,0 +1,208 @@
+use anyhow::Result;
+use chrono::{Duration as ChronoDuration, Utc};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use tracing::{error, info};
+
+use crate::config::Config;
+use crate::storage::Storage;
+use crate::types::{EdgeDecay, SignalExecution, Strategy};
+
+/// Half-lives the fit searches between, in seconds
+const MIN_HALF_LIFE_SECS: f64 = 1.0;
+const MAX_HALF_LIFE_SECS: f64 = 7.0 * 86_400.0;
+
+const ITERATIONS: usize = 100;
+
+/// Squared error of `remaining = 0.5^(age / half_life)` over the samples
+fn squared_error(samples: &[(f64, f64)], half_life: f64) -> f64 {
+    samples.iter()
+        .map(|(age, remaining)| {
+            let diff = remaining - 0.5f64.powf(age / half_life);
+            diff * diff
+        })
+        .sum()
+}
+
+/// Half-life in seconds of `remaining = 0.5^(age / half_life)` fitted by
+/// least squares to (age in seconds, share of edge remaining) samples
+///
+/// Shares are clamped to [0, 2]: an edge the price ran past is simply gone,
+/// and one that grew should not outweigh the rest. Golden-section search
+/// over the log of the half-life; an edge that does not measurably decay
+/// comes out at the longest half-life searched.
+pub fn fit_half_life(samples: &[(f64, f64)]) -> f64 {
+    let samples: Vec<(f64, f64)> = samples.iter()
+        .filter(|(age, remaining)| age.is_finite() && remaining.is_finite())
+        .map(|(age, remaining)| (age.max(0.0), remaining.clamp(0.0, 2.0)))
+        .collect();
+    let error = |log_half_life: f64| squared_error(&samples, log_half_life.exp());
+
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let (mut lo, mut hi) = (MIN_HALF_LIFE_SECS.ln(), MAX_HALF_LIFE_SECS.ln());
+    let mut a = hi - ratio * (hi - lo);
+    let mut b = lo + ratio * (hi - lo);
+    let (mut error_a, mut error_b) = (error(a), error(b));
+    for _ in 0..ITERATIONS {
+        if error_a <= error_b {
+            hi = b;
+            b = a;
+            error_b = error_a;
+            a = hi - ratio * (hi - lo);
+            error_a = error(a);
+        } else {
+            lo = a;
+            a = b;
+            error_a = error_b;
+            b = lo + ratio * (hi - lo);
+            error_b = error(b);
+        }
+    }
+
+    ((lo + hi) / 2.0).exp()
+}
+
+/// Each strategy's (age, remaining share) samples from the signals taken up
+fn decay_samples(executions: &[SignalExecution]) -> BTreeMap<&str, (Strategy, Vec<(f64, f64)>)> {
+    let mut samples: BTreeMap<&str, (Strategy, Vec<(f64, f64)>)> = BTreeMap::new();
+    for execution in executions {
+        if let Some(remaining) = execution.remaining_edge() {
+            samples.entry(execution.strategy.as_str())
+                .or_insert_with(|| (execution.strategy, Vec::new()))
+                .1
+                .push((execution.age_secs(), remaining));
+        }
+    }
+    samples
+}
+
+/// Periodically fits how fast each strategy's edge decays with signal age
+///
+/// The share of a signal's edge left when the engine took it up is how far
+/// the executable price then still was from fair value, over how far it was
+/// at generation. Every signal taken up counts, traded or not, since fills
+/// only happen at or under the signal's own price. Strategies with fewer
+/// than `min_samples` samples are left without a decay and their signals
+/// taken at face value; the execution engine scales the rest by what is
+/// left at their age.
+pub struct EdgeDecayEstimator {
+    storage: Arc<dyn Storage>,
+    lookback_days: i64,
+    min_samples: usize,
+    check_interval: Duration,
+}
+
+impl EdgeDecayEstimator {
+    pub fn new(storage: Arc<dyn Storage>, config: &Config) -> Self {
+        let decay = &config.risk.edge_decay;
+
+        Self {
+            storage,
+            lookback_days: decay.lookback_days.max(1),
+            min_samples: decay.min_samples.max(2),
+            check_interval: Duration::from_secs(decay.check_interval_secs.max(1)),
+        }
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let mut tick = interval(self.check_interval);
+
+        info!("⏳ Edge decay estimator started");
+
+        loop {
+            tick.tick().await;
+
+            if let Err(e) = self.fit().await {
+                error!("Error fitting edge decay: {}", e);
+            }
+        }
+    }
+
+    async fn fit(&self) -> Result<()> {
+        let since = Utc::now() - ChronoDuration::days(self.lookback_days);
+        let executions = self.storage.fetch_signal_executions(since).await?;
+
+        let now = Utc::now();
+        let decays: Vec<EdgeDecay> = decay_samples(&executions)
+            .into_values()
+            .filter(|(_, samples)| samples.len() >= self.min_samples)
+            .map(|(strategy, samples)| EdgeDecay {
+                strategy,
+                half_life_secs: fit_half_life(&samples).round() as i64,
+                samples: samples.len() as i64,
+                updated_at: now,
+            })
+            .collect();
+        if decays.is_empty() {
+            return Ok(());
+        }
+        self.storage.replace_edge_decays(&decays).await?;
+
+        info!(
+            "⏳ Edge half-lives: {}",
+            decays.iter()
+                .map(|d| format!("{} {}s ({} signals)", d.strategy.as_str(), d.half_life_secs, d.samples))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{MarketRepo, MemoryStorage, SignalRepo};
+    use crate::test_support::{market_fixture, signal_fixture};
+    use crate::types::{Price, Signal};
+    use rust_decimal_macros::dec;
+
+    #[tokio::test]
+    async fn samples_every_signal_taken_up_at_its_quote() {
+        let storage = MemoryStorage::new();
+        let market = market_fixture("0xmarket", dec!(0.40));
+        storage.upsert_market(&market).await.unwrap();
+        let traded = signal_fixture(&market, dec!(0.50));
+        let expired = signal_fixture(&market, dec!(0.50));
+        let never_taken = signal_fixture(&market, dec!(0.50));
+        for signal in [&traded, &expired, &never_taken] {
+            storage.insert_signal(signal).await.unwrap();
+        }
+
+        // The price moved halfway to fair value before the first was taken
+        // up, and all the way before the second expired
+        let ask = traded.current_price.value();
+        let halfway = Price::new((ask + dec!(0.50)) / dec!(2)).unwrap();
+        let later = |signal: &Signal| signal.generated_at + ChronoDuration::seconds(60);
+        storage.record_execution_quote(traded.signal_id, halfway, later(&traded)).await.unwrap();
+        storage.record_execution_quote(expired.signal_id, Price::new(dec!(0.50)).unwrap(), later(&expired)).await.unwrap();
+
+        let executions = storage.fetch_signal_executions(Utc::now() - ChronoDuration::days(1)).await.unwrap();
+        let samples = &decay_samples(&executions)[Strategy::ClvArbitrage.as_str()].1;
+        assert_eq!(samples.len(), 2);
+        assert!(samples.iter().all(|(age, _)| (age - 60.0).abs() < 1e-9));
+        let mut remaining: Vec<f64> = samples.iter().map(|(_, r)| *r).collect();
+        remaining.sort_by(f64::total_cmp);
+        assert!(remaining[0].abs() < 1e-9 && (remaining[1] - 0.5).abs() < 1e-9, "{:?}", remaining);
+    }
+
+    #[test]
+    fn recovers_the_half_life_of_decaying_edges() {
+        // Half the edge gone by two minutes, with noise either side
+        let samples: Vec<(f64, f64)> = (0..200)
+            .map(|i| {
+                let age = i as f64 * 3.0;
+                let noise = if i % 2 == 0 { 0.05 } else { -0.05 };
+                (age, 0.5f64.powf(age / 120.0) + noise)
+            })
+            .collect();
+        let half_life = fit_half_life(&samples);
+        assert!((half_life - 120.0).abs() < 10.0, "{}", half_life);
+
+        // Edges still whole however late they were taken do not decay
+        let flat: Vec<(f64, f64)> = (0..50).map(|i| (i as f64 * 60.0, 1.0)).collect();
+        assert!(fit_half_life(&flat) > MAX_HALF_LIFE_SECS * 0.99);
+    }
+}
@@ -0,0 +1,224 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+
+use crate::config::EscalationConfig;
+use crate::numeric;
+use crate::types::{BookmakerOdds, Market, Position, Signal};
+
+/// Why a signal was sized up, for the sizing log
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Escalation {
+    /// Rung of the ladder reached, from 1
+    pub stage: usize,
+    pub multiplier: Decimal,
+    /// How long the edge has held
+    pub persisted: Duration,
+    /// Fresh bookmaker lines showing the edge
+    pub sources: usize,
+}
+
+/// Staged size increases for large edges that persist into the event
+/// start, from `risk.escalation`
+///
+/// An edge only climbs the ladder while it is at least `min_edge` on every
+/// signal on its side of the market, the signals are no more than
+/// `max_gap` apart, the event starts within `window`, the market's prices
+/// and the signal are fresh, and `min_sources` bookmakers' fresh lines
+/// each show the edge on their own. Anything short of that is sized as
+/// usual.
+#[derive(Debug, Clone)]
+pub struct EscalationPolicy {
+    min_edge: Decimal,
+    window: Duration,
+    max_gap: Duration,
+    max_price_age: Duration,
+    max_odds_age: Duration,
+    min_sources: usize,
+    /// (persisted at least, multiplier), ascending
+    stages: Vec<(Duration, Decimal)>,
+}
+
+impl EscalationPolicy {
+    /// The configured policy, `None` when escalation is off
+    pub fn from_config(config: &EscalationConfig) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let min_edge = numeric::to_decimal(config.min_edge).context("risk.escalation.min_edge")?;
+        if min_edge <= Decimal::ZERO || min_edge >= Decimal::ONE {
+            bail!("risk.escalation.min_edge must be between 0 and 1");
+        }
+        if config.min_sources == 0 {
+            bail!("risk.escalation.min_sources must be at least 1");
+        }
+        if config.stages.is_empty() {
+            bail!("risk.escalation.stages must not be empty");
+        }
+
+        let mut stages = Vec::with_capacity(config.stages.len());
+        for (i, stage) in config.stages.iter().enumerate() {
+            let multiplier = numeric::to_decimal(stage.multiplier)
+                .with_context(|| format!("risk.escalation.stages[{}].multiplier", i))?;
+            if multiplier < Decimal::ONE {
+                bail!("risk.escalation.stages[{}].multiplier must be at least 1", i);
+            }
+            if matches!(stages.last(), Some((after, _)) if *after >= Duration::seconds(stage.after_secs)) {
+                bail!("risk.escalation.stages must be in ascending after_secs");
+            }
+            stages.push((Duration::seconds(stage.after_secs), multiplier));
+        }
+
+        Ok(Some(Self {
+            min_edge,
+            window: Duration::minutes(config.window_minutes),
+            max_gap: Duration::seconds(config.max_gap_secs),
+            max_price_age: Duration::seconds(config.max_price_age_secs),
+            max_odds_age: Duration::seconds(config.max_odds_age_secs),
+            min_sources: config.min_sources,
+            stages,
+        }))
+    }
+
+    /// Whether the signal's edge is large enough to be considered at all
+    pub fn applies_to(&self, signal: &Signal) -> bool {
+        signal.edge_size >= self.min_edge
+    }
+
+    /// Earliest signal that can count towards `signal`'s persistence
+    pub fn history_since(&self, signal: &Signal) -> DateTime<Utc> {
+        let longest = self.stages.last().map_or(Duration::zero(), |(after, _)| *after);
+        signal.generated_at - longest - self.max_gap
+    }
+
+    /// How long the edge of `signal` has held, walking back through the
+    /// market's signals (oldest first) until one is on the other side, below
+    /// `min_edge`, or more than `max_gap` before the next
+    fn persisted(&self, signal: &Signal, history: &[Signal]) -> Duration {
+        let position = signal.signal_type.to_position();
+        let mut start = signal.generated_at;
+        for earlier in history.iter().rev().filter(|s| s.generated_at <= signal.generated_at) {
+            if earlier.signal_type.to_position() != position
+                || earlier.edge_size < self.min_edge
+                || start - earlier.generated_at > self.max_gap
+            {
+                break;
+            }
+            start = earlier.generated_at;
+        }
+        signal.generated_at - start
+    }
+
+    /// Bookmakers whose line, no older than `max_odds_age`, shows at least
+    /// `min_edge` against the signal's price
+    fn confirming_sources(&self, signal: &Signal, odds: &[BookmakerOdds], now: DateTime<Utc>) -> usize {
+        let price = signal.current_price.value();
+        odds.iter()
+            .filter(|line| line.age(now) <= self.max_odds_age)
+            .filter(|line| {
+                let fair = match signal.signal_type.to_position() {
+                    Position::Yes => line.yes_implied_prob,
+                    Position::No => line.no_implied_prob,
+                };
+                fair.value() - price >= self.min_edge
+            })
+            .count()
+    }
+
+    /// Rung of the ladder `signal` has reached, `None` when it is to be
+    /// sized as usual
+    pub fn escalation(
+        &self,
+        signal: &Signal,
+        market: &Market,
+        history: &[Signal],
+        odds: &[BookmakerOdds],
+        now: DateTime<Utc>,
+    ) -> Option<Escalation> {
+        if !self.applies_to(signal) {
+            return None;
+        }
+        let until_start = market.event_time - now;
+        if until_start <= Duration::zero() || until_start > self.window {
+            return None;
+        }
+        // The edge is measured at the signal's price; both it and the market must be current
+        if now - signal.generated_at > self.max_price_age || now - market.updated_at > self.max_price_age {
+            return None;
+        }
+
+        let sources = self.confirming_sources(signal, odds, now);
+        if sources < self.min_sources {
+            return None;
+        }
+
+        let persisted = self.persisted(signal, history);
+        let stage = self.stages.iter().rposition(|(after, _)| persisted >= *after)?;
+
+        Some(Escalation { stage: stage + 1, multiplier: self.stages[stage].1, persisted, sources })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::EscalationStageConfig;
+    use crate::types::{Price, Probability, SignalType, Strategy};
+    use rust_decimal_macros::dec;
+    use uuid::Uuid;
+
+    fn signal(signal_type: SignalType, edge: Decimal, generated_at: DateTime<Utc>) -> Signal {
+        Signal {
+            signal_id: Uuid::new_v4(),
+            market_id: "0xabc".to_string(),
+            strategy: Strategy::ClvArbitrage,
+            signal_type,
+            confidence: dec!(0.8),
+            edge_size: edge,
+            recommended_size: dec!(100),
+            current_price: Price::new(dec!(0.40)).unwrap(),
+            executable_size: None,
+            quoted_edge: edge,
+            fair_value: Probability::new(dec!(0.40) + edge).unwrap(),
+            generated_at,
+            metadata: serde_json::Value::Null,
+        }
+    }
+
+    #[test]
+    fn edges_persisting_without_gaps_climb_the_ladder() {
+        let policy = EscalationPolicy::from_config(&EscalationConfig {
+            enabled: true,
+            max_gap_secs: 600,
+            stages: vec![
+                EscalationStageConfig { after_secs: 300, multiplier: 1.25 },
+                EscalationStageConfig { after_secs: 900, multiplier: 1.5 },
+            ],
+            ..EscalationConfig::default()
+        }).unwrap().unwrap();
+        let now = Utc::now();
+        let at = |mins: i64| now - Duration::minutes(mins);
+        let latest = signal(SignalType::BuyYes, dec!(0.10), now);
+
+        // 20 minutes of the edge, signals at most 10 minutes apart
+        let history = vec![
+            signal(SignalType::BuyYes, dec!(0.09), at(20)),
+            signal(SignalType::BuyYes, dec!(0.12), at(10)),
+            latest.clone(),
+        ];
+        assert_eq!(policy.persisted(&latest, &history), Duration::minutes(20));
+
+        // A small edge, the other side, or a long gap ends the run
+        let mut broken = history.clone();
+        broken[1].edge_size = dec!(0.04);
+        assert_eq!(policy.persisted(&latest, &broken), Duration::zero());
+        broken[1] = signal(SignalType::BuyNo, dec!(0.12), at(10));
+        assert_eq!(policy.persisted(&latest, &broken), Duration::zero());
+        let gapped = vec![signal(SignalType::BuyYes, dec!(0.12), at(15)), latest.clone()];
+        assert_eq!(policy.persisted(&latest, &gapped), Duration::zero());
+
+        let stage = policy.stages.iter().rposition(|(after, _)| policy.persisted(&latest, &history) >= *after);
+        assert_eq!(stage.map(|s| policy.stages[s].1), Some(dec!(1.5)));
+    }
+}
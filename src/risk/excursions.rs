@@ -0,0 +1,145 @@
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::types::{Strategy, Trade, Usdc};
+
+/// Excursion percentiles, as a percentage of the position's cost
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Percentiles {
+    pub p25: Decimal,
+    pub p50: Decimal,
+    pub p75: Decimal,
+    pub p90: Decimal,
+}
+
+/// How far one strategy's closed trades moved against and for them
+#[derive(Debug, Clone, Serialize)]
+pub struct StrategyExcursions {
+    pub strategy: Strategy,
+    pub trades: usize,
+    /// Drawdown while held, as a positive loss
+    pub adverse_pct: Percentiles,
+    pub favorable_pct: Percentiles,
+    /// Drawdown that 90% of winning trades stayed within; a stop inside
+    /// this would have cut more than one winner in ten
+    pub winners_adverse_p90_pct: Option<Decimal>,
+}
+
+/// Per-strategy MAE/MFE distributions of closed trades, for setting stop
+/// and target levels from what trades actually did
+pub fn excursion_report(trades: &[Trade]) -> Vec<StrategyExcursions> {
+    // (adverse, favorable, won) per trade, as percentages of cost
+    let mut by_strategy: BTreeMap<&str, (Strategy, Vec<(Decimal, Decimal, bool)>)> = BTreeMap::new();
+    for trade in trades {
+        let cost = trade.position_size_usd();
+        let (Some(mae), Some(mfe)) = (trade.max_adverse_excursion, trade.max_favorable_excursion) else {
+            continue;
+        };
+        if cost <= Decimal::ZERO {
+            continue;
+        }
+
+        let pct = |value: Decimal| value / cost * Decimal::ONE_HUNDRED;
+        let won = trade.pnl.map_or(false, |pnl| pnl > Usdc::ZERO);
+        by_strategy.entry(trade.strategy.as_str())
+            .or_insert_with(|| (trade.strategy, Vec::new()))
+            .1
+            .push((pct(-mae), pct(mfe), won));
+    }
+
+    by_strategy.into_values()
+        .map(|(strategy, rows)| {
+            let adverse: Vec<Decimal> = rows.iter().map(|r| r.0).collect();
+            let favorable: Vec<Decimal> = rows.iter().map(|r| r.1).collect();
+            let winners: Vec<Decimal> = rows.iter().filter(|r| r.2).map(|r| r.0).collect();
+
+            StrategyExcursions {
+                strategy,
+                trades: rows.len(),
+                adverse_pct: percentiles(adverse),
+                favorable_pct: percentiles(favorable),
+                winners_adverse_p90_pct: (!winners.is_empty()).then(|| percentile(&sorted(winners), 90)),
+            }
+        })
+        .collect()
+}
+
+fn sorted(mut values: Vec<Decimal>) -> Vec<Decimal> {
+    values.sort();
+    values
+}
+
+fn percentiles(values: Vec<Decimal>) -> Percentiles {
+    let values = sorted(values);
+    Percentiles {
+        p25: percentile(&values, 25),
+        p50: percentile(&values, 50),
+        p75: percentile(&values, 75),
+        p90: percentile(&values, 90),
+    }
+}
+
+/// Nearest-rank percentile of non-empty sorted values
+fn percentile(sorted: &[Decimal], pct: usize) -> Decimal {
+    let rank = (pct * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Position, TradeOrigin, TradeStatus, POLYMARKET_VENUE};
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+    use uuid::Uuid;
+
+    fn closed(strategy: Strategy, mae: Decimal, mfe: Decimal, pnl: Decimal) -> Trade {
+        Trade {
+            trade_id: Uuid::new_v4(),
+            market_id: "0xmarket".to_string(),
+            strategy,
+            position: Position::Yes,
+            quantity: dec!(100),
+            entry_price: dec!(0.50),
+            exit_price: Some(dec!(0.50)),
+            entry_time: Utc::now(),
+            exit_time: Some(Utc::now()),
+            gas_cost: None,
+            fees: None,
+            slippage: None,
+            pnl: Some(Usdc::new(pnl)),
+            pnl_percent: None,
+            max_adverse_excursion: Some(mae),
+            max_favorable_excursion: Some(mfe),
+            status: TradeStatus::Closed,
+            tx_hash_entry: None,
+            tx_hash_exit: None,
+            venue: POLYMARKET_VENUE.to_string(),
+            origin: TradeOrigin::Bot,
+        }
+    }
+
+    #[test]
+    fn reports_excursions_as_percent_of_cost_per_strategy() {
+        // $50 cost each: drawdowns of 2, 4, ..., 20 percent
+        let mut trades: Vec<Trade> = (1..=10)
+            .map(|i| closed(Strategy::ClvArbitrage, Decimal::from(-i), Decimal::from(2 * i), Decimal::from(i % 2)))
+            .collect();
+        trades.push(closed(Strategy::PoissonExpectedValue, dec!(-5), dec!(0), dec!(-5)));
+
+        let report = excursion_report(&trades);
+        assert_eq!(report.len(), 2);
+
+        let clv = report.iter().find(|r| r.strategy == Strategy::ClvArbitrage).unwrap();
+        assert_eq!(clv.trades, 10);
+        assert_eq!(clv.adverse_pct.p50, dec!(10));
+        assert_eq!(clv.adverse_pct.p90, dec!(18));
+        assert_eq!(clv.favorable_pct.p90, dec!(36));
+        // Winners are the odd trades: 2, 6, 10, 14, 18 percent
+        assert_eq!(clv.winners_adverse_p90_pct, Some(dec!(18)));
+
+        let poisson = report.iter().find(|r| r.strategy == Strategy::PoissonExpectedValue).unwrap();
+        assert_eq!(poisson.winners_adverse_p90_pct, None);
+    }
+}
@@ -0,0 +1,306 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::config::ExposureLimitsConfig;
+use crate::numeric;
+use crate::storage::Storage;
+use crate::types::{Market, Position, Trade};
+
+/// Time-to-event buckets, nearest first
+const TIME_TO_EVENT_BUCKETS: &[&str] = &["in_play", "<1h", "1-6h", "6-24h", "1-3d", ">3d", "unknown"];
+
+/// Concentration limits as fractions of total capital
+pub struct ExposureLimits {
+    sport: Decimal,
+    event: Decimal,
+    strategy: Decimal,
+}
+
+impl ExposureLimits {
+    pub fn from_config(config: &ExposureLimitsConfig) -> Result<Self> {
+        let fraction = |pct: f64, key: &str| -> Result<Decimal> {
+            let pct = numeric::to_decimal(pct).with_context(|| format!("risk.exposure_limits.{}", key))?;
+            Ok(pct / Decimal::ONE_HUNDRED)
+        };
+
+        Ok(Self {
+            sport: fraction(config.sport_pct, "sport_pct")?,
+            event: fraction(config.event_pct, "event_pct")?,
+            strategy: fraction(config.strategy_pct, "strategy_pct")?,
+        })
+    }
+}
+
+/// Open exposure of one sport, event, strategy or time-to-event bucket
+#[derive(Debug, Clone, Serialize)]
+pub struct ExposureSlice {
+    pub key: String,
+    pub positions: usize,
+    /// Cost of the positions held
+    pub gross: Decimal,
+    /// Worst-case loss, after offsetting YES and NO shares held in the same market
+    pub net: Decimal,
+    /// USDC limit on gross exposure, where one applies
+    pub limit: Option<Decimal>,
+    pub utilization_pct: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExposureReport {
+    pub total_capital: Decimal,
+    pub gross: Decimal,
+    pub net: Decimal,
+    /// Gross exposure as a percentage of total capital
+    pub utilization_pct: Option<Decimal>,
+    /// Largest first, except time-to-event which runs nearest first
+    pub by_sport: Vec<ExposureSlice>,
+    pub by_event: Vec<ExposureSlice>,
+    pub by_strategy: Vec<ExposureSlice>,
+    pub by_time_to_event: Vec<ExposureSlice>,
+    pub as_of: DateTime<Utc>,
+}
+
+/// Shares and cost held on each side of one market
+#[derive(Default)]
+struct Book {
+    yes_shares: Decimal,
+    no_shares: Decimal,
+    cost: Decimal,
+}
+
+impl Book {
+    fn add(&mut self, trade: &Trade) {
+        match trade.position {
+            Position::Yes => self.yes_shares += trade.quantity,
+            Position::No => self.no_shares += trade.quantity,
+        }
+        self.cost += trade.position_size_usd();
+    }
+
+    /// A YES and a NO share together always pay out $1
+    fn worst_case_loss(&self) -> Decimal {
+        (self.cost - self.yes_shares.min(self.no_shares)).max(Decimal::ZERO)
+    }
+}
+
+#[derive(Default)]
+struct Bucket {
+    positions: usize,
+    books: HashMap<String, Book>,
+}
+
+impl Bucket {
+    fn add(&mut self, trade: &Trade) {
+        self.positions += 1;
+        self.books.entry(trade.market_id.clone()).or_default().add(trade);
+    }
+
+    fn gross(&self) -> Decimal {
+        self.books.values().map(|b| b.cost).sum()
+    }
+
+    fn net(&self) -> Decimal {
+        self.books.values().map(Book::worst_case_loss).sum()
+    }
+
+    fn slice(&self, key: String, limit: Option<Decimal>) -> ExposureSlice {
+        let gross = self.gross();
+        ExposureSlice {
+            key,
+            positions: self.positions,
+            gross,
+            net: self.net(),
+            limit,
+            utilization_pct: limit.and_then(|limit| utilization_pct(gross, limit)),
+        }
+    }
+}
+
+/// Open exposure sliced by sport, event, strategy and time to event, with
+/// utilization of the concentration limits
+///
+/// Trades in markets missing from `markets` are reported under `unknown`.
+pub fn exposure_report(
+    trades: &[Trade],
+    markets: &HashMap<String, Market>,
+    total_capital: Decimal,
+    limits: &ExposureLimits,
+    now: DateTime<Utc>,
+) -> ExposureReport {
+    let mut total = Bucket::default();
+    let mut by_sport: HashMap<String, Bucket> = HashMap::new();
+    let mut by_event: HashMap<String, Bucket> = HashMap::new();
+    let mut by_strategy: HashMap<String, Bucket> = HashMap::new();
+    let mut by_time: HashMap<&'static str, Bucket> = HashMap::new();
+
+    for trade in trades {
+        let market = markets.get(&trade.market_id);
+        let sport = market.map_or("unknown", |m| m.sport.as_str());
+        let event = market.map_or(trade.market_id.as_str(), |m| m.event_name.as_str());
+
+        total.add(trade);
+        by_sport.entry(sport.to_string()).or_default().add(trade);
+        by_event.entry(event.to_string()).or_default().add(trade);
+        by_strategy.entry(trade.strategy.as_str().to_string()).or_default().add(trade);
+        by_time.entry(market.map_or("unknown", |m| time_to_event_bucket(m.event_time - now)))
+            .or_default()
+            .add(trade);
+    }
+
+    let limited = |buckets: HashMap<String, Bucket>, limit: Decimal| {
+        let mut slices: Vec<ExposureSlice> = buckets.into_iter()
+            .map(|(key, bucket)| bucket.slice(key, Some(total_capital * limit)))
+            .collect();
+        slices.sort_by(|a, b| b.gross.cmp(&a.gross).then_with(|| a.key.cmp(&b.key)));
+        slices
+    };
+
+    let gross = total.gross();
+    ExposureReport {
+        total_capital,
+        gross,
+        net: total.net(),
+        utilization_pct: utilization_pct(gross, total_capital),
+        by_sport: limited(by_sport, limits.sport),
+        by_event: limited(by_event, limits.event),
+        by_strategy: limited(by_strategy, limits.strategy),
+        by_time_to_event: TIME_TO_EVENT_BUCKETS.iter()
+            .filter_map(|key| Some(by_time.get(key)?.slice(key.to_string(), None)))
+            .collect(),
+        as_of: now,
+    }
+}
+
+/// Exposure report of the trades open right now
+pub async fn current_exposure(
+    storage: &dyn Storage,
+    total_capital: Decimal,
+    limits: &ExposureLimits,
+) -> Result<ExposureReport> {
+    let trades = storage.fetch_open_trades().await?;
+
+    let mut markets = HashMap::new();
+    for trade in &trades {
+        if markets.contains_key(&trade.market_id) {
+            continue;
+        }
+        if let Some(market) = storage.fetch_market(&trade.market_id).await? {
+            markets.insert(trade.market_id.clone(), market);
+        }
+    }
+
+    Ok(exposure_report(&trades, &markets, total_capital, limits, Utc::now()))
+}
+
+fn time_to_event_bucket(until_start: Duration) -> &'static str {
+    if until_start <= Duration::zero() {
+        "in_play"
+    } else if until_start < Duration::hours(1) {
+        "<1h"
+    } else if until_start < Duration::hours(6) {
+        "1-6h"
+    } else if until_start < Duration::hours(24) {
+        "6-24h"
+    } else if until_start < Duration::days(3) {
+        "1-3d"
+    } else {
+        ">3d"
+    }
+}
+
+fn utilization_pct(exposure: Decimal, limit: Decimal) -> Option<Decimal> {
+    (limit > Decimal::ZERO).then(|| exposure / limit * Decimal::ONE_HUNDRED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{MarketStatus, MarketType, Price, Quote, Sport, Strategy, TradeOrigin, TradeStatus, POLYMARKET_VENUE};
+    use rust_decimal_macros::dec;
+    use uuid::Uuid;
+
+    fn market(market_id: &str, sport: Sport, event_name: &str, event_time: DateTime<Utc>) -> Market {
+        let price = Price::new(dec!(0.5)).unwrap();
+        Market {
+            market_id: market_id.to_string(),
+            sport,
+            event_name: event_name.to_string(),
+            event_time,
+            market_type: MarketType::Moneyline,
+            description: None,
+            resolution_source: None,
+            min_liquidity: Decimal::ZERO,
+            current_liquidity: dec!(10000),
+            yes_price: price,
+            no_price: price,
+            yes_quote: Quote::at(price),
+            no_quote: Quote::at(price),
+            status: MarketStatus::Active,
+            tokens: None,
+            created_at: event_time,
+            updated_at: event_time,
+        }
+    }
+
+    fn open(market_id: &str, strategy: Strategy, position: Position, quantity: Decimal, entry_price: Decimal) -> Trade {
+        Trade {
+            trade_id: Uuid::new_v4(),
+            market_id: market_id.to_string(),
+            strategy,
+            position,
+            quantity,
+            entry_price,
+            exit_price: None,
+            entry_time: Utc::now(),
+            exit_time: None,
+            gas_cost: None,
+            fees: None,
+            slippage: None,
+            pnl: None,
+            pnl_percent: None,
+            max_adverse_excursion: None,
+            max_favorable_excursion: None,
+            status: TradeStatus::Open,
+            tx_hash_entry: None,
+            tx_hash_exit: None,
+            venue: POLYMARKET_VENUE.to_string(),
+            origin: TradeOrigin::Bot,
+        }
+    }
+
+    #[test]
+    fn slices_exposure_and_nets_opposite_sides_of_a_market() {
+        let now = Utc::now();
+        let markets = HashMap::from([
+            ("0xnba".to_string(), market("0xnba", Sport::NBA, "Lakers @ Celtics", now + Duration::hours(3))),
+            ("0xnfl".to_string(), market("0xnfl", Sport::NFL, "Chiefs @ Bills", now + Duration::days(2))),
+        ]);
+        let trades = vec![
+            // $40 YES and $30 NO on the same market: 100 of the shares pay out either way
+            open("0xnba", Strategy::ClvArbitrage, Position::Yes, dec!(100), dec!(0.40)),
+            open("0xnba", Strategy::ClvArbitrage, Position::No, dec!(100), dec!(0.30)),
+            open("0xnfl", Strategy::PoissonExpectedValue, Position::Yes, dec!(200), dec!(0.50)),
+        ];
+        let limits = ExposureLimits { sport: dec!(0.30), event: dec!(0.10), strategy: dec!(0.40) };
+
+        let report = exposure_report(&trades, &markets, dec!(1000), &limits, now);
+
+        assert_eq!(report.gross, dec!(170));
+        assert_eq!(report.net, dec!(100));
+        assert_eq!(report.utilization_pct, Some(dec!(17)));
+
+        let nfl = &report.by_event[0];
+        assert_eq!(nfl.key, "Chiefs @ Bills");
+        assert_eq!((nfl.gross, nfl.net), (dec!(100), dec!(100)));
+        assert_eq!(nfl.utilization_pct, Some(dec!(100)));
+
+        let clv = report.by_strategy.iter().find(|s| s.key == Strategy::ClvArbitrage.as_str()).unwrap();
+        assert_eq!((clv.positions, clv.gross, clv.net), (2, dec!(70), dec!(0)));
+
+        let buckets: Vec<&str> = report.by_time_to_event.iter().map(|s| s.key.as_str()).collect();
+        assert_eq!(buckets, vec!["1-6h", "1-3d"]);
+    }
+}
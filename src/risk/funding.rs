@@ -0,0 +1,313 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use ethers::abi::parse_abi;
+use ethers::prelude::*;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use rust_decimal::Decimal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use tracing::{info, error, warn};
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::execution::{usdc_from_chain, RpcFailover};
+use crate::monitoring::{Heartbeats, IncidentManager, Notifier};
+use crate::numeric;
+use crate::storage::{Storage, VenueBalances};
+use crate::types::{CashFlow, CashFlowKind, CashFlowSource, Trade, TradeStatus, Usdc, POLYMARKET_VENUE};
+
+/// USDC the book says the wallet holds: capital, flows and realized PnL,
+/// less what open Polymarket positions cost to enter
+///
+/// Gas is paid in POL but booked against PnL, so the wallet normally holds
+/// a little more than this.
+pub fn book_cash(starting_capital: Decimal, balances: &VenueBalances, open: &[Trade]) -> Usdc {
+    let held: Usdc = open.iter()
+        .filter(|t| t.status == TradeStatus::Open && t.venue == balances.venue)
+        .map(|t| Usdc::new(t.position_size_usd()) + t.fees.unwrap_or_default())
+        .sum();
+    Usdc::new(starting_capital + balances.net_cash_flows + balances.realized_pnl) - held
+}
+
+/// How far the wallet falls short of the book, when by more than `tolerance`
+pub fn cash_shortfall(wallet: Usdc, book: Usdc, tolerance: Usdc) -> Option<Usdc> {
+    let shortfall = book - wallet;
+    (shortfall > tolerance).then_some(shortfall)
+}
+
+/// Records USDC transfers into and out of the trading wallet as cash flows
+///
+/// Transfers to or from the Polymarket contracts are trading activity, not
+/// funding, and are ignored.
+pub struct FundingMonitor {
+    storage: Arc<dyn Storage>,
+    rpc: Arc<RpcFailover<Http>>,
+    usdc: Address,
+    wallet: Address,
+    /// Counterparties whose transfers are trades rather than funding
+    excluded: Vec<Address>,
+    poll_interval: Duration,
+    erc20_abi: BaseContract,
+    starting_capital: Decimal,
+    reconcile_tolerance: Usdc,
+    notifier: Notifier,
+    incidents: IncidentManager,
+    heartbeats: Heartbeats,
+    /// Whether the current shortfall has been alerted, so it is raised once
+    short: AtomicBool,
+}
+
+impl FundingMonitor {
+    pub fn new(
+        storage: Arc<dyn Storage>,
+        rpc: Arc<RpcFailover<Http>>,
+        config: &Config,
+        notifier: Notifier,
+        heartbeats: Heartbeats,
+    ) -> Result<Self> {
+        let chain = config.chain()?;
+        let wallet = config.blockchain.private_key
+            .parse::<LocalWallet>()
+            .context("blockchain.private_key")?
+            .address();
+        let starting_capital = config.risk.venue_limits()
+            .get(POLYMARKET_VENUE)
+            .map(|limits| numeric::to_decimal(limits.starting_capital))
+            .transpose()
+            .context("risk.venues.polymarket.starting_capital")?
+            .unwrap_or_default();
+        let reconcile_tolerance = numeric::to_decimal(config.risk.balance_reconcile_tolerance_usd)
+            .context("risk.balance_reconcile_tolerance_usd")?;
+
+        Ok(Self {
+            storage,
+            rpc,
+            usdc: chain.usdc,
+            wallet,
+            excluded: vec![chain.ctf_exchange, chain.conditional_tokens],
+            poll_interval: Duration::from_secs(config.polymarket.poll_interval_secs),
+            erc20_abi: BaseContract::from(parse_abi(&[
+                "function balanceOf(address owner) external view returns (uint256)",
+            ])?),
+            starting_capital,
+            reconcile_tolerance: Usdc::new(reconcile_tolerance),
+            notifier,
+            incidents: IncidentManager::new(&config.monitoring.incidents),
+            heartbeats,
+            short: AtomicBool::new(false),
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let mut tick = interval(self.poll_interval);
+        // Only watch new blocks; earlier funding is entered via the admin API
+        let mut from_block = self.head().await?;
+
+        info!("💵 Funding monitor started for {:?}", self.wallet);
+
+        loop {
+            tick.tick().await;
+
+            match self.scan(from_block).await {
+                Ok(next) => from_block = next,
+                Err(e) => error!("Error scanning USDC transfers: {}", e),
+            }
+            if let Err(e) = self.reconcile().await {
+                error!("Error reconciling the wallet's USDC: {}", e);
+            }
+            self.heartbeats.beat_every("funding_monitor", self.poll_interval);
+        }
+    }
+
+    /// USDC held by the wallet
+    async fn wallet_usdc(&self) -> Result<Usdc> {
+        let data = self.erc20_abi.encode("balanceOf", self.wallet)?;
+        let tx: TypedTransaction = TransactionRequest::new().to(self.usdc).data(data).into();
+        let balance = self.rpc.call(|provider| async move { Ok(provider.call(&tx, None).await?) }).await?;
+        usdc_from_chain(self.erc20_abi.decode_output("balanceOf", balance)?)
+    }
+
+    /// Compare the wallet's USDC with the book, alerting once and paging
+    /// while it falls short
+    async fn reconcile(&self) -> Result<()> {
+        let wallet = self.wallet_usdc().await?;
+        let balances = self.storage.calculate_venue_balances().await?
+            .into_iter()
+            .find(|b| b.venue == POLYMARKET_VENUE)
+            .unwrap_or_else(|| VenueBalances::empty(POLYMARKET_VENUE.to_string()));
+        let open = self.storage.fetch_open_trades().await?;
+        let book = book_cash(self.starting_capital, &balances, &open);
+
+        let shortfall = cash_shortfall(wallet, book, self.reconcile_tolerance);
+        match shortfall {
+            Some(shortfall) => {
+                warn!("💵 Wallet holds ${} USDC, ${} short of the book's ${}", wallet, shortfall.round_dp(2), book.round_dp(2));
+                if !self.short.swap(true, Ordering::Relaxed) {
+                    self.notifier.critical(format!(
+                        "💵 Wallet holds ${} USDC, ${} short of the book's ${}",
+                        wallet.round_dp(2),
+                        shortfall.round_dp(2),
+                        book.round_dp(2)
+                    ));
+                }
+            }
+            None => {
+                if self.short.swap(false, Ordering::Relaxed) {
+                    info!("💵 Wallet USDC ${} reconciles with the book's ${}", wallet, book.round_dp(2));
+                }
+            }
+        }
+
+        let summary = shortfall.map(|shortfall| format!(
+            "Wallet holds ${} USDC, ${} short of the book's ${}",
+            wallet.round_dp(2),
+            shortfall.round_dp(2),
+            book.round_dp(2)
+        ));
+        self.incidents.update("reconciliation-cash", summary).await;
+        Ok(())
+    }
+
+    async fn head(&self) -> Result<U64> {
+        self.rpc.call(|provider| async move { Ok(provider.get_block_number().await?) }).await
+    }
+
+    async fn logs(&self, filter: Filter) -> Result<Vec<Log>> {
+        self.rpc.call(|provider| async move { Ok(provider.get_logs(&filter).await?) }).await
+    }
+
+    /// Record transfers from `from_block` up to the chain head; returns the next block to scan
+    async fn scan(&self, from_block: U64) -> Result<U64> {
+        let to_block = self.head().await?;
+        if to_block < from_block {
+            return Ok(from_block);
+        }
+
+        let wallet = H256::from(self.wallet);
+        let transfers = Filter::new()
+            .address(self.usdc)
+            .event("Transfer(address,address,uint256)")
+            .from_block(from_block)
+            .to_block(to_block);
+
+        let incoming = self.logs(transfers.clone().topic2(wallet)).await?;
+        let outgoing = self.logs(transfers.topic1(wallet)).await?;
+
+        for (kind, log) in incoming.iter().map(|l| (CashFlowKind::Deposit, l))
+            .chain(outgoing.iter().map(|l| (CashFlowKind::Withdrawal, l)))
+        {
+            self.record(kind, log).await?;
+        }
+
+        Ok(to_block + 1)
+    }
+
+    async fn record(&self, kind: CashFlowKind, log: &Log) -> Result<()> {
+        let Some(tx_hash) = log.transaction_hash else {
+            return Ok(());
+        };
+        if log.topics.len() < 3 {
+            return Ok(());
+        }
+
+        let counterparty = match kind {
+            CashFlowKind::Deposit => Address::from(log.topics[1]),
+            CashFlowKind::Withdrawal => Address::from(log.topics[2]),
+        };
+        if self.excluded.contains(&counterparty) {
+            return Ok(());
+        }
+
+        let tx_hash = format!("{:?}", tx_hash);
+        if self.storage.cash_flow_exists(&tx_hash).await? {
+            return Ok(());
+        }
+
+        let amount = match usdc_from_chain(U256::from_big_endian(&log.data)) {
+            Ok(amount) => amount,
+            Err(e) => {
+                warn!("💵 Skipping transfer {}: {}", tx_hash, e);
+                return Ok(());
+            }
+        };
+        if amount <= Usdc::ZERO {
+            return Ok(());
+        }
+
+        let flow = CashFlow {
+            flow_id: Uuid::new_v4(),
+            kind,
+            amount,
+            source: CashFlowSource::OnChain,
+            tx_hash: Some(tx_hash.clone()),
+            note: Some(format!("USDC transfer {:?}", counterparty)),
+            occurred_at: Utc::now(),
+            venue: POLYMARKET_VENUE.to_string(),
+        };
+        self.storage.insert_cash_flow(&flow).await?;
+
+        info!("💵 Recorded {} of ${} ({})", kind.as_str(), amount, tx_hash);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Position, Strategy, TradeOrigin};
+    use rust_decimal_macros::dec;
+
+    fn open_trade(venue: &str, quantity: Decimal, entry_price: Decimal, fees: Decimal) -> Trade {
+        Trade {
+            trade_id: Uuid::new_v4(),
+            market_id: "0xbook".to_string(),
+            strategy: Strategy::ClvArbitrage,
+            position: Position::Yes,
+            quantity,
+            entry_price,
+            exit_price: None,
+            entry_time: Utc::now(),
+            exit_time: None,
+            gas_cost: Some(dec!(0.05)),
+            fees: Some(Usdc::new(fees)),
+            slippage: None,
+            pnl: None,
+            pnl_percent: None,
+            max_adverse_excursion: None,
+            max_favorable_excursion: None,
+            status: TradeStatus::Open,
+            tx_hash_entry: None,
+            tx_hash_exit: None,
+            venue: venue.to_string(),
+            origin: TradeOrigin::Bot,
+        }
+    }
+
+    #[test]
+    fn book_cash_nets_flows_pnl_and_open_positions_of_the_venue() {
+        let balances = VenueBalances {
+            net_cash_flows: dec!(500),
+            realized_pnl: dec!(-20),
+            ..VenueBalances::empty(POLYMARKET_VENUE.to_string())
+        };
+        let open = vec![
+            open_trade(POLYMARKET_VENUE, dec!(100), dec!(0.40), dec!(0.80)),
+            open_trade("betfair", dec!(100), dec!(0.50), dec!(1)),
+        ];
+
+        // 1000 + 500 - 20, less 40 of shares and 0.80 of fees; entry gas was paid in POL
+        assert_eq!(book_cash(dec!(1000), &balances, &open), Usdc::new(dec!(1439.20)));
+    }
+
+    #[test]
+    fn only_a_shortfall_past_tolerance_is_reported() {
+        let book = Usdc::new(dec!(1000));
+        let tolerance = Usdc::new(dec!(5));
+
+        assert_eq!(cash_shortfall(Usdc::new(dec!(996)), book, tolerance), None);
+        // Unbooked gas leaves the wallet ahead of the book, which is no shortfall
+        assert_eq!(cash_shortfall(Usdc::new(dec!(1012)), book, tolerance), None);
+        assert_eq!(cash_shortfall(Usdc::new(dec!(990)), book, tolerance), Some(Usdc::new(dec!(10))));
+    }
+}
@@ -0,0 +1,211 @@
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+use crate::numeric;
+use crate::types::{PredictionOutcome, Strategy, TradeSizing};
+
+/// A strategy's stakes are judged off-policy once their ratio to the
+/// optimal stake strays this far, relatively, from the policy fraction
+const POLICY_TOLERANCE: Decimal = dec!(0.25);
+
+/// Fair-value buckets per strategy that realized probabilities are taken over
+const BUCKETS: u32 = 10;
+
+/// One closed trade's stake against the full-Kelly stake at the realized probability
+#[derive(Debug, Clone, Serialize)]
+pub struct KellyRealization {
+    pub trade_id: Uuid,
+    pub strategy: Strategy,
+    pub bankroll: Decimal,
+    pub stake: Decimal,
+    /// Mean exit price of the strategy's trades in the same fair-value decile
+    pub realized_probability: Decimal,
+    pub optimal_stake: Decimal,
+    /// Log growth the trade added to the bankroll, `None` if it would have been ruined
+    pub growth: Option<f64>,
+    /// Log growth at the optimal stake
+    pub optimal_growth: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sizing {
+    Over,
+    Under,
+    InLine,
+}
+
+/// How a strategy's stakes compared with full Kelly over its closed trades
+#[derive(Debug, Clone, Serialize)]
+pub struct StrategyKelly {
+    pub strategy: Strategy,
+    pub trades: usize,
+    pub stake: Decimal,
+    pub optimal_stake: Decimal,
+    /// Staked / optimal, `None` when no trade had a realized edge
+    pub kelly_multiple: Option<Decimal>,
+    pub policy_fraction: Decimal,
+    pub sizing: Sizing,
+    pub growth: f64,
+    pub optimal_growth: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KellyReport {
+    pub strategies: Vec<StrategyKelly>,
+    pub trades: Vec<KellyRealization>,
+}
+
+/// Full-Kelly stakes at realized probabilities against what was staked
+///
+/// A trade's realized probability is the mean exit price of its strategy's
+/// trades in the same fair-value decile, so it measures what the signals
+/// were worth rather than what they claimed. Trades without a recorded
+/// bankroll are skipped. A strategy staking more than `policy_fraction` of
+/// the optimal stake, by more than the tolerance, is over-betting.
+pub fn kelly_realization(
+    outcomes: &[PredictionOutcome],
+    sizing: &[TradeSizing],
+    policy_fraction: Decimal,
+) -> KellyReport {
+    let sizing: HashMap<Uuid, &TradeSizing> = sizing.iter().map(|s| (s.trade_id, s)).collect();
+    let sized: Vec<(&PredictionOutcome, &TradeSizing)> = outcomes.iter()
+        .filter_map(|o| Some((o, *sizing.get(&o.trade_id)?)))
+        .filter(|(o, s)| s.bankroll > Decimal::ZERO && o.entry_price > Decimal::ZERO && o.entry_price < Decimal::ONE)
+        .collect();
+
+    // (sum of exit prices, count) per strategy and fair-value bucket
+    let mut realized: HashMap<(&str, u32), (Decimal, Decimal)> = HashMap::new();
+    for (outcome, _) in &sized {
+        let entry = realized.entry((outcome.strategy.as_str(), bucket(outcome.fair_value))).or_default();
+        entry.0 += outcome.exit_price;
+        entry.1 += Decimal::ONE;
+    }
+
+    let trades: Vec<KellyRealization> = sized.iter()
+        .map(|(outcome, sizing)| {
+            let (sum, count) = realized[&(outcome.strategy.as_str(), bucket(outcome.fair_value))];
+            let probability = sum / count;
+            let price = outcome.entry_price;
+            let optimal_fraction = ((probability - price) / (Decimal::ONE - price)).max(Decimal::ZERO);
+            let trade_return = outcome.exit_price / price - Decimal::ONE;
+
+            KellyRealization {
+                trade_id: outcome.trade_id,
+                strategy: outcome.strategy,
+                bankroll: sizing.bankroll,
+                stake: sizing.stake,
+                realized_probability: probability,
+                optimal_stake: optimal_fraction * sizing.bankroll,
+                growth: log_growth(sizing.stake / sizing.bankroll, trade_return),
+                optimal_growth: log_growth(optimal_fraction, trade_return),
+            }
+        })
+        .collect();
+
+    let mut by_strategy: BTreeMap<&str, (Strategy, Vec<&KellyRealization>)> = BTreeMap::new();
+    for trade in &trades {
+        by_strategy.entry(trade.strategy.as_str())
+            .or_insert_with(|| (trade.strategy, Vec::new()))
+            .1
+            .push(trade);
+    }
+
+    let strategies = by_strategy.into_values()
+        .map(|(strategy, rows)| {
+            let stake: Decimal = rows.iter().map(|r| r.stake).sum();
+            let optimal_stake: Decimal = rows.iter().map(|r| r.optimal_stake).sum();
+            let kelly_multiple = (optimal_stake > Decimal::ZERO).then(|| stake / optimal_stake);
+
+            let sizing = match kelly_multiple {
+                None if stake > Decimal::ZERO => Sizing::Over,
+                None => Sizing::InLine,
+                Some(m) if m > policy_fraction * (Decimal::ONE + POLICY_TOLERANCE) => Sizing::Over,
+                Some(m) if m < policy_fraction * (Decimal::ONE - POLICY_TOLERANCE) => Sizing::Under,
+                Some(_) => Sizing::InLine,
+            };
+
+            StrategyKelly {
+                strategy,
+                trades: rows.len(),
+                stake,
+                optimal_stake,
+                kelly_multiple,
+                policy_fraction,
+                sizing,
+                growth: rows.iter().filter_map(|r| r.growth).sum(),
+                optimal_growth: rows.iter().filter_map(|r| r.optimal_growth).sum(),
+            }
+        })
+        .collect();
+
+    KellyReport { strategies, trades }
+}
+
+fn bucket(fair_value: Decimal) -> u32 {
+    (fair_value * Decimal::from(BUCKETS)).floor().to_u32().unwrap_or(0).min(BUCKETS - 1)
+}
+
+/// ln(1 + fraction * return), `None` when the bankroll would be wiped out
+fn log_growth(fraction: Decimal, trade_return: Decimal) -> Option<f64> {
+    let wealth = numeric::to_f64(Decimal::ONE + fraction * trade_return).ok()?;
+    (wealth > 0.0).then(|| wealth.ln())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn outcome(fair_value: Decimal, entry_price: Decimal, exit_price: Decimal) -> (PredictionOutcome, TradeSizing) {
+        let trade_id = Uuid::new_v4();
+        let outcome = PredictionOutcome {
+            trade_id,
+            strategy: Strategy::PoissonExpectedValue,
+            fair_value,
+            entry_price,
+            exit_price,
+            payout: None,
+            closing_price: None,
+            exit_time: Utc::now(),
+        };
+        let sizing = TradeSizing { trade_id, bankroll: dec!(1000), stake: dec!(100), sized_at: Utc::now() };
+        (outcome, sizing)
+    }
+
+    #[test]
+    fn flags_stakes_well_above_the_policy_fraction_of_realized_kelly() {
+        // Signals said 0.65 at a price of 0.5; half of them won
+        let (outcomes, sizing): (Vec<_>, Vec<_>) = [dec!(1), dec!(1), dec!(0), dec!(0)]
+            .into_iter()
+            .map(|exit| outcome(dec!(0.65), dec!(0.5), exit))
+            .unzip();
+
+        let report = kelly_realization(&outcomes, &sizing, dec!(0.5));
+        assert_eq!(report.trades.len(), 4);
+        assert_eq!(report.trades[0].realized_probability, dec!(0.5));
+        assert_eq!(report.trades[0].optimal_stake, Decimal::ZERO);
+
+        // No realized edge, yet every trade staked 10% of the bankroll
+        let strategy = &report.strategies[0];
+        assert_eq!(strategy.kelly_multiple, None);
+        assert_eq!(strategy.sizing, Sizing::Over);
+        assert!(strategy.growth < 0.0);
+        assert_eq!(strategy.optimal_growth, 0.0);
+
+        // Three in four winning at 0.5 makes full Kelly half the bankroll,
+        // so 10% stakes are a fifth of it against a half-Kelly policy
+        let (outcomes, sizing): (Vec<_>, Vec<_>) = [dec!(1), dec!(1), dec!(1), dec!(0)]
+            .into_iter()
+            .map(|exit| outcome(dec!(0.65), dec!(0.5), exit))
+            .unzip();
+        let strategy = &kelly_realization(&outcomes, &sizing, dec!(0.5)).strategies[0];
+        assert_eq!(strategy.kelly_multiple, Some(dec!(0.2)));
+        assert_eq!(strategy.sizing, Sizing::Under);
+        assert!(strategy.optimal_growth > strategy.growth);
+    }
+}
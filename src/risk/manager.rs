@@ -1,54 +1,157 @@
-use anyhow::{Result, bail};
-use sqlx::PgPool;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use tokio::sync::RwLock;
-use tracing::{info, warn};
+use tracing::{debug, error, info, warn};
 
 use crate::config::Config;
-use crate::types::{Signal, RiskLimits, PortfolioState};
-use super::PortfolioTracker;
+use crate::events::{Event, EventBus};
+use crate::monitoring::Notifier;
+use crate::numeric::{self, RoundingPolicy};
+use crate::storage::Storage;
+use crate::types::{Signal, RiskLimits, PortfolioState, Strategy, StrategyThrottle, StrategyWeight, Usdc};
+use super::{EscalationPolicy, PortfolioTracker};
+
+/// Depth a stake may take from the book, from `risk.depth_sizing`
+#[derive(Debug, Clone, Copy)]
+struct DepthSizing {
+    /// Fraction of price above the best offer
+    tolerance: Decimal,
+    max_book_age: Duration,
+}
+
+/// How long sizing reuses the strategy weights it last read; they are
+/// solved daily
+const WEIGHTS_REFRESH_SECS: i64 = 300;
+
+/// How strategy weights scale stakes, from `risk.weights`
+#[derive(Debug, Clone, Copy)]
+struct WeightSizing {
+    /// Least multiplier of a weighted strategy
+    floor: Decimal,
+    /// Multiplier of strategies the solve left without a weight
+    unweighted: Decimal,
+}
 
 #[derive(Clone)]
 pub struct RiskManager {
-    db_pool: PgPool,
+    storage: Arc<dyn Storage>,
     limits: RiskLimits,
+    /// Daily drawdown limits overriding `limits` for individual venues
+    venue_drawdown_limits: HashMap<String, Decimal>,
+    /// (min, max) trade notional of markets overriding the global bounds
+    market_notional: HashMap<String, (Decimal, Decimal)>,
+    /// Caps stakes at book depth, when enabled
+    depth_sizing: Option<DepthSizing>,
+    /// Sizes up large, persistent edges near the event start, when enabled
+    escalation: Option<EscalationPolicy>,
+    /// Scales stakes by strategy weight, when enabled
+    weight_sizing: Option<WeightSizing>,
+    /// Strategy weights as last read, and when
+    strategy_weights: Arc<Mutex<Option<(DateTime<Utc>, Vec<StrategyWeight>)>>>,
     portfolio_tracker: Arc<RwLock<PortfolioTracker>>,
+    /// Day the execution cost budget alert last fired
+    cost_alert_day: Arc<Mutex<Option<NaiveDate>>>,
+    events: EventBus,
+    notifier: Notifier,
 }
 
 impl RiskManager {
-    pub async fn new(db_pool: PgPool, config: &Config) -> Result<Self> {
+    pub async fn new(storage: Arc<dyn Storage>, events: EventBus, config: &Config, notifier: Notifier) -> Result<Self> {
         let limits = RiskLimits {
-            max_position_size_pct: Decimal::from_f64_retain(config.risk.max_position_size_pct)
-                .unwrap_or(dec!(2.0)),
-            daily_drawdown_limit_pct: Decimal::from_f64_retain(config.risk.daily_drawdown_limit_pct)
-                .unwrap_or(dec!(8.0)),
-            max_correlation: Decimal::from_f64_retain(config.risk.max_correlation)
-                .unwrap_or(dec!(0.6)),
-            min_market_liquidity: Decimal::from_f64_retain(config.risk.min_market_liquidity)
-                .unwrap_or(dec!(5000.0)),
+            max_position_size_pct: numeric::to_decimal(config.risk.max_position_size_pct)
+                .context("risk.max_position_size_pct")?,
+            daily_drawdown_limit_pct: numeric::to_decimal(config.risk.daily_drawdown_limit_pct)
+                .context("risk.daily_drawdown_limit_pct")?,
+            max_correlation: numeric::to_decimal(config.risk.max_correlation)
+                .context("risk.max_correlation")?,
+            min_market_liquidity: numeric::to_decimal(config.risk.min_market_liquidity)
+                .context("risk.min_market_liquidity")?,
             max_daily_trades: config.risk.max_daily_trades,
             cooldown_after_losses: 3,
             cooldown_period_minutes: 60,
-            kelly_fraction: Decimal::from_f64_retain(config.risk.kelly_fraction)
-                .unwrap_or(dec!(0.5)),
+            kelly_fraction: numeric::to_decimal(config.risk.kelly_fraction)
+                .context("risk.kelly_fraction")?,
             min_edge_size: dec!(0.03),
+            min_executable_notional: numeric::to_decimal(config.risk.min_executable_notional)
+                .context("risk.min_executable_notional")?,
+            daily_execution_cost_budget: numeric::to_decimal(config.risk.daily_execution_cost_budget)
+                .context("risk.daily_execution_cost_budget")?,
+            min_trade_notional: numeric::to_decimal(config.risk.min_trade_notional)
+                .context("risk.min_trade_notional")?,
+            max_trade_notional: numeric::to_decimal(config.risk.max_trade_notional)
+                .context("risk.max_trade_notional")?,
         };
 
         let portfolio_tracker = Arc::new(RwLock::new(
-            PortfolioTracker::new(db_pool.clone(), config.risk.starting_capital).await?
+            PortfolioTracker::new(storage.clone(), &config.risk).await?
         ));
 
+        let mut market_notional = HashMap::new();
+        for (market_id, bounds) in &config.risk.market_notional {
+            let bound = |value: Option<f64>, key: &str| -> Result<Option<Decimal>> {
+                value.map(|v| {
+                    numeric::to_decimal(v)
+                        .with_context(|| format!("risk.market_notional.{}.{}", market_id, key))
+                }).transpose()
+            };
+            let min = bound(bounds.min, "min")?.unwrap_or(limits.min_trade_notional);
+            let max = bound(bounds.max, "max")?.unwrap_or(limits.max_trade_notional);
+            market_notional.insert(market_id.clone(), (min, max));
+        }
+
+        let depth = &config.risk.depth_sizing;
+        let depth_sizing = if depth.enabled {
+            let bps = numeric::to_decimal(depth.max_slippage_bps).context("risk.depth_sizing.max_slippage_bps")?;
+            Some(DepthSizing {
+                tolerance: bps / Decimal::from(10_000),
+                max_book_age: Duration::seconds(depth.max_book_age_secs as i64),
+            })
+        } else {
+            None
+        };
+
+        let weights = &config.risk.weights;
+        let weight_sizing = if weights.enabled {
+            Some(WeightSizing {
+                floor: numeric::to_decimal(weights.min_multiplier).context("risk.weights.min_multiplier")?,
+                unweighted: numeric::to_decimal(weights.unweighted_multiplier)
+                    .context("risk.weights.unweighted_multiplier")?,
+            })
+        } else {
+            None
+        };
+
+        let mut venue_drawdown_limits = HashMap::new();
+        for (venue, venue_limits) in config.risk.venue_limits() {
+            if let Some(pct) = venue_limits.daily_drawdown_limit_pct {
+                let pct = numeric::to_decimal(pct)
+                    .with_context(|| format!("risk.venues.{}.daily_drawdown_limit_pct", venue))?;
+                venue_drawdown_limits.insert(venue, pct);
+            }
+        }
+
         Ok(Self {
-            db_pool,
+            storage,
             limits,
+            venue_drawdown_limits,
+            market_notional,
+            depth_sizing,
+            escalation: EscalationPolicy::from_config(&config.risk.escalation)?,
+            weight_sizing,
+            strategy_weights: Arc::new(Mutex::new(None)),
             portfolio_tracker,
+            cost_alert_day: Arc::new(Mutex::new(None)),
+            events,
+            notifier,
         })
     }
 
-    /// Validate if a signal passes all risk checks
-    pub async fn validate_signal(&self, signal: &Signal) -> Result<bool> {
+    /// Validate if a signal to be executed on `venue` passes all risk checks
+    pub async fn validate_signal(&self, signal: &Signal, venue: &str) -> Result<bool> {
         // Check if circuit breaker is active
         if self.is_circuit_breaker_active().await? {
             warn!("⚠️ Circuit breaker active - rejecting signal");
@@ -60,6 +163,41 @@ impl RiskManager {
             return Ok(false);
         }
 
+        // An edge that only exists at the mid is the spread, not mispricing
+        if signal.fair_value.edge_over(signal.current_price) <= Decimal::ZERO {
+            warn!(
+                "⚠️ Signal {} has no edge at {} (quoted edge {})",
+                signal.signal_id, signal.current_price.value(), signal.quoted_edge
+            );
+            return Ok(false);
+        }
+
+        // Top of book too thin to be worth crossing
+        if let Some(size) = signal.executable_size {
+            let notional = size * signal.current_price.value();
+            if notional < self.limits.min_executable_notional {
+                warn!("⚠️ Only ${} offered at {} for signal {}", notional, signal.current_price.value(), signal.signal_id);
+                return Ok(false);
+            }
+        }
+
+        // Gas and fees runaway, e.g. resubmitting through RPC trouble
+        if self.execution_budget_exhausted().await? {
+            return Ok(false);
+        }
+
+        // Throttled strategies trade fewer signals a day
+        if let Some(throttle) = self.strategy_throttle(signal.strategy).await? {
+            let traded = self.storage.count_strategy_trades_today(signal.strategy).await?;
+            if traded >= throttle.max_daily_signals {
+                warn!(
+                    "⚠️ Strategy {} is throttled to {} signals/day",
+                    signal.strategy.as_str(), throttle.max_daily_signals
+                );
+                return Ok(false);
+            }
+        }
+
         // Check daily trade limit
         let portfolio = self.portfolio_tracker.read().await;
         if portfolio.get_state().trades_today >= self.limits.max_daily_trades {
@@ -67,97 +205,280 @@ impl RiskManager {
             return Ok(false);
         }
 
-        // Check daily drawdown
+        Ok(!self.drawdown_limited(&portfolio, venue))
+    }
+
+    /// Whether the next child of a working parent order may buy `notional`
+    ///
+    /// The parent's signal was validated once, when the parent was created;
+    /// breakers, the cost budget and drawdown limits may have tripped since,
+    /// and other trades may have taken the capital. `parent` is what the
+    /// parent has bought so far and `reserved` what it still holds back.
+    pub async fn validate_child_order(&self, venue: &str, notional: Decimal, parent: Decimal, reserved: Decimal) -> Result<bool> {
+        if self.is_circuit_breaker_active().await? {
+            warn!("⚠️ Circuit breaker active - holding child order");
+            return Ok(false);
+        }
+        if self.execution_budget_exhausted().await? {
+            return Ok(false);
+        }
+
+        self.refresh_portfolio().await?;
+        let portfolio = self.portfolio_tracker.read().await;
+        if self.drawdown_limited(&portfolio, venue) {
+            return Ok(false);
+        }
+
+        // The parent's own reservation is already held back from available capital
+        let state = portfolio.get_state();
+        if notional > state.available_capital + reserved {
+            warn!("⚠️ Child order of ${} is over the ${} available", notional, state.available_capital + reserved);
+            return Ok(false);
+        }
+        let max_position = state.total_capital * self.limits.max_position_size_pct / dec!(100);
+        if parent + notional > max_position {
+            warn!("⚠️ Child order would take the position to ${}, over the ${} limit", parent + notional, max_position);
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+
+    /// Whether the portfolio, or `venue` on its own, is at its daily drawdown limit
+    fn drawdown_limited(&self, portfolio: &PortfolioTracker, venue: &str) -> bool {
         let state = portfolio.get_state();
         if state.daily_drawdown >= self.limits.daily_drawdown_limit_pct {
             warn!("⚠️ Daily drawdown limit reached: {:.2}%", state.daily_drawdown);
+            return true;
+        }
+
+        // A venue over its own limit halts only that venue
+        if let Some(venue_state) = portfolio.venue_state(venue) {
+            let limit = self.venue_drawdown_limit(venue);
+            if venue_state.daily_drawdown >= limit {
+                warn!("⚠️ {} daily drawdown limit reached: {:.2}%", venue, venue_state.daily_drawdown);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Whether today's gas and fees have reached the budget, alerting once a day
+    ///
+    /// Only entries pass through `validate_signal`, so exits continue.
+    async fn execution_budget_exhausted(&self) -> Result<bool> {
+        let costs = self.storage.execution_costs_today().await?;
+        if costs < self.limits.daily_execution_cost_budget {
             return Ok(false);
         }
 
+        let today = Utc::now().date_naive();
+        let mut alerted = self.cost_alert_day.lock().unwrap();
+        if *alerted != Some(today) {
+            *alerted = Some(today);
+            let message = format!(
+                "🚨 Execution costs today ${} exceed budget ${} - blocking new entries",
+                costs, self.limits.daily_execution_cost_budget
+            );
+            error!("{}", message);
+            self.notifier.critical(message);
+        } else {
+            warn!("⚠️ Execution cost budget exhausted - rejecting signal");
+        }
+
         Ok(true)
     }
 
+    async fn strategy_throttle(&self, strategy: Strategy) -> Result<Option<StrategyThrottle>> {
+        Ok(self.storage.fetch_strategy_throttles().await?
+            .into_iter()
+            .find(|t| t.strategy == strategy))
+    }
+
+    /// A strategy's Sharpe-optimal weight over the largest weight; strategies
+    /// without a weight trade at full size
+    async fn strategy_weight_multiplier(&self, strategy: Strategy) -> Result<Decimal> {
+        let Some(sizing) = self.weight_sizing else {
+            return Ok(Decimal::ONE);
+        };
+        let weights = self.strategy_weights().await?;
+        // Before the first solve every strategy is as unproven as the next
+        let largest = weights.iter().map(|w| w.weight).max().unwrap_or_default();
+        if largest <= Decimal::ZERO {
+            return Ok(Decimal::ONE);
+        }
+
+        Ok(weights.iter()
+            .find(|w| w.strategy == strategy)
+            .map_or(sizing.unweighted, |w| (w.weight / largest).max(sizing.floor)))
+    }
+
+    /// Strategy weights, read again once `WEIGHTS_REFRESH_SECS` old
+    async fn strategy_weights(&self) -> Result<Vec<StrategyWeight>> {
+        let now = Utc::now();
+        if let Some((read_at, weights)) = self.strategy_weights.lock().unwrap().as_ref() {
+            if now - *read_at < Duration::seconds(WEIGHTS_REFRESH_SECS) {
+                return Ok(weights.clone());
+            }
+        }
+
+        let weights = self.storage.fetch_strategy_weights().await?;
+        *self.strategy_weights.lock().unwrap() = Some((now, weights.clone()));
+        Ok(weights)
+    }
+
+    /// (min, max) USDC notional of a trade in `market_id`
+    fn notional_bounds(&self, market_id: &str) -> (Decimal, Decimal) {
+        self.market_notional
+            .get(market_id)
+            .copied()
+            .unwrap_or((self.limits.min_trade_notional, self.limits.max_trade_notional))
+    }
+
+    fn venue_drawdown_limit(&self, venue: &str) -> Decimal {
+        self.venue_drawdown_limits
+            .get(venue)
+            .copied()
+            .unwrap_or(self.limits.daily_drawdown_limit_pct)
+    }
+
     /// Calculate optimal position size using Kelly Criterion with risk limits
     pub async fn calculate_position_size(&self, signal: &Signal) -> Result<Decimal> {
+        // Pick up realized gains and flows since the last rebase
+        if self.portfolio_tracker.read().await.rebase_due() {
+            self.portfolio_tracker.write().await.refresh_state().await?;
+        }
+
         let portfolio = self.portfolio_tracker.read().await;
         let state = portfolio.get_state();
+        // Kelly stakes scale with the sizing bankroll rather than the latest capital figure
+        let bankroll = PortfolioState { total_capital: portfolio.sizing_capital(), ..state.clone() };
 
-        // Calculate position size using Kelly Criterion
-        let win_probability = signal.fair_value;
-        let edge = signal.edge_size;
-
-        let position_size = state.calculate_position_size(
-            edge,
-            win_probability,
+        // Calculate position size using Kelly Criterion, staged up for a large
+        // edge that has held into the event start
+        let escalation = self.escalation_multiplier(signal).await?;
+        let mut position_size = kelly_stake(
+            &bankroll,
+            signal,
             self.limits.kelly_fraction,
             self.limits.max_position_size_pct,
+            escalation,
         );
 
+        // Scaled down while the strategy's realized edge is negative
+        if let Some(throttle) = self.strategy_throttle(signal.strategy).await? {
+            position_size *= throttle.size_multiplier;
+        }
+
+        // Scaled by the strategy's capital weight relative to the largest
+        position_size *= self.strategy_weight_multiplier(signal.strategy).await?;
+
         // Ensure we have enough available capital
         let max_available = state.available_capital * dec!(0.95); // Keep 5% buffer
-        let final_size = position_size.min(max_available);
+        let fillable = self.fillable_notional(signal).await?;
+        let (min_notional, max_notional) = self.notional_bounds(&signal.market_id);
+        let final_size = bound_notional(
+            RoundingPolicy::Usd.apply(position_size.min(max_available).min(fillable.unwrap_or(Decimal::MAX))),
+            min_notional,
+            max_notional,
+        );
 
         info!(
-            "💰 Position sizing: Kelly={:.2}, Max={:.2}, Final={:.2}",
-            position_size, max_available, final_size
+            "💰 Position sizing: Bankroll={:.2}, Kelly={:.2}, Max={:.2}, Fillable={}, Final={:.2}",
+            bankroll.total_capital,
+            position_size,
+            max_available,
+            fillable.map_or_else(|| "-".to_string(), |f| format!("{:.2}", f)),
+            final_size
         );
 
         Ok(final_size)
     }
 
+    /// Multiplier of the escalation ladder rung `signal` has reached, one
+    /// when escalation is off or the signal does not qualify
+    async fn escalation_multiplier(&self, signal: &Signal) -> Result<Decimal> {
+        let Some(policy) = &self.escalation else {
+            return Ok(Decimal::ONE);
+        };
+        if !policy.applies_to(signal) {
+            return Ok(Decimal::ONE);
+        }
+        let Some(market) = self.storage.fetch_market(&signal.market_id).await? else {
+            return Ok(Decimal::ONE);
+        };
+
+        let history = self.storage.fetch_market_signals(&signal.market_id, policy.history_since(signal)).await?;
+        let odds = self.storage.fetch_bookmaker_odds(&signal.market_id).await?;
+        let Some(escalation) = policy.escalation(signal, &market, &history, &odds, Utc::now()) else {
+            return Ok(Decimal::ONE);
+        };
+
+        info!(
+            "📈 Escalating {} to stage {} (x{}): edge {:.3} held {}s, confirmed by {} bookmakers",
+            signal.signal_id,
+            escalation.stage,
+            escalation.multiplier,
+            signal.edge_size,
+            escalation.persisted.num_seconds(),
+            escalation.sources
+        );
+        Ok(escalation.multiplier)
+    }
+
+    /// USDC the market's stored book offers within the depth tolerance of
+    /// its best offer; `None` when depth sizing is off or the book is
+    /// missing or stale
+    async fn fillable_notional(&self, signal: &Signal) -> Result<Option<Decimal>> {
+        let Some(depth) = self.depth_sizing else {
+            return Ok(None);
+        };
+        let Some(book) = self.storage.fetch_book_snapshot(&signal.market_id).await? else {
+            debug!("No order book stored for {}, stake not capped by depth", signal.market_id);
+            return Ok(None);
+        };
+        if Utc::now() - book.captured_at > depth.max_book_age {
+            debug!("Order book for {} is from {}, stake not capped by depth", signal.market_id, book.captured_at);
+            return Ok(None);
+        }
+
+        Ok(Some(book.fillable_notional(signal.signal_type.to_position(), depth.tolerance)))
+    }
+
     /// Check if any circuit breakers are active
     async fn is_circuit_breaker_active(&self) -> Result<bool> {
-        let result = sqlx::query!(
-            r#"
-            SELECT COUNT(*) as count
-            FROM circuit_breakers
-            WHERE status = 'active'
-            "#
-        )
-        .fetch_one(&self.db_pool)
-        .await?;
+        let count = self.storage.count_active_circuit_breakers().await?;
 
-        Ok(result.count.unwrap_or(0) > 0)
+        Ok(count > 0)
     }
 
     /// Trigger circuit breaker
     pub async fn trigger_circuit_breaker(&self, reason: String) -> Result<()> {
+        // Operators are alerted by EventAlerts from the published event
         warn!("🚨 CIRCUIT BREAKER TRIGGERED: {}", reason);
 
-        sqlx::query!(
-            r#"
-            INSERT INTO circuit_breakers (reason, metadata)
-            VALUES ($1, $2)
-            "#,
-            reason,
+        let triggered_at = chrono::Utc::now();
+        self.storage.insert_circuit_breaker(
+            &reason,
             serde_json::json!({
-                "timestamp": chrono::Utc::now(),
-            })
-        )
-        .execute(&self.db_pool)
-        .await?;
+                "timestamp": triggered_at,
+            }),
+        ).await?;
+        self.events.publish(Event::BreakerTriggered { reason, triggered_at }).await;
 
         Ok(())
     }
 
     /// Check for consecutive losses and trigger cooldown if needed
     pub async fn check_consecutive_losses(&self) -> Result<()> {
-        let recent_trades = sqlx::query!(
-            r#"
-            SELECT pnl
-            FROM trades
-            WHERE status = 'closed'
-                AND entry_time > NOW() - INTERVAL '1 hour'
-            ORDER BY entry_time DESC
-            LIMIT 5
-            "#
-        )
-        .fetch_all(&self.db_pool)
-        .await?;
+        let recent_pnls = self.storage
+            .recent_closed_pnls(chrono::Utc::now() - chrono::Duration::hours(1), 5)
+            .await?;
 
         let mut consecutive_losses = 0;
-        for trade in recent_trades {
-            if let Some(pnl) = trade.pnl {
+        for pnl in recent_pnls {
+            if let Some(pnl) = pnl {
                 if pnl < dec!(0.0) {
                     consecutive_losses += 1;
                 } else {
@@ -177,7 +498,7 @@ impl RiskManager {
     }
 
     /// Update portfolio state after a trade
-    pub async fn update_portfolio(&self, trade_pnl: Decimal) -> Result<()> {
+    pub async fn update_portfolio(&self, trade_pnl: Usdc) -> Result<()> {
         let mut portfolio = self.portfolio_tracker.write().await;
         portfolio.update_pnl(trade_pnl).await?;
         
@@ -193,9 +514,245 @@ impl RiskManager {
         Ok(())
     }
 
+    /// Recompute portfolio state, e.g. after trades recorded outside the engine
+    pub async fn refresh_portfolio(&self) -> Result<()> {
+        self.portfolio_tracker.write().await.refresh_state().await
+    }
+
+    /// Capital positions are currently sized against
+    pub async fn sizing_bankroll(&self) -> Decimal {
+        self.portfolio_tracker.read().await.sizing_capital()
+    }
+
+    /// Fraction of full Kelly positions are sized at
+    pub fn kelly_fraction(&self) -> Decimal {
+        self.limits.kelly_fraction
+    }
+
     /// Get current portfolio state
     pub async fn get_portfolio_state(&self) -> PortfolioState {
         let portfolio = self.portfolio_tracker.read().await;
         portfolio.get_state().clone()
     }
 }
+
+/// A stake capped at `max`, or zero when it falls short of `min`
+fn bound_notional(size: Decimal, min: Decimal, max: Decimal) -> Decimal {
+    let size = size.min(max);
+    if size < min {
+        Decimal::ZERO
+    } else {
+        size
+    }
+}
+
+/// Fractional Kelly stake for `signal` on `bankroll`, with the escalation
+/// multiplier applied before the max position cap so it can never exceed it
+fn kelly_stake(
+    bankroll: &PortfolioState,
+    signal: &Signal,
+    kelly_fraction: Decimal,
+    max_position_size_pct: Decimal,
+    escalation: Decimal,
+) -> Decimal {
+    bankroll.calculate_position_size(
+        signal.edge_size,
+        signal.fair_value.value(),
+        kelly_fraction * escalation,
+        max_position_size_pct,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+    use crate::cache::InProcessCache;
+    use crate::config::VenueRiskConfig;
+    use crate::storage::{MemoryStorage, SignalRepo, TradeRepo};
+    use crate::test_support::{market_fixture, signal_fixture, test_config, trade_fixture};
+    use crate::types::{EntryOrder, ParentOrder, Position, Price, RestingOrderStatus, SliceMode, Trade, POLYMARKET_VENUE};
+
+    #[test]
+    fn stakes_are_capped_and_tiny_ones_skipped() {
+        assert_eq!(bound_notional(dec!(4000), dec!(25), dec!(2500)), dec!(2500));
+        assert_eq!(bound_notional(dec!(400), dec!(25), dec!(2500)), dec!(400));
+        assert_eq!(bound_notional(dec!(20), dec!(25), dec!(2500)), dec!(0));
+    }
+
+    #[test]
+    fn escalation_stays_under_the_position_cap() {
+        let bankroll = PortfolioState {
+            total_capital: dec!(100000),
+            available_capital: dec!(100000),
+            invested_capital: dec!(0),
+            settlement_pending: dec!(0),
+            unrealized_pnl: dec!(0),
+            realized_pnl_today: dec!(0),
+            daily_drawdown: dec!(0),
+            max_drawdown: dec!(0),
+            open_positions: 0,
+            trades_today: 0,
+            timestamp: Utc::now(),
+        };
+        // Half Kelly on a 0.51 fair value at 0.50 stakes 1% of the bankroll
+        let signal = signal_fixture(&market_fixture("0xescalated", dec!(0.50)), dec!(0.51));
+
+        assert_eq!(kelly_stake(&bankroll, &signal, dec!(0.5), dec!(2.0), dec!(1)), dec!(1000));
+        // A 3x rung would stake 3%, but the 2% cap still holds
+        assert_eq!(kelly_stake(&bankroll, &signal, dec!(0.5), dec!(2.0), dec!(3)), dec!(2000));
+    }
+
+    #[tokio::test]
+    async fn child_orders_are_held_by_breakers_limits_and_other_reservations() {
+        let config = test_config("sqlite::memory:");
+        let storage = Arc::new(MemoryStorage::new());
+        let events = EventBus::new(Arc::new(InProcessCache::new(100)));
+        let risk = RiskManager::new(storage.clone(), events, &config, Notifier::detached()).await.unwrap();
+        let child = |notional, filled, reserved| risk.validate_child_order(POLYMARKET_VENUE, notional, filled, reserved);
+
+        // 2% of the $50k bankroll per position
+        assert!(child(dec!(500), dec!(0), dec!(0)).await.unwrap());
+        assert!(!child(dec!(500), dec!(600), dec!(0)).await.unwrap());
+
+        // Another parent's unfilled notional is held back from this one
+        let now = Utc::now();
+        storage.insert_parent_order(&ParentOrder {
+            parent_id: Uuid::new_v4(),
+            signal_id: Uuid::new_v4(),
+            market_id: "0xworking".to_string(),
+            position: Position::Yes,
+            mode: SliceMode::Twap,
+            target_notional: dec!(50000),
+            limit_price: dec!(0.45),
+            slices: 4,
+            children_sent: 1,
+            filled_quantity: dec!(1000),
+            filled_notional: dec!(400),
+            status: RestingOrderStatus::Open,
+            next_slice_at: now,
+            created_at: now,
+            expires_at: now + Duration::hours(1),
+        }).await.unwrap();
+        assert!(!child(dec!(500), dec!(0), dec!(0)).await.unwrap());
+        assert_eq!(risk.get_portfolio_state().await.available_capital, dec!(400));
+        assert!(child(dec!(500), dec!(400), dec!(49600)).await.unwrap());
+
+        // A breaker tripped since the parent was created stops its children
+        risk.trigger_circuit_breaker("test".to_string()).await.unwrap();
+        assert!(!child(dec!(500), dec!(400), dec!(49600)).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn strategy_weights_are_floored_and_unweighted_strategies_sized_down() {
+        let config = test_config("sqlite::memory:");
+        let storage = Arc::new(MemoryStorage::new());
+        let events = EventBus::new(Arc::new(InProcessCache::new(100)));
+        let risk = RiskManager::new(storage.clone(), events, &config, Notifier::detached()).await.unwrap();
+
+        // Nothing solved yet
+        assert_eq!(risk.strategy_weight_multiplier(Strategy::ClvArbitrage).await.unwrap(), Decimal::ONE);
+
+        let weight = |strategy, weight| StrategyWeight {
+            strategy,
+            weight,
+            mean_return: dec!(0.001),
+            volatility: dec!(0.01),
+            updated_at: Utc::now(),
+        };
+        storage.replace_strategy_weights(&[
+            weight(Strategy::ClvArbitrage, dec!(0.8)),
+            weight(Strategy::PoissonExpectedValue, dec!(0.2)),
+            weight(Strategy::Middling, dec!(0)),
+        ]).await.unwrap();
+        // Drop the empty read cached above, as if it had aged out
+        *risk.strategy_weights.lock().unwrap() = None;
+
+        assert_eq!(risk.strategy_weight_multiplier(Strategy::ClvArbitrage).await.unwrap(), dec!(1));
+        assert_eq!(risk.strategy_weight_multiplier(Strategy::PoissonExpectedValue).await.unwrap(), dec!(0.25));
+        assert_eq!(risk.strategy_weight_multiplier(Strategy::Middling).await.unwrap(), dec!(0.1));
+        assert_eq!(risk.strategy_weight_multiplier(Strategy::SeasonFutures).await.unwrap(), dec!(0.25));
+
+        // Served from the cache until it ages out
+        storage.replace_strategy_weights(&[]).await.unwrap();
+        assert_eq!(risk.strategy_weight_multiplier(Strategy::Middling).await.unwrap(), dec!(0.1));
+    }
+
+    #[tokio::test]
+    async fn resting_entry_bids_are_held_back_until_they_close() {
+        let config = test_config("sqlite::memory:");
+        let storage = Arc::new(MemoryStorage::new());
+        let events = EventBus::new(Arc::new(InProcessCache::new(100)));
+        let risk = RiskManager::new(storage.clone(), events, &config, Notifier::detached()).await.unwrap();
+
+        let now = Utc::now();
+        let bid = |order_id: &str| EntryOrder {
+            order_id: order_id.to_string(),
+            signal_id: Uuid::new_v4(),
+            market_id: "0xresting".to_string(),
+            position: Position::Yes,
+            price: dec!(0.40),
+            quantity: dec!(2500),
+            gas_cost: dec!(0.01),
+            placed_at: now,
+            expires_at: now + Duration::minutes(15),
+        };
+        storage.insert_entry_order(&bid("0xfirst")).await.unwrap();
+        storage.insert_entry_order(&bid("0xsecond")).await.unwrap();
+        risk.refresh_portfolio().await.unwrap();
+        assert_eq!(risk.get_portfolio_state().await.available_capital, dec!(48000));
+
+        storage.set_entry_order_status("0xfirst", RestingOrderStatus::Cancelled).await.unwrap();
+        risk.refresh_portfolio().await.unwrap();
+        assert_eq!(risk.get_portfolio_state().await.available_capital, dec!(49000));
+    }
+
+    #[tokio::test]
+    async fn a_venue_over_its_own_drawdown_limit_halts_only_that_venue() {
+        let mut config = test_config("sqlite::memory:");
+        config.risk.venues = HashMap::from([
+            (POLYMARKET_VENUE.to_string(), VenueRiskConfig { starting_capital: 40000.0, daily_drawdown_limit_pct: None }),
+            ("kalshi".to_string(), VenueRiskConfig { starting_capital: 10000.0, daily_drawdown_limit_pct: Some(5.0) }),
+        ]);
+        let storage = Arc::new(MemoryStorage::new());
+        let events = EventBus::new(Arc::new(InProcessCache::new(100)));
+        let risk = RiskManager::new(storage.clone(), events, &config, Notifier::detached()).await.unwrap();
+        let signal = signal_fixture(&market_fixture("0xvenues", dec!(0.40)), dec!(0.50));
+        assert!(risk.validate_signal(&signal, "kalshi").await.unwrap());
+
+        // $1,000 lost today is 10% of Kalshi's $10k but 2% of the whole book
+        let trade = Trade { venue: "kalshi".to_string(), ..trade_fixture("0xvenues", dec!(0.50), dec!(4000)) };
+        storage.insert_trade(&trade).await.unwrap();
+        storage.close_trade(trade.trade_id, dec!(0.25), Utc::now(), Usdc::new(dec!(-1000)), "0xexit").await.unwrap();
+        risk.refresh_portfolio().await.unwrap();
+
+        let portfolio = risk.portfolio_tracker.read().await;
+        let kalshi = portfolio.venue_state("kalshi").unwrap();
+        assert_eq!((kalshi.total_capital, kalshi.daily_drawdown), (dec!(9000), dec!(10)));
+        assert_eq!(portfolio.venue_state(POLYMARKET_VENUE).unwrap().daily_drawdown, dec!(0));
+        drop(portfolio);
+
+        assert!(!risk.validate_signal(&signal, "kalshi").await.unwrap());
+        assert!(risk.validate_signal(&signal, POLYMARKET_VENUE).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn signals_without_an_edge_at_the_ask_or_size_behind_it_are_rejected() {
+        let config = test_config("sqlite::memory:");
+        let storage = Arc::new(MemoryStorage::new());
+        let events = EventBus::new(Arc::new(InProcessCache::new(100)));
+        let risk = RiskManager::new(storage, events, &config, Notifier::detached()).await.unwrap();
+        let signal = signal_fixture(&market_fixture("0xexecutable", dec!(0.40)), dec!(0.50));
+        assert!(risk.validate_signal(&signal, POLYMARKET_VENUE).await.unwrap());
+
+        // Quoted at the mid, but the fair value is under the ask
+        let at_mid = Signal { current_price: Price::new(dec!(0.52)).unwrap(), ..signal.clone() };
+        assert!(!risk.validate_signal(&at_mid, POLYMARKET_VENUE).await.unwrap());
+
+        // $40 offered at the ask is under the $100 minimum; $400 is not
+        let thin = Signal { executable_size: Some(dec!(100)), ..signal.clone() };
+        assert!(!risk.validate_signal(&thin, POLYMARKET_VENUE).await.unwrap());
+        let deep = Signal { executable_size: Some(dec!(1000)), ..signal };
+        assert!(risk.validate_signal(&deep, POLYMARKET_VENUE).await.unwrap());
+    }
+}
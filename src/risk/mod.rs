@@ -1,5 +1,39 @@
+mod calibration;
+mod consistency;
+mod convergence;
+mod drift;
+mod edge_decay;
+mod escalation;
+mod excursions;
+mod exposure;
+mod funding;
+mod kelly;
 mod manager;
 mod portfolio;
+mod positions;
+mod returns;
+mod scenarios;
+mod settlement;
+mod shortfall;
+mod throttle;
+mod weights;
 
+pub use calibration::{calibration_report, StrategyCalibration};
+pub use consistency::{ladder_rung, scan_consistency, ConsistencyMonitor, ConsistencyThresholds, Inconsistency, Ladder, Rung};
+pub use convergence::{convergence_summary, ConvergenceSummary, ConvergenceTracker};
+pub use drift::DriftMonitor;
+pub use edge_decay::EdgeDecayEstimator;
+pub use escalation::EscalationPolicy;
+pub use excursions::{excursion_report, StrategyExcursions};
+pub use exposure::{current_exposure, ExposureLimits, ExposureReport};
+pub use funding::FundingMonitor;
+pub use kelly::{kelly_realization, KellyReport};
 pub use manager::RiskManager;
 pub use portfolio::PortfolioTracker;
+pub use positions::PositionMonitor;
+pub use returns::{returns_report, ReturnsReport};
+pub use scenarios::{ScenarioAnalyzer, ScenarioReport, ScenarioRequest};
+pub use settlement::SettlementMonitor;
+pub use shortfall::{shortfall_report, ShortfallReport};
+pub use throttle::StrategyThrottler;
+pub use weights::WeightOptimizer;
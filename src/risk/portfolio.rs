@@ -1,25 +1,53 @@
-use anyhow::Result;
-use sqlx::PgPool;
+use anyhow::{Context, Result};
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::info;
 
-use crate::types::PortfolioState;
+use crate::config::RiskConfig;
+use crate::numeric;
+use crate::storage::{Storage, VenueBalances};
+use crate::types::{PortfolioState, Usdc, VenueState};
 
 pub struct PortfolioTracker {
-    db_pool: PgPool,
+    storage: Arc<dyn Storage>,
+    starting_capital: Decimal,
     state: PortfolioState,
+    /// Units outstanding; deposits and withdrawals issue or redeem units at
+    /// the current unit value so they do not register as gains or losses
+    units: Decimal,
+    peak_unit_value: Decimal,
+    /// Net cash flows already reflected in `units`
+    net_cash_flows: Decimal,
+    /// Starting capital allocated to each configured venue
+    venue_capital: HashMap<String, Decimal>,
+    venues: HashMap<String, VenueState>,
+    /// Bankroll positions are sized from; follows total capital down at once
+    /// and up once per `rebase_interval`
+    sizing_capital: Decimal,
+    rebase_interval: Duration,
+    rebased_at: Option<DateTime<Utc>>,
 }
 
 impl PortfolioTracker {
-    pub async fn new(db_pool: PgPool, starting_capital: f64) -> Result<Self> {
-        let starting_capital = Decimal::from_f64_retain(starting_capital)
-            .unwrap_or(dec!(50000.0));
+    pub async fn new(storage: Arc<dyn Storage>, config: &RiskConfig) -> Result<Self> {
+        let starting_capital = numeric::to_decimal(config.starting_capital)
+            .context("risk.starting_capital")?;
+
+        let mut venue_capital = HashMap::new();
+        for (venue, limits) in config.venue_limits() {
+            let capital = numeric::to_decimal(limits.starting_capital)
+                .with_context(|| format!("risk.venues.{}.starting_capital", venue))?;
+            venue_capital.insert(venue, capital);
+        }
 
         let state = PortfolioState {
             total_capital: starting_capital,
             available_capital: starting_capital,
             invested_capital: dec!(0.0),
+            settlement_pending: dec!(0.0),
             unrealized_pnl: dec!(0.0),
             realized_pnl_today: dec!(0.0),
             daily_drawdown: dec!(0.0),
@@ -31,8 +59,17 @@ impl PortfolioTracker {
 
         // Store initial state
         let mut tracker = Self {
-            db_pool,
+            storage,
+            starting_capital,
             state,
+            units: dec!(0.0),
+            peak_unit_value: dec!(1.0),
+            net_cash_flows: dec!(0.0),
+            venue_capital,
+            venues: HashMap::new(),
+            sizing_capital: starting_capital,
+            rebase_interval: Duration::seconds(config.sizing_rebase_interval_secs as i64),
+            rebased_at: None,
         };
         
         tracker.refresh_state().await?;
@@ -43,74 +80,49 @@ impl PortfolioTracker {
     /// Refresh portfolio state from database
     pub async fn refresh_state(&mut self) -> Result<()> {
         // Calculate current state from database
-        let result = sqlx::query!(
-            r#"
-            SELECT * FROM calculate_portfolio_state()
-            "#
-        )
-        .fetch_one(&self.db_pool)
-        .await?;
-
-        let total_capital = result.total_capital.unwrap_or(dec!(50000.0));
-        let available_capital = result.available_capital.unwrap_or(total_capital);
-        let invested_capital = result.invested_capital.unwrap_or(dec!(0.0));
-        let unrealized_pnl = result.unrealized_pnl.unwrap_or(dec!(0.0));
+        let balances = self.storage
+            .calculate_portfolio_balances(self.starting_capital)
+            .await?;
+
+        let total_capital = balances.total_capital;
+        // Orders still working may yet buy this much
+        let available_capital = balances.available_capital - self.reserved_notional().await?;
+        let invested_capital = balances.invested_capital;
+        let unrealized_pnl = balances.unrealized_pnl;
 
         // Get today's realized PnL
-        let today_pnl = sqlx::query!(
-            r#"
-            SELECT COALESCE(SUM(pnl), 0) as total_pnl
-            FROM trades
-            WHERE status = 'closed'
-                AND DATE(exit_time) = CURRENT_DATE
-            "#
-        )
-        .fetch_one(&self.db_pool)
-        .await?;
-
-        let realized_pnl_today = today_pnl.total_pnl.unwrap_or(dec!(0.0));
+        let realized_pnl_today = self.storage.realized_pnl_today().await?;
 
         // Get open positions count
-        let positions = sqlx::query!(
-            r#"
-            SELECT COUNT(*) as count
-            FROM trades
-            WHERE status = 'open'
-            "#
-        )
-        .fetch_one(&self.db_pool)
-        .await?;
-
-        let open_positions = positions.count.unwrap_or(0) as i32;
+        let open_positions = self.storage.count_open_trades().await? as i32;
 
         // Get trades today count
-        let trades = sqlx::query!(
-            r#"
-            SELECT COUNT(*) as count
-            FROM trades
-            WHERE DATE(entry_time) = CURRENT_DATE
-            "#
-        )
-        .fetch_one(&self.db_pool)
-        .await?;
-
-        let trades_today = trades.count.unwrap_or(0) as i32;
-
-        // Calculate daily drawdown
-        let daily_drawdown = if total_capital > dec!(0.0) {
-            (realized_pnl_today / total_capital) * dec!(-100.0)
+        let trades_today = self.storage.count_trades_today().await? as i32;
+
+        // Daily drawdown against capital before today's PnL (including
+        // any funds moved in or out today)
+        let day_base = total_capital - realized_pnl_today;
+        let daily_drawdown = if day_base > dec!(0.0) {
+            (realized_pnl_today / day_base) * dec!(-100.0)
         } else {
             dec!(0.0)
         }.max(dec!(0.0));
 
+        let equity = total_capital + unrealized_pnl;
+        let max_drawdown = self.track_unit_value(equity, balances.net_cash_flows);
+
+        self.refresh_venues().await?;
+        self.rebase(total_capital);
+
         self.state = PortfolioState {
             total_capital,
             available_capital,
             invested_capital,
+            settlement_pending: balances.settlement_pending,
             unrealized_pnl,
             realized_pnl_today,
             daily_drawdown,
-            max_drawdown: self.state.max_drawdown.max(daily_drawdown),
+            max_drawdown: self.state.max_drawdown.max(max_drawdown),
             open_positions,
             trades_today,
             timestamp: Utc::now(),
@@ -122,10 +134,111 @@ impl PortfolioTracker {
         Ok(())
     }
 
+    /// USDC that parent orders still working have left to buy, and that
+    /// resting entry bids would spend should they all fill
+    async fn reserved_notional(&self) -> Result<Decimal> {
+        let parents: Decimal = self.storage.fetch_open_parent_orders().await?
+            .iter()
+            .map(|parent| (parent.target_notional - parent.filled_notional).max(Decimal::ZERO))
+            .sum();
+        let entries: Decimal = self.storage.fetch_open_entry_orders().await?
+            .iter()
+            .map(|order| order.price * order.quantity)
+            .sum();
+        Ok(parents + entries)
+    }
+
+    /// Rebuild per-venue states; configured venues appear even before any activity
+    async fn refresh_venues(&mut self) -> Result<()> {
+        let mut balances: HashMap<String, VenueBalances> = self.storage
+            .calculate_venue_balances()
+            .await?
+            .into_iter()
+            .map(|b| (b.venue.clone(), b))
+            .collect();
+
+        for venue in self.venue_capital.keys() {
+            balances.entry(venue.clone())
+                .or_insert_with(|| VenueBalances::empty(venue.clone()));
+        }
+
+        self.venues = balances.into_values()
+            .map(|b| {
+                let starting = self.venue_capital.get(&b.venue).copied().unwrap_or_default();
+                (b.venue.clone(), venue_state(b, starting))
+            })
+            .collect();
+
+        Ok(())
+    }
+
+    /// Time-weighted drawdown (%) from the peak unit value
+    fn track_unit_value(&mut self, equity: Decimal, net_cash_flows: Decimal) -> Decimal {
+        if self.units <= dec!(0.0) {
+            // First refresh (or fully withdrawn): start a new series at 1.0
+            if equity <= dec!(0.0) {
+                return dec!(0.0);
+            }
+            self.units = equity;
+            self.peak_unit_value = dec!(1.0);
+            self.net_cash_flows = net_cash_flows;
+            return dec!(0.0);
+        }
+
+        // Price new flows at the unit value before they arrived
+        let new_flows = net_cash_flows - self.net_cash_flows;
+        if new_flows != dec!(0.0) {
+            let pre_flow_value = (equity - new_flows) / self.units;
+            if pre_flow_value > dec!(0.0) {
+                self.units += new_flows / pre_flow_value;
+            }
+            self.net_cash_flows = net_cash_flows;
+        }
+
+        if self.units <= dec!(0.0) {
+            return dec!(0.0);
+        }
+
+        let unit_value = equity / self.units;
+        self.peak_unit_value = self.peak_unit_value.max(unit_value);
+
+        ((self.peak_unit_value - unit_value) / self.peak_unit_value * dec!(100.0))
+            .max(dec!(0.0))
+    }
+
+    /// Move the sizing bankroll to `total_capital` if it has fallen or a rebase is due
+    fn rebase(&mut self, total_capital: Decimal) {
+        let now = Utc::now();
+        let due = self.rebase_due_at(now);
+        let next = next_sizing_capital(self.sizing_capital, total_capital, due);
+
+        if next != self.sizing_capital && self.rebased_at.is_some() {
+            info!("🏦 Sizing bankroll {} -> {}", self.sizing_capital, next);
+        }
+        if due {
+            self.rebased_at = Some(now);
+        }
+        self.sizing_capital = next;
+    }
+
+    fn rebase_due_at(&self, now: DateTime<Utc>) -> bool {
+        self.rebased_at.map_or(true, |at| now - at >= self.rebase_interval)
+    }
+
+    /// Whether the sizing bankroll is due to be raised to current capital
+    pub fn rebase_due(&self) -> bool {
+        self.rebase_due_at(Utc::now())
+    }
+
+    /// Capital positions are sized from
+    pub fn sizing_capital(&self) -> Decimal {
+        self.sizing_capital
+    }
+
     /// Update PnL after a trade
-    pub async fn update_pnl(&mut self, pnl: Decimal) -> Result<()> {
-        self.state.realized_pnl_today += pnl;
-        self.state.total_capital += pnl;
+    pub async fn update_pnl(&mut self, pnl: Usdc) -> Result<()> {
+        self.state.realized_pnl_today += pnl.value();
+        self.state.total_capital += pnl.value();
         self.refresh_state().await?;
         Ok(())
     }
@@ -135,29 +248,81 @@ impl PortfolioTracker {
         &self.state
     }
 
+    /// State of a single venue, `None` if it is neither configured nor traded
+    pub fn venue_state(&self, venue: &str) -> Option<&VenueState> {
+        self.venues.get(venue)
+    }
+
     /// Store portfolio snapshot to database
     async fn store_snapshot(&self) -> Result<()> {
-        sqlx::query!(
-            r#"
-            INSERT INTO portfolio_state (
-                total_capital, available_capital, invested_capital,
-                unrealized_pnl, realized_pnl_today, daily_drawdown,
-                max_drawdown, open_positions, trades_today
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
-            "#,
-            self.state.total_capital,
-            self.state.available_capital,
-            self.state.invested_capital,
-            self.state.unrealized_pnl,
-            self.state.realized_pnl_today,
-            self.state.daily_drawdown,
-            self.state.max_drawdown,
-            self.state.open_positions,
-            self.state.trades_today,
-        )
-        .execute(&self.db_pool)
-        .await?;
+        self.storage.insert_portfolio_snapshot(&self.state).await
+    }
+}
 
-        Ok(())
+/// Bankroll after a refresh: losses (and withdrawals) are followed at once so
+/// a shrinking bankroll is never over-bet, gains only when a rebase is due
+fn next_sizing_capital(current: Decimal, total_capital: Decimal, due: bool) -> Decimal {
+    if due || total_capital < current {
+        total_capital.max(dec!(0.0))
+    } else {
+        current
+    }
+}
+
+fn venue_state(balances: VenueBalances, starting_capital: Decimal) -> VenueState {
+    let total_capital = starting_capital + balances.net_cash_flows + balances.realized_pnl;
+
+    let day_base = total_capital - balances.realized_pnl_today;
+    let daily_drawdown = if day_base > dec!(0.0) {
+        (balances.realized_pnl_today / day_base) * dec!(-100.0)
+    } else {
+        dec!(0.0)
+    }.max(dec!(0.0));
+
+    VenueState {
+        venue: balances.venue,
+        total_capital,
+        available_capital: total_capital - balances.invested_capital,
+        invested_capital: balances.invested_capital,
+        unrealized_pnl: balances.unrealized_pnl,
+        realized_pnl_today: balances.realized_pnl_today,
+        daily_drawdown,
+        open_positions: balances.open_positions as i32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{MarketRepo, MemoryStorage, TradeRepo};
+    use crate::test_support::{market_fixture, test_config, trade_fixture};
+    use crate::types::{Market, MarketStatus};
+
+    #[tokio::test]
+    async fn ended_markets_stay_marked_while_their_capital_is_locked() {
+        let config = test_config("sqlite::memory:");
+        let storage = Arc::new(MemoryStorage::new());
+        storage.upsert_market(&market_fixture("0xended", dec!(0.50))).await.unwrap();
+        storage.insert_trade(&trade_fixture("0xended", dec!(0.40), dec!(10000))).await.unwrap();
+        let mut tracker = PortfolioTracker::new(storage.clone(), &config.risk).await.unwrap();
+        assert_eq!((tracker.get_state().invested_capital, tracker.get_state().unrealized_pnl), (dec!(4000), dec!(1000)));
+
+        // The game ends at the same price: the capital moves to pending, the mark stays
+        let ended = Market { status: MarketStatus::Closed, ..market_fixture("0xended", dec!(0.50)) };
+        storage.upsert_market(&ended).await.unwrap();
+        tracker.refresh_state().await.unwrap();
+        let state = tracker.get_state();
+        assert_eq!((state.invested_capital, state.settlement_pending), (dec!(0), dec!(4000)));
+        assert_eq!((state.available_capital, state.unrealized_pnl), (dec!(46000), dec!(1000)));
+        assert_eq!(state.max_drawdown, dec!(0));
+    }
+
+    #[test]
+    fn sizing_capital_falls_at_once_and_grows_on_rebase() {
+        // Doubled bankroll waits for the rebase
+        assert_eq!(next_sizing_capital(dec!(50000), dec!(100000), false), dec!(50000));
+        assert_eq!(next_sizing_capital(dec!(50000), dec!(100000), true), dec!(100000));
+        // Halved bankroll is followed straight away
+        assert_eq!(next_sizing_capital(dec!(50000), dec!(25000), false), dec!(25000));
     }
 }
@@ -0,0 +1,180 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use tokio::time::{interval, Duration};
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::monitoring::{Heartbeats, Notifier};
+use crate::numeric;
+use crate::storage::Storage;
+use crate::types::{Position, Trade, TradeMark};
+
+/// Marks every open trade to market and persists its unrealized PnL and
+/// max adverse and favorable excursions
+///
+/// Trades are marked at the bid for the shares held, the price an exit
+/// would actually get. A position whose loss passes the alert threshold
+/// alerts once, when it first crosses.
+pub struct PositionMonitor {
+    storage: Arc<dyn Storage>,
+    mark_interval: Duration,
+    /// Loss as a fraction of cost that alerts
+    loss_alert_fraction: Decimal,
+    alerted: Mutex<HashSet<Uuid>>,
+    notifier: Notifier,
+    heartbeats: Heartbeats,
+}
+
+impl PositionMonitor {
+    pub fn new(storage: Arc<dyn Storage>, config: &Config, notifier: Notifier, heartbeats: Heartbeats) -> Result<Self> {
+        let loss_alert_pct = numeric::to_decimal(config.risk.position_loss_alert_pct)
+            .context("risk.position_loss_alert_pct")?;
+
+        Ok(Self {
+            storage,
+            mark_interval: Duration::from_secs(config.risk.position_mark_interval_secs),
+            loss_alert_fraction: loss_alert_pct / Decimal::ONE_HUNDRED,
+            alerted: Mutex::new(HashSet::new()),
+            notifier,
+            heartbeats,
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let mut tick = interval(self.mark_interval);
+
+        info!("📍 Position monitor started");
+
+        loop {
+            tick.tick().await;
+            self.heartbeats.beat("position_monitor");
+
+            if let Err(e) = self.mark_open_positions().await {
+                error!("Error marking open positions: {}", e);
+            }
+        }
+    }
+
+    async fn mark_open_positions(&self) -> Result<()> {
+        let trades = self.storage.fetch_open_trades().await?;
+        let previous: HashMap<Uuid, TradeMark> = self.storage.fetch_trade_marks().await?
+            .into_iter()
+            .map(|m| (m.trade_id, m))
+            .collect();
+        let now = Utc::now();
+
+        for trade in &trades {
+            let (yes_quote, no_quote) = self.storage.fetch_market_quotes(&trade.market_id).await?;
+            let bid = match trade.position {
+                Position::Yes => yes_quote.bid,
+                Position::No => no_quote.bid,
+            };
+
+            let mark = mark(trade, bid.value(), previous.get(&trade.trade_id), now);
+            self.storage.upsert_trade_mark(&mark).await?;
+            self.check_loss(trade, &mark);
+        }
+
+        // Closed trades can no longer alert
+        let open: HashSet<Uuid> = trades.iter().map(|t| t.trade_id).collect();
+        self.alerted.lock().unwrap().retain(|id| open.contains(id));
+
+        Ok(())
+    }
+
+    fn check_loss(&self, trade: &Trade, mark: &TradeMark) {
+        let cost = trade.position_size_usd();
+        if cost <= Decimal::ZERO || -mark.unrealized_pnl < cost * self.loss_alert_fraction {
+            return;
+        }
+
+        if self.alerted.lock().unwrap().insert(trade.trade_id) {
+            let message = format!(
+                "🚨 Position {} in {} down ${} ({:.1}% of cost), marked at {}",
+                trade.trade_id,
+                trade.market_id,
+                -mark.unrealized_pnl,
+                -mark.unrealized_pnl / cost * Decimal::ONE_HUNDRED,
+                mark.mark_price
+            );
+            error!("{}", message);
+            self.notifier.critical(message);
+        }
+    }
+}
+
+/// Mark a trade at `price`, carrying the worst and best excursions forward
+fn mark(trade: &Trade, price: Decimal, previous: Option<&TradeMark>, now: DateTime<Utc>) -> TradeMark {
+    let unrealized_pnl = trade.unrealized_pnl(price);
+    let (worst_so_far, best_so_far) = previous.map_or((Decimal::ZERO, Decimal::ZERO), |m| {
+        (m.max_adverse_excursion, m.max_favorable_excursion)
+    });
+
+    TradeMark {
+        trade_id: trade.trade_id,
+        mark_price: price,
+        unrealized_pnl,
+        max_adverse_excursion: worst_so_far.min(unrealized_pnl),
+        max_favorable_excursion: best_so_far.max(unrealized_pnl),
+        marked_at: now,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Strategy, TradeOrigin, TradeStatus, POLYMARKET_VENUE};
+    use rust_decimal_macros::dec;
+
+    fn trade() -> Trade {
+        Trade {
+            trade_id: Uuid::new_v4(),
+            market_id: "0xmarket".to_string(),
+            strategy: Strategy::ClvArbitrage,
+            position: Position::Yes,
+            quantity: dec!(100),
+            entry_price: dec!(0.50),
+            exit_price: None,
+            entry_time: Utc::now(),
+            exit_time: None,
+            gas_cost: None,
+            fees: None,
+            slippage: None,
+            pnl: None,
+            pnl_percent: None,
+            max_adverse_excursion: None,
+            max_favorable_excursion: None,
+            status: TradeStatus::Open,
+            tx_hash_entry: None,
+            tx_hash_exit: None,
+            venue: POLYMARKET_VENUE.to_string(),
+            origin: TradeOrigin::Bot,
+        }
+    }
+
+    #[test]
+    fn excursions_keep_the_extreme_marks() {
+        let trade = trade();
+        let now = Utc::now();
+
+        let first = mark(&trade, dec!(0.55), None, now);
+        assert_eq!(first.unrealized_pnl, dec!(5));
+        assert_eq!(first.max_adverse_excursion, dec!(0));
+
+        let dip = mark(&trade, dec!(0.42), Some(&first), now);
+        assert_eq!(dip.max_adverse_excursion, dec!(-8));
+
+        let recovered = mark(&trade, dec!(0.60), Some(&dip), now);
+        assert_eq!(recovered.unrealized_pnl, dec!(10));
+        assert_eq!(recovered.max_adverse_excursion, dec!(-8));
+        assert_eq!(recovered.max_favorable_excursion, dec!(10));
+
+        // The exit counts as a final mark
+        let closed = Trade { status: TradeStatus::Closed, ..trade };
+        assert_eq!(closed.excursions_at_close(dec!(0.38), Some(&recovered)), (dec!(-12), dec!(10)));
+    }
+}
@@ -0,0 +1,145 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+use crate::types::{CapitalPoint, CashFlow, Usdc};
+
+const SECONDS_PER_YEAR: f64 = 365.25 * 24.0 * 3600.0;
+
+/// Returns over a period, net of deposits and withdrawals
+#[derive(Debug, Clone, Serialize)]
+pub struct ReturnsReport {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub starting_capital: Decimal,
+    pub ending_capital: Decimal,
+    /// Deposits minus withdrawals during the period
+    pub net_cash_flows: Decimal,
+    /// Change in capital not explained by cash flows
+    pub pnl: Decimal,
+    /// Snapshot returns chained together, so the size and timing of flows
+    /// don't move it: how the strategy did
+    pub time_weighted_return: Option<Decimal>,
+    pub time_weighted_return_annualized: Option<f64>,
+    /// Annualized IRR of the flows and capital: how the money invested did
+    pub money_weighted_return_annualized: Option<f64>,
+}
+
+/// TWR and IRR of the capital history, taking each flow as landing just
+/// before the first snapshot at or after it
+///
+/// `None` when there are fewer than two snapshots.
+pub fn returns_report(points: &[CapitalPoint], flows: &[CashFlow]) -> Option<ReturnsReport> {
+    let (first, last) = (points.first()?, points.last()?);
+    if last.timestamp <= first.timestamp {
+        return None;
+    }
+    let flows: Vec<&CashFlow> = flows.iter()
+        .filter(|f| f.occurred_at > first.timestamp && f.occurred_at <= last.timestamp)
+        .collect();
+    let net_cash_flows = flows.iter().map(|f| f.signed_amount()).sum::<Usdc>().value();
+
+    let twr = time_weighted_return(points, &flows);
+    let years = (last.timestamp - first.timestamp).num_seconds() as f64 / SECONDS_PER_YEAR;
+
+    Some(ReturnsReport {
+        start: first.timestamp,
+        end: last.timestamp,
+        starting_capital: first.total_capital,
+        ending_capital: last.total_capital,
+        net_cash_flows,
+        pnl: last.total_capital - first.total_capital - net_cash_flows,
+        time_weighted_return: twr.map(|r| r.round_dp(6)),
+        time_weighted_return_annualized: twr
+            .and_then(|r| r.to_f64())
+            .map(|r| (1.0 + r).powf(1.0 / years) - 1.0)
+            .filter(|r| r.is_finite()),
+        money_weighted_return_annualized: money_weighted_return(first, last, &flows),
+    })
+}
+
+fn time_weighted_return(points: &[CapitalPoint], flows: &[&CashFlow]) -> Option<Decimal> {
+    let mut growth = Decimal::ONE;
+    for window in points.windows(2) {
+        let (start, end) = (&window[0], &window[1]);
+        if start.total_capital <= Decimal::ZERO {
+            return None;
+        }
+        let flow: Decimal = flows.iter()
+            .filter(|f| f.occurred_at > start.timestamp && f.occurred_at <= end.timestamp)
+            .map(|f| f.signed_amount())
+            .sum::<Usdc>()
+            .value();
+        growth *= (end.total_capital - flow) / start.total_capital;
+    }
+    Some(growth - Decimal::ONE)
+}
+
+/// Rate at which the starting capital and deposits, less withdrawals,
+/// discount to the ending capital; bisected, since the value of the flows
+/// falls as the rate rises
+fn money_weighted_return(first: &CapitalPoint, last: &CapitalPoint, flows: &[&CashFlow]) -> Option<f64> {
+    let years = |at: DateTime<Utc>| (at - first.timestamp).num_seconds() as f64 / SECONDS_PER_YEAR;
+    // Money in is negative, money out (and the ending capital) positive
+    let mut cash_flows: Vec<(f64, f64)> = vec![(0.0, -first.total_capital.to_f64()?)];
+    for flow in flows {
+        cash_flows.push((years(flow.occurred_at), -flow.signed_amount().value().to_f64()?));
+    }
+    cash_flows.push((years(last.timestamp), last.total_capital.to_f64()?));
+
+    let npv = |rate: f64| cash_flows.iter().map(|(t, amount)| amount / (1.0 + rate).powf(*t)).sum::<f64>();
+    let (mut low, mut high) = (-0.9999, 1_000.0);
+    if npv(low).signum() == npv(high).signum() {
+        return None;
+    }
+    for _ in 0..200 {
+        let mid = (low + high) / 2.0;
+        if npv(mid).signum() == npv(low).signum() {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Some((low + high) / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CashFlowKind, CashFlowSource, POLYMARKET_VENUE};
+    use chrono::Duration;
+    use rust_decimal_macros::dec;
+    use uuid::Uuid;
+
+    fn point(at: DateTime<Utc>, total_capital: Decimal) -> CapitalPoint {
+        CapitalPoint { timestamp: at, total_capital }
+    }
+
+    #[test]
+    fn deposits_do_not_count_as_returns() {
+        let start = Utc::now() - Duration::days(365);
+        let mid = start + Duration::days(182);
+        let end = start + Duration::days(365);
+        // +10% on 1000, then a 1000 deposit, then -10% on 2100
+        let points = [point(start, dec!(1000)), point(mid, dec!(2100)), point(end, dec!(1890))];
+        let deposit = CashFlow {
+            flow_id: Uuid::new_v4(),
+            kind: CashFlowKind::Deposit,
+            amount: Usdc::new(dec!(1000)),
+            source: CashFlowSource::Manual,
+            tx_hash: None,
+            note: None,
+            occurred_at: mid,
+            venue: POLYMARKET_VENUE.to_string(),
+        };
+
+        let report = returns_report(&points, &[deposit]).unwrap();
+        assert_eq!(report.net_cash_flows, dec!(1000));
+        assert_eq!(report.pnl, dec!(-110));
+        assert_eq!(report.time_weighted_return, Some(dec!(-0.01)));
+        // More money was in the losing half, so the money-weighted return is worse
+        let irr = report.money_weighted_return_annualized.unwrap();
+        assert!(irr < -0.01 && irr > -0.10, "{}", irr);
+    }
+}
@@ -0,0 +1,343 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::numeric;
+use crate::storage::Storage;
+use crate::types::{Market, MarketType, Position, Sport, Trade, Usdc};
+
+/// Markets left free are enumerated exhaustively up to this count (2^12 outcomes)
+const MAX_ENUMERATED_MARKETS: usize = 12;
+
+/// Paths drawn when there are too many free markets to enumerate
+const SAMPLE_COUNT: usize = 10_000;
+
+/// Outcomes a scenario forces; markets it says nothing about stay uncertain
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Scenario {
+    /// The higher-priced side of every non-total market wins
+    FavoritesWin,
+    UnderdogsWin,
+    /// Every total market goes over (YES)
+    TotalsOver,
+    TotalsUnder,
+    /// Explicit resolutions by market id
+    Outcomes { outcomes: HashMap<String, Position> },
+}
+
+impl Scenario {
+    fn resolve(&self, market: &Market) -> Option<Position> {
+        let favorite = if market.yes_price >= market.no_price {
+            Position::Yes
+        } else {
+            Position::No
+        };
+        let is_total = market.market_type == MarketType::Total;
+
+        match self {
+            Scenario::FavoritesWin if !is_total => Some(favorite),
+            Scenario::UnderdogsWin if !is_total => Some(favorite.opposite()),
+            Scenario::TotalsOver if is_total => Some(Position::Yes),
+            Scenario::TotalsUnder if is_total => Some(Position::No),
+            Scenario::Outcomes { outcomes } => outcomes.get(&market.market_id).copied(),
+            _ => None,
+        }
+    }
+}
+
+/// Scenario plus the slice of the book it applies to
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScenarioRequest {
+    pub scenario: Scenario,
+    /// Only fix outcomes for this sport
+    pub sport: Option<Sport>,
+    /// Only fix outcomes for events starting before this time (e.g. end of tonight's slate)
+    pub until: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ScenarioMethod {
+    Exhaustive { outcomes: usize },
+    Sampled { samples: usize },
+}
+
+/// PnL distribution of the open book under a scenario
+///
+/// PnL is measured at resolution against entry, net of entry costs.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScenarioReport {
+    pub open_markets: usize,
+    pub fixed_markets: usize,
+    pub method: ScenarioMethod,
+    /// PnL of positions whose outcome the scenario fixes
+    pub fixed_pnl: Decimal,
+    pub expected_pnl: Decimal,
+    pub worst_pnl: Decimal,
+    pub best_pnl: Decimal,
+    /// 5th percentile of PnL
+    pub pnl_p05: Decimal,
+    pub probability_of_loss: Decimal,
+}
+
+/// Open positions in one market
+struct BookEntry {
+    market: Market,
+    trades: Vec<Trade>,
+}
+
+impl BookEntry {
+    fn pnl(&self, resolution: Position) -> Decimal {
+        self.trades.iter()
+            .map(|t| {
+                let payout = if t.position == resolution { Decimal::ONE } else { Decimal::ZERO };
+                t.realized_pnl(payout, Usdc::ZERO)
+            })
+            .sum::<Usdc>()
+            .value()
+    }
+
+    /// Vig-free YES probability implied by current mid prices
+    fn yes_probability(&self) -> Decimal {
+        let yes = self.market.yes_quote.mid().value();
+        let total = yes + self.market.no_quote.mid().value();
+        if total > Decimal::ZERO {
+            yes / total
+        } else {
+            dec!(0.5)
+        }
+    }
+}
+
+/// A market whose outcome is left to chance: PnL on each side and P(YES)
+struct FreeMarket {
+    yes_pnl: Decimal,
+    no_pnl: Decimal,
+    yes_probability: Decimal,
+}
+
+/// Answers "what if" questions about the open book before a slate
+pub struct ScenarioAnalyzer {
+    storage: Arc<dyn Storage>,
+}
+
+impl ScenarioAnalyzer {
+    pub fn new(storage: Arc<dyn Storage>) -> Self {
+        Self { storage }
+    }
+
+    pub async fn run(&self, request: &ScenarioRequest) -> Result<ScenarioReport> {
+        let book = self.load_book().await?;
+        Ok(evaluate(&book, request, &mut rand::thread_rng()))
+    }
+
+    async fn load_book(&self) -> Result<Vec<BookEntry>> {
+        let mut by_market: HashMap<String, Vec<Trade>> = HashMap::new();
+        for trade in self.storage.fetch_open_trades().await? {
+            by_market.entry(trade.market_id.clone()).or_default().push(trade);
+        }
+
+        let mut book = Vec::with_capacity(by_market.len());
+        for (market_id, trades) in by_market {
+            if let Some(market) = self.storage.fetch_market(&market_id).await? {
+                book.push(BookEntry { market, trades });
+            }
+        }
+
+        Ok(book)
+    }
+}
+
+fn evaluate(book: &[BookEntry], request: &ScenarioRequest, rng: &mut impl Rng) -> ScenarioReport {
+    let mut fixed_markets = 0;
+    let mut fixed_pnl = Decimal::ZERO;
+    let mut free = Vec::new();
+
+    for entry in book {
+        let in_scope = request.sport.map_or(true, |s| s == entry.market.sport)
+            && request.until.map_or(true, |until| entry.market.event_time <= until);
+
+        match request.scenario.resolve(&entry.market).filter(|_| in_scope) {
+            Some(resolution) => {
+                fixed_markets += 1;
+                fixed_pnl += entry.pnl(resolution);
+            }
+            None => free.push(FreeMarket {
+                yes_pnl: entry.pnl(Position::Yes),
+                no_pnl: entry.pnl(Position::No),
+                yes_probability: entry.yes_probability(),
+            }),
+        }
+    }
+
+    let (method, outcomes) = if free.len() <= MAX_ENUMERATED_MARKETS {
+        let outcomes = enumerate(&free, fixed_pnl);
+        (ScenarioMethod::Exhaustive { outcomes: outcomes.len() }, outcomes)
+    } else {
+        (ScenarioMethod::Sampled { samples: SAMPLE_COUNT }, sample(&free, fixed_pnl, rng))
+    };
+
+    summarize(book.len(), fixed_markets, method, fixed_pnl, outcomes)
+}
+
+/// Every combination of free outcomes as (pnl, probability)
+fn enumerate(free: &[FreeMarket], fixed_pnl: Decimal) -> Vec<(Decimal, Decimal)> {
+    (0u32..1 << free.len())
+        .map(|mask| {
+            free.iter().enumerate().fold((fixed_pnl, Decimal::ONE), |(pnl, p), (i, m)| {
+                if mask & (1 << i) != 0 {
+                    (pnl + m.yes_pnl, p * m.yes_probability)
+                } else {
+                    (pnl + m.no_pnl, p * (Decimal::ONE - m.yes_probability))
+                }
+            })
+        })
+        .collect()
+}
+
+/// Monte Carlo paths, equally weighted
+fn sample(free: &[FreeMarket], fixed_pnl: Decimal, rng: &mut impl Rng) -> Vec<(Decimal, Decimal)> {
+    let weight = Decimal::ONE / Decimal::from(SAMPLE_COUNT);
+    let probabilities: Vec<f64> = free.iter()
+        .map(|m| numeric::to_f64(m.yes_probability).unwrap_or(0.5))
+        .collect();
+
+    (0..SAMPLE_COUNT)
+        .map(|_| {
+            let pnl = free.iter().zip(&probabilities).fold(fixed_pnl, |pnl, (m, p)| {
+                if rng.gen_bool(p.clamp(0.0, 1.0)) {
+                    pnl + m.yes_pnl
+                } else {
+                    pnl + m.no_pnl
+                }
+            });
+            (pnl, weight)
+        })
+        .collect()
+}
+
+fn summarize(
+    open_markets: usize,
+    fixed_markets: usize,
+    method: ScenarioMethod,
+    fixed_pnl: Decimal,
+    mut outcomes: Vec<(Decimal, Decimal)>,
+) -> ScenarioReport {
+    outcomes.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let expected_pnl = outcomes.iter().map(|(pnl, p)| pnl * p).sum();
+    let probability_of_loss = outcomes.iter()
+        .filter(|(pnl, _)| *pnl < Decimal::ZERO)
+        .map(|(_, p)| *p)
+        .sum();
+
+    let mut cumulative = Decimal::ZERO;
+    let pnl_p05 = outcomes.iter()
+        .find(|(_, p)| {
+            cumulative += *p;
+            cumulative >= dec!(0.05)
+        })
+        .map(|(pnl, _)| *pnl)
+        .unwrap_or(fixed_pnl);
+
+    ScenarioReport {
+        open_markets,
+        fixed_markets,
+        method,
+        fixed_pnl,
+        expected_pnl,
+        worst_pnl: outcomes.first().map_or(fixed_pnl, |o| o.0),
+        best_pnl: outcomes.last().map_or(fixed_pnl, |o| o.0),
+        pnl_p05,
+        probability_of_loss,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::market_fixture;
+    use crate::types::{Strategy, TradeOrigin, TradeStatus, POLYMARKET_VENUE};
+    use uuid::Uuid;
+
+    fn yes_trade(market: &Market, quantity: Decimal) -> Trade {
+        Trade {
+            trade_id: Uuid::new_v4(),
+            market_id: market.market_id.clone(),
+            strategy: Strategy::ClvArbitrage,
+            position: Position::Yes,
+            quantity,
+            entry_price: market.yes_price.value(),
+            exit_price: None,
+            entry_time: Utc::now(),
+            exit_time: None,
+            gas_cost: None,
+            fees: None,
+            slippage: None,
+            pnl: None,
+            pnl_percent: None,
+            max_adverse_excursion: None,
+            max_favorable_excursion: None,
+            status: TradeStatus::Open,
+            tx_hash_entry: None,
+            tx_hash_exit: None,
+            venue: POLYMARKET_VENUE.to_string(),
+            origin: TradeOrigin::Bot,
+        }
+    }
+
+    fn entry(market_id: &str, yes_price: Decimal) -> BookEntry {
+        let market = market_fixture(market_id, yes_price);
+        let trades = vec![yes_trade(&market, dec!(100))];
+        BookEntry { market, trades }
+    }
+
+    fn request(scenario: Scenario) -> ScenarioRequest {
+        ScenarioRequest { scenario, sport: None, until: None }
+    }
+
+    #[test]
+    fn unconstrained_book_is_enumerated_at_fair_value() {
+        let book = vec![entry("0xa", dec!(0.6)), entry("0xb", dec!(0.3))];
+        let scenario = Scenario::Outcomes { outcomes: HashMap::new() };
+        let report = evaluate(&book, &request(scenario), &mut rand::thread_rng());
+
+        assert!(matches!(report.method, ScenarioMethod::Exhaustive { outcomes: 4 }));
+        assert_eq!(report.fixed_markets, 0);
+        // Bought at the vig-free price, so the book is worth zero in expectation
+        assert_eq!(report.expected_pnl, dec!(0));
+        assert_eq!(report.worst_pnl, dec!(-90));
+        assert_eq!(report.best_pnl, dec!(110));
+        // Only both-NO loses: -60 - 30
+        assert_eq!(report.probability_of_loss, dec!(0.28));
+    }
+
+    #[test]
+    fn favorites_sweep_fixes_only_favored_markets() {
+        let book = vec![entry("0xa", dec!(0.6)), entry("0xb", dec!(0.3))];
+        let report = evaluate(&book, &request(Scenario::FavoritesWin), &mut rand::thread_rng());
+
+        // 0xa's YES favorite wins (+40); 0xb's NO favorite wins, so our YES loses (-30)
+        assert_eq!(report.fixed_markets, 2);
+        assert_eq!(report.fixed_pnl, dec!(10));
+        assert_eq!(report.worst_pnl, dec!(10));
+        assert_eq!(report.best_pnl, dec!(10));
+    }
+
+    #[test]
+    fn large_books_are_sampled() {
+        let book: Vec<BookEntry> = (0..MAX_ENUMERATED_MARKETS + 1)
+            .map(|i| entry(&format!("0x{}", i), dec!(0.5)))
+            .collect();
+        let report = evaluate(&book, &request(Scenario::TotalsOver), &mut rand::thread_rng());
+
+        assert!(matches!(report.method, ScenarioMethod::Sampled { samples: SAMPLE_COUNT }));
+        assert_eq!(report.fixed_markets, 0);
+    }
+}
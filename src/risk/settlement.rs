@@ -0,0 +1,409 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use ethers::abi::{parse_abi, Detokenize, Tokenize};
+use ethers::prelude::*;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use crate::config::{Config, TradingMode};
+use crate::data::PolymarketClient;
+use crate::execution::{ChainRpc, Redeemer, Redemption, RedemptionGas, RpcFailover};
+use crate::monitoring::{Heartbeats, Notifier};
+use crate::numeric;
+use crate::storage::Storage;
+use crate::types::{
+    ExecutionCost, ExecutionCostKind, Market, MarketStatus, Position, SettlementReview, Trade, TradeOutcome, TradeSettlement, Usdc,
+};
+use super::RiskManager;
+
+/// Outcome slot of YES in Polymarket's binary conditions; NO is slot 1
+const YES_OUTCOME_INDEX: u64 = 0;
+
+/// What to do with open trades on a market
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettlementVerdict {
+    /// Book the trade at this payout per share
+    Settle(Decimal),
+    /// Not resolved yet, or not yet reported on-chain
+    Wait,
+    /// Hold for manual review
+    Review(String),
+}
+
+/// Payout per share of `position` from a CTF payout vector, `None` until
+/// the condition has been reported (zero denominator)
+pub fn payout_per_share(numerators: [U256; 2], denominator: U256, position: Position) -> Option<Decimal> {
+    if denominator.is_zero() {
+        return None;
+    }
+    let slot = match position {
+        Position::Yes => YES_OUTCOME_INDEX as usize,
+        Position::No => 1 - YES_OUTCOME_INDEX as usize,
+    };
+
+    let numerator = Decimal::from_str(&numerators[slot].to_string()).ok()?;
+    let denominator = Decimal::from_str(&denominator.to_string()).ok()?;
+    Some(numerator / denominator)
+}
+
+/// Settle `position` on `market` at the on-chain payout, provided the
+/// market has ended and its reported final price agrees with the payout to
+/// within `tolerance`
+pub fn verify_settlement(
+    market: &Market,
+    position: Position,
+    onchain_payout: Option<Decimal>,
+    tolerance: Decimal,
+) -> SettlementVerdict {
+    if market.status == MarketStatus::Disputed {
+        return SettlementVerdict::Review("resolution disputed".to_string());
+    }
+    // The adapter can report on-chain before the listing shows the market
+    // resolved, so a closed market settles once its payout is reported
+    let ended = matches!(market.status, MarketStatus::Resolved | MarketStatus::Closed);
+    let Some(onchain) = onchain_payout.filter(|_| ended) else {
+        return SettlementVerdict::Wait;
+    };
+
+    let reported = market.price(position).value();
+    if (reported - onchain).abs() > tolerance {
+        return SettlementVerdict::Review(format!(
+            "reported payout {} but on-chain payout {}",
+            reported, onchain
+        ));
+    }
+
+    SettlementVerdict::Settle(onchain)
+}
+
+/// Reads condition payouts from the Conditional Tokens contract
+struct PayoutReader {
+    rpc: Arc<RpcFailover<Http>>,
+    ctf: Address,
+    abi: BaseContract,
+}
+
+impl PayoutReader {
+    async fn call<T: Tokenize, R: Detokenize>(&self, function: &str, args: T) -> Result<R> {
+        let data = self.abi.encode(function, args)?;
+        let tx: TypedTransaction = TransactionRequest::new().to(self.ctf).data(data).into();
+        let output = self.rpc.call(|provider| async move { Ok(provider.call(&tx, None).await?) }).await?;
+        Ok(self.abi.decode_output(function, output)?)
+    }
+
+    /// Payout per share of `position`, `None` until reported on-chain
+    async fn payout(&self, condition_id: &str, position: Position) -> Result<Option<Decimal>> {
+        let condition = condition_id.parse::<H256>().context("condition id")?;
+
+        let denominator: U256 = self.call("payoutDenominator", (condition,)).await?;
+        if denominator.is_zero() {
+            return Ok(None);
+        }
+        let yes: U256 = self.call("payoutNumerators", (condition, U256::from(YES_OUTCOME_INDEX))).await?;
+        let no: U256 = self.call("payoutNumerators", (condition, U256::from(1 - YES_OUTCOME_INDEX))).await?;
+
+        Ok(payout_per_share([yes, no], denominator, position))
+    }
+}
+
+/// Books open trades on resolved markets at the on-chain payout
+///
+/// The data pipeline only polls open listings, so markets with open trades
+/// whose events have started are re-fetched here until they resolve. The
+/// payout comes from the CTF contract's payout vector, which UMA's
+/// adapter writes once a resolution is final, not from the market's
+/// reported price. Disputes and disagreements between the two are flagged
+/// in `settlement_reviews` and left open until settled through the admin API.
+///
+/// In live mode winning positions are redeemed from the wallet before they
+/// are booked, once every market in a check has been looked at; a market
+/// whose redemption fails or pays nothing stays open and is tried again on
+/// the next check.
+pub struct SettlementMonitor {
+    storage: Arc<dyn Storage>,
+    risk_manager: RiskManager,
+    notifier: Notifier,
+    payouts: PayoutReader,
+    polymarket: PolymarketClient,
+    redeemer: Option<Redeemer>,
+    tolerance: Decimal,
+    check_interval: Duration,
+    heartbeats: Heartbeats,
+}
+
+impl SettlementMonitor {
+    pub fn new(
+        storage: Arc<dyn Storage>,
+        risk_manager: RiskManager,
+        rpc: &ChainRpc,
+        config: &Config,
+        notifier: Notifier,
+        heartbeats: Heartbeats,
+    ) -> Result<Self> {
+        let abi = BaseContract::from(parse_abi(&[
+            "function payoutDenominator(bytes32 conditionId) external view returns (uint256)",
+            "function payoutNumerators(bytes32 conditionId, uint256 index) external view returns (uint256)",
+        ])?);
+
+        let chain = config.chain()?;
+
+        Ok(Self {
+            storage,
+            risk_manager,
+            notifier,
+            payouts: PayoutReader {
+                rpc: rpc.http.clone(),
+                ctf: chain.conditional_tokens,
+                abi,
+            },
+            polymarket: PolymarketClient::new(config)?,
+            redeemer: match config.mode {
+                TradingMode::Live => Some(Redeemer::new(rpc.http.clone(), config)?),
+                _ => None,
+            },
+            tolerance: numeric::to_decimal(config.risk.settlement_payout_tolerance)
+                .context("risk.settlement_payout_tolerance")?,
+            check_interval: Duration::from_secs(config.risk.settlement_check_interval_secs),
+            heartbeats,
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let mut tick = interval(self.check_interval);
+
+        info!("⚖️ Settlement monitor started");
+
+        loop {
+            tick.tick().await;
+
+            if let Err(e) = self.settle_open_trades().await {
+                error!("Error settling trades: {}", e);
+            }
+            self.heartbeats.beat_every("settlement_monitor", self.check_interval);
+        }
+    }
+
+    async fn settle_open_trades(&self) -> Result<()> {
+        let held: HashSet<_> = self.storage.fetch_open_settlement_reviews().await?
+            .into_iter()
+            .map(|r| r.trade_id)
+            .collect();
+
+        let mut by_market: HashMap<String, Vec<Trade>> = HashMap::new();
+        for trade in self.storage.fetch_open_trades().await? {
+            if !held.contains(&trade.trade_id) {
+                by_market.entry(trade.market_id.clone()).or_default().push(trade);
+            }
+        }
+
+        // Trades to book per resolved condition, redeemed together after every market is checked
+        let mut settled: Vec<(H256, Vec<(Trade, Decimal)>)> = Vec::new();
+        for (market_id, trades) in by_market {
+            let Some(market) = self.storage.fetch_market(&market_id).await? else {
+                continue;
+            };
+            let market = match self.refresh(&market).await {
+                Ok(Some(current)) => current,
+                Ok(None) => market,
+                Err(e) => {
+                    warn!("Failed to refresh market {}: {}", market_id, e);
+                    market
+                }
+            };
+            if !market.status.awaits_settlement() {
+                continue;
+            }
+            let Some(tokens) = &market.tokens else {
+                warn!("No condition id for resolved market {}", market_id);
+                continue;
+            };
+
+            let mut due = Vec::new();
+            for trade in trades {
+                let onchain = self.payouts.payout(&tokens.condition_id, trade.position).await?;
+                match verify_settlement(&market, trade.position, onchain, self.tolerance) {
+                    SettlementVerdict::Settle(payout) => due.push((trade, payout)),
+                    SettlementVerdict::Wait => debug!("Market {} not yet resolved on-chain", market_id),
+                    SettlementVerdict::Review(reason) => self.flag(&trade, &market, onchain, reason).await?,
+                }
+            }
+            if !due.is_empty() {
+                let condition = H256::from_str(&tokens.condition_id).context("condition id")?;
+                settled.push((condition, due));
+            }
+        }
+
+        self.redeem_and_settle(settled).await
+    }
+
+    /// Store and return the market as currently listed, once its event has
+    /// started and until it resolves; `None` when there is nothing to refresh
+    async fn refresh(&self, market: &Market) -> Result<Option<Market>> {
+        if market.event_time > Utc::now() || matches!(market.status, MarketStatus::Resolved | MarketStatus::Disputed) {
+            return Ok(None);
+        }
+        let Some((current, resolution)) = self.polymarket.fetch_market(&market.market_id, market.sport).await? else {
+            return Ok(None);
+        };
+
+        if current.status != market.status {
+            info!("⚖️ Market {} status {} -> {}", market.market_id, market.status.as_str(), current.status.as_str());
+        }
+        self.storage.upsert_market(&current).await?;
+        if let Some(resolution) = resolution {
+            self.storage.set_market_resolution(&current.market_id, resolution, Utc::now()).await?;
+        }
+
+        Ok(Some(current))
+    }
+
+    /// Redeem the winning positions among `settled` and book every trade
+    /// whose market needed no redemption or was redeemed
+    async fn redeem_and_settle(&self, settled: Vec<(H256, Vec<(Trade, Decimal)>)>) -> Result<()> {
+        let Some(redeemer) = &self.redeemer else {
+            for (trade, payout) in settled.iter().flat_map(|(_, due)| due) {
+                self.settle(trade, *payout, "").await?;
+            }
+            return Ok(());
+        };
+
+        // Losing positions pay nothing, so aren't worth the gas to redeem
+        let (winning, losing): (Vec<_>, Vec<_>) = settled.into_iter()
+            .partition(|(_, due)| due.iter().any(|(_, payout)| *payout > Decimal::ZERO));
+        for (trade, payout) in losing.iter().flat_map(|(_, due)| due) {
+            self.settle(trade, *payout, "").await?;
+        }
+        if winning.is_empty() {
+            return Ok(());
+        }
+
+        let conditions: Vec<H256> = winning.iter().map(|(condition, _)| *condition).collect();
+        let mut outcomes: HashMap<H256, Redemption> = HashMap::new();
+        for (condition, outcome, gas) in redeemer.redeem(&conditions).await {
+            if let Some(gas) = gas {
+                self.record_redemption_gas(gas).await?;
+            }
+            outcomes.insert(condition, outcome);
+        }
+        for (condition, due) in &winning {
+            match outcomes.get(condition) {
+                Some(Redemption::Redeemed(tx_hash)) => {
+                    for (trade, payout) in due {
+                        self.settle(trade, *payout, tx_hash).await?;
+                    }
+                }
+                Some(Redemption::Failed(reason)) => {
+                    let market_id = &due[0].0.market_id;
+                    warn!("⚖️ Redemption of {} failed, retrying next check: {}", market_id, reason);
+                    self.notifier.critical(format!("⚖️ Redemption of {} failed: {}", market_id, reason));
+                }
+                None => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Book a mined redemption's gas against the daily cost budget
+    async fn record_redemption_gas(&self, gas: RedemptionGas) -> Result<()> {
+        self.storage.insert_execution_cost(&ExecutionCost {
+            cost_id: Uuid::new_v4(),
+            kind: ExecutionCostKind::Redemption,
+            amount: gas.cost,
+            trade_id: None,
+            tx_hash: Some(gas.tx_hash),
+            incurred_at: Utc::now(),
+        }).await
+    }
+
+    async fn settle(&self, trade: &Trade, payout: Decimal, tx_hash: &str) -> Result<()> {
+        let pnl = trade.realized_pnl(payout, Usdc::ZERO);
+        let outcome = TradeOutcome::from_payout(payout);
+        let now = Utc::now();
+        self.storage.close_trade(trade.trade_id, payout, now, pnl, tx_hash).await?;
+        self.storage.insert_trade_settlement(&TradeSettlement {
+            trade_id: trade.trade_id,
+            market_id: trade.market_id.clone(),
+            outcome,
+            payout,
+            pnl,
+            tx_hash: (!tx_hash.is_empty()).then(|| tx_hash.to_string()),
+            settled_at: now,
+        }).await?;
+        self.risk_manager.update_portfolio(pnl).await?;
+
+        info!("⚖️ Settled trade {} {} at {} per share, PnL: {}", trade.trade_id, outcome.as_str(), payout, pnl);
+        self.notifier.info(format!(
+            "⚖️ Settled {} {} in {}: {} at {}, PnL ${}",
+            trade.strategy.as_str(),
+            trade.position.as_str(),
+            trade.market_id,
+            outcome.as_str(),
+            payout,
+            pnl.round_dp(2)
+        ));
+
+        Ok(())
+    }
+
+    async fn flag(&self, trade: &Trade, market: &Market, onchain: Option<Decimal>, reason: String) -> Result<()> {
+        let review = SettlementReview {
+            trade_id: trade.trade_id,
+            market_id: market.market_id.clone(),
+            reported_payout: Some(market.price(trade.position).value()),
+            onchain_payout: onchain,
+            reason,
+            flagged_at: Utc::now(),
+            resolved_at: None,
+        };
+        self.storage.upsert_settlement_review(&review).await?;
+
+        warn!("⚖️ Trade {} held for settlement review: {}", trade.trade_id, review.reason);
+        self.notifier.critical(format!(
+            "⚖️ Trade {} in {} held for settlement review: {}",
+            trade.trade_id, market.market_id, review.reason
+        ));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::market_fixture;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn settles_at_the_payout_vector_only_when_the_reported_price_agrees() {
+        let mut market = market_fixture("0xsettle", dec!(0.999));
+        market.status = MarketStatus::Resolved;
+        let yes_won = payout_per_share([U256::one(), U256::zero()], U256::one(), Position::Yes);
+        assert_eq!(yes_won, Some(dec!(1)));
+        assert_eq!(payout_per_share([U256::one(), U256::zero()], U256::zero(), Position::Yes), None);
+
+        let tolerance = dec!(0.01);
+        assert_eq!(verify_settlement(&market, Position::Yes, yes_won, tolerance), SettlementVerdict::Settle(dec!(1)));
+        assert_eq!(verify_settlement(&market, Position::Yes, None, tolerance), SettlementVerdict::Wait);
+
+        // The chain says NO won while the API still shows YES at the top
+        let no_won = payout_per_share([U256::zero(), U256::one()], U256::one(), Position::Yes);
+        assert!(matches!(verify_settlement(&market, Position::Yes, no_won, tolerance), SettlementVerdict::Review(_)));
+
+        // Reported on-chain while the listing only shows the market closed
+        market.status = MarketStatus::Closed;
+        assert_eq!(verify_settlement(&market, Position::Yes, yes_won, tolerance), SettlementVerdict::Settle(dec!(1)));
+        assert_eq!(verify_settlement(&market, Position::Yes, None, tolerance), SettlementVerdict::Wait);
+        market.status = MarketStatus::InPlay;
+        assert_eq!(verify_settlement(&market, Position::Yes, yes_won, tolerance), SettlementVerdict::Wait);
+
+        market.status = MarketStatus::Disputed;
+        assert!(matches!(verify_settlement(&market, Position::Yes, yes_won, tolerance), SettlementVerdict::Review(_)));
+    }
+}
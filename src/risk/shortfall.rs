@@ -0,0 +1,218 @@
+use chrono::Duration;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::types::{Strategy, Trade, TradeOrigin, Usdc};
+
+/// A live trade and the paper trade taken on the same signal
+#[derive(Debug, Clone, Serialize)]
+pub struct TradePair {
+    pub live_trade_id: Uuid,
+    pub paper_trade_id: Uuid,
+    pub market_id: String,
+    pub strategy: Strategy,
+    /// Seconds the live entry filled after the paper one
+    pub entry_delay_secs: i64,
+    /// Paper PnL scaled to the live quantity
+    pub paper_pnl: Decimal,
+    pub live_pnl: Decimal,
+    /// Paper minus live PnL; positive is what live execution cost
+    pub shortfall: Decimal,
+    /// Part of the shortfall from a worse live entry price
+    pub entry_cost: Decimal,
+    /// Part from a worse live exit price
+    pub exit_cost: Decimal,
+    /// The rest: fees, gas and anything netted into PnL at close
+    pub fees_and_gas_cost: Decimal,
+}
+
+/// Execution shortfall of one strategy's live trades against paper
+#[derive(Debug, Clone, Serialize)]
+pub struct StrategyShortfall {
+    pub strategy: Strategy,
+    pub matched: usize,
+    /// Paper trades with no live counterpart: rejected, unfilled or too late
+    pub paper_only: usize,
+    pub live_only: usize,
+    pub paper_pnl: Decimal,
+    pub live_pnl: Decimal,
+    pub shortfall: Decimal,
+    pub entry_cost: Decimal,
+    pub exit_cost: Decimal,
+    pub fees_and_gas_cost: Decimal,
+    /// Shortfall per dollar staked live, in basis points
+    pub shortfall_bps: Option<Decimal>,
+    pub mean_entry_delay_secs: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ShortfallReport {
+    pub strategies: Vec<StrategyShortfall>,
+    pub pairs: Vec<TradePair>,
+}
+
+/// Compare closed bot trades of a live run with those of a paper run fed
+/// the same signals
+///
+/// Trades pair up by market, strategy and side, each live trade with the
+/// nearest unpaired paper entry within `window`. Paper PnL is scaled to the
+/// live quantity, since the two runs size off different bankrolls.
+pub fn shortfall_report(live: &[Trade], paper: &[Trade], window: Duration) -> ShortfallReport {
+    let eligible = |t: &&Trade| t.origin == TradeOrigin::Bot && t.pnl.is_some() && t.quantity > Decimal::ZERO;
+    let paper: Vec<&Trade> = paper.iter().filter(eligible).collect();
+    let mut paired = vec![false; paper.len()];
+
+    let mut pairs = Vec::new();
+    let mut by_strategy: BTreeMap<&str, StrategyShortfall> = BTreeMap::new();
+    let mut staked: BTreeMap<&str, Decimal> = BTreeMap::new();
+    let mut delays: BTreeMap<&str, Vec<i64>> = BTreeMap::new();
+    for live_trade in live.iter().filter(eligible) {
+        let nearest = paper.iter()
+            .enumerate()
+            .filter(|(i, p)| {
+                !paired[*i]
+                    && p.market_id == live_trade.market_id
+                    && p.strategy == live_trade.strategy
+                    && p.position == live_trade.position
+                    && (live_trade.entry_time - p.entry_time).abs() <= window
+            })
+            .min_by_key(|(_, p)| (live_trade.entry_time - p.entry_time).abs());
+
+        let name = live_trade.strategy.as_str();
+        let row = by_strategy.entry(name).or_insert_with(|| empty(live_trade.strategy));
+        let Some((i, paper_trade)) = nearest else {
+            row.live_only += 1;
+            continue;
+        };
+        paired[i] = true;
+
+        let pair = pair(live_trade, paper_trade);
+        row.matched += 1;
+        row.paper_pnl += pair.paper_pnl;
+        row.live_pnl += pair.live_pnl;
+        row.shortfall += pair.shortfall;
+        row.entry_cost += pair.entry_cost;
+        row.exit_cost += pair.exit_cost;
+        row.fees_and_gas_cost += pair.fees_and_gas_cost;
+        *staked.entry(name).or_default() += live_trade.position_size_usd();
+        delays.entry(name).or_default().push(pair.entry_delay_secs);
+        pairs.push(pair);
+    }
+    for (paper_trade, _) in paper.iter().zip(&paired).filter(|(_, paired)| !**paired) {
+        by_strategy.entry(paper_trade.strategy.as_str()).or_insert_with(|| empty(paper_trade.strategy)).paper_only += 1;
+    }
+
+    for (name, row) in by_strategy.iter_mut() {
+        row.shortfall_bps = staked.get(name)
+            .filter(|stake| **stake > Decimal::ZERO)
+            .map(|stake| (row.shortfall / stake * Decimal::from(10_000)).round_dp(1));
+        row.mean_entry_delay_secs = delays.get(name)
+            .map(|d| d.iter().sum::<i64>() as f64 / d.len() as f64);
+    }
+
+    ShortfallReport { strategies: by_strategy.into_values().collect(), pairs }
+}
+
+fn empty(strategy: Strategy) -> StrategyShortfall {
+    StrategyShortfall {
+        strategy,
+        matched: 0,
+        paper_only: 0,
+        live_only: 0,
+        paper_pnl: Decimal::ZERO,
+        live_pnl: Decimal::ZERO,
+        shortfall: Decimal::ZERO,
+        entry_cost: Decimal::ZERO,
+        exit_cost: Decimal::ZERO,
+        fees_and_gas_cost: Decimal::ZERO,
+        shortfall_bps: None,
+        mean_entry_delay_secs: None,
+    }
+}
+
+fn pair(live: &Trade, paper: &Trade) -> TradePair {
+    let quantity = live.quantity;
+    let paper_pnl = paper.pnl.unwrap_or_default().value() / paper.quantity * quantity;
+    let live_pnl = live.pnl.unwrap_or_default().value();
+    let shortfall = paper_pnl - live_pnl;
+
+    let entry_cost = (live.entry_price - paper.entry_price) * quantity;
+    let exit_cost = match (paper.exit_price, live.exit_price) {
+        (Some(paper_exit), Some(live_exit)) => (paper_exit - live_exit) * quantity,
+        _ => Decimal::ZERO,
+    };
+
+    TradePair {
+        live_trade_id: live.trade_id,
+        paper_trade_id: paper.trade_id,
+        market_id: live.market_id.clone(),
+        strategy: live.strategy,
+        entry_delay_secs: (live.entry_time - paper.entry_time).num_seconds(),
+        paper_pnl,
+        live_pnl,
+        shortfall,
+        entry_cost,
+        exit_cost,
+        fees_and_gas_cost: shortfall - entry_cost - exit_cost,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Position, TradeStatus, POLYMARKET_VENUE};
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn closed(quantity: Decimal, entry_price: Decimal, exit_price: Decimal, pnl: Decimal) -> Trade {
+        Trade {
+            trade_id: Uuid::new_v4(),
+            market_id: "0xmarket".to_string(),
+            strategy: Strategy::ClvArbitrage,
+            position: Position::Yes,
+            quantity,
+            entry_price,
+            exit_price: Some(exit_price),
+            entry_time: Utc::now(),
+            exit_time: Some(Utc::now()),
+            gas_cost: None,
+            fees: None,
+            slippage: None,
+            pnl: Some(Usdc::new(pnl)),
+            pnl_percent: None,
+            max_adverse_excursion: None,
+            max_favorable_excursion: None,
+            status: TradeStatus::Closed,
+            tx_hash_entry: None,
+            tx_hash_exit: None,
+            venue: POLYMARKET_VENUE.to_string(),
+            origin: TradeOrigin::Bot,
+        }
+    }
+
+    #[test]
+    fn splits_shortfall_into_entry_exit_and_fees() {
+        // Paper makes 0.20/share; live pays 0.02 more in, gets 0.01 less out and 2.00 in fees
+        let paper = closed(dec!(200), dec!(0.40), dec!(0.60), dec!(40));
+        let mut live = closed(dec!(100), dec!(0.42), dec!(0.59), dec!(15));
+        live.entry_time = paper.entry_time + Duration::seconds(4);
+
+        let report = shortfall_report(&[live], &[paper], Duration::minutes(5));
+        let pair = &report.pairs[0];
+        assert_eq!(pair.paper_pnl, dec!(20));
+        assert_eq!(pair.shortfall, dec!(5));
+        assert_eq!(pair.entry_cost, dec!(2));
+        assert_eq!(pair.exit_cost, dec!(1));
+        assert_eq!(pair.fees_and_gas_cost, dec!(2));
+        assert_eq!(pair.entry_delay_secs, 4);
+        assert_eq!(report.strategies[0].matched, 1);
+        assert_eq!(report.strategies[0].shortfall_bps, Some(dec!(1190.5)));
+
+        let paper = closed(dec!(100), dec!(0.40), dec!(0.60), dec!(20));
+        let late = Trade { entry_time: paper.entry_time + Duration::minutes(10), ..closed(dec!(100), dec!(0.42), dec!(0.59), dec!(15)) };
+        let report = shortfall_report(&[late], &[paper], Duration::minutes(5));
+        assert_eq!((report.strategies[0].live_only, report.strategies[0].paper_only), (1, 1));
+    }
+}
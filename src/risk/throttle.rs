@@ -0,0 +1,225 @@
+use anyhow::{Context, Result};
+use chrono::{Duration as ChronoDuration, Utc};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::config::{Config, ThrottleConfig};
+use crate::monitoring::Notifier;
+use crate::numeric;
+use crate::storage::Storage;
+use crate::types::{Decision, Strategy, StrategyThrottle, Trade, Usdc};
+
+/// Decision log kind for throttle adjustments
+const THROTTLE_DECISION: &str = "throttle";
+
+/// How throttle levels step with realized edge
+#[derive(Debug, Clone)]
+pub struct ThrottleSteps {
+    step: Decimal,
+    min_size_multiplier: Decimal,
+    recover_edge: Decimal,
+    /// Daily signal cap at full size
+    max_daily_signals: i64,
+}
+
+impl ThrottleSteps {
+    pub fn from_config(config: &ThrottleConfig, max_daily_trades: i32) -> Result<Self> {
+        Ok(Self {
+            step: numeric::to_decimal(config.step).context("risk.throttle.step")?,
+            min_size_multiplier: numeric::to_decimal(config.min_size_multiplier)
+                .context("risk.throttle.min_size_multiplier")?,
+            recover_edge: numeric::to_decimal(config.recover_edge).context("risk.throttle.recover_edge")?,
+            max_daily_signals: max_daily_trades as i64,
+        })
+    }
+
+    /// Size multiplier after one check: a step down while `edge` is negative,
+    /// a step back up once it clears `recover_edge`, otherwise unchanged
+    pub fn next_multiplier(&self, current: Decimal, edge: Decimal) -> Decimal {
+        if edge < Decimal::ZERO {
+            (current * self.step).max(self.min_size_multiplier).min(current)
+        } else if edge >= self.recover_edge && self.step > Decimal::ZERO {
+            (current / self.step).min(Decimal::ONE)
+        } else {
+            current
+        }
+    }
+
+    /// Daily signal cap at a size multiplier, never below one
+    pub fn max_daily_signals(&self, size_multiplier: Decimal) -> i64 {
+        let cap = (Decimal::from(self.max_daily_signals) * size_multiplier).ceil();
+        i64::try_from(cap).unwrap_or(self.max_daily_signals).max(1)
+    }
+}
+
+/// PnL over cost of the trades: the edge the strategy actually realized
+pub fn realized_edge(trades: &[&Trade]) -> Option<Decimal> {
+    let cost: Decimal = trades.iter().map(|t| t.position_size_usd()).sum();
+    let pnl: Decimal = trades.iter().filter_map(|t| t.pnl).sum::<Usdc>().value();
+    (cost > Decimal::ZERO).then(|| pnl / cost)
+}
+
+/// Throttles strategies whose rolling realized edge has turned negative
+///
+/// Each check moves a strategy at most one step, so sizing comes down and
+/// recovers gradually; every adjustment is written to the decision log.
+pub struct StrategyThrottler {
+    storage: Arc<dyn Storage>,
+    notifier: Notifier,
+    steps: ThrottleSteps,
+    window_trades: usize,
+    min_trades: usize,
+    lookback_days: i64,
+    check_interval: Duration,
+}
+
+impl StrategyThrottler {
+    pub fn new(storage: Arc<dyn Storage>, config: &Config, notifier: Notifier) -> Result<Self> {
+        let throttle = &config.risk.throttle;
+
+        Ok(Self {
+            storage,
+            notifier,
+            steps: ThrottleSteps::from_config(throttle, config.risk.max_daily_trades)?,
+            window_trades: throttle.window_trades.max(1),
+            min_trades: throttle.min_trades,
+            lookback_days: throttle.lookback_days,
+            check_interval: Duration::from_secs(throttle.check_interval_secs.max(1)),
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let mut tick = interval(self.check_interval);
+
+        info!("🎚️ Strategy throttler started");
+
+        loop {
+            tick.tick().await;
+
+            if let Err(e) = self.check().await {
+                error!("Error adjusting strategy throttles: {}", e);
+            }
+        }
+    }
+
+    async fn check(&self) -> Result<()> {
+        let since = Utc::now() - ChronoDuration::days(self.lookback_days);
+        let closed = self.storage.fetch_closed_trades(since).await?;
+
+        let mut by_strategy: BTreeMap<&str, (Strategy, Vec<&Trade>)> = BTreeMap::new();
+        for trade in closed.iter().filter(|t| t.strategy != Strategy::Manual && t.pnl.is_some()) {
+            by_strategy.entry(trade.strategy.as_str())
+                .or_insert_with(|| (trade.strategy, Vec::new()))
+                .1
+                .push(trade);
+        }
+
+        let throttles: HashMap<Strategy, StrategyThrottle> = self.storage.fetch_strategy_throttles().await?
+            .into_iter()
+            .map(|t| (t.strategy, t))
+            .collect();
+
+        for (strategy, trades) in by_strategy.into_values() {
+            if trades.len() < self.min_trades {
+                continue;
+            }
+            // Closed trades come oldest first
+            let window = &trades[trades.len().saturating_sub(self.window_trades)..];
+            let Some(edge) = realized_edge(window) else {
+                continue;
+            };
+
+            let current = throttles.get(&strategy).map_or(Decimal::ONE, |t| t.size_multiplier);
+            let next = self.steps.next_multiplier(current, edge);
+            if next != current {
+                self.adjust(strategy, current, next, edge, window.len()).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn adjust(&self, strategy: Strategy, from: Decimal, to: Decimal, edge: Decimal, trades: usize) -> Result<()> {
+        let max_daily_signals = self.steps.max_daily_signals(to);
+        let now = Utc::now();
+
+        if to >= Decimal::ONE {
+            self.storage.clear_strategy_throttle(strategy).await?;
+        } else {
+            self.storage.upsert_strategy_throttle(&StrategyThrottle {
+                strategy,
+                size_multiplier: to,
+                max_daily_signals,
+                realized_edge: edge,
+                updated_at: now,
+            }).await?;
+        }
+
+        let edge_pct = (edge * Decimal::ONE_HUNDRED).round_dp(2);
+        let summary = format!(
+            "{} {} to {}x size, {} signals/day (realized edge {}% over {} trades)",
+            strategy.as_str(),
+            if to < from { "throttled" } else { "restored" },
+            to.normalize(),
+            max_daily_signals,
+            edge_pct,
+            trades,
+        );
+
+        self.storage.insert_decision(&Decision {
+            decision_id: Uuid::new_v4(),
+            kind: THROTTLE_DECISION.to_string(),
+            subject: strategy.as_str().to_string(),
+            summary: summary.clone(),
+            detail: serde_json::json!({
+                "from_size_multiplier": from,
+                "to_size_multiplier": to,
+                "max_daily_signals": max_daily_signals,
+                "realized_edge": edge,
+                "trades": trades,
+            }),
+            decided_at: now,
+        }).await?;
+
+        info!("🎚️ {}", summary);
+        self.notifier.info(format!("🎚️ {}", summary));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn steps_down_to_the_floor_and_back_up_to_full_size() {
+        let steps = ThrottleSteps {
+            step: dec!(0.5),
+            min_size_multiplier: dec!(0.25),
+            recover_edge: dec!(0.01),
+            max_daily_signals: 20,
+        };
+
+        let mut size = Decimal::ONE;
+        for expected in [dec!(0.5), dec!(0.25), dec!(0.25)] {
+            size = steps.next_multiplier(size, dec!(-0.02));
+            assert_eq!(size, expected);
+        }
+        assert_eq!(steps.max_daily_signals(size), 5);
+
+        // Positive but under the recovery threshold holds the level
+        assert_eq!(steps.next_multiplier(size, dec!(0.005)), dec!(0.25));
+
+        size = steps.next_multiplier(size, dec!(0.03));
+        assert_eq!(size, dec!(0.5));
+        size = steps.next_multiplier(size, dec!(0.03));
+        assert_eq!(size, Decimal::ONE);
+        assert_eq!(steps.next_multiplier(size, dec!(0.03)), Decimal::ONE);
+    }
+}
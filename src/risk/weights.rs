@@ -0,0 +1,260 @@
+use anyhow::{Context, Result};
+use chrono::{Duration as ChronoDuration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::monitoring::Notifier;
+use crate::numeric;
+use crate::storage::Storage;
+use crate::types::{Decision, Strategy, StrategyWeight, Trade};
+
+/// Decision log kind for weight solves
+const WEIGHTS_DECISION: &str = "strategy_weights";
+
+const ITERATIONS: usize = 2000;
+const STEP: f64 = 0.05;
+const VARIANCE_FLOOR: f64 = 1e-12;
+
+/// Each strategy's daily PnL over `days`, as a fraction of `capital`, with
+/// zero on days it closed nothing
+pub fn daily_returns(trades: &[Trade], days: &[NaiveDate], capital: Decimal) -> Vec<(Strategy, Vec<f64>)> {
+    let index: BTreeMap<NaiveDate, usize> = days.iter().enumerate().map(|(i, d)| (*d, i)).collect();
+    let mut pnl: BTreeMap<&str, (Strategy, Vec<Decimal>)> = BTreeMap::new();
+    for trade in trades {
+        let (Some(exit), Some(trade_pnl)) = (trade.exit_time, trade.pnl) else {
+            continue;
+        };
+        let Some(&day) = index.get(&exit.date_naive()) else {
+            continue;
+        };
+        pnl.entry(trade.strategy.as_str())
+            .or_insert_with(|| (trade.strategy, vec![Decimal::ZERO; days.len()]))
+            .1[day] += trade_pnl.value();
+    }
+
+    pnl.into_values()
+        .map(|(strategy, pnl)| {
+            let returns = pnl.into_iter()
+                .map(|p| p.checked_div(capital).and_then(|r| numeric::to_f64(r).ok()).unwrap_or(0.0))
+                .collect();
+            (strategy, returns)
+        })
+        .collect()
+}
+
+/// Sample means and covariance matrix of equal-length return series
+pub fn moments(series: &[&[f64]]) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let n = series.first().map_or(0, |s| s.len());
+    let means: Vec<f64> = series.iter().map(|s| s.iter().sum::<f64>() / n.max(1) as f64).collect();
+    let cov = series.iter().zip(&means)
+        .map(|(a, ma)| {
+            series.iter().zip(&means)
+                .map(|(b, mb)| {
+                    let sum: f64 = a.iter().zip(b.iter()).map(|(x, y)| (x - ma) * (y - mb)).sum();
+                    sum / n.saturating_sub(1).max(1) as f64
+                })
+                .collect()
+        })
+        .collect();
+    (means, cov)
+}
+
+fn sharpe(weights: &[f64], means: &[f64], cov: &[Vec<f64>]) -> f64 {
+    let ret: f64 = weights.iter().zip(means).map(|(w, m)| w * m).sum();
+    let variance: f64 = weights.iter().enumerate()
+        .map(|(i, wi)| weights.iter().enumerate().map(|(j, wj)| wi * cov[i][j] * wj).sum::<f64>())
+        .sum();
+    ret / variance.max(VARIANCE_FLOOR).sqrt()
+}
+
+/// Euclidean projection onto {w : sum(w) = 1, 0 <= w <= cap}
+fn project(v: &[f64], cap: f64) -> Vec<f64> {
+    let clamp = |tau: f64| -> Vec<f64> { v.iter().map(|x| (x - tau).clamp(0.0, cap)).collect() };
+    let mut lo = v.iter().copied().fold(f64::INFINITY, f64::min) - cap;
+    let mut hi = v.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    for _ in 0..100 {
+        let mid = (lo + hi) / 2.0;
+        if clamp(mid).iter().sum::<f64>() > 1.0 {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    clamp((lo + hi) / 2.0)
+}
+
+/// Long-only, fully invested weights maximizing portfolio Sharpe, each at
+/// most `max_weight` (raised to an equal split when that can't sum to one)
+///
+/// Sharpe is not concave over the simplex in general, so this is projected
+/// gradient ascent from equal weights keeping the best point seen.
+pub fn sharpe_weights(means: &[f64], cov: &[Vec<f64>], max_weight: f64) -> Vec<f64> {
+    let n = means.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let cap = max_weight.max(1.0 / n as f64);
+
+    let mut weights = vec![1.0 / n as f64; n];
+    let mut best = (sharpe(&weights, means, cov), weights.clone());
+    for k in 0..ITERATIONS {
+        let ret: f64 = weights.iter().zip(means).map(|(w, m)| w * m).sum();
+        let cov_w: Vec<f64> = cov.iter().map(|row| row.iter().zip(&weights).map(|(c, w)| c * w).sum()).collect();
+        let variance = weights.iter().zip(&cov_w).map(|(w, c)| w * c).sum::<f64>().max(VARIANCE_FLOOR);
+        let sd = variance.sqrt();
+
+        let grad: Vec<f64> = means.iter().zip(&cov_w).map(|(m, c)| m / sd - ret * c / (variance * sd)).collect();
+        let norm = grad.iter().map(|g| g * g).sum::<f64>().sqrt();
+        if norm == 0.0 || !norm.is_finite() {
+            break;
+        }
+
+        let step = STEP / ((k + 1) as f64).sqrt();
+        let stepped: Vec<f64> = weights.iter().zip(&grad).map(|(w, g)| w + step * g / norm).collect();
+        weights = project(&stepped, cap);
+
+        let value = sharpe(&weights, means, cov);
+        if value > best.0 {
+            best = (value, weights.clone());
+        }
+    }
+
+    best.1
+}
+
+/// Periodically solves for the Sharpe-optimal split of capital across
+/// strategies from their daily returns
+///
+/// Strategies with fewer than `min_days` days of closed trades are left
+/// unweighted, and the risk manager sizes them conservatively; it scales
+/// the rest by their weight relative to the largest, down to a floor.
+pub struct WeightOptimizer {
+    storage: Arc<dyn Storage>,
+    notifier: Notifier,
+    capital: Decimal,
+    max_weight: f64,
+    lookback_days: i64,
+    min_days: usize,
+    check_interval: Duration,
+}
+
+impl WeightOptimizer {
+    pub fn new(storage: Arc<dyn Storage>, config: &Config, notifier: Notifier) -> Result<Self> {
+        let weights = &config.risk.weights;
+
+        Ok(Self {
+            storage,
+            notifier,
+            capital: numeric::to_decimal(config.risk.starting_capital).context("risk.starting_capital")?,
+            max_weight: config.risk.exposure_limits.strategy_pct / 100.0,
+            lookback_days: weights.lookback_days.max(1),
+            min_days: weights.min_days.max(2),
+            check_interval: Duration::from_secs(weights.check_interval_secs.max(1)),
+        })
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let mut tick = interval(self.check_interval);
+
+        info!("⚖️ Strategy weight optimizer started");
+
+        loop {
+            tick.tick().await;
+
+            if let Err(e) = self.solve().await {
+                error!("Error solving strategy weights: {}", e);
+            }
+        }
+    }
+
+    async fn solve(&self) -> Result<()> {
+        let today = Utc::now().date_naive();
+        let days: Vec<NaiveDate> = (0..self.lookback_days)
+            .rev()
+            .map(|back| today - ChronoDuration::days(back))
+            .collect();
+        let since = Utc::now() - ChronoDuration::days(self.lookback_days);
+        let closed: Vec<Trade> = self.storage.fetch_closed_trades(since).await?
+            .into_iter()
+            .filter(|t| t.strategy != Strategy::Manual)
+            .collect();
+
+        let eligible: Vec<(Strategy, Vec<f64>)> = daily_returns(&closed, &days, self.capital)
+            .into_iter()
+            .filter(|(_, returns)| returns.iter().filter(|r| **r != 0.0).count() >= self.min_days)
+            .collect();
+        if eligible.is_empty() {
+            return Ok(());
+        }
+
+        let series: Vec<&[f64]> = eligible.iter().map(|(_, r)| r.as_slice()).collect();
+        let (means, cov) = moments(&series);
+        let solved = sharpe_weights(&means, &cov, self.max_weight);
+
+        let now = Utc::now();
+        let weights = eligible.iter().enumerate()
+            .map(|(i, (strategy, _))| {
+                Ok(StrategyWeight {
+                    strategy: *strategy,
+                    weight: numeric::to_decimal(solved[i])?.round_dp(4),
+                    mean_return: numeric::to_decimal(means[i])?.round_dp(8),
+                    volatility: numeric::to_decimal(cov[i][i].sqrt())?.round_dp(8),
+                    updated_at: now,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.storage.replace_strategy_weights(&weights).await?;
+
+        let summary = format!(
+            "strategy weights: {}",
+            weights.iter()
+                .map(|w| format!("{} {}%", w.strategy.as_str(), (w.weight * Decimal::ONE_HUNDRED).round_dp(1)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        self.storage.insert_decision(&Decision {
+            decision_id: Uuid::new_v4(),
+            kind: WEIGHTS_DECISION.to_string(),
+            subject: "portfolio".to_string(),
+            summary: summary.clone(),
+            detail: serde_json::json!({
+                "weights": weights,
+                "portfolio_sharpe": sharpe(&solved, &means, &cov),
+                "days": days.len(),
+            }),
+            decided_at: now,
+        }).await?;
+
+        info!("⚖️ {}", summary);
+        self.notifier.info(format!("⚖️ {}", summary));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tilts_toward_the_lower_variance_strategy_within_the_cap() {
+        // Equal means, uncorrelated, variances 1 and 4: tangency is 0.8 / 0.2
+        let cov = vec![vec![1.0, 0.0], vec![0.0, 4.0]];
+        let weights = sharpe_weights(&[0.1, 0.1], &cov, 1.0);
+        assert!((weights[0] - 0.8).abs() < 0.01, "{:?}", weights);
+        assert!((weights.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+
+        let capped = sharpe_weights(&[0.1, 0.1], &cov, 0.6);
+        assert!((capped[0] - 0.6).abs() < 1e-6, "{:?}", capped);
+
+        // A cap too tight to sum to one falls back to an equal split
+        let identity = [vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 1.0]];
+        let equal = sharpe_weights(&[0.1, 0.2, 0.3], &identity, 0.2);
+        assert!(equal.iter().all(|w| (w - 1.0 / 3.0).abs() < 1e-6), "{:?}", equal);
+    }
+}
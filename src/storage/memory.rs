@@ -0,0 +1,1288 @@
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::types::{
+    BookmakerOdds, CapitalPoint, CashFlow, Decision, EntryOrder, FeedLatency, Game, GameFeature, HomeAdvantageEstimate, InjuryAdjustment, Market,
+    MarketStatus, MarketTokens, OfficialAssignment, OfficialTendency, PortfolioState, Quote, Signal,
+    Position, PredictionOutcome, Price, RestingOrder, RestingOrderStatus, ShadowedStrategy, SlippageEvent, Sport, Strategy,
+    StrategyThrottle, TeamRating, Trade, TradeMark, TradeStatus, ConvergenceSample, SettlementReview, StrategyWeight, SignalExecution, EdgeDecay, TradeSizing, OnchainFill, MarketMicrostructure, BookSnapshot, LargeTrade, PricePoint, PreEventHistory, Resolution, SealedCredential, Usdc,
+    MarketListing, CoveredMarket, TradeOutcome, TradeSettlement, DeadLetter,
+    FailedExecution, ParentOrder, ChildOrder, ProbablePitcher, BullpenUsage, ExecutionCost,
+};
+use super::{
+    CashFlowRepo, CredentialRepo, DecisionRepo, FeatureRepo, MarketRepo, PortfolioBalances, PortfolioRepo, SeasonRepo, SignalRepo, TradeRepo,
+    VenueBalances,
+};
+
+struct StoredSignal {
+    signal: Signal,
+    executed: bool,
+    executed_trade_id: Option<Uuid>,
+    /// Owner and lease end of the consumer processing the signal
+    claim: Option<(String, DateTime<Utc>)>,
+    /// Executable price when the engine last took the signal up
+    execution_quote: Option<(Price, DateTime<Utc>)>,
+}
+
+struct StoredBreaker {
+    reason: String,
+    metadata: serde_json::Value,
+    active: bool,
+}
+
+#[derive(Default)]
+struct MemoryState {
+    markets: HashMap<String, Market>,
+    bookmaker_odds: Vec<BookmakerOdds>,
+    feed_latency: Vec<FeedLatency>,
+    microstructure: Vec<MarketMicrostructure>,
+    books: HashMap<String, BookSnapshot>,
+    listings: HashMap<Sport, MarketListing>,
+    /// By (source, item key, reason)
+    dead_letters: HashMap<(String, String, String), DeadLetter>,
+    /// Last evaluation by (market id, strategy name)
+    evaluations: HashMap<(String, String), DateTime<Utc>>,
+    large_trades: HashMap<(String, String, String, String), LargeTrade>,
+    resolutions: HashMap<String, (Resolution, DateTime<Utc>)>,
+    price_history: HashMap<(String, DateTime<Utc>), PricePoint>,
+    signals: Vec<StoredSignal>,
+    trades: HashMap<Uuid, Trade>,
+    slippage_events: Vec<SlippageEvent>,
+    execution_costs: Vec<ExecutionCost>,
+    cash_flows: Vec<CashFlow>,
+    snapshots: Vec<PortfolioState>,
+    circuit_breakers: Vec<StoredBreaker>,
+    team_ratings: Vec<TeamRating>,
+    games: Vec<Game>,
+    injuries: Vec<InjuryAdjustment>,
+    official_assignments: Vec<OfficialAssignment>,
+    probable_pitchers: Vec<ProbablePitcher>,
+    bullpen_usage: Vec<BullpenUsage>,
+    game_features: Vec<GameFeature>,
+    home_advantage: Vec<HomeAdvantageEstimate>,
+    trade_marks: HashMap<Uuid, TradeMark>,
+    convergence: Vec<ConvergenceSample>,
+    trade_sizing: HashMap<Uuid, TradeSizing>,
+    settlement_reviews: HashMap<Uuid, SettlementReview>,
+    trade_settlements: HashMap<Uuid, TradeSettlement>,
+    failed_executions: HashMap<Uuid, FailedExecution>,
+    /// Fill events by (tx hash, log index)
+    onchain_fills: HashMap<(String, i64), OnchainFill>,
+    /// Open resting orders by order id; filled and cancelled ones are dropped
+    resting_orders: HashMap<String, RestingOrder>,
+    entry_orders: HashMap<String, EntryOrder>,
+    /// Open parent orders by id; filled and cancelled ones are dropped
+    parent_orders: HashMap<Uuid, ParentOrder>,
+    child_orders: Vec<ChildOrder>,
+    /// Encrypted credentials by (provider, name, version)
+    credentials: HashMap<(String, String, i32), SealedCredential>,
+    /// (yes, no) closing prices by market
+    closing_lines: HashMap<String, (Price, Price)>,
+    shadowed: Vec<ShadowedStrategy>,
+    throttles: HashMap<Strategy, StrategyThrottle>,
+    weights: Vec<StrategyWeight>,
+    edge_decays: Vec<EdgeDecay>,
+    decisions: Vec<Decision>,
+}
+
+/// Non-persistent storage for hermetic tests of engine and risk logic
+#[derive(Default)]
+pub struct MemoryStorage {
+    state: RwLock<MemoryState>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All trades regardless of status
+    pub async fn trades(&self) -> Vec<Trade> {
+        self.state.read().await.trades.values().cloned().collect()
+    }
+
+    /// Trade id recorded against a signal, if it was executed into a trade
+    pub async fn executed_trade_for(&self, signal_id: Uuid) -> Option<Uuid> {
+        self.state.read().await.signals.iter()
+            .find(|s| s.signal.signal_id == signal_id)
+            .and_then(|s| s.executed_trade_id)
+    }
+
+    /// Fills recorded as slipping past tolerance, oldest first
+    pub async fn slippage_events(&self) -> Vec<SlippageEvent> {
+        self.state.read().await.slippage_events.clone()
+    }
+
+    /// Reasons of breakers that have been triggered and not cleared
+    pub async fn active_circuit_breakers(&self) -> Vec<(String, serde_json::Value)> {
+        self.state.read().await.circuit_breakers.iter()
+            .filter(|b| b.active)
+            .map(|b| (b.reason.clone(), b.metadata.clone()))
+            .collect()
+    }
+
+    /// Load ratings and a schedule, replacing any for the same sports
+    pub async fn seed_season(&self, team_ratings: Vec<TeamRating>, games: Vec<Game>) {
+        let mut state = self.state.write().await;
+        state.team_ratings.retain(|t| !team_ratings.iter().any(|n| n.sport == t.sport));
+        state.games.retain(|g| !games.iter().any(|n| n.sport == g.sport));
+        state.team_ratings.extend(team_ratings);
+        state.games.extend(games);
+    }
+
+    pub async fn insert_injury(&self, injury: InjuryAdjustment) {
+        self.state.write().await.injuries.push(injury);
+    }
+
+    fn start_of_today() -> DateTime<Utc> {
+        Utc::now()
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is a valid time")
+            .and_utc()
+    }
+}
+
+#[async_trait]
+impl MarketRepo for MemoryStorage {
+    async fn fetch_active_markets(&self, min_liquidity: Decimal, limit: i64) -> Result<Vec<Market>> {
+        let state = self.state.read().await;
+        let now = Utc::now();
+
+        let mut markets: Vec<Market> = state.markets.values()
+            .filter(|m| m.status == MarketStatus::Active)
+            .filter(|m| m.event_time > now && m.current_liquidity >= min_liquidity)
+            .cloned()
+            .collect();
+        markets.sort_by_key(|m| m.event_time);
+        markets.truncate(limit.max(0) as usize);
+
+        Ok(markets)
+    }
+
+    async fn fetch_market(&self, market_id: &str) -> Result<Option<Market>> {
+        Ok(self.state.read().await.markets.get(market_id).cloned())
+    }
+
+    async fn fetch_market_quotes(&self, market_id: &str) -> Result<(Quote, Quote)> {
+        let state = self.state.read().await;
+        let market = state.markets.get(market_id)
+            .ok_or_else(|| anyhow!("Market {} not found", market_id))?;
+
+        Ok((market.yes_quote, market.no_quote))
+    }
+
+    async fn fetch_bookmaker_odds(&self, market_id: &str) -> Result<Vec<BookmakerOdds>> {
+        let state = self.state.read().await;
+        let cutoff = Utc::now() - Duration::hours(1);
+
+        let mut latest: Vec<BookmakerOdds> = Vec::new();
+        for odds in state.bookmaker_odds.iter()
+            .filter(|o| o.market_id == market_id && o.timestamp > cutoff)
+        {
+            match latest.iter_mut().find(|o| o.bookmaker == odds.bookmaker) {
+                Some(existing) if existing.timestamp < odds.timestamp => *existing = odds.clone(),
+                Some(_) => {}
+                None => latest.push(odds.clone()),
+            }
+        }
+
+        Ok(latest)
+    }
+
+    async fn insert_bookmaker_odds(&self, odds: &BookmakerOdds) -> Result<()> {
+        self.state.write().await.bookmaker_odds.push(odds.clone());
+        Ok(())
+    }
+
+    async fn insert_feed_latency(&self, latency: &FeedLatency) -> Result<()> {
+        self.state.write().await.feed_latency.push(latency.clone());
+        Ok(())
+    }
+
+    async fn fetch_feed_latency(&self, since: DateTime<Utc>) -> Result<Vec<FeedLatency>> {
+        Ok(self.state.read().await.feed_latency.iter()
+            .filter(|l| l.measured_at >= since)
+            .cloned()
+            .collect())
+    }
+
+    async fn upsert_market(&self, market: &Market) -> Result<()> {
+        let mut state = self.state.write().await;
+        state.markets.insert(market.market_id.clone(), market.clone());
+        Ok(())
+    }
+
+    async fn update_market_quotes(&self, market_id: &str, yes_quote: &Quote, no_quote: &Quote) -> Result<()> {
+        let mut state = self.state.write().await;
+        if let Some(market) = state.markets.get_mut(market_id) {
+            market.yes_quote = *yes_quote;
+            market.no_quote = *no_quote;
+            market.updated_at = Utc::now();
+        }
+        Ok(())
+    }
+
+    async fn fetch_market_tokens(&self, market_id: &str) -> Result<Option<MarketTokens>> {
+        let state = self.state.read().await;
+        Ok(state.markets.get(market_id).and_then(|m| m.tokens.clone()))
+    }
+
+    async fn fetch_market_status(&self, market_id: &str) -> Result<Option<MarketStatus>> {
+        let state = self.state.read().await;
+        Ok(state.markets.get(market_id).map(|m| m.status))
+    }
+
+    async fn insert_microstructure(&self, features: &MarketMicrostructure) -> Result<()> {
+        self.state.write().await.microstructure.push(features.clone());
+        Ok(())
+    }
+
+    async fn latest_microstructure(&self, market_id: &str) -> Result<Option<MarketMicrostructure>> {
+        Ok(self.state.read().await.microstructure.iter()
+            .filter(|m| m.market_id == market_id)
+            .max_by_key(|m| m.observed_at)
+            .cloned())
+    }
+
+    async fn upsert_book_snapshot(&self, book: &BookSnapshot) -> Result<()> {
+        self.state.write().await.books.insert(book.market_id.clone(), book.clone());
+        Ok(())
+    }
+
+    async fn fetch_book_snapshot(&self, market_id: &str) -> Result<Option<BookSnapshot>> {
+        Ok(self.state.read().await.books.get(market_id).cloned())
+    }
+
+    async fn upsert_market_listing(&self, listing: &MarketListing) -> Result<()> {
+        self.state.write().await.listings.insert(listing.sport, listing.clone());
+        Ok(())
+    }
+
+    async fn fetch_market_listings(&self) -> Result<Vec<MarketListing>> {
+        Ok(self.state.read().await.listings.values().cloned().collect())
+    }
+
+    async fn record_market_evaluations(&self, strategy: &str, market_ids: &[String], evaluated_at: DateTime<Utc>) -> Result<()> {
+        let mut state = self.state.write().await;
+        for market_id in market_ids {
+            state.evaluations.insert((market_id.clone(), strategy.to_string()), evaluated_at);
+        }
+        Ok(())
+    }
+
+    async fn fetch_covered_markets(&self, since: DateTime<Utc>) -> Result<Vec<CoveredMarket>> {
+        let state = self.state.read().await;
+        let now = Utc::now();
+
+        Ok(state.markets.values()
+            .filter(|m| m.status == MarketStatus::Active && m.event_time > now)
+            .map(|m| {
+                let mut strategies: Vec<String> = state.evaluations.iter()
+                    .filter(|((market_id, _), at)| *market_id == m.market_id && **at >= since)
+                    .map(|((_, strategy), _)| strategy.clone())
+                    .collect();
+                strategies.sort();
+                CoveredMarket {
+                    market_id: m.market_id.clone(),
+                    sport: m.sport,
+                    matched: state.bookmaker_odds.iter().any(|o| o.market_id == m.market_id && o.timestamp >= since),
+                    strategies,
+                }
+            })
+            .collect())
+    }
+
+    async fn record_dead_letter(&self, source: &str, item_key: &str, reason: &str, raw: &serde_json::Value, seen_at: DateTime<Utc>) -> Result<()> {
+        let mut state = self.state.write().await;
+        let key = (source.to_string(), item_key.to_string(), reason.to_string());
+        let letter = state.dead_letters.entry(key).or_insert_with(|| DeadLetter {
+            source: source.to_string(),
+            item_key: item_key.to_string(),
+            reason: reason.to_string(),
+            raw: raw.clone(),
+            occurrences: 0,
+            first_seen: seen_at,
+            last_seen: seen_at,
+        });
+        letter.raw = raw.clone();
+        letter.occurrences += 1;
+        letter.last_seen = seen_at;
+        Ok(())
+    }
+
+    async fn fetch_dead_letters(&self, since: DateTime<Utc>) -> Result<Vec<DeadLetter>> {
+        let mut letters: Vec<DeadLetter> = self.state.read().await.dead_letters.values()
+            .filter(|l| l.last_seen >= since)
+            .cloned()
+            .collect();
+        letters.sort_by(|a, b| b.last_seen.cmp(&a.last_seen));
+        Ok(letters)
+    }
+
+    async fn insert_large_trade(&self, trade: &LargeTrade) -> Result<bool> {
+        let mut state = self.state.write().await;
+        let key = (
+            trade.tx_hash.clone(),
+            trade.wallet.clone(),
+            trade.position.as_str().to_string(),
+            trade.side.as_str().to_string(),
+        );
+        if state.large_trades.contains_key(&key) {
+            return Ok(false);
+        }
+        state.large_trades.insert(key, trade.clone());
+        Ok(true)
+    }
+
+    async fn fetch_large_trades(&self, since: DateTime<Utc>, market_id: Option<&str>) -> Result<Vec<LargeTrade>> {
+        let mut trades: Vec<LargeTrade> = self.state.read().await.large_trades.values()
+            .filter(|t| t.traded_at >= since && market_id.map_or(true, |m| t.market_id == m))
+            .cloned()
+            .collect();
+        trades.sort_by(|a, b| b.traded_at.cmp(&a.traded_at));
+        Ok(trades)
+    }
+
+    async fn set_market_resolution(&self, market_id: &str, resolution: Resolution, resolved_at: DateTime<Utc>) -> Result<()> {
+        self.state.write().await.resolutions.insert(market_id.to_string(), (resolution, resolved_at));
+        Ok(())
+    }
+
+    async fn insert_price_history(&self, points: &[PricePoint]) -> Result<u64> {
+        let mut state = self.state.write().await;
+        let mut inserted = 0;
+        for point in points {
+            let key = (point.market_id.clone(), point.observed_at);
+            if !state.price_history.contains_key(&key) {
+                state.price_history.insert(key, point.clone());
+                inserted += 1;
+            }
+        }
+        Ok(inserted)
+    }
+
+    async fn last_price_history_at(&self, market_id: &str) -> Result<Option<DateTime<Utc>>> {
+        Ok(self.state.read().await.price_history.keys()
+            .filter(|(id, _)| id == market_id)
+            .map(|(_, at)| *at)
+            .max())
+    }
+
+    async fn fetch_pre_event_price_history(&self, since: DateTime<Utc>) -> Result<PreEventHistory> {
+        let state = self.state.read().await;
+        let now = Utc::now();
+        let mut history = PreEventHistory::default();
+        for point in state.price_history.values() {
+            let Some(market) = state.markets.get(&point.market_id) else { continue };
+            if market.event_time >= since && market.event_time <= now && point.observed_at < market.event_time {
+                history.points.push(point.clone());
+                history.event_times.insert(market.market_id.clone(), market.event_time);
+            }
+        }
+        history.points.sort_by(|a, b| (&a.market_id, a.observed_at).cmp(&(&b.market_id, b.observed_at)));
+        Ok(history)
+    }
+
+    async fn upsert_closing_line(
+        &self,
+        market_id: &str,
+        yes_price: Price,
+        no_price: Price,
+        _captured_at: DateTime<Utc>,
+    ) -> Result<()> {
+        self.state.write().await.closing_lines.insert(market_id.to_string(), (yes_price, no_price));
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SignalRepo for MemoryStorage {
+    async fn insert_signal(&self, signal: &Signal) -> Result<()> {
+        self.state.write().await.signals.push(StoredSignal {
+            signal: signal.clone(),
+            executed: false,
+            executed_trade_id: None,
+            claim: None,
+            execution_quote: None,
+        });
+        Ok(())
+    }
+
+    async fn fetch_pending_signals(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<Signal>> {
+        let state = self.state.read().await;
+
+        let mut signals: Vec<Signal> = state.signals.iter()
+            .filter(|s| !s.executed && s.signal.generated_at > since)
+            .map(|s| s.signal.clone())
+            .collect();
+        signals.sort_by(|a, b| {
+            b.confidence.cmp(&a.confidence).then(b.edge_size.cmp(&a.edge_size))
+        });
+        signals.truncate(limit.max(0) as usize);
+
+        Ok(signals)
+    }
+
+    async fn claim_pending_signals(
+        &self,
+        since: DateTime<Utc>,
+        limit: i64,
+        owner: &str,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<Signal>> {
+        let now = Utc::now();
+        let mut state = self.state.write().await;
+
+        let mut claimable: Vec<&mut StoredSignal> = state.signals.iter_mut()
+            .filter(|s| !s.executed && s.signal.generated_at > since)
+            .filter(|s| s.claim.as_ref().map_or(true, |(_, expires)| *expires <= now))
+            .collect();
+        claimable.sort_by(|a, b| {
+            b.signal.confidence.cmp(&a.signal.confidence).then(b.signal.edge_size.cmp(&a.signal.edge_size))
+        });
+        claimable.truncate(limit.max(0) as usize);
+
+        Ok(claimable.into_iter()
+            .map(|s| {
+                s.claim = Some((owner.to_string(), until));
+                s.signal.clone()
+            })
+            .collect())
+    }
+
+    async fn release_signal(&self, signal_id: Uuid, owner: &str) -> Result<()> {
+        let mut state = self.state.write().await;
+        if let Some(stored) = state.signals.iter_mut()
+            .find(|s| s.signal.signal_id == signal_id && matches!(&s.claim, Some((o, _)) if o == owner))
+        {
+            stored.claim = None;
+        }
+        Ok(())
+    }
+
+    async fn renew_signal_claim(&self, signal_id: Uuid, owner: &str, until: DateTime<Utc>) -> Result<bool> {
+        let mut state = self.state.write().await;
+        match state.signals.iter_mut().find(|s| {
+            s.signal.signal_id == signal_id && !s.executed && matches!(&s.claim, Some((o, _)) if o == owner)
+        }) {
+            Some(stored) => {
+                stored.claim = Some((owner.to_string(), until));
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn fetch_signal(&self, signal_id: Uuid) -> Result<Option<Signal>> {
+        let state = self.state.read().await;
+        Ok(state.signals.iter().find(|s| s.signal.signal_id == signal_id).map(|s| s.signal.clone()))
+    }
+
+    async fn fetch_market_signals(&self, market_id: &str, since: DateTime<Utc>) -> Result<Vec<Signal>> {
+        let state = self.state.read().await;
+        let mut signals: Vec<Signal> = state.signals.iter()
+            .map(|s| &s.signal)
+            .filter(|s| s.market_id == market_id && s.generated_at >= since)
+            .cloned()
+            .collect();
+        signals.sort_by_key(|s| s.generated_at);
+        Ok(signals)
+    }
+
+    async fn upsert_failed_execution(&self, failure: &FailedExecution) -> Result<()> {
+        self.state.write().await.failed_executions.insert(failure.signal_id, failure.clone());
+        Ok(())
+    }
+
+    async fn fetch_failed_execution(&self, signal_id: Uuid) -> Result<Option<FailedExecution>> {
+        Ok(self.state.read().await.failed_executions.get(&signal_id).cloned())
+    }
+
+    async fn fetch_failed_executions(&self, since: DateTime<Utc>) -> Result<Vec<FailedExecution>> {
+        let mut failures: Vec<FailedExecution> = self.state.read().await.failed_executions.values()
+            .filter(|f| f.updated_at >= since)
+            .cloned()
+            .collect();
+        failures.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        Ok(failures)
+    }
+
+    async fn latest_fair_value(&self, market_id: &str, position: Position) -> Result<Option<Decimal>> {
+        let state = self.state.read().await;
+        Ok(state.signals.iter()
+            .map(|s| &s.signal)
+            .filter(|s| s.market_id == market_id && s.signal_type.to_position() == position)
+            .max_by_key(|s| s.generated_at)
+            .map(|s| s.fair_value.value()))
+    }
+
+    async fn mark_signal_executed(&self, signal_id: Uuid, trade_id: Option<Uuid>) -> Result<()> {
+        let mut state = self.state.write().await;
+        if let Some(stored) = state.signals.iter_mut().find(|s| s.signal.signal_id == signal_id) {
+            stored.executed = true;
+            stored.executed_trade_id = trade_id;
+        }
+        Ok(())
+    }
+
+    async fn shadow_strategy(&self, strategy: Strategy, reason: &str) -> Result<()> {
+        let mut state = self.state.write().await;
+        if !state.shadowed.iter().any(|s| s.strategy == strategy) {
+            state.shadowed.push(ShadowedStrategy { strategy, reason: reason.to_string(), since: Utc::now() });
+        }
+        Ok(())
+    }
+
+    async fn unshadow_strategy(&self, strategy: Strategy) -> Result<()> {
+        self.state.write().await.shadowed.retain(|s| s.strategy != strategy);
+        Ok(())
+    }
+
+    async fn fetch_shadowed_strategies(&self) -> Result<Vec<ShadowedStrategy>> {
+        Ok(self.state.read().await.shadowed.clone())
+    }
+
+    async fn upsert_strategy_throttle(&self, throttle: &StrategyThrottle) -> Result<()> {
+        self.state.write().await.throttles.insert(throttle.strategy, throttle.clone());
+        Ok(())
+    }
+
+    async fn clear_strategy_throttle(&self, strategy: Strategy) -> Result<()> {
+        self.state.write().await.throttles.remove(&strategy);
+        Ok(())
+    }
+
+    async fn fetch_strategy_throttles(&self) -> Result<Vec<StrategyThrottle>> {
+        Ok(self.state.read().await.throttles.values().cloned().collect())
+    }
+
+    async fn replace_strategy_weights(&self, weights: &[StrategyWeight]) -> Result<()> {
+        self.state.write().await.weights = weights.to_vec();
+        Ok(())
+    }
+
+    async fn fetch_strategy_weights(&self) -> Result<Vec<StrategyWeight>> {
+        Ok(self.state.read().await.weights.clone())
+    }
+
+    async fn record_execution_quote(&self, signal_id: Uuid, price: Price, at: DateTime<Utc>) -> Result<()> {
+        let mut state = self.state.write().await;
+        if let Some(stored) = state.signals.iter_mut().find(|s| s.signal.signal_id == signal_id) {
+            stored.execution_quote = Some((price, at));
+        }
+        Ok(())
+    }
+
+    async fn fetch_signal_executions(&self, since: DateTime<Utc>) -> Result<Vec<SignalExecution>> {
+        let state = self.state.read().await;
+        let mut executions: Vec<SignalExecution> = state.signals.iter()
+            .filter_map(|stored| {
+                let (price, at) = stored.execution_quote?;
+                (at >= since).then(|| SignalExecution {
+                    strategy: stored.signal.strategy,
+                    signal_price: stored.signal.current_price.value(),
+                    fair_value: stored.signal.fair_value.value(),
+                    generated_at: stored.signal.generated_at,
+                    quoted_price: price.value(),
+                    quoted_at: at,
+                })
+            })
+            .collect();
+        executions.sort_by_key(|e| e.quoted_at);
+        Ok(executions)
+    }
+
+    async fn replace_edge_decays(&self, decays: &[EdgeDecay]) -> Result<()> {
+        self.state.write().await.edge_decays = decays.to_vec();
+        Ok(())
+    }
+
+    async fn fetch_edge_decays(&self) -> Result<Vec<EdgeDecay>> {
+        Ok(self.state.read().await.edge_decays.clone())
+    }
+}
+
+#[async_trait]
+impl TradeRepo for MemoryStorage {
+    async fn insert_trade(&self, trade: &Trade) -> Result<()> {
+        self.state.write().await.trades.insert(trade.trade_id, trade.clone());
+        Ok(())
+    }
+
+    async fn fetch_open_trades(&self) -> Result<Vec<Trade>> {
+        let state = self.state.read().await;
+        Ok(state.trades.values()
+            .filter(|t| t.status == TradeStatus::Open)
+            .cloned()
+            .collect())
+    }
+
+    async fn fetch_trade(&self, trade_id: Uuid) -> Result<Option<Trade>> {
+        Ok(self.state.read().await.trades.get(&trade_id).cloned())
+    }
+
+    async fn fetch_trade_by_entry_tx(&self, tx_hash: &str) -> Result<Option<Trade>> {
+        Ok(self.state.read().await.trades.values()
+            .find(|t| t.tx_hash_entry.as_deref() == Some(tx_hash))
+            .cloned())
+    }
+
+    async fn fetch_closed_trades(&self, since: DateTime<Utc>) -> Result<Vec<Trade>> {
+        let state = self.state.read().await;
+
+        let mut closed: Vec<Trade> = state.trades.values()
+            .filter(|t| t.status == TradeStatus::Closed && t.exit_time.map_or(false, |exit| exit >= since))
+            .cloned()
+            .collect();
+        closed.sort_by_key(|t| t.exit_time);
+
+        Ok(closed)
+    }
+
+    async fn fetch_prediction_outcomes(&self, since: DateTime<Utc>) -> Result<Vec<PredictionOutcome>> {
+        let closed = self.fetch_closed_trades(since).await?;
+        let state = self.state.read().await;
+
+        Ok(closed.into_iter()
+            .filter_map(|trade| {
+                let signal = state.signals.iter()
+                    .find(|s| s.executed_trade_id == Some(trade.trade_id))?;
+                let closing_price = state.closing_lines.get(&trade.market_id).map(|(yes, no)| match trade.position {
+                    Position::Yes => yes.value(),
+                    Position::No => no.value(),
+                });
+
+                Some(PredictionOutcome {
+                    trade_id: trade.trade_id,
+                    strategy: trade.strategy,
+                    fair_value: signal.signal.fair_value.value(),
+                    entry_price: trade.entry_price,
+                    exit_price: trade.exit_price?,
+                    payout: state.trade_settlements.get(&trade.trade_id)
+                        .filter(|s| s.outcome != TradeOutcome::Void)
+                        .map(|s| s.payout),
+                    closing_price,
+                    exit_time: trade.exit_time?,
+                })
+            })
+            .collect())
+    }
+
+    async fn close_trade(
+        &self,
+        trade_id: Uuid,
+        exit_price: Decimal,
+        exit_time: DateTime<Utc>,
+        pnl: Usdc,
+        tx_hash: &str,
+    ) -> Result<()> {
+        let mut state = self.state.write().await;
+        let state = &mut *state;
+        let trade = state.trades.get_mut(&trade_id)
+            .ok_or_else(|| anyhow!("Trade {} not found", trade_id))?;
+
+        let (mae, mfe) = trade.excursions_at_close(exit_price, state.trade_marks.get(&trade_id));
+        trade.max_adverse_excursion = Some(mae);
+        trade.max_favorable_excursion = Some(mfe);
+        trade.exit_price = Some(exit_price);
+        trade.exit_time = Some(exit_time);
+        trade.pnl = Some(pnl);
+        trade.status = TradeStatus::Closed;
+        trade.tx_hash_exit = Some(tx_hash.to_string());
+
+        Ok(())
+    }
+
+    async fn correct_trade_entry(
+        &self,
+        trade_id: Uuid,
+        entry_price: Decimal,
+        quantity: Decimal,
+        slippage: Option<Decimal>,
+    ) -> Result<()> {
+        let mut state = self.state.write().await;
+        let trade = state.trades.get_mut(&trade_id)
+            .ok_or_else(|| anyhow!("Trade {} not found", trade_id))?;
+        if trade.status == TradeStatus::Open {
+            trade.entry_price = entry_price;
+            trade.quantity = quantity;
+            trade.slippage = slippage;
+        }
+        Ok(())
+    }
+
+    async fn recent_closed_pnls(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<Option<Decimal>>> {
+        let state = self.state.read().await;
+
+        let mut closed: Vec<&Trade> = state.trades.values()
+            .filter(|t| t.status == TradeStatus::Closed && t.entry_time > since)
+            .collect();
+        closed.sort_by(|a, b| b.entry_time.cmp(&a.entry_time));
+
+        Ok(closed.into_iter()
+            .take(limit.max(0) as usize)
+            .map(|t| t.pnl.map(|pnl| pnl.value()))
+            .collect())
+    }
+
+    async fn realized_pnl_today(&self) -> Result<Decimal> {
+        let state = self.state.read().await;
+        let today = Self::start_of_today();
+
+        Ok(state.trades.values()
+            .filter(|t| t.status == TradeStatus::Closed)
+            .filter(|t| t.exit_time.map_or(false, |exit| exit >= today))
+            .filter_map(|t| t.pnl)
+            .sum::<Usdc>()
+            .value())
+    }
+
+    async fn execution_costs_today(&self) -> Result<Decimal> {
+        let state = self.state.read().await;
+        let today = Self::start_of_today();
+
+        let entries: Decimal = state.trades.values()
+            .filter(|t| t.entry_time >= today)
+            .map(|t| t.gas_cost.unwrap_or_default() + t.fees.unwrap_or_default().value())
+            .sum();
+        let others: Decimal = state.execution_costs.iter()
+            .filter(|c| c.incurred_at >= today)
+            .map(|c| c.amount)
+            .sum();
+        Ok(entries + others)
+    }
+
+    async fn insert_execution_cost(&self, cost: &ExecutionCost) -> Result<()> {
+        self.state.write().await.execution_costs.push(cost.clone());
+        Ok(())
+    }
+
+    async fn count_open_trades(&self) -> Result<i64> {
+        let state = self.state.read().await;
+        Ok(state.trades.values().filter(|t| t.status == TradeStatus::Open).count() as i64)
+    }
+
+    async fn count_trades_today(&self) -> Result<i64> {
+        let state = self.state.read().await;
+        let today = Self::start_of_today();
+        Ok(state.trades.values().filter(|t| t.entry_time >= today).count() as i64)
+    }
+
+    async fn count_strategy_trades_today(&self, strategy: Strategy) -> Result<i64> {
+        let state = self.state.read().await;
+        let today = Self::start_of_today();
+        Ok(state.trades.values().filter(|t| t.strategy == strategy && t.entry_time >= today).count() as i64)
+    }
+
+    async fn insert_slippage_event(&self, event: &SlippageEvent) -> Result<()> {
+        self.state.write().await.slippage_events.push(event.clone());
+        Ok(())
+    }
+
+    async fn upsert_trade_mark(&self, mark: &TradeMark) -> Result<()> {
+        self.state.write().await.trade_marks.insert(mark.trade_id, mark.clone());
+        Ok(())
+    }
+
+    async fn fetch_trade_marks(&self) -> Result<Vec<TradeMark>> {
+        let state = self.state.read().await;
+
+        Ok(state.trade_marks.values()
+            .filter(|m| state.trades.get(&m.trade_id).map_or(false, |t| t.status == TradeStatus::Open))
+            .cloned()
+            .collect())
+    }
+
+    async fn insert_convergence_sample(&self, sample: &ConvergenceSample) -> Result<()> {
+        self.state.write().await.convergence.push(sample.clone());
+        Ok(())
+    }
+
+    async fn fetch_convergence_samples(&self, trade_id: Uuid) -> Result<Vec<ConvergenceSample>> {
+        let mut samples: Vec<ConvergenceSample> = self.state.read().await.convergence.iter()
+            .filter(|s| s.trade_id == trade_id)
+            .cloned()
+            .collect();
+        samples.sort_by_key(|s| s.sampled_at);
+        Ok(samples)
+    }
+
+    async fn insert_trade_sizing(&self, sizing: &TradeSizing) -> Result<()> {
+        self.state.write().await.trade_sizing.entry(sizing.trade_id).or_insert_with(|| sizing.clone());
+        Ok(())
+    }
+
+    async fn fetch_trade_sizing(&self, since: DateTime<Utc>) -> Result<Vec<TradeSizing>> {
+        let closed = self.fetch_closed_trades(since).await?;
+        let state = self.state.read().await;
+
+        Ok(closed.iter()
+            .filter_map(|trade| state.trade_sizing.get(&trade.trade_id).cloned())
+            .collect())
+    }
+
+    async fn upsert_settlement_review(&self, review: &SettlementReview) -> Result<()> {
+        let mut state = self.state.write().await;
+        match state.settlement_reviews.get_mut(&review.trade_id) {
+            Some(existing) if existing.resolved_at.is_none() => {
+                existing.reported_payout = review.reported_payout;
+                existing.onchain_payout = review.onchain_payout;
+                existing.reason = review.reason.clone();
+            }
+            Some(_) => {}
+            None => {
+                state.settlement_reviews.insert(review.trade_id, review.clone());
+            }
+        }
+        Ok(())
+    }
+
+    async fn fetch_open_settlement_reviews(&self) -> Result<Vec<SettlementReview>> {
+        let mut reviews: Vec<SettlementReview> = self.state.read().await.settlement_reviews.values()
+            .filter(|r| r.resolved_at.is_none())
+            .cloned()
+            .collect();
+        reviews.sort_by_key(|r| r.flagged_at);
+        Ok(reviews)
+    }
+
+    async fn resolve_settlement_review(&self, trade_id: Uuid, resolved_at: DateTime<Utc>) -> Result<()> {
+        if let Some(review) = self.state.write().await.settlement_reviews.get_mut(&trade_id) {
+            review.resolved_at.get_or_insert(resolved_at);
+        }
+        Ok(())
+    }
+
+    async fn insert_trade_settlement(&self, settlement: &TradeSettlement) -> Result<()> {
+        self.state.write().await.trade_settlements.insert(settlement.trade_id, settlement.clone());
+        Ok(())
+    }
+
+    async fn fetch_trade_settlements(&self, since: DateTime<Utc>) -> Result<Vec<TradeSettlement>> {
+        let mut settlements: Vec<TradeSettlement> = self.state.read().await.trade_settlements.values()
+            .filter(|s| s.settled_at >= since)
+            .cloned()
+            .collect();
+        settlements.sort_by(|a, b| b.settled_at.cmp(&a.settled_at));
+        Ok(settlements)
+    }
+
+    async fn insert_onchain_fill(&self, fill: &OnchainFill) -> Result<bool> {
+        let mut state = self.state.write().await;
+        let key = (fill.tx_hash.clone(), fill.log_index);
+        if state.onchain_fills.contains_key(&key) {
+            return Ok(false);
+        }
+        state.onchain_fills.insert(key, fill.clone());
+        Ok(true)
+    }
+
+    async fn fetch_order_fills(&self, order_hash: &str) -> Result<Vec<OnchainFill>> {
+        let mut fills: Vec<OnchainFill> = self.state.read().await.onchain_fills.values()
+            .filter(|f| f.order_hash == order_hash)
+            .cloned()
+            .collect();
+        fills.sort_by_key(|f| (f.block_number, f.log_index));
+        Ok(fills)
+    }
+
+    async fn fetch_tx_fills(&self, tx_hash: &str) -> Result<Vec<OnchainFill>> {
+        let mut fills: Vec<OnchainFill> = self.state.read().await.onchain_fills.values()
+            .filter(|f| f.tx_hash == tx_hash)
+            .cloned()
+            .collect();
+        fills.sort_by_key(|f| f.log_index);
+        Ok(fills)
+    }
+
+    async fn insert_resting_order(&self, order: &RestingOrder) -> Result<()> {
+        self.state.write().await.resting_orders.insert(order.order_id.clone(), order.clone());
+        Ok(())
+    }
+
+    async fn fetch_resting_orders(&self, trade_id: Uuid) -> Result<Vec<RestingOrder>> {
+        let state = self.state.read().await;
+        Ok(state.resting_orders.values().filter(|o| o.trade_id == trade_id).cloned().collect())
+    }
+
+    async fn set_resting_order_status(&self, order_id: &str, status: RestingOrderStatus) -> Result<()> {
+        if status != RestingOrderStatus::Open {
+            self.state.write().await.resting_orders.remove(order_id);
+        }
+        Ok(())
+    }
+
+    async fn insert_entry_order(&self, order: &EntryOrder) -> Result<()> {
+        self.state.write().await.entry_orders.insert(order.order_id.clone(), order.clone());
+        Ok(())
+    }
+
+    async fn fetch_open_entry_orders(&self) -> Result<Vec<EntryOrder>> {
+        let state = self.state.read().await;
+        let mut orders: Vec<EntryOrder> = state.entry_orders.values().cloned().collect();
+        orders.sort_by_key(|o| o.placed_at);
+        Ok(orders)
+    }
+
+    async fn set_entry_order_status(&self, order_id: &str, status: RestingOrderStatus) -> Result<()> {
+        if status != RestingOrderStatus::Open {
+            self.state.write().await.entry_orders.remove(order_id);
+        }
+        Ok(())
+    }
+
+    async fn insert_parent_order(&self, order: &ParentOrder) -> Result<()> {
+        self.state.write().await.parent_orders.insert(order.parent_id, order.clone());
+        Ok(())
+    }
+
+    async fn update_parent_order(&self, order: &ParentOrder) -> Result<()> {
+        let mut state = self.state.write().await;
+        if order.status == RestingOrderStatus::Open {
+            state.parent_orders.insert(order.parent_id, order.clone());
+        } else {
+            state.parent_orders.remove(&order.parent_id);
+        }
+        Ok(())
+    }
+
+    async fn fetch_open_parent_orders(&self) -> Result<Vec<ParentOrder>> {
+        let state = self.state.read().await;
+        let mut orders: Vec<ParentOrder> = state.parent_orders.values()
+            .filter(|o| o.status == RestingOrderStatus::Open)
+            .cloned()
+            .collect();
+        orders.sort_by_key(|o| o.created_at);
+        Ok(orders)
+    }
+
+    async fn insert_child_order(&self, order: &ChildOrder) -> Result<()> {
+        self.state.write().await.child_orders.push(order.clone());
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CashFlowRepo for MemoryStorage {
+    async fn insert_cash_flow(&self, flow: &CashFlow) -> Result<()> {
+        self.state.write().await.cash_flows.push(flow.clone());
+        Ok(())
+    }
+
+    async fn fetch_cash_flows(&self, since: DateTime<Utc>) -> Result<Vec<CashFlow>> {
+        let state = self.state.read().await;
+
+        let mut flows: Vec<CashFlow> = state.cash_flows.iter()
+            .filter(|f| f.occurred_at >= since)
+            .cloned()
+            .collect();
+        flows.sort_by_key(|f| f.occurred_at);
+
+        Ok(flows)
+    }
+
+    async fn cash_flow_exists(&self, tx_hash: &str) -> Result<bool> {
+        let state = self.state.read().await;
+        Ok(state.cash_flows.iter().any(|f| f.tx_hash.as_deref() == Some(tx_hash)))
+    }
+}
+
+#[async_trait]
+impl PortfolioRepo for MemoryStorage {
+    async fn calculate_portfolio_balances(&self, starting_capital: Decimal) -> Result<PortfolioBalances> {
+        let state = self.state.read().await;
+
+        let realized: Decimal = state.trades.values()
+            .filter(|t| t.status == TradeStatus::Closed)
+            .filter_map(|t| t.pnl)
+            .sum::<Usdc>()
+            .value();
+
+        let mut invested = dec!(0.0);
+        let mut pending = dec!(0.0);
+        let mut unrealized = dec!(0.0);
+        for trade in state.trades.values().filter(|t| t.status == TradeStatus::Open) {
+            let market = state.markets.get(&trade.market_id);
+            if market.map_or(false, |m| m.status.awaits_settlement()) {
+                pending += trade.position_size_usd();
+            } else {
+                invested += trade.position_size_usd();
+            }
+            // Mirrors calculate_portfolio_state(), which marks against the YES
+            // price, pending positions included
+            if let Some(market) = market {
+                unrealized += (market.yes_price.value() - trade.entry_price) * trade.quantity;
+            }
+        }
+
+        let net_flows = state.cash_flows.iter().map(|f| f.signed_amount()).sum::<Usdc>().value();
+
+        let total_capital = starting_capital + net_flows + realized;
+
+        Ok(PortfolioBalances {
+            total_capital,
+            available_capital: total_capital - invested - pending,
+            invested_capital: invested,
+            settlement_pending: pending,
+            unrealized_pnl: unrealized,
+            net_cash_flows: net_flows,
+        })
+    }
+
+    async fn calculate_venue_balances(&self) -> Result<Vec<VenueBalances>> {
+        let state = self.state.read().await;
+        let start_of_today = Self::start_of_today();
+        let mut venues: HashMap<String, VenueBalances> = HashMap::new();
+
+        for trade in state.trades.values() {
+            let balances = venues.entry(trade.venue.clone())
+                .or_insert_with(|| VenueBalances::empty(trade.venue.clone()));
+
+            match trade.status {
+                TradeStatus::Open => {
+                    balances.invested_capital += trade.position_size_usd();
+                    if let Some(market) = state.markets.get(&trade.market_id) {
+                        balances.unrealized_pnl += (market.yes_price.value() - trade.entry_price) * trade.quantity;
+                    }
+                    balances.open_positions += 1;
+                }
+                TradeStatus::Closed => {
+                    let pnl = trade.pnl.unwrap_or_default().value();
+                    balances.realized_pnl += pnl;
+                    if trade.exit_time.map_or(false, |t| t >= start_of_today) {
+                        balances.realized_pnl_today += pnl;
+                    }
+                }
+                TradeStatus::StoppedOut => {}
+            }
+        }
+
+        for flow in &state.cash_flows {
+            venues.entry(flow.venue.clone())
+                .or_insert_with(|| VenueBalances::empty(flow.venue.clone()))
+                .net_cash_flows += flow.signed_amount().value();
+        }
+
+        Ok(venues.into_values().collect())
+    }
+
+    async fn insert_portfolio_snapshot(&self, state: &PortfolioState) -> Result<()> {
+        self.state.write().await.snapshots.push(state.clone());
+        Ok(())
+    }
+
+    async fn latest_portfolio_snapshot(&self) -> Result<Option<PortfolioState>> {
+        Ok(self.state.read().await.snapshots.last().cloned())
+    }
+
+    async fn fetch_capital_history(&self, since: DateTime<Utc>) -> Result<Vec<CapitalPoint>> {
+        let state = self.state.read().await;
+        let mut points: Vec<CapitalPoint> = state.snapshots.iter()
+            .filter(|s| s.timestamp >= since)
+            .map(|s| CapitalPoint { timestamp: s.timestamp, total_capital: s.total_capital })
+            .collect();
+        points.sort_by_key(|p| p.timestamp);
+        Ok(points)
+    }
+
+    async fn count_active_circuit_breakers(&self) -> Result<i64> {
+        let state = self.state.read().await;
+        Ok(state.circuit_breakers.iter().filter(|b| b.active).count() as i64)
+    }
+
+    async fn insert_circuit_breaker(&self, reason: &str, metadata: serde_json::Value) -> Result<()> {
+        self.state.write().await.circuit_breakers.push(StoredBreaker {
+            reason: reason.to_string(),
+            metadata,
+            active: true,
+        });
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SeasonRepo for MemoryStorage {
+    async fn fetch_team_ratings(&self, sport: Sport) -> Result<Vec<TeamRating>> {
+        let state = self.state.read().await;
+        Ok(state.team_ratings.iter().filter(|t| t.sport == sport).cloned().collect())
+    }
+
+    async fn fetch_season_games(&self, sport: Sport) -> Result<Vec<Game>> {
+        let state = self.state.read().await;
+        let Some(season) = state.games.iter().filter(|g| g.sport == sport).map(|g| g.season).max() else {
+            return Ok(Vec::new());
+        };
+
+        let mut games: Vec<Game> = state.games.iter()
+            .filter(|g| g.sport == sport && g.season == season)
+            .cloned()
+            .collect();
+        games.sort_by_key(|g| g.start_time);
+        Ok(games)
+    }
+
+    async fn fetch_game_history(&self, sport: Sport) -> Result<Vec<Game>> {
+        let mut games: Vec<Game> = self.state.read().await.games.iter()
+            .filter(|g| g.sport == sport && g.is_played())
+            .cloned()
+            .collect();
+        games.sort_by_key(|g| g.start_time);
+        Ok(games)
+    }
+
+    async fn upsert_home_advantage(&self, estimate: &HomeAdvantageEstimate) -> Result<()> {
+        let mut state = self.state.write().await;
+        state.home_advantage.retain(|e| !(e.sport == estimate.sport && e.team == estimate.team));
+        state.home_advantage.push(estimate.clone());
+        Ok(())
+    }
+
+    async fn fetch_home_advantage(&self, sport: Option<Sport>) -> Result<Vec<HomeAdvantageEstimate>> {
+        let state = self.state.read().await;
+        let mut estimates: Vec<HomeAdvantageEstimate> = state.home_advantage.iter()
+            .filter(|e| sport.map_or(true, |sport| e.sport == sport))
+            .cloned()
+            .collect();
+        estimates.sort_by(|a, b| a.sport.as_str().cmp(b.sport.as_str()).then(a.team.cmp(&b.team)));
+        Ok(estimates)
+    }
+
+    async fn fetch_injury_adjustments(&self, sport: Sport) -> Result<Vec<InjuryAdjustment>> {
+        let state = self.state.read().await;
+        let now = Utc::now();
+        Ok(state.injuries.iter()
+            .filter(|i| i.sport == sport && i.out_until.map_or(true, |until| until > now))
+            .cloned()
+            .collect())
+    }
+
+    async fn upsert_official_assignment(&self, assignment: &OfficialAssignment) -> Result<()> {
+        let mut state = self.state.write().await;
+        state.official_assignments.retain(|a| !(a.game_id == assignment.game_id && a.role == assignment.role));
+        state.official_assignments.push(assignment.clone());
+        Ok(())
+    }
+
+    async fn fetch_official_tendencies(&self, game_id: &str) -> Result<Vec<OfficialTendency>> {
+        let state = self.state.read().await;
+        let Some(game) = state.games.iter().find(|g| g.game_id == game_id) else {
+            return Ok(Vec::new());
+        };
+
+        // Same measure as v_official_tendencies: played totals over the season average
+        let season_average = |season: i32| {
+            let totals: Vec<i32> = state.games.iter()
+                .filter(|g| g.sport == game.sport && g.season == season)
+                .filter_map(|g| Some(g.home_score? + g.away_score?))
+                .collect();
+            totals.iter().sum::<i32>() as f64 / totals.len().max(1) as f64
+        };
+
+        Ok(state.official_assignments.iter()
+            .filter(|a| a.game_id == game_id)
+            .map(|assignment| {
+                let deltas: Vec<f64> = state.official_assignments.iter()
+                    .filter(|a| a.official == assignment.official && a.role == assignment.role)
+                    .filter_map(|a| state.games.iter().find(|g| g.game_id == a.game_id && g.sport == game.sport))
+                    .filter_map(|g| Some((g.home_score? + g.away_score?) as f64 - season_average(g.season)))
+                    .collect();
+
+                OfficialTendency {
+                    official: assignment.official.clone(),
+                    role: assignment.role.clone(),
+                    games: deltas.len() as i64,
+                    total_delta: deltas.iter().sum::<f64>() / deltas.len().max(1) as f64,
+                }
+            })
+            .collect())
+    }
+
+    async fn upsert_probable_pitcher(&self, pitcher: &ProbablePitcher) -> Result<()> {
+        let mut state = self.state.write().await;
+        state.probable_pitchers.retain(|p| !(p.game_id == pitcher.game_id && p.team == pitcher.team));
+        state.probable_pitchers.push(pitcher.clone());
+        Ok(())
+    }
+
+    async fn fetch_probable_pitchers(&self, game_id: &str) -> Result<Vec<ProbablePitcher>> {
+        let state = self.state.read().await;
+        Ok(state.probable_pitchers.iter().filter(|p| p.game_id == game_id).cloned().collect())
+    }
+
+    async fn upsert_bullpen_usage(&self, usage: &BullpenUsage) -> Result<()> {
+        let mut state = self.state.write().await;
+        state.bullpen_usage.retain(|u| !(u.team == usage.team && u.game_time == usage.game_time));
+        state.bullpen_usage.push(usage.clone());
+        Ok(())
+    }
+
+    async fn fetch_bullpen_usage(&self, since: DateTime<Utc>) -> Result<Vec<BullpenUsage>> {
+        let state = self.state.read().await;
+        Ok(state.bullpen_usage.iter().filter(|u| u.game_time >= since).cloned().collect())
+    }
+}
+
+#[async_trait]
+impl FeatureRepo for MemoryStorage {
+    async fn upsert_game_feature(&self, feature: &GameFeature) -> Result<()> {
+        let mut state = self.state.write().await;
+        state.game_features.retain(|f| {
+            !(f.game_id == feature.game_id && f.team == feature.team && f.name == feature.name)
+        });
+        state.game_features.push(feature.clone());
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DecisionRepo for MemoryStorage {
+    async fn insert_decision(&self, decision: &Decision) -> Result<()> {
+        self.state.write().await.decisions.push(decision.clone());
+        Ok(())
+    }
+
+    async fn fetch_decisions(&self, since: DateTime<Utc>) -> Result<Vec<Decision>> {
+        let state = self.state.read().await;
+        let mut decisions: Vec<Decision> = state.decisions.iter()
+            .filter(|d| d.decided_at >= since)
+            .cloned()
+            .collect();
+        decisions.sort_by(|a, b| b.decided_at.cmp(&a.decided_at));
+        Ok(decisions)
+    }
+}
+
+#[async_trait]
+impl CredentialRepo for MemoryStorage {
+    async fn insert_credential(&self, credential: &SealedCredential) -> Result<()> {
+        let mut state = self.state.write().await;
+        let key = (credential.provider.clone(), credential.name.clone(), credential.version);
+        if state.credentials.contains_key(&key) {
+            bail!("credential {}/{} version {} already exists", credential.provider, credential.name, credential.version);
+        }
+        state.credentials.insert(key, credential.clone());
+        Ok(())
+    }
+
+    async fn fetch_credentials(&self) -> Result<Vec<SealedCredential>> {
+        let state = self.state.read().await;
+        let mut latest: HashMap<(&str, &str), &SealedCredential> = HashMap::new();
+        for credential in state.credentials.values() {
+            let entry = latest.entry((&credential.provider, &credential.name)).or_insert(credential);
+            if credential.version > entry.version {
+                *entry = credential;
+            }
+        }
+
+        let mut credentials: Vec<SealedCredential> = latest.into_values().cloned().collect();
+        credentials.sort_by(|a, b| (&a.provider, &a.name).cmp(&(&b.provider, &b.name)));
+        Ok(credentials)
+    }
+}
@@ -0,0 +1,96 @@
+use serde_json::{json, Map, Value};
+
+/// Stored JSON metadata whose shape evolves on its own schedule
+///
+/// Each kind is written inside an envelope, `{"version": n, "data": ...}`,
+/// and read back through the upgrades from its stored version to the
+/// current one, so readers only ever see the current shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetadataKind {
+    /// Strategy-specific detail on a signal
+    Signal,
+    /// Context recorded when a circuit breaker trips
+    CircuitBreaker,
+}
+
+/// Rewrites a payload of one version into the next
+type Upgrade = fn(Value) -> Value;
+
+impl MetadataKind {
+    /// Upgrades from version 1 onwards, the first taking 1 to 2; the current
+    /// version is one past the last. Add a new version by appending here.
+    fn upgrades(&self) -> &'static [Upgrade] {
+        match self {
+            MetadataKind::Signal => &[into_object],
+            MetadataKind::CircuitBreaker => &[into_object],
+        }
+    }
+
+    pub fn current_version(&self) -> u64 {
+        self.upgrades().len() as u64 + 1
+    }
+}
+
+/// v1 -> v2: rows written before envelopes could hold null or a bare value;
+/// the payload is always an object from v2
+fn into_object(data: Value) -> Value {
+    match data {
+        Value::Object(_) => data,
+        Value::Null => Value::Object(Map::new()),
+        value => json!({ "value": value }),
+    }
+}
+
+/// A payload wrapped for storage at its kind's current version
+pub fn envelope(kind: MetadataKind, data: &Value) -> Value {
+    json!({ "version": kind.current_version(), "data": data })
+}
+
+/// The payload of stored metadata brought up to its kind's current version
+///
+/// Anything not in an envelope was written before versioning and is taken
+/// as version 1. A version newer than this build knows, written by a newer
+/// deployment, is passed through as stored.
+pub fn upgrade(kind: MetadataKind, stored: Value) -> Value {
+    let (version, data) = match stored {
+        Value::Object(mut fields) if is_envelope(&fields) => {
+            let version = fields["version"].as_u64().unwrap_or(1);
+            (version, fields.remove("data").unwrap_or(Value::Null))
+        }
+        legacy => (1, legacy),
+    };
+
+    kind.upgrades()
+        .iter()
+        .skip(version.saturating_sub(1) as usize)
+        .fold(data, |data, upgrade| upgrade(data))
+}
+
+fn is_envelope(fields: &Map<String, Value>) -> bool {
+    fields.len() == 2
+        && fields.get("version").map_or(false, Value::is_u64)
+        && fields.contains_key("data")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_and_enveloped_rows_read_back_current() {
+        let payload = json!({ "fair_yes": 0.55, "num_bookmakers": 3 });
+
+        let stored = envelope(MetadataKind::Signal, &payload);
+        assert_eq!(stored["version"], MetadataKind::Signal.current_version());
+        assert_eq!(upgrade(MetadataKind::Signal, stored), payload);
+
+        // Written before envelopes
+        assert_eq!(upgrade(MetadataKind::Signal, payload.clone()), payload);
+        assert_eq!(upgrade(MetadataKind::Signal, Value::Null), json!({}));
+        assert_eq!(upgrade(MetadataKind::CircuitBreaker, json!({ "version": 1, "data": null })), json!({}));
+
+        // From a newer build
+        let newer = json!({ "version": 99, "data": "opaque" });
+        assert_eq!(upgrade(MetadataKind::Signal, newer), json!("opaque"));
+    }
+}
@@ -0,0 +1,474 @@
+mod memory;
+mod metadata;
+mod postgres;
+mod sqlite;
+
+pub use memory::MemoryStorage;
+pub use postgres::PostgresStorage;
+pub use sqlite::SqliteStorage;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::types::{
+    BookmakerOdds, CapitalPoint, CashFlow, Decision, EntryOrder, FeedLatency, Game, GameFeature, HomeAdvantageEstimate, InjuryAdjustment, Market,
+    MarketStatus, MarketTokens, OfficialAssignment, OfficialTendency, PortfolioState, Quote, Signal,
+    Position, PredictionOutcome, Price, RestingOrder, RestingOrderStatus, ShadowedStrategy, SlippageEvent, Sport, Strategy,
+    StrategyThrottle, TeamRating, Trade, TradeMark, ConvergenceSample, SettlementReview, StrategyWeight, TradeSizing, OnchainFill, MarketMicrostructure, BookSnapshot, LargeTrade, PricePoint, PreEventHistory, Resolution,
+    SealedCredential, MarketListing, CoveredMarket, TradeSettlement, DeadLetter, FailedExecution, SignalExecution, EdgeDecay,
+    ParentOrder, ChildOrder, ProbablePitcher, BullpenUsage, ExecutionCost, Usdc,
+};
+
+/// Aggregate capital figures derived from the trade history
+#[derive(Debug, Clone)]
+pub struct PortfolioBalances {
+    pub total_capital: Decimal,
+    pub available_capital: Decimal,
+    pub invested_capital: Decimal,
+    /// Cost of open positions on ended markets awaiting settlement
+    pub settlement_pending: Decimal,
+    /// Against live markets only; positions awaiting settlement are held at cost
+    pub unrealized_pnl: Decimal,
+    /// Deposits minus withdrawals to date
+    pub net_cash_flows: Decimal,
+}
+
+/// Flows and PnL attributed to one venue, before its starting capital
+#[derive(Debug, Clone)]
+pub struct VenueBalances {
+    pub venue: String,
+    pub net_cash_flows: Decimal,
+    pub realized_pnl: Decimal,
+    pub realized_pnl_today: Decimal,
+    pub invested_capital: Decimal,
+    pub unrealized_pnl: Decimal,
+    pub open_positions: i64,
+}
+
+impl VenueBalances {
+    pub fn empty(venue: String) -> Self {
+        Self {
+            venue,
+            net_cash_flows: Decimal::ZERO,
+            realized_pnl: Decimal::ZERO,
+            realized_pnl_today: Decimal::ZERO,
+            invested_capital: Decimal::ZERO,
+            unrealized_pnl: Decimal::ZERO,
+            open_positions: 0,
+        }
+    }
+}
+
+/// Markets, their prices and the reference bookmaker odds
+#[async_trait]
+pub trait MarketRepo: Send + Sync {
+    /// Active markets with upcoming events and at least `min_liquidity`
+    async fn fetch_active_markets(&self, min_liquidity: Decimal, limit: i64) -> Result<Vec<Market>>;
+
+    async fn fetch_market(&self, market_id: &str) -> Result<Option<Market>>;
+
+    /// Current (yes, no) top-of-book quotes for a market
+    async fn fetch_market_quotes(&self, market_id: &str) -> Result<(Quote, Quote)>;
+
+    /// Latest odds per bookmaker from the last hour
+    async fn fetch_bookmaker_odds(&self, market_id: &str) -> Result<Vec<BookmakerOdds>>;
+
+    /// Record a moneyline quote linked to a market
+    async fn insert_bookmaker_odds(&self, odds: &BookmakerOdds) -> Result<()>;
+
+    async fn insert_feed_latency(&self, latency: &FeedLatency) -> Result<()>;
+
+    /// Feed latency measurements since `since`, oldest first
+    async fn fetch_feed_latency(&self, since: DateTime<Utc>) -> Result<Vec<FeedLatency>>;
+
+    /// Insert a market or refresh its prices, liquidity and status
+    async fn upsert_market(&self, market: &Market) -> Result<()>;
+
+    /// Replace a market's top-of-book quotes, leaving the rest as last ingested
+    async fn update_market_quotes(&self, market_id: &str, yes_quote: &Quote, no_quote: &Quote) -> Result<()>;
+
+    /// CLOB identifiers for a market, `None` if not yet known
+    async fn fetch_market_tokens(&self, market_id: &str) -> Result<Option<MarketTokens>>;
+
+    /// Current status of a market, `None` if it has never been ingested
+    async fn fetch_market_status(&self, market_id: &str) -> Result<Option<MarketStatus>>;
+
+    async fn insert_microstructure(&self, features: &MarketMicrostructure) -> Result<()>;
+
+    /// Most recent microstructure features of a market
+    async fn latest_microstructure(&self, market_id: &str) -> Result<Option<MarketMicrostructure>>;
+
+    /// Replace a market's stored order book
+    async fn upsert_book_snapshot(&self, book: &BookSnapshot) -> Result<()>;
+
+    /// A market's order book as last stored, `None` if never fetched
+    async fn fetch_book_snapshot(&self, market_id: &str) -> Result<Option<BookSnapshot>>;
+
+    /// Replace a sport's listing counts with the latest poll's
+    async fn upsert_market_listing(&self, listing: &MarketListing) -> Result<()>;
+
+    /// Each sport's listing counts as of its last poll
+    async fn fetch_market_listings(&self) -> Result<Vec<MarketListing>>;
+
+    /// Record that `strategy` evaluated the markets at `evaluated_at`
+    async fn record_market_evaluations(&self, strategy: &str, market_ids: &[String], evaluated_at: DateTime<Utc>) -> Result<()>;
+
+    /// Upcoming active markets, each with whether bookmaker odds arrived
+    /// for it and which strategies evaluated it since `since`
+    async fn fetch_covered_markets(&self, since: DateTime<Utc>) -> Result<Vec<CoveredMarket>>;
+
+    /// Record a row that failed to map; a repeat of the same item and reason
+    /// bumps its count and last-seen time and keeps the latest raw row
+    async fn record_dead_letter(&self, source: &str, item_key: &str, reason: &str, raw: &serde_json::Value, seen_at: DateTime<Utc>) -> Result<()>;
+
+    /// Dead letters seen at or after `since`, most recently seen first
+    async fn fetch_dead_letters(&self, since: DateTime<Utc>) -> Result<Vec<DeadLetter>>;
+
+    /// Store a large trade; false if it was already recorded
+    async fn insert_large_trade(&self, trade: &LargeTrade) -> Result<bool>;
+
+    /// Large trades since `since`, on one market or all, newest first
+    async fn fetch_large_trades(&self, since: DateTime<Utc>, market_id: Option<&str>) -> Result<Vec<LargeTrade>>;
+
+    /// Record how a market resolved
+    async fn set_market_resolution(&self, market_id: &str, resolution: Resolution, resolved_at: DateTime<Utc>) -> Result<()>;
+
+    /// Store points of a market's price history; returns how many were new
+    async fn insert_price_history(&self, points: &[PricePoint]) -> Result<u64>;
+
+    /// Time of the latest stored price history point of a market
+    async fn last_price_history_at(&self, market_id: &str) -> Result<Option<DateTime<Utc>>>;
+
+    /// Price history before the start of markets whose events started at or
+    /// after `since`, with those start times
+    async fn fetch_pre_event_price_history(&self, since: DateTime<Utc>) -> Result<PreEventHistory>;
+
+    /// Record a market's latest pre-event prices as its closing line
+    async fn upsert_closing_line(
+        &self,
+        market_id: &str,
+        yes_price: Price,
+        no_price: Price,
+        captured_at: DateTime<Utc>,
+    ) -> Result<()>;
+}
+
+/// Strategy signals awaiting (or done with) execution
+#[async_trait]
+pub trait SignalRepo: Send + Sync {
+    async fn insert_signal(&self, signal: &Signal) -> Result<()>;
+
+    /// Unexecuted signals generated after `since`, best first
+    async fn fetch_pending_signals(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<Signal>>;
+
+    /// Claim up to `limit` unexecuted signals generated after `since` for
+    /// `owner` until `until`, best first
+    ///
+    /// Signals under another unexpired claim are skipped, so concurrent
+    /// consumers never take the same signal; a consumer that dies leaves its
+    /// claims to expire.
+    async fn claim_pending_signals(
+        &self,
+        since: DateTime<Utc>,
+        limit: i64,
+        owner: &str,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<Signal>>;
+
+    /// Drop `owner`'s claim on a signal it left pending
+    async fn release_signal(&self, signal_id: Uuid, owner: &str) -> Result<()>;
+
+    /// Extend `owner`'s claim on a pending signal to `until`; false when the
+    /// signal has been executed or its lapsed claim was taken by another owner
+    async fn renew_signal_claim(&self, signal_id: Uuid, owner: &str, until: DateTime<Utc>) -> Result<bool>;
+
+    async fn mark_signal_executed(&self, signal_id: Uuid, trade_id: Option<Uuid>) -> Result<()>;
+
+    async fn fetch_signal(&self, signal_id: Uuid) -> Result<Option<Signal>>;
+
+    /// Every signal on a market generated at or after `since`, executed or
+    /// not, oldest first
+    async fn fetch_market_signals(&self, market_id: &str, since: DateTime<Utc>) -> Result<Vec<Signal>>;
+
+    /// Insert or replace the failure record of a signal
+    async fn upsert_failed_execution(&self, failure: &FailedExecution) -> Result<()>;
+
+    async fn fetch_failed_execution(&self, signal_id: Uuid) -> Result<Option<FailedExecution>>;
+
+    /// Failure records updated at or after `since`, most recent first
+    async fn fetch_failed_executions(&self, since: DateTime<Utc>) -> Result<Vec<FailedExecution>>;
+
+    /// Fair value of the most recent signal on `position` in a market, i.e.
+    /// the model's latest probability that those shares pay out
+    async fn latest_fair_value(&self, market_id: &str, position: Position) -> Result<Option<Decimal>>;
+
+    /// Stop trading a strategy's signals; a strategy already in shadow keeps its original reason
+    async fn shadow_strategy(&self, strategy: Strategy, reason: &str) -> Result<()>;
+
+    /// Return a strategy to live trading
+    async fn unshadow_strategy(&self, strategy: Strategy) -> Result<()>;
+
+    async fn fetch_shadowed_strategies(&self) -> Result<Vec<ShadowedStrategy>>;
+
+    /// Insert or overwrite a strategy's throttle
+    async fn upsert_strategy_throttle(&self, throttle: &StrategyThrottle) -> Result<()>;
+
+    /// Return a strategy to full size
+    async fn clear_strategy_throttle(&self, strategy: Strategy) -> Result<()>;
+
+    async fn fetch_strategy_throttles(&self) -> Result<Vec<StrategyThrottle>>;
+
+    /// Replace every strategy weight with the latest solve
+    async fn replace_strategy_weights(&self, weights: &[StrategyWeight]) -> Result<()>;
+
+    async fn fetch_strategy_weights(&self) -> Result<Vec<StrategyWeight>>;
+
+    /// Record the executable price of a signal's position as the engine
+    /// takes it up, whatever then becomes of it
+    async fn record_execution_quote(&self, signal_id: Uuid, price: Price, at: DateTime<Utc>) -> Result<()>;
+
+    /// Signals the engine took up since `since`, traded, discarded or
+    /// expired, with their prices at generation and when taken up
+    async fn fetch_signal_executions(&self, since: DateTime<Utc>) -> Result<Vec<SignalExecution>>;
+
+    /// Replace every strategy's edge decay with the latest fit
+    async fn replace_edge_decays(&self, decays: &[EdgeDecay]) -> Result<()>;
+
+    async fn fetch_edge_decays(&self) -> Result<Vec<EdgeDecay>>;
+}
+
+/// Executed trades and their lifecycle
+#[async_trait]
+pub trait TradeRepo: Send + Sync {
+    async fn insert_trade(&self, trade: &Trade) -> Result<()>;
+
+    async fn fetch_open_trades(&self) -> Result<Vec<Trade>>;
+
+    async fn fetch_trade(&self, trade_id: Uuid) -> Result<Option<Trade>>;
+
+    /// The trade entered by transaction `tx_hash`, if any
+    async fn fetch_trade_by_entry_tx(&self, tx_hash: &str) -> Result<Option<Trade>>;
+
+    /// Trades closed at or after `since`, oldest exit first
+    async fn fetch_closed_trades(&self, since: DateTime<Utc>) -> Result<Vec<Trade>>;
+
+    /// Closed trades since `since` that were opened from a signal, oldest exit first
+    async fn fetch_prediction_outcomes(&self, since: DateTime<Utc>) -> Result<Vec<PredictionOutcome>>;
+
+    async fn close_trade(
+        &self,
+        trade_id: Uuid,
+        exit_price: Decimal,
+        exit_time: DateTime<Utc>,
+        pnl: Usdc,
+        tx_hash: &str,
+    ) -> Result<()>;
+
+    /// Set an open trade's entry to what its transaction actually filled
+    async fn correct_trade_entry(
+        &self,
+        trade_id: Uuid,
+        entry_price: Decimal,
+        quantity: Decimal,
+        slippage: Option<Decimal>,
+    ) -> Result<()>;
+
+    /// PnL of closed trades entered after `since`, most recent first
+    async fn recent_closed_pnls(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<Option<Decimal>>>;
+
+    async fn realized_pnl_today(&self) -> Result<Decimal>;
+
+    /// Gas and fees of trades entered today, plus the exit, failed entry
+    /// and redemption costs booked today
+    async fn execution_costs_today(&self) -> Result<Decimal>;
+
+    async fn insert_execution_cost(&self, cost: &ExecutionCost) -> Result<()>;
+
+    async fn count_open_trades(&self) -> Result<i64>;
+
+    async fn count_trades_today(&self) -> Result<i64>;
+
+    async fn count_strategy_trades_today(&self, strategy: Strategy) -> Result<i64>;
+
+    async fn insert_slippage_event(&self, event: &SlippageEvent) -> Result<()>;
+
+    /// Insert or overwrite a trade's mark
+    async fn upsert_trade_mark(&self, mark: &TradeMark) -> Result<()>;
+
+    /// Latest marks of open trades
+    async fn fetch_trade_marks(&self) -> Result<Vec<TradeMark>>;
+
+    async fn insert_convergence_sample(&self, sample: &ConvergenceSample) -> Result<()>;
+
+    /// A trade's convergence samples, oldest first
+    async fn fetch_convergence_samples(&self, trade_id: Uuid) -> Result<Vec<ConvergenceSample>>;
+
+    async fn insert_trade_sizing(&self, sizing: &TradeSizing) -> Result<()>;
+
+    /// Entry sizing of trades closed at or after `since`
+    async fn fetch_trade_sizing(&self, since: DateTime<Utc>) -> Result<Vec<TradeSizing>>;
+
+    /// Flag a trade for settlement review; refreshes the details of an open flag
+    async fn upsert_settlement_review(&self, review: &SettlementReview) -> Result<()>;
+
+    /// Reviews not yet resolved, oldest first
+    async fn fetch_open_settlement_reviews(&self) -> Result<Vec<SettlementReview>>;
+
+    async fn resolve_settlement_review(&self, trade_id: Uuid, resolved_at: DateTime<Utc>) -> Result<()>;
+
+    /// Record how a trade settled at resolution
+    async fn insert_trade_settlement(&self, settlement: &TradeSettlement) -> Result<()>;
+
+    /// Trades settled at or after `since`, most recent first
+    async fn fetch_trade_settlements(&self, since: DateTime<Utc>) -> Result<Vec<TradeSettlement>>;
+
+    /// Record a fill event; returns false if it was already recorded
+    async fn insert_onchain_fill(&self, fill: &OnchainFill) -> Result<bool>;
+
+    /// Fill events of an order, in chain order
+    async fn fetch_order_fills(&self, order_hash: &str) -> Result<Vec<OnchainFill>>;
+
+    /// Fill events of a transaction, in log order
+    async fn fetch_tx_fills(&self, tx_hash: &str) -> Result<Vec<OnchainFill>>;
+
+    async fn insert_resting_order(&self, order: &RestingOrder) -> Result<()>;
+
+    /// Exit orders still resting for a trade
+    async fn fetch_resting_orders(&self, trade_id: Uuid) -> Result<Vec<RestingOrder>>;
+
+    async fn set_resting_order_status(&self, order_id: &str, status: RestingOrderStatus) -> Result<()>;
+
+    async fn insert_entry_order(&self, order: &EntryOrder) -> Result<()>;
+
+    /// Maker entries still resting
+    async fn fetch_open_entry_orders(&self) -> Result<Vec<EntryOrder>>;
+
+    async fn set_entry_order_status(&self, order_id: &str, status: RestingOrderStatus) -> Result<()>;
+
+    async fn insert_parent_order(&self, order: &ParentOrder) -> Result<()>;
+
+    /// Store a parent order's progress, next child and status
+    async fn update_parent_order(&self, order: &ParentOrder) -> Result<()>;
+
+    /// Parent orders with children still to send, oldest first
+    async fn fetch_open_parent_orders(&self) -> Result<Vec<ParentOrder>>;
+
+    async fn insert_child_order(&self, order: &ChildOrder) -> Result<()>;
+}
+
+/// Deposits and withdrawals of trading capital
+#[async_trait]
+pub trait CashFlowRepo: Send + Sync {
+    async fn insert_cash_flow(&self, flow: &CashFlow) -> Result<()>;
+
+    /// Flows that occurred at or after `since`, oldest first
+    async fn fetch_cash_flows(&self, since: DateTime<Utc>) -> Result<Vec<CashFlow>>;
+
+    /// Whether an on-chain transfer has already been recorded
+    async fn cash_flow_exists(&self, tx_hash: &str) -> Result<bool>;
+}
+
+/// Portfolio snapshots and circuit breaker state
+#[async_trait]
+pub trait PortfolioRepo: Send + Sync {
+    /// Balances from starting capital, net cash flows and realized PnL
+    async fn calculate_portfolio_balances(&self, starting_capital: Decimal) -> Result<PortfolioBalances>;
+
+    /// Per-venue breakdown of flows and PnL for venues with any trades or flows
+    async fn calculate_venue_balances(&self) -> Result<Vec<VenueBalances>>;
+
+    async fn insert_portfolio_snapshot(&self, state: &PortfolioState) -> Result<()>;
+
+    async fn latest_portfolio_snapshot(&self) -> Result<Option<PortfolioState>>;
+
+    /// Total capital of every snapshot since `since`, oldest first
+    async fn fetch_capital_history(&self, since: DateTime<Utc>) -> Result<Vec<CapitalPoint>>;
+
+    async fn count_active_circuit_breakers(&self) -> Result<i64>;
+
+    async fn insert_circuit_breaker(&self, reason: &str, metadata: serde_json::Value) -> Result<()>;
+}
+
+/// Team ratings, schedules, results, injuries and officials feeding the game and season models
+#[async_trait]
+pub trait SeasonRepo: Send + Sync {
+    async fn fetch_team_ratings(&self, sport: Sport) -> Result<Vec<TeamRating>>;
+
+    /// Played and remaining games of the sport's latest season, in start order
+    async fn fetch_season_games(&self, sport: Sport) -> Result<Vec<Game>>;
+
+    /// Played games of every stored season, in start order
+    async fn fetch_game_history(&self, sport: Sport) -> Result<Vec<Game>>;
+
+    /// Record the latest home advantage estimate for a venue
+    async fn upsert_home_advantage(&self, estimate: &HomeAdvantageEstimate) -> Result<()>;
+
+    /// Latest home advantage estimate of each venue, of one sport or all
+    async fn fetch_home_advantage(&self, sport: Option<Sport>) -> Result<Vec<HomeAdvantageEstimate>>;
+
+    /// Injuries whose player has not yet returned
+    async fn fetch_injury_adjustments(&self, sport: Sport) -> Result<Vec<InjuryAdjustment>>;
+
+    /// Record an assignment, replacing any official previously in that role
+    async fn upsert_official_assignment(&self, assignment: &OfficialAssignment) -> Result<()>;
+
+    /// Officials assigned to a game with their record in that role
+    async fn fetch_official_tendencies(&self, game_id: &str) -> Result<Vec<OfficialTendency>>;
+
+    /// Record a side's starter, replacing any pitcher announced before
+    async fn upsert_probable_pitcher(&self, pitcher: &ProbablePitcher) -> Result<()>;
+
+    /// Starters announced for a game
+    async fn fetch_probable_pitchers(&self, game_id: &str) -> Result<Vec<ProbablePitcher>>;
+
+    async fn upsert_bullpen_usage(&self, usage: &BullpenUsage) -> Result<()>;
+
+    /// Relief innings of games started at or after `since`
+    async fn fetch_bullpen_usage(&self, since: DateTime<Utc>) -> Result<Vec<BullpenUsage>>;
+}
+
+/// Feature store: per-game model inputs, kept for training as well as scoring
+#[async_trait]
+pub trait FeatureRepo: Send + Sync {
+    /// Insert or overwrite a feature for (game, team, name)
+    async fn upsert_game_feature(&self, feature: &GameFeature) -> Result<()>;
+}
+
+/// Log of automated decisions, e.g. throttling a strategy
+#[async_trait]
+pub trait DecisionRepo: Send + Sync {
+    async fn insert_decision(&self, decision: &Decision) -> Result<()>;
+
+    /// Decisions made at or after `since`, most recent first
+    async fn fetch_decisions(&self, since: DateTime<Utc>) -> Result<Vec<Decision>>;
+}
+
+/// Encrypted API credentials; sealing and opening is done by the caller
+#[async_trait]
+pub trait CredentialRepo: Send + Sync {
+    /// Store a new version; fails if (provider, name, version) exists
+    async fn insert_credential(&self, credential: &SealedCredential) -> Result<()>;
+
+    /// The latest version of every credential
+    async fn fetch_credentials(&self) -> Result<Vec<SealedCredential>>;
+}
+
+/// Persistence layer shared by the data pipeline, strategies, execution and risk
+///
+/// Postgres backs live trading; SQLite allows paper trading and backtests
+/// without provisioning a database server; the in-memory store lets engine
+/// and risk logic run without any database.
+pub trait Storage:
+    MarketRepo + SignalRepo + TradeRepo + CashFlowRepo + PortfolioRepo + SeasonRepo + FeatureRepo + DecisionRepo
+    + CredentialRepo
+{
+}
+
+impl<T> Storage for T where
+    T: MarketRepo + SignalRepo + TradeRepo + CashFlowRepo + PortfolioRepo + SeasonRepo + FeatureRepo
+        + DecisionRepo + CredentialRepo
+{
+}
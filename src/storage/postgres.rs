@@ -0,0 +1,3116 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use sqlx::PgPool;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::numeric::NumericError;
+use crate::types::{
+    Bookmaker, BookmakerOdds, CapitalPoint, CashFlow, CashFlowKind, CashFlowSource, Decision, EntryOrder, FeedLatency, Game, GameFeature,
+    HomeAdvantageEstimate, InjuryAdjustment, Market, MarketStatus, MarketTokens, MarketType, OfficialAssignment, OfficialTendency, PortfolioState, Position,
+    PredictionOutcome, Price, Probability, Quote, RestingOrder, RestingOrderKind, RestingOrderStatus, ShadowedStrategy, Signal, SignalType, SlippageEvent, Sport, Strategy, StrategyThrottle, TeamRating, Trade, TradeMark, TradeOrigin, TradeStatus, ConvergenceSample, SettlementReview, StrategyWeight, SignalExecution, EdgeDecay, TradeSizing, FillSide, OnchainFill, MarketMicrostructure, BookSnapshot, LargeTrade, PricePoint, PreEventHistory, Resolution, SealedCredential, Usdc,
+    MarketListing, CoveredMarket, TradeOutcome, TradeSettlement, DeadLetter,
+    ExecutionFailureStatus, FailedExecution, ParentOrder, ChildOrder, ProbablePitcher, BullpenUsage, ExecutionCost, SliceMode,
+};
+use super::metadata::{envelope, upgrade, MetadataKind};
+use super::{
+    CashFlowRepo, CredentialRepo, DecisionRepo, FeatureRepo, MarketRepo, PortfolioBalances, PortfolioRepo, SeasonRepo, SignalRepo, TradeRepo,
+    VenueBalances,
+};
+
+pub struct PostgresStorage {
+    db_pool: PgPool,
+}
+
+impl PostgresStorage {
+    pub fn new(db_pool: PgPool) -> Self {
+        Self { db_pool }
+    }
+
+    pub fn pool(&self) -> &PgPool {
+        &self.db_pool
+    }
+}
+
+#[async_trait]
+impl MarketRepo for PostgresStorage {
+    async fn fetch_active_markets(&self, min_liquidity: Decimal, limit: i64) -> Result<Vec<Market>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                market_id,
+                sport,
+                event_name,
+                event_time,
+                market_type,
+                description,
+                current_liquidity,
+                yes_price,
+                no_price,
+                yes_bid,
+                yes_ask,
+                no_bid,
+                no_ask,
+                yes_bid_size,
+                yes_ask_size,
+                no_bid_size,
+                no_ask_size,
+                condition_id,
+                question_id,
+                yes_token_id,
+                no_token_id,
+                created_at,
+                updated_at
+            FROM markets
+            WHERE status = 'active'
+                AND event_time > NOW()
+                AND current_liquidity >= $1
+            ORDER BY event_time ASC
+            LIMIT $2
+            "#,
+            min_liquidity,
+            limit,
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        // Rows that no longer map are dead-lettered rather than dropped
+        let now = Utc::now();
+        let mut markets = Vec::with_capacity(rows.len());
+        for row in rows {
+            let mapped = (|| {
+                let yes_price = Price::new(row.yes_price).map_err(|_| "YES price outside [0, 1]")?;
+                let no_price = Price::new(row.no_price).map_err(|_| "NO price outside [0, 1]")?;
+                let sport = Sport::parse(&row.sport).ok_or("unknown sport")?;
+                let market_type: MarketType = serde_json::from_str(&row.market_type).map_err(|_| "unknown market type")?;
+                Ok::<_, &str>((yes_price, no_price, sport, market_type))
+            })();
+            let (yes_price, no_price, sport, market_type) = match mapped {
+                Ok(mapped) => mapped,
+                Err(reason) => {
+                    let raw = serde_json::json!({
+                        "sport": row.sport,
+                        "market_type": row.market_type,
+                        "yes_price": row.yes_price,
+                        "no_price": row.no_price,
+                    });
+                    if let Err(e) = self.record_dead_letter(DeadLetter::MARKET_ROW, &row.market_id, reason, &raw, now).await {
+                        warn!("Failed to record dead letter for market {}: {}", row.market_id, e);
+                    }
+                    continue;
+                }
+            };
+
+            markets.push(Market {
+                market_id: row.market_id,
+                sport,
+                event_name: row.event_name,
+                event_time: row.event_time,
+                market_type,
+                description: row.description,
+                resolution_source: None,
+                min_liquidity: Decimal::ZERO,
+                current_liquidity: row.current_liquidity,
+                yes_price,
+                no_price,
+                yes_quote: Quote::from_book(row.yes_bid, row.yes_ask, yes_price)
+                    .with_depth(row.yes_bid_size, row.yes_ask_size),
+                no_quote: Quote::from_book(row.no_bid, row.no_ask, no_price)
+                    .with_depth(row.no_bid_size, row.no_ask_size),
+                status: MarketStatus::Active,
+                tokens: market_tokens(
+                    row.condition_id,
+                    row.question_id,
+                    row.yes_token_id,
+                    row.no_token_id,
+                ),
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            });
+        }
+
+        Ok(markets)
+    }
+
+    async fn fetch_market(&self, market_id: &str) -> Result<Option<Market>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                market_id,
+                sport,
+                event_name,
+                event_time,
+                market_type,
+                description,
+                resolution_source,
+                min_liquidity,
+                current_liquidity,
+                yes_price,
+                no_price,
+                yes_bid,
+                yes_ask,
+                no_bid,
+                no_ask,
+                yes_bid_size,
+                yes_ask_size,
+                no_bid_size,
+                no_ask_size,
+                status,
+                condition_id,
+                question_id,
+                yes_token_id,
+                no_token_id,
+                created_at,
+                updated_at
+            FROM markets
+            WHERE market_id = $1
+            "#,
+            market_id
+        )
+        .fetch_optional(&self.db_pool)
+        .await?;
+
+        Ok(row.and_then(|row| {
+            let yes_price = Price::new(row.yes_price).ok()?;
+            let no_price = Price::new(row.no_price).ok()?;
+
+            Some(Market {
+                market_id: row.market_id,
+                sport: Sport::parse(&row.sport)?,
+                event_name: row.event_name,
+                event_time: row.event_time,
+                market_type: serde_json::from_str(&row.market_type).ok()?,
+                description: row.description,
+                resolution_source: row.resolution_source,
+                min_liquidity: row.min_liquidity.unwrap_or(Decimal::ZERO),
+                current_liquidity: row.current_liquidity,
+                yes_price,
+                no_price,
+                yes_quote: Quote::from_book(row.yes_bid, row.yes_ask, yes_price)
+                    .with_depth(row.yes_bid_size, row.yes_ask_size),
+                no_quote: Quote::from_book(row.no_bid, row.no_ask, no_price)
+                    .with_depth(row.no_bid_size, row.no_ask_size),
+                status: MarketStatus::parse(row.status.as_deref()?)?,
+                tokens: market_tokens(
+                    row.condition_id,
+                    row.question_id,
+                    row.yes_token_id,
+                    row.no_token_id,
+                ),
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            })
+        }))
+    }
+
+    async fn fetch_market_quotes(&self, market_id: &str) -> Result<(Quote, Quote)> {
+        let market = sqlx::query!(
+            r#"
+            SELECT
+                yes_price, no_price, yes_bid, yes_ask, no_bid, no_ask,
+                yes_bid_size, yes_ask_size, no_bid_size, no_ask_size
+            FROM markets
+            WHERE market_id = $1
+            "#,
+            market_id
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok((
+            Quote::from_book(market.yes_bid, market.yes_ask, Price::new(market.yes_price)?)
+                .with_depth(market.yes_bid_size, market.yes_ask_size),
+            Quote::from_book(market.no_bid, market.no_ask, Price::new(market.no_price)?)
+                .with_depth(market.no_bid_size, market.no_ask_size),
+        ))
+    }
+
+    async fn fetch_bookmaker_odds(&self, market_id: &str) -> Result<Vec<BookmakerOdds>> {
+        let odds = sqlx::query_as!(
+            BookmakerOddsRow,
+            r#"
+            SELECT DISTINCT ON (bookmaker)
+                market_id,
+                bookmaker,
+                yes_odds,
+                no_odds,
+                yes_implied_prob,
+                no_implied_prob,
+                timestamp,
+                source_timestamp
+            FROM bookmaker_odds
+            WHERE market_id = $1
+                AND timestamp > NOW() - INTERVAL '1 hour'
+            ORDER BY bookmaker, timestamp DESC
+            "#,
+            market_id
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(odds.into_iter().filter_map(|row| row.try_into().ok()).collect())
+    }
+
+    async fn insert_bookmaker_odds(&self, odds: &BookmakerOdds) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO bookmaker_odds (
+                market_id, bookmaker, odds_type, yes_odds, no_odds,
+                yes_implied_prob, no_implied_prob, timestamp, source_timestamp
+            ) VALUES ($1, $2, 'moneyline', $3, $4, $5, $6, $7, $8)
+            "#,
+            odds.market_id,
+            odds.bookmaker.as_str(),
+            odds.yes_odds,
+            odds.no_odds,
+            odds.yes_implied_prob.value(),
+            odds.no_implied_prob.value(),
+            odds.timestamp,
+            odds.source_timestamp,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn insert_feed_latency(&self, latency: &FeedLatency) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO odds_feed_latency (provider, lines, median_ms, max_ms, measured_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            latency.provider,
+            latency.lines as i32,
+            latency.median_ms,
+            latency.max_ms,
+            latency.measured_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_feed_latency(&self, since: DateTime<Utc>) -> Result<Vec<FeedLatency>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT provider, lines, median_ms, max_ms, measured_at
+            FROM odds_feed_latency
+            WHERE measured_at >= $1
+            ORDER BY measured_at
+            "#,
+            since
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .map(|row| FeedLatency {
+                provider: row.provider,
+                lines: row.lines as i64,
+                median_ms: row.median_ms,
+                max_ms: row.max_ms,
+                measured_at: row.measured_at,
+            })
+            .collect())
+    }
+
+    async fn upsert_market(&self, market: &Market) -> Result<()> {
+        let tokens = market.tokens.as_ref();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO markets (
+                market_id, sport, event_name, event_time, market_type,
+                description, resolution_source, min_liquidity, current_liquidity,
+                yes_price, no_price, yes_bid, yes_ask, no_bid, no_ask,
+                yes_bid_size, yes_ask_size, no_bid_size, no_ask_size, status,
+                condition_id, question_id, yes_token_id, no_token_id
+            ) VALUES (
+                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14,
+                $15, $16, $17, $18, $19, $20, $21, $22, $23, $24
+            )
+            ON CONFLICT (market_id) DO UPDATE SET
+                event_time = EXCLUDED.event_time,
+                current_liquidity = EXCLUDED.current_liquidity,
+                yes_price = EXCLUDED.yes_price,
+                no_price = EXCLUDED.no_price,
+                yes_bid = EXCLUDED.yes_bid,
+                yes_ask = EXCLUDED.yes_ask,
+                no_bid = EXCLUDED.no_bid,
+                no_ask = EXCLUDED.no_ask,
+                yes_bid_size = EXCLUDED.yes_bid_size,
+                yes_ask_size = EXCLUDED.yes_ask_size,
+                no_bid_size = EXCLUDED.no_bid_size,
+                no_ask_size = EXCLUDED.no_ask_size,
+                status = EXCLUDED.status,
+                condition_id = COALESCE(EXCLUDED.condition_id, markets.condition_id),
+                question_id = COALESCE(EXCLUDED.question_id, markets.question_id),
+                yes_token_id = COALESCE(EXCLUDED.yes_token_id, markets.yes_token_id),
+                no_token_id = COALESCE(EXCLUDED.no_token_id, markets.no_token_id),
+                updated_at = NOW()
+            "#,
+            market.market_id,
+            market.sport.as_str(),
+            market.event_name,
+            market.event_time,
+            serde_json::to_string(&market.market_type)?,
+            market.description,
+            market.resolution_source,
+            market.min_liquidity,
+            market.current_liquidity,
+            market.yes_price.value(),
+            market.no_price.value(),
+            market.yes_quote.bid.value(),
+            market.yes_quote.ask.value(),
+            market.no_quote.bid.value(),
+            market.no_quote.ask.value(),
+            market.yes_quote.bid_size,
+            market.yes_quote.ask_size,
+            market.no_quote.bid_size,
+            market.no_quote.ask_size,
+            market.status.as_str(),
+            tokens.map(|t| t.condition_id.as_str()),
+            tokens.map(|t| t.question_id.as_str()),
+            tokens.map(|t| t.yes_token_id.as_str()),
+            tokens.map(|t| t.no_token_id.as_str()),
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_market_quotes(&self, market_id: &str, yes_quote: &Quote, no_quote: &Quote) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE markets
+            SET yes_bid = $2, yes_ask = $3, no_bid = $4, no_ask = $5,
+                yes_bid_size = $6, yes_ask_size = $7, no_bid_size = $8, no_ask_size = $9,
+                updated_at = NOW()
+            WHERE market_id = $1
+            "#,
+            market_id,
+            yes_quote.bid.value(),
+            yes_quote.ask.value(),
+            no_quote.bid.value(),
+            no_quote.ask.value(),
+            yes_quote.bid_size,
+            yes_quote.ask_size,
+            no_quote.bid_size,
+            no_quote.ask_size,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_market_tokens(&self, market_id: &str) -> Result<Option<MarketTokens>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT condition_id, question_id, yes_token_id, no_token_id
+            FROM markets
+            WHERE market_id = $1
+            "#,
+            market_id
+        )
+        .fetch_optional(&self.db_pool)
+        .await?;
+
+        Ok(row.and_then(|r| {
+            market_tokens(r.condition_id, r.question_id, r.yes_token_id, r.no_token_id)
+        }))
+    }
+
+    async fn fetch_market_status(&self, market_id: &str) -> Result<Option<MarketStatus>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT status
+            FROM markets
+            WHERE market_id = $1
+            "#,
+            market_id
+        )
+        .fetch_optional(&self.db_pool)
+        .await?;
+
+        Ok(row.and_then(|r| r.status).as_deref().and_then(MarketStatus::parse))
+    }
+
+    async fn insert_microstructure(&self, features: &MarketMicrostructure) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO market_microstructure (
+                market_id, bid_depth, ask_depth, imbalance, bid_depth_change, ask_depth_change,
+                taker_buy_volume, taker_sell_volume, observed_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+            features.market_id,
+            features.bid_depth,
+            features.ask_depth,
+            features.imbalance,
+            features.bid_depth_change,
+            features.ask_depth_change,
+            features.taker_buy_volume,
+            features.taker_sell_volume,
+            features.observed_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn latest_microstructure(&self, market_id: &str) -> Result<Option<MarketMicrostructure>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT market_id, bid_depth, ask_depth, imbalance, bid_depth_change, ask_depth_change,
+                   taker_buy_volume, taker_sell_volume, observed_at
+            FROM market_microstructure
+            WHERE market_id = $1
+            ORDER BY observed_at DESC
+            LIMIT 1
+            "#,
+            market_id
+        )
+        .fetch_optional(&self.db_pool)
+        .await?;
+
+        Ok(row.map(|row| MarketMicrostructure {
+            market_id: row.market_id,
+            bid_depth: row.bid_depth,
+            ask_depth: row.ask_depth,
+            imbalance: row.imbalance,
+            bid_depth_change: row.bid_depth_change,
+            ask_depth_change: row.ask_depth_change,
+            taker_buy_volume: row.taker_buy_volume,
+            taker_sell_volume: row.taker_sell_volume,
+            observed_at: row.observed_at,
+        }))
+    }
+
+    async fn upsert_book_snapshot(&self, book: &BookSnapshot) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO order_book_snapshots (market_id, bids, asks, captured_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (market_id) DO UPDATE SET
+                bids = EXCLUDED.bids,
+                asks = EXCLUDED.asks,
+                captured_at = EXCLUDED.captured_at
+            "#,
+            book.market_id,
+            serde_json::to_value(&book.bids)?,
+            serde_json::to_value(&book.asks)?,
+            book.captured_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_book_snapshot(&self, market_id: &str) -> Result<Option<BookSnapshot>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT market_id, bids, asks, captured_at
+            FROM order_book_snapshots
+            WHERE market_id = $1
+            "#,
+            market_id
+        )
+        .fetch_optional(&self.db_pool)
+        .await?;
+
+        row.map(|row| {
+            Ok(BookSnapshot {
+                market_id: row.market_id,
+                bids: serde_json::from_value(row.bids)?,
+                asks: serde_json::from_value(row.asks)?,
+                captured_at: row.captured_at,
+            })
+        })
+        .transpose()
+    }
+
+    async fn upsert_market_listing(&self, listing: &MarketListing) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO market_listings (sport, listed, ingested, polled_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (sport) DO UPDATE SET
+                listed = EXCLUDED.listed,
+                ingested = EXCLUDED.ingested,
+                polled_at = EXCLUDED.polled_at
+            "#,
+            listing.sport.as_str(),
+            listing.listed,
+            listing.ingested,
+            listing.polled_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_market_listings(&self) -> Result<Vec<MarketListing>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT sport, listed, ingested, polled_at
+            FROM market_listings
+            "#
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| Some(MarketListing {
+                sport: Sport::parse(&row.sport)?,
+                listed: row.listed,
+                ingested: row.ingested,
+                polled_at: row.polled_at,
+            }))
+            .collect())
+    }
+
+    async fn record_market_evaluations(&self, strategy: &str, market_ids: &[String], evaluated_at: DateTime<Utc>) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO market_evaluations (market_id, strategy, evaluated_at)
+            SELECT market_id, $2, $3 FROM UNNEST($1::VARCHAR[]) AS market_id
+            ON CONFLICT (market_id, strategy) DO UPDATE SET
+                evaluated_at = EXCLUDED.evaluated_at
+            "#,
+            market_ids,
+            strategy,
+            evaluated_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_covered_markets(&self, since: DateTime<Utc>) -> Result<Vec<CoveredMarket>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                m.market_id,
+                m.sport,
+                EXISTS (
+                    SELECT 1 FROM bookmaker_odds o
+                    WHERE o.market_id = m.market_id AND o.timestamp >= $1
+                ) AS "matched!",
+                ARRAY(
+                    SELECT e.strategy FROM market_evaluations e
+                    WHERE e.market_id = m.market_id AND e.evaluated_at >= $1
+                    ORDER BY e.strategy
+                ) AS "strategies!"
+            FROM markets m
+            WHERE m.status = 'active'
+                AND m.event_time > NOW()
+            "#,
+            since
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| Some(CoveredMarket {
+                market_id: row.market_id,
+                sport: Sport::parse(&row.sport)?,
+                matched: row.matched,
+                strategies: row.strategies,
+            }))
+            .collect())
+    }
+
+    async fn record_dead_letter(&self, source: &str, item_key: &str, reason: &str, raw: &serde_json::Value, seen_at: DateTime<Utc>) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO dead_letters (source, item_key, reason, raw, occurrences, first_seen, last_seen)
+            VALUES ($1, $2, $3, $4, 1, $5, $5)
+            ON CONFLICT (source, item_key, reason) DO UPDATE SET
+                raw = EXCLUDED.raw,
+                occurrences = dead_letters.occurrences + 1,
+                last_seen = EXCLUDED.last_seen
+            "#,
+            source,
+            item_key,
+            reason,
+            raw,
+            seen_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_dead_letters(&self, since: DateTime<Utc>) -> Result<Vec<DeadLetter>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT source, item_key, reason, raw, occurrences, first_seen, last_seen
+            FROM dead_letters
+            WHERE last_seen >= $1
+            ORDER BY last_seen DESC
+            "#,
+            since
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .map(|row| DeadLetter {
+                source: row.source,
+                item_key: row.item_key,
+                reason: row.reason,
+                raw: row.raw,
+                occurrences: row.occurrences,
+                first_seen: row.first_seen,
+                last_seen: row.last_seen,
+            })
+            .collect())
+    }
+
+    async fn insert_large_trade(&self, trade: &LargeTrade) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO large_trades (
+                tx_hash, market_id, wallet, position, side, shares, price, notional, size_multiple, traded_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT (tx_hash, wallet, position, side) DO NOTHING
+            "#,
+            trade.tx_hash,
+            trade.market_id,
+            trade.wallet,
+            trade.position.as_str(),
+            trade.side.as_str(),
+            trade.shares,
+            trade.price,
+            trade.notional,
+            trade.size_multiple,
+            trade.traded_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn fetch_large_trades(&self, since: DateTime<Utc>, market_id: Option<&str>) -> Result<Vec<LargeTrade>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT tx_hash, market_id, wallet, position, side, shares, price, notional, size_multiple, traded_at
+            FROM large_trades
+            WHERE traded_at >= $1 AND ($2::text IS NULL OR market_id = $2)
+            ORDER BY traded_at DESC
+            "#,
+            since,
+            market_id,
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| {
+                Some(LargeTrade {
+                    position: Position::parse(&row.position)?,
+                    side: FillSide::parse(&row.side)?,
+                    tx_hash: row.tx_hash,
+                    market_id: row.market_id,
+                    wallet: row.wallet,
+                    shares: row.shares,
+                    price: row.price,
+                    notional: row.notional,
+                    size_multiple: row.size_multiple,
+                    traded_at: row.traded_at,
+                })
+            })
+            .collect())
+    }
+
+    async fn set_market_resolution(&self, market_id: &str, resolution: Resolution, resolved_at: DateTime<Utc>) -> Result<()> {
+        sqlx::query!(
+            "UPDATE markets SET resolution = $2, resolved_at = $3 WHERE market_id = $1",
+            market_id,
+            resolution.as_str(),
+            resolved_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn insert_price_history(&self, points: &[PricePoint]) -> Result<u64> {
+        let mut tx = self.db_pool.begin().await?;
+        let mut inserted = 0;
+        for point in points {
+            inserted += sqlx::query!(
+                r#"
+                INSERT INTO market_price_history (market_id, yes_price, observed_at)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (market_id, observed_at) DO NOTHING
+                "#,
+                point.market_id,
+                point.yes_price.value(),
+                point.observed_at,
+            )
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        }
+        tx.commit().await?;
+
+        Ok(inserted)
+    }
+
+    async fn last_price_history_at(&self, market_id: &str) -> Result<Option<DateTime<Utc>>> {
+        let row = sqlx::query!(
+            "SELECT MAX(observed_at) AS last FROM market_price_history WHERE market_id = $1",
+            market_id,
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(row.last)
+    }
+
+    async fn fetch_pre_event_price_history(&self, since: DateTime<Utc>) -> Result<PreEventHistory> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT h.market_id, h.yes_price, h.observed_at, m.event_time
+            FROM market_price_history h
+            JOIN markets m ON m.market_id = h.market_id
+            WHERE m.event_time >= $1
+                AND m.event_time <= NOW()
+                AND h.observed_at < m.event_time
+            ORDER BY h.market_id, h.observed_at
+            "#,
+            since,
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let mut history = PreEventHistory::default();
+        for row in rows {
+            let Ok(yes_price) = Price::new(row.yes_price) else { continue };
+            history.event_times.insert(row.market_id.clone(), row.event_time);
+            history.points.push(PricePoint { market_id: row.market_id, yes_price, observed_at: row.observed_at });
+        }
+        Ok(history)
+    }
+
+    async fn upsert_closing_line(
+        &self,
+        market_id: &str,
+        yes_price: Price,
+        no_price: Price,
+        captured_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO closing_lines (market_id, yes_price, no_price, captured_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (market_id) DO UPDATE SET
+                yes_price = EXCLUDED.yes_price,
+                no_price = EXCLUDED.no_price,
+                captured_at = EXCLUDED.captured_at
+            "#,
+            market_id,
+            yes_price.value(),
+            no_price.value(),
+            captured_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SignalRepo for PostgresStorage {
+    async fn insert_signal(&self, signal: &Signal) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO signals (
+                signal_id, market_id, strategy, signal_type,
+                confidence, edge_size, recommended_size,
+                current_price, executable_size, quoted_edge, fair_value, metadata
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            "#,
+            signal.signal_id,
+            signal.market_id,
+            signal.strategy.as_str(),
+            serde_json::to_string(&signal.signal_type)?,
+            signal.confidence,
+            signal.edge_size,
+            signal.recommended_size,
+            signal.current_price.value(),
+            signal.executable_size,
+            signal.quoted_edge,
+            signal.fair_value.value(),
+            envelope(MetadataKind::Signal, &signal.metadata),
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_pending_signals(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<Signal>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                signal_id, market_id, strategy, signal_type,
+                confidence, edge_size, recommended_size,
+                current_price, executable_size, quoted_edge, fair_value, generated_at, metadata
+            FROM signals
+            WHERE executed = FALSE
+                AND generated_at > $1
+            ORDER BY confidence DESC, edge_size DESC
+            LIMIT $2
+            "#,
+            since,
+            limit,
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let signals: Vec<Signal> = rows.into_iter()
+            .filter_map(|row| {
+                Some(Signal {
+                    signal_id: row.signal_id,
+                    market_id: row.market_id,
+                    strategy: Strategy::parse(&row.strategy)?,
+                    signal_type: serde_json::from_str(&row.signal_type).ok()?,
+                    confidence: row.confidence,
+                    edge_size: row.edge_size,
+                    recommended_size: row.recommended_size,
+                    current_price: Price::new(row.current_price).ok()?,
+                    executable_size: row.executable_size,
+                    // Signals stored before quoted edges were recorded
+                    quoted_edge: row.quoted_edge.unwrap_or(row.edge_size),
+                    fair_value: Probability::new(row.fair_value).ok()?,
+                    generated_at: row.generated_at,
+                    metadata: upgrade(MetadataKind::Signal, row.metadata),
+                })
+            })
+            .collect();
+
+        Ok(signals)
+    }
+
+    async fn claim_pending_signals(
+        &self,
+        since: DateTime<Utc>,
+        limit: i64,
+        owner: &str,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<Signal>> {
+        // SKIP LOCKED passes over rows a concurrent claim has locked rather
+        // than waiting on them and then claiming them a second time
+        let rows = sqlx::query!(
+            r#"
+            UPDATE signals s
+            SET claimed_by = $3, claimed_until = $4
+            FROM (
+                SELECT signal_id
+                FROM signals
+                WHERE executed = FALSE
+                    AND generated_at > $1
+                    AND (claimed_until IS NULL OR claimed_until <= NOW())
+                ORDER BY confidence DESC, edge_size DESC
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            ) claimable
+            WHERE s.signal_id = claimable.signal_id
+            RETURNING
+                s.signal_id, s.market_id, s.strategy, s.signal_type,
+                s.confidence, s.edge_size, s.recommended_size,
+                s.current_price, s.executable_size, s.quoted_edge, s.fair_value, s.generated_at, s.metadata
+            "#,
+            since,
+            limit,
+            owner,
+            until,
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let mut signals: Vec<Signal> = rows.into_iter()
+            .filter_map(|row| {
+                Some(Signal {
+                    signal_id: row.signal_id,
+                    market_id: row.market_id,
+                    strategy: Strategy::parse(&row.strategy)?,
+                    signal_type: serde_json::from_str(&row.signal_type).ok()?,
+                    confidence: row.confidence,
+                    edge_size: row.edge_size,
+                    recommended_size: row.recommended_size,
+                    current_price: Price::new(row.current_price).ok()?,
+                    executable_size: row.executable_size,
+                    quoted_edge: row.quoted_edge.unwrap_or(row.edge_size),
+                    fair_value: Probability::new(row.fair_value).ok()?,
+                    generated_at: row.generated_at,
+                    metadata: upgrade(MetadataKind::Signal, row.metadata),
+                })
+            })
+            .collect();
+        // RETURNING does not keep the subquery's order
+        signals.sort_by(|a, b| {
+            b.confidence.cmp(&a.confidence).then(b.edge_size.cmp(&a.edge_size))
+        });
+
+        Ok(signals)
+    }
+
+    async fn release_signal(&self, signal_id: Uuid, owner: &str) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE signals
+            SET claimed_by = NULL, claimed_until = NULL
+            WHERE signal_id = $1 AND claimed_by = $2
+            "#,
+            signal_id,
+            owner,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn renew_signal_claim(&self, signal_id: Uuid, owner: &str, until: DateTime<Utc>) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE signals
+            SET claimed_until = $3
+            WHERE signal_id = $1 AND claimed_by = $2 AND executed = false
+            "#,
+            signal_id,
+            owner,
+            until,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn fetch_signal(&self, signal_id: Uuid) -> Result<Option<Signal>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                signal_id, market_id, strategy, signal_type,
+                confidence, edge_size, recommended_size,
+                current_price, executable_size, quoted_edge, fair_value, generated_at, metadata
+            FROM signals
+            WHERE signal_id = $1
+            "#,
+            signal_id,
+        )
+        .fetch_optional(&self.db_pool)
+        .await?;
+
+        Ok(row.and_then(|row| {
+            Some(Signal {
+                signal_id: row.signal_id,
+                market_id: row.market_id,
+                strategy: Strategy::parse(&row.strategy)?,
+                signal_type: serde_json::from_str(&row.signal_type).ok()?,
+                confidence: row.confidence,
+                edge_size: row.edge_size,
+                recommended_size: row.recommended_size,
+                current_price: Price::new(row.current_price).ok()?,
+                executable_size: row.executable_size,
+                quoted_edge: row.quoted_edge.unwrap_or(row.edge_size),
+                fair_value: Probability::new(row.fair_value).ok()?,
+                generated_at: row.generated_at,
+                metadata: upgrade(MetadataKind::Signal, row.metadata),
+            })
+        }))
+    }
+
+    async fn fetch_market_signals(&self, market_id: &str, since: DateTime<Utc>) -> Result<Vec<Signal>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                signal_id, market_id, strategy, signal_type,
+                confidence, edge_size, recommended_size,
+                current_price, executable_size, quoted_edge, fair_value, generated_at, metadata
+            FROM signals
+            WHERE market_id = $1
+                AND generated_at >= $2
+            ORDER BY generated_at ASC
+            "#,
+            market_id,
+            since,
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| {
+                Some(Signal {
+                    signal_id: row.signal_id,
+                    market_id: row.market_id,
+                    strategy: Strategy::parse(&row.strategy)?,
+                    signal_type: serde_json::from_str(&row.signal_type).ok()?,
+                    confidence: row.confidence,
+                    edge_size: row.edge_size,
+                    recommended_size: row.recommended_size,
+                    current_price: Price::new(row.current_price).ok()?,
+                    executable_size: row.executable_size,
+                    quoted_edge: row.quoted_edge.unwrap_or(row.edge_size),
+                    fair_value: Probability::new(row.fair_value).ok()?,
+                    generated_at: row.generated_at,
+                    metadata: upgrade(MetadataKind::Signal, row.metadata),
+                })
+            })
+            .collect())
+    }
+
+    async fn upsert_failed_execution(&self, failure: &FailedExecution) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO failed_executions (
+                signal_id, market_id, strategy, attempts, last_error, status,
+                next_attempt_at, first_failed_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (signal_id) DO UPDATE SET
+                attempts = EXCLUDED.attempts,
+                last_error = EXCLUDED.last_error,
+                status = EXCLUDED.status,
+                next_attempt_at = EXCLUDED.next_attempt_at,
+                updated_at = EXCLUDED.updated_at
+            "#,
+            failure.signal_id,
+            failure.market_id,
+            failure.strategy.as_str(),
+            failure.attempts,
+            failure.last_error,
+            failure.status.as_str(),
+            failure.next_attempt_at,
+            failure.first_failed_at,
+            failure.updated_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_failed_execution(&self, signal_id: Uuid) -> Result<Option<FailedExecution>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT signal_id, market_id, strategy, attempts, last_error, status,
+                   next_attempt_at, first_failed_at, updated_at
+            FROM failed_executions
+            WHERE signal_id = $1
+            "#,
+            signal_id
+        )
+        .fetch_optional(&self.db_pool)
+        .await?;
+
+        Ok(row.and_then(|row| Some(FailedExecution {
+            signal_id: row.signal_id,
+            market_id: row.market_id,
+            strategy: Strategy::parse(&row.strategy)?,
+            attempts: row.attempts,
+            last_error: row.last_error,
+            status: ExecutionFailureStatus::parse(&row.status)?,
+            next_attempt_at: row.next_attempt_at,
+            first_failed_at: row.first_failed_at,
+            updated_at: row.updated_at,
+        })))
+    }
+
+    async fn fetch_failed_executions(&self, since: DateTime<Utc>) -> Result<Vec<FailedExecution>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT signal_id, market_id, strategy, attempts, last_error, status,
+                   next_attempt_at, first_failed_at, updated_at
+            FROM failed_executions
+            WHERE updated_at >= $1
+            ORDER BY updated_at DESC
+            "#,
+            since
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| Some(FailedExecution {
+                signal_id: row.signal_id,
+                market_id: row.market_id,
+                strategy: Strategy::parse(&row.strategy)?,
+                attempts: row.attempts,
+                last_error: row.last_error,
+                status: ExecutionFailureStatus::parse(&row.status)?,
+                next_attempt_at: row.next_attempt_at,
+                first_failed_at: row.first_failed_at,
+                updated_at: row.updated_at,
+            }))
+            .collect())
+    }
+    async fn latest_fair_value(&self, market_id: &str, position: Position) -> Result<Option<Decimal>> {
+        let signal_type = serde_json::to_string(&SignalType::buy(position))?;
+        let row = sqlx::query!(
+            r#"
+            SELECT fair_value
+            FROM signals
+            WHERE market_id = $1 AND signal_type = $2 AND fair_value IS NOT NULL
+            ORDER BY generated_at DESC
+            LIMIT 1
+            "#,
+            market_id,
+            signal_type,
+        )
+        .fetch_optional(&self.db_pool)
+        .await?;
+
+        Ok(row.and_then(|r| r.fair_value))
+    }
+
+    async fn mark_signal_executed(&self, signal_id: Uuid, trade_id: Option<Uuid>) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE signals
+            SET executed = TRUE, executed_trade_id = $2
+            WHERE signal_id = $1
+            "#,
+            signal_id,
+            trade_id,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn shadow_strategy(&self, strategy: Strategy, reason: &str) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO shadowed_strategies (strategy, reason, since)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (strategy) DO NOTHING
+            "#,
+            strategy.as_str(),
+            reason,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn unshadow_strategy(&self, strategy: Strategy) -> Result<()> {
+        sqlx::query!("DELETE FROM shadowed_strategies WHERE strategy = $1", strategy.as_str())
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_shadowed_strategies(&self) -> Result<Vec<ShadowedStrategy>> {
+        let rows = sqlx::query!("SELECT strategy, reason, since FROM shadowed_strategies")
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| {
+                Some(ShadowedStrategy {
+                    strategy: Strategy::parse(&row.strategy)?,
+                    reason: row.reason,
+                    since: row.since,
+                })
+            })
+            .collect())
+    }
+
+    async fn upsert_strategy_throttle(&self, throttle: &StrategyThrottle) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO strategy_throttles (strategy, size_multiplier, max_daily_signals, realized_edge, updated_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (strategy) DO UPDATE SET
+                size_multiplier = EXCLUDED.size_multiplier,
+                max_daily_signals = EXCLUDED.max_daily_signals,
+                realized_edge = EXCLUDED.realized_edge,
+                updated_at = EXCLUDED.updated_at
+            "#,
+            throttle.strategy.as_str(),
+            throttle.size_multiplier,
+            throttle.max_daily_signals as i32,
+            throttle.realized_edge,
+            throttle.updated_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn clear_strategy_throttle(&self, strategy: Strategy) -> Result<()> {
+        sqlx::query!("DELETE FROM strategy_throttles WHERE strategy = $1", strategy.as_str())
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_strategy_throttles(&self) -> Result<Vec<StrategyThrottle>> {
+        let rows = sqlx::query!(
+            "SELECT strategy, size_multiplier, max_daily_signals, realized_edge, updated_at FROM strategy_throttles"
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| {
+                Some(StrategyThrottle {
+                    strategy: Strategy::parse(&row.strategy)?,
+                    size_multiplier: row.size_multiplier,
+                    max_daily_signals: row.max_daily_signals as i64,
+                    realized_edge: row.realized_edge,
+                    updated_at: row.updated_at,
+                })
+            })
+            .collect())
+    }
+
+    async fn replace_strategy_weights(&self, weights: &[StrategyWeight]) -> Result<()> {
+        let mut tx = self.db_pool.begin().await?;
+        sqlx::query!("DELETE FROM strategy_weights").execute(&mut *tx).await?;
+        for weight in weights {
+            sqlx::query!(
+                r#"
+                INSERT INTO strategy_weights (strategy, weight, mean_return, volatility, updated_at)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+                weight.strategy.as_str(),
+                weight.weight,
+                weight.mean_return,
+                weight.volatility,
+                weight.updated_at,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn fetch_strategy_weights(&self) -> Result<Vec<StrategyWeight>> {
+        let rows = sqlx::query!(
+            "SELECT strategy, weight, mean_return, volatility, updated_at FROM strategy_weights"
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| {
+                Some(StrategyWeight {
+                    strategy: Strategy::parse(&row.strategy)?,
+                    weight: row.weight,
+                    mean_return: row.mean_return,
+                    volatility: row.volatility,
+                    updated_at: row.updated_at,
+                })
+            })
+            .collect())
+    }
+
+    async fn record_execution_quote(&self, signal_id: Uuid, price: Price, at: DateTime<Utc>) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE signals
+            SET execution_quote = $2, execution_quoted_at = $3
+            WHERE signal_id = $1
+            "#,
+            signal_id,
+            price.value(),
+            at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_signal_executions(&self, since: DateTime<Utc>) -> Result<Vec<SignalExecution>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                strategy, current_price AS "signal_price!", fair_value AS "fair_value!",
+                generated_at AS "generated_at!", execution_quote AS "quoted_price!",
+                execution_quoted_at AS "quoted_at!"
+            FROM signals
+            WHERE execution_quoted_at >= $1
+                AND execution_quote IS NOT NULL
+                AND current_price IS NOT NULL
+                AND fair_value IS NOT NULL
+                AND generated_at IS NOT NULL
+            ORDER BY execution_quoted_at ASC
+            "#,
+            since,
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| {
+                Some(SignalExecution {
+                    strategy: Strategy::parse(&row.strategy)?,
+                    signal_price: row.signal_price,
+                    fair_value: row.fair_value,
+                    generated_at: row.generated_at,
+                    quoted_price: row.quoted_price,
+                    quoted_at: row.quoted_at,
+                })
+            })
+            .collect())
+    }
+
+    async fn replace_edge_decays(&self, decays: &[EdgeDecay]) -> Result<()> {
+        let mut tx = self.db_pool.begin().await?;
+        sqlx::query!("DELETE FROM edge_decay").execute(&mut *tx).await?;
+        for decay in decays {
+            sqlx::query!(
+                r#"
+                INSERT INTO edge_decay (strategy, half_life_secs, samples, updated_at)
+                VALUES ($1, $2, $3, $4)
+                "#,
+                decay.strategy.as_str(),
+                decay.half_life_secs,
+                decay.samples,
+                decay.updated_at,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn fetch_edge_decays(&self) -> Result<Vec<EdgeDecay>> {
+        let rows = sqlx::query!(
+            "SELECT strategy, half_life_secs, samples, updated_at FROM edge_decay"
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| {
+                Some(EdgeDecay {
+                    strategy: Strategy::parse(&row.strategy)?,
+                    half_life_secs: row.half_life_secs,
+                    samples: row.samples,
+                    updated_at: row.updated_at,
+                })
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl TradeRepo for PostgresStorage {
+    async fn insert_trade(&self, trade: &Trade) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO trades (
+                trade_id, market_id, strategy, position, quantity,
+                entry_price, entry_time, tx_hash_entry, status, gas_cost, fees, slippage, venue, origin
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            "#,
+            trade.trade_id,
+            trade.market_id,
+            trade.strategy.as_str(),
+            trade.position.as_str(),
+            trade.quantity,
+            trade.entry_price,
+            trade.entry_time,
+            trade.tx_hash_entry,
+            trade.status.as_str(),
+            trade.gas_cost,
+            trade.fees.map(|fees| fees.value()),
+            trade.slippage,
+            trade.venue,
+            trade.origin.as_str(),
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_open_trades(&self) -> Result<Vec<Trade>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                trade_id, market_id, strategy, position, quantity,
+                entry_price, entry_time, tx_hash_entry, gas_cost, fees, venue, origin
+            FROM trades
+            WHERE status = 'open'
+            "#
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let trades: Vec<Trade> = rows.into_iter()
+            .filter_map(|row| {
+                Some(Trade {
+                    trade_id: row.trade_id,
+                    market_id: row.market_id,
+                    strategy: Strategy::parse(&row.strategy)?,
+                    position: Position::parse(&row.position)?,
+                    quantity: row.quantity,
+                    entry_price: row.entry_price,
+                    exit_price: None,
+                    entry_time: row.entry_time,
+                    exit_time: None,
+                    gas_cost: row.gas_cost,
+                    fees: row.fees.map(Usdc::new),
+                    slippage: None,
+                    pnl: None,
+                    pnl_percent: None,
+                    max_adverse_excursion: None,
+                    max_favorable_excursion: None,
+                    status: TradeStatus::Open,
+                    tx_hash_entry: row.tx_hash_entry,
+                    tx_hash_exit: None,
+                    venue: row.venue,
+                    origin: TradeOrigin::parse(&row.origin)?,
+                })
+            })
+            .collect();
+
+        Ok(trades)
+    }
+
+    async fn fetch_trade(&self, trade_id: Uuid) -> Result<Option<Trade>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                trade_id, market_id, strategy, position, quantity,
+                entry_price, exit_price, entry_time, exit_time, gas_cost, fees,
+                slippage, pnl, pnl_percent, max_adverse_excursion, max_favorable_excursion,
+                status, tx_hash_entry, tx_hash_exit, venue, origin
+            FROM trades
+            WHERE trade_id = $1
+            "#,
+            trade_id,
+        )
+        .fetch_optional(&self.db_pool)
+        .await?;
+
+        Ok(row.and_then(|row| {
+            Some(Trade {
+                trade_id: row.trade_id,
+                market_id: row.market_id,
+                strategy: Strategy::parse(&row.strategy)?,
+                position: Position::parse(&row.position)?,
+                quantity: row.quantity,
+                entry_price: row.entry_price,
+                exit_price: row.exit_price,
+                entry_time: row.entry_time,
+                exit_time: row.exit_time,
+                gas_cost: row.gas_cost,
+                fees: row.fees.map(Usdc::new),
+                slippage: row.slippage,
+                pnl: row.pnl.map(Usdc::new),
+                pnl_percent: row.pnl_percent,
+                max_adverse_excursion: row.max_adverse_excursion,
+                max_favorable_excursion: row.max_favorable_excursion,
+                status: TradeStatus::parse(row.status.as_deref()?)?,
+                tx_hash_entry: row.tx_hash_entry,
+                tx_hash_exit: row.tx_hash_exit,
+                venue: row.venue,
+                origin: TradeOrigin::parse(&row.origin)?,
+            })
+        }))
+    }
+
+    async fn fetch_trade_by_entry_tx(&self, tx_hash: &str) -> Result<Option<Trade>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                trade_id, market_id, strategy, position, quantity,
+                entry_price, exit_price, entry_time, exit_time, gas_cost, fees,
+                slippage, pnl, pnl_percent, max_adverse_excursion, max_favorable_excursion,
+                status, tx_hash_entry, tx_hash_exit, venue, origin
+            FROM trades
+            WHERE tx_hash_entry = $1
+            "#,
+            tx_hash,
+        )
+        .fetch_optional(&self.db_pool)
+        .await?;
+
+        Ok(row.and_then(|row| {
+            Some(Trade {
+                trade_id: row.trade_id,
+                market_id: row.market_id,
+                strategy: Strategy::parse(&row.strategy)?,
+                position: Position::parse(&row.position)?,
+                quantity: row.quantity,
+                entry_price: row.entry_price,
+                exit_price: row.exit_price,
+                entry_time: row.entry_time,
+                exit_time: row.exit_time,
+                gas_cost: row.gas_cost,
+                fees: row.fees.map(Usdc::new),
+                slippage: row.slippage,
+                pnl: row.pnl.map(Usdc::new),
+                pnl_percent: row.pnl_percent,
+                max_adverse_excursion: row.max_adverse_excursion,
+                max_favorable_excursion: row.max_favorable_excursion,
+                status: TradeStatus::parse(row.status.as_deref()?)?,
+                tx_hash_entry: row.tx_hash_entry,
+                tx_hash_exit: row.tx_hash_exit,
+                venue: row.venue,
+                origin: TradeOrigin::parse(&row.origin)?,
+            })
+        }))
+    }
+
+    async fn fetch_closed_trades(&self, since: DateTime<Utc>) -> Result<Vec<Trade>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                trade_id, market_id, strategy, position, quantity,
+                entry_price, exit_price, entry_time, exit_time, gas_cost, fees,
+                slippage, pnl, pnl_percent, max_adverse_excursion, max_favorable_excursion,
+                tx_hash_entry, tx_hash_exit, venue, origin
+            FROM trades
+            WHERE status = 'closed' AND exit_time >= $1
+            ORDER BY exit_time ASC
+            "#,
+            since,
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| {
+                Some(Trade {
+                    trade_id: row.trade_id,
+                    market_id: row.market_id,
+                    strategy: Strategy::parse(&row.strategy)?,
+                    position: Position::parse(&row.position)?,
+                    quantity: row.quantity,
+                    entry_price: row.entry_price,
+                    exit_price: row.exit_price,
+                    entry_time: row.entry_time,
+                    exit_time: row.exit_time,
+                    gas_cost: row.gas_cost,
+                    fees: row.fees.map(Usdc::new),
+                    slippage: row.slippage,
+                    pnl: row.pnl.map(Usdc::new),
+                    pnl_percent: row.pnl_percent,
+                    max_adverse_excursion: row.max_adverse_excursion,
+                    max_favorable_excursion: row.max_favorable_excursion,
+                    status: TradeStatus::Closed,
+                    tx_hash_entry: row.tx_hash_entry,
+                    tx_hash_exit: row.tx_hash_exit,
+                    venue: row.venue,
+                    origin: TradeOrigin::parse(&row.origin)?,
+                })
+            })
+            .collect())
+    }
+
+    async fn fetch_prediction_outcomes(&self, since: DateTime<Utc>) -> Result<Vec<PredictionOutcome>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                t.trade_id, t.strategy, s.fair_value, t.entry_price,
+                t.exit_price AS "exit_price!", t.exit_time AS "exit_time!", ts.payout AS "payout?",
+                CASE t.position WHEN 'yes' THEN c.yes_price ELSE c.no_price END AS closing_price
+            FROM trades t
+            JOIN signals s ON s.executed_trade_id = t.trade_id
+            LEFT JOIN closing_lines c ON c.market_id = t.market_id
+            LEFT JOIN trade_settlements ts ON ts.trade_id = t.trade_id AND ts.outcome <> 'void'
+            WHERE t.status = 'closed'
+                AND t.exit_time >= $1
+                AND t.exit_price IS NOT NULL
+                AND s.fair_value IS NOT NULL
+            ORDER BY t.exit_time ASC
+            "#,
+            since,
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| {
+                Some(PredictionOutcome {
+                    trade_id: row.trade_id,
+                    strategy: Strategy::parse(&row.strategy)?,
+                    fair_value: row.fair_value?,
+                    entry_price: row.entry_price,
+                    exit_price: row.exit_price,
+                    payout: row.payout,
+                    closing_price: row.closing_price,
+                    exit_time: row.exit_time,
+                })
+            })
+            .collect())
+    }
+
+    async fn close_trade(
+        &self,
+        trade_id: Uuid,
+        exit_price: Decimal,
+        exit_time: DateTime<Utc>,
+        pnl: Usdc,
+        tx_hash: &str,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE trades
+            SET exit_price = $2,
+                exit_time = $3,
+                pnl = $4,
+                status = 'closed',
+                tx_hash_exit = $5,
+                -- The exit counts as a final mark
+                max_adverse_excursion = LEAST(
+                    0,
+                    ($2 - entry_price) * quantity,
+                    COALESCE((SELECT max_adverse_excursion FROM trade_marks WHERE trade_id = $1), 0)
+                ),
+                max_favorable_excursion = GREATEST(
+                    0,
+                    ($2 - entry_price) * quantity,
+                    COALESCE((SELECT max_favorable_excursion FROM trade_marks WHERE trade_id = $1), 0)
+                )
+            WHERE trade_id = $1
+            "#,
+            trade_id,
+            exit_price,
+            exit_time,
+            pnl.value(),
+            tx_hash,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn correct_trade_entry(
+        &self,
+        trade_id: Uuid,
+        entry_price: Decimal,
+        quantity: Decimal,
+        slippage: Option<Decimal>,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE trades
+            SET entry_price = $2, quantity = $3, slippage = $4
+            WHERE trade_id = $1 AND status = 'open'
+            "#,
+            trade_id,
+            entry_price,
+            quantity,
+            slippage,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn recent_closed_pnls(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<Option<Decimal>>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT pnl
+            FROM trades
+            WHERE status = 'closed'
+                AND entry_time > $1
+            ORDER BY entry_time DESC
+            LIMIT $2
+            "#,
+            since,
+            limit,
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| row.pnl).collect())
+    }
+
+    async fn realized_pnl_today(&self) -> Result<Decimal> {
+        let today_pnl = sqlx::query!(
+            r#"
+            SELECT COALESCE(SUM(pnl), 0) as total_pnl
+            FROM trades
+            WHERE status = 'closed'
+                AND DATE(exit_time) = CURRENT_DATE
+            "#
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(today_pnl.total_pnl.unwrap_or(dec!(0.0)))
+    }
+
+    async fn execution_costs_today(&self) -> Result<Decimal> {
+        let costs = sqlx::query!(
+            r#"
+            SELECT
+                (SELECT COALESCE(SUM(COALESCE(gas_cost, 0) + COALESCE(fees, 0)), 0)
+                 FROM trades
+                 WHERE DATE(entry_time) = CURRENT_DATE)
+                + (SELECT COALESCE(SUM(amount), 0)
+                   FROM execution_costs
+                   WHERE DATE(incurred_at) = CURRENT_DATE) as total_costs
+            "#
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(costs.total_costs.unwrap_or(dec!(0.0)))
+    }
+
+    async fn insert_execution_cost(&self, cost: &ExecutionCost) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO execution_costs (cost_id, kind, amount, trade_id, tx_hash, incurred_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            cost.cost_id,
+            cost.kind.as_str(),
+            cost.amount,
+            cost.trade_id,
+            cost.tx_hash,
+            cost.incurred_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn count_open_trades(&self) -> Result<i64> {
+        let positions = sqlx::query!(
+            r#"
+            SELECT COUNT(*) as count
+            FROM trades
+            WHERE status = 'open'
+            "#
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(positions.count.unwrap_or(0))
+    }
+
+    async fn count_trades_today(&self) -> Result<i64> {
+        let trades = sqlx::query!(
+            r#"
+            SELECT COUNT(*) as count
+            FROM trades
+            WHERE DATE(entry_time) = CURRENT_DATE
+            "#
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(trades.count.unwrap_or(0))
+    }
+
+    async fn count_strategy_trades_today(&self, strategy: Strategy) -> Result<i64> {
+        let trades = sqlx::query!(
+            r#"
+            SELECT COUNT(*) as count
+            FROM trades
+            WHERE strategy = $1 AND DATE(entry_time) = CURRENT_DATE
+            "#,
+            strategy.as_str(),
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(trades.count.unwrap_or(0))
+    }
+
+    async fn insert_slippage_event(&self, event: &SlippageEvent) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO slippage_events (
+                event_id, trade_id, signal_id, market_id, expected_price, fill_price, slippage,
+                flattened, occurred_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+            event.event_id,
+            event.trade_id,
+            event.signal_id,
+            event.market_id,
+            event.expected_price,
+            event.fill_price,
+            event.slippage,
+            event.flattened,
+            event.occurred_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn upsert_trade_mark(&self, mark: &TradeMark) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO trade_marks (
+                trade_id, mark_price, unrealized_pnl, max_adverse_excursion, max_favorable_excursion, marked_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (trade_id) DO UPDATE SET
+                mark_price = EXCLUDED.mark_price,
+                unrealized_pnl = EXCLUDED.unrealized_pnl,
+                max_adverse_excursion = EXCLUDED.max_adverse_excursion,
+                max_favorable_excursion = EXCLUDED.max_favorable_excursion,
+                marked_at = EXCLUDED.marked_at
+            "#,
+            mark.trade_id,
+            mark.mark_price,
+            mark.unrealized_pnl,
+            mark.max_adverse_excursion,
+            mark.max_favorable_excursion,
+            mark.marked_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_trade_marks(&self) -> Result<Vec<TradeMark>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                m.trade_id, m.mark_price, m.unrealized_pnl, m.max_adverse_excursion,
+                m.max_favorable_excursion, m.marked_at
+            FROM trade_marks m
+            JOIN trades t ON t.trade_id = m.trade_id
+            WHERE t.status = 'open'
+            "#
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .map(|row| TradeMark {
+                trade_id: row.trade_id,
+                mark_price: row.mark_price,
+                unrealized_pnl: row.unrealized_pnl,
+                max_adverse_excursion: row.max_adverse_excursion,
+                max_favorable_excursion: row.max_favorable_excursion,
+                marked_at: row.marked_at,
+            })
+            .collect())
+    }
+
+    async fn insert_convergence_sample(&self, sample: &ConvergenceSample) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO trade_convergence (trade_id, market_price, fair_value, divergence, sampled_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            sample.trade_id,
+            sample.market_price,
+            sample.fair_value,
+            sample.divergence,
+            sample.sampled_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_convergence_samples(&self, trade_id: Uuid) -> Result<Vec<ConvergenceSample>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT trade_id, market_price, fair_value, divergence, sampled_at
+            FROM trade_convergence
+            WHERE trade_id = $1
+            ORDER BY sampled_at
+            "#,
+            trade_id
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .map(|row| ConvergenceSample {
+                trade_id: row.trade_id,
+                market_price: row.market_price,
+                fair_value: row.fair_value,
+                divergence: row.divergence,
+                sampled_at: row.sampled_at,
+            })
+            .collect())
+    }
+
+    async fn insert_trade_sizing(&self, sizing: &TradeSizing) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO trade_sizing (trade_id, bankroll, stake, sized_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (trade_id) DO NOTHING
+            "#,
+            sizing.trade_id,
+            sizing.bankroll,
+            sizing.stake,
+            sizing.sized_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_trade_sizing(&self, since: DateTime<Utc>) -> Result<Vec<TradeSizing>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT z.trade_id, z.bankroll, z.stake, z.sized_at
+            FROM trade_sizing z
+            JOIN trades t ON t.trade_id = z.trade_id
+            WHERE t.status = 'closed' AND t.exit_time >= $1
+            "#,
+            since,
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .map(|row| TradeSizing {
+                trade_id: row.trade_id,
+                bankroll: row.bankroll,
+                stake: row.stake,
+                sized_at: row.sized_at,
+            })
+            .collect())
+    }
+
+    async fn upsert_settlement_review(&self, review: &SettlementReview) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO settlement_reviews (trade_id, market_id, reported_payout, onchain_payout, reason, flagged_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (trade_id) DO UPDATE SET
+                reported_payout = EXCLUDED.reported_payout,
+                onchain_payout = EXCLUDED.onchain_payout,
+                reason = EXCLUDED.reason
+            WHERE settlement_reviews.resolved_at IS NULL
+            "#,
+            review.trade_id,
+            review.market_id,
+            review.reported_payout,
+            review.onchain_payout,
+            review.reason,
+            review.flagged_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_open_settlement_reviews(&self) -> Result<Vec<SettlementReview>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT trade_id, market_id, reported_payout, onchain_payout, reason, flagged_at, resolved_at
+            FROM settlement_reviews
+            WHERE resolved_at IS NULL
+            ORDER BY flagged_at
+            "#
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .map(|row| SettlementReview {
+                trade_id: row.trade_id,
+                market_id: row.market_id,
+                reported_payout: row.reported_payout,
+                onchain_payout: row.onchain_payout,
+                reason: row.reason,
+                flagged_at: row.flagged_at,
+                resolved_at: row.resolved_at,
+            })
+            .collect())
+    }
+
+    async fn resolve_settlement_review(&self, trade_id: Uuid, resolved_at: DateTime<Utc>) -> Result<()> {
+        sqlx::query!(
+            "UPDATE settlement_reviews SET resolved_at = $2 WHERE trade_id = $1 AND resolved_at IS NULL",
+            trade_id,
+            resolved_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn insert_trade_settlement(&self, settlement: &TradeSettlement) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO trade_settlements (trade_id, market_id, outcome, payout, pnl, tx_hash, settled_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (trade_id) DO NOTHING
+            "#,
+            settlement.trade_id,
+            settlement.market_id,
+            settlement.outcome.as_str(),
+            settlement.payout,
+            settlement.pnl.value(),
+            settlement.tx_hash,
+            settlement.settled_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_trade_settlements(&self, since: DateTime<Utc>) -> Result<Vec<TradeSettlement>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT trade_id, market_id, outcome, payout, pnl, tx_hash, settled_at
+            FROM trade_settlements
+            WHERE settled_at >= $1
+            ORDER BY settled_at DESC
+            "#,
+            since
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| Some(TradeSettlement {
+                trade_id: row.trade_id,
+                market_id: row.market_id,
+                outcome: TradeOutcome::parse(&row.outcome)?,
+                payout: row.payout,
+                pnl: Usdc::new(row.pnl),
+                tx_hash: row.tx_hash,
+                settled_at: row.settled_at,
+            }))
+            .collect())
+    }
+
+    async fn insert_onchain_fill(&self, fill: &OnchainFill) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO onchain_fills (
+                tx_hash, log_index, order_hash, token_id, side, shares, price, fee, block_number, observed_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT (tx_hash, log_index) DO NOTHING
+            "#,
+            fill.tx_hash,
+            fill.log_index as i32,
+            fill.order_hash,
+            fill.token_id,
+            fill.side.as_str(),
+            fill.shares,
+            fill.price,
+            fill.fee.value(),
+            fill.block_number,
+            fill.observed_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn fetch_order_fills(&self, order_hash: &str) -> Result<Vec<OnchainFill>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT tx_hash, log_index, order_hash, token_id, side, shares, price, fee, block_number, observed_at
+            FROM onchain_fills
+            WHERE order_hash = $1
+            ORDER BY block_number, log_index
+            "#,
+            order_hash,
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| {
+                Some(OnchainFill {
+                    tx_hash: row.tx_hash,
+                    log_index: row.log_index as i64,
+                    order_hash: row.order_hash,
+                    token_id: row.token_id,
+                    side: FillSide::parse(&row.side)?,
+                    shares: row.shares,
+                    price: row.price,
+                    fee: Usdc::new(row.fee),
+                    block_number: row.block_number,
+                    observed_at: row.observed_at,
+                })
+            })
+            .collect())
+    }
+
+    async fn fetch_tx_fills(&self, tx_hash: &str) -> Result<Vec<OnchainFill>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT tx_hash, log_index, order_hash, token_id, side, shares, price, fee, block_number, observed_at
+            FROM onchain_fills
+            WHERE tx_hash = $1
+            ORDER BY log_index
+            "#,
+            tx_hash,
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| {
+                Some(OnchainFill {
+                    tx_hash: row.tx_hash,
+                    log_index: row.log_index as i64,
+                    order_hash: row.order_hash,
+                    token_id: row.token_id,
+                    side: FillSide::parse(&row.side)?,
+                    shares: row.shares,
+                    price: row.price,
+                    fee: Usdc::new(row.fee),
+                    block_number: row.block_number,
+                    observed_at: row.observed_at,
+                })
+            })
+            .collect())
+    }
+
+    async fn insert_resting_order(&self, order: &RestingOrder) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO resting_orders (order_id, trade_id, kind, price, quantity, status, placed_at)
+            VALUES ($1, $2, $3, $4, $5, 'open', $6)
+            "#,
+            order.order_id,
+            order.trade_id,
+            order.kind.as_str(),
+            order.price,
+            order.quantity,
+            order.placed_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_resting_orders(&self, trade_id: Uuid) -> Result<Vec<RestingOrder>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT order_id, trade_id, kind, price, quantity, placed_at
+            FROM resting_orders
+            WHERE trade_id = $1 AND status = 'open'
+            "#,
+            trade_id
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| {
+                Some(RestingOrder {
+                    order_id: row.order_id,
+                    trade_id: row.trade_id,
+                    kind: RestingOrderKind::parse(&row.kind)?,
+                    price: row.price,
+                    quantity: row.quantity,
+                    placed_at: row.placed_at,
+                })
+            })
+            .collect())
+    }
+
+    async fn set_resting_order_status(&self, order_id: &str, status: RestingOrderStatus) -> Result<()> {
+        sqlx::query!(
+            "UPDATE resting_orders SET status = $2, updated_at = NOW() WHERE order_id = $1",
+            order_id,
+            status.as_str(),
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn insert_entry_order(&self, order: &EntryOrder) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO entry_orders (
+                order_id, signal_id, market_id, position, price, quantity, gas_cost, status, placed_at, expires_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, 'open', $8, $9)
+            "#,
+            order.order_id,
+            order.signal_id,
+            order.market_id,
+            order.position.as_str(),
+            order.price,
+            order.quantity,
+            order.gas_cost,
+            order.placed_at,
+            order.expires_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_open_entry_orders(&self) -> Result<Vec<EntryOrder>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT order_id, signal_id, market_id, position, price, quantity, gas_cost, placed_at, expires_at
+            FROM entry_orders
+            WHERE status = 'open'
+            ORDER BY placed_at ASC
+            "#
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| {
+                Some(EntryOrder {
+                    order_id: row.order_id,
+                    signal_id: row.signal_id,
+                    market_id: row.market_id,
+                    position: Position::parse(&row.position)?,
+                    price: row.price,
+                    quantity: row.quantity,
+                    gas_cost: row.gas_cost,
+                    placed_at: row.placed_at,
+                    expires_at: row.expires_at,
+                })
+            })
+            .collect())
+    }
+
+    async fn set_entry_order_status(&self, order_id: &str, status: RestingOrderStatus) -> Result<()> {
+        sqlx::query!(
+            "UPDATE entry_orders SET status = $2, updated_at = NOW() WHERE order_id = $1",
+            order_id,
+            status.as_str(),
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn insert_parent_order(&self, order: &ParentOrder) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO parent_orders (
+                parent_id, signal_id, market_id, position, mode, target_notional, limit_price, slices,
+                children_sent, filled_quantity, filled_notional, status, next_slice_at, created_at, expires_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+            "#,
+            order.parent_id,
+            order.signal_id,
+            order.market_id,
+            order.position.as_str(),
+            order.mode.as_str(),
+            order.target_notional,
+            order.limit_price,
+            order.slices,
+            order.children_sent,
+            order.filled_quantity,
+            order.filled_notional,
+            order.status.as_str(),
+            order.next_slice_at,
+            order.created_at,
+            order.expires_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_parent_order(&self, order: &ParentOrder) -> Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE parent_orders SET
+                children_sent = $2,
+                filled_quantity = $3,
+                filled_notional = $4,
+                status = $5,
+                next_slice_at = $6,
+                updated_at = NOW()
+            WHERE parent_id = $1
+            "#,
+            order.parent_id,
+            order.children_sent,
+            order.filled_quantity,
+            order.filled_notional,
+            order.status.as_str(),
+            order.next_slice_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_open_parent_orders(&self) -> Result<Vec<ParentOrder>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                parent_id, signal_id, market_id, position, mode, target_notional, limit_price, slices,
+                children_sent, filled_quantity, filled_notional, next_slice_at, created_at, expires_at
+            FROM parent_orders
+            WHERE status = 'open'
+            ORDER BY created_at ASC
+            "#
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| {
+                Some(ParentOrder {
+                    parent_id: row.parent_id,
+                    signal_id: row.signal_id,
+                    market_id: row.market_id,
+                    position: Position::parse(&row.position)?,
+                    mode: SliceMode::parse(&row.mode)?,
+                    target_notional: row.target_notional,
+                    limit_price: row.limit_price,
+                    slices: row.slices,
+                    children_sent: row.children_sent,
+                    filled_quantity: row.filled_quantity,
+                    filled_notional: row.filled_notional,
+                    status: RestingOrderStatus::Open,
+                    next_slice_at: row.next_slice_at,
+                    created_at: row.created_at,
+                    expires_at: row.expires_at,
+                })
+            })
+            .collect())
+    }
+
+    async fn insert_child_order(&self, order: &ChildOrder) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO child_orders (parent_id, slice, trade_id, quantity, price, tx_hash, sent_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            order.parent_id,
+            order.slice,
+            order.trade_id,
+            order.quantity,
+            order.price,
+            order.tx_hash,
+            order.sent_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CashFlowRepo for PostgresStorage {
+    async fn insert_cash_flow(&self, flow: &CashFlow) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO cash_flows (
+                flow_id, kind, amount, source, tx_hash, note, occurred_at, venue
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+            flow.flow_id,
+            flow.kind.as_str(),
+            flow.amount.value(),
+            flow.source.as_str(),
+            flow.tx_hash,
+            flow.note,
+            flow.occurred_at,
+            flow.venue,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_cash_flows(&self, since: DateTime<Utc>) -> Result<Vec<CashFlow>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT flow_id, kind, amount, source, tx_hash, note, occurred_at, venue
+            FROM cash_flows
+            WHERE occurred_at >= $1
+            ORDER BY occurred_at ASC
+            "#,
+            since,
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| {
+                Some(CashFlow {
+                    flow_id: row.flow_id,
+                    kind: CashFlowKind::parse(&row.kind)?,
+                    amount: Usdc::new(row.amount),
+                    source: CashFlowSource::parse(&row.source)?,
+                    tx_hash: row.tx_hash,
+                    note: row.note,
+                    occurred_at: row.occurred_at,
+                    venue: row.venue,
+                })
+            })
+            .collect())
+    }
+
+    async fn cash_flow_exists(&self, tx_hash: &str) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            SELECT EXISTS(SELECT 1 FROM cash_flows WHERE tx_hash = $1) as "exists!"
+            "#,
+            tx_hash,
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(result.exists)
+    }
+}
+
+#[async_trait]
+impl PortfolioRepo for PostgresStorage {
+    async fn calculate_portfolio_balances(&self, starting_capital: Decimal) -> Result<PortfolioBalances> {
+        let result = sqlx::query!(
+            r#"
+            SELECT * FROM calculate_portfolio_state($1)
+            "#,
+            starting_capital,
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        let total_capital = result.total_capital.unwrap_or(starting_capital);
+
+        Ok(PortfolioBalances {
+            total_capital,
+            available_capital: result.available_capital.unwrap_or(total_capital),
+            invested_capital: result.invested_capital.unwrap_or(dec!(0.0)),
+            settlement_pending: result.settlement_pending.unwrap_or(dec!(0.0)),
+            unrealized_pnl: result.unrealized_pnl.unwrap_or(dec!(0.0)),
+            net_cash_flows: result.net_cash_flows.unwrap_or(dec!(0.0)),
+        })
+    }
+
+    async fn calculate_venue_balances(&self) -> Result<Vec<VenueBalances>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT * FROM calculate_venue_balances()
+            "#
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| {
+                Some(VenueBalances {
+                    venue: row.venue?,
+                    net_cash_flows: row.net_cash_flows.unwrap_or_default(),
+                    realized_pnl: row.realized_pnl.unwrap_or_default(),
+                    realized_pnl_today: row.realized_pnl_today.unwrap_or_default(),
+                    invested_capital: row.invested_capital.unwrap_or_default(),
+                    unrealized_pnl: row.unrealized_pnl.unwrap_or_default(),
+                    open_positions: row.open_positions.unwrap_or(0),
+                })
+            })
+            .collect())
+    }
+
+    async fn insert_portfolio_snapshot(&self, state: &PortfolioState) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO portfolio_state (
+                total_capital, available_capital, invested_capital, settlement_pending,
+                unrealized_pnl, realized_pnl_today, daily_drawdown,
+                max_drawdown, open_positions, trades_today
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+            state.total_capital,
+            state.available_capital,
+            state.invested_capital,
+            state.settlement_pending,
+            state.unrealized_pnl,
+            state.realized_pnl_today,
+            state.daily_drawdown,
+            state.max_drawdown,
+            state.open_positions,
+            state.trades_today,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn latest_portfolio_snapshot(&self) -> Result<Option<PortfolioState>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT * FROM v_portfolio_summary
+            LIMIT 1
+            "#
+        )
+        .fetch_optional(&self.db_pool)
+        .await?;
+
+        Ok(row.map(|p| PortfolioState {
+            total_capital: p.total_capital.unwrap_or_default(),
+            available_capital: p.available_capital.unwrap_or_default(),
+            invested_capital: p.invested_capital.unwrap_or_default(),
+            settlement_pending: p.settlement_pending.unwrap_or_default(),
+            unrealized_pnl: p.unrealized_pnl.unwrap_or_default(),
+            realized_pnl_today: p.realized_pnl_today.unwrap_or_default(),
+            daily_drawdown: p.daily_drawdown.unwrap_or_default(),
+            max_drawdown: Decimal::ZERO,
+            open_positions: p.open_positions.unwrap_or(0),
+            trades_today: p.trades_today.unwrap_or(0),
+            timestamp: p.timestamp.unwrap_or_else(Utc::now),
+        }))
+    }
+
+    async fn fetch_capital_history(&self, since: DateTime<Utc>) -> Result<Vec<CapitalPoint>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT timestamp as "timestamp!", total_capital
+            FROM portfolio_state
+            WHERE timestamp >= $1
+            ORDER BY timestamp ASC
+            "#,
+            since,
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .map(|row| CapitalPoint { timestamp: row.timestamp, total_capital: row.total_capital })
+            .collect())
+    }
+
+    async fn count_active_circuit_breakers(&self) -> Result<i64> {
+        let result = sqlx::query!(
+            r#"
+            SELECT COUNT(*) as count
+            FROM circuit_breakers
+            WHERE status = 'active'
+            "#
+        )
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(result.count.unwrap_or(0))
+    }
+
+    async fn insert_circuit_breaker(&self, reason: &str, metadata: serde_json::Value) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO circuit_breakers (reason, metadata)
+            VALUES ($1, $2)
+            "#,
+            reason,
+            envelope(MetadataKind::CircuitBreaker, &metadata),
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SeasonRepo for PostgresStorage {
+    async fn fetch_team_ratings(&self, sport: Sport) -> Result<Vec<TeamRating>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT team, conference, division, rating, latitude, longitude, elevation_m, indoor
+            FROM team_ratings
+            WHERE sport = $1
+            "#,
+            sport.as_str()
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .map(|row| TeamRating {
+                sport,
+                team: row.team,
+                conference: row.conference,
+                division: row.division,
+                rating: row.rating,
+                latitude: row.latitude,
+                longitude: row.longitude,
+                elevation_m: row.elevation_m,
+                indoor: row.indoor,
+            })
+            .collect())
+    }
+
+    async fn fetch_season_games(&self, sport: Sport) -> Result<Vec<Game>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT game_id, season, home_team, away_team, start_time, home_score, away_score
+            FROM games
+            WHERE sport = $1
+                AND season = (SELECT MAX(season) FROM games WHERE sport = $1)
+            ORDER BY start_time
+            "#,
+            sport.as_str()
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .map(|row| Game {
+                game_id: row.game_id,
+                sport,
+                season: row.season,
+                home_team: row.home_team,
+                away_team: row.away_team,
+                start_time: row.start_time,
+                home_score: row.home_score,
+                away_score: row.away_score,
+            })
+            .collect())
+    }
+
+    async fn fetch_game_history(&self, sport: Sport) -> Result<Vec<Game>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT game_id, season, home_team, away_team, start_time, home_score, away_score
+            FROM games
+            WHERE sport = $1
+                AND home_score IS NOT NULL
+                AND away_score IS NOT NULL
+            ORDER BY start_time
+            "#,
+            sport.as_str()
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .map(|row| Game {
+                game_id: row.game_id,
+                sport,
+                season: row.season,
+                home_team: row.home_team,
+                away_team: row.away_team,
+                start_time: row.start_time,
+                home_score: row.home_score,
+                away_score: row.away_score,
+            })
+            .collect())
+    }
+
+    async fn upsert_home_advantage(&self, estimate: &HomeAdvantageEstimate) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO home_advantage_estimates (
+                sport, team, league, games, raw, structural, estimate, estimated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (sport, team) DO UPDATE SET
+                league = EXCLUDED.league,
+                games = EXCLUDED.games,
+                raw = EXCLUDED.raw,
+                structural = EXCLUDED.structural,
+                estimate = EXCLUDED.estimate,
+                estimated_at = EXCLUDED.estimated_at
+            "#,
+            estimate.sport.as_str(),
+            estimate.team,
+            estimate.league,
+            estimate.games,
+            estimate.raw,
+            estimate.structural,
+            estimate.estimate,
+            estimate.estimated_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_home_advantage(&self, sport: Option<Sport>) -> Result<Vec<HomeAdvantageEstimate>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT sport, team, league, games, raw, structural, estimate, estimated_at
+            FROM home_advantage_estimates
+            WHERE $1::text IS NULL OR sport = $1
+            ORDER BY sport, team
+            "#,
+            sport.map(|s| s.as_str().to_string()),
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .filter_map(|row| {
+                Some(HomeAdvantageEstimate {
+                    sport: Sport::parse(&row.sport)?,
+                    team: row.team,
+                    league: row.league,
+                    games: row.games,
+                    raw: row.raw,
+                    structural: row.structural,
+                    estimate: row.estimate,
+                    estimated_at: row.estimated_at,
+                })
+            })
+            .collect())
+    }
+
+    async fn fetch_injury_adjustments(&self, sport: Sport) -> Result<Vec<InjuryAdjustment>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT team, player, rating_delta, out_until
+            FROM injury_adjustments
+            WHERE sport = $1
+                AND (out_until IS NULL OR out_until > NOW())
+            "#,
+            sport.as_str()
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .map(|row| InjuryAdjustment {
+                sport,
+                team: row.team,
+                player: row.player,
+                rating_delta: row.rating_delta,
+                out_until: row.out_until,
+            })
+            .collect())
+    }
+
+    async fn upsert_official_assignment(&self, assignment: &OfficialAssignment) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO official_assignments (game_id, role, official)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (game_id, role) DO UPDATE SET
+                official = EXCLUDED.official,
+                updated_at = NOW()
+            "#,
+            assignment.game_id,
+            assignment.role,
+            assignment.official,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_official_tendencies(&self, game_id: &str) -> Result<Vec<OfficialTendency>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                a.official,
+                a.role,
+                COALESCE(t.games, 0) AS "games!",
+                COALESCE(t.total_delta, 0) AS "total_delta!"
+            FROM official_assignments a
+            JOIN games g ON g.game_id = a.game_id
+            LEFT JOIN v_official_tendencies t
+                ON t.sport = g.sport AND t.official = a.official AND t.role = a.role
+            WHERE a.game_id = $1
+            "#,
+            game_id
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .map(|row| OfficialTendency {
+                official: row.official,
+                role: row.role,
+                games: row.games,
+                total_delta: row.total_delta,
+            })
+            .collect())
+    }
+
+    async fn upsert_probable_pitcher(&self, pitcher: &ProbablePitcher) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO probable_pitchers (game_id, team, pitcher, era, innings_pitched)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (game_id, team) DO UPDATE SET
+                pitcher = EXCLUDED.pitcher,
+                era = EXCLUDED.era,
+                innings_pitched = EXCLUDED.innings_pitched,
+                updated_at = NOW()
+            "#,
+            pitcher.game_id,
+            pitcher.team,
+            pitcher.pitcher,
+            pitcher.era,
+            pitcher.innings_pitched,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_probable_pitchers(&self, game_id: &str) -> Result<Vec<ProbablePitcher>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT game_id, team, pitcher, era, innings_pitched
+            FROM probable_pitchers
+            WHERE game_id = $1
+            "#,
+            game_id
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .map(|row| ProbablePitcher {
+                game_id: row.game_id,
+                team: row.team,
+                pitcher: row.pitcher,
+                era: row.era,
+                innings_pitched: row.innings_pitched,
+            })
+            .collect())
+    }
+
+    async fn upsert_bullpen_usage(&self, usage: &BullpenUsage) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO bullpen_usage (team, game_time, innings)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (team, game_time) DO UPDATE SET innings = EXCLUDED.innings
+            "#,
+            usage.team,
+            usage.game_time,
+            usage.innings,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_bullpen_usage(&self, since: DateTime<Utc>) -> Result<Vec<BullpenUsage>> {
+        let rows = sqlx::query!(
+            "SELECT team, game_time, innings FROM bullpen_usage WHERE game_time >= $1",
+            since
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .map(|row| BullpenUsage {
+                team: row.team,
+                game_time: row.game_time,
+                innings: row.innings,
+            })
+            .collect())
+    }
+}
+
+/// Token ids are only usable as a complete set
+fn market_tokens(
+    condition_id: Option<String>,
+    question_id: Option<String>,
+    yes_token_id: Option<String>,
+    no_token_id: Option<String>,
+) -> Option<MarketTokens> {
+    Some(MarketTokens {
+        condition_id: condition_id?,
+        question_id: question_id?,
+        yes_token_id: yes_token_id?,
+        no_token_id: no_token_id?,
+    })
+}
+
+// Helper struct for database queries
+#[derive(Debug)]
+struct BookmakerOddsRow {
+    market_id: String,
+    bookmaker: String,
+    yes_odds: Decimal,
+    no_odds: Decimal,
+    yes_implied_prob: Decimal,
+    no_implied_prob: Decimal,
+    timestamp: DateTime<Utc>,
+    source_timestamp: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<BookmakerOddsRow> for BookmakerOdds {
+    type Error = NumericError;
+
+    fn try_from(row: BookmakerOddsRow) -> Result<Self, Self::Error> {
+        Ok(BookmakerOdds {
+            bookmaker: Bookmaker::parse(&row.bookmaker).unwrap_or(Bookmaker::Pinnacle),
+            market_id: row.market_id,
+            yes_odds: row.yes_odds,
+            no_odds: row.no_odds,
+            yes_implied_prob: Probability::new(row.yes_implied_prob)?,
+            no_implied_prob: Probability::new(row.no_implied_prob)?,
+            timestamp: row.timestamp,
+            source_timestamp: row.source_timestamp,
+        })
+    }
+}
+
+#[async_trait]
+impl FeatureRepo for PostgresStorage {
+    async fn upsert_game_feature(&self, feature: &GameFeature) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO game_features (game_id, team, name, value, computed_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (game_id, team, name) DO UPDATE SET
+                value = EXCLUDED.value,
+                computed_at = EXCLUDED.computed_at
+            "#,
+            feature.game_id,
+            feature.team,
+            feature.name,
+            feature.value,
+            feature.computed_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DecisionRepo for PostgresStorage {
+    async fn insert_decision(&self, decision: &Decision) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO decision_log (decision_id, kind, subject, summary, detail, decided_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            decision.decision_id,
+            decision.kind,
+            decision.subject,
+            decision.summary,
+            decision.detail,
+            decision.decided_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_decisions(&self, since: DateTime<Utc>) -> Result<Vec<Decision>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT decision_id, kind, subject, summary, detail, decided_at
+            FROM decision_log
+            WHERE decided_at >= $1
+            ORDER BY decided_at DESC
+            "#,
+            since,
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .map(|row| Decision {
+                decision_id: row.decision_id,
+                kind: row.kind,
+                subject: row.subject,
+                summary: row.summary,
+                detail: row.detail,
+                decided_at: row.decided_at,
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl CredentialRepo for PostgresStorage {
+    async fn insert_credential(&self, credential: &SealedCredential) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO credentials (provider, name, version, nonce, ciphertext, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            credential.provider,
+            credential.name,
+            credential.version,
+            credential.nonce,
+            credential.ciphertext,
+            credential.created_at,
+        )
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_credentials(&self) -> Result<Vec<SealedCredential>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT DISTINCT ON (provider, name) provider, name, version, nonce, ciphertext, created_at
+            FROM credentials
+            ORDER BY provider, name, version DESC
+            "#,
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows.into_iter()
+            .map(|row| SealedCredential {
+                provider: row.provider,
+                name: row.name,
+                version: row.version,
+                nonce: row.nonce,
+                ciphertext: row.ciphertext,
+                created_at: row.created_at,
+            })
+            .collect())
+    }
+}
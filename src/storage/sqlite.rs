@@ -0,0 +1,3188 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use sqlx::sqlite::{SqlitePool, SqlitePoolOptions, SqliteRow};
+use sqlx::{Executor, Row};
+use std::collections::HashMap;
+use std::str::FromStr;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::types::{
+    Bookmaker, BookmakerOdds, CapitalPoint, CashFlow, CashFlowKind, CashFlowSource, Decision, EntryOrder, FeedLatency, Game, GameFeature,
+    HomeAdvantageEstimate, InjuryAdjustment, Market, MarketStatus, MarketTokens, OfficialAssignment, OfficialTendency, PortfolioState, Position,
+    PredictionOutcome, Price, Probability, Quote, RestingOrder, RestingOrderKind, RestingOrderStatus, ShadowedStrategy, Signal, SignalType, SlippageEvent, Sport, Strategy, StrategyThrottle, TeamRating, Trade, TradeMark, TradeOrigin, TradeStatus, ConvergenceSample, SettlementReview, StrategyWeight, SignalExecution, EdgeDecay, TradeSizing, FillSide, OnchainFill, MarketMicrostructure, BookSnapshot, LargeTrade, PricePoint, PreEventHistory, Resolution, SealedCredential, Usdc,
+    MarketListing, CoveredMarket, TradeOutcome, TradeSettlement, DeadLetter,
+    ExecutionFailureStatus, FailedExecution, ParentOrder, ChildOrder, ProbablePitcher, BullpenUsage, ExecutionCost, SliceMode,
+};
+use super::metadata::{envelope, upgrade, MetadataKind};
+use super::{
+    CashFlowRepo, CredentialRepo, DecisionRepo, FeatureRepo, MarketRepo, PortfolioBalances, PortfolioRepo, SeasonRepo, SignalRepo, TradeRepo,
+    VenueBalances,
+};
+
+const SCHEMA: &str = include_str!("../../sql/sqlite/schema.sql");
+
+/// Lightweight storage for paper trading and backtests
+///
+/// Uses runtime-checked queries since the compile-time `query!` macros are
+/// bound to the Postgres schema.
+pub struct SqliteStorage {
+    pool: SqlitePool,
+}
+
+impl SqliteStorage {
+    /// Open (or create) the database and apply the schema
+    pub async fn connect(url: &str, max_connections: u32) -> Result<Self> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(max_connections)
+            .connect(url)
+            .await?;
+
+        pool.execute(SCHEMA).await?;
+
+        Ok(Self { pool })
+    }
+
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    fn start_of_today() -> DateTime<Utc> {
+        Utc::now()
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is a valid time")
+            .and_utc()
+    }
+}
+
+fn decimal(row: &SqliteRow, column: &str) -> Result<Decimal> {
+    let value: String = row.try_get(column)?;
+    Ok(Decimal::from_str(&value)?)
+}
+
+fn opt_decimal(row: &SqliteRow, column: &str) -> Result<Option<Decimal>> {
+    let value: Option<String> = row.try_get(column)?;
+    Ok(value.map(|v| Decimal::from_str(&v)).transpose()?)
+}
+
+fn price(row: &SqliteRow, column: &str) -> Result<Price> {
+    Ok(Price::new(decimal(row, column)?)?)
+}
+
+fn probability(row: &SqliteRow, column: &str) -> Result<Probability> {
+    Ok(Probability::new(decimal(row, column)?)?)
+}
+
+fn uuid(row: &SqliteRow, column: &str) -> Result<Uuid> {
+    let value: String = row.try_get(column)?;
+    Ok(Uuid::parse_str(&value)?)
+}
+
+fn market_from_row(row: &SqliteRow) -> Option<Market> {
+    let sport: String = row.try_get("sport").ok()?;
+    let status: String = row.try_get("status").ok()?;
+    let market_type: String = row.try_get("market_type").ok()?;
+    let (yes_quote, no_quote) = quotes_from_row(row).ok()?;
+
+    Some(Market {
+        market_id: row.try_get("market_id").ok()?,
+        sport: Sport::parse(&sport)?,
+        event_name: row.try_get("event_name").ok()?,
+        event_time: row.try_get("event_time").ok()?,
+        market_type: serde_json::from_str(&market_type).ok()?,
+        description: row.try_get("description").ok()?,
+        resolution_source: row.try_get("resolution_source").ok()?,
+        min_liquidity: opt_decimal(row, "min_liquidity").ok()?.unwrap_or(Decimal::ZERO),
+        current_liquidity: decimal(row, "current_liquidity").ok()?,
+        yes_price: price(row, "yes_price").ok()?,
+        no_price: price(row, "no_price").ok()?,
+        yes_quote,
+        no_quote,
+        status: MarketStatus::parse(&status)?,
+        tokens: tokens_from_row(row),
+        created_at: row.try_get("created_at").ok()?,
+        updated_at: row.try_get("updated_at").ok()?,
+    })
+}
+
+/// (YES, NO) quotes; an outcome without a stored book is quoted at its last price
+fn quotes_from_row(row: &SqliteRow) -> Result<(Quote, Quote)> {
+    Ok((
+        Quote::from_book(opt_decimal(row, "yes_bid")?, opt_decimal(row, "yes_ask")?, price(row, "yes_price")?)
+            .with_depth(opt_decimal(row, "yes_bid_size")?, opt_decimal(row, "yes_ask_size")?),
+        Quote::from_book(opt_decimal(row, "no_bid")?, opt_decimal(row, "no_ask")?, price(row, "no_price")?)
+            .with_depth(opt_decimal(row, "no_bid_size")?, opt_decimal(row, "no_ask_size")?),
+    ))
+}
+
+/// A failure record; `None` if its strategy or status no longer parses
+fn failed_execution_from_row(row: &SqliteRow) -> Result<Option<FailedExecution>> {
+    let strategy: String = row.try_get("strategy")?;
+    let status: String = row.try_get("status")?;
+    let (Some(strategy), Some(status)) = (Strategy::parse(&strategy), ExecutionFailureStatus::parse(&status)) else {
+        return Ok(None);
+    };
+
+    Ok(Some(FailedExecution {
+        signal_id: uuid(row, "signal_id")?,
+        market_id: row.try_get("market_id")?,
+        strategy,
+        attempts: row.try_get("attempts")?,
+        last_error: row.try_get("last_error")?,
+        status,
+        next_attempt_at: row.try_get("next_attempt_at")?,
+        first_failed_at: row.try_get("first_failed_at")?,
+        updated_at: row.try_get("updated_at")?,
+    }))
+}
+
+/// Token ids are only usable as a complete set
+fn tokens_from_row(row: &SqliteRow) -> Option<MarketTokens> {
+    Some(MarketTokens {
+        condition_id: row.try_get::<Option<String>, _>("condition_id").ok()??,
+        question_id: row.try_get::<Option<String>, _>("question_id").ok()??,
+        yes_token_id: row.try_get::<Option<String>, _>("yes_token_id").ok()??,
+        no_token_id: row.try_get::<Option<String>, _>("no_token_id").ok()??,
+    })
+}
+
+fn signal_from_row(row: &SqliteRow) -> Option<Signal> {
+    let strategy: String = row.try_get("strategy").ok()?;
+    let signal_type: String = row.try_get("signal_type").ok()?;
+    let metadata: Option<String> = row.try_get("metadata").ok()?;
+
+    Some(Signal {
+        signal_id: uuid(row, "signal_id").ok()?,
+        market_id: row.try_get("market_id").ok()?,
+        strategy: Strategy::parse(&strategy)?,
+        signal_type: serde_json::from_str(&signal_type).ok()?,
+        confidence: decimal(row, "confidence").ok()?,
+        edge_size: decimal(row, "edge_size").ok()?,
+        recommended_size: decimal(row, "recommended_size").ok()?,
+        current_price: price(row, "current_price").ok()?,
+        executable_size: opt_decimal(row, "executable_size").ok()?,
+        // Signals stored before quoted edges were recorded
+        quoted_edge: match opt_decimal(row, "quoted_edge").ok()? {
+            Some(edge) => edge,
+            None => decimal(row, "edge_size").ok()?,
+        },
+        fair_value: probability(row, "fair_value").ok()?,
+        generated_at: row.try_get("generated_at").ok()?,
+        metadata: upgrade(
+            MetadataKind::Signal,
+            metadata
+                .and_then(|m| serde_json::from_str(&m).ok())
+                .unwrap_or(serde_json::Value::Null),
+        ),
+    })
+}
+
+const TRADE_COLUMNS: &str = r#"
+    trade_id, market_id, strategy, position, quantity,
+    entry_price, exit_price, entry_time, exit_time, gas_cost, fees,
+    slippage, pnl, pnl_percent, max_adverse_excursion, max_favorable_excursion,
+    status, tx_hash_entry, tx_hash_exit, venue, origin
+"#;
+
+fn trade_from_row(row: &SqliteRow) -> Option<Trade> {
+    let strategy: String = row.try_get("strategy").ok()?;
+    let position: String = row.try_get("position").ok()?;
+    let status: String = row.try_get("status").ok()?;
+    let origin: String = row.try_get("origin").ok()?;
+
+    Some(Trade {
+        trade_id: uuid(row, "trade_id").ok()?,
+        market_id: row.try_get("market_id").ok()?,
+        strategy: Strategy::parse(&strategy)?,
+        position: Position::parse(&position)?,
+        quantity: decimal(row, "quantity").ok()?,
+        entry_price: decimal(row, "entry_price").ok()?,
+        exit_price: opt_decimal(row, "exit_price").ok()?,
+        entry_time: row.try_get("entry_time").ok()?,
+        exit_time: row.try_get("exit_time").ok()?,
+        gas_cost: opt_decimal(row, "gas_cost").ok()?,
+        fees: opt_decimal(row, "fees").ok()?.map(Usdc::new),
+        slippage: opt_decimal(row, "slippage").ok()?,
+        pnl: opt_decimal(row, "pnl").ok()?.map(Usdc::new),
+        pnl_percent: opt_decimal(row, "pnl_percent").ok()?,
+        max_adverse_excursion: opt_decimal(row, "max_adverse_excursion").ok()?,
+        max_favorable_excursion: opt_decimal(row, "max_favorable_excursion").ok()?,
+        status: TradeStatus::parse(&status)?,
+        tx_hash_entry: row.try_get("tx_hash_entry").ok()?,
+        tx_hash_exit: row.try_get("tx_hash_exit").ok()?,
+        venue: row.try_get("venue").ok()?,
+        origin: TradeOrigin::parse(&origin)?,
+    })
+}
+
+fn onchain_fill_from_row(row: &SqliteRow) -> Result<OnchainFill> {
+    let side: String = row.try_get("side")?;
+    Ok(OnchainFill {
+        tx_hash: row.try_get("tx_hash")?,
+        log_index: row.try_get("log_index")?,
+        order_hash: row.try_get("order_hash")?,
+        token_id: row.try_get("token_id")?,
+        side: FillSide::parse(&side).ok_or_else(|| anyhow!("Unknown fill side {}", side))?,
+        shares: decimal(row, "shares")?,
+        price: decimal(row, "price")?,
+        fee: Usdc::new(decimal(row, "fee")?),
+        block_number: row.try_get("block_number")?,
+        observed_at: row.try_get("observed_at")?,
+    })
+}
+
+fn cash_flow_from_row(row: &SqliteRow) -> Option<CashFlow> {
+    let kind: String = row.try_get("kind").ok()?;
+    let source: String = row.try_get("source").ok()?;
+
+    Some(CashFlow {
+        flow_id: uuid(row, "flow_id").ok()?,
+        kind: CashFlowKind::parse(&kind)?,
+        amount: Usdc::new(decimal(row, "amount").ok()?),
+        source: CashFlowSource::parse(&source)?,
+        tx_hash: row.try_get("tx_hash").ok()?,
+        note: row.try_get("note").ok()?,
+        occurred_at: row.try_get("occurred_at").ok()?,
+        venue: row.try_get("venue").ok()?,
+    })
+}
+
+#[async_trait]
+impl MarketRepo for SqliteStorage {
+    async fn fetch_active_markets(&self, min_liquidity: Decimal, limit: i64) -> Result<Vec<Market>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                market_id, sport, event_name, event_time, market_type,
+                description, resolution_source, min_liquidity, current_liquidity,
+                yes_price, no_price, yes_bid, yes_ask, no_bid, no_ask,
+                yes_bid_size, yes_ask_size, no_bid_size, no_ask_size, status,
+                condition_id, question_id, yes_token_id, no_token_id,
+                created_at, updated_at
+            FROM markets
+            WHERE status = 'active'
+                AND event_time > ?1
+            ORDER BY event_time ASC
+            "#,
+        )
+        .bind(Utc::now())
+        .fetch_all(&self.pool)
+        .await?;
+
+        // Rows that no longer map are dead-lettered rather than dropped
+        let now = Utc::now();
+        let mut markets = Vec::new();
+        for row in &rows {
+            match market_from_row(row) {
+                Some(market) => markets.push(market),
+                None => {
+                    let market_id: String = row.try_get("market_id")?;
+                    let raw = serde_json::json!({
+                        "sport": row.try_get::<Option<String>, _>("sport").ok().flatten(),
+                        "market_type": row.try_get::<Option<String>, _>("market_type").ok().flatten(),
+                        "yes_price": row.try_get::<Option<String>, _>("yes_price").ok().flatten(),
+                        "no_price": row.try_get::<Option<String>, _>("no_price").ok().flatten(),
+                    });
+                    if let Err(e) = self.record_dead_letter(DeadLetter::MARKET_ROW, &market_id, "unmappable market row", &raw, now).await {
+                        warn!("Failed to record dead letter for market {}: {}", market_id, e);
+                    }
+                }
+            }
+        }
+
+        // Liquidity is stored as TEXT, so the threshold is applied after parsing
+        Ok(markets.into_iter()
+            .filter(|market| market.current_liquidity >= min_liquidity)
+            .take(limit.max(0) as usize)
+            .collect())
+    }
+
+    async fn fetch_market(&self, market_id: &str) -> Result<Option<Market>> {
+        let row = sqlx::query(
+            r#"
+            SELECT
+                market_id, sport, event_name, event_time, market_type,
+                description, resolution_source, min_liquidity, current_liquidity,
+                yes_price, no_price, yes_bid, yes_ask, no_bid, no_ask,
+                yes_bid_size, yes_ask_size, no_bid_size, no_ask_size, status,
+                condition_id, question_id, yes_token_id, no_token_id,
+                created_at, updated_at
+            FROM markets
+            WHERE market_id = ?1
+            "#,
+        )
+        .bind(market_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().and_then(market_from_row))
+    }
+
+    async fn fetch_market_quotes(&self, market_id: &str) -> Result<(Quote, Quote)> {
+        let row = sqlx::query(
+            r#"
+            SELECT
+                yes_price, no_price, yes_bid, yes_ask, no_bid, no_ask,
+                yes_bid_size, yes_ask_size, no_bid_size, no_ask_size
+            FROM markets
+            WHERE market_id = ?1
+            "#,
+        )
+        .bind(market_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        quotes_from_row(&row)
+    }
+
+    async fn fetch_bookmaker_odds(&self, market_id: &str) -> Result<Vec<BookmakerOdds>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT market_id, bookmaker, yes_odds, no_odds,
+                   yes_implied_prob, no_implied_prob, timestamp, source_timestamp
+            FROM bookmaker_odds
+            WHERE market_id = ?1
+                AND timestamp > ?2
+            ORDER BY bookmaker, timestamp DESC
+            "#,
+        )
+        .bind(market_id)
+        .bind(Utc::now() - chrono::Duration::hours(1))
+        .fetch_all(&self.pool)
+        .await?;
+
+        // Keep the latest row per bookmaker (rows are ordered newest first)
+        let mut odds: Vec<BookmakerOdds> = Vec::new();
+        for row in &rows {
+            let bookmaker: String = row.try_get("bookmaker")?;
+            let bookmaker = Bookmaker::parse(&bookmaker).unwrap_or(Bookmaker::Pinnacle);
+            if odds.iter().any(|o| o.bookmaker == bookmaker) {
+                continue;
+            }
+            odds.push(BookmakerOdds {
+                bookmaker,
+                market_id: row.try_get("market_id")?,
+                yes_odds: decimal(row, "yes_odds")?,
+                no_odds: decimal(row, "no_odds")?,
+                yes_implied_prob: probability(row, "yes_implied_prob")?,
+                no_implied_prob: probability(row, "no_implied_prob")?,
+                timestamp: row.try_get("timestamp")?,
+                source_timestamp: row.try_get("source_timestamp")?,
+            });
+        }
+
+        Ok(odds)
+    }
+
+    async fn insert_bookmaker_odds(&self, odds: &BookmakerOdds) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO bookmaker_odds (
+                market_id, bookmaker, odds_type, yes_odds, no_odds,
+                yes_implied_prob, no_implied_prob, timestamp, source_timestamp
+            ) VALUES (?1, ?2, 'moneyline', ?3, ?4, ?5, ?6, ?7, ?8)
+            "#,
+        )
+        .bind(&odds.market_id)
+        .bind(odds.bookmaker.as_str())
+        .bind(odds.yes_odds.to_string())
+        .bind(odds.no_odds.to_string())
+        .bind(odds.yes_implied_prob.value().to_string())
+        .bind(odds.no_implied_prob.value().to_string())
+        .bind(odds.timestamp)
+        .bind(odds.source_timestamp)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn insert_feed_latency(&self, latency: &FeedLatency) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO odds_feed_latency (provider, lines, median_ms, max_ms, measured_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+        )
+        .bind(&latency.provider)
+        .bind(latency.lines)
+        .bind(latency.median_ms)
+        .bind(latency.max_ms)
+        .bind(latency.measured_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_feed_latency(&self, since: DateTime<Utc>) -> Result<Vec<FeedLatency>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT provider, lines, median_ms, max_ms, measured_at
+            FROM odds_feed_latency
+            WHERE measured_at >= ?1
+            ORDER BY measured_at
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(FeedLatency {
+                    provider: row.try_get("provider")?,
+                    lines: row.try_get("lines")?,
+                    median_ms: row.try_get("median_ms")?,
+                    max_ms: row.try_get("max_ms")?,
+                    measured_at: row.try_get("measured_at")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn upsert_market(&self, market: &Market) -> Result<()> {
+        let tokens = market.tokens.as_ref();
+
+        sqlx::query(
+            r#"
+            INSERT INTO markets (
+                market_id, sport, event_name, event_time, market_type,
+                description, resolution_source, min_liquidity, current_liquidity,
+                yes_price, no_price, yes_bid, yes_ask, no_bid, no_ask,
+                yes_bid_size, yes_ask_size, no_bid_size, no_ask_size, status, updated_at,
+                condition_id, question_id, yes_token_id, no_token_id
+            ) VALUES (
+                ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14,
+                ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25
+            )
+            ON CONFLICT (market_id) DO UPDATE SET
+                event_time = excluded.event_time,
+                current_liquidity = excluded.current_liquidity,
+                yes_price = excluded.yes_price,
+                no_price = excluded.no_price,
+                yes_bid = excluded.yes_bid,
+                yes_ask = excluded.yes_ask,
+                no_bid = excluded.no_bid,
+                no_ask = excluded.no_ask,
+                yes_bid_size = excluded.yes_bid_size,
+                yes_ask_size = excluded.yes_ask_size,
+                no_bid_size = excluded.no_bid_size,
+                no_ask_size = excluded.no_ask_size,
+                status = excluded.status,
+                updated_at = excluded.updated_at,
+                condition_id = COALESCE(excluded.condition_id, markets.condition_id),
+                question_id = COALESCE(excluded.question_id, markets.question_id),
+                yes_token_id = COALESCE(excluded.yes_token_id, markets.yes_token_id),
+                no_token_id = COALESCE(excluded.no_token_id, markets.no_token_id)
+            "#,
+        )
+        .bind(&market.market_id)
+        .bind(market.sport.as_str())
+        .bind(&market.event_name)
+        .bind(market.event_time)
+        .bind(serde_json::to_string(&market.market_type)?)
+        .bind(&market.description)
+        .bind(&market.resolution_source)
+        .bind(market.min_liquidity.to_string())
+        .bind(market.current_liquidity.to_string())
+        .bind(market.yes_price.to_string())
+        .bind(market.no_price.to_string())
+        .bind(market.yes_quote.bid.to_string())
+        .bind(market.yes_quote.ask.to_string())
+        .bind(market.no_quote.bid.to_string())
+        .bind(market.no_quote.ask.to_string())
+        .bind(market.yes_quote.bid_size.map(|s| s.to_string()))
+        .bind(market.yes_quote.ask_size.map(|s| s.to_string()))
+        .bind(market.no_quote.bid_size.map(|s| s.to_string()))
+        .bind(market.no_quote.ask_size.map(|s| s.to_string()))
+        .bind(market.status.as_str())
+        .bind(Utc::now())
+        .bind(tokens.map(|t| t.condition_id.as_str()))
+        .bind(tokens.map(|t| t.question_id.as_str()))
+        .bind(tokens.map(|t| t.yes_token_id.as_str()))
+        .bind(tokens.map(|t| t.no_token_id.as_str()))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_market_quotes(&self, market_id: &str, yes_quote: &Quote, no_quote: &Quote) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE markets
+            SET yes_bid = ?2, yes_ask = ?3, no_bid = ?4, no_ask = ?5,
+                yes_bid_size = ?6, yes_ask_size = ?7, no_bid_size = ?8, no_ask_size = ?9,
+                updated_at = ?10
+            WHERE market_id = ?1
+            "#,
+        )
+        .bind(market_id)
+        .bind(yes_quote.bid.to_string())
+        .bind(yes_quote.ask.to_string())
+        .bind(no_quote.bid.to_string())
+        .bind(no_quote.ask.to_string())
+        .bind(yes_quote.bid_size.map(|s| s.to_string()))
+        .bind(yes_quote.ask_size.map(|s| s.to_string()))
+        .bind(no_quote.bid_size.map(|s| s.to_string()))
+        .bind(no_quote.ask_size.map(|s| s.to_string()))
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_market_tokens(&self, market_id: &str) -> Result<Option<MarketTokens>> {
+        let row = sqlx::query(
+            r#"
+            SELECT condition_id, question_id, yes_token_id, no_token_id
+            FROM markets
+            WHERE market_id = ?1
+            "#,
+        )
+        .bind(market_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().and_then(tokens_from_row))
+    }
+
+    async fn fetch_market_status(&self, market_id: &str) -> Result<Option<MarketStatus>> {
+        let status: Option<String> = sqlx::query_scalar("SELECT status FROM markets WHERE market_id = ?1")
+            .bind(market_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(status.as_deref().and_then(MarketStatus::parse))
+    }
+
+    async fn insert_microstructure(&self, features: &MarketMicrostructure) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO market_microstructure (
+                market_id, bid_depth, ask_depth, imbalance, bid_depth_change, ask_depth_change,
+                taker_buy_volume, taker_sell_volume, observed_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            "#,
+        )
+        .bind(&features.market_id)
+        .bind(features.bid_depth.to_string())
+        .bind(features.ask_depth.to_string())
+        .bind(features.imbalance.to_string())
+        .bind(features.bid_depth_change.map(|d| d.to_string()))
+        .bind(features.ask_depth_change.map(|d| d.to_string()))
+        .bind(features.taker_buy_volume.to_string())
+        .bind(features.taker_sell_volume.to_string())
+        .bind(features.observed_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn latest_microstructure(&self, market_id: &str) -> Result<Option<MarketMicrostructure>> {
+        let row = sqlx::query(
+            r#"
+            SELECT market_id, bid_depth, ask_depth, imbalance, bid_depth_change, ask_depth_change,
+                   taker_buy_volume, taker_sell_volume, observed_at
+            FROM market_microstructure
+            WHERE market_id = ?1
+            ORDER BY observed_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(market_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|row| {
+            Ok(MarketMicrostructure {
+                market_id: row.try_get("market_id")?,
+                bid_depth: decimal(&row, "bid_depth")?,
+                ask_depth: decimal(&row, "ask_depth")?,
+                imbalance: decimal(&row, "imbalance")?,
+                bid_depth_change: opt_decimal(&row, "bid_depth_change")?,
+                ask_depth_change: opt_decimal(&row, "ask_depth_change")?,
+                taker_buy_volume: decimal(&row, "taker_buy_volume")?,
+                taker_sell_volume: decimal(&row, "taker_sell_volume")?,
+                observed_at: row.try_get("observed_at")?,
+            })
+        })
+        .transpose()
+    }
+
+    async fn upsert_book_snapshot(&self, book: &BookSnapshot) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO order_book_snapshots (market_id, bids, asks, captured_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT (market_id) DO UPDATE SET
+                bids = excluded.bids,
+                asks = excluded.asks,
+                captured_at = excluded.captured_at
+            "#,
+        )
+        .bind(&book.market_id)
+        .bind(serde_json::to_string(&book.bids)?)
+        .bind(serde_json::to_string(&book.asks)?)
+        .bind(book.captured_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_book_snapshot(&self, market_id: &str) -> Result<Option<BookSnapshot>> {
+        let row = sqlx::query(
+            r#"
+            SELECT market_id, bids, asks, captured_at
+            FROM order_book_snapshots
+            WHERE market_id = ?1
+            "#,
+        )
+        .bind(market_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|row| {
+            let bids: String = row.try_get("bids")?;
+            let asks: String = row.try_get("asks")?;
+            Ok(BookSnapshot {
+                market_id: row.try_get("market_id")?,
+                bids: serde_json::from_str(&bids)?,
+                asks: serde_json::from_str(&asks)?,
+                captured_at: row.try_get("captured_at")?,
+            })
+        })
+        .transpose()
+    }
+
+    async fn upsert_market_listing(&self, listing: &MarketListing) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO market_listings (sport, listed, ingested, polled_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT (sport) DO UPDATE SET
+                listed = excluded.listed,
+                ingested = excluded.ingested,
+                polled_at = excluded.polled_at
+            "#,
+        )
+        .bind(listing.sport.as_str())
+        .bind(listing.listed)
+        .bind(listing.ingested)
+        .bind(listing.polled_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_market_listings(&self) -> Result<Vec<MarketListing>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT sport, listed, ingested, polled_at
+            FROM market_listings
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut listings = Vec::new();
+        for row in rows {
+            let sport: String = row.try_get("sport")?;
+            let Some(sport) = Sport::parse(&sport) else {
+                continue;
+            };
+            listings.push(MarketListing {
+                sport,
+                listed: row.try_get("listed")?,
+                ingested: row.try_get("ingested")?,
+                polled_at: row.try_get("polled_at")?,
+            });
+        }
+        Ok(listings)
+    }
+
+    async fn record_market_evaluations(&self, strategy: &str, market_ids: &[String], evaluated_at: DateTime<Utc>) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for market_id in market_ids {
+            sqlx::query(
+                r#"
+                INSERT INTO market_evaluations (market_id, strategy, evaluated_at)
+                VALUES (?1, ?2, ?3)
+                ON CONFLICT (market_id, strategy) DO UPDATE SET
+                    evaluated_at = excluded.evaluated_at
+                "#,
+            )
+            .bind(market_id)
+            .bind(strategy)
+            .bind(evaluated_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn fetch_covered_markets(&self, since: DateTime<Utc>) -> Result<Vec<CoveredMarket>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                m.market_id,
+                m.sport,
+                EXISTS (
+                    SELECT 1 FROM bookmaker_odds o
+                    WHERE o.market_id = m.market_id AND o.timestamp >= ?1
+                ) AS matched,
+                (
+                    SELECT group_concat(e.strategy, char(31)) FROM market_evaluations e
+                    WHERE e.market_id = m.market_id AND e.evaluated_at >= ?1
+                ) AS strategies
+            FROM markets m
+            WHERE m.status = 'active'
+                AND m.event_time > ?2
+            "#,
+        )
+        .bind(since)
+        .bind(Utc::now())
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut markets = Vec::new();
+        for row in rows {
+            let sport: String = row.try_get("sport")?;
+            let Some(sport) = Sport::parse(&sport) else {
+                continue;
+            };
+            let strategies: Option<String> = row.try_get("strategies")?;
+            let mut strategies: Vec<String> = strategies
+                .map(|s| s.split('\u{1f}').map(str::to_string).collect())
+                .unwrap_or_default();
+            strategies.sort();
+            markets.push(CoveredMarket {
+                market_id: row.try_get("market_id")?,
+                sport,
+                matched: row.try_get("matched")?,
+                strategies,
+            });
+        }
+        Ok(markets)
+    }
+
+    async fn record_dead_letter(&self, source: &str, item_key: &str, reason: &str, raw: &serde_json::Value, seen_at: DateTime<Utc>) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO dead_letters (source, item_key, reason, raw, occurrences, first_seen, last_seen)
+            VALUES (?1, ?2, ?3, ?4, 1, ?5, ?5)
+            ON CONFLICT (source, item_key, reason) DO UPDATE SET
+                raw = excluded.raw,
+                occurrences = dead_letters.occurrences + 1,
+                last_seen = excluded.last_seen
+            "#,
+        )
+        .bind(source)
+        .bind(item_key)
+        .bind(reason)
+        .bind(raw.to_string())
+        .bind(seen_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_dead_letters(&self, since: DateTime<Utc>) -> Result<Vec<DeadLetter>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT source, item_key, reason, raw, occurrences, first_seen, last_seen
+            FROM dead_letters
+            WHERE last_seen >= ?1
+            ORDER BY last_seen DESC
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut letters = Vec::new();
+        for row in rows {
+            let raw: String = row.try_get("raw")?;
+            letters.push(DeadLetter {
+                source: row.try_get("source")?,
+                item_key: row.try_get("item_key")?,
+                reason: row.try_get("reason")?,
+                raw: serde_json::from_str(&raw)?,
+                occurrences: row.try_get("occurrences")?,
+                first_seen: row.try_get("first_seen")?,
+                last_seen: row.try_get("last_seen")?,
+            });
+        }
+        Ok(letters)
+    }
+
+    async fn insert_large_trade(&self, trade: &LargeTrade) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO large_trades (
+                tx_hash, market_id, wallet, position, side, shares, price, notional, size_multiple, traded_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+            ON CONFLICT (tx_hash, wallet, position, side) DO NOTHING
+            "#,
+        )
+        .bind(&trade.tx_hash)
+        .bind(&trade.market_id)
+        .bind(&trade.wallet)
+        .bind(trade.position.as_str())
+        .bind(trade.side.as_str())
+        .bind(trade.shares.to_string())
+        .bind(trade.price.to_string())
+        .bind(trade.notional.to_string())
+        .bind(trade.size_multiple.to_string())
+        .bind(trade.traded_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn fetch_large_trades(&self, since: DateTime<Utc>, market_id: Option<&str>) -> Result<Vec<LargeTrade>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT tx_hash, market_id, wallet, position, side, shares, price, notional, size_multiple, traded_at
+            FROM large_trades
+            WHERE traded_at >= ?1 AND (?2 IS NULL OR market_id = ?2)
+            ORDER BY traded_at DESC
+            "#,
+        )
+        .bind(since)
+        .bind(market_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let position: String = row.try_get("position")?;
+                let side: String = row.try_get("side")?;
+                Ok(LargeTrade {
+                    tx_hash: row.try_get("tx_hash")?,
+                    market_id: row.try_get("market_id")?,
+                    wallet: row.try_get("wallet")?,
+                    position: Position::parse(&position).ok_or_else(|| anyhow!("Unknown position {}", position))?,
+                    side: FillSide::parse(&side).ok_or_else(|| anyhow!("Unknown fill side {}", side))?,
+                    shares: decimal(row, "shares")?,
+                    price: decimal(row, "price")?,
+                    notional: decimal(row, "notional")?,
+                    size_multiple: decimal(row, "size_multiple")?,
+                    traded_at: row.try_get("traded_at")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn set_market_resolution(&self, market_id: &str, resolution: Resolution, resolved_at: DateTime<Utc>) -> Result<()> {
+        sqlx::query("UPDATE markets SET resolution = ?2, resolved_at = ?3 WHERE market_id = ?1")
+            .bind(market_id)
+            .bind(resolution.as_str())
+            .bind(resolved_at)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn insert_price_history(&self, points: &[PricePoint]) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        let mut inserted = 0;
+        for point in points {
+            inserted += sqlx::query(
+                r#"
+                INSERT INTO market_price_history (market_id, yes_price, observed_at)
+                VALUES (?1, ?2, ?3)
+                ON CONFLICT (market_id, observed_at) DO NOTHING
+                "#,
+            )
+            .bind(&point.market_id)
+            .bind(point.yes_price.to_string())
+            .bind(point.observed_at)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        }
+        tx.commit().await?;
+
+        Ok(inserted)
+    }
+
+    async fn last_price_history_at(&self, market_id: &str) -> Result<Option<DateTime<Utc>>> {
+        let row = sqlx::query("SELECT MAX(observed_at) AS last FROM market_price_history WHERE market_id = ?1")
+            .bind(market_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row.try_get("last")?)
+    }
+
+    async fn fetch_pre_event_price_history(&self, since: DateTime<Utc>) -> Result<PreEventHistory> {
+        let rows = sqlx::query(
+            r#"
+            SELECT h.market_id, h.yes_price, h.observed_at, m.event_time
+            FROM market_price_history h
+            JOIN markets m ON m.market_id = h.market_id
+            WHERE m.event_time >= ?1
+                AND m.event_time <= ?2
+                AND h.observed_at < m.event_time
+            ORDER BY h.market_id, h.observed_at
+            "#,
+        )
+        .bind(since)
+        .bind(Utc::now())
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut history = PreEventHistory::default();
+        for row in &rows {
+            let market_id: String = row.try_get("market_id")?;
+            history.event_times.insert(market_id.clone(), row.try_get("event_time")?);
+            history.points.push(PricePoint {
+                market_id,
+                yes_price: price(row, "yes_price")?,
+                observed_at: row.try_get("observed_at")?,
+            });
+        }
+        Ok(history)
+    }
+
+    async fn upsert_closing_line(
+        &self,
+        market_id: &str,
+        yes_price: Price,
+        no_price: Price,
+        captured_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO closing_lines (market_id, yes_price, no_price, captured_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT (market_id) DO UPDATE SET
+                yes_price = excluded.yes_price,
+                no_price = excluded.no_price,
+                captured_at = excluded.captured_at
+            "#,
+        )
+        .bind(market_id)
+        .bind(yes_price.value().to_string())
+        .bind(no_price.value().to_string())
+        .bind(captured_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SignalRepo for SqliteStorage {
+    async fn insert_signal(&self, signal: &Signal) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO signals (
+                signal_id, market_id, strategy, signal_type,
+                confidence, edge_size, recommended_size,
+                current_price, executable_size, quoted_edge, fair_value, generated_at, metadata
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+            "#,
+        )
+        .bind(signal.signal_id.to_string())
+        .bind(&signal.market_id)
+        .bind(signal.strategy.as_str())
+        .bind(serde_json::to_string(&signal.signal_type)?)
+        .bind(signal.confidence.to_string())
+        .bind(signal.edge_size.to_string())
+        .bind(signal.recommended_size.to_string())
+        .bind(signal.current_price.to_string())
+        .bind(signal.executable_size.map(|s| s.to_string()))
+        .bind(signal.quoted_edge.to_string())
+        .bind(signal.fair_value.to_string())
+        .bind(signal.generated_at)
+        .bind(envelope(MetadataKind::Signal, &signal.metadata).to_string())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_pending_signals(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<Signal>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                signal_id, market_id, strategy, signal_type,
+                confidence, edge_size, recommended_size,
+                current_price, executable_size, quoted_edge, fair_value, generated_at, metadata
+            FROM signals
+            WHERE executed = 0
+                AND generated_at > ?1
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut signals: Vec<Signal> = rows.iter().filter_map(signal_from_row).collect();
+        signals.sort_by(|a, b| {
+            b.confidence.cmp(&a.confidence).then(b.edge_size.cmp(&a.edge_size))
+        });
+        signals.truncate(limit.max(0) as usize);
+
+        Ok(signals)
+    }
+
+    async fn claim_pending_signals(
+        &self,
+        since: DateTime<Utc>,
+        limit: i64,
+        owner: &str,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<Signal>> {
+        let now = Utc::now();
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                signal_id, market_id, strategy, signal_type,
+                confidence, edge_size, recommended_size,
+                current_price, executable_size, quoted_edge, fair_value, generated_at, metadata
+            FROM signals
+            WHERE executed = 0
+                AND generated_at > ?1
+                AND (claimed_until IS NULL OR claimed_until <= ?2)
+            "#,
+        )
+        .bind(since)
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut candidates: Vec<Signal> = rows.iter().filter_map(signal_from_row).collect();
+        candidates.sort_by(|a, b| {
+            b.confidence.cmp(&a.confidence).then(b.edge_size.cmp(&a.edge_size))
+        });
+        candidates.truncate(limit.max(0) as usize);
+
+        // Each claim re-checks the lease as it writes, so a signal another
+        // connection claimed since the read is left to it
+        let mut signals = Vec::with_capacity(candidates.len());
+        for signal in candidates {
+            let claimed = sqlx::query(
+                r#"
+                UPDATE signals
+                SET claimed_by = ?2, claimed_until = ?3
+                WHERE signal_id = ?1
+                    AND executed = 0
+                    AND (claimed_until IS NULL OR claimed_until <= ?4)
+                "#,
+            )
+            .bind(signal.signal_id.to_string())
+            .bind(owner)
+            .bind(until)
+            .bind(now)
+            .execute(&self.pool)
+            .await?
+            .rows_affected() == 1;
+            if claimed {
+                signals.push(signal);
+            }
+        }
+
+        Ok(signals)
+    }
+
+    async fn release_signal(&self, signal_id: Uuid, owner: &str) -> Result<()> {
+        sqlx::query("UPDATE signals SET claimed_by = NULL, claimed_until = NULL WHERE signal_id = ?1 AND claimed_by = ?2")
+            .bind(signal_id.to_string())
+            .bind(owner)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn renew_signal_claim(&self, signal_id: Uuid, owner: &str, until: DateTime<Utc>) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE signals SET claimed_until = ?3 WHERE signal_id = ?1 AND claimed_by = ?2 AND executed = 0",
+        )
+        .bind(signal_id.to_string())
+        .bind(owner)
+        .bind(until)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn fetch_signal(&self, signal_id: Uuid) -> Result<Option<Signal>> {
+        let row = sqlx::query(
+            r#"
+            SELECT
+                signal_id, market_id, strategy, signal_type,
+                confidence, edge_size, recommended_size,
+                current_price, executable_size, quoted_edge, fair_value, generated_at, metadata
+            FROM signals
+            WHERE signal_id = ?1
+            "#,
+        )
+        .bind(signal_id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().and_then(signal_from_row))
+    }
+
+    async fn fetch_market_signals(&self, market_id: &str, since: DateTime<Utc>) -> Result<Vec<Signal>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                signal_id, market_id, strategy, signal_type,
+                confidence, edge_size, recommended_size,
+                current_price, executable_size, quoted_edge, fair_value, generated_at, metadata
+            FROM signals
+            WHERE market_id = ?1
+                AND generated_at >= ?2
+            "#,
+        )
+        .bind(market_id)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut signals: Vec<Signal> = rows.iter().filter_map(signal_from_row).collect();
+        signals.sort_by_key(|s| s.generated_at);
+
+        Ok(signals)
+    }
+
+    async fn upsert_failed_execution(&self, failure: &FailedExecution) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO failed_executions (
+                signal_id, market_id, strategy, attempts, last_error, status,
+                next_attempt_at, first_failed_at, updated_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            ON CONFLICT (signal_id) DO UPDATE SET
+                attempts = excluded.attempts,
+                last_error = excluded.last_error,
+                status = excluded.status,
+                next_attempt_at = excluded.next_attempt_at,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(failure.signal_id.to_string())
+        .bind(&failure.market_id)
+        .bind(failure.strategy.as_str())
+        .bind(failure.attempts)
+        .bind(&failure.last_error)
+        .bind(failure.status.as_str())
+        .bind(failure.next_attempt_at)
+        .bind(failure.first_failed_at)
+        .bind(failure.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_failed_execution(&self, signal_id: Uuid) -> Result<Option<FailedExecution>> {
+        let row = sqlx::query(
+            r#"
+            SELECT signal_id, market_id, strategy, attempts, last_error, status,
+                   next_attempt_at, first_failed_at, updated_at
+            FROM failed_executions
+            WHERE signal_id = ?1
+            "#,
+        )
+        .bind(signal_id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => failed_execution_from_row(&row),
+            None => Ok(None),
+        }
+    }
+
+    async fn fetch_failed_executions(&self, since: DateTime<Utc>) -> Result<Vec<FailedExecution>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT signal_id, market_id, strategy, attempts, last_error, status,
+                   next_attempt_at, first_failed_at, updated_at
+            FROM failed_executions
+            WHERE updated_at >= ?1
+            ORDER BY updated_at DESC
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut failures = Vec::new();
+        for row in &rows {
+            if let Some(failure) = failed_execution_from_row(row)? {
+                failures.push(failure);
+            }
+        }
+        Ok(failures)
+    }
+    async fn latest_fair_value(&self, market_id: &str, position: Position) -> Result<Option<Decimal>> {
+        let row = sqlx::query(
+            r#"
+            SELECT fair_value
+            FROM signals
+            WHERE market_id = ?1 AND signal_type = ?2 AND fair_value IS NOT NULL
+            ORDER BY generated_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(market_id)
+        .bind(serde_json::to_string(&SignalType::buy(position))?)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|r| decimal(&r, "fair_value")).transpose()
+    }
+
+    async fn mark_signal_executed(&self, signal_id: Uuid, trade_id: Option<Uuid>) -> Result<()> {
+        sqlx::query("UPDATE signals SET executed = 1, executed_trade_id = ?2 WHERE signal_id = ?1")
+            .bind(signal_id.to_string())
+            .bind(trade_id.map(|id| id.to_string()))
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn shadow_strategy(&self, strategy: Strategy, reason: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO shadowed_strategies (strategy, reason, since)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT (strategy) DO NOTHING
+            "#,
+        )
+        .bind(strategy.as_str())
+        .bind(reason)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn unshadow_strategy(&self, strategy: Strategy) -> Result<()> {
+        sqlx::query("DELETE FROM shadowed_strategies WHERE strategy = ?1")
+            .bind(strategy.as_str())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_shadowed_strategies(&self) -> Result<Vec<ShadowedStrategy>> {
+        let rows = sqlx::query("SELECT strategy, reason, since FROM shadowed_strategies")
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                let strategy: String = row.try_get("strategy")?;
+                Ok(ShadowedStrategy {
+                    strategy: Strategy::parse(&strategy)
+                        .ok_or_else(|| anyhow!("Unknown strategy {}", strategy))?,
+                    reason: row.try_get("reason")?,
+                    since: row.try_get("since")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn upsert_strategy_throttle(&self, throttle: &StrategyThrottle) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO strategy_throttles (strategy, size_multiplier, max_daily_signals, realized_edge, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT (strategy) DO UPDATE SET
+                size_multiplier = excluded.size_multiplier,
+                max_daily_signals = excluded.max_daily_signals,
+                realized_edge = excluded.realized_edge,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(throttle.strategy.as_str())
+        .bind(throttle.size_multiplier.to_string())
+        .bind(throttle.max_daily_signals)
+        .bind(throttle.realized_edge.to_string())
+        .bind(throttle.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn clear_strategy_throttle(&self, strategy: Strategy) -> Result<()> {
+        sqlx::query("DELETE FROM strategy_throttles WHERE strategy = ?1")
+            .bind(strategy.as_str())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_strategy_throttles(&self) -> Result<Vec<StrategyThrottle>> {
+        let rows = sqlx::query(
+            "SELECT strategy, size_multiplier, max_daily_signals, realized_edge, updated_at FROM strategy_throttles",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let strategy: String = row.try_get("strategy")?;
+                Ok(StrategyThrottle {
+                    strategy: Strategy::parse(&strategy)
+                        .ok_or_else(|| anyhow!("Unknown strategy {}", strategy))?,
+                    size_multiplier: decimal(row, "size_multiplier")?,
+                    max_daily_signals: row.try_get("max_daily_signals")?,
+                    realized_edge: decimal(row, "realized_edge")?,
+                    updated_at: row.try_get("updated_at")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn replace_strategy_weights(&self, weights: &[StrategyWeight]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM strategy_weights").execute(&mut *tx).await?;
+        for weight in weights {
+            sqlx::query(
+                r#"
+                INSERT INTO strategy_weights (strategy, weight, mean_return, volatility, updated_at)
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                "#,
+            )
+            .bind(weight.strategy.as_str())
+            .bind(weight.weight.to_string())
+            .bind(weight.mean_return.to_string())
+            .bind(weight.volatility.to_string())
+            .bind(weight.updated_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn fetch_strategy_weights(&self) -> Result<Vec<StrategyWeight>> {
+        let rows = sqlx::query(
+            "SELECT strategy, weight, mean_return, volatility, updated_at FROM strategy_weights",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let strategy: String = row.try_get("strategy")?;
+                Ok(StrategyWeight {
+                    strategy: Strategy::parse(&strategy)
+                        .ok_or_else(|| anyhow!("Unknown strategy {}", strategy))?,
+                    weight: decimal(row, "weight")?,
+                    mean_return: decimal(row, "mean_return")?,
+                    volatility: decimal(row, "volatility")?,
+                    updated_at: row.try_get("updated_at")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn record_execution_quote(&self, signal_id: Uuid, price: Price, at: DateTime<Utc>) -> Result<()> {
+        sqlx::query("UPDATE signals SET execution_quote = ?2, execution_quoted_at = ?3 WHERE signal_id = ?1")
+            .bind(signal_id.to_string())
+            .bind(price.value().to_string())
+            .bind(at)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_signal_executions(&self, since: DateTime<Utc>) -> Result<Vec<SignalExecution>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                strategy, current_price AS signal_price, fair_value, generated_at,
+                execution_quote, execution_quoted_at
+            FROM signals
+            WHERE execution_quoted_at >= ?1
+                AND execution_quote IS NOT NULL
+                AND current_price IS NOT NULL
+                AND fair_value IS NOT NULL
+            ORDER BY execution_quoted_at ASC
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let strategy: String = row.try_get("strategy")?;
+                Ok(SignalExecution {
+                    strategy: Strategy::parse(&strategy)
+                        .ok_or_else(|| anyhow!("Unknown strategy {}", strategy))?,
+                    signal_price: decimal(row, "signal_price")?,
+                    fair_value: decimal(row, "fair_value")?,
+                    generated_at: row.try_get("generated_at")?,
+                    quoted_price: decimal(row, "execution_quote")?,
+                    quoted_at: row.try_get("execution_quoted_at")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn replace_edge_decays(&self, decays: &[EdgeDecay]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM edge_decay").execute(&mut *tx).await?;
+        for decay in decays {
+            sqlx::query(
+                r#"
+                INSERT INTO edge_decay (strategy, half_life_secs, samples, updated_at)
+                VALUES (?1, ?2, ?3, ?4)
+                "#,
+            )
+            .bind(decay.strategy.as_str())
+            .bind(decay.half_life_secs)
+            .bind(decay.samples)
+            .bind(decay.updated_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn fetch_edge_decays(&self) -> Result<Vec<EdgeDecay>> {
+        let rows = sqlx::query(
+            "SELECT strategy, half_life_secs, samples, updated_at FROM edge_decay",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let strategy: String = row.try_get("strategy")?;
+                Ok(EdgeDecay {
+                    strategy: Strategy::parse(&strategy)
+                        .ok_or_else(|| anyhow!("Unknown strategy {}", strategy))?,
+                    half_life_secs: row.try_get("half_life_secs")?,
+                    samples: row.try_get("samples")?,
+                    updated_at: row.try_get("updated_at")?,
+                })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl TradeRepo for SqliteStorage {
+    async fn insert_trade(&self, trade: &Trade) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO trades (
+                trade_id, market_id, strategy, position, quantity,
+                entry_price, entry_time, tx_hash_entry, status, gas_cost, fees, slippage, venue, origin
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+            "#,
+        )
+        .bind(trade.trade_id.to_string())
+        .bind(&trade.market_id)
+        .bind(trade.strategy.as_str())
+        .bind(trade.position.as_str())
+        .bind(trade.quantity.to_string())
+        .bind(trade.entry_price.to_string())
+        .bind(trade.entry_time)
+        .bind(&trade.tx_hash_entry)
+        .bind(trade.status.as_str())
+        .bind(trade.gas_cost.map(|v| v.to_string()))
+        .bind(trade.fees.map(|v| v.to_string()))
+        .bind(trade.slippage.map(|v| v.to_string()))
+        .bind(&trade.venue)
+        .bind(trade.origin.as_str())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_open_trades(&self) -> Result<Vec<Trade>> {
+        let rows = sqlx::query(&format!("SELECT {} FROM trades WHERE status = 'open'", TRADE_COLUMNS))
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.iter().filter_map(trade_from_row).collect())
+    }
+
+    async fn fetch_trade(&self, trade_id: Uuid) -> Result<Option<Trade>> {
+        let row = sqlx::query(&format!("SELECT {} FROM trades WHERE trade_id = ?1", TRADE_COLUMNS))
+            .bind(trade_id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.as_ref().and_then(trade_from_row))
+    }
+
+    async fn fetch_trade_by_entry_tx(&self, tx_hash: &str) -> Result<Option<Trade>> {
+        let row = sqlx::query(&format!("SELECT {} FROM trades WHERE tx_hash_entry = ?1", TRADE_COLUMNS))
+            .bind(tx_hash)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.as_ref().and_then(trade_from_row))
+    }
+
+    async fn fetch_closed_trades(&self, since: DateTime<Utc>) -> Result<Vec<Trade>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM trades WHERE status = 'closed' AND exit_time >= ?1 ORDER BY exit_time ASC",
+            TRADE_COLUMNS
+        ))
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().filter_map(trade_from_row).collect())
+    }
+
+    async fn fetch_prediction_outcomes(&self, since: DateTime<Utc>) -> Result<Vec<PredictionOutcome>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                t.trade_id, t.strategy, s.fair_value, t.entry_price, t.exit_price, t.exit_time, ts.payout,
+                CASE t.position WHEN 'yes' THEN c.yes_price ELSE c.no_price END AS closing_price
+            FROM trades t
+            JOIN signals s ON s.executed_trade_id = t.trade_id
+            LEFT JOIN closing_lines c ON c.market_id = t.market_id
+            LEFT JOIN trade_settlements ts ON ts.trade_id = t.trade_id AND ts.outcome <> 'void'
+            WHERE t.status = 'closed'
+                AND t.exit_time >= ?1
+                AND t.exit_price IS NOT NULL
+                AND s.fair_value IS NOT NULL
+            ORDER BY t.exit_time ASC
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let strategy: String = row.try_get("strategy")?;
+                Ok(PredictionOutcome {
+                    trade_id: uuid(row, "trade_id")?,
+                    strategy: Strategy::parse(&strategy)
+                        .ok_or_else(|| anyhow!("Unknown strategy {}", strategy))?,
+                    fair_value: decimal(row, "fair_value")?,
+                    entry_price: decimal(row, "entry_price")?,
+                    exit_price: decimal(row, "exit_price")?,
+                    payout: opt_decimal(row, "payout")?,
+                    closing_price: opt_decimal(row, "closing_price")?,
+                    exit_time: row.try_get("exit_time")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn close_trade(
+        &self,
+        trade_id: Uuid,
+        exit_price: Decimal,
+        exit_time: DateTime<Utc>,
+        pnl: Usdc,
+        tx_hash: &str,
+    ) -> Result<()> {
+        // Excursions are computed in Rust so TEXT decimals are not coerced to REAL
+        let trade = self.fetch_trade(trade_id).await?
+            .ok_or_else(|| anyhow!("Trade {} not found", trade_id))?;
+        let mark = self.fetch_trade_marks().await?
+            .into_iter()
+            .find(|m| m.trade_id == trade_id);
+        let (mae, mfe) = trade.excursions_at_close(exit_price, mark.as_ref());
+
+        sqlx::query(
+            r#"
+            UPDATE trades
+            SET exit_price = ?2,
+                exit_time = ?3,
+                pnl = ?4,
+                status = 'closed',
+                tx_hash_exit = ?5,
+                max_adverse_excursion = ?6,
+                max_favorable_excursion = ?7
+            WHERE trade_id = ?1
+            "#,
+        )
+        .bind(trade_id.to_string())
+        .bind(exit_price.to_string())
+        .bind(exit_time)
+        .bind(pnl.to_string())
+        .bind(tx_hash)
+        .bind(mae.to_string())
+        .bind(mfe.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn correct_trade_entry(
+        &self,
+        trade_id: Uuid,
+        entry_price: Decimal,
+        quantity: Decimal,
+        slippage: Option<Decimal>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE trades
+            SET entry_price = ?2, quantity = ?3, slippage = ?4
+            WHERE trade_id = ?1 AND status = 'open'
+            "#,
+        )
+        .bind(trade_id.to_string())
+        .bind(entry_price.to_string())
+        .bind(quantity.to_string())
+        .bind(slippage.map(|s| s.to_string()))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn recent_closed_pnls(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<Option<Decimal>>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT pnl
+            FROM trades
+            WHERE status = 'closed'
+                AND entry_time > ?1
+            ORDER BY entry_time DESC
+            LIMIT ?2
+            "#,
+        )
+        .bind(since)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(|row| opt_decimal(row, "pnl")).collect()
+    }
+
+    async fn realized_pnl_today(&self) -> Result<Decimal> {
+        let rows = sqlx::query("SELECT pnl FROM trades WHERE status = 'closed' AND exit_time >= ?1")
+            .bind(Self::start_of_today())
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.iter()
+            .filter_map(|row| opt_decimal(row, "pnl").ok().flatten())
+            .sum())
+    }
+
+    async fn execution_costs_today(&self) -> Result<Decimal> {
+        let rows = sqlx::query("SELECT gas_cost, fees FROM trades WHERE entry_time >= ?1")
+            .bind(Self::start_of_today())
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut total = Decimal::ZERO;
+        for row in &rows {
+            total += opt_decimal(row, "gas_cost")?.unwrap_or_default() + opt_decimal(row, "fees")?.unwrap_or_default();
+        }
+
+        let rows = sqlx::query("SELECT amount FROM execution_costs WHERE incurred_at >= ?1")
+            .bind(Self::start_of_today())
+            .fetch_all(&self.pool)
+            .await?;
+        for row in &rows {
+            total += decimal(row, "amount")?;
+        }
+        Ok(total)
+    }
+
+    async fn insert_execution_cost(&self, cost: &ExecutionCost) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO execution_costs (cost_id, kind, amount, trade_id, tx_hash, incurred_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            "#,
+        )
+        .bind(cost.cost_id.to_string())
+        .bind(cost.kind.as_str())
+        .bind(cost.amount.to_string())
+        .bind(cost.trade_id.map(|id| id.to_string()))
+        .bind(&cost.tx_hash)
+        .bind(cost.incurred_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn count_open_trades(&self) -> Result<i64> {
+        let row = sqlx::query("SELECT COUNT(*) AS count FROM trades WHERE status = 'open'")
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row.try_get("count")?)
+    }
+
+    async fn count_trades_today(&self) -> Result<i64> {
+        let row = sqlx::query("SELECT COUNT(*) AS count FROM trades WHERE entry_time >= ?1")
+            .bind(Self::start_of_today())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row.try_get("count")?)
+    }
+
+    async fn count_strategy_trades_today(&self, strategy: Strategy) -> Result<i64> {
+        let row = sqlx::query("SELECT COUNT(*) AS count FROM trades WHERE strategy = ?1 AND entry_time >= ?2")
+            .bind(strategy.as_str())
+            .bind(Self::start_of_today())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row.try_get("count")?)
+    }
+
+    async fn insert_slippage_event(&self, event: &SlippageEvent) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO slippage_events (
+                event_id, trade_id, signal_id, market_id, expected_price, fill_price, slippage,
+                flattened, occurred_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            "#,
+        )
+        .bind(event.event_id.to_string())
+        .bind(event.trade_id.to_string())
+        .bind(event.signal_id.to_string())
+        .bind(&event.market_id)
+        .bind(event.expected_price.to_string())
+        .bind(event.fill_price.to_string())
+        .bind(event.slippage.to_string())
+        .bind(event.flattened)
+        .bind(event.occurred_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn upsert_trade_mark(&self, mark: &TradeMark) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO trade_marks (
+                trade_id, mark_price, unrealized_pnl, max_adverse_excursion, max_favorable_excursion, marked_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            ON CONFLICT (trade_id) DO UPDATE SET
+                mark_price = excluded.mark_price,
+                unrealized_pnl = excluded.unrealized_pnl,
+                max_adverse_excursion = excluded.max_adverse_excursion,
+                max_favorable_excursion = excluded.max_favorable_excursion,
+                marked_at = excluded.marked_at
+            "#,
+        )
+        .bind(mark.trade_id.to_string())
+        .bind(mark.mark_price.to_string())
+        .bind(mark.unrealized_pnl.to_string())
+        .bind(mark.max_adverse_excursion.to_string())
+        .bind(mark.max_favorable_excursion.to_string())
+        .bind(mark.marked_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_trade_marks(&self) -> Result<Vec<TradeMark>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                m.trade_id, m.mark_price, m.unrealized_pnl, m.max_adverse_excursion,
+                m.max_favorable_excursion, m.marked_at
+            FROM trade_marks m
+            JOIN trades t ON t.trade_id = m.trade_id
+            WHERE t.status = 'open'
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(TradeMark {
+                    trade_id: uuid(row, "trade_id")?,
+                    mark_price: decimal(row, "mark_price")?,
+                    unrealized_pnl: decimal(row, "unrealized_pnl")?,
+                    max_adverse_excursion: decimal(row, "max_adverse_excursion")?,
+                    max_favorable_excursion: decimal(row, "max_favorable_excursion")?,
+                    marked_at: row.try_get("marked_at")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn insert_convergence_sample(&self, sample: &ConvergenceSample) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO trade_convergence (trade_id, market_price, fair_value, divergence, sampled_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+        )
+        .bind(sample.trade_id.to_string())
+        .bind(sample.market_price.to_string())
+        .bind(sample.fair_value.to_string())
+        .bind(sample.divergence.to_string())
+        .bind(sample.sampled_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_convergence_samples(&self, trade_id: Uuid) -> Result<Vec<ConvergenceSample>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT trade_id, market_price, fair_value, divergence, sampled_at
+            FROM trade_convergence
+            WHERE trade_id = ?1
+            ORDER BY sampled_at
+            "#,
+        )
+        .bind(trade_id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(ConvergenceSample {
+                    trade_id: uuid(row, "trade_id")?,
+                    market_price: decimal(row, "market_price")?,
+                    fair_value: decimal(row, "fair_value")?,
+                    divergence: decimal(row, "divergence")?,
+                    sampled_at: row.try_get("sampled_at")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn insert_trade_sizing(&self, sizing: &TradeSizing) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO trade_sizing (trade_id, bankroll, stake, sized_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT (trade_id) DO NOTHING
+            "#,
+        )
+        .bind(sizing.trade_id.to_string())
+        .bind(sizing.bankroll.to_string())
+        .bind(sizing.stake.to_string())
+        .bind(sizing.sized_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_trade_sizing(&self, since: DateTime<Utc>) -> Result<Vec<TradeSizing>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT z.trade_id, z.bankroll, z.stake, z.sized_at
+            FROM trade_sizing z
+            JOIN trades t ON t.trade_id = z.trade_id
+            WHERE t.status = 'closed' AND t.exit_time >= ?1
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(TradeSizing {
+                    trade_id: uuid(row, "trade_id")?,
+                    bankroll: decimal(row, "bankroll")?,
+                    stake: decimal(row, "stake")?,
+                    sized_at: row.try_get("sized_at")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn upsert_settlement_review(&self, review: &SettlementReview) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO settlement_reviews (trade_id, market_id, reported_payout, onchain_payout, reason, flagged_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            ON CONFLICT (trade_id) DO UPDATE SET
+                reported_payout = excluded.reported_payout,
+                onchain_payout = excluded.onchain_payout,
+                reason = excluded.reason
+            WHERE settlement_reviews.resolved_at IS NULL
+            "#,
+        )
+        .bind(review.trade_id.to_string())
+        .bind(&review.market_id)
+        .bind(review.reported_payout.map(|p| p.to_string()))
+        .bind(review.onchain_payout.map(|p| p.to_string()))
+        .bind(&review.reason)
+        .bind(review.flagged_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_open_settlement_reviews(&self) -> Result<Vec<SettlementReview>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT trade_id, market_id, reported_payout, onchain_payout, reason, flagged_at, resolved_at
+            FROM settlement_reviews
+            WHERE resolved_at IS NULL
+            ORDER BY flagged_at
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(SettlementReview {
+                    trade_id: uuid(row, "trade_id")?,
+                    market_id: row.try_get("market_id")?,
+                    reported_payout: opt_decimal(row, "reported_payout")?,
+                    onchain_payout: opt_decimal(row, "onchain_payout")?,
+                    reason: row.try_get("reason")?,
+                    flagged_at: row.try_get("flagged_at")?,
+                    resolved_at: row.try_get("resolved_at")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn resolve_settlement_review(&self, trade_id: Uuid, resolved_at: DateTime<Utc>) -> Result<()> {
+        sqlx::query("UPDATE settlement_reviews SET resolved_at = ?2 WHERE trade_id = ?1 AND resolved_at IS NULL")
+            .bind(trade_id.to_string())
+            .bind(resolved_at)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn insert_trade_settlement(&self, settlement: &TradeSettlement) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO trade_settlements (trade_id, market_id, outcome, payout, pnl, tx_hash, settled_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            ON CONFLICT (trade_id) DO NOTHING
+            "#,
+        )
+        .bind(settlement.trade_id.to_string())
+        .bind(&settlement.market_id)
+        .bind(settlement.outcome.as_str())
+        .bind(settlement.payout.to_string())
+        .bind(settlement.pnl.to_string())
+        .bind(&settlement.tx_hash)
+        .bind(settlement.settled_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_trade_settlements(&self, since: DateTime<Utc>) -> Result<Vec<TradeSettlement>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT trade_id, market_id, outcome, payout, pnl, tx_hash, settled_at
+            FROM trade_settlements
+            WHERE settled_at >= ?1
+            ORDER BY settled_at DESC
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut settlements = Vec::new();
+        for row in rows {
+            let outcome: String = row.try_get("outcome")?;
+            let Some(outcome) = TradeOutcome::parse(&outcome) else {
+                continue;
+            };
+            settlements.push(TradeSettlement {
+                trade_id: uuid(&row, "trade_id")?,
+                market_id: row.try_get("market_id")?,
+                outcome,
+                payout: decimal(&row, "payout")?,
+                pnl: Usdc::new(decimal(&row, "pnl")?),
+                tx_hash: row.try_get("tx_hash")?,
+                settled_at: row.try_get("settled_at")?,
+            });
+        }
+        Ok(settlements)
+    }
+
+    async fn insert_onchain_fill(&self, fill: &OnchainFill) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO onchain_fills (
+                tx_hash, log_index, order_hash, token_id, side, shares, price, fee, block_number, observed_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+            ON CONFLICT (tx_hash, log_index) DO NOTHING
+            "#,
+        )
+        .bind(&fill.tx_hash)
+        .bind(fill.log_index)
+        .bind(&fill.order_hash)
+        .bind(&fill.token_id)
+        .bind(fill.side.as_str())
+        .bind(fill.shares.to_string())
+        .bind(fill.price.to_string())
+        .bind(fill.fee.to_string())
+        .bind(fill.block_number)
+        .bind(fill.observed_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn fetch_order_fills(&self, order_hash: &str) -> Result<Vec<OnchainFill>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT tx_hash, log_index, order_hash, token_id, side, shares, price, fee, block_number, observed_at
+            FROM onchain_fills
+            WHERE order_hash = ?1
+            ORDER BY block_number, log_index
+            "#,
+        )
+        .bind(order_hash)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(onchain_fill_from_row).collect()
+    }
+
+    async fn fetch_tx_fills(&self, tx_hash: &str) -> Result<Vec<OnchainFill>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT tx_hash, log_index, order_hash, token_id, side, shares, price, fee, block_number, observed_at
+            FROM onchain_fills
+            WHERE tx_hash = ?1
+            ORDER BY log_index
+            "#,
+        )
+        .bind(tx_hash)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(onchain_fill_from_row).collect()
+    }
+
+    async fn insert_resting_order(&self, order: &RestingOrder) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO resting_orders (order_id, trade_id, kind, price, quantity, status, placed_at, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, 'open', ?6, ?6)
+            "#,
+        )
+        .bind(&order.order_id)
+        .bind(order.trade_id.to_string())
+        .bind(order.kind.as_str())
+        .bind(order.price.to_string())
+        .bind(order.quantity.to_string())
+        .bind(order.placed_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_resting_orders(&self, trade_id: Uuid) -> Result<Vec<RestingOrder>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT order_id, trade_id, kind, price, quantity, placed_at
+            FROM resting_orders
+            WHERE trade_id = ?1 AND status = 'open'
+            "#,
+        )
+        .bind(trade_id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let kind: String = row.try_get("kind")?;
+                Ok(RestingOrder {
+                    order_id: row.try_get("order_id")?,
+                    trade_id: uuid(row, "trade_id")?,
+                    kind: RestingOrderKind::parse(&kind)
+                        .ok_or_else(|| anyhow!("Unknown resting order kind {}", kind))?,
+                    price: decimal(row, "price")?,
+                    quantity: decimal(row, "quantity")?,
+                    placed_at: row.try_get("placed_at")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn set_resting_order_status(&self, order_id: &str, status: RestingOrderStatus) -> Result<()> {
+        sqlx::query("UPDATE resting_orders SET status = ?2, updated_at = ?3 WHERE order_id = ?1")
+            .bind(order_id)
+            .bind(status.as_str())
+            .bind(Utc::now())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn insert_entry_order(&self, order: &EntryOrder) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO entry_orders (
+                order_id, signal_id, market_id, position, price, quantity, gas_cost, status, placed_at, expires_at, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 'open', ?8, ?9, ?8)
+            "#,
+        )
+        .bind(&order.order_id)
+        .bind(order.signal_id.to_string())
+        .bind(&order.market_id)
+        .bind(order.position.as_str())
+        .bind(order.price.to_string())
+        .bind(order.quantity.to_string())
+        .bind(order.gas_cost.to_string())
+        .bind(order.placed_at)
+        .bind(order.expires_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_open_entry_orders(&self) -> Result<Vec<EntryOrder>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT order_id, signal_id, market_id, position, price, quantity, gas_cost, placed_at, expires_at
+            FROM entry_orders
+            WHERE status = 'open'
+            ORDER BY placed_at ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let position: String = row.try_get("position")?;
+                Ok(EntryOrder {
+                    order_id: row.try_get("order_id")?,
+                    signal_id: uuid(row, "signal_id")?,
+                    market_id: row.try_get("market_id")?,
+                    position: Position::parse(&position)
+                        .ok_or_else(|| anyhow!("Unknown position {}", position))?,
+                    price: decimal(row, "price")?,
+                    quantity: decimal(row, "quantity")?,
+                    gas_cost: decimal(row, "gas_cost")?,
+                    placed_at: row.try_get("placed_at")?,
+                    expires_at: row.try_get("expires_at")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn set_entry_order_status(&self, order_id: &str, status: RestingOrderStatus) -> Result<()> {
+        sqlx::query("UPDATE entry_orders SET status = ?2, updated_at = ?3 WHERE order_id = ?1")
+            .bind(order_id)
+            .bind(status.as_str())
+            .bind(Utc::now())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn insert_parent_order(&self, order: &ParentOrder) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO parent_orders (
+                parent_id, signal_id, market_id, position, mode, target_notional, limit_price, slices,
+                children_sent, filled_quantity, filled_notional, status, next_slice_at, created_at, expires_at, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?14)
+            "#,
+        )
+        .bind(order.parent_id.to_string())
+        .bind(order.signal_id.to_string())
+        .bind(&order.market_id)
+        .bind(order.position.as_str())
+        .bind(order.mode.as_str())
+        .bind(order.target_notional.to_string())
+        .bind(order.limit_price.to_string())
+        .bind(order.slices)
+        .bind(order.children_sent)
+        .bind(order.filled_quantity.to_string())
+        .bind(order.filled_notional.to_string())
+        .bind(order.status.as_str())
+        .bind(order.next_slice_at)
+        .bind(order.created_at)
+        .bind(order.expires_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_parent_order(&self, order: &ParentOrder) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE parent_orders SET
+                children_sent = ?2,
+                filled_quantity = ?3,
+                filled_notional = ?4,
+                status = ?5,
+                next_slice_at = ?6,
+                updated_at = ?7
+            WHERE parent_id = ?1
+            "#,
+        )
+        .bind(order.parent_id.to_string())
+        .bind(order.children_sent)
+        .bind(order.filled_quantity.to_string())
+        .bind(order.filled_notional.to_string())
+        .bind(order.status.as_str())
+        .bind(order.next_slice_at)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_open_parent_orders(&self) -> Result<Vec<ParentOrder>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                parent_id, signal_id, market_id, position, mode, target_notional, limit_price, slices,
+                children_sent, filled_quantity, filled_notional, next_slice_at, created_at, expires_at
+            FROM parent_orders
+            WHERE status = 'open'
+            ORDER BY created_at ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let position: String = row.try_get("position")?;
+                let mode: String = row.try_get("mode")?;
+                Ok(ParentOrder {
+                    parent_id: uuid(row, "parent_id")?,
+                    signal_id: uuid(row, "signal_id")?,
+                    market_id: row.try_get("market_id")?,
+                    position: Position::parse(&position)
+                        .ok_or_else(|| anyhow!("Unknown position {}", position))?,
+                    mode: SliceMode::parse(&mode).ok_or_else(|| anyhow!("Unknown slice mode {}", mode))?,
+                    target_notional: decimal(row, "target_notional")?,
+                    limit_price: decimal(row, "limit_price")?,
+                    slices: row.try_get("slices")?,
+                    children_sent: row.try_get("children_sent")?,
+                    filled_quantity: decimal(row, "filled_quantity")?,
+                    filled_notional: decimal(row, "filled_notional")?,
+                    status: RestingOrderStatus::Open,
+                    next_slice_at: row.try_get("next_slice_at")?,
+                    created_at: row.try_get("created_at")?,
+                    expires_at: row.try_get("expires_at")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn insert_child_order(&self, order: &ChildOrder) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO child_orders (parent_id, slice, trade_id, quantity, price, tx_hash, sent_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+        )
+        .bind(order.parent_id.to_string())
+        .bind(order.slice)
+        .bind(order.trade_id.to_string())
+        .bind(order.quantity.to_string())
+        .bind(order.price.to_string())
+        .bind(&order.tx_hash)
+        .bind(order.sent_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CashFlowRepo for SqliteStorage {
+    async fn insert_cash_flow(&self, flow: &CashFlow) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO cash_flows (
+                flow_id, kind, amount, source, tx_hash, note, occurred_at, venue
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            "#,
+        )
+        .bind(flow.flow_id.to_string())
+        .bind(flow.kind.as_str())
+        .bind(flow.amount.to_string())
+        .bind(flow.source.as_str())
+        .bind(&flow.tx_hash)
+        .bind(&flow.note)
+        .bind(flow.occurred_at)
+        .bind(&flow.venue)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_cash_flows(&self, since: DateTime<Utc>) -> Result<Vec<CashFlow>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT flow_id, kind, amount, source, tx_hash, note, occurred_at, venue
+            FROM cash_flows
+            WHERE occurred_at >= ?1
+            ORDER BY occurred_at ASC
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().filter_map(cash_flow_from_row).collect())
+    }
+
+    async fn cash_flow_exists(&self, tx_hash: &str) -> Result<bool> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM cash_flows WHERE tx_hash = ?1")
+            .bind(tx_hash)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count > 0)
+    }
+}
+
+#[async_trait]
+impl PortfolioRepo for SqliteStorage {
+    async fn calculate_portfolio_balances(&self, starting_capital: Decimal) -> Result<PortfolioBalances> {
+        // Sum in Rust rather than SQL so TEXT decimals are not coerced to REAL
+        let closed = sqlx::query("SELECT pnl FROM trades WHERE status = 'closed'")
+            .fetch_all(&self.pool)
+            .await?;
+        let realized: Decimal = closed.iter()
+            .filter_map(|row| opt_decimal(row, "pnl").ok().flatten())
+            .sum();
+
+        let open = sqlx::query(
+            r#"
+            SELECT t.position, t.quantity, t.entry_price, m.yes_price, m.status
+            FROM trades t
+            JOIN markets m ON t.market_id = m.market_id
+            WHERE t.status = 'open'
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut invested = dec!(0.0);
+        let mut pending = dec!(0.0);
+        let mut unrealized = dec!(0.0);
+        for row in &open {
+            let quantity = decimal(row, "quantity")?;
+            let entry_price = decimal(row, "entry_price")?;
+            let status: String = row.try_get("status")?;
+            if MarketStatus::parse(&status).map_or(false, |s| s.awaits_settlement()) {
+                pending += entry_price * quantity;
+            } else {
+                invested += entry_price * quantity;
+            }
+            // Pending positions stay marked so equity does not jump as a market ends
+            unrealized += (decimal(row, "yes_price")? - entry_price) * quantity;
+        }
+
+        let flows = sqlx::query("SELECT kind, amount FROM cash_flows")
+            .fetch_all(&self.pool)
+            .await?;
+        let mut net_flows = dec!(0.0);
+        for row in &flows {
+            let kind: String = row.try_get("kind")?;
+            let amount = decimal(row, "amount")?;
+            match CashFlowKind::parse(&kind) {
+                Some(CashFlowKind::Deposit) => net_flows += amount,
+                Some(CashFlowKind::Withdrawal) => net_flows -= amount,
+                None => {}
+            }
+        }
+
+        let total_capital = starting_capital + net_flows + realized;
+
+        Ok(PortfolioBalances {
+            total_capital,
+            available_capital: total_capital - invested - pending,
+            invested_capital: invested,
+            settlement_pending: pending,
+            unrealized_pnl: unrealized,
+            net_cash_flows: net_flows,
+        })
+    }
+
+    async fn calculate_venue_balances(&self) -> Result<Vec<VenueBalances>> {
+        let mut venues: HashMap<String, VenueBalances> = HashMap::new();
+        let start_of_today = Self::start_of_today();
+
+        let trades = sqlx::query(
+            r#"
+            SELECT t.venue, t.status, t.quantity, t.entry_price, t.pnl, t.exit_time, m.yes_price
+            FROM trades t
+            JOIN markets m ON t.market_id = m.market_id
+            WHERE t.status IN ('open', 'closed')
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        for row in &trades {
+            let venue: String = row.try_get("venue")?;
+            let status: String = row.try_get("status")?;
+            let balances = venues.entry(venue.clone()).or_insert_with(|| VenueBalances::empty(venue));
+
+            if status == "open" {
+                let quantity = decimal(row, "quantity")?;
+                let entry_price = decimal(row, "entry_price")?;
+                balances.invested_capital += entry_price * quantity;
+                balances.unrealized_pnl += (decimal(row, "yes_price")? - entry_price) * quantity;
+                balances.open_positions += 1;
+            } else if let Some(pnl) = opt_decimal(row, "pnl")? {
+                balances.realized_pnl += pnl;
+                let exit_time: Option<DateTime<Utc>> = row.try_get("exit_time")?;
+                if exit_time.map_or(false, |t| t >= start_of_today) {
+                    balances.realized_pnl_today += pnl;
+                }
+            }
+        }
+
+        let flows = sqlx::query("SELECT venue, kind, amount FROM cash_flows")
+            .fetch_all(&self.pool)
+            .await?;
+
+        for row in &flows {
+            let venue: String = row.try_get("venue")?;
+            let kind: String = row.try_get("kind")?;
+            let amount = decimal(row, "amount")?;
+            let balances = venues.entry(venue.clone()).or_insert_with(|| VenueBalances::empty(venue));
+            match CashFlowKind::parse(&kind) {
+                Some(CashFlowKind::Deposit) => balances.net_cash_flows += amount,
+                Some(CashFlowKind::Withdrawal) => balances.net_cash_flows -= amount,
+                None => {}
+            }
+        }
+
+        Ok(venues.into_values().collect())
+    }
+
+    async fn insert_portfolio_snapshot(&self, state: &PortfolioState) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO portfolio_state (
+                total_capital, available_capital, invested_capital, settlement_pending,
+                unrealized_pnl, realized_pnl_today, daily_drawdown,
+                max_drawdown, open_positions, trades_today, timestamp
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+            "#,
+        )
+        .bind(state.total_capital.to_string())
+        .bind(state.available_capital.to_string())
+        .bind(state.invested_capital.to_string())
+        .bind(state.settlement_pending.to_string())
+        .bind(state.unrealized_pnl.to_string())
+        .bind(state.realized_pnl_today.to_string())
+        .bind(state.daily_drawdown.to_string())
+        .bind(state.max_drawdown.to_string())
+        .bind(state.open_positions)
+        .bind(state.trades_today)
+        .bind(state.timestamp)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn latest_portfolio_snapshot(&self) -> Result<Option<PortfolioState>> {
+        let row = sqlx::query("SELECT * FROM portfolio_state ORDER BY timestamp DESC LIMIT 1")
+            .fetch_optional(&self.pool)
+            .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        Ok(Some(PortfolioState {
+            total_capital: decimal(&row, "total_capital")?,
+            available_capital: decimal(&row, "available_capital")?,
+            invested_capital: decimal(&row, "invested_capital")?,
+            settlement_pending: decimal(&row, "settlement_pending")?,
+            unrealized_pnl: decimal(&row, "unrealized_pnl")?,
+            realized_pnl_today: decimal(&row, "realized_pnl_today")?,
+            daily_drawdown: decimal(&row, "daily_drawdown")?,
+            max_drawdown: decimal(&row, "max_drawdown")?,
+            open_positions: row.try_get("open_positions")?,
+            trades_today: row.try_get("trades_today")?,
+            timestamp: row.try_get("timestamp")?,
+        }))
+    }
+
+    async fn fetch_capital_history(&self, since: DateTime<Utc>) -> Result<Vec<CapitalPoint>> {
+        let rows = sqlx::query(
+            "SELECT timestamp, total_capital FROM portfolio_state WHERE timestamp >= ?1 ORDER BY timestamp ASC",
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| Ok(CapitalPoint { timestamp: row.try_get("timestamp")?, total_capital: decimal(row, "total_capital")? }))
+            .collect()
+    }
+
+    async fn count_active_circuit_breakers(&self) -> Result<i64> {
+        let row = sqlx::query("SELECT COUNT(*) AS count FROM circuit_breakers WHERE status = 'active'")
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(row.try_get("count")?)
+    }
+
+    async fn insert_circuit_breaker(&self, reason: &str, metadata: serde_json::Value) -> Result<()> {
+        sqlx::query("INSERT INTO circuit_breakers (reason, triggered_at, metadata) VALUES (?1, ?2, ?3)")
+            .bind(reason)
+            .bind(Utc::now())
+            .bind(envelope(MetadataKind::CircuitBreaker, &metadata).to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SeasonRepo for SqliteStorage {
+    async fn fetch_team_ratings(&self, sport: Sport) -> Result<Vec<TeamRating>> {
+        let rows = sqlx::query(
+            "SELECT team, conference, division, rating, latitude, longitude, elevation_m, indoor FROM team_ratings WHERE sport = ?1",
+        )
+        .bind(sport.as_str())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(TeamRating {
+                    sport,
+                    team: row.try_get("team")?,
+                    conference: row.try_get("conference")?,
+                    division: row.try_get("division")?,
+                    rating: row.try_get("rating")?,
+                    latitude: row.try_get("latitude")?,
+                    longitude: row.try_get("longitude")?,
+                    elevation_m: row.try_get("elevation_m")?,
+                    indoor: row.try_get("indoor")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn fetch_season_games(&self, sport: Sport) -> Result<Vec<Game>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT game_id, season, home_team, away_team, start_time, home_score, away_score
+            FROM games
+            WHERE sport = ?1
+                AND season = (SELECT MAX(season) FROM games WHERE sport = ?1)
+            ORDER BY start_time
+            "#,
+        )
+        .bind(sport.as_str())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(Game {
+                    game_id: row.try_get("game_id")?,
+                    sport,
+                    season: row.try_get("season")?,
+                    home_team: row.try_get("home_team")?,
+                    away_team: row.try_get("away_team")?,
+                    start_time: row.try_get("start_time")?,
+                    home_score: row.try_get("home_score")?,
+                    away_score: row.try_get("away_score")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn fetch_game_history(&self, sport: Sport) -> Result<Vec<Game>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT game_id, season, home_team, away_team, start_time, home_score, away_score
+            FROM games
+            WHERE sport = ?1
+                AND home_score IS NOT NULL
+                AND away_score IS NOT NULL
+            ORDER BY start_time
+            "#,
+        )
+        .bind(sport.as_str())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(Game {
+                    game_id: row.try_get("game_id")?,
+                    sport,
+                    season: row.try_get("season")?,
+                    home_team: row.try_get("home_team")?,
+                    away_team: row.try_get("away_team")?,
+                    start_time: row.try_get("start_time")?,
+                    home_score: row.try_get("home_score")?,
+                    away_score: row.try_get("away_score")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn upsert_home_advantage(&self, estimate: &HomeAdvantageEstimate) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO home_advantage_estimates (
+                sport, team, league, games, raw, structural, estimate, estimated_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            ON CONFLICT (sport, team) DO UPDATE SET
+                league = excluded.league,
+                games = excluded.games,
+                raw = excluded.raw,
+                structural = excluded.structural,
+                estimate = excluded.estimate,
+                estimated_at = excluded.estimated_at
+            "#,
+        )
+        .bind(estimate.sport.as_str())
+        .bind(&estimate.team)
+        .bind(estimate.league)
+        .bind(estimate.games)
+        .bind(estimate.raw)
+        .bind(estimate.structural)
+        .bind(estimate.estimate)
+        .bind(estimate.estimated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_home_advantage(&self, sport: Option<Sport>) -> Result<Vec<HomeAdvantageEstimate>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT sport, team, league, games, raw, structural, estimate, estimated_at
+            FROM home_advantage_estimates
+            WHERE ?1 IS NULL OR sport = ?1
+            ORDER BY sport, team
+            "#,
+        )
+        .bind(sport.map(|s| s.as_str().to_string()))
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut estimates = Vec::new();
+        for row in rows {
+            let sport: String = row.try_get("sport")?;
+            let Some(sport) = Sport::parse(&sport) else {
+                continue;
+            };
+            estimates.push(HomeAdvantageEstimate {
+                sport,
+                team: row.try_get("team")?,
+                league: row.try_get("league")?,
+                games: row.try_get("games")?,
+                raw: row.try_get("raw")?,
+                structural: row.try_get("structural")?,
+                estimate: row.try_get("estimate")?,
+                estimated_at: row.try_get("estimated_at")?,
+            });
+        }
+        Ok(estimates)
+    }
+
+    async fn fetch_injury_adjustments(&self, sport: Sport) -> Result<Vec<InjuryAdjustment>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT team, player, rating_delta, out_until
+            FROM injury_adjustments
+            WHERE sport = ?1
+                AND (out_until IS NULL OR out_until > ?2)
+            "#,
+        )
+        .bind(sport.as_str())
+        .bind(Utc::now())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(InjuryAdjustment {
+                    sport,
+                    team: row.try_get("team")?,
+                    player: row.try_get("player")?,
+                    rating_delta: row.try_get("rating_delta")?,
+                    out_until: row.try_get("out_until")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn upsert_official_assignment(&self, assignment: &OfficialAssignment) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO official_assignments (game_id, role, official, updated_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT (game_id, role) DO UPDATE SET
+                official = excluded.official,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(&assignment.game_id)
+        .bind(&assignment.role)
+        .bind(&assignment.official)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_official_tendencies(&self, game_id: &str) -> Result<Vec<OfficialTendency>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                a.official,
+                a.role,
+                COALESCE(t.games, 0) AS games,
+                COALESCE(t.total_delta, 0.0) AS total_delta
+            FROM official_assignments a
+            JOIN games g ON g.game_id = a.game_id
+            LEFT JOIN v_official_tendencies t
+                ON t.sport = g.sport AND t.official = a.official AND t.role = a.role
+            WHERE a.game_id = ?1
+            "#,
+        )
+        .bind(game_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(OfficialTendency {
+                    official: row.try_get("official")?,
+                    role: row.try_get("role")?,
+                    games: row.try_get("games")?,
+                    total_delta: row.try_get("total_delta")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn upsert_probable_pitcher(&self, pitcher: &ProbablePitcher) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO probable_pitchers (game_id, team, pitcher, era, innings_pitched, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            ON CONFLICT (game_id, team) DO UPDATE SET
+                pitcher = excluded.pitcher,
+                era = excluded.era,
+                innings_pitched = excluded.innings_pitched,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(&pitcher.game_id)
+        .bind(&pitcher.team)
+        .bind(&pitcher.pitcher)
+        .bind(pitcher.era)
+        .bind(pitcher.innings_pitched)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_probable_pitchers(&self, game_id: &str) -> Result<Vec<ProbablePitcher>> {
+        let rows = sqlx::query(
+            "SELECT game_id, team, pitcher, era, innings_pitched FROM probable_pitchers WHERE game_id = ?1",
+        )
+        .bind(game_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(ProbablePitcher {
+                    game_id: row.try_get("game_id")?,
+                    team: row.try_get("team")?,
+                    pitcher: row.try_get("pitcher")?,
+                    era: row.try_get("era")?,
+                    innings_pitched: row.try_get("innings_pitched")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn upsert_bullpen_usage(&self, usage: &BullpenUsage) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO bullpen_usage (team, game_time, innings)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT (team, game_time) DO UPDATE SET innings = excluded.innings
+            "#,
+        )
+        .bind(&usage.team)
+        .bind(usage.game_time)
+        .bind(usage.innings)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_bullpen_usage(&self, since: DateTime<Utc>) -> Result<Vec<BullpenUsage>> {
+        let rows = sqlx::query("SELECT team, game_time, innings FROM bullpen_usage WHERE game_time >= ?1")
+            .bind(since)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(BullpenUsage {
+                    team: row.try_get("team")?,
+                    game_time: row.try_get("game_time")?,
+                    innings: row.try_get("innings")?,
+                })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl FeatureRepo for SqliteStorage {
+    async fn upsert_game_feature(&self, feature: &GameFeature) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO game_features (game_id, team, name, value, computed_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT (game_id, team, name) DO UPDATE SET
+                value = excluded.value,
+                computed_at = excluded.computed_at
+            "#,
+        )
+        .bind(&feature.game_id)
+        .bind(&feature.team)
+        .bind(&feature.name)
+        .bind(feature.value)
+        .bind(feature.computed_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DecisionRepo for SqliteStorage {
+    async fn insert_decision(&self, decision: &Decision) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO decision_log (decision_id, kind, subject, summary, detail, decided_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            "#,
+        )
+        .bind(decision.decision_id.to_string())
+        .bind(&decision.kind)
+        .bind(&decision.subject)
+        .bind(&decision.summary)
+        .bind(decision.detail.to_string())
+        .bind(decision.decided_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_decisions(&self, since: DateTime<Utc>) -> Result<Vec<Decision>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT decision_id, kind, subject, summary, detail, decided_at
+            FROM decision_log
+            WHERE decided_at >= ?1
+            ORDER BY decided_at DESC
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let detail: String = row.try_get("detail")?;
+                Ok(Decision {
+                    decision_id: uuid(row, "decision_id")?,
+                    kind: row.try_get("kind")?,
+                    subject: row.try_get("subject")?,
+                    summary: row.try_get("summary")?,
+                    detail: serde_json::from_str(&detail)?,
+                    decided_at: row.try_get("decided_at")?,
+                })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl CredentialRepo for SqliteStorage {
+    async fn insert_credential(&self, credential: &SealedCredential) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO credentials (provider, name, version, nonce, ciphertext, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            "#,
+        )
+        .bind(&credential.provider)
+        .bind(&credential.name)
+        .bind(credential.version)
+        .bind(&credential.nonce)
+        .bind(&credential.ciphertext)
+        .bind(credential.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fetch_credentials(&self) -> Result<Vec<SealedCredential>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT provider, name, version, nonce, ciphertext, created_at
+            FROM credentials c
+            WHERE version = (
+                SELECT MAX(version) FROM credentials latest
+                WHERE latest.provider = c.provider AND latest.name = c.name
+            )
+            ORDER BY provider, name
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(SealedCredential {
+                    provider: row.try_get("provider")?,
+                    name: row.try_get("name")?,
+                    version: row.try_get("version")?,
+                    nonce: row.try_get("nonce")?,
+                    ciphertext: row.try_get("ciphertext")?,
+                    created_at: row.try_get("created_at")?,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{market_fixture, signal_fixture, trade_fixture};
+    use crate::types::{ExecutionCostKind, POLYMARKET_VENUE};
+
+    #[tokio::test]
+    async fn fetches_home_advantage_of_one_sport_or_all() {
+        let storage = SqliteStorage::connect("sqlite::memory:", 1).await.unwrap();
+        let estimate = |sport, team: &str| HomeAdvantageEstimate {
+            sport,
+            team: team.to_string(),
+            league: 1.5,
+            games: 16,
+            raw: None,
+            structural: 1.5,
+            estimate: 1.5,
+            estimated_at: Utc::now(),
+        };
+        storage.upsert_home_advantage(&estimate(Sport::NFL, "Broncos")).await.unwrap();
+        storage.upsert_home_advantage(&estimate(Sport::NBA, "Nuggets")).await.unwrap();
+        // A later estimate replaces the venue's last
+        storage.upsert_home_advantage(&HomeAdvantageEstimate { raw: Some(3.1), ..estimate(Sport::NFL, "Broncos") }).await.unwrap();
+
+        assert_eq!(storage.fetch_home_advantage(None).await.unwrap().len(), 2);
+        let nfl = storage.fetch_home_advantage(Some(Sport::NFL)).await.unwrap();
+        assert_eq!((nfl.len(), nfl[0].team.as_str(), nfl[0].raw), (1, "Broncos", Some(3.1)));
+    }
+
+    #[tokio::test]
+    async fn pending_positions_are_locked_but_still_marked() {
+        let storage = SqliteStorage::connect("sqlite::memory:", 1).await.unwrap();
+        let market = market_fixture("0xdisputed", dec!(0.55));
+        storage.upsert_market(&market).await.unwrap();
+        storage.insert_trade(&trade_fixture("0xdisputed", dec!(0.40), dec!(1000))).await.unwrap();
+        storage.upsert_market(&Market { status: MarketStatus::Disputed, ..market }).await.unwrap();
+
+        let balances = storage.calculate_portfolio_balances(dec!(50000)).await.unwrap();
+        assert_eq!((balances.invested_capital, balances.settlement_pending), (dec!(0), dec!(400)));
+        assert_eq!((balances.available_capital, balances.unrealized_pnl), (dec!(49600), dec!(150)));
+    }
+
+    #[tokio::test]
+    async fn renews_only_its_own_pending_claims() {
+        let storage = SqliteStorage::connect("sqlite::memory:", 1).await.unwrap();
+        let market = market_fixture("0xmarket", dec!(0.40));
+        storage.upsert_market(&market).await.unwrap();
+        let signal = signal_fixture(&market, dec!(0.50));
+        storage.insert_signal(&signal).await.unwrap();
+
+        let since = Utc::now() - chrono::Duration::minutes(5);
+        let until = Utc::now() + chrono::Duration::minutes(5);
+        assert_eq!(storage.claim_pending_signals(since, 10, "engine-a", until).await.unwrap().len(), 1);
+        assert!(storage.renew_signal_claim(signal.signal_id, "engine-a", until).await.unwrap());
+        assert!(!storage.renew_signal_claim(signal.signal_id, "engine-b", until).await.unwrap());
+
+        // Once executed there is nothing left to hold
+        storage.mark_signal_executed(signal.signal_id, None).await.unwrap();
+        assert!(!storage.renew_signal_claim(signal.signal_id, "engine-a", until).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn sums_entry_exit_and_failed_transaction_costs_of_today() {
+        let storage = SqliteStorage::connect("sqlite::memory:", 1).await.unwrap();
+        let market = market_fixture("0xmarket", dec!(0.45));
+        storage.upsert_market(&market).await.unwrap();
+        let trade = |entry_time| Trade {
+            trade_id: Uuid::new_v4(),
+            market_id: market.market_id.clone(),
+            strategy: Strategy::ClvArbitrage,
+            position: Position::Yes,
+            quantity: dec!(100),
+            entry_price: dec!(0.45),
+            exit_price: None,
+            entry_time,
+            exit_time: None,
+            gas_cost: Some(dec!(0.02)),
+            fees: Some(Usdc::new(dec!(0.90))),
+            slippage: None,
+            pnl: None,
+            pnl_percent: None,
+            max_adverse_excursion: None,
+            max_favorable_excursion: None,
+            status: TradeStatus::Open,
+            tx_hash_entry: None,
+            tx_hash_exit: None,
+            venue: POLYMARKET_VENUE.to_string(),
+            origin: TradeOrigin::Bot,
+        };
+        let cost = |kind, amount, incurred_at| ExecutionCost {
+            cost_id: Uuid::new_v4(),
+            kind,
+            amount,
+            trade_id: None,
+            tx_hash: Some("0xcost".to_string()),
+            incurred_at,
+        };
+        let yesterday = Utc::now() - chrono::Duration::days(1);
+        storage.insert_trade(&trade(Utc::now())).await.unwrap();
+        storage.insert_trade(&trade(yesterday)).await.unwrap();
+        storage.insert_execution_cost(&cost(ExecutionCostKind::Exit, dec!(0.75), Utc::now())).await.unwrap();
+        storage.insert_execution_cost(&cost(ExecutionCostKind::FailedEntry, dec!(0.02), Utc::now())).await.unwrap();
+        storage.insert_execution_cost(&cost(ExecutionCostKind::Redemption, dec!(0.03), Utc::now())).await.unwrap();
+        storage.insert_execution_cost(&cost(ExecutionCostKind::Redemption, dec!(5), yesterday)).await.unwrap();
+
+        assert_eq!(storage.execution_costs_today().await.unwrap(), dec!(1.72));
+    }
+
+    #[tokio::test]
+    async fn corrects_only_open_trade_entries() {
+        // One connection, since each in-memory connection is its own database
+        let storage = SqliteStorage::connect("sqlite::memory:", 1).await.unwrap();
+        let market = market_fixture("0xmarket", dec!(0.45));
+        storage.upsert_market(&market).await.unwrap();
+        let trade = Trade {
+            trade_id: Uuid::new_v4(),
+            market_id: market.market_id.clone(),
+            strategy: Strategy::ClvArbitrage,
+            position: Position::Yes,
+            quantity: dec!(1000),
+            entry_price: dec!(0.45),
+            exit_price: None,
+            entry_time: Utc::now(),
+            exit_time: None,
+            gas_cost: None,
+            fees: None,
+            slippage: Some(dec!(0.01)),
+            pnl: None,
+            pnl_percent: None,
+            max_adverse_excursion: None,
+            max_favorable_excursion: None,
+            status: TradeStatus::Open,
+            tx_hash_entry: Some("0xentry".to_string()),
+            tx_hash_exit: None,
+            venue: POLYMARKET_VENUE.to_string(),
+            origin: TradeOrigin::Bot,
+        };
+        storage.insert_trade(&trade).await.unwrap();
+
+        storage.correct_trade_entry(trade.trade_id, dec!(0.46), dec!(980), Some(dec!(0.02))).await.unwrap();
+        let corrected = storage.fetch_trade_by_entry_tx("0xentry").await.unwrap().unwrap();
+        assert_eq!((corrected.entry_price, corrected.quantity, corrected.slippage), (dec!(0.46), dec!(980), Some(dec!(0.02))));
+
+        // A closed trade keeps the entry its PnL was computed from
+        storage.close_trade(trade.trade_id, dec!(0.55), Utc::now(), Usdc::new(dec!(88.2)), "0xexit").await.unwrap();
+        storage.correct_trade_entry(trade.trade_id, dec!(0.50), dec!(900), None).await.unwrap();
+        let closed = storage.fetch_trade(trade.trade_id).await.unwrap().unwrap();
+        assert_eq!((closed.entry_price, closed.quantity), (dec!(0.46), dec!(980)));
+    }
+}
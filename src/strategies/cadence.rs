@@ -0,0 +1,84 @@
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+use crate::config::CadenceConfig;
+use crate::types::{Market, Sport};
+
+struct Tier {
+    within: chrono::Duration,
+    interval: Duration,
+    sports: Option<Vec<Sport>>,
+}
+
+/// How often a market is re-evaluated, tighter as its event approaches
+pub struct Cadence {
+    tiers: Vec<Tier>,
+    default_interval: Duration,
+}
+
+impl Cadence {
+    pub fn from_config(config: &CadenceConfig) -> Result<Self> {
+        if config.default_interval_secs == 0 || config.tiers.iter().any(|t| t.interval_secs == 0) {
+            bail!("cadence intervals must be at least one second");
+        }
+
+        Ok(Self {
+            tiers: config.tiers.iter()
+                .map(|t| Tier {
+                    within: chrono::Duration::minutes(t.within_mins),
+                    interval: Duration::from_secs(t.interval_secs),
+                    sports: t.sports.clone(),
+                })
+                .collect(),
+            default_interval: Duration::from_secs(config.default_interval_secs),
+        })
+    }
+
+    /// Interval for a market of `sport` starting `until_start` from now
+    pub fn interval(&self, sport: Sport, until_start: chrono::Duration) -> Duration {
+        self.tiers.iter()
+            .filter(|t| t.sports.as_ref().map_or(true, |sports| sports.contains(&sport)))
+            .find(|t| until_start <= t.within)
+            .map_or(self.default_interval, |t| t.interval)
+    }
+
+    pub fn interval_for(&self, market: &Market, now: DateTime<Utc>) -> Duration {
+        self.interval(market.sport, market.event_time - now)
+    }
+
+    /// How often the generator has to wake to honour the shortest interval
+    pub fn tick(&self) -> Duration {
+        self.tiers.iter()
+            .map(|t| t.interval)
+            .fold(self.default_interval, Duration::min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CadenceTierConfig;
+
+    #[test]
+    fn tightens_towards_kickoff_with_sport_specific_tiers_first() {
+        let tier = |within_mins, interval_secs, sports| CadenceTierConfig { within_mins, interval_secs, sports };
+        let cadence = Cadence::from_config(&CadenceConfig {
+            tiers: vec![
+                tier(180, 5, Some(vec![Sport::MLB])),
+                tier(60, 15, None),
+                tier(24 * 60, 60, None),
+            ],
+            default_interval_secs: 600,
+        })
+        .unwrap();
+        let hours = chrono::Duration::hours;
+
+        assert_eq!(cadence.interval(Sport::NFL, chrono::Duration::minutes(30)), Duration::from_secs(15));
+        assert_eq!(cadence.interval(Sport::NFL, hours(-1)), Duration::from_secs(15));
+        assert_eq!(cadence.interval(Sport::NFL, hours(2)), Duration::from_secs(60));
+        assert_eq!(cadence.interval(Sport::NFL, hours(72)), Duration::from_secs(600));
+        assert_eq!(cadence.interval(Sport::MLB, hours(2)), Duration::from_secs(5));
+        assert_eq!(cadence.tick(), Duration::from_secs(5));
+    }
+}
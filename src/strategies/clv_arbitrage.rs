@@ -1,13 +1,16 @@
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
-use sqlx::PgPool;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Arc;
 use uuid::Uuid;
 use tracing::{info, debug};
 
-use crate::types::{Market, Signal, SignalType, Strategy as StrategyEnum, Position, BookmakerOdds};
+use crate::config::ClvArbConfig;
+use crate::numeric;
+use crate::storage::Storage;
+use crate::types::{Market, Signal, SignalType, Strategy as StrategyEnum, BookmakerOdds, LargeTrade, Position, Probability};
 use super::Strategy;
 
 /// Strategy 1: Closing Line Value (CLV) Arbitrage
@@ -21,85 +24,53 @@ use super::Strategy;
 /// 4. Execute with confidence proportional to divergence
 /// 5. Exit position as markets converge (or hold to resolution)
 pub struct ClvArbitrageStrategy {
-    db_pool: PgPool,
+    storage: Arc<dyn Storage>,
     min_divergence_pct: Decimal,
     max_hold_hours: i64,
+    freshness: OddsFreshness,
+    /// Large trades this recent are not faded; zero disables the check
+    large_trade_lookback: Duration,
 }
 
 impl ClvArbitrageStrategy {
     pub fn new(
-        db_pool: PgPool,
+        storage: Arc<dyn Storage>,
         min_divergence_pct: f64,
         max_hold_hours: u64,
-    ) -> Self {
-        Self {
-            db_pool,
-            min_divergence_pct: Decimal::from_f64_retain(min_divergence_pct).unwrap_or(dec!(3.0)),
+        freshness: OddsFreshness,
+        large_trade_lookback: Duration,
+    ) -> Result<Self> {
+        Ok(Self {
+            storage,
+            min_divergence_pct: numeric::to_decimal(min_divergence_pct)
+                .context("clv_arb.min_divergence_pct")?,
             max_hold_hours: max_hold_hours as i64,
-        }
+            freshness,
+            large_trade_lookback,
+        })
     }
 
-    /// Fetch latest bookmaker odds for a market
-    async fn fetch_bookmaker_odds(&self, market_id: &str) -> Result<Vec<BookmakerOdds>> {
-        let odds = sqlx::query_as!(
-            BookmakerOddsRow,
-            r#"
-            SELECT DISTINCT ON (bookmaker)
-                market_id,
-                bookmaker,
-                yes_odds,
-                no_odds,
-                yes_implied_prob,
-                no_implied_prob,
-                timestamp
-            FROM bookmaker_odds
-            WHERE market_id = $1
-                AND timestamp > NOW() - INTERVAL '1 hour'
-            ORDER BY bookmaker, timestamp DESC
-            "#,
-            market_id
-        )
-        .fetch_all(&self.db_pool)
-        .await?;
-
-        Ok(odds.into_iter().map(|row| row.into()).collect())
-    }
-
-    /// Calculate the fair value based on sharp bookmaker odds
-    fn calculate_fair_value(&self, bookmaker_odds: &[BookmakerOdds]) -> Option<(Decimal, Decimal)> {
-        if bookmaker_odds.is_empty() {
+    /// A recent large trade backing the other side of `position`, if any
+    ///
+    /// Large informed flow tends to lead the sharp books' next move, so a
+    /// divergence against it is more likely a stale line than an edge.
+    async fn faded_large_trade(&self, market_id: &str, position: Position, now: DateTime<Utc>) -> Option<LargeTrade> {
+        if self.large_trade_lookback <= Duration::zero() {
             return None;
         }
 
-        // Weight Pinnacle heavily as they're the sharpest
-        let mut yes_prob_sum = dec!(0.0);
-        let mut no_prob_sum = dec!(0.0);
-        let mut weight_sum = dec!(0.0);
-
-        for odds in bookmaker_odds {
-            let weight = match odds.bookmaker {
-                crate::types::Bookmaker::Pinnacle => dec!(2.0), // 2x weight for Pinnacle
-                crate::types::Bookmaker::Betfair => dec!(1.5),
-                _ => dec!(1.0),
-            };
-
-            yes_prob_sum += odds.yes_implied_prob * weight;
-            no_prob_sum += odds.no_implied_prob * weight;
-            weight_sum += weight;
-        }
-
-        if weight_sum > dec!(0.0) {
-            let fair_yes = yes_prob_sum / weight_sum;
-            let fair_no = no_prob_sum / weight_sum;
-            
-            // Normalize to sum to 1.0 (remove vig)
-            let total = fair_yes + fair_no;
-            if total > dec!(0.0) {
-                return Some((fair_yes / total, fair_no / total));
+        match self.storage.fetch_large_trades(now - self.large_trade_lookback, Some(market_id)).await {
+            Ok(trades) => trades.into_iter().find(|t| t.backs() != position),
+            Err(e) => {
+                debug!("Failed to fetch large trades for {}: {}", market_id, e);
+                None
             }
         }
+    }
 
-        None
+    /// Fetch latest bookmaker odds for a market
+    async fn fetch_bookmaker_odds(&self, market_id: &str) -> Result<Vec<BookmakerOdds>> {
+        self.storage.fetch_bookmaker_odds(market_id).await
     }
 
     /// Calculate confidence based on divergence size and data quality
@@ -128,11 +99,12 @@ impl ClvArbitrageStrategy {
     fn determine_signal_type(
         &self,
         market: &Market,
-        fair_yes: Decimal,
-        fair_no: Decimal,
-    ) -> Option<(SignalType, Decimal, Decimal)> {
-        let yes_divergence = fair_yes - market.yes_price;
-        let no_divergence = fair_no - market.no_price;
+        fair_yes: Probability,
+        fair_no: Probability,
+    ) -> Option<(SignalType, Decimal, Probability)> {
+        // Edge is measured against the ask: a mid-price edge inside the spread is not tradable
+        let yes_divergence = fair_yes.edge_over(market.entry_price(Position::Yes));
+        let no_divergence = fair_no.edge_over(market.entry_price(Position::No));
 
         // Check if YES is underpriced (market price < fair value)
         if yes_divergence > self.min_divergence_pct / dec!(100.0) {
@@ -148,6 +120,91 @@ impl ClvArbitrageStrategy {
     }
 }
 
+/// How much a bookmaker line counts given its age
+///
+/// Stale lines are a classic source of phantom edges: a book that has not
+/// moved yet looks like a divergence from a Polymarket price that already
+/// has. Lines count fully up to `fresh`, fade linearly to nothing at
+/// `max_age`, and are ignored beyond it.
+#[derive(Debug, Clone, Copy)]
+pub struct OddsFreshness {
+    fresh: Duration,
+    max_age: Duration,
+}
+
+impl OddsFreshness {
+    pub fn from_config(config: &ClvArbConfig) -> Result<Self> {
+        if config.fresh_odds_secs > config.max_odds_age_secs {
+            return Err(anyhow!("clv_arb.fresh_odds_secs must not exceed clv_arb.max_odds_age_secs"));
+        }
+
+        Ok(Self {
+            fresh: Duration::seconds(config.fresh_odds_secs as i64),
+            max_age: Duration::seconds(config.max_odds_age_secs as i64),
+        })
+    }
+
+    /// Weight in [0, 1] of `odds` at `now`; zero means skip the line
+    pub fn weight(&self, odds: &BookmakerOdds, now: DateTime<Utc>) -> Decimal {
+        let age = odds.age(now);
+        if age <= self.fresh {
+            return Decimal::ONE;
+        }
+        if age >= self.max_age {
+            return Decimal::ZERO;
+        }
+
+        let faded = Decimal::from((age - self.fresh).num_milliseconds())
+            / Decimal::from((self.max_age - self.fresh).num_milliseconds());
+        Decimal::ONE - faded
+    }
+}
+
+/// Vig-free (YES, NO) fair value from sharp bookmaker odds, Pinnacle and
+/// Betfair weighted heaviest and older lines discounted by `freshness`
+pub fn sharp_fair_value(
+    bookmaker_odds: &[BookmakerOdds],
+    freshness: &OddsFreshness,
+    now: DateTime<Utc>,
+) -> Option<(Probability, Probability)> {
+    if bookmaker_odds.is_empty() {
+        return None;
+    }
+
+    // Weight Pinnacle heavily as they're the sharpest
+    let mut yes_prob_sum = dec!(0.0);
+    let mut no_prob_sum = dec!(0.0);
+    let mut weight_sum = dec!(0.0);
+
+    for odds in bookmaker_odds {
+        let weight = match odds.bookmaker {
+            crate::types::Bookmaker::Pinnacle => dec!(2.0), // 2x weight for Pinnacle
+            crate::types::Bookmaker::Betfair => dec!(1.5),
+            _ => dec!(1.0),
+        } * freshness.weight(odds, now);
+
+        yes_prob_sum += odds.yes_implied_prob.value() * weight;
+        no_prob_sum += odds.no_implied_prob.value() * weight;
+        weight_sum += weight;
+    }
+
+    if weight_sum > dec!(0.0) {
+        let fair_yes = yes_prob_sum / weight_sum;
+        let fair_no = no_prob_sum / weight_sum;
+        
+        // Normalize to sum to 1.0 (remove vig)
+        let total = fair_yes + fair_no;
+        if total > dec!(0.0) {
+            return Some((
+                Probability::clamped(fair_yes / total),
+                Probability::clamped(fair_no / total),
+            ));
+        }
+    }
+
+    None
+}
+
 #[async_trait]
 impl Strategy for ClvArbitrageStrategy {
     async fn generate_signals(&self, markets: &[Market]) -> Result<Vec<Signal>> {
@@ -155,7 +212,7 @@ impl Strategy for ClvArbitrageStrategy {
 
         for market in markets {
             // Only analyze active markets with sufficient liquidity
-            if market.status != crate::types::MarketStatus::Active {
+            if !market.status.allows_entry() {
                 continue;
             }
 
@@ -168,12 +225,22 @@ impl Strategy for ClvArbitrageStrategy {
                 }
             };
 
+            // Stale lines are phantom edges: drop them before counting books
+            let now = Utc::now();
+            let fetched = bookmaker_odds.len();
+            let bookmaker_odds: Vec<BookmakerOdds> = bookmaker_odds.into_iter()
+                .filter(|odds| self.freshness.weight(odds, now) > Decimal::ZERO)
+                .collect();
+            if bookmaker_odds.len() < fetched {
+                debug!("Skipped {} stale bookmaker lines for {}", fetched - bookmaker_odds.len(), market.market_id);
+            }
+
             if bookmaker_odds.is_empty() {
                 continue;
             }
 
             // Calculate fair value from bookmaker odds
-            let (fair_yes, fair_no) = match self.calculate_fair_value(&bookmaker_odds) {
+            let (fair_yes, fair_no) = match sharp_fair_value(&bookmaker_odds, &self.freshness, now) {
                 Some(values) => values,
                 None => continue,
             };
@@ -182,10 +249,23 @@ impl Strategy for ClvArbitrageStrategy {
             if let Some((signal_type, edge_pct, fair_value)) = 
                 self.determine_signal_type(market, fair_yes, fair_no)
             {
+                if let Some(trade) = self.faded_large_trade(&market.market_id, signal_type.to_position(), now).await {
+                    info!(
+                        "🐋 Not fading large trade on {}: {} {} ${} at {}",
+                        market.event_name,
+                        trade.side.as_str(),
+                        trade.position.as_str(),
+                        trade.notional.round_dp(0),
+                        trade.traded_at,
+                    );
+                    continue;
+                }
+
                 let confidence = self.calculate_confidence(edge_pct, bookmaker_odds.len());
                 
                 // Calculate recommended position size (will be adjusted by risk management)
                 let recommended_size = dec!(1000.0) * confidence; // Base size * confidence
+                let quote = market.quote(signal_type.to_position());
 
                 let signal = Signal {
                     signal_id: Uuid::new_v4(),
@@ -195,7 +275,9 @@ impl Strategy for ClvArbitrageStrategy {
                     confidence,
                     edge_size: edge_pct / dec!(100.0),
                     recommended_size,
-                    current_price: market.implied_probability(signal_type.to_position()),
+                    current_price: quote.ask,
+                    executable_size: quote.ask_size,
+                    quoted_edge: fair_value.edge_over(quote.mid()),
                     fair_value,
                     generated_at: Utc::now(),
                     metadata: serde_json::json!({
@@ -204,6 +286,8 @@ impl Strategy for ClvArbitrageStrategy {
                         "fair_no": fair_no,
                         "market_yes": market.yes_price,
                         "market_no": market.no_price,
+                        "yes_spread": market.yes_quote.spread(),
+                        "no_spread": market.no_quote.spread(),
                     }),
                 };
 
@@ -234,37 +318,117 @@ impl Strategy for ClvArbitrageStrategy {
     }
 }
 
-// Helper struct for database queries
-#[derive(Debug)]
-struct BookmakerOddsRow {
-    market_id: String,
-    bookmaker: String,
-    yes_odds: Decimal,
-    no_odds: Decimal,
-    yes_implied_prob: Decimal,
-    no_implied_prob: Decimal,
-    timestamp: chrono::DateTime<Utc>,
-}
+#[cfg(test)]
+mod tests {
+    use super::{sharp_fair_value, ClvArbitrageStrategy, OddsFreshness};
+    use crate::storage::{MarketRepo, MemoryStorage};
+    use crate::strategies::Strategy as _;
+    use crate::test_support::{market_fixture, odds_fixture};
+    use crate::types::{Bookmaker, BookmakerOdds, Market, Price, Probability, Quote};
+    use chrono::{Duration, Utc};
+    use proptest::prelude::*;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use std::sync::Arc;
 
-impl From<BookmakerOddsRow> for BookmakerOdds {
-    fn from(row: BookmakerOddsRow) -> Self {
-        use crate::types::Bookmaker;
-        
-        let bookmaker = match row.bookmaker.as_str() {
-            "pinnacle" => Bookmaker::Pinnacle,
-            "betfair" => Bookmaker::Betfair,
-            "draftkings" => Bookmaker::DraftKings,
-            _ => Bookmaker::Pinnacle,
+    fn freshness() -> OddsFreshness {
+        OddsFreshness { fresh: Duration::seconds(60), max_age: Duration::seconds(300) }
+    }
+
+    fn strategy() -> ClvArbitrageStrategy {
+        ClvArbitrageStrategy::new(Arc::new(MemoryStorage::new()), 3.0, 24, freshness(), Duration::zero()).unwrap()
+    }
+
+    #[test]
+    fn stale_lines_fade_then_drop_out() {
+        let now = Utc::now();
+        let aged = |secs: i64| BookmakerOdds {
+            source_timestamp: Some(now - Duration::seconds(secs)),
+            ..odds_fixture("0xmarket", Bookmaker::Pinnacle, dec!(0.5))
         };
 
-        BookmakerOdds {
-            bookmaker,
-            market_id: row.market_id,
-            yes_odds: row.yes_odds,
-            no_odds: row.no_odds,
-            yes_implied_prob: row.yes_implied_prob,
-            no_implied_prob: row.no_implied_prob,
-            timestamp: row.timestamp,
+        assert_eq!(freshness().weight(&aged(30), now), dec!(1));
+        assert_eq!(freshness().weight(&aged(180), now), dec!(0.5));
+        assert_eq!(freshness().weight(&aged(300), now), dec!(0));
+
+        // A stale book that has not moved yet does not drag the line
+        let fair = sharp_fair_value(
+            &[
+                BookmakerOdds { source_timestamp: Some(now), ..odds_fixture("0xmarket", Bookmaker::Pinnacle, dec!(0.60)) },
+                BookmakerOdds { source_timestamp: Some(now - Duration::seconds(600)), ..odds_fixture("0xmarket", Bookmaker::Betfair, dec!(0.40)) },
+            ],
+            &freshness(),
+            now,
+        ).unwrap();
+        assert_eq!(fair.0.value(), dec!(0.60));
+    }
+
+    /// A market quoted `bid`/`ask` on YES with 800 shares bid and 300 offered
+    fn quoted_market(bid: Decimal, ask: Decimal) -> Market {
+        let market = market_fixture("0xquoted", (bid + ask) / dec!(2));
+        let yes_quote = Quote::from_book(Some(bid), Some(ask), Price::new(bid).unwrap())
+            .with_depth(Some(dec!(800)), Some(dec!(300)));
+        Market { yes_quote, no_quote: yes_quote.complement(), ..market }
+    }
+
+    #[tokio::test]
+    async fn signals_carry_the_ask_its_size_and_the_edge_at_the_mid() {
+        let storage = Arc::new(MemoryStorage::new());
+        storage.insert_bookmaker_odds(&odds_fixture("0xquoted", Bookmaker::Pinnacle, dec!(0.55))).await.unwrap();
+        let strategy = ClvArbitrageStrategy::new(storage, 3.0, 24, freshness(), Duration::zero()).unwrap();
+
+        let signals = strategy.generate_signals(&[quoted_market(dec!(0.44), dec!(0.50))]).await.unwrap();
+        assert_eq!(signals.len(), 1);
+        let signal = &signals[0];
+        assert_eq!((signal.current_price.value(), signal.executable_size), (dec!(0.50), Some(dec!(300))));
+        assert_eq!((signal.edge_size, signal.quoted_edge), (dec!(0.05), dec!(0.08)));
+
+        // Six points at the mid, but one at the ask: the spread, not an edge
+        assert!(strategy.generate_signals(&[quoted_market(dec!(0.44), dec!(0.54))]).await.unwrap().is_empty());
+    }
+
+    fn bookmaker_odds() -> impl Strategy<Value = BookmakerOdds> {
+        let bookmaker = prop_oneof![
+            Just(Bookmaker::Pinnacle),
+            Just(Bookmaker::Betfair),
+            Just(Bookmaker::DraftKings),
+            Just(Bookmaker::FanDuel),
+        ];
+
+        (bookmaker, 1i64..10_000, 1i64..10_000).prop_map(|(bookmaker, yes_bp, no_bp)| {
+            let yes_implied_prob = Probability::new(Decimal::new(yes_bp, 4)).unwrap();
+            let no_implied_prob = Probability::new(Decimal::new(no_bp, 4)).unwrap();
+            BookmakerOdds {
+                bookmaker,
+                market_id: "0xproptest".to_string(),
+                yes_odds: yes_implied_prob.to_decimal_odds().unwrap(),
+                no_odds: no_implied_prob.to_decimal_odds().unwrap(),
+                yes_implied_prob,
+                no_implied_prob,
+                timestamp: Utc::now(),
+                source_timestamp: None,
+            }
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn fair_value_is_a_probability_pair(
+            odds in prop::collection::vec(bookmaker_odds(), 1..6),
+        ) {
+            let (fair_yes, fair_no) = sharp_fair_value(&odds, &freshness(), Utc::now()).unwrap();
+
+            prop_assert!((fair_yes.value() + fair_no.value() - dec!(1)).abs() < dec!(0.000001));
+        }
+
+        #[test]
+        fn confidence_stays_in_unit_interval(
+            divergence in (0i64..100_000).prop_map(|v| Decimal::new(v, 2)),
+            num_bookmakers in 0usize..10,
+        ) {
+            let confidence = strategy().calculate_confidence(divergence, num_bookmakers);
+
+            prop_assert!(confidence >= dec!(0) && confidence <= dec!(1));
         }
     }
 }
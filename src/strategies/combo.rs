@@ -0,0 +1,321 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::sync::Arc;
+use tracing::{info, debug};
+use uuid::Uuid;
+
+use crate::cache::ReferenceCache;
+use crate::models::{price_combo, Leg, ScoreDistribution, ScoringProfile};
+use crate::numeric;
+use crate::risk::ladder_rung;
+use crate::types::{Game, Market, MarketType, Position, Probability, Signal, SignalType, Strategy as StrategyEnum};
+use super::Strategy;
+
+/// Component markets must start within this many minutes of the combo
+const MAX_EVENT_TIME_DRIFT_MINUTES: i64 = 180;
+
+/// Strategy: correlated combination markets
+///
+/// Edge: "Team wins AND total over X" markets tend to be priced as the product
+/// of their legs, but a favorite's margin and the total are correlated.
+///
+/// Implementation:
+/// 1. Parse the combo's legs (team to win, total over/under a line)
+/// 2. Read each leg's probability from its own moneyline and total market
+/// 3. Calibrate a correlated margin/total score model to those marginals
+/// 4. Simulate the joint probability and trade the combo against it
+pub struct ComboPricingStrategy {
+    reference: Arc<ReferenceCache>,
+    min_edge_pct: Decimal,
+    simulation_count: u32,
+}
+
+/// A combo question split into its legs
+#[derive(Debug, Clone, PartialEq)]
+struct ComboLegs {
+    /// Lowercased team name as written in the question
+    team: String,
+    win: bool,
+    over: bool,
+    line: f64,
+}
+
+impl ComboLegs {
+    /// Parse "<Team> win(s) and over|under <line>", legs in either order
+    fn parse(question: &str) -> Option<Self> {
+        let question = question.to_lowercase();
+        let question = question.trim_end_matches('?');
+        let parts: Vec<&str> = question.split(" and ").flat_map(|p| p.split(" & ")).collect();
+        let [a, b] = parts.as_slice() else {
+            return None;
+        };
+
+        let (result_part, total_part) = if total_leg(a).is_some() { (b, a) } else { (a, b) };
+        let (over, line) = total_leg(total_part)?;
+        let (team, win) = result_leg(result_part)?;
+
+        Some(Self { team, win, over, line })
+    }
+
+    fn legs(&self) -> [Leg; 2] {
+        [
+            if self.win { Leg::Win } else { Leg::Lose },
+            if self.over { Leg::Over(self.line) } else { Leg::Under(self.line) },
+        ]
+    }
+}
+
+/// "over 45.5" / "under 45.5 points"
+fn total_leg(part: &str) -> Option<(bool, f64)> {
+    let words: Vec<&str> = part.split_whitespace().collect();
+    words.windows(2).find_map(|w| {
+        let over = match w[0] {
+            "over" => true,
+            "under" => false,
+            _ => return None,
+        };
+        Some((over, w[1].parse().ok()?))
+    })
+}
+
+/// "will the chiefs win" / "chiefs lose"
+fn result_leg(part: &str) -> Option<(String, bool)> {
+    let words: Vec<&str> = part.split_whitespace()
+        .filter(|w| !matches!(*w, "will" | "the"))
+        .collect();
+    let position = words.iter().position(|w| matches!(*w, "win" | "wins" | "lose" | "loses"))?;
+    if position == 0 {
+        return None;
+    }
+
+    let win = words[position].starts_with("win");
+    Some((words[..position].join(" "), win))
+}
+
+fn within_event_window(a: &Market, b: &Market) -> bool {
+    (a.event_time - b.event_time).num_minutes().abs() <= MAX_EVENT_TIME_DRIFT_MINUTES
+}
+
+/// Probability `team` wins, from a moneyline market on its scheduled game
+///
+/// Which team YES backs is read off the question the way the consistency
+/// ladders read it, so "Will the Chiefs beat the Bills?" backs the Chiefs
+/// whichever of them is at home.
+fn win_probability(combo: &Market, team: &str, markets: &[Market], games: &[Game]) -> Option<f64> {
+    markets.iter()
+        .filter(|m| m.market_type == MarketType::Moneyline && within_event_window(m, combo))
+        .find_map(|m| {
+            let game = games.iter().find(|g| g.matches_event(&m.event_name, m.event_time))?;
+            let home = match game.team_positions(team) {
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                _ => return None,
+            };
+            let (_, rung) = ladder_rung(game, m)?;
+            let yes = m.yes_quote.mid().value();
+            let home_wins = if rung.above == Position::Yes { yes } else { Decimal::ONE - yes };
+            numeric::to_f64(if home { home_wins } else { Decimal::ONE - home_wins }).ok()
+        })
+}
+
+impl ComboPricingStrategy {
+    pub fn new(reference: Arc<ReferenceCache>, min_edge_pct: f64, simulation_count: u32) -> Result<Self> {
+        Ok(Self {
+            reference,
+            min_edge_pct: numeric::to_decimal(min_edge_pct)
+                .context("combo.min_edge_pct")?,
+            simulation_count,
+        })
+    }
+
+    /// Probability the total goes over `line`, from a total market on the same line
+    fn over_probability(&self, combo: &Market, team: &str, line: f64, markets: &[Market]) -> Option<f64> {
+        markets.iter()
+            .filter(|m| m.market_type == MarketType::Total && within_event_window(m, combo))
+            .filter(|m| m.event_name.to_lowercase().contains(team))
+            .filter(|m| {
+                total_leg(&m.event_name.to_lowercase())
+                    .map_or(false, |(_, l)| (l - line).abs() < 1e-6)
+            })
+            .find_map(|m| numeric::to_f64(m.yes_quote.mid().value()).ok())
+    }
+}
+
+#[async_trait]
+impl Strategy for ComboPricingStrategy {
+    async fn generate_signals(&self, markets: &[Market]) -> Result<Vec<Signal>> {
+        let mut signals = Vec::new();
+
+        for market in markets {
+            if market.market_type != MarketType::Combo || !market.status.allows_entry() {
+                continue;
+            }
+
+            let Some(combo) = self.reference.parsed(market, |m| ComboLegs::parse(&m.event_name)).await else {
+                debug!("Unrecognised combo question: {}", market.event_name);
+                continue;
+            };
+            let Some(profile) = ScoringProfile::for_sport(market.sport) else {
+                continue;
+            };
+
+            let games = self.reference.season_games(market.sport).await?;
+            let win = win_probability(market, &combo.team, markets, &games);
+            let over = self.over_probability(market, &combo.team, combo.line, markets);
+            let (Some(team_wins), Some(over_line)) = (win, over) else {
+                continue;
+            };
+
+            // Calibrate to the team winning; a "lose" leg is handled by the leg itself
+            let distribution = match ScoreDistribution::calibrate(profile, team_wins, combo.line, over_line) {
+                Ok(distribution) => distribution,
+                Err(e) => {
+                    debug!("Cannot calibrate {}: {}", market.market_id, e);
+                    continue;
+                }
+            };
+            let estimate = price_combo(
+                &distribution,
+                &combo.legs(),
+                self.simulation_count,
+                &mut rand::thread_rng(),
+            )?;
+
+            let joint = match Probability::from_f64(estimate.joint) {
+                Ok(p) => p,
+                Err(e) => {
+                    debug!("Invalid combo probability for {}: {}", market.market_id, e);
+                    continue;
+                }
+            };
+
+            let min_edge = self.min_edge_pct / dec!(100.0);
+            let yes_edge = joint.edge_over(market.entry_price(Position::Yes));
+            let no_edge = joint.complement().edge_over(market.entry_price(Position::No));
+            let (signal_type, edge, fair_value) = if yes_edge > min_edge {
+                (SignalType::BuyYes, yes_edge, joint)
+            } else if no_edge > min_edge {
+                (SignalType::BuyNo, no_edge, joint.complement())
+            } else {
+                continue;
+            };
+
+            // Scale with edge, capped like the other model-driven strategies
+            let confidence = (edge * dec!(5.0)).min(dec!(0.9));
+            let quote = market.quote(signal_type.to_position());
+
+            let signal = Signal {
+                signal_id: Uuid::new_v4(),
+                market_id: market.market_id.clone(),
+                strategy: StrategyEnum::ComboPricing,
+                signal_type: signal_type.clone(),
+                confidence,
+                edge_size: edge,
+                recommended_size: dec!(1000.0) * confidence,
+                current_price: quote.ask,
+                executable_size: quote.ask_size,
+                quoted_edge: fair_value.edge_over(quote.mid()),
+                fair_value,
+                generated_at: Utc::now(),
+                metadata: serde_json::json!({
+                    "team": combo.team,
+                    "win": combo.win,
+                    "over": combo.over,
+                    "line": combo.line,
+                    "team_win_probability": team_wins,
+                    "over_probability": over_line,
+                    "joint_probability": estimate.joint,
+                    "independent_probability": estimate.independent,
+                    "simulations": estimate.simulations,
+                }),
+            };
+
+            info!(
+                "🔗 Combo Signal: {} {} - Joint: {:.3}, Independent: {:.3}, Edge: {:.2}%",
+                market.event_name,
+                match signal_type {
+                    SignalType::BuyYes => "YES",
+                    SignalType::BuyNo => "NO",
+                },
+                estimate.joint,
+                estimate.independent,
+                edge * dec!(100.0)
+            );
+
+            signals.push(signal);
+        }
+
+        Ok(signals)
+    }
+
+    fn name(&self) -> &str {
+        "Combo Pricing"
+    }
+
+    fn is_enabled(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::market_fixture;
+    use crate::types::Sport;
+
+    #[test]
+    fn parses_legs_in_either_order() {
+        let expected = ComboLegs { team: "chiefs".to_string(), win: true, over: true, line: 45.5 };
+
+        assert_eq!(ComboLegs::parse("Will the Chiefs win and over 45.5 points?"), Some(expected.clone()));
+        assert_eq!(ComboLegs::parse("Over 45.5 & Chiefs win"), Some(expected));
+        assert_eq!(
+            ComboLegs::parse("Bills lose and under 44"),
+            Some(ComboLegs { team: "bills".to_string(), win: false, over: false, line: 44.0 })
+        );
+    }
+
+    #[test]
+    fn reads_the_team_yes_backs_off_the_moneyline_question() {
+        let kickoff = Utc::now() + chrono::Duration::days(1);
+        let game = Game {
+            game_id: "nfl-1".to_string(),
+            sport: Sport::NFL,
+            season: 2026,
+            home_team: "Buffalo Bills".to_string(),
+            away_team: "Kansas City Chiefs".to_string(),
+            start_time: kickoff,
+            home_score: None,
+            away_score: None,
+        };
+        let market = |market_id: &str, event_name: &str, market_type, yes_price| Market {
+            event_name: event_name.to_string(),
+            market_type,
+            event_time: kickoff,
+            sport: Sport::NFL,
+            ..market_fixture(market_id, yes_price)
+        };
+        let combo = market("0xcombo", "Chiefs win and over 45.5", MarketType::Combo, dec!(0.30));
+
+        // YES backs the Chiefs, though the question opens with "Will the"
+        let beat = market("0xbeat", "Will the Chiefs beat the Bills?", MarketType::Moneyline, dec!(0.60));
+        let p = win_probability(&combo, "chiefs", &[combo.clone(), beat.clone()], &[game.clone()]).unwrap();
+        assert!((p - 0.60).abs() < 0.01, "{}", p);
+        let p = win_probability(&combo, "bills", &[combo.clone(), beat], &[game.clone()]).unwrap();
+        assert!((p - 0.40).abs() < 0.01, "{}", p);
+
+        // And the Bills when they are named first
+        let versus = market("0xvs", "Bills vs. Chiefs", MarketType::Moneyline, dec!(0.60));
+        let p = win_probability(&combo, "chiefs", &[combo.clone(), versus], &[game]).unwrap();
+        assert!((p - 0.40).abs() < 0.01, "{}", p);
+    }
+
+    #[test]
+    fn rejects_non_combo_questions() {
+        assert_eq!(ComboLegs::parse("Chiefs vs. Bills"), None);
+        assert_eq!(ComboLegs::parse("Chiefs win and Bills win and over 45.5"), None);
+    }
+}
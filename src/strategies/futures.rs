@@ -0,0 +1,265 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, debug};
+use uuid::Uuid;
+
+use crate::models::{Prize, SeasonRun, SeasonSimulator};
+use crate::numeric;
+use crate::types::{
+    Market, MarketType, Position, Probability, Signal, SignalType, Sport, Strategy as StrategyEnum,
+    TeamRating,
+};
+use super::Strategy;
+
+/// Phrases naming a league title rather than a division or conference
+const CHAMPIONSHIP_TERMS: [&str; 5] = ["super bowl", "nba finals", "nba championship", "world series", "mlb championship"];
+
+/// What a futures market pays out on
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Target {
+    Prize(Prize),
+    /// At least this many regular-season wins
+    Wins(u32),
+}
+
+/// Strategy: season-long futures
+///
+/// Edge: futures books carry heavy overround and react slowly to results
+/// elsewhere in the league.
+///
+/// Implementation:
+/// 1. Take the latest season simulation for the sport (re-run when ratings,
+///    results or injuries change)
+/// 2. Price division, conference, championship and win-total markets from
+///    the simulated outcomes
+pub struct SeasonFuturesStrategy {
+    simulator: Arc<SeasonSimulator>,
+    min_edge_pct: Decimal,
+}
+
+impl SeasonFuturesStrategy {
+    pub fn new(simulator: Arc<SeasonSimulator>, min_edge_pct: f64) -> Result<Self> {
+        Ok(Self {
+            simulator,
+            min_edge_pct: numeric::to_decimal(min_edge_pct)
+                .context("futures.min_edge_pct")?,
+        })
+    }
+
+    fn price(&self, market: &Market, run: &SeasonRun) -> Option<Signal> {
+        let question = market.event_name.to_lowercase();
+        let (team, target) = parse_future(&question, &run.teams)?;
+        let outcomes = &run.outcomes;
+        let probability = match target {
+            Target::Prize(prize) => outcomes.probability(&team.team, prize)?,
+            Target::Wins(wins) => outcomes.wins_at_least(&team.team, wins)?,
+        };
+        let fair = Probability::from_f64(probability).ok()?;
+
+        let min_edge = self.min_edge_pct / dec!(100.0);
+        let yes_edge = fair.edge_over(market.entry_price(Position::Yes));
+        let no_edge = fair.complement().edge_over(market.entry_price(Position::No));
+        let (signal_type, edge, fair_value) = if yes_edge > min_edge {
+            (SignalType::BuyYes, yes_edge, fair)
+        } else if no_edge > min_edge {
+            (SignalType::BuyNo, no_edge, fair.complement())
+        } else {
+            return None;
+        };
+
+        // Long-dated, so confidence is capped below the game-level strategies
+        let confidence = (edge * dec!(4.0)).min(dec!(0.8));
+        let quote = market.quote(signal_type.to_position());
+
+        info!(
+            "🏆 Futures Signal: {} {} - Simulated: {:.3}, Edge: {:.2}%",
+            market.event_name,
+            match signal_type {
+                SignalType::BuyYes => "YES",
+                SignalType::BuyNo => "NO",
+            },
+            probability,
+            edge * dec!(100.0)
+        );
+
+        Some(Signal {
+            signal_id: Uuid::new_v4(),
+            market_id: market.market_id.clone(),
+            strategy: StrategyEnum::SeasonFutures,
+            signal_type,
+            confidence,
+            edge_size: edge,
+            recommended_size: dec!(1000.0) * confidence,
+            current_price: quote.ask,
+            executable_size: quote.ask_size,
+            quoted_edge: fair_value.edge_over(quote.mid()),
+            fair_value,
+            generated_at: Utc::now(),
+            metadata: serde_json::json!({
+                "team": team.team,
+                "target": format!("{:?}", target),
+                "simulated_probability": probability,
+                "expected_wins": outcomes.expected_wins(&team.team),
+                "playoff_probability": outcomes.playoff_probability(&team.team),
+                "simulations": outcomes.simulations,
+                "season_run_id": run.run_id,
+                "simulated_at": run.simulated_at,
+            }),
+        })
+    }
+}
+
+/// Team and target named by a lowercased futures question
+///
+/// Win totals are checked first, then divisions before conferences since
+/// division names contain them ("AFC West"); league titles must be named
+/// explicitly.
+fn parse_future<'a>(question: &str, teams: &'a [TeamRating]) -> Option<(&'a TeamRating, Target)> {
+    let team = teams.iter().find(|t| {
+        let name = t.team.to_lowercase();
+        question.contains(&name)
+            || name.split_whitespace().last().map_or(false, |nickname| question.contains(nickname))
+    })?;
+
+    let target = if let Some(wins) = win_total(question) {
+        Target::Wins(wins)
+    } else if question.contains(&team.division.to_lowercase()) {
+        Target::Prize(Prize::Division)
+    } else if question.contains(&team.conference.to_lowercase()) {
+        Target::Prize(Prize::Conference)
+    } else if CHAMPIONSHIP_TERMS.iter().any(|t| question.contains(t)) {
+        Target::Prize(Prize::Championship)
+    } else {
+        return None;
+    };
+
+    Some((team, target))
+}
+
+/// Wins needed by "win 11+ games", "win 11 or more games" or "over 10.5 wins"
+fn win_total(question: &str) -> Option<u32> {
+    let words: Vec<&str> = question.split_whitespace()
+        .map(|w| w.trim_end_matches(['?', ',', '.']))
+        .collect();
+
+    (0..words.len()).find_map(|i| {
+        let next = |k: usize| words.get(i + k).copied().unwrap_or_default();
+        if words[i] == "over" && next(2).starts_with("win") {
+            let line: f64 = next(1).parse().ok()?;
+            Some(line.floor() as u32 + 1)
+        } else if words[i].ends_with('+') && next(1) == "games" {
+            words[i].trim_end_matches('+').parse().ok()
+        } else if next(1) == "or" && next(2) == "more" {
+            words[i].parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
+#[async_trait]
+impl Strategy for SeasonFuturesStrategy {
+    async fn generate_signals(&self, markets: &[Market]) -> Result<Vec<Signal>> {
+        let mut by_sport: HashMap<Sport, Vec<&Market>> = HashMap::new();
+        for market in markets {
+            if market.market_type == MarketType::Futures && market.status.allows_entry() {
+                by_sport.entry(market.sport).or_default().push(market);
+            }
+        }
+
+        let mut signals = Vec::new();
+        for (sport, futures) in by_sport {
+            // One simulation per sport prices all of its futures
+            let run = match self.simulator.run(sport).await {
+                Ok(run) => run,
+                Err(e) => {
+                    debug!("Skipping {} futures: {}", sport.as_str(), e);
+                    continue;
+                }
+            };
+
+            signals.extend(futures.into_iter().filter_map(|m| self.price(m, &run)));
+        }
+
+        Ok(signals)
+    }
+
+    fn name(&self) -> &str {
+        "Season Futures"
+    }
+
+    fn is_enabled(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SimulationConfig;
+    use crate::models::backend_from_config;
+    use crate::storage::MemoryStorage;
+    use crate::test_support::market_fixture;
+
+    fn team(name: &str, conference: &str, division: &str) -> TeamRating {
+        TeamRating {
+            sport: Sport::NFL,
+            team: name.to_string(),
+            conference: conference.to_string(),
+            division: division.to_string(),
+            rating: 0.0,
+            latitude: None,
+            longitude: None,
+            elevation_m: None,
+            indoor: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn prices_futures_from_simulated_seasons() {
+        let storage = Arc::new(MemoryStorage::new());
+        let teams = vec![
+            TeamRating { rating: 9.0, ..team("Kansas City Chiefs", "AFC", "AFC West") },
+            TeamRating { rating: -9.0, ..team("Las Vegas Raiders", "AFC", "AFC West") },
+            team("Philadelphia Eagles", "NFC", "NFC East"),
+        ];
+        storage.seed_season(teams, Vec::new()).await;
+
+        // Level records with no games left: the division is a coin flip
+        let mut market = market_fixture("0xfuture", dec!(0.2));
+        market.market_type = MarketType::Futures;
+        market.event_name = "Will the Raiders win the AFC West?".to_string();
+
+        let backend = backend_from_config(&SimulationConfig::default()).unwrap();
+        let simulator = SeasonSimulator::new(storage, backend, 4_000);
+        let strategy = SeasonFuturesStrategy::new(Arc::new(simulator), 5.0).unwrap();
+        let signals = strategy.generate_signals(&[market]).await.unwrap();
+
+        assert_eq!(signals.len(), 1);
+        assert!(matches!(signals[0].signal_type, SignalType::BuyYes));
+        assert!((signals[0].fair_value.value() - dec!(0.5)).abs() < dec!(0.05));
+    }
+
+    #[test]
+    fn parses_team_and_prize() {
+        let teams = vec![
+            team("Kansas City Chiefs", "AFC", "AFC West"),
+            team("Philadelphia Eagles", "NFC", "NFC East"),
+        ];
+        let parse = |q: &str| parse_future(&q.to_lowercase(), &teams).map(|(t, target)| (t.team.as_str(), target));
+        let chiefs = |target| Some(("Kansas City Chiefs", target));
+
+        assert_eq!(parse("Will the Chiefs win the AFC West?"), chiefs(Target::Prize(Prize::Division)));
+        assert_eq!(parse("Will the Eagles win the NFC Championship?"), Some(("Philadelphia Eagles", Target::Prize(Prize::Conference))));
+        assert_eq!(parse("Will the Chiefs win Super Bowl LX?"), chiefs(Target::Prize(Prize::Championship)));
+        assert_eq!(parse("Will the Chiefs win 12+ games?"), chiefs(Target::Wins(12)));
+        assert_eq!(parse("Chiefs over 10.5 wins in the 2025 regular season?"), chiefs(Target::Wins(11)));
+        assert_eq!(parse("Will the Chiefs win 11 or more games in the AFC West?"), chiefs(Target::Wins(11)));
+        assert_eq!(parse("Will the Chiefs make the playoffs?"), None);
+    }
+}
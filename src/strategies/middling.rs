@@ -0,0 +1,319 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use statrs::distribution::{ContinuousCDF, Normal};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::config::MiddleConfig;
+use crate::models::ScoringProfile;
+use crate::numeric;
+use crate::risk::{ladder_rung, Ladder, Rung};
+use crate::storage::Storage;
+use crate::types::{Game, Market, Position, Probability, Signal, SignalType, Sport, Strategy as StrategyEnum};
+use super::{OddsFreshness, Strategy};
+
+/// Strategy: spread and total middles
+///
+/// Edge: Polymarket's spread and total ladders lag the bookmakers when the
+/// line moves, leaving a low line and a higher one both mispriced toward
+/// the old number. Buying above the low line and below the high one wins
+/// both legs if the score lands between them, and loses one at worst.
+///
+/// Implementation:
+/// 1. Read each upcoming game's spread and total markets as rungs of a ladder
+/// 2. Fit the bookmakers' mean margin or total from their fresh lines on
+///    those markets, with the sport's score dispersion
+/// 3. Find the pair of lines whose two legs are worth most together under
+///    that distribution, one of them at least `min_line_gap` points off it
+/// 4. Emit one signal per leg, sharing a pair id
+pub struct MiddlingStrategy {
+    storage: Arc<dyn Storage>,
+    freshness: OddsFreshness,
+    min_ev: Decimal,
+    min_line_gap: f64,
+}
+
+/// Buying above `rungs[low]` and below `rungs[high]`
+#[derive(Debug, Clone, PartialEq)]
+struct Middle {
+    low: usize,
+    high: usize,
+    /// Score-model probability each leg pays
+    low_fair: Probability,
+    high_fair: Probability,
+    /// Probability the quantity lands between the lines and both pay
+    middle_probability: Decimal,
+    /// Expected profit per pair of shares after both asks
+    combined_ev: Decimal,
+    /// Points the further leg's Polymarket-implied mean sits off the consensus
+    line_gap: f64,
+}
+
+/// Mean of the ladder's quantity implied by `p` of ending above `line`
+fn implied_mean(line: f64, p: f64, sd: f64, standard: &Normal) -> Option<f64> {
+    (p > 0.0 && p < 1.0).then(|| line + sd * standard.inverse_cdf(p))
+}
+
+/// Most valuable middle among a ladder's rungs when the quantity is normal
+/// around `consensus` with dispersion `sd`
+///
+/// Each leg must be worth its ask on its own, so the pair survives risk
+/// checks leg by leg.
+fn best_middle(rungs: &[Rung], consensus: f64, sd: f64, min_line_gap: f64, min_ev: Decimal) -> Result<Option<Middle>> {
+    let standard = Normal::new(0.0, 1.0)?;
+    let market_means: Vec<Option<f64>> = rungs.iter()
+        .map(|rung| {
+            let p = numeric::to_f64(rung.probability()).ok()?;
+            implied_mean(rung.line, p, sd, &standard)
+        })
+        .collect();
+
+    let mut best: Option<Middle> = None;
+    for (low, low_rung) in rungs.iter().enumerate() {
+        for (high, high_rung) in rungs.iter().enumerate() {
+            if low_rung.line >= high_rung.line {
+                continue;
+            }
+            let (Some(low_mean), Some(high_mean)) = (market_means[low], market_means[high]) else {
+                continue;
+            };
+            // The low leg is cheap when Polymarket puts the mean below the
+            // bookmakers, the high leg when it puts it above
+            let line_gap = (consensus - low_mean).max(high_mean - consensus);
+            if line_gap < min_line_gap {
+                continue;
+            }
+
+            let low_fair = Probability::from_f64(standard.cdf((consensus - low_rung.line) / sd))?;
+            let high_fair = Probability::from_f64(standard.cdf((high_rung.line - consensus) / sd))?;
+            let low_edge = low_fair.edge_over(low_rung.market.entry_price(low_rung.above));
+            let high_edge = high_fair.edge_over(high_rung.market.entry_price(high_rung.above.opposite()));
+            if low_edge <= Decimal::ZERO || high_edge <= Decimal::ZERO {
+                continue;
+            }
+
+            // Paying 1 on one leg is certain; the middle pays the second
+            let combined_ev = low_edge + high_edge;
+            if combined_ev < min_ev || matches!(&best, Some(b) if b.combined_ev >= combined_ev) {
+                continue;
+            }
+            best = Some(Middle {
+                low,
+                high,
+                low_fair,
+                high_fair,
+                middle_probability: low_fair.value() + high_fair.value() - Decimal::ONE,
+                combined_ev,
+                line_gap,
+            });
+        }
+    }
+    Ok(best)
+}
+
+impl MiddlingStrategy {
+    pub fn new(storage: Arc<dyn Storage>, config: &MiddleConfig, freshness: OddsFreshness) -> Result<Self> {
+        Ok(Self {
+            storage,
+            freshness,
+            min_ev: numeric::to_decimal(config.min_ev_pct).context("middle.min_ev_pct")? / dec!(100.0),
+            min_line_gap: config.min_line_gap,
+        })
+    }
+
+    /// Mean of the ladder's quantity implied by the bookmakers' lines on its
+    /// rungs, older lines weighted down by `freshness`; vig is removed per line
+    async fn consensus_mean(&self, rungs: &[Rung<'_>], sd: f64, now: DateTime<Utc>) -> Result<Option<f64>> {
+        let standard = Normal::new(0.0, 1.0)?;
+        let mut weighted = 0.0;
+        let mut weight_sum = 0.0;
+
+        for rung in rungs {
+            for odds in self.storage.fetch_bookmaker_odds(&rung.market.market_id).await? {
+                let weight = numeric::to_f64(self.freshness.weight(&odds, now))?;
+                let overround = odds.yes_implied_prob.value() + odds.no_implied_prob.value();
+                if weight <= 0.0 || overround <= Decimal::ZERO {
+                    continue;
+                }
+                let above = match rung.above {
+                    Position::Yes => odds.yes_implied_prob.value(),
+                    Position::No => odds.no_implied_prob.value(),
+                };
+                let Some(mean) = implied_mean(rung.line, numeric::to_f64(above / overround)?, sd, &standard) else {
+                    continue;
+                };
+                weighted += weight * mean;
+                weight_sum += weight;
+            }
+        }
+
+        Ok((weight_sum > 0.0).then(|| weighted / weight_sum))
+    }
+
+    /// The two legs of `middle`, sharing a pair id in their metadata
+    fn signals(&self, game: &Game, ladder: Ladder, rungs: &[Rung], middle: &Middle, consensus: f64) -> Vec<Signal> {
+        let pair_id = Uuid::new_v4();
+        let low = &rungs[middle.low];
+        let high = &rungs[middle.high];
+        // Both legs are sized alike so the pair stays balanced
+        let confidence = (middle.combined_ev * dec!(5.0)).min(dec!(0.9));
+        let now = Utc::now();
+
+        [
+            (low, low.above, middle.low_fair, high),
+            (high, high.above.opposite(), middle.high_fair, low),
+        ]
+        .into_iter()
+        .map(|(rung, position, fair_value, partner)| {
+            let quote = rung.market.quote(position);
+            Signal {
+                signal_id: Uuid::new_v4(),
+                market_id: rung.market.market_id.clone(),
+                strategy: StrategyEnum::Middling,
+                signal_type: SignalType::buy(position),
+                confidence,
+                edge_size: fair_value.edge_over(quote.ask),
+                recommended_size: dec!(1000.0) * confidence,
+                current_price: quote.ask,
+                executable_size: quote.ask_size,
+                quoted_edge: fair_value.edge_over(quote.mid()),
+                fair_value,
+                generated_at: now,
+                metadata: serde_json::json!({
+                    "pair_id": pair_id,
+                    "game_id": game.game_id,
+                    "ladder": ladder,
+                    "partner_market_id": partner.market.market_id,
+                    "low_line": low.line,
+                    "high_line": high.line,
+                    "consensus_mean": consensus,
+                    "line_gap": middle.line_gap,
+                    "middle_probability": middle.middle_probability,
+                    "combined_ev": middle.combined_ev,
+                }),
+            }
+        })
+        .collect()
+    }
+}
+
+#[async_trait]
+impl Strategy for MiddlingStrategy {
+    async fn generate_signals(&self, markets: &[Market]) -> Result<Vec<Signal>> {
+        let mut signals = Vec::new();
+        let now = Utc::now();
+        let sports: HashSet<Sport> = markets.iter().map(|m| m.sport).collect();
+
+        for sport in sports {
+            // Draws break the normal score model
+            let Some(profile) = ScoringProfile::for_sport(sport) else {
+                continue;
+            };
+
+            for game in self.storage.fetch_season_games(sport).await? {
+                if game.is_played() || game.start_time <= now {
+                    continue;
+                }
+
+                for ladder in [Ladder::Margin, Ladder::Total] {
+                    let rungs: Vec<Rung> = markets.iter()
+                        .filter(|m| m.status.allows_entry())
+                        .filter_map(|m| ladder_rung(&game, m))
+                        .filter(|(l, _)| *l == ladder)
+                        .map(|(_, rung)| rung)
+                        .collect();
+                    if rungs.len() < 2 {
+                        continue;
+                    }
+
+                    let sd = match ladder {
+                        Ladder::Margin => profile.margin_sd,
+                        Ladder::Total => profile.total_sd,
+                    };
+                    let Some(consensus) = self.consensus_mean(&rungs, sd, now).await? else {
+                        continue;
+                    };
+                    let Some(middle) = best_middle(&rungs, consensus, sd, self.min_line_gap, self.min_ev)? else {
+                        continue;
+                    };
+
+                    info!(
+                        "🥪 Middle: {} above {} / below {} - Consensus: {:.1}, Middle: {:.3}, EV: {:.2}%",
+                        game.game_id,
+                        rungs[middle.low].line,
+                        rungs[middle.high].line,
+                        consensus,
+                        middle.middle_probability,
+                        middle.combined_ev * dec!(100.0)
+                    );
+                    signals.extend(self.signals(&game, ladder, &rungs, &middle, consensus));
+                }
+            }
+        }
+
+        Ok(signals)
+    }
+
+    fn name(&self) -> &str {
+        "Middling"
+    }
+
+    fn is_enabled(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::market_fixture;
+    use crate::types::{MarketType, Price, Quote};
+
+    fn total(market_id: &str, line: &str, yes_mid: Decimal, game: &Game) -> Market {
+        let mut market = market_fixture(market_id, yes_mid);
+        market.sport = game.sport;
+        market.event_time = game.start_time;
+        market.market_type = MarketType::Total;
+        market.event_name = format!("Yankees vs. Red Sox O/U {}", line);
+        let yes = Price::new(yes_mid).unwrap();
+        market.yes_quote = Quote::from_book(Some(yes_mid - dec!(0.01)), Some(yes_mid + dec!(0.01)), yes);
+        market.no_quote = market.yes_quote.complement();
+        market
+    }
+
+    #[test]
+    fn pairs_the_lines_either_side_of_the_consensus() {
+        let game = Game {
+            game_id: "mlb-nyy-bos".to_string(),
+            sport: Sport::MLB,
+            season: 2026,
+            home_team: "New York Yankees".to_string(),
+            away_team: "Boston Red Sox".to_string(),
+            start_time: Utc::now() + chrono::Duration::days(1),
+            home_score: None,
+            away_score: None,
+        };
+        // Bookmakers have moved to 8.5; Polymarket still prices the 7.5 and
+        // 9.5 lines around a lower and a higher total
+        let markets = vec![
+            total("o7.5", "7.5", dec!(0.45), &game),
+            total("o8.5", "8.5", dec!(0.50), &game),
+            total("o9.5", "9.5", dec!(0.45), &game),
+        ];
+        let rungs: Vec<Rung> = markets.iter().filter_map(|m| ladder_rung(&game, m)).map(|(_, r)| r).collect();
+
+        let middle = best_middle(&rungs, 8.5, 4.5, 1.0, dec!(0.03)).unwrap().unwrap();
+        assert_eq!((middle.low, middle.high), (0, 2));
+        // Over 7.5 at 0.46 and under 9.5 at 0.56, each ~0.588 to pay
+        assert!((middle.combined_ev - dec!(0.156)).abs() < dec!(0.001));
+        assert!((middle.middle_probability - dec!(0.176)).abs() < dec!(0.001));
+
+        // Lines within the gap of the consensus are left alone
+        assert!(best_middle(&rungs, 8.5, 4.5, 2.0, dec!(0.03)).unwrap().is_none());
+    }
+}
@@ -0,0 +1,204 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use crate::cache::ReferenceCache;
+use crate::config::MlbConfig;
+use crate::models::{bullpen_innings, MlbModel, TeamStrengths, LEAGUE_RUNS};
+use crate::numeric;
+use crate::risk::{ladder_rung, Ladder};
+use crate::storage::Storage;
+use crate::types::{
+    Market, MarketType, Position, ProbablePitcher, Probability, Signal, SignalType, Sport, Strategy as StrategyEnum,
+};
+use super::Strategy;
+
+/// Strategy: MLB moneylines and totals off the announced starters
+///
+/// Edge: baseball prices lean on the starting pitchers, and lines are slow
+/// to take in a late scratch, a hitter's park or a bullpen that has been
+/// carrying the staff for days.
+///
+/// Implementation:
+/// 1. Wait until both starters are announced (`pitchers` ingestion)
+/// 2. Baseline runs from the fitted team ratings, league average without them
+/// 3. Adjust for the starters, the home park and each bullpen's recent
+///    innings (`MlbModel`)
+/// 4. Price the moneyline and totals with independent Poisson runs
+pub struct MlbPitchingStrategy {
+    storage: Arc<dyn Storage>,
+    reference: Arc<ReferenceCache>,
+    model: MlbModel,
+    min_edge: Decimal,
+    bullpen_window: Duration,
+}
+
+impl MlbPitchingStrategy {
+    pub fn new(storage: Arc<dyn Storage>, reference: Arc<ReferenceCache>, config: &MlbConfig) -> Result<Self> {
+        Ok(Self {
+            storage,
+            reference,
+            model: MlbModel::from_config(config)?,
+            min_edge: numeric::to_decimal(config.min_edge_pct).context("mlb.min_edge_pct")? / dec!(100.0),
+            bullpen_window: Duration::days(config.bullpen_window_days),
+        })
+    }
+
+    /// Fitted MLB ratings, shared through the reference cache
+    async fn strengths(&self) -> Result<Arc<Option<TeamStrengths>>> {
+        let storage = self.storage.clone();
+        self.reference.team_strengths(Sport::MLB, async move {
+            let history = storage.fetch_game_history(Sport::MLB).await?;
+            Ok(match TeamStrengths::fit(Sport::MLB, &history, Utc::now()) {
+                Ok(strengths) => Some(strengths),
+                Err(e) => {
+                    debug!("No team strengths for MLB: {}", e);
+                    None
+                }
+            })
+        }).await
+    }
+}
+
+#[async_trait]
+impl Strategy for MlbPitchingStrategy {
+    async fn generate_signals(&self, markets: &[Market]) -> Result<Vec<Signal>> {
+        let markets: Vec<&Market> = markets.iter()
+            .filter(|m| m.sport == Sport::MLB && m.status.allows_entry())
+            .filter(|m| matches!(m.market_type, MarketType::Moneyline | MarketType::Total))
+            .collect();
+        if markets.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let now = Utc::now();
+        let games = self.reference.season_games(Sport::MLB).await?;
+        let strengths = self.strengths().await?;
+        let bullpens = self.storage.fetch_bullpen_usage(now - self.bullpen_window).await?;
+        let mut starters: HashMap<String, Vec<ProbablePitcher>> = HashMap::new();
+        let mut signals = Vec::new();
+
+        for market in markets {
+            let Some(game) = games.iter().find(|g| g.matches_event(&market.event_name, market.event_time)) else {
+                continue;
+            };
+            if game.is_played() {
+                continue;
+            }
+            let Some((ladder, rung)) = ladder_rung(game, market) else {
+                continue;
+            };
+
+            if !starters.contains_key(&game.game_id) {
+                let announced = self.storage.fetch_probable_pitchers(&game.game_id).await?;
+                starters.insert(game.game_id.clone(), announced);
+            }
+            let announced = &starters[&game.game_id];
+            let home_starter = announced.iter().find(|p| p.team == game.home_team);
+            let away_starter = announced.iter().find(|p| p.team == game.away_team);
+            let (Some(home_starter), Some(away_starter)) = (home_starter, away_starter) else {
+                debug!("Starters not yet announced for {}", game.game_id);
+                continue;
+            };
+
+            let baseline = (*strengths).as_ref()
+                .and_then(|s| s.expected_scores(&game.home_team, &game.away_team))
+                .unwrap_or((LEAGUE_RUNS, LEAGUE_RUNS));
+            let bullpen = (
+                bullpen_innings(&bullpens, &game.home_team),
+                bullpen_innings(&bullpens, &game.away_team),
+            );
+            let odds = match self.model.game_odds(&game.home_team, baseline, (home_starter, away_starter), bullpen) {
+                Ok(odds) => odds,
+                Err(e) => {
+                    debug!("Cannot price {}: {}", market.market_id, e);
+                    continue;
+                }
+            };
+
+            let above = match ladder {
+                Ladder::Margin => odds.home_win,
+                Ladder::Total => odds.over(rung.line)?,
+            };
+            let yes = match rung.above {
+                Position::Yes => above,
+                Position::No => 1.0 - above,
+            };
+            let fair_yes = match Probability::from_f64(yes) {
+                Ok(p) => p,
+                Err(e) => {
+                    debug!("Invalid MLB probability for {}: {}", market.market_id, e);
+                    continue;
+                }
+            };
+
+            let yes_edge = fair_yes.edge_over(market.entry_price(Position::Yes));
+            let no_edge = fair_yes.complement().edge_over(market.entry_price(Position::No));
+            let (signal_type, edge, fair_value) = if yes_edge > self.min_edge {
+                (SignalType::BuyYes, yes_edge, fair_yes)
+            } else if no_edge > self.min_edge {
+                (SignalType::BuyNo, no_edge, fair_yes.complement())
+            } else {
+                continue;
+            };
+
+            // Scale with edge, capped like the other model-driven strategies
+            let confidence = (edge * dec!(5.0)).min(dec!(0.9));
+            let quote = market.quote(signal_type.to_position());
+
+            info!(
+                "⚾ MLB Signal: {} {} - {} vs {}, Runs: {:.2}-{:.2}, Edge: {:.2}%",
+                market.event_name,
+                signal_type.to_position().as_str(),
+                home_starter.pitcher,
+                away_starter.pitcher,
+                odds.home_runs,
+                odds.away_runs,
+                edge * dec!(100.0)
+            );
+
+            signals.push(Signal {
+                signal_id: Uuid::new_v4(),
+                market_id: market.market_id.clone(),
+                strategy: StrategyEnum::MlbPitching,
+                signal_type,
+                confidence,
+                edge_size: edge,
+                recommended_size: dec!(1000.0) * confidence,
+                current_price: quote.ask,
+                executable_size: quote.ask_size,
+                quoted_edge: fair_value.edge_over(quote.mid()),
+                fair_value,
+                generated_at: now,
+                metadata: serde_json::json!({
+                    "game_id": game.game_id,
+                    "home_starter": home_starter.pitcher,
+                    "away_starter": away_starter.pitcher,
+                    "home_runs": odds.home_runs,
+                    "away_runs": odds.away_runs,
+                    "home_win_probability": odds.home_win,
+                    "line": rung.line,
+                    "park_factor": self.model.park_factor(&game.home_team),
+                    "home_bullpen_innings": bullpen.0,
+                    "away_bullpen_innings": bullpen.1,
+                }),
+            });
+        }
+
+        Ok(signals)
+    }
+
+    fn name(&self) -> &str {
+        "MLB Pitching"
+    }
+
+    fn is_enabled(&self) -> bool {
+        true
+    }
+}
@@ -1,9 +1,22 @@
+mod cadence;
 mod clv_arbitrage;
+mod combo;
+mod futures;
+mod middling;
+mod mlb;
 mod poisson_ev;
+mod remote_model;
+mod scripted;
 mod signal_generator;
 
-pub use clv_arbitrage::ClvArbitrageStrategy;
+pub use clv_arbitrage::{sharp_fair_value, ClvArbitrageStrategy, OddsFreshness};
+pub use combo::ComboPricingStrategy;
+pub use futures::SeasonFuturesStrategy;
+pub use middling::MiddlingStrategy;
+pub use mlb::MlbPitchingStrategy;
 pub use poisson_ev::PoissonEvStrategy;
+pub use remote_model::RemoteModelStrategy;
+pub use scripted::ScriptedStrategy;
 pub use signal_generator::SignalGenerator;
 
 use async_trait::async_trait;
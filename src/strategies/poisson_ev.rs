@@ -1,14 +1,22 @@
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use async_trait::async_trait;
-use sqlx::PgPool;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use chrono::Utc;
+use std::collections::hash_map::{Entry, HashMap};
+use std::sync::Arc;
+use std::time::Instant;
 use uuid::Uuid;
 use tracing::{info, debug};
-use statrs::distribution::{Poisson, Discrete};
 
-use crate::types::{Market, Signal, SignalType, Strategy as StrategyEnum, MarketType};
+use crate::cache::ReferenceCache;
+use crate::config::{AdaptiveSimulationConfig, LambdaSource};
+use crate::models::{
+    adjust_lambdas, officiating_total_shift, AdaptiveSampling, BatchSimulator, ModelParameters, SamplingStop, TeamStrengths,
+};
+use crate::numeric;
+use crate::storage::Storage;
+use crate::types::{Game, Market, Signal, SignalType, Sport, Strategy as StrategyEnum, MarketType, Position, Probability};
 use super::Strategy;
 
 /// Strategy 2: Poisson Expected Value Model
@@ -17,55 +25,118 @@ use super::Strategy;
 /// 
 /// Implementation:
 /// 1. For "Total Points Over/Under" markets
-/// 2. Input: Team offensive/defensive ratings, pace, injuries
-/// 3. Simulate 10,000 game outcomes using Poisson process
+/// 2. Input: Team attack/defense ratings (league-typical scoring, or a
+///    hierarchical model fitted to stored results), and the scoring
+///    tendencies of officials once they are assigned
+/// 3. Simulate 10,000 game outcomes using Poisson process, or with adaptive
+///    sampling draw in batches until a sequential test settles the edge
 /// 4. Compare simulated probability vs. market probability
 /// 5. Bet when edge > 5% and sample size significance > 95%
 pub struct PoissonEvStrategy {
-    db_pool: PgPool,
+    storage: Arc<dyn Storage>,
+    reference: Arc<ReferenceCache>,
+    backend: Arc<dyn BatchSimulator>,
     min_edge_pct: Decimal,
     simulation_count: u32,
     min_significance: f64,
+    lambda_source: LambdaSource,
+    /// Stops drawing once the edge decision is clear, when enabled
+    adaptive: Option<AdaptiveSampling>,
+}
+
+/// Schedule and fitted ratings for one sport, loaded once per pass
+struct SportModels {
+    games: Arc<Vec<Game>>,
+    strengths: Arc<Option<TeamStrengths>>,
 }
 
 impl PoissonEvStrategy {
     pub fn new(
-        db_pool: PgPool,
+        storage: Arc<dyn Storage>,
+        reference: Arc<ReferenceCache>,
+        backend: Arc<dyn BatchSimulator>,
         min_edge_pct: f64,
         simulation_count: u32,
         min_significance: f64,
-    ) -> Self {
-        Self {
-            db_pool,
-            min_edge_pct: Decimal::from_f64_retain(min_edge_pct).unwrap_or(dec!(5.0)),
+        lambda_source: LambdaSource,
+        adaptive: &AdaptiveSimulationConfig,
+    ) -> Result<Self> {
+        Ok(Self {
+            storage,
+            reference,
+            backend,
+            min_edge_pct: numeric::to_decimal(min_edge_pct)
+                .context("poisson_ev.min_edge_pct")?,
             simulation_count,
             min_significance,
-        }
+            lambda_source,
+            adaptive: AdaptiveSampling::from_config(adaptive).context("poisson_ev.adaptive")?,
+        })
     }
 
-    /// Estimate team scoring rates (lambda parameters for Poisson)
-    async fn estimate_scoring_rates(&self, market: &Market) -> Result<Option<(f64, f64)>> {
-        // In production, this would:
-        // 1. Parse team names from market description
-        // 2. Query team stats from database (offensive/defensive ratings)
-        // 3. Adjust for injuries, home/away, pace, etc.
-        
-        // For now, return example values
-        // These would come from a comprehensive sports analytics database
-        
+    /// Schedule, plus fitted strengths when they are the lambda source
+    ///
+    /// Both come from the reference cache, so the fit runs once per TTL
+    /// rather than once per pass.
+    async fn load_sport(&self, sport: Sport) -> Result<SportModels> {
+        let games = self.reference.season_games(sport).await?;
+        let strengths = match self.lambda_source {
+            LambdaSource::Fixed => Arc::new(None),
+            LambdaSource::Hierarchical => {
+                let storage = self.storage.clone();
+                self.reference.team_strengths(sport, async move {
+                    let history = storage.fetch_game_history(sport).await?;
+                    Ok(match TeamStrengths::fit(sport, &history, Utc::now()) {
+                        Ok(strengths) => Some(strengths),
+                        Err(e) => {
+                            debug!("No team strengths for {}: {}", sport.as_str(), e);
+                            None
+                        }
+                    })
+                }).await?
+            }
+        };
+
+        Ok(SportModels { games, strengths })
+    }
+
+    /// Estimate team scoring rates (lambda parameters for Poisson), scaled by
+    /// the league's pace from the parameter sheets
+    fn estimate_scoring_rates(
+        &self,
+        market: &Market,
+        game: Option<&Game>,
+        models: &SportModels,
+    ) -> Option<(f64, f64)> {
         if market.market_type != MarketType::Total {
-            return Ok(None);
+            return None;
         }
 
-        // Example: NFL game with average scoring
-        // Team A expected: 24 points (lambda = 24)
-        // Team B expected: 21 points (lambda = 21)
-        // Total expected: 45 points
-        
-        let team_a_lambda = 24.0;
-        let team_b_lambda = 21.0;
-        
-        Ok(Some((team_a_lambda, team_b_lambda)))
+        let (a, b) = match self.lambda_source {
+            // Example: NFL game with average scoring
+            // Team A expected: 24 points (lambda = 24)
+            // Team B expected: 21 points (lambda = 21)
+            LambdaSource::Fixed => (24.0, 21.0),
+            LambdaSource::Hierarchical => {
+                let game = game?;
+                (*models.strengths).as_ref()?.expected_scores(&game.home_team, &game.away_team)?
+            }
+        };
+
+        let pace = ModelParameters::for_sport(market.sport).pace.unwrap_or(1.0);
+        Some((a * pace, b * pace))
+    }
+
+    /// Expected change in the total from the officials assigned to a market's game
+    ///
+    /// Zero until the game is found in the schedule and its crew announced.
+    async fn officiating_shift(&self, game: Option<&Game>) -> Result<f64> {
+        let Some(game) = game else {
+            return Ok(0.0);
+        };
+
+        let tendencies = self.reference.official_tendencies(&game.game_id).await?;
+        Ok(officiating_total_shift(&tendencies))
     }
 
     /// Simulate game outcomes using Poisson distribution
@@ -75,69 +146,56 @@ impl PoissonEvStrategy {
         team_b_lambda: f64,
         total_line: f64,
     ) -> Result<SimulationResult> {
-        use rand::Rng;
-        
-        let poisson_a = Poisson::new(team_a_lambda)?;
-        let poisson_b = Poisson::new(team_b_lambda)?;
-        
-        let mut over_count = 0;
-        let mut under_count = 0;
-        let mut total_scores = Vec::with_capacity(self.simulation_count as usize);
-        
-        let mut rng = rand::thread_rng();
-        
-        for _ in 0..self.simulation_count {
-            // Sample from Poisson distributions
-            let score_a = self.sample_poisson(&poisson_a, &mut rng);
-            let score_b = self.sample_poisson(&poisson_b, &mut rng);
-            let total = score_a + score_b;
-            
-            total_scores.push(total);
-            
-            if total as f64 > total_line {
-                over_count += 1;
-            } else {
-                under_count += 1;
+        let rate = total_rate(team_a_lambda, team_b_lambda)?;
+        let mut tally = TotalsTally::new(total_line);
+        tally.add(&self.draw_totals(rate, self.simulation_count)?);
+        Ok(tally.result(SamplingStop::Fixed))
+    }
+
+    /// Simulate in batches until sequential tests settle whether the over
+    /// probability clears `over_above` (an edge on YES) and whether it falls
+    /// short of `over_below` (an edge on NO), or the draw cap or time budget
+    /// runs out
+    fn simulate_adaptively(
+        &self,
+        sampling: &AdaptiveSampling,
+        team_a_lambda: f64,
+        team_b_lambda: f64,
+        total_line: f64,
+        (over_above, over_below): (f64, f64),
+    ) -> Result<SimulationResult> {
+        let rate = total_rate(team_a_lambda, team_b_lambda)?;
+        let mut tests = [sampling.test(over_above), sampling.test(over_below)];
+        let mut tally = TotalsTally::new(total_line);
+        let started = Instant::now();
+
+        loop {
+            let before = tally.over;
+            tally.add(&self.draw_totals(rate, sampling.batch_size())?);
+            for test in &mut tests {
+                test.observe(tally.over - before, sampling.batch_size() as u64);
+            }
+            if let Some(stop) = sampling.stop(&tests, tally.draws as u32, started) {
+                return Ok(tally.result(stop));
             }
         }
-        
-        let over_probability = over_count as f64 / self.simulation_count as f64;
-        let under_probability = under_count as f64 / self.simulation_count as f64;
-        
-        // Calculate mean and standard deviation
-        let mean: f64 = total_scores.iter().sum::<u32>() as f64 / total_scores.len() as f64;
-        let variance: f64 = total_scores.iter()
-            .map(|&x| {
-                let diff = x as f64 - mean;
-                diff * diff
-            })
-            .sum::<f64>() / total_scores.len() as f64;
-        let std_dev = variance.sqrt();
-        
-        Ok(SimulationResult {
-            over_probability,
-            under_probability,
-            mean_total: mean,
-            std_dev,
-            simulations: self.simulation_count,
-        })
     }
 
-    fn sample_poisson<R: rand::Rng>(&self, poisson: &Poisson, rng: &mut R) -> u32 {
-        // Sample from Poisson distribution
-        let lambda = poisson.lambda();
-        let mut k = 0;
-        let mut p = (-lambda).exp();
-        let mut s = p;
-        let u: f64 = rng.gen();
-        
-        while u > s {
-            k += 1;
-            p *= lambda / k as f64;
-            s += p;
-        }
-        
-        k as u32
+    /// Simulated game totals at `rate`
+    fn draw_totals(&self, rate: f64, runs: u32) -> Result<Vec<u32>> {
+        use rand::Rng;
+
+        // A sum of independent Poissons is Poisson in the summed rate
+        self.backend.poisson(&[rate], runs, rand::thread_rng().gen())
+    }
+
+    /// Over probabilities above and below which an edge clears the minimum,
+    /// on YES and on NO respectively, at the market's asks
+    fn edge_thresholds(&self, market: &Market) -> Result<(f64, f64)> {
+        let min_edge = self.min_edge_pct / dec!(100.0);
+        let yes_ask = numeric::to_f64(market.entry_price(Position::Yes).value() + min_edge)?;
+        let no_ask = numeric::to_f64(market.entry_price(Position::No).value() + min_edge)?;
+        Ok((yes_ask, 1.0 - no_ask))
     }
 
     /// Calculate confidence based on edge size and statistical significance
@@ -194,6 +252,7 @@ impl PoissonEvStrategy {
 impl Strategy for PoissonEvStrategy {
     async fn generate_signals(&self, markets: &[Market]) -> Result<Vec<Signal>> {
         let mut signals = Vec::new();
+        let mut sports: HashMap<Sport, SportModels> = HashMap::new();
 
         for market in markets {
             // Only analyze totals markets
@@ -201,28 +260,39 @@ impl Strategy for PoissonEvStrategy {
                 continue;
             }
 
-            if market.status != crate::types::MarketStatus::Active {
+            if !market.status.allows_entry() {
                 continue;
             }
 
+            let models = match sports.entry(market.sport) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(self.load_sport(market.sport).await?),
+            };
+            let game = models.games.iter().find(|g| g.matches_event(&market.event_name, market.event_time));
+
             // Get scoring rates for both teams
-            let (team_a_lambda, team_b_lambda) = match self.estimate_scoring_rates(market).await? {
+            let rates = match self.estimate_scoring_rates(market, game, models) {
                 Some(rates) => rates,
                 None => continue,
             };
 
+            let officiating_shift = self.officiating_shift(game).await?;
+            let (team_a_lambda, team_b_lambda) = adjust_lambdas(rates, officiating_shift);
+
             // Parse the total line
-            let total_line = match self.parse_total_line(market) {
-                Some(line) => line,
+            let total_line = match self.reference.parsed(market, |m| self.parse_total_line(m)).await {
+                Some(line) => *line,
                 None => continue,
             };
 
             // Run Monte Carlo simulation
-            let simulation_result = match self.simulate_game_outcomes(
-                team_a_lambda,
-                team_b_lambda,
-                total_line,
-            ) {
+            let simulated = match &self.adaptive {
+                Some(sampling) => self.edge_thresholds(market).and_then(|thresholds| {
+                    self.simulate_adaptively(sampling, team_a_lambda, team_b_lambda, total_line, thresholds)
+                }),
+                None => self.simulate_game_outcomes(team_a_lambda, team_b_lambda, total_line),
+            };
+            let simulation_result = match simulated {
                 Ok(result) => result,
                 Err(e) => {
                     debug!("Simulation failed for {}: {}", market.market_id, e);
@@ -230,23 +300,32 @@ impl Strategy for PoissonEvStrategy {
                 }
             };
 
+            let (over_probability, under_probability) = match (
+                Probability::from_f64(simulation_result.over_probability),
+                Probability::from_f64(simulation_result.under_probability),
+            ) {
+                (Ok(over), Ok(under)) => (over, under),
+                (Err(e), _) | (_, Err(e)) => {
+                    debug!("Invalid simulated probability for {}: {}", market.market_id, e);
+                    continue;
+                }
+            };
+
             // Determine if there's an edge
-            let over_edge = Decimal::from_f64_retain(simulation_result.over_probability).unwrap() 
-                          - market.yes_price;
-            let under_edge = Decimal::from_f64_retain(simulation_result.under_probability).unwrap() 
-                           - market.no_price;
+            let over_edge = over_probability.edge_over(market.entry_price(Position::Yes));
+            let under_edge = under_probability.edge_over(market.entry_price(Position::No));
 
             let (signal_type, edge_pct, fair_value) = if over_edge > self.min_edge_pct / dec!(100.0) {
                 (
                     SignalType::BuyYes,
                     over_edge * dec!(100.0),
-                    Decimal::from_f64_retain(simulation_result.over_probability).unwrap(),
+                    over_probability,
                 )
             } else if under_edge > self.min_edge_pct / dec!(100.0) {
                 (
                     SignalType::BuyNo,
                     under_edge * dec!(100.0),
-                    Decimal::from_f64_retain(simulation_result.under_probability).unwrap(),
+                    under_probability,
                 )
             } else {
                 continue;
@@ -254,6 +333,7 @@ impl Strategy for PoissonEvStrategy {
 
             let confidence = self.calculate_confidence(edge_pct, &simulation_result);
             let recommended_size = dec!(1000.0) * confidence;
+            let quote = market.quote(signal_type.to_position());
 
             let signal = Signal {
                 signal_id: Uuid::new_v4(),
@@ -263,18 +343,25 @@ impl Strategy for PoissonEvStrategy {
                 confidence,
                 edge_size: edge_pct / dec!(100.0),
                 recommended_size,
-                current_price: market.implied_probability(signal_type.to_position()),
+                current_price: quote.ask,
+                executable_size: quote.ask_size,
+                quoted_edge: fair_value.edge_over(quote.mid()),
                 fair_value,
                 generated_at: Utc::now(),
                 metadata: serde_json::json!({
                     "team_a_lambda": team_a_lambda,
                     "team_b_lambda": team_b_lambda,
+                    "officiating_shift": officiating_shift,
+                    "lambda_source": format!("{:?}", self.lambda_source).to_lowercase(),
                     "total_line": total_line,
+                    "over_spread": market.yes_quote.spread(),
+                    "under_spread": market.no_quote.spread(),
                     "simulated_mean": simulation_result.mean_total,
                     "simulated_std_dev": simulation_result.std_dev,
                     "over_probability": simulation_result.over_probability,
                     "under_probability": simulation_result.under_probability,
                     "simulations": simulation_result.simulations,
+                    "sampling_stop": simulation_result.stop.as_str(),
                 }),
             };
 
@@ -311,4 +398,144 @@ struct SimulationResult {
     mean_total: f64,
     std_dev: f64,
     simulations: u32,
+    stop: SamplingStop,
+}
+
+/// Both scoring rates summed, once checked to be usable
+fn total_rate(team_a_lambda: f64, team_b_lambda: f64) -> Result<f64> {
+    if !(team_a_lambda > 0.0 && team_b_lambda > 0.0 && (team_a_lambda + team_b_lambda).is_finite()) {
+        bail!("invalid scoring rates {} and {}", team_a_lambda, team_b_lambda);
+    }
+    Ok(team_a_lambda + team_b_lambda)
+}
+
+/// Running counts of simulated totals against a line, across batches
+struct TotalsTally {
+    line: f64,
+    draws: u64,
+    over: u64,
+    sum: f64,
+    sum_sq: f64,
+}
+
+impl TotalsTally {
+    fn new(line: f64) -> Self {
+        Self { line, draws: 0, over: 0, sum: 0.0, sum_sq: 0.0 }
+    }
+
+    fn add(&mut self, totals: &[u32]) {
+        for &total in totals {
+            let total = total as f64;
+            if total > self.line {
+                self.over += 1;
+            }
+            self.sum += total;
+            self.sum_sq += total * total;
+        }
+        self.draws += totals.len() as u64;
+    }
+
+    fn result(&self, stop: SamplingStop) -> SimulationResult {
+        let draws = self.draws.max(1) as f64;
+        let over_probability = self.over as f64 / draws;
+        let mean = self.sum / draws;
+        let variance = (self.sum_sq / draws - mean * mean).max(0.0);
+
+        SimulationResult {
+            over_probability,
+            under_probability: 1.0 - over_probability,
+            mean_total: mean,
+            std_dev: variance.sqrt(),
+            simulations: self.draws as u32,
+            stop,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PoissonEvStrategy;
+    use crate::models::SamplingStop;
+    use crate::cache::ReferenceCache;
+    use crate::config::{AdaptiveSimulationConfig, LambdaSource, ReferenceCacheConfig, SimulationConfig};
+    use crate::models::backend_from_config;
+    use crate::storage::MemoryStorage;
+    use proptest::prelude::*;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use std::sync::Arc;
+
+    fn strategy_with(simulation_count: u32, adaptive: &AdaptiveSimulationConfig) -> PoissonEvStrategy {
+        let backend = backend_from_config(&SimulationConfig::default()).unwrap();
+        let storage = Arc::new(MemoryStorage::new());
+        let reference = Arc::new(ReferenceCache::new(storage.clone(), &ReferenceCacheConfig::default()));
+        PoissonEvStrategy::new(storage, reference, backend, 5.0, simulation_count, 0.95, LambdaSource::Fixed, adaptive)
+            .unwrap()
+    }
+
+    fn strategy(simulation_count: u32) -> PoissonEvStrategy {
+        strategy_with(simulation_count, &AdaptiveSimulationConfig::default())
+    }
+
+    #[test]
+    fn adaptive_sampling_draws_more_for_marginal_edges() {
+        let strategy = strategy_with(
+            10_000,
+            &AdaptiveSimulationConfig {
+                enabled: true,
+                time_budget_ms: 60_000,
+                ..AdaptiveSimulationConfig::default()
+            },
+        );
+        let sampling = strategy.adaptive.as_ref().unwrap();
+
+        // Lambda 45 against a 44.5 line puts the over near 0.52
+        let clear = strategy.simulate_adaptively(sampling, 24.0, 21.0, 44.5, (0.80, 0.20)).unwrap();
+        assert_eq!(clear.stop, SamplingStop::Decided);
+        let marginal = strategy.simulate_adaptively(sampling, 24.0, 21.0, 44.5, (0.53, 0.20)).unwrap();
+        assert!(
+            clear.simulations < marginal.simulations,
+            "clear {} vs marginal {}",
+            clear.simulations,
+            marginal.simulations
+        );
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn simulated_probabilities_partition_outcomes(
+            team_a_lambda in 0.5f64..60.0,
+            team_b_lambda in 0.5f64..60.0,
+            total_line in 0.5f64..120.0,
+        ) {
+            let result = strategy(500)
+                .simulate_game_outcomes(team_a_lambda, team_b_lambda, total_line)
+                .unwrap();
+
+            prop_assert!((0.0..=1.0).contains(&result.over_probability));
+            prop_assert!((0.0..=1.0).contains(&result.under_probability));
+            prop_assert!((result.over_probability + result.under_probability - 1.0).abs() < 1e-9);
+        }
+
+        #[test]
+        fn confidence_stays_in_unit_interval(
+            edge_pct in (0i64..10_000).prop_map(|v| Decimal::new(v, 2)),
+            over_probability in 0.0f64..=1.0,
+            simulations in 1u32..100_000,
+        ) {
+            let result = super::SimulationResult {
+                over_probability,
+                under_probability: 1.0 - over_probability,
+                mean_total: 0.0,
+                std_dev: 0.0,
+                simulations,
+                stop: SamplingStop::Fixed,
+            };
+            let confidence = strategy(simulations).calculate_confidence(edge_pct, &result);
+
+            prop_assert!(confidence >= dec!(0) && confidence <= dec!(1));
+        }
+    }
 }
@@ -0,0 +1,225 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use crate::config::RemoteModelConfig;
+use crate::numeric;
+use crate::types::{Market, Position, Probability, Signal, SignalType, Strategy as StrategyEnum};
+use super::Strategy;
+
+/// Request body of `POST {url}/predict`
+#[derive(Debug, Serialize)]
+struct PredictRequest<'a> {
+    markets: Vec<MarketFeatures<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct MarketFeatures<'a> {
+    market_id: &'a str,
+    sport: &'a str,
+    event_name: &'a str,
+    market_type: &'a str,
+    event_time: DateTime<Utc>,
+    liquidity: Decimal,
+    yes_bid: Decimal,
+    yes_ask: Decimal,
+    no_bid: Decimal,
+    no_ask: Decimal,
+}
+
+impl<'a> MarketFeatures<'a> {
+    fn from_market(market: &'a Market) -> Self {
+        Self {
+            market_id: &market.market_id,
+            sport: market.sport.as_str(),
+            event_name: &market.event_name,
+            market_type: market.market_type.as_str(),
+            event_time: market.event_time,
+            liquidity: market.current_liquidity,
+            yes_bid: market.yes_quote.bid.value(),
+            yes_ask: market.yes_quote.ask.value(),
+            no_bid: market.no_quote.bid.value(),
+            no_ask: market.no_quote.ask.value(),
+        }
+    }
+}
+
+/// Response body; markets the model does not cover are left out
+#[derive(Debug, Deserialize)]
+struct PredictResponse {
+    model_version: String,
+    predictions: Vec<Prediction>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Prediction {
+    pub market_id: String,
+    pub yes_probability: f64,
+    /// The model's confidence in [0, 1]
+    pub confidence: f64,
+}
+
+/// Strategy 9: External Model
+///
+/// Fair values served by a model outside the bot, typically a Python model
+/// server (see `python/ml_pipeline/model_server.py`), so research models
+/// trade without first being ported to Rust. Markets are sent in batches
+/// to `POST {url}/predict`; the bot takes the side whose ask is furthest
+/// below the predicted probability once it clears `min_edge_pct`.
+pub struct RemoteModelStrategy {
+    http: Client,
+    url: String,
+    min_edge_pct: Decimal,
+    batch_size: usize,
+}
+
+impl RemoteModelStrategy {
+    pub fn new(config: &RemoteModelConfig) -> Result<Self> {
+        let http = Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()?;
+
+        Ok(Self {
+            http,
+            url: format!("{}/predict", config.url.trim_end_matches('/')),
+            min_edge_pct: numeric::to_decimal(config.min_edge_pct)
+                .context("remote_model.min_edge_pct")?,
+            batch_size: config.batch_size.max(1),
+        })
+    }
+
+    async fn predict(&self, markets: &[Market]) -> Result<PredictResponse> {
+        let request = PredictRequest {
+            markets: markets.iter().map(MarketFeatures::from_market).collect(),
+        };
+        let response = self.http.post(&self.url)
+            .json(&request)
+            .send()
+            .await
+            .with_context(|| format!("model server {}", self.url))?
+            .error_for_status()?;
+
+        response.json().await.context("decoding model server response")
+    }
+}
+
+/// Signal on the side with the larger edge at the ask, if it clears `min_edge`
+pub fn prediction_signal(market: &Market, prediction: &Prediction, min_edge: Decimal, model_version: &str) -> Result<Option<Signal>> {
+    let yes = Probability::from_f64(prediction.yes_probability)?;
+    let confidence = numeric::to_decimal(prediction.confidence.clamp(0.0, 1.0))?;
+
+    let yes_edge = yes.edge_over(market.entry_price(Position::Yes));
+    let no_edge = yes.complement().edge_over(market.entry_price(Position::No));
+    let (position, edge, fair_value) = if yes_edge >= no_edge {
+        (Position::Yes, yes_edge, yes)
+    } else {
+        (Position::No, no_edge, yes.complement())
+    };
+    if edge <= min_edge {
+        return Ok(None);
+    }
+
+    let quote = market.quote(position);
+    Ok(Some(Signal {
+        signal_id: Uuid::new_v4(),
+        market_id: market.market_id.clone(),
+        strategy: StrategyEnum::RemoteModel,
+        signal_type: SignalType::buy(position),
+        confidence,
+        edge_size: edge,
+        recommended_size: dec!(1000.0) * confidence,
+        current_price: quote.ask,
+        executable_size: quote.ask_size,
+        quoted_edge: fair_value.edge_over(quote.mid()),
+        fair_value,
+        generated_at: Utc::now(),
+        metadata: serde_json::json!({
+            "model_version": model_version,
+            "yes_probability": prediction.yes_probability,
+            "model_confidence": prediction.confidence,
+        }),
+    }))
+}
+
+#[async_trait]
+impl Strategy for RemoteModelStrategy {
+    async fn generate_signals(&self, markets: &[Market]) -> Result<Vec<Signal>> {
+        let open: Vec<Market> = markets.iter()
+            .filter(|m| m.status.allows_entry())
+            .cloned()
+            .collect();
+        let min_edge = self.min_edge_pct / dec!(100.0);
+        let mut signals = Vec::new();
+
+        for batch in open.chunks(self.batch_size) {
+            let response = self.predict(batch).await?;
+            let by_id: HashMap<&str, &Market> = batch.iter().map(|m| (m.market_id.as_str(), m)).collect();
+
+            for prediction in &response.predictions {
+                let Some(market) = by_id.get(prediction.market_id.as_str()) else {
+                    debug!("Model returned unrequested market {}", prediction.market_id);
+                    continue;
+                };
+                match prediction_signal(market, prediction, min_edge, &response.model_version) {
+                    Ok(Some(signal)) => {
+                        info!(
+                            "🐍 Model Signal: {} {:?} - Fair: {}, Edge: {:.2}% ({})",
+                            market.event_name,
+                            signal.signal_type,
+                            signal.fair_value.value(),
+                            signal.edge_size * dec!(100.0),
+                            response.model_version
+                        );
+                        signals.push(signal);
+                    }
+                    Ok(None) => {}
+                    Err(e) => debug!("Invalid prediction for {}: {}", market.market_id, e),
+                }
+            }
+        }
+
+        Ok(signals)
+    }
+
+    fn name(&self) -> &str {
+        "Remote Model"
+    }
+
+    fn is_enabled(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::market_fixture;
+
+    #[test]
+    fn takes_the_side_with_the_larger_edge() {
+        let market = market_fixture("0xmarket", dec!(0.40));
+        let prediction = |yes_probability| Prediction {
+            market_id: "0xmarket".to_string(),
+            yes_probability,
+            confidence: 0.7,
+        };
+
+        let yes = prediction_signal(&market, &prediction(0.48), dec!(0.03), "v1").unwrap().unwrap();
+        assert!(matches!(yes.signal_type, SignalType::BuyYes));
+        assert_eq!(yes.metadata["model_version"], "v1");
+
+        let no = prediction_signal(&market, &prediction(0.30), dec!(0.03), "v1").unwrap().unwrap();
+        assert!(matches!(no.signal_type, SignalType::BuyNo));
+
+        assert!(prediction_signal(&market, &prediction(0.41), dec!(0.03), "v1").unwrap().is_none());
+        assert!(prediction_signal(&market, &prediction(1.7), dec!(0.03), "v1").is_err());
+    }
+}
@@ -0,0 +1,298 @@
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use rhai::{module_resolvers::DummyModuleResolver, Dynamic, Engine, Scope, AST};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::config::ScriptedConfig;
+use crate::numeric;
+use crate::storage::Storage;
+use crate::types::{Market, Position, Probability, Signal, SignalType, Strategy as StrategyEnum};
+use super::{sharp_fair_value, OddsFreshness, Strategy};
+
+const ENTRY_POINT: &str = "generate_signal";
+
+/// What a script returns to trade; returning `()` skips the market
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ScriptSignal {
+    /// "yes" or "no"
+    pub side: String,
+    /// The script's probability that `side` wins
+    pub fair_value: f64,
+    pub confidence: f64,
+}
+
+/// A compiled script and the sandboxed engine it runs in
+///
+/// The engine has no module imports and so no file access; every call is
+/// bounded in operations, call depth and collection sizes.
+pub struct Script {
+    engine: Engine,
+    ast: AST,
+}
+
+impl Script {
+    pub fn compile(source: &str, limits: &ScriptedConfig) -> Result<Self> {
+        let mut engine = Engine::new();
+        engine.set_module_resolver(DummyModuleResolver::new());
+        engine.set_max_operations(limits.max_operations);
+        engine.set_max_call_levels(limits.max_call_levels);
+        engine.set_max_string_size(limits.max_collection_size);
+        engine.set_max_array_size(limits.max_collection_size);
+        engine.set_max_map_size(limits.max_collection_size);
+        engine.on_print(|text| debug!("script: {}", text));
+        engine.on_debug(|text, _, position| debug!("script {}: {}", position, text));
+
+        let ast = engine.compile(source).map_err(|e| anyhow!("{}", e))?;
+        if !ast.iter_functions().any(|f| f.name == ENTRY_POINT && f.params.len() == 2) {
+            bail!("script does not define {}(market, context)", ENTRY_POINT);
+        }
+
+        Ok(Self { engine, ast })
+    }
+
+    /// Run `generate_signal` on one market
+    pub fn call(&self, market: serde_json::Value, context: serde_json::Value) -> Result<Option<ScriptSignal>> {
+        let args = (
+            rhai::serde::to_dynamic(market).map_err(|e| anyhow!("{}", e))?,
+            rhai::serde::to_dynamic(context).map_err(|e| anyhow!("{}", e))?,
+        );
+        let result: Dynamic = self.engine
+            .call_fn(&mut Scope::new(), &self.ast, ENTRY_POINT, args)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        if result.is_unit() {
+            return Ok(None);
+        }
+        rhai::serde::from_dynamic(&result)
+            .map(Some)
+            .map_err(|e| anyhow!("invalid signal returned: {}", e))
+    }
+}
+
+/// Strategy 8: User Script
+///
+/// Power users prototype ideas in Rhai without touching the bot. The script
+/// defines `generate_signal(market, context)`, sees the market's prices, the
+/// bookmaker lines and sharp fair value, and the latest order book features,
+/// and returns `#{ side, fair_value, confidence }` to trade or `()` to pass.
+/// Edge and sizing are computed here, as for the other strategies, and only
+/// an edge at the ask over `min_edge_pct` is traded.
+pub struct ScriptedStrategy {
+    storage: Arc<dyn Storage>,
+    script: Script,
+    freshness: OddsFreshness,
+    /// Fraction, not percent
+    min_edge: Decimal,
+}
+
+impl ScriptedStrategy {
+    pub fn new(storage: Arc<dyn Storage>, config: &ScriptedConfig, freshness: OddsFreshness) -> Result<Self> {
+        let source = std::fs::read_to_string(&config.script_path)
+            .with_context(|| format!("reading strategies.scripted.script_path {}", config.script_path))?;
+        let script = Script::compile(&source, config)
+            .with_context(|| format!("compiling {}", config.script_path))?;
+        let min_edge = numeric::to_decimal(config.min_edge_pct)
+            .context("strategies.scripted.min_edge_pct")? / dec!(100.0);
+
+        Ok(Self { storage, script, freshness, min_edge })
+    }
+
+    fn market_value(market: &Market) -> Result<serde_json::Value> {
+        let price = |p: crate::types::Price| numeric::to_f64(p.value());
+        Ok(json!({
+            "market_id": market.market_id,
+            "sport": market.sport.as_str(),
+            "event_name": market.event_name,
+            "market_type": market.market_type.as_str(),
+            "hours_to_start": (market.event_time - Utc::now()).num_minutes() as f64 / 60.0,
+            "liquidity": numeric::to_f64(market.current_liquidity)?,
+            "yes_price": price(market.yes_price)?,
+            "no_price": price(market.no_price)?,
+            "yes_bid": price(market.yes_quote.bid)?,
+            "yes_ask": price(market.yes_quote.ask)?,
+            "no_bid": price(market.no_quote.bid)?,
+            "no_ask": price(market.no_quote.ask)?,
+        }))
+    }
+
+    /// Bookmaker lines, their sharp consensus and order book features
+    async fn context_value(&self, market: &Market) -> Result<serde_json::Value> {
+        let now = Utc::now();
+        let odds = self.storage.fetch_bookmaker_odds(&market.market_id).await?;
+        let sharp = sharp_fair_value(&odds, &self.freshness, now)
+            .map(|(yes, _)| numeric::to_f64(yes.value()))
+            .transpose()?;
+        let microstructure = self.storage.latest_microstructure(&market.market_id).await?;
+
+        let lines = odds.iter()
+            .map(|o| Ok(json!({
+                "bookmaker": o.bookmaker.as_str(),
+                "yes_probability": numeric::to_f64(o.yes_implied_prob.value())?,
+                "no_probability": numeric::to_f64(o.no_implied_prob.value())?,
+                "age_secs": o.age(now).num_seconds(),
+            })))
+            .collect::<Result<Vec<_>>>()?;
+        let features = microstructure
+            .map(|m| -> Result<serde_json::Value> {
+                Ok(json!({
+                    "imbalance": numeric::to_f64(m.imbalance)?,
+                    "taker_flow": m.taker_flow().map(numeric::to_f64).transpose()?,
+                    "bid_depth": numeric::to_f64(m.bid_depth)?,
+                    "ask_depth": numeric::to_f64(m.ask_depth)?,
+                }))
+            })
+            .transpose()?;
+
+        Ok(json!({
+            "odds": lines,
+            "sharp_yes_probability": sharp,
+            "microstructure": features,
+        }))
+    }
+
+    fn signal(&self, market: &Market, returned: ScriptSignal) -> Result<Option<Signal>> {
+        let position = match returned.side.as_str() {
+            "yes" => Position::Yes,
+            "no" => Position::No,
+            other => bail!("side must be \"yes\" or \"no\", got {:?}", other),
+        };
+        let fair_value = Probability::from_f64(returned.fair_value)?;
+        let confidence = numeric::to_decimal(returned.confidence.clamp(0.0, 1.0))?;
+
+        let quote = market.quote(position);
+        let edge = fair_value.edge_over(quote.ask);
+        if edge <= self.min_edge {
+            debug!("Script signal on {} has {} edge at the ask, under the {} floor", market.market_id, edge, self.min_edge);
+            return Ok(None);
+        }
+
+        Ok(Some(Signal {
+            signal_id: Uuid::new_v4(),
+            market_id: market.market_id.clone(),
+            strategy: StrategyEnum::Scripted,
+            signal_type: SignalType::buy(position),
+            confidence,
+            edge_size: edge,
+            recommended_size: dec!(1000.0) * confidence,
+            current_price: quote.ask,
+            executable_size: quote.ask_size,
+            quoted_edge: fair_value.edge_over(quote.mid()),
+            fair_value,
+            generated_at: Utc::now(),
+            metadata: json!({
+                "side": returned.side,
+                "script_fair_value": returned.fair_value,
+                "script_confidence": returned.confidence,
+            }),
+        }))
+    }
+}
+
+#[async_trait]
+impl Strategy for ScriptedStrategy {
+    async fn generate_signals(&self, markets: &[Market]) -> Result<Vec<Signal>> {
+        let mut signals = Vec::new();
+
+        for market in markets {
+            if !market.status.allows_entry() {
+                continue;
+            }
+
+            let context = self.context_value(market).await?;
+            let returned = match self.script.call(Self::market_value(market)?, context) {
+                Ok(Some(returned)) => returned,
+                Ok(None) => continue,
+                Err(e) => {
+                    warn!("Script failed on {}: {}", market.market_id, e);
+                    continue;
+                }
+            };
+
+            match self.signal(market, returned) {
+                Ok(Some(signal)) => {
+                    info!(
+                        "📜 Script Signal: {} {:?} - Fair: {}, Edge: {:.2}%",
+                        market.event_name,
+                        signal.signal_type,
+                        signal.fair_value.value(),
+                        signal.edge_size * dec!(100.0)
+                    );
+                    signals.push(signal);
+                }
+                Ok(None) => {}
+                Err(e) => warn!("Script returned an invalid signal for {}: {}", market.market_id, e),
+            }
+        }
+
+        Ok(signals)
+    }
+
+    fn name(&self) -> &str {
+        "Scripted"
+    }
+
+    fn is_enabled(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_scripts_within_limits() {
+        let limits = ScriptedConfig { max_operations: 10_000, ..ScriptedConfig::default() };
+        let script = Script::compile(r#"
+            fn generate_signal(market, context) {
+                if context.sharp_yes_probability == () { return; }
+                let gap = context.sharp_yes_probability - market.yes_ask;
+                if gap > 0.03 {
+                    #{ side: "yes", fair_value: context.sharp_yes_probability, confidence: 0.6 }
+                }
+            }
+        "#, &limits).unwrap();
+
+        let market = json!({ "yes_ask": 0.50 });
+        let signal = script.call(market.clone(), json!({ "sharp_yes_probability": 0.56 })).unwrap();
+        assert_eq!(signal, Some(ScriptSignal { side: "yes".to_string(), fair_value: 0.56, confidence: 0.6 }));
+        assert_eq!(script.call(market.clone(), json!({ "sharp_yes_probability": null })).unwrap(), None);
+
+        let runaway = Script::compile("fn generate_signal(market, context) { loop {} }", &limits).unwrap();
+        assert!(runaway.call(market, json!({})).is_err());
+
+        assert!(Script::compile("fn on_market(market) { () }", &limits).is_err());
+        assert!(Script::compile(r#"import "os" as os; fn generate_signal(m, c) { () }"#, &limits)
+            .and_then(|s| s.call(json!({}), json!({})))
+            .is_err());
+    }
+
+    #[test]
+    fn signals_under_the_edge_floor_are_dropped() {
+        let dir = std::env::temp_dir().join(format!("scripted-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("fair.rhai");
+        std::fs::write(&path, r#"fn generate_signal(market, context) { #{ side: "yes", fair_value: 0.55, confidence: 0.5 } }"#).unwrap();
+        let config = ScriptedConfig {
+            script_path: path.to_string_lossy().into_owned(),
+            min_edge_pct: 4.0,
+            ..ScriptedConfig::default()
+        };
+        let freshness = OddsFreshness::from_config(&crate::test_support::test_config("").strategies.clv_arb).unwrap();
+        let strategy = ScriptedStrategy::new(Arc::new(crate::storage::MemoryStorage::new()), &config, freshness).unwrap();
+        let returned = || ScriptSignal { side: "yes".to_string(), fair_value: 0.55, confidence: 0.5 };
+
+        // Five points at a 0.50 ask clear a 4% floor; two at 0.53 do not
+        let signal = strategy.signal(&crate::test_support::market_fixture("0xscripted", dec!(0.50)), returned()).unwrap().unwrap();
+        assert_eq!(signal.edge_size, dec!(0.05));
+        assert!(strategy.signal(&crate::test_support::market_fixture("0xscripted", dec!(0.53)), returned()).unwrap().is_none());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,64 +1,194 @@
 use anyhow::Result;
-use sqlx::PgPool;
-use tokio::time::{interval, Duration};
-use tracing::{info, error};
+use chrono::{DateTime, Utc};
+use rust_decimal_macros::dec;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::time::{interval, Duration, Instant};
+use tracing::{debug, info, error, warn};
 
-use crate::types::{Signal, Market};
+use crate::cache::ReferenceCache;
+use crate::types::{Signal, Market, Sport};
 use crate::config::Config;
-use super::{Strategy, ClvArbitrageStrategy, PoissonEvStrategy};
+use crate::data::DirtyMarkets;
+use crate::models::{backend_from_config, SeasonSimulator};
+use crate::events::{Event, EventBus};
+use crate::monitoring::{Latencies, LatencyStage};
+use crate::storage::Storage;
+use super::cadence::Cadence;
+use super::{Strategy, ClvArbitrageStrategy, ComboPricingStrategy, MiddlingStrategy, MlbPitchingStrategy, OddsFreshness, PoissonEvStrategy, RemoteModelStrategy, ScriptedStrategy, SeasonFuturesStrategy};
+
+/// A strategy and the sports routed to it (`None` for all)
+struct RoutedStrategy {
+    strategy: Box<dyn Strategy>,
+    sports: Option<Vec<Sport>>,
+}
+
+impl RoutedStrategy {
+    fn accepts(&self, market: &Market) -> bool {
+        self.sports.as_ref().map_or(true, |sports| sports.contains(&market.sport))
+    }
+}
+
+/// Run-loop bookkeeping per active market
+#[derive(Default)]
+struct Schedule {
+    evaluated: HashMap<String, Instant>,
+    /// Marked by ingestion but not yet due under the cadence, with when
+    pending: HashMap<String, DateTime<Utc>>,
+}
 
 pub struct SignalGenerator {
-    db_pool: PgPool,
-    strategies: Vec<Box<dyn Strategy>>,
+    storage: Arc<dyn Storage>,
+    events: EventBus,
+    strategies: Vec<RoutedStrategy>,
+    cadence: Cadence,
+    /// Markets ingestion saw move; `None` re-evaluates every market when due
+    dirty: Option<DirtyMarkets>,
+    latencies: Latencies,
+    full_sweep_interval: Duration,
 }
 
 impl SignalGenerator {
-    pub async fn new(db_pool: PgPool, config: &Config) -> Result<Self> {
-        let mut strategies: Vec<Box<dyn Strategy>> = Vec::new();
+    pub async fn new(
+        storage: Arc<dyn Storage>,
+        events: EventBus,
+        reference: Arc<ReferenceCache>,
+        dirty: DirtyMarkets,
+        latencies: Latencies,
+        config: &Config,
+    ) -> Result<Self> {
+        let mut strategies: Vec<RoutedStrategy> = Vec::new();
+        let route = |key: &str, strategy: Box<dyn Strategy>| RoutedStrategy {
+            strategy,
+            sports: config.strategies.sports_for(key).map(<[Sport]>::to_vec),
+        };
+
+        // Monte Carlo draws for every simulating strategy
+        let backend = backend_from_config(&config.simulation)?;
+        info!("🎲 Simulation backend: {}", backend.name());
 
         // Initialize enabled strategies
         if config.strategies.enabled_strategies.contains(&"clv_arb".to_string()) {
             let clv_strategy = ClvArbitrageStrategy::new(
-                db_pool.clone(),
+                storage.clone(),
                 config.strategies.clv_arb.min_divergence_pct,
                 config.strategies.clv_arb.max_hold_hours,
-            );
-            strategies.push(Box::new(clv_strategy));
+                OddsFreshness::from_config(&config.strategies.clv_arb)?,
+                chrono::Duration::seconds(config.strategies.clv_arb.large_trade_lookback_secs as i64),
+            )?;
+            strategies.push(route("clv_arb", Box::new(clv_strategy)));
             info!("✅ CLV Arbitrage strategy enabled");
         }
 
         if config.strategies.enabled_strategies.contains(&"poisson_ev".to_string()) {
             let poisson_strategy = PoissonEvStrategy::new(
-                db_pool.clone(),
+                storage.clone(),
+                reference.clone(),
+                backend.clone(),
                 config.strategies.poisson_ev.min_edge_pct,
                 config.strategies.poisson_ev.simulation_count,
                 config.strategies.poisson_ev.min_significance,
-            );
-            strategies.push(Box::new(poisson_strategy));
+                config.strategies.poisson_ev.lambda_source,
+                &config.strategies.poisson_ev.adaptive,
+            )?;
+            strategies.push(route("poisson_ev", Box::new(poisson_strategy)));
             info!("✅ Poisson EV strategy enabled");
         }
 
+        if config.strategies.enabled_strategies.contains(&"combo".to_string()) {
+            let combo_strategy = ComboPricingStrategy::new(
+                reference.clone(),
+                config.strategies.combo.min_edge_pct,
+                config.strategies.combo.simulation_count,
+            )?;
+            strategies.push(route("combo", Box::new(combo_strategy)));
+            info!("✅ Combo pricing strategy enabled");
+        }
+
+        if config.strategies.enabled_strategies.contains(&"futures".to_string()) {
+            let simulator = Arc::new(SeasonSimulator::new(
+                storage.clone(),
+                backend.clone(),
+                config.strategies.futures.simulation_count,
+            ));
+            let futures_strategy = SeasonFuturesStrategy::new(
+                simulator,
+                config.strategies.futures.min_edge_pct,
+            )?;
+            strategies.push(route("futures", Box::new(futures_strategy)));
+            info!("✅ Season futures strategy enabled");
+        }
+
+        if config.strategies.enabled_strategies.contains(&"middle".to_string()) {
+            let middling_strategy = MiddlingStrategy::new(
+                storage.clone(),
+                &config.strategies.middle,
+                OddsFreshness::from_config(&config.strategies.clv_arb)?,
+            )?;
+            strategies.push(route("middle", Box::new(middling_strategy)));
+            info!("✅ Middling strategy enabled");
+        }
+
+        if config.strategies.enabled_strategies.contains(&"mlb".to_string()) {
+            let mlb_strategy = MlbPitchingStrategy::new(
+                storage.clone(),
+                reference.clone(),
+                &config.strategies.mlb,
+            )?;
+            strategies.push(route("mlb", Box::new(mlb_strategy)));
+            info!("✅ MLB pitching strategy enabled");
+        }
+
+        if config.strategies.enabled_strategies.contains(&"scripted".to_string()) {
+            let scripted_strategy = ScriptedStrategy::new(
+                storage.clone(),
+                &config.strategies.scripted,
+                OddsFreshness::from_config(&config.strategies.clv_arb)?,
+            )?;
+            strategies.push(route("scripted", Box::new(scripted_strategy)));
+            info!("✅ Scripted strategy enabled ({})", config.strategies.scripted.script_path);
+        }
+
+        if config.strategies.enabled_strategies.contains(&"remote_model".to_string()) {
+            let remote_strategy = RemoteModelStrategy::new(&config.strategies.remote_model)?;
+            strategies.push(route("remote_model", Box::new(remote_strategy)));
+            info!("✅ Remote model strategy enabled ({})", config.strategies.remote_model.url);
+        }
+
+        let incremental = &config.strategies.incremental;
         Ok(Self {
-            db_pool,
+            storage,
+            events,
             strategies,
+            cadence: Cadence::from_config(&config.strategies.cadence)?,
+            dirty: incremental.enabled.then_some(dirty),
+            latencies,
+            full_sweep_interval: Duration::from_secs(incremental.full_sweep_interval_secs),
         })
     }
 
     pub async fn run(&self) -> Result<()> {
-        let mut tick = interval(Duration::from_secs(60)); // Run every minute
+        let mut tick = interval(self.cadence.tick());
+        let mut schedule = Schedule::default();
 
         info!("🎯 Signal generator started with {} strategies", self.strategies.len());
 
         loop {
             tick.tick().await;
 
-            if let Err(e) = self.generate_and_store_signals().await {
+            if let Some(dirty) = &self.dirty {
+                for (market_id, marked_at) in dirty.take_marked() {
+                    schedule.pending.entry(market_id).or_insert(marked_at);
+                }
+            }
+            if let Err(e) = self.generate_due(&mut schedule).await {
                 error!("Error generating signals: {}", e);
             }
         }
     }
 
-    async fn generate_and_store_signals(&self) -> Result<()> {
+    /// A full sweep over every active market
+    pub(crate) async fn generate_and_store_signals(&self) -> Result<()> {
         // Fetch active markets
         let markets = self.fetch_active_markets().await?;
         
@@ -67,14 +197,90 @@ impl SignalGenerator {
         }
 
         info!("📊 Analyzing {} markets", markets.len());
+        self.generate_for(&markets).await?;
+        Ok(())
+    }
+
+    /// Evaluate the markets whose cadence interval has passed
+    ///
+    /// In incremental mode a due market is skipped unless ingestion marked
+    /// it or its full-sweep interval has passed too.
+    async fn generate_due(&self, schedule: &mut Schedule) -> Result<()> {
+        let markets = self.fetch_active_markets().await?;
+        let now = Utc::now();
+
+        // Forget markets that are no longer active
+        let active: HashSet<&str> = markets.iter().map(|m| m.market_id.as_str()).collect();
+        schedule.evaluated.retain(|id, _| active.contains(id.as_str()));
+        schedule.pending.retain(|id, _| active.contains(id.as_str()));
 
-        // Run all strategies
-        for strategy in &self.strategies {
-            match strategy.generate_signals(&markets).await {
+        let due: HashSet<String> = markets.iter()
+            .filter(|m| self.is_due(m, schedule, now))
+            .map(|m| m.market_id.clone())
+            .collect();
+        if due.is_empty() {
+            return Ok(());
+        }
+
+        let markets = with_same_game(markets, &due);
+        debug!("📊 Analyzing {} markets ({} due)", markets.len(), due.len());
+        let signaled = self.generate_for(&markets).await?;
+
+        // How long a move took to become a signal; cadence waits count too
+        let stored_at = Utc::now();
+        for (market_id, marked_at) in &schedule.pending {
+            if signaled.contains(market_id) {
+                self.latencies.record(LatencyStage::Signal, stored_at - *marked_at);
+            }
+        }
+
+        let evaluated_at = Instant::now();
+        for market in &markets {
+            schedule.pending.remove(&market.market_id);
+            schedule.evaluated.insert(market.market_id.clone(), evaluated_at);
+        }
+
+        Ok(())
+    }
+
+    fn is_due(&self, market: &Market, schedule: &Schedule, now: DateTime<Utc>) -> bool {
+        let Some(last) = schedule.evaluated.get(&market.market_id) else {
+            return true;
+        };
+        let since = last.elapsed();
+
+        since >= self.cadence.interval_for(market, now)
+            && (self.dirty.is_none()
+                || since >= self.full_sweep_interval
+                || schedule.pending.contains_key(&market.market_id))
+    }
+
+    /// Run each strategy on the markets of its routed sports; returns the
+    /// markets signalled on
+    async fn generate_for(&self, markets: &[Market]) -> Result<HashSet<String>> {
+        let mut signaled = HashSet::new();
+        for routed in &self.strategies {
+            let eligible: Vec<Market> = markets.iter()
+                .filter(|m| routed.accepts(m))
+                .cloned()
+                .collect();
+            if eligible.is_empty() {
+                continue;
+            }
+
+            let strategy = &routed.strategy;
+            match strategy.generate_signals(&eligible).await {
                 Ok(signals) => {
+                    // For the coverage report; losing it must not lose the signals
+                    let ids: Vec<String> = eligible.iter().map(|m| m.market_id.clone()).collect();
+                    if let Err(e) = self.storage.record_market_evaluations(strategy.name(), &ids, Utc::now()).await {
+                        warn!("Failed to record {} evaluations: {}", strategy.name(), e);
+                    }
+
                     if !signals.is_empty() {
                         info!("✨ {} generated {} signals", strategy.name(), signals.len());
                         self.store_signals(&signals).await?;
+                        signaled.extend(signals.iter().map(|s| s.market_id.clone()));
                     }
                 }
                 Err(e) => {
@@ -83,91 +289,34 @@ impl SignalGenerator {
             }
         }
 
-        Ok(())
+        Ok(signaled)
     }
 
     async fn fetch_active_markets(&self) -> Result<Vec<Market>> {
-        let rows = sqlx::query!(
-            r#"
-            SELECT 
-                market_id,
-                sport,
-                event_name,
-                event_time,
-                market_type,
-                description,
-                current_liquidity,
-                yes_price,
-                no_price,
-                created_at,
-                updated_at
-            FROM markets
-            WHERE status = 'active'
-                AND event_time > NOW()
-                AND current_liquidity >= 5000
-            ORDER BY event_time ASC
-            LIMIT 100
-            "#
-        )
-        .fetch_all(&self.db_pool)
-        .await?;
-
-        let markets: Vec<Market> = rows.into_iter()
-            .filter_map(|row| {
-                // Parse the market data
-                Some(Market {
-                    market_id: row.market_id,
-                    sport: match row.sport.as_str() {
-                        "NFL" => crate::types::Sport::NFL,
-                        "NBA" => crate::types::Sport::NBA,
-                        "Premier League" => crate::types::Sport::PremierLeague,
-                        "MLB" => crate::types::Sport::MLB,
-                        _ => return None,
-                    },
-                    event_name: row.event_name,
-                    event_time: row.event_time,
-                    market_type: serde_json::from_str(&row.market_type).ok()?,
-                    description: row.description,
-                    resolution_source: None,
-                    min_liquidity: rust_decimal::Decimal::ZERO,
-                    current_liquidity: row.current_liquidity,
-                    yes_price: row.yes_price,
-                    no_price: row.no_price,
-                    status: crate::types::MarketStatus::Active,
-                    created_at: row.created_at,
-                    updated_at: row.updated_at,
-                })
-            })
-            .collect();
-
-        Ok(markets)
+        self.storage.fetch_active_markets(dec!(5000), 100).await
     }
 
     async fn store_signals(&self, signals: &[Signal]) -> Result<()> {
         for signal in signals {
-            sqlx::query!(
-                r#"
-                INSERT INTO signals (
-                    signal_id, market_id, strategy, signal_type,
-                    confidence, edge_size, recommended_size,
-                    current_price, fair_value, metadata
-                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
-                "#,
-                signal.signal_id,
-                signal.market_id,
-                signal.strategy.as_str(),
-                serde_json::to_string(&signal.signal_type)?,
-                signal.confidence,
-                signal.edge_size,
-                signal.recommended_size,
-                signal.current_price,
-                signal.fair_value,
-                signal.metadata,
-            )
-            .execute(&self.db_pool)
-            .await?;
+            self.storage.insert_signal(signal).await?;
+            self.events.publish(Event::SignalGenerated(signal.clone())).await;
         }
 
         Ok(())
     }
 }
+
+/// Due markets plus the other markets on the same games
+///
+/// Strategies price some markets off their siblings (a combo off the game's
+/// moneyline and total), so evaluating one re-evaluates the whole game.
+fn with_same_game(markets: Vec<Market>, due: &HashSet<String>) -> Vec<Market> {
+    let games: HashSet<(Sport, DateTime<Utc>)> = markets.iter()
+        .filter(|m| due.contains(&m.market_id))
+        .map(|m| (m.sport, m.event_time))
+        .collect();
+
+    markets.into_iter()
+        .filter(|m| games.contains(&(m.sport, m.event_time)))
+        .collect()
+}
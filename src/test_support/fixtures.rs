@@ -0,0 +1,177 @@
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::types::{
+    Bookmaker, BookmakerOdds, Market, MarketStatus, MarketTokens, MarketType, Position, Price, Probability,
+    Quote, Signal, SignalType, Sport, Strategy, Trade, TradeOrigin, TradeStatus, POLYMARKET_VENUE,
+};
+
+/// Default config pointed at a test database
+pub fn test_config(database_url: &str) -> Config {
+    let mut config = Config::default();
+    config.database.url = database_url.to_string();
+    config.redis.enabled = false;
+    config
+}
+
+/// An active NFL moneyline market starting tomorrow with ample liquidity
+pub fn market_fixture(market_id: &str, yes_price: Decimal) -> Market {
+    let now = Utc::now();
+    let yes_price = Price::new(yes_price).expect("fixture price in [0, 1]");
+    let no_price = yes_price.complement();
+
+    Market {
+        market_id: market_id.to_string(),
+        sport: Sport::NFL,
+        event_name: format!("Fixture game {}", market_id),
+        event_time: now + Duration::days(1),
+        market_type: MarketType::Moneyline,
+        description: None,
+        resolution_source: None,
+        min_liquidity: dec!(5000),
+        current_liquidity: dec!(25000),
+        yes_price,
+        no_price,
+        yes_quote: Quote::at(yes_price),
+        no_quote: Quote::at(no_price),
+        status: MarketStatus::Active,
+        tokens: Some(MarketTokens {
+            condition_id: format!("0xcondition{}", market_id),
+            question_id: format!("0xquestion{}", market_id),
+            yes_token_id: "1".to_string(),
+            no_token_id: "2".to_string(),
+        }),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+/// A fresh CLV signal to buy YES on `market` at its ask, worth `fair_value`
+pub fn signal_fixture(market: &Market, fair_value: Decimal) -> Signal {
+    let fair_value = Probability::new(fair_value).expect("fixture probability in [0, 1]");
+    let ask = market.yes_quote.ask;
+
+    Signal {
+        signal_id: Uuid::new_v4(),
+        market_id: market.market_id.clone(),
+        strategy: Strategy::ClvArbitrage,
+        signal_type: SignalType::BuyYes,
+        confidence: dec!(0.5),
+        edge_size: fair_value.edge_over(ask),
+        recommended_size: dec!(500),
+        current_price: ask,
+        executable_size: None,
+        quoted_edge: fair_value.edge_over(ask),
+        fair_value,
+        generated_at: Utc::now(),
+        metadata: serde_json::json!({}),
+    }
+}
+
+/// An open bot trade of `quantity` YES shares on `market_id` entered at `entry_price`
+pub fn trade_fixture(market_id: &str, entry_price: Decimal, quantity: Decimal) -> Trade {
+    Trade {
+        trade_id: Uuid::new_v4(),
+        market_id: market_id.to_string(),
+        strategy: Strategy::ClvArbitrage,
+        position: Position::Yes,
+        quantity,
+        entry_price,
+        exit_price: None,
+        entry_time: Utc::now(),
+        exit_time: None,
+        gas_cost: None,
+        fees: None,
+        slippage: None,
+        pnl: None,
+        pnl_percent: None,
+        max_adverse_excursion: None,
+        max_favorable_excursion: None,
+        status: TradeStatus::Open,
+        tx_hash_entry: None,
+        tx_hash_exit: None,
+        venue: POLYMARKET_VENUE.to_string(),
+        origin: TradeOrigin::Bot,
+    }
+}
+
+/// Fresh odds from `bookmaker` implying `yes_prob` (no vig)
+pub fn odds_fixture(market_id: &str, bookmaker: Bookmaker, yes_prob: Decimal) -> BookmakerOdds {
+    let yes_prob = Probability::new(yes_prob).expect("fixture probability in [0, 1]");
+    let no_prob = yes_prob.complement();
+
+    BookmakerOdds {
+        bookmaker,
+        market_id: market_id.to_string(),
+        yes_odds: yes_prob.to_decimal_odds().expect("fixture probability above zero"),
+        no_odds: no_prob.to_decimal_odds().expect("fixture probability below one"),
+        yes_implied_prob: yes_prob,
+        no_implied_prob: no_prob,
+        timestamp: Utc::now(),
+        source_timestamp: None,
+    }
+}
+
+pub async fn seed_market(pool: &PgPool, market: &Market) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO markets (
+            market_id, sport, event_name, event_time, market_type,
+            description, min_liquidity, current_liquidity,
+            yes_price, no_price, yes_bid, yes_ask, no_bid, no_ask, status,
+            condition_id, question_id, yes_token_id, no_token_id
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, 'active', $15, $16, $17, $18)
+        "#,
+    )
+    .bind(&market.market_id)
+    .bind(market.sport.as_str())
+    .bind(&market.event_name)
+    .bind(market.event_time)
+    // Stored the way the storage layer reads it back (serde JSON)
+    .bind(serde_json::to_string(&market.market_type)?)
+    .bind(&market.description)
+    .bind(market.min_liquidity)
+    .bind(market.current_liquidity)
+    .bind(market.yes_price.value())
+    .bind(market.no_price.value())
+    .bind(market.yes_quote.bid.value())
+    .bind(market.yes_quote.ask.value())
+    .bind(market.no_quote.bid.value())
+    .bind(market.no_quote.ask.value())
+    .bind(market.tokens.as_ref().map(|t| t.condition_id.as_str()))
+    .bind(market.tokens.as_ref().map(|t| t.question_id.as_str()))
+    .bind(market.tokens.as_ref().map(|t| t.yes_token_id.as_str()))
+    .bind(market.tokens.as_ref().map(|t| t.no_token_id.as_str()))
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn seed_bookmaker_odds(pool: &PgPool, odds: &BookmakerOdds) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO bookmaker_odds (
+            market_id, bookmaker, odds_type, yes_odds, no_odds,
+            yes_implied_prob, no_implied_prob, timestamp, source_timestamp
+        ) VALUES ($1, $2, 'moneyline', $3, $4, $5, $6, $7, $8)
+        "#,
+    )
+    .bind(&odds.market_id)
+    .bind(odds.bookmaker.as_str())
+    .bind(odds.yes_odds)
+    .bind(odds.no_odds)
+    .bind(odds.yes_implied_prob.value())
+    .bind(odds.no_implied_prob.value())
+    .bind(odds.timestamp)
+    .bind(odds.source_timestamp)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
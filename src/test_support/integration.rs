@@ -0,0 +1,185 @@
+use chrono::{Duration, Utc};
+use rust_decimal_macros::dec;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::cache::{InProcessCache, ReferenceCache};
+use crate::data::DirtyMarkets;
+use crate::events::EventBus;
+use crate::execution::PaperExecutor;
+use crate::monitoring::{Latencies, Notifier};
+use crate::risk::RiskManager;
+use crate::storage::{MarketRepo, MemoryStorage, SignalRepo, Storage, TradeRepo};
+use crate::strategies::SignalGenerator;
+use crate::types::{
+    Bookmaker, Position, SignalType, Strategy, Trade, TradeOrigin, TradeStatus, Usdc, POLYMARKET_VENUE,
+};
+use super::*;
+
+#[tokio::test]
+#[ignore = "requires Docker"]
+async fn clv_signal_is_generated_and_stored_for_lagging_market() {
+    let db = TestDatabase::start().await.unwrap();
+    let config = test_config(&db.url);
+    let storage: Arc<dyn Storage> = db.storage.clone();
+
+    // Polymarket has YES at 0.40 while Pinnacle implies 0.50
+    let market = market_fixture("0xfixture01", dec!(0.40));
+    seed_market(&db.pool, &market).await.unwrap();
+    seed_bookmaker_odds(&db.pool, &odds_fixture(&market.market_id, Bookmaker::Pinnacle, dec!(0.50)))
+        .await
+        .unwrap();
+
+    let reference = Arc::new(ReferenceCache::new(storage.clone(), &config.reference_cache));
+    let generator = SignalGenerator::new(storage.clone(), EventBus::new(Arc::new(InProcessCache::new(100))), reference, DirtyMarkets::new(dec!(0.005)), Latencies::new(), &config).await.unwrap();
+    run_signal_generation(&generator).await.unwrap();
+
+    let pending = storage
+        .fetch_pending_signals(Utc::now() - Duration::minutes(5), 10)
+        .await
+        .unwrap();
+
+    assert_eq!(pending.len(), 1);
+    let signal = &pending[0];
+    assert_eq!(signal.market_id, market.market_id);
+    assert_eq!(signal.strategy, Strategy::ClvArbitrage);
+    assert!(matches!(signal.signal_type, SignalType::BuyYes));
+    assert_eq!(signal.edge_size, dec!(0.10));
+}
+
+#[tokio::test]
+#[ignore = "requires Docker"]
+async fn no_signal_when_market_agrees_with_bookmakers() {
+    let db = TestDatabase::start().await.unwrap();
+    let config = test_config(&db.url);
+    let storage: Arc<dyn Storage> = db.storage.clone();
+
+    let market = market_fixture("0xfixture02", dec!(0.50));
+    seed_market(&db.pool, &market).await.unwrap();
+    seed_bookmaker_odds(&db.pool, &odds_fixture(&market.market_id, Bookmaker::Pinnacle, dec!(0.51)))
+        .await
+        .unwrap();
+
+    let reference = Arc::new(ReferenceCache::new(storage.clone(), &config.reference_cache));
+    let generator = SignalGenerator::new(storage.clone(), EventBus::new(Arc::new(InProcessCache::new(100))), reference, DirtyMarkets::new(dec!(0.005)), Latencies::new(), &config).await.unwrap();
+    run_signal_generation(&generator).await.unwrap();
+
+    let pending = storage
+        .fetch_pending_signals(Utc::now() - Duration::minutes(5), 10)
+        .await
+        .unwrap();
+    assert!(pending.is_empty());
+}
+
+#[tokio::test]
+#[ignore = "requires Docker"]
+async fn portfolio_state_reflects_closed_trade_pnl() {
+    let db = TestDatabase::start().await.unwrap();
+    let config = test_config(&db.url);
+    let storage: Arc<dyn Storage> = db.storage.clone();
+
+    let market = market_fixture("0xfixture03", dec!(0.55));
+    seed_market(&db.pool, &market).await.unwrap();
+
+    let trade_id = Uuid::new_v4();
+    storage.insert_trade(&Trade {
+        trade_id,
+        market_id: market.market_id.clone(),
+        strategy: Strategy::ClvArbitrage,
+        position: Position::Yes,
+        quantity: dec!(1000),
+        entry_price: dec!(0.45),
+        exit_price: None,
+        entry_time: Utc::now(),
+        exit_time: None,
+        gas_cost: None,
+        fees: None,
+        slippage: None,
+        pnl: None,
+        pnl_percent: None,
+        max_adverse_excursion: None,
+        max_favorable_excursion: None,
+        status: TradeStatus::Open,
+        tx_hash_entry: Some("0xentry".to_string()),
+        tx_hash_exit: None,
+        venue: POLYMARKET_VENUE.to_string(),
+        origin: TradeOrigin::Bot,
+    }).await.unwrap();
+    storage
+        .close_trade(trade_id, dec!(0.55), Utc::now(), Usdc::new(dec!(100)), "0xexit")
+        .await
+        .unwrap();
+
+    let risk_manager = RiskManager::new(storage.clone(), EventBus::new(Arc::new(InProcessCache::new(100))), &config, Notifier::detached()).await.unwrap();
+    let state = risk_manager.get_portfolio_state().await;
+
+    assert_eq!(state.total_capital, dec!(50100));
+    assert_eq!(state.realized_pnl_today, dec!(100));
+    assert_eq!(state.open_positions, 0);
+    assert_eq!(state.trades_today, 1);
+}
+
+#[tokio::test]
+#[ignore = "requires Docker"]
+async fn concurrent_claims_take_each_signal_once() {
+    let db = TestDatabase::start().await.unwrap();
+    let config = test_config(&db.url);
+    let storage: Arc<dyn Storage> = db.storage.clone();
+
+    let market = market_fixture("0xfixture04", dec!(0.40));
+    seed_market(&db.pool, &market).await.unwrap();
+    seed_bookmaker_odds(&db.pool, &odds_fixture(&market.market_id, Bookmaker::Pinnacle, dec!(0.50)))
+        .await
+        .unwrap();
+
+    let reference = Arc::new(ReferenceCache::new(storage.clone(), &config.reference_cache));
+    let generator = SignalGenerator::new(storage.clone(), EventBus::new(Arc::new(InProcessCache::new(100))), reference, DirtyMarkets::new(dec!(0.005)), Latencies::new(), &config).await.unwrap();
+    run_signal_generation(&generator).await.unwrap();
+
+    let since = Utc::now() - Duration::minutes(5);
+    let until = Utc::now() + Duration::minutes(2);
+    let (first, second) = tokio::join!(
+        storage.claim_pending_signals(since, 10, "engine-a", until),
+        storage.claim_pending_signals(since, 10, "engine-b", until),
+    );
+    let (first, second) = (first.unwrap(), second.unwrap());
+    assert_eq!(first.len() + second.len(), 1);
+
+    // Only the holder's release frees the signal for another engine
+    let (holder, other) = if first.is_empty() { ("engine-b", "engine-a") } else { ("engine-a", "engine-b") };
+    let signal_id = first.iter().chain(&second).next().unwrap().signal_id;
+    storage.release_signal(signal_id, other).await.unwrap();
+    assert!(storage.claim_pending_signals(since, 10, other, until).await.unwrap().is_empty());
+    assert!(!storage.renew_signal_claim(signal_id, other, until).await.unwrap());
+    assert!(storage.renew_signal_claim(signal_id, holder, until).await.unwrap());
+    storage.release_signal(signal_id, holder).await.unwrap();
+    assert_eq!(storage.claim_pending_signals(since, 10, other, until).await.unwrap().len(), 1);
+
+    // A lapsed claim another engine retook cannot be renewed by its first holder
+    assert!(!storage.renew_signal_claim(signal_id, holder, until).await.unwrap());
+}
+
+#[tokio::test]
+async fn execution_cycle_turns_a_pending_signal_into_a_trade() {
+    let config = test_config("sqlite::memory:");
+    let storage = Arc::new(MemoryStorage::new());
+
+    let market = market_fixture("0xfixture05", dec!(0.40));
+    storage.upsert_market(&market).await.unwrap();
+    let signal = signal_fixture(&market, dec!(0.50));
+    storage.insert_signal(&signal).await.unwrap();
+
+    let executor = Arc::new(PaperExecutor::new(storage.clone(), &config.execution.paper).unwrap());
+    let engine = execution_engine(storage.clone(), executor, &config).await.unwrap();
+    run_execution_cycle(&engine).await.unwrap();
+
+    // The risk manager sized it and the executor's fill was persisted as an open trade
+    let trade_id = storage.executed_trade_for(signal.signal_id).await.expect("signal traded");
+    let trade = storage.fetch_trade(trade_id).await.unwrap().unwrap();
+    assert_eq!(trade.market_id, market.market_id);
+    assert_eq!(trade.strategy, Strategy::ClvArbitrage);
+    assert_eq!(trade.position, Position::Yes);
+    assert_eq!(trade.status, TradeStatus::Open);
+    assert!(trade.quantity > dec!(0));
+    assert!(storage.fetch_pending_signals(Utc::now() - Duration::minutes(5), 10).await.unwrap().is_empty());
+}
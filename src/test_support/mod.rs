@@ -0,0 +1,60 @@
+//! Test-only helpers: an ephemeral, schema-migrated Postgres plus fixtures
+//! for driving SignalGenerator and ExecutionEngine end-to-end.
+//!
+//! The Postgres-backed tests need a local Docker daemon and are ignored by
+//! default; run them with `make test-integration`.
+
+mod fixtures;
+mod postgres;
+
+mod integration;
+
+pub use fixtures::{market_fixture, odds_fixture, seed_bookmaker_odds, seed_market, signal_fixture, test_config, trade_fixture};
+pub use postgres::TestDatabase;
+
+use anyhow::Result;
+use std::sync::Arc;
+
+use crate::cache::InProcessCache;
+use crate::config::Config;
+use crate::events::EventBus;
+use crate::execution::{ExecutionEngine, TradeExecutor};
+use crate::monitoring::{Heartbeats, Latencies, Notifier};
+use crate::risk::RiskManager;
+use crate::storage::Storage;
+use crate::strategies::SignalGenerator;
+
+/// An execution engine and its risk manager over `storage`, sending orders to
+/// `executor`, with nothing shared beyond this process
+pub async fn execution_engine(
+    storage: Arc<dyn Storage>,
+    executor: Arc<dyn TradeExecutor>,
+    config: &Config,
+) -> Result<ExecutionEngine> {
+    let cache = Arc::new(InProcessCache::new(100));
+    let events = EventBus::new(cache.clone());
+    let risk_manager = RiskManager::new(storage.clone(), events.clone(), config, Notifier::detached()).await?;
+
+    ExecutionEngine::new(
+        storage,
+        cache,
+        events,
+        executor,
+        config,
+        risk_manager,
+        Notifier::detached(),
+        Heartbeats::new(),
+        Latencies::new(),
+    ).await
+}
+
+/// Run a single signal generation pass (one tick of `SignalGenerator::run`)
+pub async fn run_signal_generation(generator: &SignalGenerator) -> Result<()> {
+    generator.generate_and_store_signals().await
+}
+
+/// Run a single execution pass (one tick of `ExecutionEngine::run`)
+pub async fn run_execution_cycle(engine: &ExecutionEngine) -> Result<()> {
+    engine.process_pending_signals().await?;
+    engine.monitor_positions().await
+}
@@ -1,8 +1,15 @@
 use chrono::{DateTime, Utc};
-use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
+use crate::numeric::{self, NumericError};
+
+/// Venue key for Polymarket's CLOB (fees, trades, cash flows, venue limits)
+pub const POLYMARKET_VENUE: &str = "polymarket";
+
 /// Sports supported by the trading bot
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum Sport {
@@ -21,6 +28,16 @@ impl Sport {
             Sport::MLB => "MLB",
         }
     }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "NFL" => Some(Sport::NFL),
+            "NBA" => Some(Sport::NBA),
+            "Premier League" => Some(Sport::PremierLeague),
+            "MLB" => Some(Sport::MLB),
+            _ => None,
+        }
+    }
 }
 
 /// Market types
@@ -30,14 +47,83 @@ pub enum MarketType {
     Spread,
     Total,
     Prop,
+    /// Several conditions on one game that must all hold ("A wins and over X")
+    Combo,
+    /// Season-long outcome: division winner, conference or championship
+    Futures,
+}
+
+impl MarketType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            MarketType::Moneyline => "moneyline",
+            MarketType::Spread => "spread",
+            MarketType::Total => "total",
+            MarketType::Prop => "prop",
+            MarketType::Combo => "combo",
+            MarketType::Futures => "futures",
+        }
+    }
 }
 
 /// Market status
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum MarketStatus {
     Active,
+    /// Order book paused, typically around breaking news
+    Suspended,
+    /// Event has started; prices move on live play
+    InPlay,
     Closed,
     Resolved,
+    /// Proposed resolution is being disputed
+    Disputed,
+}
+
+impl MarketStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            MarketStatus::Active => "active",
+            MarketStatus::Suspended => "suspended",
+            MarketStatus::InPlay => "in_play",
+            MarketStatus::Closed => "closed",
+            MarketStatus::Resolved => "resolved",
+            MarketStatus::Disputed => "disputed",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "active" => Some(MarketStatus::Active),
+            "suspended" => Some(MarketStatus::Suspended),
+            "in_play" => Some(MarketStatus::InPlay),
+            "closed" => Some(MarketStatus::Closed),
+            "resolved" => Some(MarketStatus::Resolved),
+            "disputed" => Some(MarketStatus::Disputed),
+            _ => None,
+        }
+    }
+
+    /// Whether new positions may be opened (pre-game markets only)
+    pub fn allows_entry(&self) -> bool {
+        matches!(self, MarketStatus::Active)
+    }
+
+    /// Whether open positions can be traded out of
+    pub fn allows_exit(&self) -> bool {
+        matches!(self, MarketStatus::Active | MarketStatus::InPlay)
+    }
+
+    /// Whether the condition may come back to `Active` (signals can wait)
+    pub fn is_temporary(&self) -> bool {
+        matches!(self, MarketStatus::Suspended)
+    }
+
+    /// Whether the event is over and positions only wait on resolution,
+    /// a dispute or settlement
+    pub fn awaits_settlement(&self) -> bool {
+        matches!(self, MarketStatus::Closed | MarketStatus::Resolved | MarketStatus::Disputed)
+    }
 }
 
 /// Trading position
@@ -54,6 +140,375 @@ impl Position {
             Position::No => "no",
         }
     }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "yes" => Some(Position::Yes),
+            "no" => Some(Position::No),
+            _ => None,
+        }
+    }
+
+    pub fn opposite(&self) -> Self {
+        match self {
+            Position::Yes => Position::No,
+            Position::No => Position::Yes,
+        }
+    }
+}
+
+/// Probability of an outcome, bounded to [0, 1]
+///
+/// Model outputs and de-vigged bookmaker probabilities. Serialized as a plain
+/// decimal; deserialization rejects out-of-range values.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(try_from = "Decimal", into = "Decimal")]
+pub struct Probability(Decimal);
+
+impl Probability {
+    pub const ZERO: Probability = Probability(Decimal::ZERO);
+    pub const ONE: Probability = Probability(Decimal::ONE);
+
+    pub fn new(value: Decimal) -> Result<Self, NumericError> {
+        numeric::probability(value).map(Probability)
+    }
+
+    /// Model probability computed in f64, e.g. a simulated frequency
+    pub fn from_f64(value: f64) -> Result<Self, NumericError> {
+        numeric::probability_from_f64(value).map(Probability)
+    }
+
+    /// Clamp an unbounded estimate into [0, 1]
+    pub fn clamped(value: Decimal) -> Self {
+        Probability(value.max(Decimal::ZERO).min(Decimal::ONE))
+    }
+
+    /// Implied probability of decimal odds (1 / odds), before removing vig
+    pub fn from_decimal_odds(odds: Decimal) -> Result<Self, NumericError> {
+        if odds < Decimal::ONE {
+            return Err(NumericError::InvalidOdds(odds));
+        }
+        Self::new(Decimal::ONE / odds)
+    }
+
+    pub fn value(&self) -> Decimal {
+        self.0
+    }
+
+    /// Probability of the opposite outcome
+    pub fn complement(&self) -> Self {
+        Probability(Decimal::ONE - self.0)
+    }
+
+    /// Fair decimal odds (1 / p); `None` for an impossible outcome
+    pub fn to_decimal_odds(&self) -> Option<Decimal> {
+        (self.0 > Decimal::ZERO).then(|| Decimal::ONE / self.0)
+    }
+
+    /// Signed edge of this probability over a share price
+    pub fn edge_over(&self, price: Price) -> Decimal {
+        self.0 - price.value()
+    }
+}
+
+impl TryFrom<Decimal> for Probability {
+    type Error = NumericError;
+
+    fn try_from(value: Decimal) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<Probability> for Decimal {
+    fn from(p: Probability) -> Self {
+        p.0
+    }
+}
+
+impl std::fmt::Display for Probability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Price of an outcome share in USDC, bounded to [0, 1]
+///
+/// A share pays out $1 if the outcome occurs, so the price doubles as the
+/// market-implied probability.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(try_from = "Decimal", into = "Decimal")]
+pub struct Price(Decimal);
+
+impl Price {
+    pub const ZERO: Price = Price(Decimal::ZERO);
+    pub const ONE: Price = Price(Decimal::ONE);
+
+    pub fn new(value: Decimal) -> Result<Self, NumericError> {
+        numeric::probability(value).map(Price)
+    }
+
+    pub fn value(&self) -> Decimal {
+        self.0
+    }
+
+    /// Price of the opposite share in a complete (vig-free) book
+    pub fn complement(&self) -> Self {
+        Price(Decimal::ONE - self.0)
+    }
+
+    pub fn implied_probability(&self) -> Probability {
+        Probability(self.0)
+    }
+
+    /// Decimal odds paid by buying at this price (1 / price); `None` at zero
+    pub fn to_decimal_odds(&self) -> Option<Decimal> {
+        (self.0 > Decimal::ZERO).then(|| Decimal::ONE / self.0)
+    }
+
+    /// Move the price by `delta`, saturating at the bounds
+    pub fn saturating_add(&self, delta: Decimal) -> Self {
+        Price((self.0 + delta).max(Decimal::ZERO).min(Decimal::ONE))
+    }
+}
+
+/// Decimal places of USDC on Polygon; outcome tokens use the same
+pub const USDC_DECIMALS: u32 = 6;
+
+/// An amount of USDC, in dollars
+///
+/// On chain, amounts are integers of base units (10^-6 USDC). They only
+/// cross into dollars through `from_base_units` and `to_base_units`, so a raw
+/// on-chain integer is never booked as dollars, nor dollars sent as units.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(transparent)]
+pub struct Usdc(Decimal);
+
+impl Usdc {
+    pub const ZERO: Usdc = Usdc(Decimal::ZERO);
+
+    pub fn new(amount: Decimal) -> Self {
+        Usdc(amount)
+    }
+
+    pub fn value(&self) -> Decimal {
+        self.0
+    }
+
+    pub fn from_base_units(units: u128) -> Result<Self, NumericError> {
+        i128::try_from(units).ok()
+            .and_then(|units| Decimal::try_from_i128_with_scale(units, USDC_DECIMALS).ok())
+            .map(Usdc)
+            .ok_or(NumericError::UsdcOutOfRange(units))
+    }
+
+    /// Base units of a non-negative amount, truncated so no more is sent than meant
+    pub fn to_base_units(&self) -> Result<u128, NumericError> {
+        if self.0 < Decimal::ZERO {
+            return Err(NumericError::InvalidUsdc(self.0));
+        }
+        self.0.round_dp_with_strategy(USDC_DECIMALS, RoundingStrategy::ToZero)
+            .checked_mul(Decimal::from(10u64.pow(USDC_DECIMALS)))
+            .and_then(|units| units.to_u128())
+            .ok_or(NumericError::InvalidUsdc(self.0))
+    }
+
+    /// Rounded to `dp` decimal places, for display
+    pub fn round_dp(&self, dp: u32) -> Usdc {
+        Usdc(self.0.round_dp(dp))
+    }
+
+    /// Whether the amount is a whole number of base units
+    pub fn is_exact(&self) -> bool {
+        self.0.normalize().scale() <= USDC_DECIMALS
+    }
+}
+
+impl std::ops::Add for Usdc {
+    type Output = Usdc;
+
+    fn add(self, other: Usdc) -> Usdc {
+        Usdc(self.0 + other.0)
+    }
+}
+
+impl std::ops::Sub for Usdc {
+    type Output = Usdc;
+
+    fn sub(self, other: Usdc) -> Usdc {
+        Usdc(self.0 - other.0)
+    }
+}
+
+impl std::ops::Neg for Usdc {
+    type Output = Usdc;
+
+    fn neg(self) -> Usdc {
+        Usdc(-self.0)
+    }
+}
+
+impl std::iter::Sum for Usdc {
+    fn sum<I: Iterator<Item = Usdc>>(iter: I) -> Usdc {
+        Usdc(iter.map(|amount| amount.0).sum())
+    }
+}
+
+impl std::ops::AddAssign for Usdc {
+    fn add_assign(&mut self, other: Usdc) {
+        self.0 += other.0;
+    }
+}
+
+impl std::ops::SubAssign for Usdc {
+    fn sub_assign(&mut self, other: Usdc) {
+        self.0 -= other.0;
+    }
+}
+
+impl std::fmt::Display for Usdc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// A quantity of outcome tokens (shares)
+///
+/// Outcome tokens are ERC-1155 balances with USDC's decimals, so a share is
+/// 10^6 base units. Kept apart from `Usdc` so an order's share leg is never
+/// encoded as dollars, nor its dollar leg as shares.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(transparent)]
+pub struct Shares(Decimal);
+
+impl Shares {
+    pub fn new(quantity: Decimal) -> Self {
+        Shares(quantity)
+    }
+
+    pub fn value(&self) -> Decimal {
+        self.0
+    }
+
+    pub fn from_base_units(units: u128) -> Result<Self, NumericError> {
+        i128::try_from(units).ok()
+            .and_then(|units| Decimal::try_from_i128_with_scale(units, USDC_DECIMALS).ok())
+            .map(Shares)
+            .ok_or(NumericError::SharesOutOfRange(units))
+    }
+
+    /// Base units of a non-negative quantity, truncated so no more is sold than held
+    pub fn to_base_units(&self) -> Result<u128, NumericError> {
+        if self.0 < Decimal::ZERO {
+            return Err(NumericError::InvalidShares(self.0));
+        }
+        self.0.round_dp_with_strategy(USDC_DECIMALS, RoundingStrategy::ToZero)
+            .checked_mul(Decimal::from(10u64.pow(USDC_DECIMALS)))
+            .and_then(|units| units.to_u128())
+            .ok_or(NumericError::InvalidShares(self.0))
+    }
+
+    /// USDC the shares cost at `price` each
+    pub fn cost(&self, price: Price) -> Usdc {
+        Usdc(self.0 * price.value())
+    }
+}
+
+impl std::fmt::Display for Shares {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Best bid and ask for one outcome's shares
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Quote {
+    pub bid: Price,
+    pub ask: Price,
+    /// Shares resting at the best bid/ask; `None` when depth is unknown
+    pub bid_size: Option<Decimal>,
+    pub ask_size: Option<Decimal>,
+}
+
+impl Quote {
+    /// Zero-width quote at `price`, for books we have no depth for
+    pub fn at(price: Price) -> Self {
+        Self { bid: price, ask: price, bid_size: None, ask_size: None }
+    }
+
+    pub fn with_depth(self, bid_size: Option<Decimal>, ask_size: Option<Decimal>) -> Self {
+        Self { bid_size, ask_size, ..self }
+    }
+
+    /// Quote from a possibly empty or crossed top of book, falling back to `last`
+    pub fn from_book(bid: Option<Decimal>, ask: Option<Decimal>, last: Price) -> Self {
+        let bid = bid.and_then(|p| Price::new(p).ok());
+        let ask = ask.and_then(|p| Price::new(p).ok());
+
+        match (bid, ask) {
+            (Some(bid), Some(ask)) if bid <= ask => Self { bid, ask, bid_size: None, ask_size: None },
+            _ => Self::at(last),
+        }
+    }
+
+    /// The other outcome's book: buying NO at p is selling YES at 1 - p
+    pub fn complement(&self) -> Self {
+        Self {
+            bid: self.ask.complement(),
+            ask: self.bid.complement(),
+            bid_size: self.ask_size,
+            ask_size: self.bid_size,
+        }
+    }
+
+    pub fn mid(&self) -> Price {
+        Price((self.bid.0 + self.ask.0) / Decimal::TWO)
+    }
+
+    pub fn spread(&self) -> Decimal {
+        self.ask.0 - self.bid.0
+    }
+}
+
+impl TryFrom<Decimal> for Price {
+    type Error = NumericError;
+
+    fn try_from(value: Decimal) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<Price> for Decimal {
+    fn from(p: Price) -> Self {
+        p.0
+    }
+}
+
+impl std::fmt::Display for Price {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// On-chain identifiers needed to place CLOB orders for a market
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MarketTokens {
+    /// CTF condition id (bytes32 hex)
+    pub condition_id: String,
+    /// UMA question id (bytes32 hex)
+    pub question_id: String,
+    /// ERC-1155 position id of the YES outcome (uint256 decimal)
+    pub yes_token_id: String,
+    /// ERC-1155 position id of the NO outcome (uint256 decimal)
+    pub no_token_id: String,
+}
+
+impl MarketTokens {
+    pub fn token_id(&self, position: Position) -> &str {
+        match position {
+            Position::Yes => &self.yes_token_id,
+            Position::No => &self.no_token_id,
+        }
+    }
 }
 
 /// Market information from Polymarket
@@ -68,100 +523,947 @@ pub struct Market {
     pub resolution_source: Option<String>,
     pub min_liquidity: Decimal,
     pub current_liquidity: Decimal,
-    pub yes_price: Decimal,
-    pub no_price: Decimal,
+    /// Last traded (displayed) prices
+    pub yes_price: Price,
+    pub no_price: Price,
+    /// Best bid/ask per outcome; zero-width at the last price when the book is unknown
+    pub yes_quote: Quote,
+    pub no_quote: Quote,
     pub status: MarketStatus,
+    /// Missing until the market has been ingested from the CLOB API
+    pub tokens: Option<MarketTokens>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 impl Market {
-    /// Calculate implied probability from price
-    pub fn implied_probability(&self, position: Position) -> Decimal {
+    /// Share price of a position
+    pub fn price(&self, position: Position) -> Price {
         match position {
             Position::Yes => self.yes_price,
             Position::No => self.no_price,
         }
     }
 
-    /// Check if market has sufficient liquidity
-    pub fn has_sufficient_liquidity(&self, min_liquidity: Decimal) -> bool {
-        self.current_liquidity >= min_liquidity
+    pub fn quote(&self, position: Position) -> Quote {
+        match position {
+            Position::Yes => self.yes_quote,
+            Position::No => self.no_quote,
+        }
+    }
+
+    /// Price a position can be bought at right now
+    pub fn entry_price(&self, position: Position) -> Price {
+        self.quote(position).ask
+    }
+
+    /// Calculate implied probability from price
+    pub fn implied_probability(&self, position: Position) -> Probability {
+        self.price(position).implied_probability()
+    }
+
+    /// Check if market has sufficient liquidity
+    pub fn has_sufficient_liquidity(&self, min_liquidity: Decimal) -> bool {
+        self.current_liquidity >= min_liquidity
+    }
+}
+
+/// A team's current strength, as used by the season model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamRating {
+    pub sport: Sport,
+    pub team: String,
+    pub conference: String,
+    pub division: String,
+    /// Expected margin against a league-average team on a neutral field,
+    /// in points (runs for MLB)
+    pub rating: f64,
+    /// Home venue, for travel distances
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// Home venue elevation, for its home advantage
+    pub elevation_m: Option<f64>,
+    /// Home venue is a dome or has a closed roof
+    pub indoor: bool,
+}
+
+/// A regular-season game; scores are set once it has been played
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Game {
+    pub game_id: String,
+    pub sport: Sport,
+    pub season: i32,
+    pub home_team: String,
+    pub away_team: String,
+    pub start_time: DateTime<Utc>,
+    pub home_score: Option<i32>,
+    pub away_score: Option<i32>,
+}
+
+impl Game {
+    /// Winning team of a played game; `None` if unplayed or tied
+    pub fn winner(&self) -> Option<&str> {
+        match (self.home_score?, self.away_score?) {
+            (home, away) if home > away => Some(&self.home_team),
+            (home, away) if away > home => Some(&self.away_team),
+            _ => None,
+        }
+    }
+
+    pub fn is_played(&self) -> bool {
+        self.home_score.is_some() && self.away_score.is_some()
+    }
+
+    /// Whether a market question names both teams (full name or nickname)
+    /// and starts within three hours of this game
+    pub fn matches_event(&self, event_name: &str, event_time: DateTime<Utc>) -> bool {
+        let (home, away) = self.team_positions(&event_name.to_lowercase());
+        self.starts_near(event_time) && home.is_some() && away.is_some()
+    }
+
+    /// Whether `event_time` is within three hours of this game's start
+    pub fn starts_near(&self, event_time: DateTime<Utc>) -> bool {
+        (self.start_time - event_time).num_minutes().abs() <= 180
+    }
+
+    /// Where a lowercased question names the home and the away team, by
+    /// full name or nickname
+    pub fn team_positions(&self, question: &str) -> (Option<usize>, Option<usize>) {
+        let find = |team: &str| {
+            let team = team.to_lowercase();
+            question.find(&team)
+                .or_else(|| question.find(team.split_whitespace().last()?))
+        };
+        (find(&self.home_team), find(&self.away_team))
+    }
+}
+
+/// Home advantage estimated for a team's home venue, kept for audit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HomeAdvantageEstimate {
+    pub sport: Sport,
+    pub team: String,
+    /// League-wide home edge the venue terms are added to
+    pub league: f64,
+    /// Played games at and away from this venue behind `raw`
+    pub games: i64,
+    /// Unshrunk estimate from this team's home and away results
+    pub raw: Option<f64>,
+    /// League edge plus altitude and roof terms
+    pub structural: f64,
+    /// Raw shrunk toward structural; used by the models
+    pub estimate: f64,
+    pub estimated_at: DateTime<Utc>,
+}
+
+/// One named input to ML models for a team going into a game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameFeature {
+    pub game_id: String,
+    pub team: String,
+    /// e.g. "rest_days", "travel_km"
+    pub name: String,
+    pub value: f64,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// An official assigned to a game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfficialAssignment {
+    pub game_id: String,
+    pub official: String,
+    /// e.g. "referee", "home_plate"
+    pub role: String,
+}
+
+/// How games an official worked in a role have scored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfficialTendency {
+    pub official: String,
+    pub role: String,
+    /// Played games worked in this role
+    pub games: i64,
+    /// Average total over the season average in those games
+    pub total_delta: f64,
+}
+
+/// Starting pitcher announced for one side of a game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProbablePitcher {
+    pub game_id: String,
+    pub team: String,
+    pub pitcher: String,
+    /// Earned runs per nine innings this season; `None` before a first start
+    pub era: Option<f64>,
+    /// Innings behind `era`
+    pub innings_pitched: f64,
+}
+
+/// Innings a team's relievers threw in one game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BullpenUsage {
+    pub team: String,
+    pub game_time: DateTime<Utc>,
+    pub innings: f64,
+}
+
+/// Rating lost while a player is out
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InjuryAdjustment {
+    pub sport: Sport,
+    pub team: String,
+    pub player: String,
+    /// Change to the team's rating, negative for a loss
+    pub rating_delta: f64,
+    /// Expected return; `None` if out for the season
+    pub out_until: Option<DateTime<Utc>>,
+}
+
+/// Trading strategy types
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Strategy {
+    ClvArbitrage,
+    PoissonExpectedValue,
+    NewsScalping,
+    MarketMicrostructure,
+    SentimentGap,
+    /// Combination markets priced off a correlated score model
+    ComboPricing,
+    /// Season futures priced off simulated seasons
+    SeasonFutures,
+    /// MLB moneylines and totals priced off the announced starters
+    MlbPitching,
+    /// Paired spread or total legs either side of a gap between lines
+    Middling,
+    /// A user-written script (`strategies.scripted`)
+    Scripted,
+    /// Fair values from an external model server (`strategies.remote_model`)
+    RemoteModel,
+    /// Trades entered by hand through the admin API
+    Manual,
+}
+
+impl Strategy {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Strategy::ClvArbitrage => "clv_arb",
+            Strategy::PoissonExpectedValue => "poisson_ev",
+            Strategy::NewsScalping => "news_scalp",
+            Strategy::MarketMicrostructure => "market_micro",
+            Strategy::SentimentGap => "sentiment_gap",
+            Strategy::ComboPricing => "combo",
+            Strategy::SeasonFutures => "futures",
+            Strategy::MlbPitching => "mlb",
+            Strategy::Middling => "middle",
+            Strategy::Scripted => "scripted",
+            Strategy::RemoteModel => "remote_model",
+            Strategy::Manual => "manual",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "clv_arb" => Some(Strategy::ClvArbitrage),
+            "poisson_ev" => Some(Strategy::PoissonExpectedValue),
+            "news_scalp" => Some(Strategy::NewsScalping),
+            "market_micro" => Some(Strategy::MarketMicrostructure),
+            "sentiment_gap" => Some(Strategy::SentimentGap),
+            "combo" => Some(Strategy::ComboPricing),
+            "futures" => Some(Strategy::SeasonFutures),
+            "mlb" => Some(Strategy::MlbPitching),
+            "middle" => Some(Strategy::Middling),
+            "scripted" => Some(Strategy::Scripted),
+            "remote_model" => Some(Strategy::RemoteModel),
+            "manual" => Some(Strategy::Manual),
+            _ => None,
+        }
+    }
+}
+
+/// Trading signal generated by strategies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Signal {
+    pub signal_id: Uuid,
+    pub market_id: String,
+    pub strategy: Strategy,
+    pub signal_type: SignalType,
+    pub confidence: Decimal,
+    /// Edge at `current_price`, the level the signal can actually be filled at
+    pub edge_size: Decimal,
+    pub recommended_size: Decimal,
+    /// Executable price (best ask for the outcome bought) at generation time
+    pub current_price: Price,
+    /// Shares offered at `current_price` at generation time; `None` if depth was unknown
+    pub executable_size: Option<Decimal>,
+    /// Edge against the mid, before crossing the spread
+    pub quoted_edge: Decimal,
+    pub fair_value: Probability,
+    pub generated_at: DateTime<Utc>,
+    pub metadata: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SignalType {
+    BuyYes,
+    BuyNo,
+}
+
+impl SignalType {
+    /// The signal that buys `position`
+    pub fn buy(position: Position) -> Self {
+        match position {
+            Position::Yes => SignalType::BuyYes,
+            Position::No => SignalType::BuyNo,
+        }
+    }
+
+    pub fn to_position(&self) -> Position {
+        match self {
+            SignalType::BuyYes => Position::Yes,
+            SignalType::BuyNo => Position::No,
+        }
+    }
+}
+
+/// Trade execution record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trade {
+    pub trade_id: Uuid,
+    pub market_id: String,
+    pub strategy: Strategy,
+    pub position: Position,
+    pub quantity: Decimal,
+    pub entry_price: Decimal,
+    pub exit_price: Option<Decimal>,
+    pub entry_time: DateTime<Utc>,
+    pub exit_time: Option<DateTime<Utc>>,
+    pub gas_cost: Option<Decimal>,
+    /// Venue fees paid on entry; exit fees are netted into `pnl` at close
+    pub fees: Option<Usdc>,
+    pub slippage: Option<Decimal>,
+    pub pnl: Option<Usdc>,
+    pub pnl_percent: Option<Decimal>,
+    /// Worst and best mark-to-market PnL while held, set at close
+    pub max_adverse_excursion: Option<Decimal>,
+    pub max_favorable_excursion: Option<Decimal>,
+    pub status: TradeStatus,
+    pub tx_hash_entry: Option<String>,
+    pub tx_hash_exit: Option<String>,
+    /// Venue the position is held on, e.g. `polymarket`
+    pub venue: String,
+    pub origin: TradeOrigin,
+}
+
+/// Latest mark-to-market of an open trade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeMark {
+    pub trade_id: Uuid,
+    /// Best bid for the shares held, i.e. what an exit would fetch
+    pub mark_price: Decimal,
+    pub unrealized_pnl: Decimal,
+    /// Worst `unrealized_pnl` seen since entry, never above zero
+    pub max_adverse_excursion: Decimal,
+    /// Best `unrealized_pnl` seen since entry, never below zero
+    pub max_favorable_excursion: Decimal,
+    pub marked_at: DateTime<Utc>,
+}
+
+/// One point on an open CLV trade's path back toward the sharp line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvergenceSample {
+    pub trade_id: Uuid,
+    /// Mid of the side held
+    pub market_price: Decimal,
+    /// Sharp bookmaker fair value of the side held
+    pub fair_value: Decimal,
+    /// `fair_value - market_price`; shrinks toward zero as the thesis plays out
+    pub divergence: Decimal,
+    pub sampled_at: DateTime<Utc>,
+}
+
+/// The bankroll a trade was sized against and the stake it took
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeSizing {
+    pub trade_id: Uuid,
+    /// Sizing capital when the trade was entered
+    pub bankroll: Decimal,
+    /// Capital committed, excluding gas and fees
+    pub stake: Decimal,
+    pub sized_at: DateTime<Utc>,
+}
+
+/// A trade held back from settlement until its payout is confirmed by hand
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettlementReview {
+    pub trade_id: Uuid,
+    pub market_id: String,
+    /// Payout per share implied by the market's reported final price
+    pub reported_payout: Option<Decimal>,
+    /// Payout per share from the CTF payout vector, once reported on-chain
+    pub onchain_payout: Option<Decimal>,
+    pub reason: String,
+    pub flagged_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// How a trade held to resolution came out
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TradeOutcome {
+    Won,
+    Lost,
+    /// Resolved 50/50 or otherwise partially paid
+    Void,
+}
+
+impl TradeOutcome {
+    /// Outcome of a position paying `payout` per share
+    pub fn from_payout(payout: Decimal) -> Self {
+        if payout >= Decimal::ONE {
+            TradeOutcome::Won
+        } else if payout.is_zero() {
+            TradeOutcome::Lost
+        } else {
+            TradeOutcome::Void
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TradeOutcome::Won => "won",
+            TradeOutcome::Lost => "lost",
+            TradeOutcome::Void => "void",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "won" => Some(TradeOutcome::Won),
+            "lost" => Some(TradeOutcome::Lost),
+            "void" => Some(TradeOutcome::Void),
+            _ => None,
+        }
+    }
+}
+
+/// Where a signal whose submission failed stands
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecutionFailureStatus {
+    /// Still pending; resubmitted once its backoff elapses
+    Retrying,
+    /// Given up on: rejected, out of attempts or expired while retrying
+    Failed,
+    /// Went through on a later attempt
+    Recovered,
+}
+
+impl ExecutionFailureStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExecutionFailureStatus::Retrying => "retrying",
+            ExecutionFailureStatus::Failed => "failed",
+            ExecutionFailureStatus::Recovered => "recovered",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "retrying" => Some(ExecutionFailureStatus::Retrying),
+            "failed" => Some(ExecutionFailureStatus::Failed),
+            "recovered" => Some(ExecutionFailureStatus::Recovered),
+            _ => None,
+        }
+    }
+}
+
+/// Failed submissions of a signal and what became of it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FailedExecution {
+    pub signal_id: Uuid,
+    pub market_id: String,
+    pub strategy: Strategy,
+    /// Failed submissions so far
+    pub attempts: i32,
+    pub last_error: String,
+    pub status: ExecutionFailureStatus,
+    /// When the signal is next submitted; set only while retrying
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub first_failed_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A trade booked at its market's resolution rather than sold
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TradeSettlement {
+    pub trade_id: Uuid,
+    pub market_id: String,
+    pub outcome: TradeOutcome,
+    /// Per share, from the CTF payout vector or a reviewer
+    pub payout: Decimal,
+    pub pnl: Usdc,
+    /// Redemption transaction; unset for losses and outside live mode
+    pub tx_hash: Option<String>,
+    pub settled_at: DateTime<Utc>,
+}
+
+/// A fill that moved past tolerance from its signal's executable price
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlippageEvent {
+    pub event_id: Uuid,
+    pub trade_id: Uuid,
+    pub signal_id: Uuid,
+    pub market_id: String,
+    pub expected_price: Decimal,
+    pub fill_price: Decimal,
+    /// Fill less expected price; positive is adverse
+    pub slippage: Decimal,
+    /// Whether the position was closed out straight away
+    pub flattened: bool,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// What an execution cost booked outside a trade's entry paid for
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionCostKind {
+    /// Fees and gas of closing a position
+    Exit,
+    /// Gas of an entry that failed once its transaction had gone out
+    FailedEntry,
+    /// Gas of a redemption transaction, mined whether or not it reverted
+    Redemption,
+}
+
+impl ExecutionCostKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ExecutionCostKind::Exit => "exit",
+            ExecutionCostKind::FailedEntry => "failed_entry",
+            ExecutionCostKind::Redemption => "redemption",
+        }
+    }
+}
+
+/// Gas or fees spent on something other than opening a trade
+///
+/// Entry gas and fees stay on the trade; these count against the daily cost
+/// budget alongside them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionCost {
+    pub cost_id: Uuid,
+    pub kind: ExecutionCostKind,
+    /// USD spent
+    pub amount: Decimal,
+    pub trade_id: Option<Uuid>,
+    pub tx_hash: Option<String>,
+    pub incurred_at: DateTime<Utc>,
+}
+
+/// Which side of the bracket a resting exit order protects
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RestingOrderKind {
+    Stop,
+    Target,
+}
+
+impl RestingOrderKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            RestingOrderKind::Stop => "stop",
+            RestingOrderKind::Target => "target",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "stop" => Some(RestingOrderKind::Stop),
+            "target" => Some(RestingOrderKind::Target),
+            _ => None,
+        }
+    }
+}
+
+/// How long an order may wait on the book
+///
+/// Takers are fill-or-kill, filling in full or not at all, or
+/// immediate-or-cancel, keeping whatever part fills at once. Resting orders
+/// are good-til-cancelled, or good-til-date when the venue should pull them
+/// at an expiration even if we never get to cancel.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeInForce {
+    Gtc,
+    Gtd,
+    Fok,
+    Ioc,
+}
+
+impl TimeInForce {
+    pub fn as_str(&self) -> &str {
+        match self {
+            TimeInForce::Gtc => "GTC",
+            TimeInForce::Gtd => "GTD",
+            TimeInForce::Fok => "FOK",
+            TimeInForce::Ioc => "IOC",
+        }
+    }
+
+    /// Whatever does not fill at once is cancelled
+    pub fn is_immediate(&self) -> bool {
+        matches!(self, TimeInForce::Fok | TimeInForce::Ioc)
+    }
+}
+
+/// How soon a transaction needs to be mined, which sets the priority fee
+/// it bids
+///
+/// Low bids a tip most recent blocks beat and waits for quiet blocks; high
+/// bids one most of them did not need, to land in the next.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum GasUrgency {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl GasUrgency {
+    pub fn as_str(&self) -> &str {
+        match self {
+            GasUrgency::Low => "low",
+            GasUrgency::Normal => "normal",
+            GasUrgency::High => "high",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RestingOrderStatus {
+    Open,
+    Filled,
+    Cancelled,
+}
+
+impl RestingOrderStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            RestingOrderStatus::Open => "open",
+            RestingOrderStatus::Filled => "filled",
+            RestingOrderStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// Which way shares moved for our wallet in a fill
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FillSide {
+    Buy,
+    Sell,
+}
+
+impl FillSide {
+    pub fn as_str(&self) -> &str {
+        match self {
+            FillSide::Buy => "buy",
+            FillSide::Sell => "sell",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "buy" => Some(FillSide::Buy),
+            "sell" => Some(FillSide::Sell),
+            _ => None,
+        }
+    }
+}
+
+/// An `OrderFilled` event of the CTF Exchange involving our wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnchainFill {
+    pub tx_hash: String,
+    pub log_index: i64,
+    /// Hash of the filled order, which is its CLOB order id
+    pub order_hash: String,
+    /// ERC-1155 outcome token id
+    pub token_id: String,
+    pub side: FillSide,
+    pub shares: Decimal,
+    /// USDC per share
+    pub price: Decimal,
+    pub fee: Usdc,
+    pub block_number: i64,
+    pub observed_at: DateTime<Utc>,
+}
+
+/// An exit order left on the CLOB for an open trade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestingOrder {
+    /// Venue order id
+    pub order_id: String,
+    pub trade_id: Uuid,
+    pub kind: RestingOrderKind,
+    pub price: Decimal,
+    /// Shares the order sells; tracks the trade's quantity
+    pub quantity: Decimal,
+    pub placed_at: DateTime<Utc>,
+}
+
+/// A maker entry resting on the CLOB for a signal, until it fills or expires
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryOrder {
+    /// Venue order id
+    pub order_id: String,
+    pub signal_id: Uuid,
+    pub market_id: String,
+    pub position: Position,
+    pub price: Decimal,
+    pub quantity: Decimal,
+    /// Estimated gas of the entry, charged to the trade it opens
+    pub gas_cost: Decimal,
+    pub placed_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// How a large entry is split into child orders
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SliceMode {
+    /// Equal children at a fixed interval
+    Twap,
+    /// Children no larger than what the best offer shows, as it refills
+    Iceberg,
+}
+
+impl SliceMode {
+    pub fn as_str(&self) -> &str {
+        match self {
+            SliceMode::Twap => "twap",
+            SliceMode::Iceberg => "iceberg",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "twap" => Some(SliceMode::Twap),
+            "iceberg" => Some(SliceMode::Iceberg),
+            _ => None,
+        }
+    }
+}
+
+/// A large entry worked as child orders until its notional is bought, the
+/// price runs past its limit for good or it expires
+///
+/// Status is open while children are still to be sent, filled once the
+/// notional is bought and cancelled when it expired or was stopped short.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParentOrder {
+    pub parent_id: Uuid,
+    pub signal_id: Uuid,
+    pub market_id: String,
+    pub position: Position,
+    pub mode: SliceMode,
+    /// USDC to buy across the children
+    pub target_notional: Decimal,
+    /// Highest price any child may pay
+    pub limit_price: Decimal,
+    /// Children a TWAP is split into; the most an iceberg child takes is
+    /// the target over this
+    pub slices: i32,
+    pub children_sent: i32,
+    pub filled_quantity: Decimal,
+    pub filled_notional: Decimal,
+    pub status: RestingOrderStatus,
+    pub next_slice_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// One child of a parent order, and the trade its fill opened
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChildOrder {
+    pub parent_id: Uuid,
+    /// Position among the parent's children, from 1
+    pub slice: i32,
+    pub trade_id: Uuid,
+    pub quantity: Decimal,
+    pub price: Decimal,
+    pub tx_hash: String,
+    pub sent_at: DateTime<Utc>,
+}
+
+/// A closed bot trade with the prediction behind it and the line it closed against
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PredictionOutcome {
+    pub trade_id: Uuid,
+    pub strategy: Strategy,
+    /// Signal's probability that the position wins
+    pub fair_value: Decimal,
+    pub entry_price: Decimal,
+    /// 1 or 0 once resolved; the market price for earlier exits
+    pub exit_price: Decimal,
+    /// 1 or 0 when the trade was settled as won or lost; `None` when it was
+    /// sold before resolution or its market was void
+    pub payout: Option<Decimal>,
+    /// Last pre-event price of the position's outcome, if one was captured
+    pub closing_price: Option<Decimal>,
+    pub exit_time: DateTime<Utc>,
+}
+
+impl PredictionOutcome {
+    /// Closing line value: how far the market moved toward the entry, in probability points
+    pub fn clv(&self) -> Option<Decimal> {
+        self.closing_price.map(|closing| closing - self.entry_price)
+    }
+
+    /// Squared error of the predicted probability against the realized exit
+    pub fn brier(&self) -> Decimal {
+        (self.fair_value - self.exit_price) * (self.fair_value - self.exit_price)
+    }
+}
+
+/// A strategy whose signals are recorded but not traded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShadowedStrategy {
+    pub strategy: Strategy,
+    pub reason: String,
+    pub since: DateTime<Utc>,
+}
+
+/// Reduced sizing and signal cap applied to a strategy whose realized edge has turned negative
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyThrottle {
+    pub strategy: Strategy,
+    /// Fraction of the normal position size traded
+    pub size_multiplier: Decimal,
+    /// Signals the strategy may trade per day
+    pub max_daily_signals: i64,
+    /// Rolling realized edge that set this level
+    pub realized_edge: Decimal,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A strategy's share of capital from the Sharpe-optimal weight solve
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyWeight {
+    pub strategy: Strategy,
+    /// Fraction of capital, summing to one across weighted strategies
+    pub weight: Decimal,
+    /// Mean daily return on capital over the solve's history
+    pub mean_return: Decimal,
+    /// Standard deviation of daily return on capital
+    pub volatility: Decimal,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A signal's executable price when it was generated and when the engine
+/// took it up, traded or not, for measuring how much of its edge was left
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalExecution {
+    pub strategy: Strategy,
+    /// Executable price of the position when the signal was generated
+    pub signal_price: Decimal,
+    pub fair_value: Decimal,
+    pub generated_at: DateTime<Utc>,
+    /// Executable price of the position when the engine last took it up
+    pub quoted_price: Decimal,
+    pub quoted_at: DateTime<Utc>,
+}
+
+impl SignalExecution {
+    /// Seconds from generation to the engine's quote
+    pub fn age_secs(&self) -> f64 {
+        (self.quoted_at - self.generated_at).num_milliseconds().max(0) as f64 / 1000.0
+    }
+
+    /// Share of the signal's edge over its own price still there at the
+    /// quoted price; `None` when it had no edge to begin with
+    pub fn remaining_edge(&self) -> Option<f64> {
+        let edge = self.fair_value - self.signal_price;
+        if edge <= Decimal::ZERO {
+            return None;
+        }
+        ((self.fair_value - self.quoted_price) / edge).to_f64()
     }
 }
 
-/// Trading strategy types
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
-pub enum Strategy {
-    ClvArbitrage,
-    PoissonExpectedValue,
-    NewsScalping,
-    MarketMicrostructure,
-    SentimentGap,
+/// How fast a strategy's edge decays with signal age, fitted from its
+/// executions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdgeDecay {
+    pub strategy: Strategy,
+    /// Signal age by which half the edge is gone
+    pub half_life_secs: i64,
+    /// Signals the fit is over
+    pub samples: i64,
+    pub updated_at: DateTime<Utc>,
 }
 
-impl Strategy {
-    pub fn as_str(&self) -> &str {
-        match self {
-            Strategy::ClvArbitrage => "clv_arb",
-            Strategy::PoissonExpectedValue => "poisson_ev",
-            Strategy::NewsScalping => "news_scalp",
-            Strategy::MarketMicrostructure => "market_micro",
-            Strategy::SentimentGap => "sentiment_gap",
-        }
+impl EdgeDecay {
+    /// Share of the edge left at `age`, in [0, 1]
+    pub fn remaining(&self, age: chrono::Duration) -> f64 {
+        let age = age.num_milliseconds().max(0) as f64 / 1000.0;
+        0.5f64.powf(age / self.half_life_secs.max(1) as f64)
     }
 }
 
-/// Trading signal generated by strategies
+/// An automated change to how the bot trades, kept for review
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Signal {
-    pub signal_id: Uuid,
-    pub market_id: String,
-    pub strategy: Strategy,
-    pub signal_type: SignalType,
-    pub confidence: Decimal,
-    pub edge_size: Decimal,
-    pub recommended_size: Decimal,
-    pub current_price: Decimal,
-    pub fair_value: Decimal,
-    pub generated_at: DateTime<Utc>,
-    pub metadata: serde_json::Value,
+pub struct Decision {
+    pub decision_id: Uuid,
+    /// What sort of decision, e.g. `throttle`
+    pub kind: String,
+    /// What it applies to, e.g. a strategy
+    pub subject: String,
+    pub summary: String,
+    pub detail: serde_json::Value,
+    pub decided_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum SignalType {
-    BuyYes,
-    BuyNo,
+/// An API credential as stored: encrypted, one row per version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SealedCredential {
+    /// External service, e.g. `pinnacle`
+    pub provider: String,
+    /// Which of its secrets, e.g. `password`
+    pub name: String,
+    /// Rotating stores a new version; the highest is in use
+    pub version: i32,
+    pub nonce: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+    pub created_at: DateTime<Utc>,
 }
 
-impl SignalType {
-    pub fn to_position(&self) -> Position {
+/// Who placed a trade
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TradeOrigin {
+    Bot,
+    /// Placed outside the bot and recorded so risk and reporting see it
+    Manual,
+}
+
+impl TradeOrigin {
+    pub fn as_str(&self) -> &str {
         match self {
-            SignalType::BuyYes => Position::Yes,
-            SignalType::BuyNo => Position::No,
+            TradeOrigin::Bot => "bot",
+            TradeOrigin::Manual => "manual",
         }
     }
-}
 
-/// Trade execution record
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Trade {
-    pub trade_id: Uuid,
-    pub market_id: String,
-    pub strategy: Strategy,
-    pub position: Position,
-    pub quantity: Decimal,
-    pub entry_price: Decimal,
-    pub exit_price: Option<Decimal>,
-    pub entry_time: DateTime<Utc>,
-    pub exit_time: Option<DateTime<Utc>>,
-    pub gas_cost: Option<Decimal>,
-    pub slippage: Option<Decimal>,
-    pub pnl: Option<Decimal>,
-    pub pnl_percent: Option<Decimal>,
-    pub status: TradeStatus,
-    pub tx_hash_entry: Option<String>,
-    pub tx_hash_exit: Option<String>,
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "bot" => Some(TradeOrigin::Bot),
+            "manual" => Some(TradeOrigin::Manual),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -171,6 +1473,25 @@ pub enum TradeStatus {
     StoppedOut,
 }
 
+impl TradeStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            TradeStatus::Open => "open",
+            TradeStatus::Closed => "closed",
+            TradeStatus::StoppedOut => "stopped_out",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "open" => Some(TradeStatus::Open),
+            "closed" => Some(TradeStatus::Closed),
+            "stopped_out" => Some(TradeStatus::StoppedOut),
+            _ => None,
+        }
+    }
+}
+
 impl Trade {
     /// Calculate current unrealized PnL
     pub fn unrealized_pnl(&self, current_price: Decimal) -> Decimal {
@@ -180,10 +1501,26 @@ impl Trade {
         (current_price - self.entry_price) * self.quantity
     }
 
+    /// (MAE, MFE) over the holding period: the running marks plus the exit itself
+    pub fn excursions_at_close(&self, exit_price: Decimal, mark: Option<&TradeMark>) -> (Decimal, Decimal) {
+        let at_exit = (exit_price - self.entry_price) * self.quantity;
+        let (worst, best) = mark.map_or((Decimal::ZERO, Decimal::ZERO), |m| {
+            (m.max_adverse_excursion, m.max_favorable_excursion)
+        });
+        (worst.min(at_exit).min(Decimal::ZERO), best.max(at_exit).max(Decimal::ZERO))
+    }
+
     /// Calculate position size in USD
     pub fn position_size_usd(&self) -> Decimal {
         self.entry_price * self.quantity
     }
+
+    /// Realized PnL when exiting at `exit_price`, net of entry gas, entry fees and exit costs
+    pub fn realized_pnl(&self, exit_price: Decimal, exit_costs: Usdc) -> Usdc {
+        Usdc::new((exit_price - self.entry_price) * self.quantity - self.gas_cost.unwrap_or(Decimal::ZERO))
+            - self.fees.unwrap_or(Usdc::ZERO)
+            - exit_costs
+    }
 }
 
 /// Bookmaker odds for reference
@@ -193,12 +1530,253 @@ pub struct BookmakerOdds {
     pub market_id: String,
     pub yes_odds: Decimal,
     pub no_odds: Decimal,
-    pub yes_implied_prob: Decimal,
-    pub no_implied_prob: Decimal,
+    pub yes_implied_prob: Probability,
+    pub no_implied_prob: Probability,
+    /// When we received the line
     pub timestamp: DateTime<Utc>,
+    /// When the bookmaker last updated the line, if the feed says
+    pub source_timestamp: Option<DateTime<Utc>>,
+}
+
+impl BookmakerOdds {
+    /// Age of the line, from the bookmaker's own update time when known
+    pub fn age(&self, now: DateTime<Utc>) -> chrono::Duration {
+        now - self.source_timestamp.unwrap_or(self.timestamp)
+    }
+}
+
+/// Delay between bookmakers updating lines and us receiving them, for one
+/// provider's batch of odds
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FeedLatency {
+    pub provider: String,
+    /// Lines in the batch carrying a bookmaker timestamp
+    pub lines: i64,
+    pub median_ms: i64,
+    pub max_ms: i64,
+    pub measured_at: DateTime<Utc>,
 }
 
+/// Order book and trade flow features of a market at one poll, all from
+/// the YES side (buying NO is selling YES)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MarketMicrostructure {
+    pub market_id: String,
+    /// Shares bid and offered within the top levels of the book
+    pub bid_depth: Decimal,
+    pub ask_depth: Decimal,
+    /// (bid depth - ask depth) / total depth, in [-1, 1]; negative leans to sellers
+    pub imbalance: Decimal,
+    /// Change in depth since the previous poll, `None` for the first
+    pub bid_depth_change: Option<Decimal>,
+    pub ask_depth_change: Option<Decimal>,
+    /// Shares bought and sold by takers over the flow window
+    pub taker_buy_volume: Decimal,
+    pub taker_sell_volume: Decimal,
+    pub observed_at: DateTime<Utc>,
+}
+
+impl MarketMicrostructure {
+    /// (taker buys - taker sells) / taker volume, in [-1, 1]; `None` without trades
+    pub fn taker_flow(&self) -> Option<Decimal> {
+        let volume = self.taker_buy_volume + self.taker_sell_volume;
+        (volume > Decimal::ZERO).then(|| (self.taker_buy_volume - self.taker_sell_volume) / volume)
+    }
+}
+
+/// One price level of a stored order book
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct DepthLevel {
+    pub price: Decimal,
+    pub size: Decimal,
+}
+
+/// A market's YES order book as last fetched or streamed, for sizing
+/// entries against depth; NO is bought by selling into the YES bids
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BookSnapshot {
+    pub market_id: String,
+    pub bids: Vec<DepthLevel>,
+    pub asks: Vec<DepthLevel>,
+    pub captured_at: DateTime<Utc>,
+}
+
+impl BookSnapshot {
+    /// Shares of `position` offered at `limit` or less
+    pub fn offered_shares(&self, position: Position, limit: Decimal) -> Decimal {
+        match position {
+            Position::Yes => self.asks.iter()
+                .filter(|l| l.price <= limit)
+                .map(|l| l.size)
+                .sum(),
+            // A NO share is offered by whoever bids for YES
+            Position::No => self.bids.iter()
+                .filter(|l| Decimal::ONE - l.price <= limit)
+                .map(|l| l.size)
+                .sum(),
+        }
+    }
+
+    /// Average price of the cheapest `shares` of `position` offered at
+    /// `limit` or less; `None` when fewer are offered
+    pub fn average_offer_price(&self, position: Position, shares: Decimal, limit: Decimal) -> Option<Decimal> {
+        let mut offers: Vec<(Decimal, Decimal)> = match position {
+            Position::Yes => self.asks.iter().map(|l| (l.price, l.size)).collect(),
+            Position::No => self.bids.iter().map(|l| (Decimal::ONE - l.price, l.size)).collect(),
+        };
+        offers.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let (mut remaining, mut cost) = (shares, Decimal::ZERO);
+        for (price, size) in offers.into_iter().filter(|(price, _)| *price <= limit) {
+            let taken = size.max(Decimal::ZERO).min(remaining);
+            cost += price * taken;
+            remaining -= taken;
+        }
+        (shares > Decimal::ZERO && remaining <= Decimal::ZERO).then(|| cost / shares)
+    }
+
+    /// USDC a taker can spend buying `position` at prices no more than
+    /// `tolerance` (a fraction of price) above the best offer
+    pub fn fillable_notional(&self, position: Position, tolerance: Decimal) -> Decimal {
+        // Offers on the outcome bought, as (price, shares)
+        let offers: Vec<(Decimal, Decimal)> = match position {
+            Position::Yes => self.asks.iter().map(|l| (l.price, l.size)).collect(),
+            Position::No => self.bids.iter().map(|l| (Decimal::ONE - l.price, l.size)).collect(),
+        };
+        let Some(best) = offers.iter().map(|(price, _)| *price).min() else {
+            return Decimal::ZERO;
+        };
+
+        let limit = best * (Decimal::ONE + tolerance);
+        offers.iter()
+            .filter(|(price, size)| *price <= limit && *size > Decimal::ZERO)
+            .map(|(price, size)| price * size)
+            .sum()
+    }
+}
+
+/// How many of a sport's markets Polymarket listed on the last poll, and
+/// how many of them parsed into markets we store
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MarketListing {
+    pub sport: Sport,
+    pub listed: i64,
+    pub ingested: i64,
+    pub polled_at: DateTime<Utc>,
+}
+
+/// An upcoming active market, with whether bookmaker odds were matched to
+/// it and which strategies evaluated it in the period asked about
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CoveredMarket {
+    pub market_id: String,
+    pub sport: Sport,
+    pub matched: bool,
+    /// Names of the strategies that evaluated it
+    pub strategies: Vec<String>,
+}
+
+/// A row that could not be mapped into our types, kept with why so the
+/// loss is visible; repeats of the same item and failure are counted
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeadLetter {
+    /// Where it came from, e.g. `polymarket_listing`
+    pub source: String,
+    /// The item's id at its source, e.g. a market id
+    pub item_key: String,
+    pub reason: String,
+    /// The row as received, or as much of it as could be read
+    pub raw: serde_json::Value,
+    pub occurrences: i64,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
+impl DeadLetter {
+    /// A Polymarket listing that did not parse into a market
+    pub const POLYMARKET_LISTING: &'static str = "polymarket_listing";
+    /// A bookmaker line matched to a market that could not be converted
+    pub const BOOKMAKER_ODDS: &'static str = "bookmaker_odds";
+    /// A stored market row whose sport or market type no longer maps
+    pub const MARKET_ROW: &'static str = "market_row";
+}
+
+/// An unusually large taker trade on a tracked market
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LargeTrade {
+    pub tx_hash: String,
+    pub market_id: String,
+    /// Taker's wallet (Polymarket proxy)
+    pub wallet: String,
+    /// Outcome token traded
+    pub position: Position,
+    /// Taker side
+    pub side: FillSide,
+    pub shares: Decimal,
+    pub price: Decimal,
+    /// USDC traded
+    pub notional: Decimal,
+    /// Notional as a multiple of the market's median recent trade
+    pub size_multiple: Decimal,
+    pub traded_at: DateTime<Utc>,
+}
+
+impl LargeTrade {
+    /// Outcome the trade backs: buying YES and selling NO both back YES
+    pub fn backs(&self) -> Position {
+        match self.side {
+            FillSide::Buy => self.position,
+            FillSide::Sell => self.position.opposite(),
+        }
+    }
+}
+
+/// How a market resolved
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Resolution {
+    Yes,
+    No,
+    /// Resolved 50/50, as for a cancelled game
+    Invalid,
+}
+
+impl Resolution {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Resolution::Yes => "yes",
+            Resolution::No => "no",
+            Resolution::Invalid => "invalid",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "yes" => Some(Resolution::Yes),
+            "no" => Some(Resolution::No),
+            "invalid" => Some(Resolution::Invalid),
+            _ => None,
+        }
+    }
+}
+
+/// A market's YES price at one point in its history
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PricePoint {
+    pub market_id: String,
+    pub yes_price: Price,
+    pub observed_at: DateTime<Utc>,
+}
+
+/// Price history of markets whose events have started, up to their start
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PreEventHistory {
+    /// By market then time
+    pub points: Vec<PricePoint>,
+    pub event_times: HashMap<String, DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum Bookmaker {
     Pinnacle,
     Betfair,
@@ -215,6 +1793,93 @@ impl Bookmaker {
             Bookmaker::FanDuel => "fanduel",
         }
     }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pinnacle" => Some(Bookmaker::Pinnacle),
+            "betfair" => Some(Bookmaker::Betfair),
+            "draftkings" => Some(Bookmaker::DraftKings),
+            "fanduel" => Some(Bookmaker::FanDuel),
+            _ => None,
+        }
+    }
+}
+
+/// Direction of a capital movement
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CashFlowKind {
+    Deposit,
+    Withdrawal,
+}
+
+impl CashFlowKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            CashFlowKind::Deposit => "deposit",
+            CashFlowKind::Withdrawal => "withdrawal",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "deposit" => Some(CashFlowKind::Deposit),
+            "withdrawal" => Some(CashFlowKind::Withdrawal),
+            _ => None,
+        }
+    }
+}
+
+/// How a cash flow was recorded
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CashFlowSource {
+    /// Entered by an operator through the admin API
+    Manual,
+    /// Detected from USDC transfers to or from the trading wallet
+    OnChain,
+}
+
+impl CashFlowSource {
+    pub fn as_str(&self) -> &str {
+        match self {
+            CashFlowSource::Manual => "manual",
+            CashFlowSource::OnChain => "on_chain",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "manual" => Some(CashFlowSource::Manual),
+            "on_chain" => Some(CashFlowSource::OnChain),
+            _ => None,
+        }
+    }
+}
+
+/// Deposit or withdrawal of trading capital
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CashFlow {
+    pub flow_id: Uuid,
+    pub kind: CashFlowKind,
+    /// Always positive; direction comes from `kind`
+    pub amount: Usdc,
+    pub source: CashFlowSource,
+    pub tx_hash: Option<String>,
+    pub note: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+    /// Venue account the capital moved into or out of
+    pub venue: String,
+}
+
+impl CashFlow {
+    /// Amount added to capital (negative for withdrawals)
+    pub fn signed_amount(&self) -> Usdc {
+        match self.kind {
+            CashFlowKind::Deposit => self.amount,
+            CashFlowKind::Withdrawal => -self.amount,
+        }
+    }
 }
 
 /// Risk parameters
@@ -229,6 +1894,13 @@ pub struct RiskLimits {
     pub cooldown_period_minutes: i32,
     pub kelly_fraction: Decimal,
     pub min_edge_size: Decimal,
+    /// USDC that must be offered at a signal's executable price
+    pub min_executable_notional: Decimal,
+    /// USDC of gas and fees per day after which new entries are blocked
+    pub daily_execution_cost_budget: Decimal,
+    /// USDC bounds on a single position
+    pub min_trade_notional: Decimal,
+    pub max_trade_notional: Decimal,
 }
 
 impl Default for RiskLimits {
@@ -244,16 +1916,44 @@ impl Default for RiskLimits {
             cooldown_period_minutes: 60,
             kelly_fraction: dec!(0.5),
             min_edge_size: dec!(0.03),
+            min_executable_notional: dec!(100.0),
+            daily_execution_cost_budget: dec!(250.0),
+            min_trade_notional: dec!(25.0),
+            max_trade_notional: dec!(2500.0),
         }
     }
 }
 
+/// Capital and PnL held on a single venue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VenueState {
+    pub venue: String,
+    pub total_capital: Decimal,
+    pub available_capital: Decimal,
+    /// Cost of open positions on markets still trading
+    pub invested_capital: Decimal,
+    pub unrealized_pnl: Decimal,
+    pub realized_pnl_today: Decimal,
+    pub daily_drawdown: Decimal,
+    pub open_positions: i32,
+}
+
+/// Total capital at one portfolio snapshot
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CapitalPoint {
+    pub timestamp: DateTime<Utc>,
+    pub total_capital: Decimal,
+}
+
 /// Portfolio state
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PortfolioState {
     pub total_capital: Decimal,
     pub available_capital: Decimal,
     pub invested_capital: Decimal,
+    /// Cost of open positions on markets that have ended but not settled
+    /// (awaiting resolution or under dispute); locked, so not available
+    pub settlement_pending: Decimal,
     pub unrealized_pnl: Decimal,
     pub realized_pnl_today: Decimal,
     pub daily_drawdown: Decimal,
@@ -276,7 +1976,13 @@ impl PortfolioState {
         
         // Kelly formula: f = (bp - q) / b
         // where b = odds - 1, p = win probability, q = 1 - p
-        let odds = dec!(1.0) / win_prob;
+        // Odds come from the price we pay (fair value minus edge), not from
+        // our own probability, otherwise the edge cancels out of the formula.
+        let price = win_prob - edge;
+        if edge <= dec!(0.0) || price <= dec!(0.0) || price >= dec!(1.0) {
+            return dec!(0.0);
+        }
+        let odds = dec!(1.0) / price;
         let b = odds - dec!(1.0);
         let q = dec!(1.0) - win_prob;
         
@@ -306,3 +2012,233 @@ pub struct PerformanceMetrics {
     pub total_trades: i32,
     pub total_pnl: Decimal,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{BookSnapshot, DepthLevel, PortfolioState, Position, Price, Probability, Quote, Shares, Trade, TradeStatus, Usdc};
+    use chrono::Utc;
+    use proptest::prelude::*;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use uuid::Uuid;
+
+    /// Decimal in (0, 1) with 4 decimal places
+    fn unit_interval() -> impl Strategy<Value = Decimal> {
+        (1i64..10_000).prop_map(|bp| Decimal::new(bp, 4))
+    }
+
+    fn portfolio(capital: Decimal) -> PortfolioState {
+        PortfolioState {
+            total_capital: capital,
+            available_capital: capital,
+            invested_capital: dec!(0),
+            settlement_pending: dec!(0),
+            unrealized_pnl: dec!(0),
+            realized_pnl_today: dec!(0),
+            daily_drawdown: dec!(0),
+            max_drawdown: dec!(0),
+            open_positions: 0,
+            trades_today: 0,
+            timestamp: Utc::now(),
+        }
+    }
+
+    fn open_trade(entry_price: Decimal, quantity: Decimal, gas_cost: Decimal, fees: Decimal) -> Trade {
+        Trade {
+            trade_id: Uuid::new_v4(),
+            market_id: "0xproptest".to_string(),
+            strategy: super::Strategy::ClvArbitrage,
+            position: Position::Yes,
+            quantity,
+            entry_price,
+            exit_price: None,
+            entry_time: Utc::now(),
+            exit_time: None,
+            gas_cost: Some(gas_cost),
+            fees: Some(Usdc::new(fees)),
+            slippage: None,
+            pnl: None,
+            pnl_percent: None,
+            max_adverse_excursion: None,
+            max_favorable_excursion: None,
+            status: TradeStatus::Open,
+            tx_hash_entry: None,
+            tx_hash_exit: None,
+            venue: super::POLYMARKET_VENUE.to_string(),
+            origin: super::TradeOrigin::Bot,
+        }
+    }
+
+    #[test]
+    fn swept_offers_average_from_the_cheapest_level_up() {
+        let book = BookSnapshot {
+            market_id: "0xbook".to_string(),
+            bids: vec![DepthLevel { price: dec!(0.40), size: dec!(50) }, DepthLevel { price: dec!(0.45), size: dec!(50) }],
+            asks: vec![DepthLevel { price: dec!(0.52), size: dec!(50) }, DepthLevel { price: dec!(0.50), size: dec!(50) }],
+            captured_at: Utc::now(),
+        };
+
+        assert_eq!(book.average_offer_price(Position::Yes, dec!(50), dec!(0.52)), Some(dec!(0.50)));
+        assert_eq!(book.average_offer_price(Position::Yes, dec!(100), dec!(0.52)), Some(dec!(0.51)));
+        assert_eq!(book.average_offer_price(Position::Yes, dec!(100), dec!(0.51)), None);
+        // NO is offered by the YES bids, the highest bid first
+        assert_eq!(book.average_offer_price(Position::No, dec!(100), dec!(0.60)), Some(dec!(0.575)));
+    }
+
+    proptest! {
+        #[test]
+        fn position_size_never_exceeds_cap(
+            capital in (0i64..10_000_000).prop_map(Decimal::from),
+            win_prob in unit_interval(),
+            edge in (-5_000i64..5_000).prop_map(|bp| Decimal::new(bp, 4)),
+            kelly_fraction in unit_interval(),
+            max_pct in (1i64..1_000).prop_map(|v| Decimal::new(v, 1)),
+        ) {
+            let size = portfolio(capital)
+                .calculate_position_size(edge, win_prob, kelly_fraction, max_pct);
+
+            prop_assert!(size >= dec!(0));
+            prop_assert!(size <= capital * max_pct / dec!(100));
+        }
+
+        #[test]
+        fn kelly_stake_is_zero_without_edge(
+            win_prob in unit_interval(),
+            edge in (-5_000i64..=0).prop_map(|bp| Decimal::new(bp, 4)),
+            kelly_fraction in unit_interval(),
+        ) {
+            let size = portfolio(dec!(50000))
+                .calculate_position_size(edge, win_prob, kelly_fraction, dec!(2.0));
+
+            prop_assert_eq!(size, dec!(0));
+        }
+
+        #[test]
+        fn kelly_stake_is_positive_with_edge(
+            price in unit_interval(),
+            edge_bp in 1i64..5_000,
+            kelly_fraction in unit_interval(),
+        ) {
+            let edge = Decimal::new(edge_bp, 4);
+            let win_prob = price + edge;
+            prop_assume!(win_prob < dec!(1));
+
+            let size = portfolio(dec!(50000))
+                .calculate_position_size(edge, win_prob, kelly_fraction, dec!(2.0));
+
+            prop_assert!(size > dec!(0));
+        }
+
+        #[test]
+        fn realized_pnl_accounts_for_every_cost(
+            entry in unit_interval(),
+            exit in unit_interval(),
+            quantity in (1i64..1_000_000).prop_map(Decimal::from),
+            gas in (0i64..10_000).prop_map(|c| Decimal::new(c, 2)),
+            fees in (0i64..10_000).prop_map(|c| Decimal::new(c, 2)),
+            exit_costs in (0i64..10_000).prop_map(|c| Decimal::new(c, 2)),
+        ) {
+            let trade = open_trade(entry, quantity, gas, fees);
+            let gross = trade.unrealized_pnl(exit);
+
+            prop_assert_eq!(trade.realized_pnl(exit, Usdc::new(exit_costs)).value() + gas + fees + exit_costs, gross);
+            prop_assert_eq!(trade.position_size_usd() + gross, exit * quantity);
+        }
+
+        #[test]
+        fn probability_rejects_values_outside_unit_interval(
+            value in (-20_000i64..20_000).prop_map(|bp| Decimal::new(bp, 4)),
+        ) {
+            let in_range = value >= dec!(0) && value <= dec!(1);
+
+            prop_assert_eq!(Probability::new(value).is_ok(), in_range);
+            prop_assert_eq!(Price::new(value).is_ok(), in_range);
+            prop_assert_eq!(
+                serde_json::from_value::<Probability>(serde_json::json!(value)).is_ok(),
+                in_range
+            );
+        }
+
+        #[test]
+        fn probability_odds_round_trip(p in unit_interval()) {
+            let probability = Probability::new(p).unwrap();
+            let odds = probability.to_decimal_odds().unwrap();
+            let back = Probability::from_decimal_odds(odds).unwrap();
+
+            prop_assert!((back.value() - p).abs() < dec!(0.0000001));
+            prop_assert_eq!(probability.complement().complement(), probability);
+        }
+
+        #[test]
+        fn price_moves_saturate_at_bounds(
+            price in unit_interval(),
+            delta in (-20_000i64..20_000).prop_map(|bp| Decimal::new(bp, 4)),
+        ) {
+            let moved = Price::new(price).unwrap().saturating_add(delta);
+
+            prop_assert!(moved >= Price::ZERO && moved <= Price::ONE);
+        }
+
+        #[test]
+        fn mirrored_quote_keeps_spread_and_mid(a in unit_interval(), b in unit_interval()) {
+            let quote = Quote::from_book(Some(a.min(b)), Some(a.max(b)), Price::ZERO);
+            let mirrored = quote.complement();
+
+            prop_assert!(mirrored.bid <= mirrored.ask);
+            prop_assert_eq!(mirrored.spread(), quote.spread());
+            prop_assert_eq!(mirrored.mid(), quote.mid().complement());
+            prop_assert_eq!(mirrored.complement(), quote);
+        }
+
+        #[test]
+        fn usdc_base_units_round_trip(units in 0u64..u64::MAX, extra in 1i64..10_000) {
+            let amount = Usdc::from_base_units(units as u128).unwrap();
+
+            prop_assert!(amount.is_exact());
+            prop_assert_eq!(amount.to_base_units().unwrap(), units as u128);
+            // Digits past the sixth decimal are truncated, never rounded up
+            let finer = Usdc::new(amount.value() + Decimal::new(extra, 10));
+            prop_assert!(!finer.is_exact());
+            prop_assert_eq!(finer.to_base_units().unwrap(), units as u128);
+            prop_assert!(Usdc::new(-amount.value() - dec!(0.000001)).to_base_units().is_err());
+        }
+
+        #[test]
+        fn share_base_units_round_trip(units in 0u64..u64::MAX, extra in 1i64..10_000) {
+            let shares = Shares::from_base_units(units as u128).unwrap();
+
+            prop_assert_eq!(shares.to_base_units().unwrap(), units as u128);
+            let finer = Shares::new(shares.value() + Decimal::new(extra, 10));
+            prop_assert_eq!(finer.to_base_units().unwrap(), units as u128);
+            prop_assert!(Shares::new(-shares.value() - dec!(0.000001)).to_base_units().is_err());
+        }
+
+        #[test]
+        fn fillable_notional_grows_with_tolerance_up_to_the_whole_side(
+            levels in proptest::collection::vec((unit_interval(), 1i64..10_000), 1..20),
+            tight in 0i64..100,
+            wide in 100i64..10_000,
+        ) {
+            let levels: Vec<DepthLevel> = levels.into_iter()
+                .map(|(price, size)| DepthLevel { price, size: Decimal::from(size) })
+                .collect();
+            let book = BookSnapshot {
+                market_id: "0xmarket".to_string(),
+                bids: levels.clone(),
+                asks: levels.clone(),
+                captured_at: Utc::now(),
+            };
+
+            for position in [Position::Yes, Position::No] {
+                let tight = book.fillable_notional(position, Decimal::new(tight, 4));
+                let wide = book.fillable_notional(position, Decimal::new(wide, 4));
+                let whole = book.fillable_notional(position, Decimal::from(10_000));
+                // The best offer alone is always fillable
+                prop_assert!(tight > Decimal::ZERO);
+                prop_assert!(tight <= wide && wide <= whole);
+            }
+            let asks: Decimal = levels.iter().map(|l| l.price * l.size).sum();
+            prop_assert_eq!(book.fillable_notional(Position::Yes, Decimal::from(10_000)), asks);
+        }
+    }
+}